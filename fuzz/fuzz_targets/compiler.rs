@@ -0,0 +1,21 @@
+#![no_main]
+/// fuzz/fuzz_targets/compiler.rs
+///
+/// Fuzz target for Compiler::run
+///
+/// Runs arbitrary UTF-8 through the whole pipeline against the test database (the "_test"
+/// school skips the school and term filters). Run it from the repository root so
+/// classy/test.db resolves
+///
+use classql::dsl::compiler::Compiler;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    let mut compiler = Compiler::new();
+    compiler.set_school_id(Some("_test".to_string()));
+    let _ = compiler.run(input);
+});
@@ -0,0 +1,35 @@
+#![no_main]
+/// fuzz/fuzz_targets/parser.rs
+///
+/// Fuzz target for the front end of the compiler
+///
+/// Feeds arbitrary UTF-8 through the lexer, tab completion, parser, semantic analysis, and
+/// code generation without touching a database. Errors are expected; panics and stack
+/// overflows are what the fuzzer reports
+///
+use classql::dsl::codegen::generate_sql;
+use classql::dsl::lexer::Lexer;
+use classql::dsl::parser::Parser;
+use classql::dsl::semantic::semantic_analysis;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    let mut lexer = Lexer::new(input.to_string());
+    let Ok(tokens) = lexer.analyze() else {
+        return;
+    };
+
+    let mut parser = Parser::new(input.to_string());
+    let _ = parser.get_completion_suggestions(&tokens);
+
+    let Ok(ast) = parser.parse(&tokens) else {
+        return;
+    };
+    if semantic_analysis(&ast).is_ok() {
+        let _ = generate_sql(&ast);
+    }
+});
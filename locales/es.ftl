@@ -0,0 +1,161 @@
+# locales/es.ftl
+#
+# Spanish translations. Message ids match the ids passed to i18n::tr; anything missing
+# here falls back to the English text in the code.
+
+## compiler errors (see src/tui/error_catalog.rs)
+
+error-unclosed-parenthesis = Falta el paréntesis de cierre ')'
+error-missing-token = Falta: { $expected }
+error-empty-query = Escribe una consulta para buscar
+error-expected-after = Después de '{ $after }', agrega: { $expected }
+error-expected-after-one-of = Después de '{ $after }', agrega uno de: { $expected }
+error-start-with = Empieza con: { $expected }
+error-start-with-one-of = Empieza con uno de: { $expected }
+error-invalid-token = '{ $token }' no es válido aquí ({ $context })
+error-invalid-token-try = '{ $token }' no es válido aquí. Prueba: { $suggestions }
+error-invalid-value = '{ $token }' no es válido aquí ({ $context })
+error-invalid-value-try = '{ $token }' no es válido aquí. Prueba: { $suggestions }
+error-unclosed-string = Texto sin cerrar desde el byte { $start }; agrega las comillas de cierre "
+error-unrecognized-character = Carácter no reconocido: { $characters }. Si es parte de un valor, ponlo entre comillas (p. ej., "{ $example }")
+error-unrecognized-characters = Caracteres no reconocidos: { $characters }. Si son parte de un valor, ponlos entre comillas (p. ej., "{ $example }")
+error-codegen-empty-ast = No se puede generar SQL a partir de un AST vacío
+error-codegen-unsupported-node = Tipo de nodo no admitido en la generación de código: { $node_type }
+error-codegen-invalid-structure = Estructura de AST no válida: { $message }
+error-codegen-unsafe-value = No se puede buscar "{ $value }": los valores no pueden contener caracteres de control
+error-database-query = Error en la consulta a la base de datos: { $error }
+error-nesting-too-deep = Demasiados paréntesis o 'not' anidados (como máximo { $max } niveles)
+error-unknown-value = Ninguna clase tiene { $field } '{ $value }'
+error-unknown-value-try = Ninguna clase tiene { $field } '{ $value }'. ¿Quisiste decir: { $suggestions }?
+error-value-out-of-range = { $value } está fuera de rango para { $field } (debe estar entre { $min } y { $max })
+error-comparison-never-matches = '{ $comparison }' nunca puede cumplirse, { $field } siempre está entre { $min } y { $max }
+error-invalid-pattern = '{ $pattern }' no es un patrón válido: { $reason }
+diagnostic = error[{ $code }]
+diagnostic-at-column = error[{ $code }] en la columna { $column }
+lexer-character-at = '{ $character }' en { $range }
+lexer-byte = el byte { $start }
+lexer-bytes = los bytes { $start }-{ $end }
+
+## toast titles
+
+toast-lexer-error = Error léxico
+toast-parser-error = Error de sintaxis
+toast-semantic-error = Error semántico
+toast-info = Información
+toast-success = Listo
+toast-warning = Advertencia
+toast-notice = Aviso
+
+## help bar
+
+help-main-menu = ↑↓ Navegar | Enter: Elegir | 1-5: Clase reciente | p: Perfil | Esc: Salir
+help-settings = Esc: Volver | Ctrl+C: Salir
+help-detail-view = Esc o Enter: Cerrar detalles | C: Carrito | W: Vigilar | P: Profesor | S: Secciones | M: Más del profesor
+help-results-browse = ←↑↓→ Navegar | Enter: Detalles | /: Filtrar | Alt+C: Comparar | Alt+D: Diferencias | Alt+U: Duplicados | Alt+X: Plan | Alt+A: AST | Esc: Volver | Alt+G: Guía
+help-query-input = Enter: Buscar | Tab: Completar | ↓: Resultados | Alt+F: Favorita | Alt+D: Dif. | Alt+X: Plan | Alt+A: AST | Esc: Volver | Alt+G: Guía
+help-query-guide = ↑↓ Desplazar | RePág/AvPág | Inicio/Fin | Alt+G o Esc: Cerrar
+help-help = ↑↓ Desplazar | RePág/AvPág | Inicio/Fin | Esc: Cerrar
+help-schedule-selection = ↑↓ Navegar | Espacio: Marcar | Tab: Detalles | Alt+C: Comparar | g: Una por curso | Enter: Seguir | d: Borrar | Esc: Volver
+help-schedule-view = ←→↑↓ Mover | Tab: Sig. | Enter: Ver | RePág/AvPág: Más | v: Día | x: Finales | s: Guardar | o: Amigo | f: Libre | y: CRN | Esc: Atrás
+help-my-schedules = ↑↓ Navegar | Enter: Ver | g: Regenerar | p: Importar | r: Renombrar | d: Borrar | Esc: Volver
+help-save-name = Enter: Guardar | Esc: Cancelar
+help-overlay-input = Enter: Superponer | Esc: Cancelar
+help-paste-import = Pega tu horario del registro | Ctrl+A: Añadir al carrito | Ctrl+U: Borrar | Esc: Cancelar
+help-free-time = Escribe una consulta, p. ej. 90 min TTH 9am-5pm | Ctrl+U: Borrar | Esc: Volver
+help-compare = Esc o Enter: Cerrar comparación
+help-database-stats = ↑↓ Desplazar | RePág/AvPág | Inicio/Fin | Esc: Volver a ajustes
+help-query-explain = ↑↓ Desplazar | RePág/AvPág | Inicio/Fin | i: Crear índices sugeridos | Esc: Volver a la búsqueda
+help-ast-inspector = ↑↓ Navegar | →: Expandir | ←: Contraer | e/c: Expandir/Contraer todo | Tab: AST/Tokens | Esc: Volver a la búsqueda
+help-catalog = ↑↓ Navegar | →: Expandir | ←: Contraer | Enter: Expandir/Detalles | a: Añadir al carrito | Esc: Volver
+help-professors = Escriba para buscar | ↑↓ Navegar | Enter: Secciones | Ctrl+U: Borrar | Esc: Volver
+help-professor-sections = ↑↓ Navegar | Enter: Detalles | a: Añadir al carrito | ←/Esc: Volver a profesores
+
+## status bar
+
+status-query-timing = { $results } resultados | compilada en { $compile } | ejecutada en { $execute }
+status-index-advice = { $count } índices sugeridos (Alt+X)
+status-profile = Perfil: { $name } (+{ $count } condiciones)
+status-result-filter = Filtro: { $filter } ({ $results } de { $total })
+status-result-diff = frente a la última: { $added } nuevas, { $removed } ya no están, { $seats } cambios de plazas
+status-duplicates = { $count } secciones duplicadas agrupadas
+status-truncated = resultados truncados, refina tu consulta
+
+## query guide
+
+guide-title = Guía de consultas
+guide-basic-structure = ESTRUCTURA BÁSICA
+guide-search-fields = CAMPOS DE BÚSQUEDA
+guide-professor-queries = CONSULTAS DE PROFESORES
+guide-course-queries = CONSULTAS DE CURSOS
+guide-time-queries = CONSULTAS DE HORARIO
+guide-day-queries = CONSULTAS DE DÍAS
+guide-enrollment-queries = CONSULTAS DE INSCRIPCIÓN
+guide-other-queries = OTRAS CONSULTAS
+guide-conditions = CONDICIONES Y OPERADORES
+guide-complex-examples = EJEMPLOS COMPLEJOS
+guide-project-license = PROYECTO Y LICENCIA
+guide-examples = Ejemplos:
+
+## shared labels
+
+in-cart = ✓ en el carrito
+count-course = { $count } curso
+count-courses = { $count } cursos
+count-section = { $count } sección
+count-sections = { $count } secciones
+
+## course catalog
+
+catalog-title = Catálogo de cursos
+catalog-empty = No hay secciones en este periodo. Sincroniza los datos de clases desde Ajustes.
+catalog-course-summary = { $credits } cr · { $sections }, { $open } con plazas
+
+## professors
+
+professors-title = Profesores
+professors-search = Buscar:
+professors-count = { $shown } de { $total } profesores
+professors-empty = No hay profesores en este periodo. Sincroniza los datos de clases desde Ajustes.
+professors-no-match = Ningún profesor coincide con tu búsqueda.
+professors-no-email = sin correo
+professors-summary = { $department } · { $email } · { $sections } este periodo
+
+## compare
+
+compare-title = Comparar secciones
+compare-hint = Las diferencias están resaltadas
+compare-section = Sección
+compare-course-title = Título
+compare-professor = Profesor
+compare-time = Horario
+compare-type = Tipo
+compare-seats-label = Plazas
+compare-campus = Campus
+compare-method = Modalidad
+compare-credits = Créditos
+compare-seats = { $enrolled } / { $max } ({ $open } libres)
+compare-unknown = Desconocido
+
+## query plan
+
+explain-title = Plan de consulta
+plan-index-advice = Índices sugeridos
+plan-no-advice = (ninguno, no se recorre entera ninguna tabla filtrada)
+plan-full-scan = { $table }: recorrido completo filtrado por { $expression }
+plan-query-plan = Plan de consulta
+
+## database stats
+
+stats-title = Estadísticas de la base de datos
+stats-file-size = Tamaño del archivo: { $size }
+stats-tables = Tablas
+stats-sections-per-term = Secciones por periodo
+stats-no-terms = (sin periodos)
+stats-archived = (archivado)
+stats-indexes = Índices
+stats-no-indexes = (sin índices)
+stats-used-by-search = usado por la búsqueda
+stats-unused-by-search = sin uso en la búsqueda
+stats-search-plan = Plan de búsqueda
+stats-recent-syncs = Sincronizaciones recientes
+stats-never-synced = (nunca sincronizado)
@@ -0,0 +1,273 @@
+/*
+    src/data/app_store.rs
+
+    Module for the app's own state
+    Everything the app keeps between launches (the watch list, session, and profiles) is
+    stored in one SQLite database as JSON records, read and written through typed tables so
+    callers never write SQL. The schema is created and upgraded by the migrations in
+    src/data_stores/app/migrations, applied in order and tracked with SQLite's user_version
+*/
+
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use rusqlite::{params, OptionalExtension};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::data::pool::{open_connection, write_transaction};
+
+/// Schema migrations, applied in order; the database's user_version is how many have run
+const MIGRATIONS: &[&str] = &[include_str!("../data_stores/app/migrations/001.up.sql")];
+
+/// Key of the record in a table holding a single value, like the session
+pub const DEFAULT_KEY: &str = "default";
+
+/// A table of records of one type
+///
+/// Tables are declared as constants next to the code that uses them, e.g.
+/// `const WATCH_LIST: Table<WatchList> = Table::new("watch_list");`
+///
+/// Fields:
+/// --- ---
+/// name -> Name the table's records are stored under
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for Table
+/// Clone -> Clone trait for Table
+/// Copy -> Copy trait for Table
+/// --- ---
+pub struct Table<T> {
+    pub name: &'static str,
+    record: PhantomData<fn() -> T>,
+}
+
+impl<T> Table<T> {
+    /// Declare a table
+    ///
+    /// Parameters:
+    /// --- ---
+    /// name -> Name the table's records are stored under, unique among tables
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Table<T> -> The table
+    /// --- ---
+    pub const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            record: PhantomData,
+        }
+    }
+}
+
+impl<T> Clone for Table<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Table<T> {}
+
+impl<T> std::fmt::Debug for Table<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Table").field("name", &self.name).finish()
+    }
+}
+
+/// The app's state database
+///
+/// Fields:
+/// --- ---
+/// db_path -> Path to the database file
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for AppStore
+/// Clone -> Clone trait for AppStore
+/// --- ---
+#[derive(Debug, Clone)]
+pub struct AppStore {
+    db_path: PathBuf,
+}
+
+impl AppStore {
+    /// Open the database, creating it and running any migrations it hasn't had yet
+    ///
+    /// Parameters:
+    /// --- ---
+    /// db_path -> Path to the database file
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Result<AppStore, String> -> The store, or error message
+    /// --- ---
+    pub fn open(db_path: &Path) -> Result<Self, String> {
+        write_transaction(db_path, &mut |_| {}, |tx| {
+            let version: usize = tx
+                .query_row("PRAGMA user_version", [], |row| row.get(0))
+                .map_err(|e| format!("Failed to read schema version: {}", e))?;
+            if version > MIGRATIONS.len() {
+                return Err(format!(
+                    "{} was made by a newer version of ClassQL (schema version {})",
+                    db_path.display(),
+                    version
+                ));
+            }
+            for migration in &MIGRATIONS[version..] {
+                tx.execute_batch(migration)
+                    .map_err(|e| format!("Failed to migrate app database: {}", e))?;
+            }
+            tx.execute_batch(&format!("PRAGMA user_version = {}", MIGRATIONS.len()))
+                .map_err(|e| format!("Failed to set schema version: {}", e))
+        })?;
+        Ok(Self {
+            db_path: db_path.to_path_buf(),
+        })
+    }
+
+    /// Get the schema version, the number of migrations applied
+    ///
+    /// Returns:
+    /// --- ---
+    /// Result<usize, String> -> The version, or error message
+    /// --- ---
+    pub fn schema_version(&self) -> Result<usize, String> {
+        open_connection(&self.db_path)?
+            .query_row("PRAGMA user_version", [], |row| row.get(0))
+            .map_err(|e| format!("Failed to read schema version: {}", e))
+    }
+
+    /// Read a record
+    ///
+    /// Parameters:
+    /// --- ---
+    /// table -> The table to read from
+    /// key -> The record's key
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Result<Option<T>, String> -> The record, None if there is none with that key
+    /// --- ---
+    pub fn get<T: DeserializeOwned>(
+        &self,
+        table: Table<T>,
+        key: &str,
+    ) -> Result<Option<T>, String> {
+        let value: Option<String> = open_connection(&self.db_path)?
+            .query_row(
+                "SELECT value FROM records WHERE table_name = ?1 AND key = ?2",
+                params![table.name, key],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|e| format!("Failed to read {}: {}", table.name, e))?;
+        value.map(|value| parse_record(table, &value)).transpose()
+    }
+
+    /// Write a record, replacing any with the same key
+    ///
+    /// Parameters:
+    /// --- ---
+    /// table -> The table to write to
+    /// key -> The record's key
+    /// value -> The record
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Result<(), String> -> Ok on success, error message on failure
+    /// --- ---
+    pub fn put<T: Serialize>(&self, table: Table<T>, key: &str, value: &T) -> Result<(), String> {
+        let value = serde_json::to_string(value)
+            .map_err(|e| format!("Failed to serialize {}: {}", table.name, e))?;
+        let updated_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+        write_transaction(&self.db_path, &mut |_| {}, |tx| {
+            tx.execute(
+                "INSERT INTO records (table_name, key, value, updated_at) VALUES (?1, ?2, ?3, ?4) \
+                 ON CONFLICT (table_name, key) DO UPDATE SET \
+                    value = excluded.value, updated_at = excluded.updated_at",
+                params![table.name, key, value, updated_at],
+            )
+            .map(|_| ())
+            .map_err(|e| format!("Failed to write {}: {}", table.name, e))
+        })
+    }
+
+    /// Delete a record
+    ///
+    /// Parameters:
+    /// --- ---
+    /// table -> The table to delete from
+    /// key -> The record's key
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Result<bool, String> -> true if there was a record to delete
+    /// --- ---
+    pub fn delete<T>(&self, table: Table<T>, key: &str) -> Result<bool, String> {
+        write_transaction(&self.db_path, &mut |_| {}, |tx| {
+            tx.execute(
+                "DELETE FROM records WHERE table_name = ?1 AND key = ?2",
+                params![table.name, key],
+            )
+            .map(|deleted| deleted > 0)
+            .map_err(|e| format!("Failed to delete from {}: {}", table.name, e))
+        })
+    }
+
+    /// Read every record in a table
+    ///
+    /// Parameters:
+    /// --- ---
+    /// table -> The table to read
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Result<Vec<(String, T)>, String> -> The records with their keys, in key order
+    /// --- ---
+    pub fn list<T: DeserializeOwned>(&self, table: Table<T>) -> Result<Vec<(String, T)>, String> {
+        let conn = open_connection(&self.db_path)?;
+        let mut stmt = conn
+            .prepare("SELECT key, value FROM records WHERE table_name = ?1 ORDER BY key")
+            .map_err(|e| format!("Failed to read {}: {}", table.name, e))?;
+        let rows = stmt
+            .query_map(params![table.name], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })
+            .map_err(|e| format!("Failed to read {}: {}", table.name, e))?;
+        rows.map(|row| {
+            let (key, value) = row.map_err(|e| format!("Failed to read {}: {}", table.name, e))?;
+            Ok((key, parse_record(table, &value)?))
+        })
+        .collect()
+    }
+}
+
+/// Parse a record's stored JSON
+///
+/// Parameters:
+/// --- ---
+/// table -> The table the record is from, named in errors
+/// value -> The stored JSON
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<T, String> -> The record, or error message
+/// --- ---
+fn parse_record<T: DeserializeOwned>(table: Table<T>, value: &str) -> Result<T, String> {
+    serde_json::from_str(value).map_err(|e| format!("Failed to parse {}: {}", table.name, e))
+}
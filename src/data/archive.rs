@@ -0,0 +1,310 @@
+/*
+    src/data/archive.rs
+
+    Module for archiving past terms
+    Archived terms are hidden from term selection and default queries, and their
+    sections can be purged to free space without breaking saved schedules
+*/
+
+use std::fs;
+use std::path::Path;
+
+use rusqlite::{Connection, TransactionBehavior};
+
+use crate::data::pool::{
+    checkpoint, open_read_connection, open_write_connection, write_transaction,
+};
+
+/// A section referenced by a saved schedule, which purging must keep
+///
+/// Fields:
+/// --- ---
+/// school_id -> School the schedule belongs to
+/// term_id -> Term the schedule belongs to (None matches any term)
+/// class_id -> Class unique ID (e.g., "CMPT:101-001")
+/// --- ---
+#[derive(Debug, Clone)]
+pub struct KeptSection {
+    pub school_id: String,
+    pub term_id: Option<String>,
+    pub class_id: String,
+}
+
+/// Result of purging archived terms
+///
+/// Fields:
+/// --- ---
+/// sections_removed -> Number of sections deleted
+/// meetings_removed -> Number of meeting times deleted
+/// sections_kept -> Number of archived sections kept for saved schedules
+/// bytes_reclaimed -> Reduction in database file size
+/// --- ---
+#[derive(Debug, Clone, Default)]
+pub struct PurgeReport {
+    pub sections_removed: usize,
+    pub meetings_removed: usize,
+    pub sections_kept: usize,
+    pub bytes_reclaimed: u64,
+}
+
+/// Create the archived terms table if it doesn't exist yet
+///
+/// Parameters:
+/// --- ---
+/// conn -> Open database connection
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<(), String> -> Ok on success or error message
+/// --- ---
+fn ensure_archive_table(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS archived_terms (
+            term_collection_id TEXT,
+            school_id TEXT,
+            archived_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            PRIMARY KEY (term_collection_id, school_id)
+        )",
+    )
+    .map_err(|e| format!("Failed to create archived terms table: {}", e))
+}
+
+/// Archive a term
+///
+/// Parameters:
+/// --- ---
+/// db_path -> Path to the SQLite database file
+/// school_id -> The school ID
+/// term_id -> The term collection ID to archive
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<(), String> -> Ok on success or error message
+/// --- ---
+pub fn archive_term(db_path: &Path, school_id: &str, term_id: &str) -> Result<(), String> {
+    write_transaction(db_path, &mut |_| {}, |tx| {
+        ensure_archive_table(tx)?;
+
+        let exists: bool = tx
+            .query_row(
+                "SELECT EXISTS(SELECT 1 FROM term_collections WHERE school_id = ? AND id = ?)",
+                [school_id, term_id],
+                |row| row.get(0),
+            )
+            .map_err(|e| format!("Query execution error: {}", e))?;
+        if !exists {
+            return Err(format!(
+                "Term '{}' not found for school '{}'",
+                term_id, school_id
+            ));
+        }
+
+        tx.execute(
+            "INSERT OR IGNORE INTO archived_terms (term_collection_id, school_id) VALUES (?, ?)",
+            [term_id, school_id],
+        )
+        .map_err(|e| format!("Failed to archive term: {}", e))?;
+
+        Ok(())
+    })
+}
+
+/// Restore an archived term
+///
+/// Parameters:
+/// --- ---
+/// db_path -> Path to the SQLite database file
+/// school_id -> The school ID
+/// term_id -> The term collection ID to restore
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<bool, String> -> true if the term was archived, or error message
+/// --- ---
+pub fn unarchive_term(db_path: &Path, school_id: &str, term_id: &str) -> Result<bool, String> {
+    write_transaction(db_path, &mut |_| {}, |tx| {
+        ensure_archive_table(tx)?;
+
+        let removed = tx
+            .execute(
+                "DELETE FROM archived_terms WHERE term_collection_id = ? AND school_id = ?",
+                [term_id, school_id],
+            )
+            .map_err(|e| format!("Failed to restore term: {}", e))?;
+
+        Ok(removed > 0)
+    })
+}
+
+/// Fetch the IDs of archived terms
+///
+/// Parameters:
+/// --- ---
+/// db_path -> Path to the SQLite database file
+/// school_id -> Only return terms for this school (None for all schools)
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<Vec<String>, String> -> Archived term collection IDs (empty if nothing was ever archived)
+/// --- ---
+pub fn fetch_archived_term_ids(
+    db_path: &Path,
+    school_id: Option<&str>,
+) -> Result<Vec<String>, String> {
+    let conn = open_read_connection(db_path)?;
+
+    // avoid writing to databases (like the test database) that never had a term archived
+    let has_table: bool = conn
+        .query_row(
+            "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'archived_terms')",
+            [],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Query execution error: {}", e))?;
+    if !has_table {
+        return Ok(Vec::new());
+    }
+
+    let mut stmt = conn
+        .prepare("SELECT term_collection_id FROM archived_terms WHERE ?1 IS NULL OR school_id = ?1")
+        .map_err(|e| format!("SQL preparation error: {}", e))?;
+
+    let term_ids = stmt
+        .query_map([school_id], |row| row.get(0))
+        .map_err(|e| format!("Query execution error: {}", e))?
+        .filter_map(Result::ok)
+        .collect();
+
+    Ok(term_ids)
+}
+
+/// Delete the sections of archived terms and compact the database
+///
+/// Sections referenced by saved schedules are kept so those schedules still load
+///
+/// Parameters:
+/// --- ---
+/// db_path -> Path to the SQLite database file
+/// keep -> Sections referenced by saved schedules
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<PurgeReport, String> -> What was removed, or error message
+/// --- ---
+pub fn purge_archived_terms(db_path: &Path, keep: &[KeptSection]) -> Result<PurgeReport, String> {
+    let size_before = fs::metadata(db_path).map(|m| m.len()).unwrap_or(0);
+
+    let mut conn = open_write_connection(db_path)?;
+    ensure_archive_table(&conn)?;
+
+    let tx = conn
+        .transaction_with_behavior(TransactionBehavior::Immediate)
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    tx.execute_batch(
+        "CREATE TEMP TABLE purge_keep (
+            school_id TEXT,
+            term_collection_id TEXT,
+            subject_code TEXT,
+            course_number TEXT,
+            sequence TEXT
+        )",
+    )
+    .map_err(|e| format!("Failed to prepare purge: {}", e))?;
+
+    {
+        let mut insert = tx
+            .prepare("INSERT INTO purge_keep VALUES (?, ?, ?, ?, ?)")
+            .map_err(|e| format!("SQL preparation error: {}", e))?;
+        for section in keep {
+            // unique_id format is "SUBJECT:COURSE-SECTION"
+            let Some((subject, rest)) = section.class_id.split_once(':') else {
+                continue;
+            };
+            let Some((course, sequence)) = rest.split_once('-') else {
+                continue;
+            };
+            insert
+                .execute(rusqlite::params![
+                    section.school_id,
+                    section.term_id,
+                    subject,
+                    course,
+                    sequence
+                ])
+                .map_err(|e| format!("Failed to prepare purge: {}", e))?;
+        }
+    }
+
+    // a NULL kept term matches the section in any term
+    let archived = "EXISTS (SELECT 1 FROM archived_terms a \
+            WHERE a.term_collection_id = {t}.term_collection_id AND a.school_id = {t}.school_id)";
+    let kept = "EXISTS (SELECT 1 FROM purge_keep k \
+            WHERE k.school_id = {t}.school_id \
+            AND (k.term_collection_id IS NULL OR k.term_collection_id = {t}.term_collection_id) \
+            AND k.subject_code = {t}.subject_code AND k.course_number = {t}.course_number \
+            AND k.sequence = {t}.{seq})";
+    let condition = |table: &str, sequence_column: &str| {
+        format!(
+            "{} AND NOT {}",
+            archived.replace("{t}", table),
+            kept.replace("{t}", table).replace("{seq}", sequence_column)
+        )
+    };
+
+    let meetings_removed = tx
+        .execute(
+            &format!(
+                "DELETE FROM meeting_times WHERE {}",
+                condition("meeting_times", "section_sequence")
+            ),
+            [],
+        )
+        .map_err(|e| format!("Failed to purge meeting times: {}", e))?;
+
+    let sections_kept: usize = tx
+        .query_row(
+            &format!(
+                "SELECT COUNT(*) FROM sections WHERE {}",
+                archived.replace("{t}", "sections")
+            ),
+            [],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Query execution error: {}", e))?;
+
+    let sections_removed = tx
+        .execute(
+            &format!(
+                "DELETE FROM sections WHERE {}",
+                condition("sections", "sequence")
+            ),
+            [],
+        )
+        .map_err(|e| format!("Failed to purge sections: {}", e))?;
+
+    tx.execute_batch("DROP TABLE purge_keep")
+        .map_err(|e| format!("Failed to finish purge: {}", e))?;
+    tx.commit()
+        .map_err(|e| format!("Failed to commit purge: {}", e))?;
+
+    // deleted rows only free pages inside the file, vacuum gives the space back
+    conn.execute_batch("VACUUM")
+        .map_err(|e| format!("Failed to compact database: {}", e))?;
+    drop(conn);
+    checkpoint(db_path)?;
+
+    let size_after = fs::metadata(db_path).map(|m| m.len()).unwrap_or(0);
+
+    Ok(PurgeReport {
+        sections_removed,
+        meetings_removed,
+        sections_kept: sections_kept - sections_removed,
+        bytes_reclaimed: size_before.saturating_sub(size_after),
+    })
+}
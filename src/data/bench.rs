@@ -0,0 +1,366 @@
+/*
+    src/data/bench.rs
+
+    Module for benchmarking the compiler and searches
+    Generates a synthetic class database of a chosen size and times each compiler
+    stage and the full search pipeline against it, so performance regressions show
+    up as numbers instead of a slower-feeling TUI
+*/
+
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use serde_json::{json, Value};
+
+use crate::data::sample::{generate_catalog, SampleConfig};
+use crate::data::sql::execute_query;
+use crate::dsl::codegen::generate_sql;
+use crate::dsl::lexer::Lexer;
+use crate::dsl::parser::Parser;
+use crate::dsl::semantic::semantic_analysis;
+
+/// School ID used for the synthetic data
+pub const BENCH_SCHOOL_ID: &str = "bench";
+
+/// Queries timed by the benchmark, from simple filters to multi-clause searches
+pub const BENCH_QUERIES: &[&str] = &[
+    "subject is CS",
+    "prof contains smith",
+    "title contains data and enrollment < 30",
+    "credit hours >= 3 and monday",
+    "start > 10:00am and end < 3:00pm",
+    "(subject is MATH or subject is PHYS) and credit hours < 4",
+    "subject is CS and monday and wednesday and start > 9:00am and prof contains munoz",
+];
+
+/// Benchmark settings
+///
+/// Fields:
+/// --- ---
+/// sections -> Number of sections in the synthetic database
+/// iterations -> How many times each query is timed
+/// seed -> Seed for the synthetic data, so runs with the same settings see the same data
+/// --- ---
+#[derive(Debug, Clone)]
+pub struct BenchConfig {
+    pub sections: usize,
+    pub iterations: usize,
+    pub seed: u64,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        BenchConfig {
+            sections: 5000,
+            iterations: 20,
+            seed: 42,
+        }
+    }
+}
+
+/// Timing summary for one stage
+///
+/// Fields:
+/// --- ---
+/// stage -> Stage name ("lex", "parse", "codegen", "query", or "end_to_end")
+/// samples -> Number of timed runs
+/// min -> Fastest run
+/// median -> Median run
+/// mean -> Average run
+/// p95 -> 95th percentile run
+/// --- ---
+#[derive(Debug, Clone, PartialEq)]
+pub struct StageTiming {
+    pub stage: String,
+    pub samples: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+}
+
+impl StageTiming {
+    /// Summarize a stage's timed runs
+    ///
+    /// Parameters:
+    /// --- ---
+    /// stage -> Stage name
+    /// samples -> Duration of each run, in any order
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// StageTiming -> The summary (all zero if there are no samples)
+    /// --- ---
+    pub fn from_samples(stage: &str, samples: &[Duration]) -> Self {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        // nearest-rank percentile, so p95 of 20 samples is the 19th
+        let percentile = |percent: usize| {
+            let rank = (sorted.len() * percent).div_ceil(100).max(1);
+            sorted.get(rank - 1).copied().unwrap_or_default()
+        };
+        let mean = if sorted.is_empty() {
+            Duration::ZERO
+        } else {
+            sorted.iter().sum::<Duration>() / sorted.len() as u32
+        };
+
+        StageTiming {
+            stage: stage.to_string(),
+            samples: sorted.len(),
+            min: sorted.first().copied().unwrap_or_default(),
+            median: percentile(50),
+            mean,
+            p95: percentile(95),
+        }
+    }
+}
+
+/// Result of a benchmark run
+///
+/// Fields:
+/// --- ---
+/// sections -> Number of sections in the synthetic database
+/// queries -> Number of distinct queries timed
+/// iterations -> How many times each query was timed
+/// matched -> Total classes returned by one pass over the queries
+/// stages -> Timing summary for each stage, in pipeline order
+/// --- ---
+#[derive(Debug, Clone)]
+pub struct BenchReport {
+    pub sections: usize,
+    pub queries: usize,
+    pub iterations: usize,
+    pub matched: usize,
+    pub stages: Vec<StageTiming>,
+}
+
+impl BenchReport {
+    /// Format the report for display
+    ///
+    /// Returns:
+    /// --- ---
+    /// Vec<String> -> A header line followed by one row per stage
+    /// --- ---
+    pub fn report_lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            format!(
+                "{} sections, {} queries x {} iterations ({} classes matched per pass)",
+                self.sections, self.queries, self.iterations, self.matched
+            ),
+            String::new(),
+            format!(
+                "  {:<12} {:>12} {:>12} {:>12} {:>12}",
+                "stage", "min", "median", "mean", "p95"
+            ),
+        ];
+        for timing in &self.stages {
+            lines.push(format!(
+                "  {:<12} {:>12} {:>12} {:>12} {:>12}",
+                timing.stage,
+                format_duration(timing.min),
+                format_duration(timing.median),
+                format_duration(timing.mean),
+                format_duration(timing.p95),
+            ));
+        }
+        lines
+    }
+
+    /// Convert the report to JSON, e.g. to save as a baseline
+    ///
+    /// Returns:
+    /// --- ---
+    /// Value -> The report, with durations in microseconds
+    /// --- ---
+    pub fn to_json(&self) -> Value {
+        let micros = |duration: Duration| duration.as_secs_f64() * 1_000_000.0;
+        json!({
+            "sections": self.sections,
+            "queries": self.queries,
+            "iterations": self.iterations,
+            "matched": self.matched,
+            "stages": self.stages.iter().map(|timing| json!({
+                "stage": timing.stage,
+                "samples": timing.samples,
+                "min_us": micros(timing.min),
+                "median_us": micros(timing.median),
+                "mean_us": micros(timing.mean),
+                "p95_us": micros(timing.p95),
+            })).collect::<Vec<_>>(),
+        })
+    }
+
+    /// Compare median timings against a saved baseline
+    ///
+    /// Stages missing from the baseline are skipped, so baselines from older versions
+    /// still work
+    ///
+    /// Parameters:
+    /// --- ---
+    /// baseline -> A report saved with to_json
+    /// threshold_percent -> How much slower than the baseline a median may get
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Vec<String> -> One description per regressed stage (empty if none regressed)
+    /// --- ---
+    pub fn regressions(&self, baseline: &Value, threshold_percent: f64) -> Vec<String> {
+        let baseline_stages = baseline["stages"].as_array().cloned().unwrap_or_default();
+
+        self.stages
+            .iter()
+            .filter_map(|timing| {
+                let baseline_median = baseline_stages
+                    .iter()
+                    .find(|stage| stage["stage"].as_str() == Some(timing.stage.as_str()))?
+                    ["median_us"]
+                    .as_f64()
+                    .filter(|median| *median > 0.0)?;
+                let median = timing.median.as_secs_f64() * 1_000_000.0;
+                let change = (median - baseline_median) / baseline_median * 100.0;
+
+                (change > threshold_percent).then(|| {
+                    format!(
+                        "{}: median {:.1}us vs baseline {:.1}us (+{:.0}%)",
+                        timing.stage, median, baseline_median, change
+                    )
+                })
+            })
+            .collect()
+    }
+}
+
+/// Format a duration for the report table
+///
+/// Parameters:
+/// --- ---
+/// duration -> The duration
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> The duration in the largest fitting unit (e.g., "850ns", "12.4us", "3.10ms")
+/// --- ---
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+    if nanos < 1_000 {
+        format!("{}ns", nanos)
+    } else if nanos < 1_000_000 {
+        format!("{:.1}us", nanos as f64 / 1_000.0)
+    } else {
+        format!("{:.2}ms", nanos as f64 / 1_000_000.0)
+    }
+}
+
+/// Create a synthetic class database
+///
+/// Any existing file at db_path is replaced. The data is the sample catalog (see
+/// data::sample) under the benchmark school, so the same seed always produces the same data
+///
+/// Parameters:
+/// --- ---
+/// db_path -> Where to write the database
+/// sections -> Number of sections to generate
+/// seed -> Seed for the generated data
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<(), String> -> Success or error message
+/// --- ---
+pub fn generate_dataset(db_path: &Path, sections: usize, seed: u64) -> Result<(), String> {
+    let config = SampleConfig {
+        school_id: BENCH_SCHOOL_ID.to_string(),
+        school_name: "Benchmark University".to_string(),
+        sections,
+        seed,
+        ..SampleConfig::default()
+    };
+    generate_catalog(db_path, &config).map(|_| ())
+}
+
+/// Time every compiler stage and the full search pipeline against a database
+///
+/// Each query in BENCH_QUERIES runs once untimed to warm caches, then config.iterations
+/// times. Stages are timed separately, and end_to_end times the whole pipeline
+/// (including semantic analysis) in one go
+///
+/// Parameters:
+/// --- ---
+/// config -> Benchmark settings (the database is assumed to hold config.sections sections)
+/// db_path -> The database to search
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<BenchReport, String> -> The timings, or an error if a query fails
+/// --- ---
+pub fn run_benchmarks(config: &BenchConfig, db_path: &Path) -> Result<BenchReport, String> {
+    let mut lex = Vec::new();
+    let mut parse = Vec::new();
+    let mut codegen = Vec::new();
+    let mut query = Vec::new();
+    let mut end_to_end = Vec::new();
+    let mut matched = 0;
+
+    for iteration in 0..=config.iterations {
+        for input in BENCH_QUERIES {
+            let pipeline_start = Instant::now();
+
+            let start = Instant::now();
+            let tokens = Lexer::new(input.to_string())
+                .analyze()
+                .map_err(|e| format!("'{}' failed to lex: {:?}", input, e))?;
+            let lex_time = start.elapsed();
+
+            let start = Instant::now();
+            let ast = Parser::new(input.to_string())
+                .parse(&tokens)
+                .map_err(|(e, _)| format!("'{}' failed to parse: {}", input, e))?;
+            let parse_time = start.elapsed();
+
+            semantic_analysis(&ast)
+                .map_err(|(e, _)| format!("'{}' failed semantic analysis: {}", input, e))?;
+
+            let start = Instant::now();
+            let sql = generate_sql(&ast)
+                .map_err(|e| format!("'{}' failed code generation: {}", input, e))?;
+            let codegen_time = start.elapsed();
+
+            let start = Instant::now();
+            let classes = execute_query(&sql, db_path)?;
+            let query_time = start.elapsed();
+
+            let pipeline_time = pipeline_start.elapsed();
+
+            // the first pass only warms up the regex and page caches
+            if iteration == 0 {
+                matched += classes.len();
+                continue;
+            }
+            lex.push(lex_time);
+            parse.push(parse_time);
+            codegen.push(codegen_time);
+            query.push(query_time);
+            end_to_end.push(pipeline_time);
+        }
+    }
+
+    Ok(BenchReport {
+        sections: config.sections,
+        queries: BENCH_QUERIES.len(),
+        iterations: config.iterations,
+        matched,
+        stages: vec![
+            StageTiming::from_samples("lex", &lex),
+            StageTiming::from_samples("parse", &parse),
+            StageTiming::from_samples("codegen", &codegen),
+            StageTiming::from_samples("query", &query),
+            StageTiming::from_samples("end_to_end", &end_to_end),
+        ],
+    })
+}
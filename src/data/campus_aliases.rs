@@ -0,0 +1,160 @@
+/*
+    src/data/campus_aliases.rs
+
+    Module for campus aliases
+    Schools name their campuses in full ("Poughkeepsie Main Campus"), so users can define
+    short names for them in Settings ("main"). Semantic resolution replaces an alias typed in
+    a campus query with the campus it stands for before the query is checked and run. The
+    aliases are kept in the app database (see tui::save)
+*/
+
+use serde::{Deserialize, Serialize};
+
+use crate::utils::text::fold_text;
+
+/// A short name for a campus
+///
+/// Fields:
+/// --- ---
+/// alias -> The short name typed in queries, e.g., "main"
+/// campus -> The campus as the school names it, e.g., "Poughkeepsie Main Campus"
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for CampusAlias
+/// Clone -> Clone trait for CampusAlias
+/// PartialEq -> PartialEq trait for CampusAlias
+/// Serialize -> Serialize trait for CampusAlias
+/// Deserialize -> Deserialize trait for CampusAlias
+/// --- ---
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CampusAlias {
+    pub alias: String,
+    pub campus: String,
+}
+
+/// The campus aliases defined in Settings
+///
+/// Fields:
+/// --- ---
+/// aliases -> The aliases, sorted by alias; no two fold to the same text
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for CampusAliases
+/// Clone -> Clone trait for CampusAliases
+/// PartialEq -> PartialEq trait for CampusAliases
+/// Default -> No aliases
+/// Serialize -> Serialize trait for CampusAliases
+/// Deserialize -> Deserialize trait for CampusAliases (missing fields are empty)
+/// --- ---
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CampusAliases {
+    pub aliases: Vec<CampusAlias>,
+}
+
+impl CampusAliases {
+    /// Check whether any aliases are defined
+    ///
+    /// Returns:
+    /// --- ---
+    /// bool -> true if there are none
+    /// --- ---
+    pub fn is_empty(&self) -> bool {
+        self.aliases.is_empty()
+    }
+
+    /// Find the campus an alias stands for, ignoring case and accents
+    ///
+    /// Parameters:
+    /// --- ---
+    /// value -> A campus value typed in a query
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Option<&str> -> The campus, None if the value isn't an alias
+    /// --- ---
+    pub fn resolve(&self, value: &str) -> Option<&str> {
+        let folded = fold_text(value.trim());
+        self.aliases
+            .iter()
+            .find(|entry| fold_text(&entry.alias) == folded)
+            .map(|entry| entry.campus.as_str())
+    }
+
+    /// Define an alias, replacing any with the same name
+    ///
+    /// Parameters:
+    /// --- ---
+    /// alias -> The short name
+    /// campus -> The campus it stands for
+    /// --- ---
+    ///
+    /// Returns: None
+    pub fn set(&mut self, alias: &str, campus: &str) {
+        self.remove(alias);
+        self.aliases.push(CampusAlias {
+            alias: alias.trim().to_string(),
+            campus: campus.trim().to_string(),
+        });
+        self.aliases.sort_by_key(|entry| fold_text(&entry.alias));
+    }
+
+    /// Remove an alias, ignoring case and accents
+    ///
+    /// Parameters:
+    /// --- ---
+    /// alias -> The short name
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// bool -> true if there was an alias to remove
+    /// --- ---
+    pub fn remove(&mut self, alias: &str) -> bool {
+        let folded = fold_text(alias.trim());
+        let before = self.aliases.len();
+        self.aliases
+            .retain(|entry| fold_text(&entry.alias) != folded);
+        self.aliases.len() < before
+    }
+}
+
+/// Parse an alias as typed in Settings
+///
+/// Parameters:
+/// --- ---
+/// text -> "alias = campus", e.g., "main = Poughkeepsie Main Campus"
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<CampusAlias, String> -> The alias, or a message saying what is missing
+/// --- ---
+pub fn parse_campus_alias(text: &str) -> Result<CampusAlias, String> {
+    let Some((alias, campus)) = text.split_once('=') else {
+        return Err("Type an alias as: short name = campus".to_string());
+    };
+    let (alias, campus) = (alias.trim(), campus.trim());
+    if alias.is_empty() || campus.is_empty() {
+        return Err("Type an alias as: short name = campus".to_string());
+    }
+    // the alias is read as a single query value, so it can't need quotes
+    if !alias
+        .chars()
+        .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(format!(
+            "Alias '{}' can only use letters, digits, '-', and '_'",
+            alias
+        ));
+    }
+    Ok(CampusAlias {
+        alias: alias.to_string(),
+        campus: campus.to_string(),
+    })
+}
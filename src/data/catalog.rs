@@ -0,0 +1,271 @@
+/*
+    src/data/catalog.rs
+
+    Module for browsing the course catalog
+    Lists a term's subjects, a subject's courses, and a course's sections for the catalog
+    browser, one level at a time as the tree is expanded. Every level is looked up by school,
+    term, subject, and course number, which the catalog index on sections answers without
+    scanning the table; the index is created after each sync and by maintenance
+*/
+
+use std::path::PathBuf;
+
+use rusqlite::types::Value;
+use rusqlite::Connection;
+
+use crate::data::pool::open_read_connection;
+use crate::data::sql::{execute_query, Class};
+use crate::dsl::codegen::generate_course_sql;
+
+/// Index on sections for looking up a term's subjects, courses, and sections
+pub const CATALOG_INDEX_SQL: &str = "CREATE INDEX IF NOT EXISTS idx_sections_catalog \
+    ON sections(school_id, term_collection_id, subject_code, course_number)";
+
+/// The database, school, and term a catalog is browsed in
+///
+/// Fields:
+/// --- ---
+/// db_path -> Path to the SQLite database file
+/// school_id -> School to list, None for every school (the test database)
+/// term_id -> Term to list, None for every term (the test database)
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for CatalogScope
+/// Clone -> Clone trait for CatalogScope
+/// PartialEq -> PartialEq trait for CatalogScope
+/// --- ---
+#[derive(Debug, Clone, PartialEq)]
+pub struct CatalogScope {
+    pub db_path: PathBuf,
+    pub school_id: Option<String>,
+    pub term_id: Option<String>,
+}
+
+impl CatalogScope {
+    /// Build the school and term conditions on sections
+    ///
+    /// Parameters:
+    /// --- ---
+    /// None
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// (Vec<&'static str>, Vec<Value>) -> Conditions with ? placeholders and their values
+    /// --- ---
+    pub fn filters(&self) -> (Vec<&'static str>, Vec<Value>) {
+        let mut conditions = Vec::new();
+        let mut values = Vec::new();
+        if let Some(school_id) = &self.school_id {
+            conditions.push("s.school_id = ?");
+            values.push(Value::Text(school_id.clone()));
+        }
+        if let Some(term_id) = &self.term_id {
+            conditions.push("s.term_collection_id = ?");
+            values.push(Value::Text(term_id.clone()));
+        }
+        (conditions, values)
+    }
+}
+
+/// A subject in the catalog
+///
+/// Fields:
+/// --- ---
+/// code -> Subject code, e.g., "CMPT"
+/// description -> Subject name from the course data, e.g., "Computer Science"
+/// courses -> Number of courses with sections in the term
+/// sections -> Number of sections in the term
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for CatalogSubject
+/// Clone -> Clone trait for CatalogSubject
+/// PartialEq -> PartialEq trait for CatalogSubject
+/// --- ---
+#[derive(Debug, Clone, PartialEq)]
+pub struct CatalogSubject {
+    pub code: String,
+    pub description: Option<String>,
+    pub courses: usize,
+    pub sections: usize,
+}
+
+/// A course in the catalog
+///
+/// Fields:
+/// --- ---
+/// subject_code -> Subject code, e.g., "CMPT"
+/// number -> Course number, e.g., "103L"
+/// title -> Course title
+/// credit_hours -> Number of credit hours
+/// sections -> Number of sections in the term
+/// open_sections -> Number of those sections with a seat open (or no cap)
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for CatalogCourse
+/// Clone -> Clone trait for CatalogCourse
+/// PartialEq -> PartialEq trait for CatalogCourse
+/// --- ---
+#[derive(Debug, Clone, PartialEq)]
+pub struct CatalogCourse {
+    pub subject_code: String,
+    pub number: String,
+    pub title: String,
+    pub credit_hours: f64,
+    pub sections: usize,
+    pub open_sections: usize,
+}
+
+/// Create the catalog index if it doesn't exist yet
+///
+/// Parameters:
+/// --- ---
+/// conn -> Connection to the database (or a transaction on it)
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<(), String> -> Ok on success, error message on failure
+/// --- ---
+pub fn create_catalog_index(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(CATALOG_INDEX_SQL)
+        .map_err(|e| format!("Failed to create catalog index: {}", e))
+}
+
+/// Fetch the subjects of a term, in order of their codes
+///
+/// Parameters:
+/// --- ---
+/// scope -> The database, school, and term to list
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<Vec<CatalogSubject>, String> -> The subjects with their course and section counts
+/// --- ---
+pub fn fetch_subjects(scope: &CatalogScope) -> Result<Vec<CatalogSubject>, String> {
+    let conn = open_read_connection(&scope.db_path)?;
+    let (conditions, values) = scope.filters();
+    let where_clause = if conditions.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {} ", conditions.join(" AND "))
+    };
+
+    let sql = format!(
+        "SELECT s.subject_code, MAX(c.subject_description), \
+            COUNT(DISTINCT s.course_number), COUNT(*) \
+         FROM sections s \
+         JOIN courses c ON s.school_id = c.school_id \
+            AND s.subject_code = c.subject_code \
+            AND s.course_number = c.number \
+         {}GROUP BY s.subject_code ORDER BY s.subject_code",
+        where_clause
+    );
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| format!("SQL preparation error: {}", e))?;
+    let rows = stmt
+        .query_map(rusqlite::params_from_iter(values), |row| {
+            Ok(CatalogSubject {
+                code: row.get(0)?,
+                description: row.get::<_, Option<String>>(1)?.filter(|d| !d.is_empty()),
+                courses: row.get::<_, i64>(2)? as usize,
+                sections: row.get::<_, i64>(3)? as usize,
+            })
+        })
+        .map_err(|e| format!("Query execution error: {}", e))?;
+
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Error reading row: {}", e))
+}
+
+/// Fetch the courses of a subject that have sections in the term
+///
+/// Parameters:
+/// --- ---
+/// scope -> The database, school, and term to list
+/// subject_code -> The subject, e.g., "CMPT"
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<Vec<CatalogCourse>, String> -> The courses in numeric order (100 before 1000)
+/// --- ---
+pub fn fetch_courses(
+    scope: &CatalogScope,
+    subject_code: &str,
+) -> Result<Vec<CatalogCourse>, String> {
+    let conn = open_read_connection(&scope.db_path)?;
+    let (mut conditions, mut values) = scope.filters();
+    conditions.push("s.subject_code = ?");
+    values.push(Value::Text(subject_code.to_string()));
+
+    let sql = format!(
+        "SELECT s.subject_code, s.course_number, c.title, c.credit_hours, COUNT(*), \
+            SUM(CASE WHEN s.max_enrollment IS NULL OR s.enrollment IS NULL \
+                OR s.enrollment < s.max_enrollment THEN 1 ELSE 0 END) \
+         FROM sections s \
+         JOIN courses c ON s.school_id = c.school_id \
+            AND s.subject_code = c.subject_code \
+            AND s.course_number = c.number \
+         WHERE {} \
+         GROUP BY s.subject_code, s.course_number \
+         ORDER BY CAST(s.course_number AS INTEGER), s.course_number",
+        conditions.join(" AND ")
+    );
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| format!("SQL preparation error: {}", e))?;
+    let rows = stmt
+        .query_map(rusqlite::params_from_iter(values), |row| {
+            Ok(CatalogCourse {
+                subject_code: row.get(0)?,
+                number: row.get(1)?,
+                title: row.get::<_, Option<String>>(2)?.unwrap_or_default(),
+                credit_hours: row.get::<_, Option<f64>>(3)?.unwrap_or(0.0),
+                sections: row.get::<_, i64>(4)? as usize,
+                open_sections: row.get::<_, i64>(5)? as usize,
+            })
+        })
+        .map_err(|e| format!("Query execution error: {}", e))?;
+
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Error reading row: {}", e))
+}
+
+/// Fetch the sections of a course
+///
+/// Parameters:
+/// --- ---
+/// scope -> The database, school, and term to list
+/// subject_code -> The course's subject, e.g., "CMPT"
+/// course_number -> The course's number, e.g., "103L"
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<Vec<Class>, String> -> The sections by sequence, with the same columns as search results
+/// --- ---
+pub fn fetch_sections(
+    scope: &CatalogScope,
+    subject_code: &str,
+    course_number: &str,
+) -> Result<Vec<Class>, String> {
+    let sql = generate_course_sql(
+        scope.school_id.as_deref(),
+        scope.term_id.as_deref(),
+        subject_code,
+        course_number,
+    )
+    .map_err(|e| e.to_string())?;
+    let mut sections = execute_query(&sql, &scope.db_path)?;
+    sections.sort_by(|a, b| a.section_sequence.cmp(&b.section_sequence));
+    Ok(sections)
+}
@@ -0,0 +1,155 @@
+/*
+    src/data/duplicates.rs
+
+    Module for collapsing duplicate sections in search results
+    Large courses run several sections at the same time, often only told apart by their
+    section number, and they fill the results grid with cards that read the same. Sections
+    of one course meeting at the same times and dates, on the same campus, and taught the
+    same way are grouped so the results can show one card per group with a count of the others
+*/
+
+use std::collections::{HashMap, HashSet};
+
+use crate::data::sql::{pack_meeting_times, Class};
+
+/// Search results with duplicate sections collapsed
+///
+/// Fields:
+/// --- ---
+/// shown -> The results to show: each group's first section, or all of an expanded group's
+/// groups -> Unique IDs of every section in each group of two or more, keyed by the unique
+///           ID of the group's first section
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for CollapsedResults
+/// Clone -> Clone trait for CollapsedResults
+/// Default -> Default trait for CollapsedResults
+/// --- ---
+#[derive(Debug, Clone, Default)]
+pub struct CollapsedResults {
+    pub shown: Vec<Class>,
+    pub groups: HashMap<String, Vec<String>>,
+}
+
+impl CollapsedResults {
+    /// Get the number of sections not shown
+    ///
+    /// Parameters:
+    /// --- ---
+    /// expanded -> Unique IDs of the first sections of the groups shown in full
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// usize -> Sections folded under another section's card
+    /// --- ---
+    pub fn hidden(&self, expanded: &HashSet<String>) -> usize {
+        self.groups
+            .iter()
+            .filter(|(first, _)| !expanded.contains(*first))
+            .map(|(_, members)| members.len() - 1)
+            .sum()
+    }
+
+    /// Find the group a section belongs to
+    ///
+    /// Parameters:
+    /// --- ---
+    /// unique_id -> Unique ID of the section
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Option<&str> -> Unique ID of the group's first section, None if it has no duplicates
+    /// --- ---
+    pub fn group_of(&self, unique_id: &str) -> Option<&str> {
+        self.groups
+            .iter()
+            .find(|(_, members)| members.iter().any(|member| member == unique_id))
+            .map(|(first, _)| first.as_str())
+    }
+}
+
+/// Collapse the duplicate sections of search results
+///
+/// Groups take the place of their first section, so the results keep their order
+///
+/// Parameters:
+/// --- ---
+/// classes -> The results, in the order they are shown
+/// expanded -> Unique IDs of the first sections of the groups to show in full
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// CollapsedResults -> The results to show and the groups found
+/// --- ---
+pub fn collapse_duplicates(classes: &[Class], expanded: &HashSet<String>) -> CollapsedResults {
+    // indexes of each group's sections, groups in the order their first section appears
+    let mut group_index: HashMap<DuplicateKey, usize> = HashMap::new();
+    let mut members: Vec<Vec<usize>> = Vec::new();
+    for (index, class) in classes.iter().enumerate() {
+        let group = *group_index.entry(duplicate_key(class)).or_insert_with(|| {
+            members.push(Vec::new());
+            members.len() - 1
+        });
+        members[group].push(index);
+    }
+
+    let mut collapsed = CollapsedResults::default();
+    for group in &members {
+        let first = classes[group[0]].unique_id();
+        // an expanded group's other sections follow its first one
+        let count = if expanded.contains(&first) {
+            group.len()
+        } else {
+            1
+        };
+        collapsed
+            .shown
+            .extend(group[..count].iter().map(|&index| classes[index].clone()));
+        if group.len() > 1 {
+            collapsed.groups.insert(
+                first,
+                group
+                    .iter()
+                    .map(|&index| classes[index].unique_id())
+                    .collect(),
+            );
+        }
+    }
+    collapsed
+}
+
+/// What makes two sections duplicates: course, campus, method, and sorted packed meetings
+type DuplicateKey = (String, String, Option<String>, Option<String>, Vec<String>);
+
+/// Build the key two sections share when they are duplicates
+///
+/// Parameters:
+/// --- ---
+/// class -> The section
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// DuplicateKey -> The section's course, campus, instruction method, and meetings with
+///                 their days, times, and dates
+/// --- ---
+fn duplicate_key(class: &Class) -> DuplicateKey {
+    let mut meetings: Vec<String> = class
+        .meeting_times
+        .iter()
+        .map(|meeting| pack_meeting_times(std::slice::from_ref(meeting)))
+        .collect();
+    meetings.sort();
+    (
+        class.subject_code.clone(),
+        class.course_number.clone(),
+        class.campus.clone(),
+        class.instruction_method.clone(),
+        meetings,
+    )
+}
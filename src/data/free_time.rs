@@ -0,0 +1,455 @@
+/*
+    src/data/free_time.rs
+
+    Module for finding free time in a schedule
+    A request like "1 hour MWF between 10am and 3pm" is parsed into days, a time window, and
+    a length; the meetings of the schedule are then inverted within the window to find the
+    blocks that are free on each day, and the blocks that are free on every one of them
+*/
+
+use crate::data::sql::Class;
+use crate::utils::time::{format_12_hour, parse_days, parse_time, DAY_CODES};
+
+/// Request shown in the free time finder until it is edited
+pub const DEFAULT_FREE_TIME_REQUEST: &str = "1 hour MWF between 10am and 3pm";
+
+/// Length of a free block when the request doesn't give one (minutes)
+const DEFAULT_LENGTH_MINUTES: i32 = 60;
+
+/// Window searched when the request doesn't give one (8am to 6pm, minutes since midnight)
+const DEFAULT_WINDOW: (i32, i32) = (8 * 60, 18 * 60);
+
+/// Words that only make the request read like a sentence
+const FILLER_WORDS: [&str; 21] = [
+    "what", "when", "free", "time", "block", "blocks", "am", "i", "a", "an", "of", "at", "least",
+    "on", "between", "and", "from", "to", "for", "every", "until",
+];
+
+/// What to look for
+///
+/// Fields:
+/// --- ---
+/// days -> Day codes to search, in week order
+/// start_minutes -> Start of the window (minutes since midnight)
+/// end_minutes -> End of the window (minutes since midnight)
+/// length_minutes -> Shortest free block that counts
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for FreeTimeRequest
+/// Clone -> Clone trait for FreeTimeRequest
+/// PartialEq -> PartialEq trait for FreeTimeRequest
+/// --- ---
+#[derive(Debug, Clone, PartialEq)]
+pub struct FreeTimeRequest {
+    pub days: Vec<String>,
+    pub start_minutes: i32,
+    pub end_minutes: i32,
+    pub length_minutes: i32,
+}
+
+/// A free block on one day
+///
+/// Fields:
+/// --- ---
+/// day -> Day code, e.g., "M"
+/// start_minutes -> Start of the block (minutes since midnight)
+/// end_minutes -> End of the block (minutes since midnight)
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for FreeBlock
+/// Clone -> Clone trait for FreeBlock
+/// PartialEq -> PartialEq trait for FreeBlock
+/// --- ---
+#[derive(Debug, Clone, PartialEq)]
+pub struct FreeBlock {
+    pub day: String,
+    pub start_minutes: i32,
+    pub end_minutes: i32,
+}
+
+impl FreeBlock {
+    /// Format the block's times
+    ///
+    /// Returns:
+    /// --- ---
+    /// String -> e.g., "10:45am-12:30pm"
+    /// --- ---
+    pub fn label(&self) -> String {
+        format_range(self.start_minutes, self.end_minutes)
+    }
+}
+
+impl FreeTimeRequest {
+    /// Parse a request
+    ///
+    /// The request is a length ("1 hour", "90 min", "1.5h"), days ("MWF", "tue thu",
+    /// "weekdays"), and a window ("between 10am and 3pm", "10am-3pm"), in any order and
+    /// each optional. A time without am/pm is read the way class times are: 8 through 11
+    /// are mornings and 12 through 7 are afternoons
+    ///
+    /// Parameters:
+    /// --- ---
+    /// text -> The request
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Result<FreeTimeRequest, String> -> The request or error message
+    /// --- ---
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let lower = text.to_lowercase().replace(['-', ',', '?', '!'], " ");
+        let words: Vec<&str> = lower.split_whitespace().collect();
+
+        let mut days: Vec<String> = Vec::new();
+        let mut times: Vec<i32> = Vec::new();
+        let mut length: Option<i32> = None;
+
+        let mut i = 0;
+        while i < words.len() {
+            let word = words[i];
+            let next = words.get(i + 1).copied();
+            i += 1;
+
+            if let Some(day_codes) = parse_day_word(word) {
+                days.extend(day_codes);
+                continue;
+            }
+            if word == "noon" {
+                times.push(12 * 60);
+                continue;
+            }
+            if word == "hour" || word == "hr" {
+                length = Some(60);
+                continue;
+            }
+            if FILLER_WORDS.contains(&word) {
+                continue;
+            }
+
+            // a number, with its unit or am/pm attached or as the next word
+            let split = word
+                .find(|c: char| !c.is_ascii_digit() && c != '.' && c != ':')
+                .unwrap_or(word.len());
+            let (number, mut suffix) = word.split_at(split);
+            if number.is_empty() {
+                return Err(format!("Don't know what '{}' means here", word));
+            }
+            if suffix.is_empty() {
+                if let Some(next) = next.filter(|n| is_time_suffix(n) || unit_minutes(n).is_some())
+                {
+                    suffix = next;
+                    i += 1;
+                }
+            }
+
+            if let Some(unit) = unit_minutes(suffix) {
+                let amount: f64 = number
+                    .parse()
+                    .map_err(|_| format!("'{}' isn't a length", word))?;
+                length = Some((amount * unit as f64).round() as i32);
+            } else if is_time_suffix(suffix) || suffix.is_empty() {
+                times.push(
+                    parse_clock(number, suffix)
+                        .ok_or_else(|| format!("'{}{}' isn't a time", number, suffix))?,
+                );
+            } else {
+                return Err(format!("Don't know what '{}' means here", word));
+            }
+        }
+
+        let (start_minutes, end_minutes) = match times[..] {
+            [] => DEFAULT_WINDOW,
+            [start, end] => (start, end),
+            _ => return Err("Give the window as two times, e.g., 10am-3pm".to_string()),
+        };
+        if end_minutes <= start_minutes {
+            return Err(format!(
+                "The window {} ends before it starts",
+                format_range(start_minutes, end_minutes)
+            ));
+        }
+        let length_minutes = length.unwrap_or(DEFAULT_LENGTH_MINUTES);
+        if length_minutes <= 0 {
+            return Err("The length has to be more than 0 minutes".to_string());
+        }
+
+        if days.is_empty() {
+            days = DAY_CODES[..5].iter().map(|d| d.to_string()).collect();
+        }
+        let days = DAY_CODES
+            .iter()
+            .filter(|code| days.iter().any(|d| d == *code))
+            .map(|code| code.to_string())
+            .collect();
+
+        Ok(FreeTimeRequest {
+            days,
+            start_minutes,
+            end_minutes,
+            length_minutes,
+        })
+    }
+
+    /// Describe the request in one line
+    ///
+    /// Returns:
+    /// --- ---
+    /// String -> e.g., "1h free on M W F between 10:00am and 3:00pm"
+    /// --- ---
+    pub fn describe(&self) -> String {
+        format!(
+            "{} free on {} between {} and {}",
+            format_length(self.length_minutes),
+            self.days.join(" "),
+            format_12_hour(self.start_minutes),
+            format_12_hour(self.end_minutes)
+        )
+    }
+}
+
+/// Find the free blocks of a schedule on each requested day
+///
+/// Parameters:
+/// --- ---
+/// schedule -> The schedule's classes
+/// request -> What to look for
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Vec<FreeBlock> -> Every free block at least the requested length, by day and then time
+/// --- ---
+pub fn find_free_time(schedule: &[Class], request: &FreeTimeRequest) -> Vec<FreeBlock> {
+    request
+        .days
+        .iter()
+        .flat_map(|day| {
+            let busy = busy_times(schedule, day);
+            invert(&busy, request)
+                .into_iter()
+                .map(move |(start_minutes, end_minutes)| FreeBlock {
+                    day: day.clone(),
+                    start_minutes,
+                    end_minutes,
+                })
+        })
+        .collect()
+}
+
+/// Find the blocks that are free on every requested day
+///
+/// Parameters:
+/// --- ---
+/// schedule -> The schedule's classes
+/// request -> What to look for
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Vec<(i32, i32)> -> (start_minutes, end_minutes) of each block at least the requested
+///                    length, in time order
+/// --- ---
+pub fn common_free_time(schedule: &[Class], request: &FreeTimeRequest) -> Vec<(i32, i32)> {
+    let busy: Vec<(i32, i32)> = request
+        .days
+        .iter()
+        .flat_map(|day| busy_times(schedule, day))
+        .collect();
+    invert(&busy, request)
+}
+
+/// Format a time range
+///
+/// Parameters:
+/// --- ---
+/// start_minutes -> Start (minutes since midnight)
+/// end_minutes -> End (minutes since midnight)
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> e.g., "10:45am-12:30pm"
+/// --- ---
+pub fn format_range(start_minutes: i32, end_minutes: i32) -> String {
+    format!(
+        "{}-{}",
+        format_12_hour(start_minutes),
+        format_12_hour(end_minutes)
+    )
+}
+
+/// Format a length of time
+///
+/// Parameters:
+/// --- ---
+/// minutes -> The length in minutes
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> e.g., "45m", "1h", "1h 45m"
+/// --- ---
+pub fn format_length(minutes: i32) -> String {
+    match (minutes / 60, minutes % 60) {
+        (0, mins) => format!("{}m", mins),
+        (hours, 0) => format!("{}h", hours),
+        (hours, mins) => format!("{}h {}m", hours, mins),
+    }
+}
+
+/// Get the meeting times of a schedule on one day
+///
+/// Parameters:
+/// --- ---
+/// schedule -> The schedule's classes
+/// day -> Day code
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Vec<(i32, i32)> -> (start_minutes, end_minutes) of each meeting that day
+/// --- ---
+fn busy_times(schedule: &[Class], day: &str) -> Vec<(i32, i32)> {
+    schedule
+        .iter()
+        .flat_map(|class| &class.meeting_times)
+        .filter(|meeting| meeting.meets_on(day))
+        .map(|meeting| (meeting.start_minutes, meeting.end_minutes))
+        .collect()
+}
+
+/// Invert busy times within the request's window
+///
+/// Parameters:
+/// --- ---
+/// busy -> (start_minutes, end_minutes) of each meeting, in any order
+/// request -> The window and the shortest block that counts
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Vec<(i32, i32)> -> The free blocks, in time order
+/// --- ---
+fn invert(busy: &[(i32, i32)], request: &FreeTimeRequest) -> Vec<(i32, i32)> {
+    let mut busy = busy.to_vec();
+    busy.sort_unstable();
+
+    let mut free = Vec::new();
+    let mut free_from = request.start_minutes;
+    for (start_minutes, end_minutes) in busy {
+        let gap_end = start_minutes.min(request.end_minutes);
+        if gap_end - free_from >= request.length_minutes {
+            free.push((free_from, gap_end));
+        }
+        free_from = free_from.max(end_minutes);
+    }
+    if request.end_minutes - free_from >= request.length_minutes {
+        free.push((free_from, request.end_minutes));
+    }
+    free
+}
+
+/// Read a word as days of the week
+///
+/// Parameters:
+/// --- ---
+/// word -> Lowercase word, e.g., "mwf", "tth", "tue", "weekdays"
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Option<Vec<String>> -> The day codes, None if the word isn't days
+/// --- ---
+fn parse_day_word(word: &str) -> Option<Vec<String>> {
+    let codes = |codes: &[&str]| Some(codes.iter().map(|c| c.to_string()).collect());
+    match word {
+        "weekdays" | "weekday" => codes(&DAY_CODES[..5]),
+        "weekends" | "weekend" => codes(&DAY_CODES[5..]),
+        "monday" | "mondays" | "mon" => codes(&["M"]),
+        "tuesday" | "tuesdays" | "tue" | "tues" => codes(&["T"]),
+        "wednesday" | "wednesdays" | "wed" => codes(&["W"]),
+        "thursday" | "thursdays" | "thu" | "thur" | "thurs" => codes(&["TH"]),
+        "friday" | "fridays" | "fri" => codes(&["F"]),
+        "saturday" | "saturdays" | "sat" => codes(&["S"]),
+        "sunday" | "sundays" | "sun" => codes(&["SU"]),
+        // compact day strings only use the letters of day codes
+        _ if word.chars().all(|c| "mtwhfsu".contains(c))
+            && !word.starts_with('h')
+            && !word.starts_with('u') =>
+        {
+            let upper = word.to_uppercase();
+            let days = parse_days(&upper);
+            // every letter has to belong to a day ("mu" isn't days)
+            (days.concat().len() == upper.len()).then_some(days)
+        }
+        _ => None,
+    }
+}
+
+/// Get the minutes in a length unit
+///
+/// Parameters:
+/// --- ---
+/// unit -> Lowercase unit, e.g., "h", "hours", "min"
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Option<i32> -> Minutes per unit, None if it isn't a unit
+/// --- ---
+fn unit_minutes(unit: &str) -> Option<i32> {
+    match unit {
+        "h" | "hr" | "hrs" | "hour" | "hours" => Some(60),
+        "m" | "min" | "mins" | "minute" | "minutes" => Some(1),
+        _ => None,
+    }
+}
+
+/// Check whether a word is am or pm
+///
+/// Parameters:
+/// --- ---
+/// word -> Lowercase word
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// bool -> true for "am" and "pm"
+/// --- ---
+fn is_time_suffix(word: &str) -> bool {
+    word == "am" || word == "pm"
+}
+
+/// Read a clock time
+///
+/// Parameters:
+/// --- ---
+/// number -> Hour or hour:minute, e.g., "10" or "10:30"
+/// suffix -> "am", "pm", or "" to guess like class times
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Option<i32> -> Minutes since midnight, None if it isn't a time
+/// --- ---
+fn parse_clock(number: &str, suffix: &str) -> Option<i32> {
+    let clock = if number.contains(':') {
+        number.to_string()
+    } else {
+        format!("{}:00", number)
+    };
+    if !suffix.is_empty() {
+        return parse_time(&format!("{}{}", clock, suffix));
+    }
+    let minutes = parse_time(&clock)?;
+    // 1 through 7 o'clock without am/pm means the afternoon
+    if (60..8 * 60).contains(&minutes) {
+        Some(minutes + 12 * 60)
+    } else {
+        Some(minutes)
+    }
+}
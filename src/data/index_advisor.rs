@@ -0,0 +1,411 @@
+/*
+    src/data/index_advisor.rs
+
+    Module for the query plan index advisor
+    Runs EXPLAIN QUERY PLAN on a generated search and, when SQLite has to scan a whole table
+    that the search filters on a searchable column, suggests an index that would let it look
+    the rows up instead. Suggested indexes are only created when the user asks for them
+*/
+
+use std::path::Path;
+
+use rusqlite::Connection;
+
+use crate::data::pool::{open_connection, open_read_connection};
+use crate::dsl::entities::ENTITIES;
+use crate::utils::i18n;
+
+/// Columns searches filter on besides the entity columns (school and term filters)
+const SCOPE_COLUMNS: &[&str] = &["s.school_id", "s.term_collection_id"];
+
+/// Comparisons an index on the bare column can answer
+const INDEXABLE_OPERATORS: &[&str] = &[" = ", " < ", " > ", " <= ", " >= ", " IN ("];
+
+/// An index that would replace a full table scan
+///
+/// Fields:
+/// --- ---
+/// table -> Table to index
+/// column -> Column to index
+/// lowercase -> Whether to index LOWER(column), for case-insensitive comparisons
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for IndexAdvice
+/// Clone -> Clone trait for IndexAdvice
+/// PartialEq -> PartialEq trait for IndexAdvice
+/// --- ---
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexAdvice {
+    pub table: String,
+    pub column: String,
+    pub lowercase: bool,
+}
+
+impl IndexAdvice {
+    /// Get the name the index is created with
+    ///
+    /// Returns:
+    /// --- ---
+    /// String -> e.g., "idx_sections_campus_lower"
+    /// --- ---
+    pub fn name(&self) -> String {
+        let suffix = if self.lowercase { "_lower" } else { "" };
+        format!("idx_{}_{}{}", self.table, self.column, suffix)
+    }
+
+    /// Get the statement that creates the index
+    ///
+    /// Returns:
+    /// --- ---
+    /// String -> CREATE INDEX IF NOT EXISTS statement
+    /// --- ---
+    pub fn create_sql(&self) -> String {
+        format!(
+            "CREATE INDEX IF NOT EXISTS {} ON {}({})",
+            self.name(),
+            self.table,
+            self.indexed_expression()
+        )
+    }
+
+    /// Get the indexed column or expression
+    ///
+    /// Returns:
+    /// --- ---
+    /// String -> The column, or LOWER(column)
+    /// --- ---
+    fn indexed_expression(&self) -> String {
+        if self.lowercase {
+            format!("LOWER({})", self.column)
+        } else {
+            self.column.clone()
+        }
+    }
+}
+
+/// The query plan of a search and the indexes suggested for it
+///
+/// Fields:
+/// --- ---
+/// sql -> The explained SQL
+/// plan -> Plan step descriptions, in EXPLAIN QUERY PLAN order
+/// advice -> Indexes that would replace full scans in the plan
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for QueryExplanation
+/// Clone -> Clone trait for QueryExplanation
+/// --- ---
+#[derive(Debug, Clone)]
+pub struct QueryExplanation {
+    pub sql: String,
+    pub plan: Vec<String>,
+    pub advice: Vec<IndexAdvice>,
+}
+
+impl QueryExplanation {
+    /// Format the explanation as plain text lines
+    ///
+    /// Section headers are unindented and their rows are indented by two spaces
+    ///
+    /// Returns:
+    /// --- ---
+    /// Vec<String> -> Report lines in display order
+    /// --- ---
+    pub fn report_lines(&self) -> Vec<String> {
+        let mut lines = vec![i18n::tr("plan-index-advice", "Index advice", &[])];
+        if self.advice.is_empty() {
+            lines.push(format!(
+                "  {}",
+                i18n::tr(
+                    "plan-no-advice",
+                    "(none, no filtered table is fully scanned)",
+                    &[]
+                )
+            ));
+        }
+        for advice in &self.advice {
+            lines.push(format!(
+                "  {}",
+                i18n::tr(
+                    "plan-full-scan",
+                    "{table}: full scan filtered on {expression}",
+                    &[
+                        ("table", &advice.table),
+                        ("expression", &advice.indexed_expression()),
+                    ],
+                )
+            ));
+            lines.push(format!("    {}", advice.create_sql()));
+        }
+
+        lines.push(String::new());
+        lines.push(i18n::tr("plan-query-plan", "Query plan", &[]));
+        for step in &self.plan {
+            lines.push(format!("  {}", step));
+        }
+
+        lines
+    }
+}
+
+/// Explain a generated search and suggest indexes for it
+///
+/// Parameters:
+/// --- ---
+/// db_path -> Database the search runs against
+/// sql -> The generated SQL
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<QueryExplanation, String> -> The plan and advice, or error message
+/// --- ---
+pub fn explain_search(db_path: &Path, sql: &str) -> Result<QueryExplanation, String> {
+    let conn = open_read_connection(db_path)?;
+    let plan = explain_query(&conn, sql)?;
+    let advice = advise_indexes(&conn, sql, &plan)?;
+    Ok(QueryExplanation {
+        sql: sql.to_string(),
+        plan,
+        advice,
+    })
+}
+
+/// Get the query plan for a SQL statement
+///
+/// Parameters:
+/// --- ---
+/// conn -> Open database connection
+/// sql -> The statement to plan
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<Vec<String>, String> -> Plan step descriptions, or error message
+/// --- ---
+pub fn explain_query(conn: &Connection, sql: &str) -> Result<Vec<String>, String> {
+    let mut stmt = conn
+        .prepare(&format!("EXPLAIN QUERY PLAN {}", sql))
+        .map_err(|e| format!("SQL preparation error: {}", e))?;
+    // the detail column is the fourth column of EXPLAIN QUERY PLAN output
+    let steps = stmt
+        .query_map([], |row| row.get::<_, String>(3))
+        .map_err(|e| format!("Query execution error: {}", e))?
+        .filter_map(Result::ok)
+        .collect();
+    Ok(steps)
+}
+
+/// Suggest indexes for the tables a plan scans in full
+///
+/// A scanned table gets advice for each searchable column its rows are filtered on in the
+/// top-level WHERE clause, as long as the comparison could use an index (LIKE and conditions
+/// checked after grouping can't) and no index on the column exists yet
+///
+/// Parameters:
+/// --- ---
+/// conn -> Open database connection, used to look up existing indexes
+/// sql -> The planned SQL
+/// plan -> Plan steps from explain_query
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<Vec<IndexAdvice>, String> -> Suggested indexes without duplicates, or error message
+/// --- ---
+pub fn advise_indexes(
+    conn: &Connection,
+    sql: &str,
+    plan: &[String],
+) -> Result<Vec<IndexAdvice>, String> {
+    let aliases = table_aliases(sql);
+    let filters = where_clause(sql);
+    let filter_columns: Vec<&str> = ENTITIES
+        .iter()
+        .flat_map(|info| info.columns.iter().copied())
+        .chain(SCOPE_COLUMNS.iter().copied())
+        .collect();
+
+    let mut advice: Vec<IndexAdvice> = Vec::new();
+    for alias in plan.iter().filter_map(|step| scanned_alias(step)) {
+        let Some((_, table)) = aliases.iter().find(|(a, _)| *a == alias) else {
+            continue;
+        };
+        for qualified in &filter_columns {
+            let Some(column) = qualified.strip_prefix(&format!("{}.", alias)) else {
+                continue;
+            };
+            let lowered = format!("LOWER({}) = ", qualified);
+            let lowercase = if filters.contains(&lowered) {
+                true
+            } else if INDEXABLE_OPERATORS
+                .iter()
+                .any(|op| filters.contains(&format!("{}{}", qualified, op)))
+            {
+                false
+            } else {
+                continue;
+            };
+
+            let suggestion = IndexAdvice {
+                table: table.to_string(),
+                column: column.to_string(),
+                lowercase,
+            };
+            if !advice.contains(&suggestion) && !is_indexed(conn, &suggestion)? {
+                advice.push(suggestion);
+            }
+        }
+    }
+
+    Ok(advice)
+}
+
+/// Create suggested indexes
+///
+/// Parameters:
+/// --- ---
+/// db_path -> Database to create the indexes in
+/// advice -> The indexes to create
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<usize, String> -> Number of indexes created, or error message
+/// --- ---
+pub fn create_indexes(db_path: &Path, advice: &[IndexAdvice]) -> Result<usize, String> {
+    let conn = open_connection(db_path)?;
+    for index in advice {
+        conn.execute(&index.create_sql(), [])
+            .map_err(|e| format!("Failed to create {}: {}", index.name(), e))?;
+    }
+    Ok(advice.len())
+}
+
+/// Get the alias of the table a plan step scans in full
+///
+/// Parameters:
+/// --- ---
+/// step -> A plan step, e.g., "SCAN s" or "SCAN TABLE sections AS s" (older SQLite)
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Option<&str> -> The alias (or table name), None if the step is not a full scan
+/// --- ---
+fn scanned_alias(step: &str) -> Option<&str> {
+    // a scan "USING INDEX" still visits every row, the index only orders them for GROUP BY
+    let rest = step.strip_prefix("SCAN ")?;
+    let rest = rest.strip_prefix("TABLE ").unwrap_or(rest);
+    let mut words = rest.split_whitespace();
+    let name = words.next()?;
+    match (words.next(), words.next()) {
+        (Some("AS"), Some(alias)) => Some(alias),
+        _ => Some(name),
+    }
+}
+
+/// Read the table aliases from a query's FROM and JOIN clauses
+///
+/// Parameters:
+/// --- ---
+/// sql -> The SQL
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Vec<(&str, &str)> -> (alias, table) pairs, a table without an alias is its own alias
+/// --- ---
+fn table_aliases(sql: &str) -> Vec<(&str, &str)> {
+    let words: Vec<&str> = sql.split_whitespace().collect();
+    let mut aliases = Vec::new();
+    for (i, word) in words.iter().enumerate() {
+        if *word != "FROM" && *word != "JOIN" {
+            continue;
+        }
+        let Some(table) = words.get(i + 1) else {
+            continue;
+        };
+        match words.get(i + 2) {
+            Some(alias) if !matches!(*alias, "ON" | "WHERE" | "GROUP" | "JOIN" | "LEFT") => {
+                aliases.push((alias.trim_end_matches(')'), *table))
+            }
+            _ => aliases.push((*table, *table)),
+        }
+    }
+    aliases
+}
+
+/// Get the top-level WHERE clause of a query
+///
+/// Conditions in EXISTS subqueries and in HAVING are left out, a table index doesn't
+/// help the outer scan with them
+///
+/// Parameters:
+/// --- ---
+/// sql -> The SQL
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// &str -> Text between the top-level WHERE and GROUP BY (empty if there is no WHERE)
+/// --- ---
+fn where_clause(sql: &str) -> &str {
+    let mut depth = 0_i32;
+    let mut in_string = false;
+    let mut start = None;
+    for (i, c) in sql.char_indices() {
+        match c {
+            '\'' => in_string = !in_string,
+            '(' if !in_string => depth += 1,
+            ')' if !in_string => depth -= 1,
+            _ if in_string || depth != 0 => {}
+            _ if start.is_none() && sql[i..].starts_with(" WHERE ") => start = Some(i),
+            _ if start.is_some() && sql[i..].starts_with(" GROUP BY ") => {
+                return &sql[start.unwrap_or(i)..i];
+            }
+            _ => {}
+        }
+    }
+    start.map(|s| &sql[s..]).unwrap_or("")
+}
+
+/// Check whether an index already covers a suggestion
+///
+/// Parameters:
+/// --- ---
+/// conn -> Open database connection
+/// advice -> The suggested index
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<bool, String> -> true if the index exists, or another index starts with the
+///     column (for plain columns), or error message
+/// --- ---
+fn is_indexed(conn: &Connection, advice: &IndexAdvice) -> Result<bool, String> {
+    let exists: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'index' AND name = ?1",
+            [advice.name()],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Failed to read indexes: {}", e))?;
+    if exists > 0 || advice.lowercase {
+        return Ok(exists > 0);
+    }
+
+    let leading: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM pragma_index_list(?1) il, pragma_index_info(il.name) ii \
+             WHERE ii.seqno = 0 AND ii.name = ?2",
+            [&advice.table, &advice.column],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Failed to read indexes: {}", e))?;
+    Ok(leading > 0)
+}
@@ -0,0 +1,215 @@
+/*
+    src/data/lookups.rs
+
+    Module for the in-memory lookup lists
+    Subject codes, campuses, instruction methods, meeting types, and professor surnames are
+    read once in a background thread when the TUI starts (and again after a school is picked
+    or a sync finishes), so tab completion can offer real values and semantic validation can
+    catch a misspelled subject or campus without a database round trip per keystroke
+*/
+
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use rusqlite::Connection;
+
+use crate::data::pool::open_read_connection;
+use crate::dsl::parser::NodeType;
+use crate::utils::text::{edit_distance, fold_text};
+
+/// Distinct values of the searchable text columns
+///
+/// Fields:
+/// --- ---
+/// subjects -> Subject codes (e.g., "CMPT"), sorted
+/// campuses -> Campus names, sorted
+/// instruction_methods -> Instruction methods (e.g., "On-Line"), sorted
+/// meeting_types -> Meeting types (e.g., "LEC"), sorted
+/// professor_prefixes -> Professor surnames, the part of "Last, First" people type first, sorted
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for Lookups
+/// Clone -> Clone trait for Lookups
+/// Default -> Default trait for Lookups
+/// PartialEq -> PartialEq trait for Lookups
+/// --- ---
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Lookups {
+    pub subjects: Vec<String>,
+    pub campuses: Vec<String>,
+    pub instruction_methods: Vec<String>,
+    pub meeting_types: Vec<String>,
+    pub professor_prefixes: Vec<String>,
+}
+
+impl Lookups {
+    /// Get the known values for a query node's field
+    ///
+    /// Parameters:
+    /// --- ---
+    /// node -> The query node type (e.g., NodeType::SubjectQuery)
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// &[String] -> The values, empty for fields without a lookup list
+    /// --- ---
+    pub fn values_for(&self, node: &NodeType) -> &[String] {
+        match node {
+            NodeType::SubjectQuery => &self.subjects,
+            NodeType::CampusQuery => &self.campuses,
+            NodeType::InstructionMethodQuery => &self.instruction_methods,
+            NodeType::MeetingTypeQuery => &self.meeting_types,
+            NodeType::ProfessorQuery => &self.professor_prefixes,
+            _ => &[],
+        }
+    }
+
+    /// Check whether a value is one of the known values, ignoring case and accents
+    ///
+    /// Parameters:
+    /// --- ---
+    /// node -> The query node type
+    /// value -> The value as typed
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// bool -> true if the value is known
+    /// --- ---
+    pub fn contains(&self, node: &NodeType, value: &str) -> bool {
+        let folded = fold_text(value);
+        self.values_for(node)
+            .iter()
+            .any(|known| fold_text(known) == folded)
+    }
+
+    /// Get the known values starting with a prefix, ignoring case and accents
+    ///
+    /// Parameters:
+    /// --- ---
+    /// node -> The query node type
+    /// prefix -> The partially typed value (empty matches every value)
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Vec<&str> -> The matching values, in sorted order
+    /// --- ---
+    pub fn starting_with(&self, node: &NodeType, prefix: &str) -> Vec<&str> {
+        let folded = fold_text(prefix);
+        self.values_for(node)
+            .iter()
+            .filter(|known| fold_text(known).starts_with(&folded))
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Get the known values closest to a misspelled one
+    ///
+    /// Values are ranked by edit distance and only the closest ones are returned, so
+    /// "CMTP" suggests "CMPT" rather than every subject starting with "C". A value needing
+    /// more than one edit per three characters isn't offered, since it's more likely a
+    /// different word than a typo
+    ///
+    /// Parameters:
+    /// --- ---
+    /// node -> The query node type
+    /// value -> The misspelled value
+    /// limit -> Maximum number of values to return
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Vec<String> -> The closest values, empty if none is close enough
+    /// --- ---
+    pub fn closest(&self, node: &NodeType, value: &str, limit: usize) -> Vec<String> {
+        let allowed = (value.chars().count() / 3).max(1);
+        let ranked: Vec<(usize, &String)> = self
+            .values_for(node)
+            .iter()
+            .map(|known| (edit_distance(value, known), known))
+            .filter(|(distance, _)| *distance <= allowed)
+            .collect();
+        let nearest = ranked.iter().map(|(distance, _)| *distance).min();
+        // values are already sorted, so equally close ones stay in alphabetical order
+        ranked
+            .into_iter()
+            .filter(|(distance, _)| Some(*distance) == nearest)
+            .take(limit)
+            .map(|(_, known)| known.clone())
+            .collect()
+    }
+}
+
+/// Read the lookup lists from a database
+///
+/// Parameters:
+/// --- ---
+/// db_path -> Path to the SQLite database file (a missing file gives empty lists)
+/// school_id -> School to read values for, or None for every school
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<Lookups, String> -> The lookup lists or error message
+/// --- ---
+pub fn load_lookups(db_path: &Path, school_id: Option<&str>) -> Result<Lookups, String> {
+    // opening a missing file would create an empty database before the first sync
+    if !db_path.exists() {
+        return Ok(Lookups::default());
+    }
+    let conn = open_read_connection(db_path)?;
+
+    let professor_names = distinct_values(&conn, "professors", "name", school_id)?;
+    let professor_prefixes: BTreeSet<String> = professor_names
+        .iter()
+        .map(|name| name.split(',').next().unwrap_or(name).trim().to_string())
+        .filter(|surname| !surname.is_empty())
+        .collect();
+
+    Ok(Lookups {
+        subjects: distinct_values(&conn, "sections", "subject_code", school_id)?,
+        campuses: distinct_values(&conn, "sections", "campus", school_id)?,
+        instruction_methods: distinct_values(&conn, "sections", "instruction_method", school_id)?,
+        meeting_types: distinct_values(&conn, "meeting_times", "meeting_type", school_id)?,
+        professor_prefixes: professor_prefixes.into_iter().collect(),
+    })
+}
+
+/// Read the distinct non-empty values of a column
+///
+/// Parameters:
+/// --- ---
+/// conn -> Open database connection
+/// table -> Table to read (must have a school_id column)
+/// column -> Column to read
+/// school_id -> School to read values for, or None for every school
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<Vec<String>, String> -> The values, sorted, or error message
+/// --- ---
+fn distinct_values(
+    conn: &Connection,
+    table: &str,
+    column: &str,
+    school_id: Option<&str>,
+) -> Result<Vec<String>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT DISTINCT {column} FROM {table} \
+             WHERE {column} IS NOT NULL AND TRIM({column}) != '' \
+             AND (?1 IS NULL OR school_id = ?1) ORDER BY {column}"
+        ))
+        .map_err(|e| format!("SQL preparation error: {}", e))?;
+    let values = stmt
+        .query_map([school_id], |row| row.get::<_, String>(0))
+        .map_err(|e| format!("Query execution error: {}", e))?
+        .filter_map(Result::ok)
+        .collect();
+    Ok(values)
+}
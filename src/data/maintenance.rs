@@ -0,0 +1,141 @@
+/*
+    src/data/maintenance.rs
+
+    Module for database maintenance
+    Repeated syncs leave free pages and stale indexes behind, so this compacts,
+    checks, and reindexes the SQLite file
+*/
+
+use std::fs;
+use std::path::Path;
+
+use crate::data::catalog::create_catalog_index;
+use crate::data::pool::{checkpoint, open_write_connection};
+use crate::data::professors::normalize_professor_names;
+
+/// Result of running database maintenance
+///
+/// Fields:
+/// --- ---
+/// size_before -> Database file size before maintenance in bytes
+/// size_after -> Database file size after maintenance in bytes
+/// integrity_problems -> Problems reported by the integrity check (empty if the database is healthy)
+/// --- ---
+#[derive(Debug, Clone, Default)]
+pub struct MaintenanceReport {
+    pub size_before: u64,
+    pub size_after: u64,
+    pub integrity_problems: Vec<String>,
+}
+
+impl MaintenanceReport {
+    /// Get the number of bytes reclaimed by maintenance
+    ///
+    /// Returns:
+    /// --- ---
+    /// u64 -> Reduction in database file size
+    /// --- ---
+    pub fn bytes_reclaimed(&self) -> u64 {
+        self.size_before.saturating_sub(self.size_after)
+    }
+
+    /// Summarize the report in one line
+    ///
+    /// Returns:
+    /// --- ---
+    /// String -> Human readable summary (e.g., "Reclaimed 1.2 MB (4.8 MB -> 3.6 MB), integrity ok")
+    /// --- ---
+    pub fn summary(&self) -> String {
+        let integrity = if self.integrity_problems.is_empty() {
+            "integrity ok".to_string()
+        } else {
+            format!(
+                "{} integrity problem(s) found",
+                self.integrity_problems.len()
+            )
+        };
+        format!(
+            "Reclaimed {} ({} -> {}), {}",
+            format_bytes(self.bytes_reclaimed()),
+            format_bytes(self.size_before),
+            format_bytes(self.size_after),
+            integrity
+        )
+    }
+}
+
+/// Format a byte count for display
+///
+/// Parameters:
+/// --- ---
+/// bytes -> Number of bytes
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> Size in the largest fitting unit (e.g., "512 B", "4.8 MB")
+/// --- ---
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 3] = ["KB", "MB", "GB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+/// Run VACUUM, an integrity check, and REINDEX on a database
+///
+/// Parameters:
+/// --- ---
+/// db_path -> Path to the SQLite database file
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<MaintenanceReport, String> -> Sizes and integrity results, or error message
+/// --- ---
+pub fn maintain_database(db_path: &Path) -> Result<MaintenanceReport, String> {
+    let size_before = fs::metadata(db_path)
+        .map(|m| m.len())
+        .map_err(|e| format!("Failed to read database file: {}", e))?;
+
+    let conn = open_write_connection(db_path)?;
+
+    // check before rewriting the file so problems are reported against the original
+    let mut stmt = conn
+        .prepare("PRAGMA integrity_check")
+        .map_err(|e| format!("SQL preparation error: {}", e))?;
+    let integrity_problems: Vec<String> = stmt
+        .query_map([], |row| row.get::<_, String>(0))
+        .map_err(|e| format!("Integrity check failed: {}", e))?
+        .filter_map(Result::ok)
+        .filter(|line| line != "ok")
+        .collect();
+    drop(stmt);
+
+    // databases synced before the catalog browser existed don't have its index yet, and
+    // ones synced before names were normalized have professors' names as the school sent them
+    create_catalog_index(&conn)?;
+    normalize_professor_names(&conn)?;
+    conn.execute_batch("REINDEX; VACUUM;")
+        .map_err(|e| format!("Failed to compact database: {}", e))?;
+    drop(conn);
+    // in WAL mode the compacted pages only reach the file once the log is checkpointed
+    checkpoint(db_path)?;
+
+    let size_after = fs::metadata(db_path)
+        .map(|m| m.len())
+        .map_err(|e| format!("Failed to read database file: {}", e))?;
+
+    Ok(MaintenanceReport {
+        size_before,
+        size_after,
+        integrity_problems,
+    })
+}
@@ -5,6 +5,27 @@
 
 */
 
+pub mod app_store;
+pub mod archive;
+pub mod bench;
+pub mod campus_aliases;
+pub mod catalog;
+pub mod duplicates;
+pub mod free_time;
+pub mod index_advisor;
+pub mod lookups;
+pub mod maintenance;
 pub mod pool;
+pub mod professors;
+pub mod profiles;
+pub mod query_log;
+pub mod registrar_import;
+pub mod result_diff;
+pub mod sample;
 pub mod sql;
+pub mod stats;
 pub mod sync;
+pub mod term_index;
+pub mod warm_cache;
+pub mod watch;
+pub mod watch_hook;
@@ -3,11 +3,32 @@
 
     For database connection management
 
-    This module provides a simple database path configuration.
-    The actual connection is handled per-query in sql.rs.
+    This module provides a simple database path configuration and the
+    connection helpers every query and write goes through. Connections wait
+    out short locks, and writes run in WAL mode inside retried transactions
+    so a sync and a search can overlap without "database is locked" errors.
+    Searches read through read-only connections, so only sync and the app's
+    own persistence can change the data.
 */
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+use rusqlite::functions::FunctionFlags;
+use rusqlite::{Connection, OpenFlags, Transaction, TransactionBehavior};
+
+use crate::dsl::codegen::TERM_SPANS_SQL;
+use crate::utils::text;
+
+/// How long a connection waits on a locked database before reporting it busy
+pub const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How many times a write is attempted before giving up on a busy database
+pub const MAX_WRITE_ATTEMPTS: u32 = 4;
+
+/// Delay before the first retry, doubled after each failed attempt
+const RETRY_BACKOFF: Duration = Duration::from_millis(250);
 
 /// Database configuration
 ///
@@ -89,3 +110,296 @@ impl DbConfig {
         &self.db_path
     }
 }
+
+/// Open a connection that waits out short locks
+///
+/// Parameters:
+/// --- ---
+/// db_path -> Path to the SQLite database file
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<Connection, String> -> The connection or error message
+/// --- ---
+///
+pub fn open_connection(db_path: &Path) -> Result<Connection, String> {
+    let conn =
+        Connection::open(db_path).map_err(|e| format!("Database connection error: {}", e))?;
+    prepare_connection(conn)
+}
+
+/// Open a read-only connection for searches and other reads of the class data
+///
+/// SQLite refuses any write on it, so a bug in generated SQL can't change the data, and
+/// a missing file is an error instead of being created empty
+///
+/// Parameters:
+/// --- ---
+/// db_path -> Path to the SQLite database file
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<Connection, String> -> The connection or error message
+/// --- ---
+///
+pub fn open_read_connection(db_path: &Path) -> Result<Connection, String> {
+    let flags = OpenFlags::SQLITE_OPEN_READ_ONLY
+        | OpenFlags::SQLITE_OPEN_URI
+        | OpenFlags::SQLITE_OPEN_NO_MUTEX;
+    let conn = Connection::open_with_flags(db_path, flags)
+        .map_err(|e| format!("Database connection error: {}", e))?;
+    prepare_connection(conn)
+}
+
+/// Set up a newly opened connection to wait out locks and fold text like the app does
+///
+/// Parameters:
+/// --- ---
+/// conn -> The connection
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<Connection, String> -> The connection or error message
+/// --- ---
+///
+fn prepare_connection(conn: Connection) -> Result<Connection, String> {
+    conn.busy_timeout(BUSY_TIMEOUT)
+        .map_err(|e| format!("Database connection error: {}", e))?;
+    register_text_functions(&conn)?;
+    define_term_spans(&conn)?;
+    Ok(conn)
+}
+
+/// Stand in for the term_spans table in databases synced before it was recorded
+///
+/// Searches join term_spans to work out sections' sessions. Without the table, a temporary
+/// view computes the same spans on each search, until the next sync records them. The
+/// view lives in the connection's temporary schema, so read-only connections can make it
+///
+/// Parameters:
+/// --- ---
+/// conn -> The connection to define the view on
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<(), String> -> Success or error message
+/// --- ---
+///
+fn define_term_spans(conn: &Connection) -> Result<(), String> {
+    let (has_meetings, has_spans): (bool, bool) = conn
+        .query_row(
+            "SELECT \
+                EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'meeting_times'), \
+                EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'term_spans')",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .map_err(|e| format!("Database connection error: {}", e))?;
+    if !has_meetings || has_spans {
+        return Ok(());
+    }
+    conn.execute_batch(&format!(
+        "CREATE TEMP VIEW IF NOT EXISTS term_spans AS SELECT * FROM {}",
+        TERM_SPANS_SQL
+    ))
+    .map_err(|e| format!("Failed to define term spans: {}", e))
+}
+
+/// Replace SQLite's LOWER and LIKE with accent- and case-insensitive versions
+///
+/// SQLite only folds ASCII case, so "munoz" would never match "Muñoz". Overriding the
+/// built-ins keeps generated SQL unchanged while every comparison goes through fold_text.
+/// Also defines REGEXP, which SQLite leaves to the application, for "matches" conditions
+///
+/// Parameters:
+/// --- ---
+/// conn -> The connection to register the functions on
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<(), String> -> Success or error message
+/// --- ---
+///
+fn register_text_functions(conn: &Connection) -> Result<(), String> {
+    let flags = FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_DETERMINISTIC;
+    let to_error = |e: rusqlite::Error| format!("Failed to register text functions: {}", e);
+
+    conn.create_scalar_function("lower", 1, flags, |ctx| {
+        Ok(ctx
+            .get::<Option<String>>(0)?
+            .map(|text| text::fold_text(&text)))
+    })
+    .map_err(to_error)?;
+
+    // x LIKE y calls like(y, x), and x LIKE y ESCAPE z calls like(y, x, z)
+    for n_args in [2, 3] {
+        conn.create_scalar_function("like", n_args, flags, |ctx| {
+            let pattern = ctx.get::<Option<String>>(0)?;
+            let value = ctx.get::<Option<String>>(1)?;
+            let escape = if ctx.len() > 2 {
+                ctx.get::<Option<String>>(2)?
+                    .and_then(|escape| escape.chars().next())
+            } else {
+                None
+            };
+            Ok(match (pattern, value) {
+                (Some(pattern), Some(value)) => Some(text::like_match(&pattern, &value, escape)),
+                _ => None,
+            })
+        })
+        .map_err(to_error)?;
+    }
+
+    // x REGEXP y calls regexp(y, x); the compiled pattern is kept for the rest of the query
+    conn.create_scalar_function("regexp", 2, flags, |ctx| {
+        let regex = ctx.get_or_create_aux(0, |pattern| {
+            let pattern = pattern.as_str().map_err(|e| e.to_string())?;
+            text::compile_pattern(pattern).map_err(|reason| format!("Invalid pattern: {}", reason))
+        })?;
+        Ok(ctx
+            .get::<Option<String>>(1)?
+            .map(|value| regex.is_match(&value) || regex.is_match(&text::fold_text(&value))))
+    })
+    .map_err(to_error)?;
+
+    Ok(())
+}
+
+/// Open a connection for writing, switching the database to WAL mode
+///
+/// WAL lets searches keep reading the last committed data while a write is in progress.
+/// The mode is stored in the database file, so connections opened elsewhere (like
+/// classy-sync's) use it too
+///
+/// Parameters:
+/// --- ---
+/// db_path -> Path to the SQLite database file
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<Connection, String> -> The connection or error message
+/// --- ---
+///
+pub fn open_write_connection(db_path: &Path) -> Result<Connection, String> {
+    let conn = open_connection(db_path)?;
+    conn.query_row("PRAGMA journal_mode = WAL", [], |_| Ok(()))
+        .map_err(|e| format!("Failed to enable WAL mode: {}", e))?;
+    Ok(conn)
+}
+
+/// Check whether an error message means the database was locked by another connection
+///
+/// Parameters:
+/// --- ---
+/// message -> The error message
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// bool -> true if retrying later may succeed
+/// --- ---
+///
+pub fn is_busy_error(message: &str) -> bool {
+    let message = message.to_lowercase();
+    message.contains("database is locked")
+        || message.contains("database table is locked")
+        || message.contains("database is busy")
+}
+
+/// Run an operation, retrying with backoff while the database is locked
+///
+/// Parameters:
+/// --- ---
+/// on_retry -> Called with a progress message before each retry
+/// op -> The operation to run; it must be safe to run again after a busy error
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<T, String> -> The operation's result, or its last error
+/// --- ---
+///
+pub fn retry_when_busy<T>(
+    on_retry: &mut dyn FnMut(&str),
+    mut op: impl FnMut() -> Result<T, String>,
+) -> Result<T, String> {
+    let mut delay = RETRY_BACKOFF;
+    let mut attempt = 1;
+    loop {
+        match op() {
+            Err(e) if is_busy_error(&e) && attempt < MAX_WRITE_ATTEMPTS => {
+                on_retry(&format!(
+                    "Database busy, retrying ({}/{})...",
+                    attempt,
+                    MAX_WRITE_ATTEMPTS - 1
+                ));
+                thread::sleep(delay);
+                delay *= 2;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Run a batch of writes in a single transaction, retrying while the database is locked
+///
+/// The transaction takes the write lock up front (BEGIN IMMEDIATE) so a conflict is
+/// reported before any work is done, and a failed attempt is rolled back before retrying
+///
+/// Parameters:
+/// --- ---
+/// db_path -> Path to the SQLite database file
+/// on_retry -> Called with a progress message before each retry
+/// write -> The writes to run inside the transaction
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<T, String> -> The value returned by write, or error message
+/// --- ---
+///
+pub fn write_transaction<T>(
+    db_path: &Path,
+    on_retry: &mut dyn FnMut(&str),
+    mut write: impl FnMut(&Transaction) -> Result<T, String>,
+) -> Result<T, String> {
+    retry_when_busy(on_retry, || {
+        // switching to WAL needs the lock too, so it is part of each attempt
+        let mut conn = open_write_connection(db_path)?;
+        let tx = conn
+            .transaction_with_behavior(TransactionBehavior::Immediate)
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        let value = write(&tx)?;
+        tx.commit()
+            .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+        Ok(value)
+    })
+}
+
+/// Fold the write-ahead log back into the database file
+///
+/// Needed before the file is copied or replaced, otherwise committed writes still in
+/// the log would be lost or applied to the wrong file
+///
+/// Parameters:
+/// --- ---
+/// db_path -> Path to the SQLite database file
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<(), String> -> Ok on success or error message
+/// --- ---
+///
+pub fn checkpoint(db_path: &Path) -> Result<(), String> {
+    let conn = open_connection(db_path)?;
+    conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))
+        .map_err(|e| format!("Failed to checkpoint database: {}", e))
+}
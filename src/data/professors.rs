@@ -0,0 +1,244 @@
+/*
+    src/data/professors.rs
+
+    Module for the professor directory
+    Lists the professors teaching in a term with their department, email, and number of
+    sections, and a professor's sections for the directory's drill-down. Professors have no
+    department in the synced data, so each is given the subject they teach the most sections of.
+    After a sync, professors' first and last names are split again from their full names, so
+    searches can match either one without trusting the synced columns
+*/
+
+use rusqlite::{params, Connection};
+
+use crate::data::catalog::CatalogScope;
+use crate::data::pool::open_read_connection;
+use crate::data::sql::{execute_query, Class};
+use crate::dsl::codegen::generate_professor_sections_sql;
+use crate::utils::text::{fold_text, split_name};
+
+/// A professor in the directory
+///
+/// Fields:
+/// --- ---
+/// id -> Professor ID in the synced data
+/// school_id -> School the professor teaches at
+/// name -> Name as synced, e.g., "Suma, Aaron"
+/// email -> Email address, if known
+/// department -> Subject the professor teaches the most sections of, e.g., "Computing Technology"
+/// sections -> Number of sections taught in the term
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for ProfessorEntry
+/// Clone -> Clone trait for ProfessorEntry
+/// PartialEq -> PartialEq trait for ProfessorEntry
+/// --- ---
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProfessorEntry {
+    pub id: String,
+    pub school_id: String,
+    pub name: String,
+    pub email: Option<String>,
+    pub department: Option<String>,
+    pub sections: usize,
+}
+
+/// Fetch the professors teaching in a term, in order of their names ("Last, First")
+///
+/// Parameters:
+/// --- ---
+/// scope -> The database, school, and term to list
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<Vec<ProfessorEntry>, String> -> The professors with their departments and sections
+/// --- ---
+pub fn fetch_professors(scope: &CatalogScope) -> Result<Vec<ProfessorEntry>, String> {
+    let conn = open_read_connection(&scope.db_path)?;
+    let (conditions, values) = scope.filters();
+    let where_clause = if conditions.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {} ", conditions.join(" AND "))
+    };
+
+    // one row per professor and subject, so the department can be picked from the counts
+    let sql = format!(
+        "SELECT p.id, p.school_id, p.name, p.email_address, s.subject_code, \
+            MAX(c.subject_description), COUNT(*) \
+         FROM sections s \
+         JOIN professors p ON s.primary_professor_id = p.id AND s.school_id = p.school_id \
+         LEFT JOIN courses c ON s.school_id = c.school_id \
+            AND s.subject_code = c.subject_code \
+            AND s.course_number = c.number \
+         {}GROUP BY p.school_id, p.id, s.subject_code \
+         ORDER BY p.name, p.school_id, p.id, s.subject_code",
+        where_clause
+    );
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| format!("SQL preparation error: {}", e))?;
+    let rows = stmt
+        .query_map(rusqlite::params_from_iter(values), |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, Option<String>>(3)?,
+                row.get::<_, String>(4)?,
+                row.get::<_, Option<String>>(5)?,
+                row.get::<_, i64>(6)? as usize,
+            ))
+        })
+        .map_err(|e| format!("Query execution error: {}", e))?;
+
+    let mut professors: Vec<ProfessorEntry> = Vec::new();
+    // sections in the subject the current department was picked from
+    let mut department_sections = 0;
+    for row in rows {
+        let (id, school_id, name, email, subject_code, description, count) =
+            row.map_err(|e| format!("Error reading row: {}", e))?;
+        let department = description
+            .filter(|d| !d.is_empty())
+            .unwrap_or(subject_code);
+        match professors.last_mut() {
+            Some(last) if last.id == id && last.school_id == school_id => {
+                last.sections += count;
+                // ties go to the subject listed first
+                if count > department_sections {
+                    last.department = Some(department);
+                    department_sections = count;
+                }
+            }
+            _ => {
+                professors.push(ProfessorEntry {
+                    id,
+                    school_id,
+                    name,
+                    email: email.filter(|e| !e.is_empty()),
+                    department: Some(department),
+                    sections: count,
+                });
+                department_sections = count;
+            }
+        }
+    }
+    Ok(professors)
+}
+
+/// Fetch the sections a professor teaches in a term
+///
+/// Parameters:
+/// --- ---
+/// scope -> The database, school, and term to list
+/// professor -> The professor
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<Vec<Class>, String> -> The sections by subject, course, and sequence
+/// --- ---
+pub fn fetch_professor_sections(
+    scope: &CatalogScope,
+    professor: &ProfessorEntry,
+) -> Result<Vec<Class>, String> {
+    let sql = generate_professor_sections_sql(
+        Some(&professor.school_id),
+        scope.term_id.as_deref(),
+        &professor.id,
+    )
+    .map_err(|e| e.to_string())?;
+    let mut sections = execute_query(&sql, &scope.db_path)?;
+    sections.sort_by(|a, b| {
+        (&a.subject_code, &a.course_number, &a.section_sequence).cmp(&(
+            &b.subject_code,
+            &b.course_number,
+            &b.section_sequence,
+        ))
+    });
+    Ok(sections)
+}
+
+/// Filter the directory by what was typed in its search box
+///
+/// Every word typed has to appear in the professor's name, department, or email, ignoring
+/// case and accents, so "aaron suma" finds "Suma, Aaron"
+///
+/// Parameters:
+/// --- ---
+/// professors -> The directory
+/// filter -> The text typed
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Vec<usize> -> Indexes of the matching professors, every one if nothing was typed
+/// --- ---
+pub fn filter_professors(professors: &[ProfessorEntry], filter: &str) -> Vec<usize> {
+    let words: Vec<String> = fold_text(filter)
+        .split_whitespace()
+        .map(str::to_string)
+        .collect();
+    professors
+        .iter()
+        .enumerate()
+        .filter(|(_, professor)| {
+            let haystack = fold_text(&format!(
+                "{} {} {}",
+                professor.name,
+                professor.department.as_deref().unwrap_or(""),
+                professor.email.as_deref().unwrap_or("")
+            ));
+            words.iter().all(|word| haystack.contains(word.as_str()))
+        })
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Fill in professors' first and last names from their full names
+///
+/// The synced first_name and last_name columns can't be relied on (some schools send them
+/// swapped), so both are rewritten from the name with utils::text::split_name
+///
+/// Parameters:
+/// --- ---
+/// conn -> Connection to the database (or a transaction on it)
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<usize, String> -> Number of professors whose names changed, or error message
+/// --- ---
+pub fn normalize_professor_names(conn: &Connection) -> Result<usize, String> {
+    let mut stmt = conn
+        .prepare("SELECT rowid, name, first_name, last_name FROM professors")
+        .map_err(|e| format!("SQL preparation error: {}", e))?;
+    let professors: Vec<(i64, String, Option<String>, Option<String>)> = stmt
+        .query_map([], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+        })
+        .map_err(|e| format!("Query execution error: {}", e))?
+        .collect::<Result<_, _>>()
+        .map_err(|e| format!("Failed to read professor names: {}", e))?;
+
+    let mut update = conn
+        .prepare("UPDATE professors SET first_name = ?1, last_name = ?2 WHERE rowid = ?3")
+        .map_err(|e| format!("SQL preparation error: {}", e))?;
+    let mut changed = 0;
+    for (rowid, name, first_name, last_name) in professors {
+        let (first, last) = split_name(&name);
+        if first_name.as_deref() == Some(first.as_str())
+            && last_name.as_deref() == Some(last.as_str())
+        {
+            continue;
+        }
+        update
+            .execute(params![first, last, rowid])
+            .map_err(|e| format!("Failed to normalize professor names: {}", e))?;
+        changed += 1;
+    }
+    Ok(changed)
+}
@@ -0,0 +1,305 @@
+/*
+    src/data/profiles.rs
+
+    Module for workspace profiles
+    A profile bundles what a line of planning needs (e.g., "CS major" or "minor exploration"):
+    the school and term to search, favorite queries to pull into the search box, constraints
+    every search must also meet, and tags describing it. Profiles are kept in one JSON file;
+    the one in use is remembered with the session
+*/
+
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+/// A workspace profile
+///
+/// Fields:
+/// --- ---
+/// name -> Name of the profile, e.g., "CS major"
+/// school_id -> School selected when switching to the profile (None keeps the current one)
+/// term_id -> Term selected when switching to the profile (None keeps the current one)
+/// queries -> Favorite queries, in the order they are cycled through
+/// constraints -> Conditions every search also has to meet, e.g., "credits >= 3"
+/// tags -> Labels describing the profile, e.g., "major"
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for Profile
+/// Clone -> Clone trait for Profile
+/// PartialEq -> PartialEq trait for Profile
+/// Default -> Default trait for Profile
+/// Serialize -> Serialize trait for Profile
+/// Deserialize -> Deserialize trait for Profile (missing fields are empty)
+/// --- ---
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Profile {
+    pub name: String,
+    pub school_id: Option<String>,
+    pub term_id: Option<String>,
+    pub queries: Vec<String>,
+    pub constraints: Vec<String>,
+    pub tags: Vec<String>,
+}
+
+impl Profile {
+    /// Describe the profile in one line
+    ///
+    /// Parameters:
+    /// --- ---
+    /// None
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// String -> e.g., "CS major [major, cs] · 2 favorites · 1 constraint"
+    /// --- ---
+    pub fn summary(&self) -> String {
+        let mut summary = self.name.clone();
+        if !self.tags.is_empty() {
+            summary.push_str(&format!(" [{}]", self.tags.join(", ")));
+        }
+        for (count, noun) in [
+            (self.queries.len(), "favorite"),
+            (self.constraints.len(), "constraint"),
+        ] {
+            if count > 0 {
+                let plural = if count == 1 { "" } else { "s" };
+                summary.push_str(&format!(" · {} {}{}", count, noun, plural));
+            }
+        }
+        summary
+    }
+
+    /// Add the profile's constraints to a query
+    ///
+    /// Parameters:
+    /// --- ---
+    /// query -> The query as typed
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// String -> The query unchanged if there are no constraints, otherwise the query in
+    ///           parentheses (so its own ORs stay together) ANDed with each constraint,
+    ///           e.g., "(subject is CMPT or subject is MATH) and (credits >= 3)"
+    /// --- ---
+    pub fn constrain(&self, query: &str) -> String {
+        let constraints: Vec<&str> = self
+            .constraints
+            .iter()
+            .map(|constraint| constraint.trim())
+            .filter(|constraint| !constraint.is_empty())
+            .collect();
+        if constraints.is_empty() {
+            return query.to_string();
+        }
+        let mut parts = Vec::with_capacity(constraints.len() + 1);
+        if !query.trim().is_empty() {
+            parts.push(format!("({})", query));
+        }
+        parts.extend(
+            constraints
+                .iter()
+                .map(|constraint| format!("({})", constraint)),
+        );
+        parts.join(" and ")
+    }
+}
+
+/// Map byte ranges in a constrained query back onto the query as typed
+///
+/// Parameters:
+/// --- ---
+/// query -> The query as typed
+/// positions -> Byte ranges in the constrained query (see Profile::constrain)
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Vec<(usize, usize)> -> The ranges that fall in the typed query, shifted past the opening
+///                        parenthesis; ranges in the constraints are dropped
+/// --- ---
+pub fn unconstrain_positions(query: &str, positions: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    positions
+        .iter()
+        .filter(|(start, _)| *start >= 1 && *start <= query.len())
+        .map(|(start, end)| (start - 1, (end - 1).min(query.len())))
+        .collect()
+}
+
+/// The saved profiles and the one in use
+///
+/// Fields:
+/// --- ---
+/// profiles -> Profiles in the order they are switched through
+/// active -> Name of the profile in use, None for no profile (not saved in the file)
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for Profiles
+/// Clone -> Clone trait for Profiles
+/// PartialEq -> PartialEq trait for Profiles
+/// Default -> Default trait for Profiles
+/// Serialize -> Serialize trait for Profiles
+/// Deserialize -> Deserialize trait for Profiles
+/// --- ---
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Profiles {
+    pub profiles: Vec<Profile>,
+    #[serde(skip)]
+    pub active: Option<String>,
+}
+
+impl Profiles {
+    /// Load profiles from a file
+    ///
+    /// Parameters:
+    /// --- ---
+    /// path -> Path to the profiles file
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Result<Self, String> -> The profiles (none if the file doesn't exist) or error
+    /// --- ---
+    pub fn load(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(Profiles::default());
+        }
+        let content =
+            fs::read_to_string(path).map_err(|e| format!("Failed to read profiles: {}", e))?;
+        serde_json::from_str(&content).map_err(|e| format!("Failed to parse profiles: {}", e))
+    }
+
+    /// Save the profiles to a file
+    ///
+    /// Parameters:
+    /// --- ---
+    /// path -> Path to the profiles file
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Result<(), String> -> Ok on success, error message on failure
+    /// --- ---
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize profiles: {}", e))?;
+        fs::write(path, content).map_err(|e| format!("Failed to write profiles: {}", e))
+    }
+
+    /// Find a profile by name, ignoring case
+    ///
+    /// Parameters:
+    /// --- ---
+    /// name -> The profile's name
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Option<&Profile> -> The profile, if there is one with that name
+    /// --- ---
+    pub fn find(&self, name: &str) -> Option<&Profile> {
+        self.profiles
+            .iter()
+            .find(|profile| profile.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Get the profile in use
+    ///
+    /// Parameters:
+    /// --- ---
+    /// None
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Option<&Profile> -> The active profile, None if none is in use (or it was removed)
+    /// --- ---
+    pub fn active_profile(&self) -> Option<&Profile> {
+        self.active.as_deref().and_then(|name| self.find(name))
+    }
+
+    /// Add a profile, replacing one with the same name
+    ///
+    /// Parameters:
+    /// --- ---
+    /// profile -> The profile to add
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// bool -> true if a profile with that name was replaced
+    /// --- ---
+    pub fn upsert(&mut self, profile: Profile) -> bool {
+        match self
+            .profiles
+            .iter_mut()
+            .find(|existing| existing.name.eq_ignore_ascii_case(&profile.name))
+        {
+            Some(existing) => {
+                *existing = profile;
+                true
+            }
+            None => {
+                self.profiles.push(profile);
+                false
+            }
+        }
+    }
+
+    /// Remove a profile by name, ignoring case
+    ///
+    /// Parameters:
+    /// --- ---
+    /// name -> The profile's name
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// bool -> true if the profile was removed
+    /// --- ---
+    pub fn remove(&mut self, name: &str) -> bool {
+        let before = self.profiles.len();
+        self.profiles
+            .retain(|profile| !profile.name.eq_ignore_ascii_case(name));
+        if self.active_profile().is_none() {
+            self.active = None;
+        }
+        self.profiles.len() != before
+    }
+
+    /// Switch to the next profile
+    ///
+    /// Goes through the profiles in order, then back to no profile
+    ///
+    /// Parameters:
+    /// --- ---
+    /// None
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Option<&Profile> -> The profile now in use, None if switched to no profile
+    /// --- ---
+    pub fn switch_next(&mut self) -> Option<&Profile> {
+        let next = match self.active_profile() {
+            Some(active) => self
+                .profiles
+                .iter()
+                .position(|profile| profile == active)
+                .map(|idx| idx + 1),
+            None => Some(0),
+        };
+        self.active = next
+            .and_then(|idx| self.profiles.get(idx))
+            .map(|profile| profile.name.clone());
+        self.active_profile()
+    }
+}
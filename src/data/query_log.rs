@@ -0,0 +1,124 @@
+/*
+    src/data/query_log.rs
+
+    Module for the slow query log
+    Searches that take longer than a threshold (CLASSQL_SLOW_QUERY_MS, 500 ms by default)
+    are appended to slow_queries.log next to the class database, with their compile and
+    execution times, so performance complaints can be traced back to the queries behind them
+*/
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::data::sync::get_synced_db_path;
+use crate::dsl::compiler::QueryTiming;
+use crate::utils::time::{now_utc_seconds, Date};
+
+/// Threshold used when CLASSQL_SLOW_QUERY_MS is unset or invalid
+pub const DEFAULT_SLOW_QUERY_MS: u64 = 500;
+
+/// File name of the slow query log
+const SLOW_QUERY_LOG_FILE: &str = "slow_queries.log";
+
+/// Read the slow query threshold from the environment
+///
+/// Returns:
+/// --- ---
+/// Duration -> CLASSQL_SLOW_QUERY_MS as a duration (0 logs every query), or the default
+/// --- ---
+pub fn slow_query_threshold() -> Duration {
+    let millis = std::env::var("CLASSQL_SLOW_QUERY_MS")
+        .ok()
+        .and_then(|value| value.trim().parse().ok())
+        .unwrap_or(DEFAULT_SLOW_QUERY_MS);
+    Duration::from_millis(millis)
+}
+
+/// Get the path of the slow query log
+///
+/// Returns:
+/// --- ---
+/// PathBuf -> slow_queries.log in the same directory as the class database
+/// --- ---
+pub fn get_slow_query_log_path() -> PathBuf {
+    get_synced_db_path().with_file_name(SLOW_QUERY_LOG_FILE)
+}
+
+/// Format a slow query log entry
+///
+/// Parameters:
+/// --- ---
+/// utc_seconds -> When the query ran, in seconds since the Unix epoch
+/// query -> The query text
+/// timing -> How long the query took
+/// results -> Number of classes it matched
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> One line, e.g.
+///     "2025-01-06T14:03:09Z compile_ms=0.412 execute_ms=812.004 results=37 query=prof is Smith"
+/// --- ---
+pub fn format_entry(utc_seconds: i64, query: &str, timing: &QueryTiming, results: usize) -> String {
+    let date = Date::from_days_since_epoch(utc_seconds.div_euclid(86_400));
+    let seconds = utc_seconds.rem_euclid(86_400);
+    let millis = |duration: Duration| duration.as_secs_f64() * 1_000.0;
+    format!(
+        "{}T{:02}:{:02}:{:02}Z compile_ms={:.3} execute_ms={:.3} results={} query={}",
+        date,
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60,
+        millis(timing.compile),
+        millis(timing.execute),
+        results,
+        // keep every entry on one line
+        query.split_whitespace().collect::<Vec<&str>>().join(" ")
+    )
+}
+
+/// Append a query to the slow query log if it took at least the threshold
+///
+/// Parameters:
+/// --- ---
+/// log_path -> The log file (created, along with its directory, if missing)
+/// threshold -> Total time at which a query counts as slow
+/// query -> The query text
+/// timing -> How long the query took
+/// results -> Number of classes it matched
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<bool, String> -> Whether the query was logged, or error message
+/// --- ---
+pub fn record_if_slow(
+    log_path: &Path,
+    threshold: Duration,
+    query: &str,
+    timing: &QueryTiming,
+    results: usize,
+) -> Result<bool, String> {
+    if timing.total() < threshold {
+        return Ok(false);
+    }
+
+    if let Some(parent) = log_path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(log_path)
+        .map_err(|e| format!("Failed to open slow query log: {}", e))?;
+    writeln!(
+        file,
+        "{}",
+        format_entry(now_utc_seconds(), query, timing, results)
+    )
+    .map_err(|e| format!("Failed to write slow query log: {}", e))?;
+    Ok(true)
+}
@@ -0,0 +1,574 @@
+/*
+    src/data/registrar_import.rs
+
+    Module for importing a schedule copied from a registration portal
+    Portals list a student's sections as rows of CRN, course, section, days, and times, in
+    whatever columns and layout the portal uses. Each pasted line is read for the pieces it
+    has (a CRN, a course like "CMPT 101-001", a day string and a time range), lines that only
+    continue a row are folded into it, and every row is matched against the synced catalog
+    with a confidence score saying how sure the match is
+*/
+
+use crate::data::sql::Class;
+use crate::data::term_index::day_mask;
+use crate::utils::time::parse_time;
+use std::cmp::Reverse;
+
+/// Lowest confidence (percent) of a match that is added to the cart without a second look
+pub const MIN_CONFIDENCE: u8 = 75;
+
+/// Ways portals write am/pm after a time, longest first, and the suffix parse_time reads
+const MERIDIEMS: [(&str, &str); 6] = [
+    ("a.m.", "am"),
+    ("p.m.", "pm"),
+    ("a.m", "am"),
+    ("p.m", "pm"),
+    ("am", "am"),
+    ("pm", "pm"),
+];
+
+/// A row of a pasted schedule
+///
+/// Fields:
+/// --- ---
+/// text -> The pasted line(s) the row was read from
+/// crn -> Registration number, e.g., "10042"
+/// subject -> Subject code, e.g., "CMPT"
+/// number -> Course number, e.g., "101L"
+/// section -> Section, e.g., "001"
+/// days -> Bitmask of the days the row meets (see term_index::day_mask), None if not given
+/// times -> (start_minutes, end_minutes) of the row's first meeting, None if not given
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for PastedRow
+/// Clone -> Clone trait for PastedRow
+/// PartialEq -> PartialEq trait for PastedRow
+/// Default -> A row with nothing read
+/// --- ---
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PastedRow {
+    pub text: String,
+    pub crn: Option<String>,
+    pub subject: Option<String>,
+    pub number: Option<String>,
+    pub section: Option<String>,
+    pub days: Option<u8>,
+    pub times: Option<(i32, i32)>,
+}
+
+impl PastedRow {
+    /// Describe what was read from the row
+    ///
+    /// Returns:
+    /// --- ---
+    /// String -> e.g., "CRN 10042 · CMPT 101-001", or the pasted text if neither was read
+    /// --- ---
+    pub fn label(&self) -> String {
+        let course = match (&self.subject, &self.number, &self.section) {
+            (Some(subject), Some(number), Some(section)) => {
+                Some(format!("{} {}-{}", subject, number, section))
+            }
+            (Some(subject), Some(number), None) => Some(format!("{} {}", subject, number)),
+            _ => None,
+        };
+        let crn = self.crn.as_ref().map(|crn| format!("CRN {}", crn));
+        let parts: Vec<String> = crn.into_iter().chain(course).collect();
+        if parts.is_empty() {
+            self.text.clone()
+        } else {
+            parts.join(" · ")
+        }
+    }
+
+    /// Check whether the row names a course
+    ///
+    /// Returns:
+    /// --- ---
+    /// bool -> true if a subject and number were read
+    /// --- ---
+    fn has_course(&self) -> bool {
+        self.subject.is_some() && self.number.is_some()
+    }
+}
+
+/// A pasted row and the section it was matched to
+///
+/// Fields:
+/// --- ---
+/// row -> The pasted row
+/// class -> The matched section, None if nothing (or more than one section) fits
+/// confidence -> How sure the match is, 0 to 100
+/// reason -> What the match rests on, or why there is none
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for PasteMatch
+/// Clone -> Clone trait for PasteMatch
+/// --- ---
+#[derive(Debug, Clone)]
+pub struct PasteMatch {
+    pub row: PastedRow,
+    pub class: Option<Class>,
+    pub confidence: u8,
+    pub reason: String,
+}
+
+impl PasteMatch {
+    /// Check whether the match is sure enough to add without a second look
+    ///
+    /// Returns:
+    /// --- ---
+    /// bool -> true if there is a section and the confidence is at least MIN_CONFIDENCE
+    /// --- ---
+    pub fn is_confident(&self) -> bool {
+        self.class.is_some() && self.confidence >= MIN_CONFIDENCE
+    }
+}
+
+/// Read the rows of a pasted schedule
+///
+/// A line with a CRN or a course starts a row. A line with neither, or with only pieces
+/// the previous row is missing (portals that put the CRN, course, and times on lines of
+/// their own), continues the previous row. Lines with nothing to read, like column
+/// headers, are skipped
+///
+/// Parameters:
+/// --- ---
+/// text -> The pasted text
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Vec<PastedRow> -> The rows, in the order pasted
+/// --- ---
+pub fn parse_pasted_rows(text: &str) -> Vec<PastedRow> {
+    let mut rows: Vec<PastedRow> = Vec::new();
+    for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        let read = read_line(line);
+        let has_course = read.has_course();
+        let starts_row = read.crn.is_some() || has_course;
+        if !starts_row && read.times.is_none() {
+            continue;
+        }
+
+        let continues = rows.last().is_some_and(|row| {
+            (read.crn.is_none() || row.crn.is_none())
+                && (!has_course || !row.has_course())
+                && (read.times.is_none() || row.times.is_none() || !starts_row)
+        });
+        match rows.last_mut() {
+            Some(row) if continues => {
+                row.text = format!("{} {}", row.text, line);
+                if row.crn.is_none() {
+                    row.crn = read.crn;
+                }
+                if !row.has_course() && has_course {
+                    row.subject = read.subject;
+                    row.number = read.number;
+                    row.section = read.section;
+                }
+                // a second meeting (e.g., a lab) of the same row keeps the first one's times
+                if row.times.is_none() {
+                    row.days = read.days;
+                    row.times = read.times;
+                }
+            }
+            // times with no row to belong to
+            _ if !starts_row => {}
+            _ => rows.push(read),
+        }
+    }
+    rows
+}
+
+/// Match pasted rows against the sections of the catalog
+///
+/// A matching CRN is certain unless the row names another course. Without one, the
+/// course and section, or the course and meeting time, pick the section; a course with
+/// a single section is taken with less confidence. When several sections fit equally
+/// well, none is picked
+///
+/// Parameters:
+/// --- ---
+/// rows -> The pasted rows
+/// classes -> The sections of the term
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Vec<PasteMatch> -> A match for every row, in the same order
+/// --- ---
+pub fn match_pasted_rows(rows: Vec<PastedRow>, classes: &[Class]) -> Vec<PasteMatch> {
+    rows.into_iter()
+        .map(|row| {
+            let mut scored: Vec<(u8, &'static str, &Class)> = classes
+                .iter()
+                .filter_map(|class| {
+                    score(&row, class).map(|(score, reason)| (score, reason, class))
+                })
+                .collect();
+            scored.sort_by_key(|(score, _, _)| Reverse(*score));
+
+            match scored.as_slice() {
+                [] => PasteMatch {
+                    reason: if row.crn.is_some() || row.has_course() {
+                        "not in the synced catalog".to_string()
+                    } else {
+                        "no CRN or course to match".to_string()
+                    },
+                    row,
+                    class: None,
+                    confidence: 0,
+                },
+                [(best, _, _), (second, _, _), ..] if best == second => {
+                    let ties = scored.iter().filter(|(score, _, _)| score == best).count();
+                    PasteMatch {
+                        reason: format!(
+                            "{} sections fit, add the section or CRN to pick one",
+                            ties
+                        ),
+                        row,
+                        class: None,
+                        confidence: 0,
+                    }
+                }
+                [(best, reason, class), ..] => PasteMatch {
+                    class: Some((*class).clone()),
+                    confidence: *best,
+                    reason: reason.to_string(),
+                    row,
+                },
+            }
+        })
+        .collect()
+}
+
+/// Score how well a section fits a pasted row
+///
+/// Parameters:
+/// --- ---
+/// row -> The pasted row
+/// class -> The section
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Option<(u8, &'static str)> -> Confidence and what it rests on, None if the section
+///                               shares neither the CRN nor the course
+/// --- ---
+fn score(row: &PastedRow, class: &Class) -> Option<(u8, &'static str)> {
+    let same_crn = row.crn.is_some() && row.crn == class.crn;
+    let same_course = row
+        .subject
+        .as_deref()
+        .is_some_and(|subject| subject.eq_ignore_ascii_case(&class.subject_code))
+        && row
+            .number
+            .as_deref()
+            .is_some_and(|number| number.eq_ignore_ascii_case(&class.course_number));
+
+    if same_crn {
+        return Some(if row.has_course() && !same_course {
+            (50, "CRN, but the course differs")
+        } else {
+            (100, "CRN")
+        });
+    }
+    if !same_course {
+        return None;
+    }
+
+    let same_section = row
+        .section
+        .as_deref()
+        .map(|section| same_section(section, &class.section_sequence));
+    let (score, reason) = match (same_section, same_time(row, class)) {
+        (Some(true), Some(false)) => (60, "course and section, but the time differs"),
+        (Some(true), _) => (90, "course and section"),
+        (Some(false), _) => return None,
+        (None, Some(true)) => (80, "course and meeting time"),
+        (None, Some(false)) => (30, "course, but no section meets then"),
+        (None, None) => (75, "only section of the course"),
+    };
+    // a CRN the catalog disagrees with may be from another term
+    if row.crn.is_some() && class.crn.is_some() {
+        return Some((score - 10, "course, but the CRN differs"));
+    }
+    Some((score, reason))
+}
+
+/// Compare section labels, ignoring case and leading zeros ("1" is "001")
+///
+/// Parameters:
+/// --- ---
+/// pasted -> The section as pasted
+/// section -> The section in the catalog
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// bool -> true if they name the same section
+/// --- ---
+fn same_section(pasted: &str, section: &str) -> bool {
+    let trim = |label: &str| label.trim_start_matches('0').to_uppercase();
+    trim(pasted) == trim(section)
+}
+
+/// Check whether a section meets when the row says it does
+///
+/// Parameters:
+/// --- ---
+/// row -> The pasted row
+/// class -> The section
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Option<bool> -> Whether any meeting has the row's times (and days, if given), None if
+///                 the row has no times
+/// --- ---
+fn same_time(row: &PastedRow, class: &Class) -> Option<bool> {
+    let (start, end) = row.times?;
+    Some(class.meeting_times.iter().any(|meeting| {
+        meeting.start_minutes == start
+            && meeting.end_minutes == end
+            && row
+                .days
+                .is_none_or(|days| day_mask(&meeting.days.concat()) == Some(days))
+    }))
+}
+
+/// Read the pieces of one pasted line
+///
+/// Parameters:
+/// --- ---
+/// line -> The line, trimmed
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// PastedRow -> What was found in the line
+/// --- ---
+fn read_line(line: &str) -> PastedRow {
+    let mut row = PastedRow {
+        text: line.to_string(),
+        ..PastedRow::default()
+    };
+
+    // times first, so their digits aren't read as anything else
+    let times = find_times(line);
+    if let [(first_at, start), (_, end), ..] = times[..] {
+        row.times = Some((start, end));
+        row.days = days_before(&line[..first_at]);
+    }
+    let untimed = times
+        .first()
+        .map_or(line, |(first_at, _)| &line[..*first_at]);
+
+    let words: Vec<&str> = untimed
+        .split(|c: char| c.is_whitespace() || c == ',' || c == '|')
+        .filter(|word| !word.is_empty())
+        .collect();
+    let mut i = 0;
+    while i < words.len() {
+        let word = words[i].trim_matches(|c: char| !c.is_ascii_alphanumeric());
+        i += 1;
+
+        // "CRN:10042" or a CRN column
+        let digits = word.strip_prefix("CRN").map_or(word, |rest| {
+            rest.trim_start_matches(|c: char| !c.is_ascii_alphanumeric())
+        });
+        if row.crn.is_none() && digits.len() == 5 && digits.chars().all(|c| c.is_ascii_digit()) {
+            row.crn = Some(digits.to_string());
+            continue;
+        }
+        if row.has_course() {
+            continue;
+        }
+
+        // "CMPT:101-001" and "CMPT101" are one word, "CMPT 101 - 001" is several
+        let subject_len = word.chars().take_while(char::is_ascii_uppercase).count();
+        if !(2..=5).contains(&subject_len) {
+            continue;
+        }
+        let (subject, attached) = word.split_at(subject_len);
+        let attached = attached.trim_start_matches([':', '-']);
+        let rest = if attached.is_empty() {
+            match words.get(i) {
+                Some(next) => {
+                    i += 1;
+                    next.trim_matches(|c: char| !c.is_ascii_alphanumeric())
+                        .to_string()
+                }
+                None => continue,
+            }
+        } else {
+            attached.to_string()
+        };
+
+        let (number, section) = match rest.split_once(['-', '.']) {
+            Some((number, section)) => (number, Some(section.to_string())),
+            None => (rest.as_str(), None),
+        };
+        if !is_course_number(number) {
+            // the next word wasn't a course number after all
+            if attached.is_empty() {
+                i -= 1;
+            }
+            continue;
+        }
+        // "101-2" is section 2, but a lone digit in a column of its own may be credits
+        let section = section.filter(|s| is_section(s, 1)).or_else(|| {
+            let mut next = i;
+            if words.get(next) == Some(&"-") {
+                next += 1;
+            }
+            let candidate = words.get(next).filter(|w| is_section(w, 2))?;
+            i = next + 1;
+            Some(candidate.to_string())
+        });
+
+        row.subject = Some(subject.to_string());
+        row.number = Some(number.to_uppercase());
+        row.section = section.map(|s| s.to_uppercase());
+    }
+    row
+}
+
+/// Check whether a word reads as a course number, e.g., "101" or "103L"
+///
+/// Parameters:
+/// --- ---
+/// word -> The word
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// bool -> true for 3 or 4 digits, optionally followed by one letter
+/// --- ---
+fn is_course_number(word: &str) -> bool {
+    let digits = word.chars().take_while(char::is_ascii_digit).count();
+    let suffix = &word[digits..];
+    (3..=4).contains(&digits)
+        && (suffix.is_empty()
+            || (suffix.len() == 1 && suffix.chars().all(|c| c.is_ascii_alphabetic())))
+}
+
+/// Check whether a word reads as a section, e.g., "001", "L01", or "111"
+///
+/// Parameters:
+/// --- ---
+/// word -> The word
+/// min_len -> The fewest characters to accept
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// bool -> true for min_len to 4 letters and digits with at least one digit
+/// --- ---
+fn is_section(word: &str, min_len: usize) -> bool {
+    (min_len..=4).contains(&word.len())
+        && word.chars().all(|c| c.is_ascii_alphanumeric())
+        && word.chars().any(|c| c.is_ascii_digit())
+}
+
+/// Find the clock times in a line
+///
+/// Parameters:
+/// --- ---
+/// line -> The line
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Vec<(usize, i32)> -> Byte offset and minutes since midnight of each time, e.g.,
+///                      "8:00am", "08:00 AM", "2:30 p.m.", or "14:30"
+/// --- ---
+fn find_times(line: &str) -> Vec<(usize, i32)> {
+    let bytes = line.as_bytes();
+    let mut times = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        // an "h:mm" or "hh:mm" that isn't part of a longer number
+        let starts_number = bytes[i].is_ascii_digit() && (i == 0 || !bytes[i - 1].is_ascii_digit());
+        let hour_len = bytes[i..].iter().take_while(|b| b.is_ascii_digit()).count();
+        let minutes_at = i + hour_len + 1;
+        let is_time = starts_number
+            && (1..=2).contains(&hour_len)
+            && bytes.get(i + hour_len) == Some(&b':')
+            && bytes.len() >= minutes_at + 2
+            && bytes[minutes_at..minutes_at + 2]
+                .iter()
+                .all(u8::is_ascii_digit)
+            && bytes
+                .get(minutes_at + 2)
+                .is_none_or(|b| !b.is_ascii_digit());
+        if !is_time {
+            i += 1;
+            continue;
+        }
+
+        let mut end = minutes_at + 2;
+        let clock = &line[i..end];
+        // "am", "AM", or "a.m." after the minutes, maybe after a space
+        let after = line[end..].trim_start();
+        let meridiem = MERIDIEMS.iter().find(|(written, _)| {
+            after
+                .get(..written.len())
+                .is_some_and(|prefix| prefix.eq_ignore_ascii_case(written))
+        });
+        let time = match meridiem {
+            Some((written, suffix)) => {
+                end = line.len() - after.len() + written.len();
+                parse_time(&format!("{}{}", clock, suffix))
+            }
+            None => parse_time(clock),
+        };
+        if let Some(minutes) = time {
+            times.push((i, minutes));
+        }
+        i = end;
+    }
+    times
+}
+
+/// Read the days written just before a line's first time
+///
+/// Parameters:
+/// --- ---
+/// before -> The line up to its first time
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Option<u8> -> Bitmask of the days, e.g., for "MWF", "M W F", "TR", or "TTh", None if the
+///               words before the time aren't days
+/// --- ---
+fn days_before(before: &str) -> Option<u8> {
+    let day_words: Vec<&str> = before
+        .split_whitespace()
+        .rev()
+        .take_while(|word| !word.is_empty() && word.chars().all(|c| "MTWRFSUHmtwrfsuh".contains(c)))
+        .collect();
+    if day_words.is_empty() {
+        return None;
+    }
+    // registrars write Thursday as R and Sunday as U (or SU)
+    let mut days = String::new();
+    for c in day_words
+        .iter()
+        .rev()
+        .flat_map(|word| word.chars())
+        .map(|c| c.to_ascii_uppercase())
+    {
+        match c {
+            'R' => days.push_str("TH"),
+            'U' if !days.ends_with('S') => days.push_str("SU"),
+            c => days.push(c),
+        }
+    }
+    day_mask(&days)
+}
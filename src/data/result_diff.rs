@@ -0,0 +1,201 @@
+/*
+    src/data/result_diff.rs
+
+    Module for comparing two runs of the same search
+    During registration week the same query is run again and again; the diff says which
+    sections are new since the previous run, which are gone, and which had seats taken or
+    freed, so the changes don't have to be spotted by eye
+*/
+
+use std::collections::{HashMap, HashSet};
+
+use crate::data::sql::Class;
+
+/// Open seats of a section before and after
+///
+/// Fields:
+/// --- ---
+/// before -> Open seats in the previous run, None if the section has no cap or count
+/// after -> Open seats in this run, None if the section has no cap or count
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for SeatChange
+/// Clone -> Clone trait for SeatChange
+/// Copy -> Copy trait for SeatChange
+/// PartialEq -> PartialEq trait for SeatChange
+/// --- ---
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SeatChange {
+    pub before: Option<i32>,
+    pub after: Option<i32>,
+}
+
+impl SeatChange {
+    /// Format the change for a result card
+    ///
+    /// Returns:
+    /// --- ---
+    /// String -> e.g., "seats 3→1", with "?" for an unknown count
+    /// --- ---
+    pub fn label(&self) -> String {
+        let seats = |value: Option<i32>| value.map_or("?".to_string(), |v| v.to_string());
+        format!("seats {}→{}", seats(self.before), seats(self.after))
+    }
+}
+
+/// How a result differs from the previous run
+///
+/// Variants:
+/// --- ---
+/// Added -> The section wasn't in the previous run's results
+/// Seats -> The section's enrollment or cap changed
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for RowChange
+/// Clone -> Clone trait for RowChange
+/// Copy -> Copy trait for RowChange
+/// PartialEq -> PartialEq trait for RowChange
+/// --- ---
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RowChange {
+    Added,
+    Seats(SeatChange),
+}
+
+/// Differences between two runs of a search
+///
+/// Fields:
+/// --- ---
+/// added -> Unique IDs of sections that are new in this run
+/// removed -> Sections from the previous run that are gone, in their previous order
+/// seat_changes -> Seat changes of sections in both runs, by unique ID
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for ResultDiff
+/// Clone -> Clone trait for ResultDiff
+/// Default -> Default trait for ResultDiff
+/// --- ---
+#[derive(Debug, Clone, Default)]
+pub struct ResultDiff {
+    pub added: HashSet<String>,
+    pub removed: Vec<Class>,
+    pub seat_changes: HashMap<String, SeatChange>,
+}
+
+impl ResultDiff {
+    /// Check whether the runs had the same results
+    ///
+    /// Returns:
+    /// --- ---
+    /// bool -> true if nothing was added, removed, or changed seats
+    /// --- ---
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.seat_changes.is_empty()
+    }
+
+    /// Get how a result differs from the previous run
+    ///
+    /// Parameters:
+    /// --- ---
+    /// class -> A result of this run
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Option<RowChange> -> The change, None if the section is unchanged
+    /// --- ---
+    pub fn change_for(&self, class: &Class) -> Option<RowChange> {
+        let id = class.unique_id();
+        if self.added.contains(&id) {
+            return Some(RowChange::Added);
+        }
+        self.seat_changes.get(&id).copied().map(RowChange::Seats)
+    }
+
+    /// Summarize the diff in one line
+    ///
+    /// Returns:
+    /// --- ---
+    /// String -> e.g., "2 new, 1 gone, 3 seat changes", or "no changes"
+    /// --- ---
+    pub fn summary(&self) -> String {
+        if self.is_empty() {
+            return "no changes".to_string();
+        }
+        format!(
+            "{} new, {} gone, {} seat changes",
+            self.added.len(),
+            self.removed.len(),
+            self.seat_changes.len()
+        )
+    }
+}
+
+/// Compare this run's results with the previous run's
+///
+/// Parameters:
+/// --- ---
+/// previous -> Results of the previous run of the same query
+/// current -> Results of this run
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// ResultDiff -> What appeared, disappeared, or changed seats
+/// --- ---
+pub fn diff_results(previous: &[Class], current: &[Class]) -> ResultDiff {
+    let before: HashMap<String, &Class> = previous
+        .iter()
+        .map(|class| (class.unique_id(), class))
+        .collect();
+    let current_ids: HashSet<String> = current.iter().map(Class::unique_id).collect();
+
+    let mut diff = ResultDiff::default();
+    for class in current {
+        let id = class.unique_id();
+        match before.get(&id) {
+            None => {
+                diff.added.insert(id);
+            }
+            Some(old) => {
+                if old.enrollment != class.enrollment || old.max_enrollment != class.max_enrollment
+                {
+                    diff.seat_changes.insert(
+                        id,
+                        SeatChange {
+                            before: open_seats(old),
+                            after: open_seats(class),
+                        },
+                    );
+                }
+            }
+        }
+    }
+    diff.removed = previous
+        .iter()
+        .filter(|class| !current_ids.contains(&class.unique_id()))
+        .cloned()
+        .collect();
+    diff
+}
+
+/// Get the number of open seats in a section
+///
+/// Parameters:
+/// --- ---
+/// class -> The section
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Option<i32> -> Cap minus enrollment (at least 0), None if either is unknown
+/// --- ---
+pub fn open_seats(class: &Class) -> Option<i32> {
+    Some((class.max_enrollment? - class.enrollment?).max(0))
+}
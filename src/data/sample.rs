@@ -0,0 +1,510 @@
+/*
+    src/data/sample.rs
+
+    Module for generating sample class data
+    Fills a database with a synthetic but realistic catalog (subjects, leveled courses
+    with prerequisites, professors, sections, and meeting patterns like MWF and TTh)
+    from a seeded random number generator, so demos, benchmarks, and tests see the
+    same data for the same settings
+*/
+
+use std::fs;
+use std::path::Path;
+
+use crate::data::catalog::create_catalog_index;
+use crate::data::pool::open_connection;
+use crate::utils::text::fold_text;
+
+/// Schema the sample database is created with
+const SCHEMA: &str = include_str!("../data_stores/sqlite/migrations/001.up.sql");
+
+/// School ID used for sample data unless another is chosen
+pub const SAMPLE_SCHOOL_ID: &str = "sample";
+
+/// Subjects courses are spread across, in the order they are used
+const SUBJECTS: &[(&str, &str)] = &[
+    ("CS", "Computer Science"),
+    ("MATH", "Mathematics"),
+    ("PHYS", "Physics"),
+    ("CHEM", "Chemistry"),
+    ("BIO", "Biology"),
+    ("HIST", "History"),
+    ("ENG", "English"),
+    ("ECON", "Economics"),
+    ("PSYC", "Psychology"),
+    ("PHIL", "Philosophy"),
+    ("ART", "Art"),
+    ("MUS", "Music"),
+    ("SPAN", "Spanish"),
+    ("POLS", "Political Science"),
+    ("SOC", "Sociology"),
+    ("ACCT", "Accounting"),
+];
+
+/// Most subjects a sample catalog can have
+pub const MAX_SUBJECTS: usize = SUBJECTS.len();
+
+/// Subjects whose lower-level courses come with a lab
+const LAB_SUBJECTS: &[&str] = &["CS", "PHYS", "CHEM", "BIO"];
+
+/// How course titles start, by course level (100 through 400)
+const TITLE_PREFIXES: &[&[&str]] = &[
+    &["Introduction to", "Foundations of", "Principles of"],
+    &["Intermediate", "Applied", "Methods in"],
+    &["Advanced", "Theory of", "Design in"],
+    &["Topics in", "Seminar in", "Research in"],
+];
+
+/// What course titles are about (the subject's own name is also used)
+const TITLE_TOPICS: &[&str] = &[
+    "Data Analysis",
+    "Systems",
+    "Theory",
+    "Research Methods",
+    "Design",
+    "Modeling",
+];
+
+/// Professor first names
+const FIRST_NAMES: &[&str] = &[
+    "Ana", "José", "Mei", "David", "Priya", "Olga", "Samuel", "Zoë",
+];
+
+/// Professor last names, including accented ones so folded comparisons are exercised
+const LAST_NAMES: &[&str] = &[
+    "Smith",
+    "Muñoz",
+    "Chen",
+    "Okafor",
+    "Novak",
+    "García",
+    "Brown",
+    "Lévesque",
+];
+
+/// Terms sections are offered in: (id, year, season, first day, last day)
+const TERMS: &[(&str, i32, &str, &str, &str)] = &[
+    ("202509", 2025, "Fall", "2025-09-02", "2025-12-12"),
+    ("202601", 2026, "Spring", "2026-01-20", "2026-05-08"),
+];
+
+/// Lecture patterns: (monday, tuesday, wednesday, thursday, friday, saturday), the
+/// minutes each meeting lasts, and the start times (minutes since midnight) it is offered at
+const LECTURE_PATTERNS: &[([bool; 6], i32, &[i32])] = &[
+    // MWF, on the hour from 8am to 2pm
+    (
+        [true, false, true, false, true, false],
+        50,
+        &[480, 540, 600, 660, 720, 780, 840],
+    ),
+    // TTh, every 90 minutes from 8am to 5pm
+    (
+        [false, true, false, true, false, false],
+        75,
+        &[480, 570, 660, 750, 840, 930, 1020],
+    ),
+    // MW afternoons
+    (
+        [true, false, true, false, false, false],
+        75,
+        &[750, 840, 930],
+    ),
+    // one evening a week
+    ([false, false, true, false, false, false], 165, &[1110]),
+    ([false, true, false, false, false, false], 165, &[1110]),
+    // Saturday mornings
+    ([false, false, false, false, false, true], 165, &[540]),
+];
+
+/// Enrollment caps by course level (100 through 400)
+const ENROLLMENT_CAPS: &[&[usize]] = &[
+    &[35, 45, 60, 120],
+    &[30, 35, 45],
+    &[20, 24, 30],
+    &[15, 20, 24],
+];
+
+/// Sample catalog settings
+///
+/// Fields:
+/// --- ---
+/// school_id -> ID of the school the catalog belongs to
+/// school_name -> Display name of the school
+/// subjects -> Number of subjects courses are spread across (1 to MAX_SUBJECTS)
+/// sections -> Number of sections to generate
+/// seed -> Seed for the generated data
+/// --- ---
+#[derive(Debug, Clone)]
+pub struct SampleConfig {
+    pub school_id: String,
+    pub school_name: String,
+    pub subjects: usize,
+    pub sections: usize,
+    pub seed: u64,
+}
+
+impl Default for SampleConfig {
+    fn default() -> Self {
+        SampleConfig {
+            school_id: SAMPLE_SCHOOL_ID.to_string(),
+            school_name: "Sample University".to_string(),
+            subjects: 8,
+            sections: 2000,
+            seed: 42,
+        }
+    }
+}
+
+/// Row counts of a generated catalog
+///
+/// Fields:
+/// --- ---
+/// subjects -> Number of subjects with courses
+/// courses -> Number of courses
+/// professors -> Number of professors
+/// sections -> Number of sections
+/// meetings -> Number of meeting times
+/// --- ---
+#[derive(Debug, Clone, PartialEq)]
+pub struct SampleSummary {
+    pub subjects: usize,
+    pub courses: usize,
+    pub professors: usize,
+    pub sections: usize,
+    pub meetings: usize,
+}
+
+impl SampleSummary {
+    /// Describe the catalog in one line
+    ///
+    /// Returns:
+    /// --- ---
+    /// String -> e.g., "2000 sections of 666 courses in 8 subjects, 200 professors, 2468 meetings"
+    /// --- ---
+    pub fn summary(&self) -> String {
+        format!(
+            "{} sections of {} courses in {} subjects, {} professors, {} meetings",
+            self.sections, self.courses, self.subjects, self.professors, self.meetings
+        )
+    }
+}
+
+/// Small deterministic random number generator (xorshift64*)
+///
+/// Fields:
+/// --- ---
+/// state -> Current generator state (never zero)
+/// --- ---
+struct SeededRng {
+    state: u64,
+}
+
+impl SeededRng {
+    /// Create a generator from a seed
+    ///
+    /// Parameters:
+    /// --- ---
+    /// seed -> The seed (zero is remapped, since xorshift gets stuck at zero)
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// SeededRng -> The generator
+    /// --- ---
+    fn new(seed: u64) -> Self {
+        SeededRng { state: seed.max(1) }
+    }
+
+    /// Get a number in 0..bound
+    ///
+    /// Parameters:
+    /// --- ---
+    /// bound -> Exclusive upper bound (must be greater than zero)
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// usize -> The number
+    /// --- ---
+    fn below(&mut self, bound: usize) -> usize {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        (self.state.wrapping_mul(0x2545_F491_4F6C_DD1D) % bound as u64) as usize
+    }
+
+    /// Pick an item from a slice
+    ///
+    /// Parameters:
+    /// --- ---
+    /// items -> The items to pick from (must not be empty)
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// &T -> The picked item
+    /// --- ---
+    fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    /// Flip a weighted coin
+    ///
+    /// Parameters:
+    /// --- ---
+    /// percent -> Chance of true, out of 100
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// bool -> The result
+    /// --- ---
+    fn chance(&mut self, percent: usize) -> bool {
+        self.below(100) < percent
+    }
+}
+
+/// Format minutes since midnight the way synced meeting times store them
+///
+/// Parameters:
+/// --- ---
+/// minutes -> Minutes since midnight
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> The time as "HH:MM:SS"
+/// --- ---
+fn clock_time(minutes: i32) -> String {
+    format!("{:02}:{:02}:00", minutes / 60, minutes % 60)
+}
+
+/// Create a sample class database
+///
+/// Any existing file at db_path is replaced. Courses and professors scale with the section
+/// count (a third and a tenth of it), course numbers run from the 100 to the 400 level
+/// (101, 201, ..., 102, 202, ...) with each upper-level course requiring the one below it,
+/// and the same config always produces the same data
+///
+/// Parameters:
+/// --- ---
+/// db_path -> Where to write the database
+/// config -> Catalog settings
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<SampleSummary, String> -> Row counts of the catalog, or error message
+/// --- ---
+pub fn generate_catalog(db_path: &Path, config: &SampleConfig) -> Result<SampleSummary, String> {
+    if !(1..=MAX_SUBJECTS).contains(&config.subjects) {
+        return Err(format!(
+            "Subjects must be between 1 and {}, got {}",
+            MAX_SUBJECTS, config.subjects
+        ));
+    }
+    if config.sections == 0 {
+        return Err("Sections must be at least 1".to_string());
+    }
+
+    if db_path.exists() {
+        fs::remove_file(db_path).map_err(|e| format!("Failed to replace database: {}", e))?;
+    }
+
+    let mut conn = open_connection(db_path)?;
+    conn.execute_batch(SCHEMA)
+        .map_err(|e| format!("Failed to create schema: {}", e))?;
+    create_catalog_index(&conn)?;
+
+    let to_error = |e: rusqlite::Error| format!("Failed to generate data: {}", e);
+    let tx = conn.transaction().map_err(to_error)?;
+    let mut rng = SeededRng::new(config.seed);
+    let school_id = config.school_id.as_str();
+
+    tx.execute(
+        "INSERT INTO schools (id, name) VALUES (?1, ?2)",
+        [school_id, config.school_name.as_str()],
+    )
+    .map_err(to_error)?;
+    for (term_id, year, season, _, _) in TERMS {
+        tx.execute(
+            "INSERT INTO term_collections (id, school_id, year, season, name, still_collecting) \
+             VALUES (?1, ?2, ?3, ?4, ?5, 0)",
+            rusqlite::params![
+                term_id,
+                school_id,
+                year,
+                season,
+                format!("{} {}", season, year)
+            ],
+        )
+        .map_err(to_error)?;
+    }
+
+    let professor_count = (config.sections / 10).max(1);
+    for id in 0..professor_count {
+        let first = rng.pick(FIRST_NAMES);
+        let last = rng.pick(LAST_NAMES);
+        tx.execute(
+            "INSERT INTO professors (id, school_id, name, email_address, first_name, last_name) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            rusqlite::params![
+                format!("P{}", id),
+                school_id,
+                format!("{}, {}", last, first),
+                format!(
+                    "{}.{}{}@{}.edu",
+                    fold_text(first),
+                    fold_text(last),
+                    id,
+                    school_id
+                ),
+                first,
+                last
+            ],
+        )
+        .map_err(to_error)?;
+    }
+
+    // courses cycle through the subjects, and within a subject through the four levels,
+    // so "<level><nn>" numbers are unique and every upper-level course has one below it
+    let course_count = (config.sections / 3).max(1);
+    let mut courses = Vec::with_capacity(course_count);
+    for index in 0..course_count {
+        let (subject, description) = SUBJECTS[index % config.subjects];
+        let within_subject = index / config.subjects;
+        let level = within_subject % 4;
+        let series = within_subject / 4;
+        let number = format!("{}{:02}", level + 1, series + 1);
+        let prerequisites = (level > 0).then(|| format!("{} {}{:02}", subject, level, series + 1));
+        let topic = if rng.chance(30) {
+            description
+        } else {
+            rng.pick(TITLE_TOPICS)
+        };
+        let title = format!("{} {}", rng.pick(TITLE_PREFIXES[level]), topic);
+        tx.execute(
+            "INSERT INTO courses (school_id, subject_code, number, subject_description, title, \
+             description, credit_hours, prerequisites) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            rusqlite::params![
+                school_id,
+                subject,
+                number,
+                description,
+                title,
+                format!(
+                    "A {}-level course in {}.",
+                    (level + 1) * 100,
+                    description.to_lowercase()
+                ),
+                [1.0, 3.0, 3.0, 3.0, 4.0][rng.below(5)],
+                prerequisites
+            ],
+        )
+        .map_err(to_error)?;
+        courses.push((subject, number, level));
+    }
+
+    let mut meetings = 0;
+    let mut next_sequence = vec![0usize; courses.len()];
+    for section in 0..config.sections {
+        let course = rng.below(courses.len());
+        let (subject, number, level) = &courses[course];
+        next_sequence[course] += 1;
+        let sequence = format!("{:03}", next_sequence[course]);
+        let (term_id, _, _, start_date, end_date) = *rng.pick(TERMS);
+        let max_enrollment = *rng.pick(ENROLLMENT_CAPS[*level]);
+        // most sections fill up, and some are full
+        let enrollment = if rng.chance(15) {
+            max_enrollment
+        } else {
+            max_enrollment / 2 + rng.below(max_enrollment / 2 + 1)
+        };
+        let method = *rng.pick(&["In Person", "In Person", "In Person", "Online", "Hybrid"]);
+        let campus = if method == "Online" {
+            "Online"
+        } else {
+            *rng.pick(&[
+                "Main Campus",
+                "Main Campus",
+                "North Campus",
+                "Downtown Center",
+            ])
+        };
+
+        tx.execute(
+            "INSERT INTO sections (sequence, term_collection_id, subject_code, course_number, \
+             school_id, max_enrollment, instruction_method, campus, enrollment, \
+             primary_professor_id, other) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, json_object('crn', ?11))",
+            rusqlite::params![
+                sequence,
+                term_id,
+                subject,
+                number,
+                school_id,
+                max_enrollment as i64,
+                method,
+                campus,
+                enrollment as i64,
+                format!("P{}", rng.below(professor_count)),
+                // registration numbers like a registrar's (e.g., 10042), unique in the catalog
+                format!("{}", 10000 + section)
+            ],
+        )
+        .map_err(to_error)?;
+
+        // a lecture, plus a weekly afternoon lab for some lower-level science courses
+        let (days, length, starts) = *rng.pick(LECTURE_PATTERNS);
+        let start = *rng.pick(starts);
+        let mut section_meetings = vec![("LEC", days, start, start + length)];
+        if LAB_SUBJECTS.contains(subject) && *level < 2 && rng.chance(60) {
+            let mut lab_days = [false; 6];
+            lab_days[rng.below(5)] = true;
+            let lab_start = *rng.pick(&[780, 840, 900]);
+            section_meetings.push(("LAB", lab_days, lab_start, lab_start + 110));
+        }
+
+        for (meeting, (meeting_type, days, start, end)) in section_meetings.iter().enumerate() {
+            tx.execute(
+                "INSERT INTO meeting_times (sequence, section_sequence, term_collection_id, \
+                 subject_code, course_number, school_id, start_date, end_date, meeting_type, \
+                 start_minutes, end_minutes, is_monday, is_tuesday, is_wednesday, is_thursday, \
+                 is_friday, is_saturday, is_sunday) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, \
+                 ?17, 0)",
+                rusqlite::params![
+                    meeting as i64,
+                    sequence,
+                    term_id,
+                    subject,
+                    number,
+                    school_id,
+                    format!("{}T00:00:00", start_date),
+                    format!("{}T00:00:00", end_date),
+                    meeting_type,
+                    clock_time(*start),
+                    clock_time(*end),
+                    days[0] as i64,
+                    days[1] as i64,
+                    days[2] as i64,
+                    days[3] as i64,
+                    days[4] as i64,
+                    days[5] as i64
+                ],
+            )
+            .map_err(to_error)?;
+        }
+        meetings += section_meetings.len();
+    }
+
+    tx.commit().map_err(to_error)?;
+
+    Ok(SampleSummary {
+        subjects: config.subjects.min(course_count),
+        courses: course_count,
+        professors: professor_count,
+        sections: config.sections,
+        meetings,
+    })
+}
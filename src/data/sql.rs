@@ -3,11 +3,16 @@
 
     For sql code execution - contains the Class struct and query execution logic
 */
-use rusqlite::Connection;
 use std::path::{Path, PathBuf};
 
+use crate::data::archive::fetch_archived_term_ids;
+use crate::data::pool::open_read_connection;
 use crate::data::sync::get_synced_db_path;
-use crate::tui::widgets::helpers::{format_day_for_display, get_day_order};
+use crate::tui::widgets::helpers::format_day_for_display;
+use crate::utils::time::{
+    day_order, format_12_hour, now_utc_seconds, parse_days, parse_time, ranges_overlap, Date,
+    SchoolTimezone,
+};
 
 /// Class struct
 ///
@@ -24,6 +29,7 @@ use crate::tui::widgets::helpers::{format_day_for_display, get_day_order};
 /// prerequisites -> Prerequisites text
 /// corequisites -> Corequisites text
 /// section_sequence -> Section identifier (e.g., "001", "002")
+/// crn -> Registration number entered in the registrar's portal (if the sync provided one)
 /// max_enrollment -> Maximum enrollment capacity
 /// enrollment -> Current enrollment count
 /// instruction_method -> Instruction method (e.g., "In Person", "Online")
@@ -32,6 +38,12 @@ use crate::tui::widgets::helpers::{format_day_for_display, get_day_order};
 /// professor_email -> Professor's email address
 /// meeting_type -> Type of meeting (e.g., "Lecture", "Lab")
 /// days -> Days the class meets (formatted string like "MWF" or "TTH")
+/// meeting_times -> Structured meeting times (days, start/end minutes, and dates of each meeting)
+/// final_exam -> The section's final exam block (if the sync provided one)
+/// session -> Part of the term the section runs ("full", "first half", "second half", or
+///            "partial"), from the sync or else its meeting dates
+/// restrictions -> Who may register for the section (if the sync provided any)
+/// fee -> Lab or course fee in dollars (if the sync provided one)
 /// --- ---
 ///
 /// Implemented Traits:
@@ -51,6 +63,7 @@ pub struct Class {
     pub prerequisites: Option<String>,
     pub corequisites: Option<String>,
     pub section_sequence: String,
+    pub crn: Option<String>,
     pub max_enrollment: Option<i32>,
     pub enrollment: Option<i32>,
     pub instruction_method: Option<String>,
@@ -59,7 +72,264 @@ pub struct Class {
     pub professor_email: Option<String>,
     pub meeting_type: Option<String>,
     pub days: String,
-    pub meeting_times: Option<String>,
+    pub meeting_times: Vec<Meeting>,
+    pub final_exam: Option<FinalExam>,
+    pub session: Option<String>,
+    pub restrictions: Restrictions,
+    pub fee: Option<f64>,
+}
+
+/// WeekParity enum for meetings held every other week
+///
+/// Weeks are counted from the week of the meeting's first date, so an Odd meeting is held
+/// the first, third, fifth, ... week it runs. Syncs record it in the meeting's JSON extras
+/// as "weekParity"
+///
+/// Variants:
+/// --- ---
+/// Odd -> Held the first week and every other week after
+/// Even -> Held the second week and every other week after
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for WeekParity
+/// Clone -> Clone trait for WeekParity
+/// Copy -> Copy trait for WeekParity
+/// PartialEq -> PartialEq trait for WeekParity
+/// --- ---
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WeekParity {
+    Odd,
+    Even,
+}
+
+impl WeekParity {
+    /// Read a week parity from its name
+    ///
+    /// Parameters:
+    /// --- ---
+    /// name -> "odd" or "even" (case-insensitive)
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Option<WeekParity> -> The parity, or None for any other name
+    /// --- ---
+    ///
+    pub fn from_name(name: &str) -> Option<WeekParity> {
+        match name.trim().to_lowercase().as_str() {
+            "odd" => Some(WeekParity::Odd),
+            "even" => Some(WeekParity::Even),
+            _ => None,
+        }
+    }
+
+    /// Get the parity's name as synced
+    ///
+    /// Returns:
+    /// --- ---
+    /// &'static str -> "odd" or "even"
+    /// --- ---
+    ///
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WeekParity::Odd => "odd",
+            WeekParity::Even => "even",
+        }
+    }
+}
+
+/// Meeting struct
+///
+/// Represents a single weekly meeting of a section, which may run for only part of the
+/// term or every other week
+///
+/// Meeting fields:
+/// --- ---
+/// days -> Day codes the meeting occurs on (e.g., ["M", "W"] or ["T", "TH"])
+/// start_minutes -> Start time in minutes since midnight
+/// end_minutes -> End time in minutes since midnight
+/// start_date -> First date the meeting is held (None if unknown)
+/// end_date -> Last date the meeting is held (None if unknown)
+/// week_parity -> Which weeks an every-other-week meeting is held (None if every week)
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for Meeting
+/// Clone -> Clone trait for Meeting
+/// PartialEq -> PartialEq trait for Meeting
+/// Default -> Default trait for Meeting
+/// --- ---
+///
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Meeting {
+    pub days: Vec<String>,
+    pub start_minutes: i32,
+    pub end_minutes: i32,
+    pub start_date: Option<Date>,
+    pub end_date: Option<Date>,
+    pub week_parity: Option<WeekParity>,
+}
+
+impl Meeting {
+    /// Check whether the meeting occurs on a day
+    ///
+    /// Parameters:
+    /// --- ---
+    /// day_code -> Day code to check (e.g., "M", "TH")
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// bool -> true if the meeting occurs on that day
+    /// --- ---
+    ///
+    pub fn meets_on(&self, day_code: &str) -> bool {
+        self.days.iter().any(|d| d == day_code)
+    }
+
+    /// Check whether the meeting is held on a date
+    ///
+    /// Parameters:
+    /// --- ---
+    /// date -> The date to check
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// bool -> true if the date is one of the meeting's days, within its dates (unknown
+    ///         dates are treated as open), and in one of its weeks
+    /// --- ---
+    ///
+    pub fn meets_on_date(&self, date: Date) -> bool {
+        self.meets_on(date.day_code())
+            && self.start_date.is_none_or(|start| date >= start)
+            && self.end_date.is_none_or(|end| date <= end)
+            && self.held_in_week(date.week_index())
+    }
+
+    /// Check whether the meeting is held in a week
+    ///
+    /// Parameters:
+    /// --- ---
+    /// week -> The week, from Date::week_index
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// bool -> true unless the meeting is every other week and this isn't one of them
+    /// --- ---
+    ///
+    pub fn held_in_week(&self, week: i64) -> bool {
+        let Some(parity) = self.week_parity else {
+            return true;
+        };
+        // weeks count from the meeting's first week, or the epoch's when it has no dates
+        let first_week = self.start_date.map_or(0, |start| start.week_index());
+        let odd_week = (week - first_week).rem_euclid(2) == 0;
+        odd_week == (parity == WeekParity::Odd)
+    }
+
+    /// Check whether two meetings overlap
+    ///
+    /// Parameters:
+    /// --- ---
+    /// other -> The meeting to compare against
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// bool -> true if the meetings share a day, their time ranges overlap, and both are
+    ///         held in some week that their dates have in common
+    /// --- ---
+    ///
+    pub fn overlaps(&self, other: &Meeting) -> bool {
+        // check if they share any day
+        if !self.days.iter().any(|d| other.meets_on(d)) {
+            return false;
+        }
+
+        ranges_overlap(
+            self.start_minutes,
+            self.end_minutes,
+            other.start_minutes,
+            other.end_minutes,
+        ) && self.shares_a_week(other)
+    }
+
+    /// Check whether two meetings are held in a common week
+    ///
+    /// Parameters:
+    /// --- ---
+    /// other -> The meeting to compare against
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// bool -> true if their dates overlap and, for every-other-week meetings, a week in
+    ///         the overlap has both of them
+    /// --- ---
+    ///
+    fn shares_a_week(&self, other: &Meeting) -> bool {
+        // the dates both meetings run, unknown ends are open
+        let first = self.start_date.max(other.start_date);
+        let last = match (self.end_date, other.end_date) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        if let (Some(first), Some(last)) = (first, last) {
+            if first > last {
+                return false;
+            }
+        }
+
+        // parity repeats every two weeks, so two consecutive weeks of the overlap decide it
+        let first_week = first.map(|date| date.week_index());
+        let last_week = last.map(|date| date.week_index());
+        let week = first_week.or(last_week.map(|week| week - 1)).unwrap_or(0);
+        (week..=week + 1)
+            .filter(|w| first_week.is_none_or(|first| *w >= first))
+            .filter(|w| last_week.is_none_or(|last| *w <= last))
+            .any(|w| self.held_in_week(w) && other.held_in_week(w))
+    }
+
+    /// Describe the dates and weeks the meeting runs
+    ///
+    /// Returns:
+    /// --- ---
+    /// Option<String> -> The dates and weeks (e.g., "Aug 26-Oct 17, odd weeks"), None for a
+    ///                   weekly meeting without dates
+    /// --- ---
+    ///
+    pub fn schedule_note(&self) -> Option<String> {
+        let mut parts = Vec::new();
+        if self.start_date.is_some() || self.end_date.is_some() {
+            let label = |date: Option<Date>| date.map_or("?".to_string(), |d| d.short_label());
+            parts.push(format!(
+                "{}-{}",
+                label(self.start_date),
+                label(self.end_date)
+            ));
+        }
+        if let Some(parity) = self.week_parity {
+            parts.push(format!("{} weeks", parity.as_str()));
+        }
+        (!parts.is_empty()).then(|| parts.join(", "))
+    }
+
+    /// Get the meeting's day codes as a compact string
+    ///
+    /// Returns:
+    /// --- ---
+    /// String -> Compact day string (e.g., "MW", "TTH")
+    /// --- ---
+    ///
+    pub fn days_compact(&self) -> String {
+        self.days.concat()
+    }
 }
 
 impl Class {
@@ -82,6 +352,76 @@ impl Class {
         )
     }
 
+    /// Get a short label for a section that runs only part of the term
+    ///
+    /// Parameters:
+    /// --- ---
+    /// self -> The class instance
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Option<String> -> The label (e.g., "1st half"), or None for full-term sections and
+    ///                   ones whose session is unknown
+    /// --- ---
+    ///
+    pub fn session_label(&self) -> Option<String> {
+        match self.session.as_deref()? {
+            "full" => None,
+            "first half" => Some("1st half".to_string()),
+            "second half" => Some("2nd half".to_string()),
+            "partial" => Some("Part of term".to_string()),
+            other => Some(other.to_string()),
+        }
+    }
+
+    /// Get the section's fee as shown to the user
+    ///
+    /// Parameters:
+    /// --- ---
+    /// self -> The class instance
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Option<String> -> The fee (e.g., "$45.00"), or None if the section has no fee
+    /// --- ---
+    ///
+    pub fn fee_label(&self) -> Option<String> {
+        self.fee.filter(|fee| *fee > 0.0).map(format_dollars)
+    }
+
+    /// Find the meeting in progress at a local date and time
+    ///
+    /// Parameters:
+    /// --- ---
+    /// self -> The class instance
+    /// calendar -> The term calendar the class belongs to
+    /// date -> Local date at the school
+    /// minutes -> Local time at the school in minutes since midnight
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Option<&Meeting> -> The meeting happening at that moment, if any
+    /// --- ---
+    ///
+    pub fn meeting_at(
+        &self,
+        calendar: &TermCalendar,
+        date: Date,
+        minutes: i32,
+    ) -> Option<&Meeting> {
+        if !calendar.is_in_session(date) {
+            return None;
+        }
+
+        self.meeting_times.iter().find(|meeting| {
+            meeting.meets_on_date(date)
+                && (meeting.start_minutes..meeting.end_minutes).contains(&minutes)
+        })
+    }
+
     /// Format the class for display in a table cell
     ///
     /// Parameters:
@@ -121,53 +461,24 @@ impl Class {
         lines.push(prof_display);
 
         // line 4: days and time
-        let time_str = if let Some(meeting_times_str) = &self.meeting_times {
-            // parse meeting times: "M:08:00:00-10:45:00|TH:08:00:00-09:15:00"
-            let mut time_parts: Vec<(u8, String)> = Vec::new(); // (day_order, formatted_string)
-            for mt in meeting_times_str.split('|') {
-                if let Some(colon_pos) = mt.find(':') {
-                    let days_part = &mt[..colon_pos];
-                    let time_part = &mt[colon_pos + 1..];
-                    if let Some(dash_pos) = time_part.find('-') {
-                        let start = format_time_short(&time_part[..dash_pos]);
-                        let end = format_time_short(&time_part[dash_pos + 1..]);
-                        if !days_part.is_empty() {
-                            // get the first day code for sorting (in case of multiple days like "MW")
-                            let first_day = if days_part.starts_with("TH") {
-                                "TH"
-                            } else if days_part.starts_with("SU") {
-                                "SU"
-                            } else if days_part.len() > 0 {
-                                &days_part[..1]
-                            } else {
-                                days_part
-                            };
-                            let day_order = get_day_order(first_day);
-                            // format day code for display (add space after single letters)
-                            let formatted_days = format_day_for_display(days_part);
-                            time_parts
-                                .push((day_order, format!("{} {}-{}", formatted_days, start, end)));
-                        }
-                    }
-                }
-            }
-
-            // sort by day order (Monday first)
-            time_parts.sort_by_key(|(day_order, _)| *day_order);
-
-            if time_parts.is_empty() {
-                format!("{} TBA", self.days)
-            } else {
-                // show all parsed meeting times with their days (already sorted)
-                time_parts
-                    .iter()
-                    .map(|(_, s)| s.as_str())
-                    .collect::<Vec<_>>()
-                    .join(", ")
-            }
-        } else {
+        let time_str = if self.meeting_times.is_empty() {
             // no meeting times available
             format!("{} TBA", self.days)
+        } else {
+            // show all meeting times with their days (already sorted Monday first)
+            self.meeting_times
+                .iter()
+                .map(|meeting| {
+                    // format day code for display (add space after single letters)
+                    format!(
+                        "{} {}-{}",
+                        format_day_for_display(&meeting.days_compact()),
+                        format_12_hour(meeting.start_minutes),
+                        format_12_hour(meeting.end_minutes)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(", ")
         };
         lines.push(time_str);
 
@@ -184,40 +495,534 @@ impl Class {
     }
 }
 
-/// Format time from "HH:MM:SS" to "H:MMam/pm"
+/// Parse the packed meeting times column into structured meetings
 ///
 /// Parameters:
 /// --- ---
-/// time -> Time string in "HH:MM:SS" format
+/// times_str -> Packed meeting times (e.g., "M:08:00:00-10:45:00|TH:08:00:00-09:15:00"),
+///              each optionally followed by its dates and week parity
+///              (e.g., "@2024-08-26/2024-10-17/odd", any part may be empty)
 /// --- ---
 ///
 /// Returns:
 /// --- ---
-/// String -> Formatted time string (e.g., "9:00am", "2:30pm")
+/// Vec<Meeting> -> Parsed meetings sorted by first day (Monday first)
 /// --- ---
 ///
-fn format_time_short(time: &str) -> String {
-    let parts: Vec<&str> = time.split(':').collect();
-    if parts.len() >= 2 {
-        let hours: i32 = parts[0].parse().unwrap_or(0);
-        let minutes: i32 = parts[1].parse().unwrap_or(0);
+pub fn parse_meeting_times(times_str: &str) -> Vec<Meeting> {
+    let mut meetings = Vec::new();
 
-        let (display_hour, period) = if hours == 0 {
-            (12, "am")
-        } else if hours < 12 {
-            (hours, "am")
-        } else if hours == 12 {
-            (12, "pm")
-        } else {
-            (hours - 12, "pm")
-        };
+    for mt in times_str.split('|') {
+        if mt.is_empty() {
+            continue;
+        }
 
-        format!("{}:{:02}{}", display_hour, minutes, period)
-    } else {
-        time.to_string()
+        // meetings packed before they had dates have no "@" part
+        let (mt, dates) = mt.split_once('@').unwrap_or((mt, ""));
+        let mut dates = dates.split('/');
+        let start_date = dates.next().and_then(Date::parse);
+        let end_date = dates.next().and_then(Date::parse);
+        let week_parity = dates.next().and_then(WeekParity::from_name);
+
+        if let Some(colon_pos) = mt.find(':') {
+            let days_part = &mt[..colon_pos];
+            let time_part = &mt[colon_pos + 1..];
+
+            if let Some(dash_pos) = time_part.find('-') {
+                let start_minutes = parse_time(&time_part[..dash_pos]).unwrap_or(0);
+                let end_minutes = parse_time(&time_part[dash_pos + 1..]).unwrap_or(0);
+
+                // parse days (handle "MW", "TTH", etc.)
+                let days = parse_days(days_part);
+
+                if !days.is_empty() && start_minutes > 0 && end_minutes > start_minutes {
+                    meetings.push(Meeting {
+                        days,
+                        start_minutes,
+                        end_minutes,
+                        start_date,
+                        end_date,
+                        week_parity,
+                    });
+                }
+            }
+        }
+    }
+
+    // sort by day order (Monday first)
+    meetings.sort_by_key(|meeting| day_order(&meeting.days[0]));
+    meetings
+}
+
+/// FinalExam struct
+///
+/// Represents the block a section's final exam is held in, read from the "finalExam"
+/// object (date, startTime, endTime) of the section's synced data
+///
+/// FinalExam fields:
+/// --- ---
+/// date -> Date of the exam
+/// start_minutes -> Start time in minutes since midnight
+/// end_minutes -> End time in minutes since midnight
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for FinalExam
+/// Clone -> Clone trait for FinalExam
+/// Copy -> Copy trait for FinalExam
+/// PartialEq -> PartialEq trait for FinalExam
+/// --- ---
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FinalExam {
+    pub date: Date,
+    pub start_minutes: i32,
+    pub end_minutes: i32,
+}
+
+impl FinalExam {
+    /// Check whether two final exams overlap
+    ///
+    /// Parameters:
+    /// --- ---
+    /// other -> The exam to compare against
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// bool -> true if the exams are on the same date and their times overlap
+    /// --- ---
+    ///
+    pub fn overlaps(&self, other: &FinalExam) -> bool {
+        self.date == other.date
+            && ranges_overlap(
+                self.start_minutes,
+                self.end_minutes,
+                other.start_minutes,
+                other.end_minutes,
+            )
+    }
+
+    /// Describe the day the exam is held
+    ///
+    /// Returns:
+    /// --- ---
+    /// String -> The weekday and date (e.g., "Mon Dec 16")
+    /// --- ---
+    ///
+    pub fn day_label(&self) -> String {
+        let weekdays = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+        format!(
+            "{} {}",
+            weekdays[day_order(self.date.day_code()) as usize % 7],
+            self.date.short_label()
+        )
+    }
+
+    /// Describe when the exam is held
+    ///
+    /// Returns:
+    /// --- ---
+    /// String -> The weekday, date, and times (e.g., "Mon Dec 16, 8:00am-10:00am")
+    /// --- ---
+    ///
+    pub fn label(&self) -> String {
+        format!(
+            "{}, {}-{}",
+            self.day_label(),
+            format_12_hour(self.start_minutes),
+            format_12_hour(self.end_minutes)
+        )
     }
 }
 
+/// Sessions a section can run in, as the session column names them
+pub const SESSIONS: [&str; 4] = ["full", "first half", "second half", "partial"];
+
+/// Read a session the way the session column names it
+///
+/// Parameters:
+/// --- ---
+/// value -> A session as typed or synced (e.g., "1st Half", "second", "Full Term")
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> The session column's name for it (e.g., "first half"), or the value in
+///           lowercase if it isn't one of SESSIONS
+/// --- ---
+///
+pub fn normalize_session(value: &str) -> String {
+    let value = value.trim().to_lowercase();
+    let words: Vec<&str> = value.split_whitespace().collect();
+    match words.first().copied() {
+        Some("first" | "1st") if words.len() == 1 || words[1..] == ["half"] => {
+            "first half".to_string()
+        }
+        Some("second" | "2nd") if words.len() == 1 || words[1..] == ["half"] => {
+            "second half".to_string()
+        }
+        Some("full") if words.len() == 1 || words[1..] == ["term"] => "full".to_string(),
+        _ => words.join(" "),
+    }
+}
+
+/// Instruction methods every school's methods are normalized to
+pub const INSTRUCTION_METHODS: [&str; 3] = ["In Person", "Online", "Hybrid"];
+
+/// Spellings schools send for each of INSTRUCTION_METHODS, lowercase with spaces and
+/// punctuation removed
+const METHOD_SPELLINGS: [(&str, &[&str]); 3] = [
+    (
+        "In Person",
+        &[
+            "inperson",
+            "inp",
+            "facetoface",
+            "f2f",
+            "traditional",
+            "classroom",
+            "oncampus",
+            "onsite",
+        ],
+    ),
+    (
+        "Online",
+        &[
+            "online",
+            "onln",
+            "onl",
+            "web",
+            "webbased",
+            "internet",
+            "distance",
+            "distancelearning",
+            "distanceeducation",
+            "remote",
+            "virtual",
+            "fullyonline",
+            "onlinesynchronous",
+            "onlineasynchronous",
+            "synchronousonline",
+            "asynchronousonline",
+        ],
+    ),
+    (
+        "Hybrid",
+        &[
+            "hybrid",
+            "hyb",
+            "hybr",
+            "blended",
+            "partiallyonline",
+            "hybridonline",
+            "webenhanced",
+        ],
+    ),
+];
+
+/// Read an instruction method as one of INSTRUCTION_METHODS
+///
+/// Parameters:
+/// --- ---
+/// value -> A method as typed or synced (e.g., "WEB", "ONLN", "Face-to-Face")
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> The method it is a spelling of (e.g., "Online"), or the value without
+///           surrounding whitespace if it isn't a known spelling
+/// --- ---
+///
+pub fn normalize_instruction_method(value: &str) -> String {
+    let folded: String = value
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect();
+    METHOD_SPELLINGS
+        .iter()
+        .find(|(_, spellings)| spellings.contains(&folded.as_str()))
+        .map_or_else(
+            || value.trim().to_string(),
+            |(method, _)| method.to_string(),
+        )
+}
+
+/// Check whether two sections' sessions share any weeks
+///
+/// Parameters:
+/// --- ---
+/// first -> One section's session, None if unknown
+/// second -> The other section's session, None if unknown
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// bool -> false only for a first half and a second half section, which never meet
+///         in the same week
+/// --- ---
+///
+pub fn sessions_overlap(first: Option<&str>, second: Option<&str>) -> bool {
+    !matches!(
+        (first, second),
+        (Some("first half"), Some("second half")) | (Some("second half"), Some("first half"))
+    )
+}
+
+/// Restrictions struct
+///
+/// Represents who may register for a section, read from the "restrictions" object
+/// (instructorConsent, majors, classLevels) of the section's synced data
+///
+/// Restrictions fields:
+/// --- ---
+/// instructor_consent -> Whether the instructor must approve each student
+/// majors -> The only majors that may register, empty if any may
+/// class_levels -> The only class levels (e.g., "Junior") that may register, empty if any may
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for Restrictions
+/// Clone -> Clone trait for Restrictions
+/// Default -> Default trait for Restrictions
+/// PartialEq -> PartialEq trait for Restrictions
+/// --- ---
+///
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Restrictions {
+    pub instructor_consent: bool,
+    pub majors: Vec<String>,
+    pub class_levels: Vec<String>,
+}
+
+impl Restrictions {
+    /// Check whether anything limits who may register
+    ///
+    /// Returns:
+    /// --- ---
+    /// bool -> true if consent is needed or the section is limited to some majors or levels
+    /// --- ---
+    ///
+    pub fn is_restricted(&self) -> bool {
+        self.instructor_consent || !self.majors.is_empty() || !self.class_levels.is_empty()
+    }
+
+    /// Describe each restriction
+    ///
+    /// Returns:
+    /// --- ---
+    /// Vec<String> -> One line per restriction (e.g., "Majors only: CMPT, MATH"), empty if
+    ///                the section is unrestricted
+    /// --- ---
+    ///
+    pub fn labels(&self) -> Vec<String> {
+        let mut labels = Vec::new();
+        if self.instructor_consent {
+            labels.push("Instructor consent required".to_string());
+        }
+        if !self.majors.is_empty() {
+            labels.push(format!("Majors only: {}", self.majors.join(", ")));
+        }
+        if !self.class_levels.is_empty() {
+            labels.push(format!("Class levels: {}", self.class_levels.join(", ")));
+        }
+        labels
+    }
+}
+
+/// Parse the packed final exam column into a final exam
+///
+/// Parameters:
+/// --- ---
+/// packed -> Packed final exam (e.g., "2024-12-16 08:00:00-10:00:00"), the times in any
+///           form parse_time reads
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Option<FinalExam> -> The exam, or None if the date or times are malformed
+/// --- ---
+///
+pub fn parse_final_exam(packed: &str) -> Option<FinalExam> {
+    let (date, times) = packed.trim().split_once(' ')?;
+    let (start, end) = times.trim().split_once('-')?;
+    let exam = FinalExam {
+        date: Date::parse(date)?,
+        start_minutes: parse_time(start.trim())?,
+        end_minutes: parse_time(end.trim())?,
+    };
+    (exam.start_minutes < exam.end_minutes).then_some(exam)
+}
+
+/// Pack a final exam into the packed final exam column format
+///
+/// Parameters:
+/// --- ---
+/// exam -> The exam to pack
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> Packed final exam (e.g., "2024-12-16 08:00:00-10:00:00"), the inverse of
+///           parse_final_exam
+/// --- ---
+///
+pub fn pack_final_exam(exam: &FinalExam) -> String {
+    format!(
+        "{} {:02}:{:02}:00-{:02}:{:02}:00",
+        exam.date,
+        exam.start_minutes / 60,
+        exam.start_minutes % 60,
+        exam.end_minutes / 60,
+        exam.end_minutes % 60
+    )
+}
+
+/// Format an amount of money in dollars
+///
+/// Parameters:
+/// --- ---
+/// amount -> The amount in dollars, not negative
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> The amount with a dollar sign and cents (e.g., "$1,250.00")
+/// --- ---
+///
+pub fn format_dollars(amount: f64) -> String {
+    let cents = (amount * 100.0).round() as i64;
+    let dollars = (cents / 100).to_string();
+    // group the dollars in thousands
+    let mut grouped = String::new();
+    for (i, digit) in dollars.chars().enumerate() {
+        if i > 0 && (dollars.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    format!("${}.{:02}", grouped, cents % 100)
+}
+
+/// Parse a section's restrictions object
+///
+/// Parameters:
+/// --- ---
+/// json -> The restrictions object as synced (e.g., {"instructorConsent":true,"majors":["CMPT"]})
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Restrictions -> The restrictions, unrestricted if the JSON is empty or malformed; other
+///                 keys and values that aren't strings in the lists are ignored
+/// --- ---
+///
+pub fn parse_restrictions(json: &str) -> Restrictions {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(json) else {
+        return Restrictions::default();
+    };
+    let list = |key: &str| -> Vec<String> {
+        value[key]
+            .as_array()
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|item| item.as_str())
+                    .map(|item| item.trim().to_string())
+                    .filter(|item| !item.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    Restrictions {
+        // some sources send 1 for true, which the restricted filter reads the same way
+        instructor_consent: value["instructorConsent"].as_bool().unwrap_or(false)
+            || value["instructorConsent"].as_i64() == Some(1),
+        majors: list("majors"),
+        class_levels: list("classLevels"),
+    }
+}
+
+/// Pack restrictions into the synced restrictions object
+///
+/// Parameters:
+/// --- ---
+/// restrictions -> The restrictions to pack
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> The restrictions object (e.g., {"instructorConsent":true}), the inverse of
+///           parse_restrictions, or empty if the section is unrestricted
+/// --- ---
+///
+pub fn pack_restrictions(restrictions: &Restrictions) -> String {
+    if !restrictions.is_restricted() {
+        return String::new();
+    }
+    let mut object = serde_json::Map::new();
+    if restrictions.instructor_consent {
+        object.insert("instructorConsent".to_string(), true.into());
+    }
+    if !restrictions.majors.is_empty() {
+        object.insert("majors".to_string(), restrictions.majors.clone().into());
+    }
+    if !restrictions.class_levels.is_empty() {
+        object.insert(
+            "classLevels".to_string(),
+            restrictions.class_levels.clone().into(),
+        );
+    }
+    serde_json::Value::Object(object).to_string()
+}
+
+/// Pack structured meetings into the packed meeting times column format
+///
+/// Parameters:
+/// --- ---
+/// meetings -> The meetings to pack
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> Packed meeting times (e.g., "MW:08:00:00-09:15:00|F:10:00:00-10:50:00"),
+///           the inverse of parse_meeting_times
+/// --- ---
+///
+pub fn pack_meeting_times(meetings: &[Meeting]) -> String {
+    meetings
+        .iter()
+        .map(|meeting| {
+            let mut packed = format!(
+                "{}:{:02}:{:02}:00-{:02}:{:02}:00",
+                meeting.days_compact(),
+                meeting.start_minutes / 60,
+                meeting.start_minutes % 60,
+                meeting.end_minutes / 60,
+                meeting.end_minutes % 60
+            );
+            // meetings without dates pack the way they always have
+            if meeting.start_date.is_some()
+                || meeting.end_date.is_some()
+                || meeting.week_parity.is_some()
+            {
+                let date = |date: Option<Date>| date.map(|d| d.to_string()).unwrap_or_default();
+                packed.push_str(&format!(
+                    "@{}/{}/{}",
+                    date(meeting.start_date),
+                    date(meeting.end_date),
+                    meeting.week_parity.map_or("", |parity| parity.as_str())
+                ));
+            }
+            packed
+        })
+        .collect::<Vec<_>>()
+        .join("|")
+}
+
 /// Format days from boolean flags into a compact string like "MWF" or "TTH"
 ///
 /// Parameters:
@@ -289,8 +1094,7 @@ fn format_days(
 ///
 pub fn execute_query(sql: &str, db_path: &Path) -> Result<Vec<Class>, String> {
     // connect to the database
-    let conn =
-        Connection::open(db_path).map_err(|e| format!("Database connection error: {}", e))?;
+    let conn = open_read_connection(db_path)?;
 
     // prepare and execute the statement
     let mut stmt = conn
@@ -309,6 +1113,7 @@ pub fn execute_query(sql: &str, db_path: &Path) -> Result<Vec<Class>, String> {
                 prerequisites: row.get(5).ok(),
                 corequisites: row.get(6).ok(),
                 section_sequence: row.get(7).unwrap_or_default(),
+                crn: row.get(23).ok().flatten(),
                 max_enrollment: row.get(8).ok(),
                 enrollment: row.get(9).ok(),
                 instruction_method: row.get(10).ok(),
@@ -325,7 +1130,20 @@ pub fn execute_query(sql: &str, db_path: &Path) -> Result<Vec<Class>, String> {
                     row.get::<_, i32>(21).unwrap_or(0) == 1,
                     row.get::<_, i32>(22).unwrap_or(0) == 1,
                 ),
-                meeting_times: row.get(14).ok(), // meeting_times is column 14
+                meeting_times: row
+                    .get::<_, String>(14) // meeting_times is column 14
+                    .map(|packed| parse_meeting_times(&packed))
+                    .unwrap_or_default(),
+                final_exam: row
+                    .get::<_, String>(24)
+                    .ok()
+                    .and_then(|packed| parse_final_exam(&packed)),
+                session: row.get(25).ok().flatten(),
+                restrictions: row
+                    .get::<_, String>(26)
+                    .map(|json| parse_restrictions(&json))
+                    .unwrap_or_default(),
+                fee: row.get(27).ok().flatten(),
             })
         })
         .map_err(|e| format!("Query execution error: {}", e))?;
@@ -374,6 +1192,114 @@ pub struct Term {
     pub season: String,
 }
 
+/// TermCalendar struct for a term's dates and its school's timezone
+///
+/// Fields:
+/// --- ---
+/// term_id -> Term collection identifier
+/// school_id -> School identifier
+/// start_date -> First day of classes (None if unknown)
+/// end_date -> Last day of classes (None if unknown)
+/// timezone -> Timezone the school's meeting times are expressed in
+/// --- ---
+#[derive(Debug, Clone, PartialEq)]
+pub struct TermCalendar {
+    pub term_id: String,
+    pub school_id: String,
+    pub start_date: Option<Date>,
+    pub end_date: Option<Date>,
+    pub timezone: SchoolTimezone,
+}
+
+impl TermCalendar {
+    /// Check whether classes are in session on a date
+    ///
+    /// Parameters:
+    /// --- ---
+    /// date -> Local date at the school
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// bool -> true if the date falls within the term (unknown bounds are treated as open)
+    /// --- ---
+    ///
+    pub fn is_in_session(&self, date: Date) -> bool {
+        self.start_date.is_none_or(|start| date >= start)
+            && self.end_date.is_none_or(|end| date <= end)
+    }
+
+    /// Get the current local date and time at the school
+    ///
+    /// Returns:
+    /// --- ---
+    /// (Date, i32) -> Local date and minutes since local midnight
+    /// --- ---
+    ///
+    pub fn local_now(&self) -> (Date, i32) {
+        self.timezone.local_datetime(now_utc_seconds())
+    }
+}
+
+/// Fetch the calendar for a term from the database
+///
+/// Uses the dates and timezone recorded at sync time, falling back to the
+/// meeting date range and the default timezone for databases that predate them
+///
+/// Parameters:
+/// --- ---
+/// db_path -> Path to the SQLite database file
+/// school_id -> The school ID
+/// term_id -> The term collection ID
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<TermCalendar, String> -> The term calendar or error message
+/// --- ---
+pub fn fetch_term_calendar(
+    db_path: &Path,
+    school_id: &str,
+    term_id: &str,
+) -> Result<TermCalendar, String> {
+    let conn = open_read_connection(db_path)?;
+
+    let stored: Option<(Option<String>, Option<String>, Option<String>)> = conn
+        .query_row(
+            "SELECT start_date, end_date, timezone FROM term_calendars \
+             WHERE school_id = ? AND term_collection_id = ?",
+            [school_id, term_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .ok();
+
+    let (start_date, end_date, timezone) = match stored {
+        Some(row) => row,
+        None => {
+            let (start, end) = conn
+                .query_row(
+                    "SELECT MIN(start_date), MAX(end_date) FROM meeting_times \
+                     WHERE school_id = ? AND term_collection_id = ?",
+                    [school_id, term_id],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .map_err(|e| format!("Query execution error: {}", e))?;
+            (start, end, None)
+        }
+    };
+
+    Ok(TermCalendar {
+        term_id: term_id.to_string(),
+        school_id: school_id.to_string(),
+        start_date: start_date.as_deref().and_then(Date::parse),
+        end_date: end_date.as_deref().and_then(Date::parse),
+        timezone: timezone
+            .as_deref()
+            .and_then(SchoolTimezone::from_name)
+            .unwrap_or_default(),
+    })
+}
+
 /// Fetch all available schools from the synced database
 ///
 /// Parameters:
@@ -386,8 +1312,7 @@ pub struct Term {
 /// Result<Vec<School>, String> -> Vector of schools or error message
 /// --- ---
 pub fn fetch_schools(db_path: &Path) -> Result<Vec<School>, String> {
-    let conn =
-        Connection::open(db_path).map_err(|e| format!("Database connection error: {}", e))?;
+    let conn = open_read_connection(db_path)?;
 
     let mut stmt = conn
         .prepare("SELECT id, name FROM schools ORDER BY name")
@@ -412,7 +1337,7 @@ pub fn fetch_schools(db_path: &Path) -> Result<Vec<School>, String> {
     Ok(schools)
 }
 
-/// Fetch all available terms for a school from the synced database
+/// Fetch all available (non-archived) terms for a school from the synced database
 ///
 /// Parameters:
 /// --- ---
@@ -425,8 +1350,7 @@ pub fn fetch_schools(db_path: &Path) -> Result<Vec<School>, String> {
 /// Result<Vec<Term>, String> -> Vector of terms or error message
 /// --- ---
 pub fn fetch_terms(db_path: &Path, school_id: &str) -> Result<Vec<Term>, String> {
-    let conn =
-        Connection::open(db_path).map_err(|e| format!("Database connection error: {}", e))?;
+    let conn = open_read_connection(db_path)?;
 
     let mut stmt = conn
         .prepare("SELECT id, school_id, name, year, season FROM term_collections WHERE school_id = ? ORDER BY year DESC, season")
@@ -451,6 +1375,10 @@ pub fn fetch_terms(db_path: &Path, school_id: &str) -> Result<Vec<Term>, String>
         }
     }
 
+    // archived terms are hidden from selection
+    let archived = fetch_archived_term_ids(db_path, Some(school_id)).unwrap_or_default();
+    terms.retain(|term| !archived.contains(&term.id));
+
     Ok(terms)
 }
 
@@ -466,7 +1394,7 @@ pub fn fetch_terms(db_path: &Path, school_id: &str) -> Result<Vec<Term>, String>
 /// Option<String> -> Last sync timestamp or None if never synced
 /// --- ---
 pub fn get_last_sync_time(db_path: &Path) -> Option<String> {
-    let conn = Connection::open(db_path).ok()?;
+    let conn = open_read_connection(db_path).ok()?;
 
     let result: Result<String, _> = conn.query_row(
         "SELECT created_at FROM _previous_all_collections ORDER BY synced_at DESC LIMIT 1",
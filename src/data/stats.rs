@@ -0,0 +1,411 @@
+/*
+    src/data/stats.rs
+
+    Module for database statistics
+    Collects row counts, per-term section counts, file size, index usage, and
+    sync history so users can see what is actually stored locally
+*/
+
+use std::fs;
+use std::path::Path;
+
+use rusqlite::Connection;
+
+use crate::data::archive::fetch_archived_term_ids;
+use crate::data::index_advisor::explain_query;
+use crate::data::maintenance::format_bytes;
+use crate::data::pool::open_read_connection;
+use crate::dsl::codegen::generate_sql;
+use crate::dsl::lexer::Lexer;
+use crate::dsl::parser::Parser;
+use crate::utils::i18n;
+
+/// Query whose plan is inspected to decide which indexes searches use
+const REPRESENTATIVE_QUERY: &str = "subject is CS and monday and start > 9:00am";
+
+/// Maximum number of sync records reported
+const MAX_SYNC_RECORDS: usize = 5;
+
+/// Section count for a single term
+///
+/// Fields:
+/// --- ---
+/// school_id -> School the term belongs to
+/// term_id -> Term collection ID
+/// name -> Display name of the term
+/// sections -> Number of sections stored for the term
+/// archived -> Whether the term has been archived
+/// --- ---
+#[derive(Debug, Clone)]
+pub struct TermSectionCount {
+    pub school_id: String,
+    pub term_id: String,
+    pub name: String,
+    pub sections: i64,
+    pub archived: bool,
+}
+
+/// An index and whether searches use it
+///
+/// Fields:
+/// --- ---
+/// name -> Index name
+/// table -> Table the index belongs to
+/// used_by_search -> Whether the query plan for a representative search uses the index
+/// --- ---
+#[derive(Debug, Clone)]
+pub struct IndexUsage {
+    pub name: String,
+    pub table: String,
+    pub used_by_search: bool,
+}
+
+/// A recorded sync from classy-sync's bookkeeping tables
+///
+/// Fields:
+/// --- ---
+/// scope -> What was synced (e.g., "all schools", "school marist")
+/// synced_at -> Sync sequence number reported by the server
+/// created_at -> Timestamp the sync was recorded
+/// --- ---
+#[derive(Debug, Clone)]
+pub struct SyncRecord {
+    pub scope: String,
+    pub synced_at: i64,
+    pub created_at: String,
+}
+
+/// Statistics about the local database
+///
+/// Fields:
+/// --- ---
+/// file_size -> Database file size in bytes
+/// tables -> Row count per table
+/// terms -> Section count per term
+/// indexes -> Indexes and whether searches use them
+/// search_plan -> Query plan steps for the representative search
+/// syncs -> Most recent syncs, newest first
+/// --- ---
+#[derive(Debug, Clone, Default)]
+pub struct DatabaseStats {
+    pub file_size: u64,
+    pub tables: Vec<(String, i64)>,
+    pub terms: Vec<TermSectionCount>,
+    pub indexes: Vec<IndexUsage>,
+    pub search_plan: Vec<String>,
+    pub syncs: Vec<SyncRecord>,
+}
+
+impl DatabaseStats {
+    /// Format the statistics as plain text lines
+    ///
+    /// Section headers are unindented and their rows are indented by two spaces
+    ///
+    /// Returns:
+    /// --- ---
+    /// Vec<String> -> Report lines in display order
+    /// --- ---
+    pub fn report_lines(&self) -> Vec<String> {
+        let mut lines = vec![i18n::tr(
+            "stats-file-size",
+            "File size: {size}",
+            &[("size", &format_bytes(self.file_size))],
+        )];
+
+        lines.push(String::new());
+        lines.push(i18n::tr("stats-tables", "Tables", &[]));
+        for (table, rows) in &self.tables {
+            lines.push(format!("  {:<36} {:>10}", table, rows));
+        }
+
+        lines.push(String::new());
+        lines.push(i18n::tr(
+            "stats-sections-per-term",
+            "Sections per term",
+            &[],
+        ));
+        if self.terms.is_empty() {
+            lines.push(format!(
+                "  {}",
+                i18n::tr("stats-no-terms", "(no terms)", &[])
+            ));
+        }
+        let archived_label = format!("  {}", i18n::tr("stats-archived", "(archived)", &[]));
+        for term in &self.terms {
+            let label = format!("{} {}", term.school_id, term.name);
+            let archived = if term.archived {
+                archived_label.as_str()
+            } else {
+                ""
+            };
+            lines.push(format!("  {:<36} {:>10}{}", label, term.sections, archived));
+        }
+
+        lines.push(String::new());
+        lines.push(i18n::tr("stats-indexes", "Indexes", &[]));
+        if self.indexes.is_empty() {
+            lines.push(format!(
+                "  {}",
+                i18n::tr("stats-no-indexes", "(no indexes)", &[])
+            ));
+        }
+        for index in &self.indexes {
+            let usage = if index.used_by_search {
+                i18n::tr("stats-used-by-search", "used by search", &[])
+            } else {
+                i18n::tr("stats-unused-by-search", "unused by search", &[])
+            };
+            lines.push(format!(
+                "  {:<38} {:<20} {}",
+                index.name, index.table, usage
+            ));
+        }
+
+        lines.push(String::new());
+        lines.push(i18n::tr("stats-search-plan", "Search plan", &[]));
+        for step in &self.search_plan {
+            lines.push(format!("  {}", step));
+        }
+
+        lines.push(String::new());
+        lines.push(i18n::tr("stats-recent-syncs", "Recent syncs", &[]));
+        if self.syncs.is_empty() {
+            lines.push(format!(
+                "  {}",
+                i18n::tr("stats-never-synced", "(never synced)", &[])
+            ));
+        }
+        for sync in &self.syncs {
+            lines.push(format!(
+                "  {:<20} {:<36} #{}",
+                sync.created_at, sync.scope, sync.synced_at
+            ));
+        }
+
+        lines
+    }
+}
+
+/// Collect statistics about a database
+///
+/// Parameters:
+/// --- ---
+/// db_path -> Path to the SQLite database file
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<DatabaseStats, String> -> Collected statistics or error message
+/// --- ---
+pub fn fetch_database_stats(db_path: &Path) -> Result<DatabaseStats, String> {
+    let file_size = fs::metadata(db_path)
+        .map(|m| m.len())
+        .map_err(|e| format!("Failed to read database file: {}", e))?;
+
+    let conn = open_read_connection(db_path)?;
+
+    let tables = fetch_table_counts(&conn)?;
+    let terms = fetch_term_section_counts(&conn, db_path)?;
+    let search_plan = explain_search(&conn);
+    let indexes = fetch_indexes(&conn, &search_plan)?;
+    let syncs = fetch_sync_records(&conn);
+
+    Ok(DatabaseStats {
+        file_size,
+        tables,
+        terms,
+        indexes,
+        search_plan,
+        syncs,
+    })
+}
+
+/// Count the rows in every table
+///
+/// Parameters:
+/// --- ---
+/// conn -> Open database connection
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<Vec<(String, i64)>, String> -> (table, row count) pairs sorted by name
+/// --- ---
+fn fetch_table_counts(conn: &Connection) -> Result<Vec<(String, i64)>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name",
+        )
+        .map_err(|e| format!("SQL preparation error: {}", e))?;
+    let names: Vec<String> = stmt
+        .query_map([], |row| row.get(0))
+        .map_err(|e| format!("Query execution error: {}", e))?
+        .filter_map(Result::ok)
+        .collect();
+
+    let mut counts = Vec::new();
+    for name in names {
+        // table names come from sqlite_master, quote them in case they contain odd characters
+        let sql = format!("SELECT COUNT(*) FROM \"{}\"", name.replace('"', "\"\""));
+        let rows: i64 = conn
+            .query_row(&sql, [], |row| row.get(0))
+            .map_err(|e| format!("Failed to count rows in {}: {}", name, e))?;
+        counts.push((name, rows));
+    }
+
+    Ok(counts)
+}
+
+/// Count the sections stored for each term
+///
+/// Parameters:
+/// --- ---
+/// conn -> Open database connection
+/// db_path -> Path to the database, used to look up archived terms
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<Vec<TermSectionCount>, String> -> Counts ordered by school, newest term first
+/// --- ---
+fn fetch_term_section_counts(
+    conn: &Connection,
+    db_path: &Path,
+) -> Result<Vec<TermSectionCount>, String> {
+    let archived = fetch_archived_term_ids(db_path, None)?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT tc.school_id, tc.id, COALESCE(tc.name, tc.season || ' ' || tc.year), COUNT(s.sequence)
+             FROM term_collections tc
+             LEFT JOIN sections s ON s.term_collection_id = tc.id AND s.school_id = tc.school_id
+             GROUP BY tc.school_id, tc.id
+             ORDER BY tc.school_id, tc.year DESC, tc.id DESC",
+        )
+        .map_err(|e| format!("SQL preparation error: {}", e))?;
+
+    let terms = stmt
+        .query_map([], |row| {
+            Ok(TermSectionCount {
+                school_id: row.get(0)?,
+                term_id: row.get(1)?,
+                name: row.get(2)?,
+                sections: row.get(3)?,
+                archived: false,
+            })
+        })
+        .map_err(|e| format!("Query execution error: {}", e))?
+        .filter_map(Result::ok)
+        .map(|mut term| {
+            term.archived = archived.contains(&term.term_id);
+            term
+        })
+        .collect();
+
+    Ok(terms)
+}
+
+/// Get the query plan for a representative search
+///
+/// Parameters:
+/// --- ---
+/// conn -> Open database connection
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Vec<String> -> Plan step descriptions (empty if the search could not be planned)
+/// --- ---
+fn explain_search(conn: &Connection) -> Vec<String> {
+    let tokens = match Lexer::new(REPRESENTATIVE_QUERY.to_string()).analyze() {
+        Ok(tokens) => tokens,
+        Err(_) => return Vec::new(),
+    };
+    let ast = match Parser::new(REPRESENTATIVE_QUERY.to_string()).parse(&tokens) {
+        Ok(ast) => ast,
+        Err(_) => return Vec::new(),
+    };
+    let sql = match generate_sql(&ast) {
+        Ok(sql) => sql,
+        Err(_) => return Vec::new(),
+    };
+
+    explain_query(conn, &sql).unwrap_or_default()
+}
+
+/// List the indexes and check them against the search plan
+///
+/// Parameters:
+/// --- ---
+/// conn -> Open database connection
+/// search_plan -> Plan steps from explain_search
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<Vec<IndexUsage>, String> -> Indexes sorted by table then name
+/// --- ---
+fn fetch_indexes(conn: &Connection, search_plan: &[String]) -> Result<Vec<IndexUsage>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT name, tbl_name FROM sqlite_master WHERE type = 'index' ORDER BY tbl_name, name",
+        )
+        .map_err(|e| format!("SQL preparation error: {}", e))?;
+
+    let indexes = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })
+        .map_err(|e| format!("Query execution error: {}", e))?
+        .filter_map(Result::ok)
+        .map(|(name, table)| IndexUsage {
+            used_by_search: search_plan
+                .iter()
+                .any(|step| step.contains(&format!("INDEX {}", name))),
+            name,
+            table,
+        })
+        .collect();
+
+    Ok(indexes)
+}
+
+/// Read the most recent syncs from classy-sync's bookkeeping tables
+///
+/// Parameters:
+/// --- ---
+/// conn -> Open database connection
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Vec<SyncRecord> -> Up to MAX_SYNC_RECORDS syncs, newest first (tables that don't exist are skipped)
+/// --- ---
+fn fetch_sync_records(conn: &Connection) -> Vec<SyncRecord> {
+    let sources = [
+        "SELECT 'all schools', synced_at, created_at FROM _previous_all_collections",
+        "SELECT 'school ' || school_id, synced_at, created_at FROM _previous_school_collections",
+        "SELECT 'term ' || school_id || ' ' || term_collection_id, synced_at, created_at FROM _previous_term_collections",
+    ];
+
+    let mut records = Vec::new();
+    for sql in sources {
+        let Ok(mut stmt) = conn.prepare(sql) else {
+            continue;
+        };
+        let rows = stmt.query_map([], |row| {
+            Ok(SyncRecord {
+                scope: row.get(0)?,
+                synced_at: row.get(1)?,
+                created_at: row.get(2)?,
+            })
+        });
+        if let Ok(rows) = rows {
+            records.extend(rows.filter_map(Result::ok));
+        }
+    }
+
+    records.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    records.truncate(MAX_SYNC_RECORDS);
+    records
+}
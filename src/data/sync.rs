@@ -5,13 +5,28 @@
     Handles synchronization with the classy server and database management
 */
 
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use classy_sync::argument_parser::SyncResources;
 use classy_sync::data_stores::replicate_datastore::Datastore;
 use classy_sync::data_stores::sqlite::storage::Sqlite;
-use classy_sync::data_stores::sync_requests::{AllSyncResult, SyncOptions};
+use classy_sync::data_stores::sync_requests::{
+    AllSyncResult, ClassDataSync, SchoolEntry, SyncAction, SyncOptions, TableName,
+};
+use rusqlite::types::Value as SqlValue;
+use rusqlite::{params_from_iter, Connection};
+use serde_json::Value;
+
+use crate::data::catalog::create_catalog_index;
+use crate::data::pool::{
+    checkpoint, open_connection, open_write_connection, retry_when_busy, write_transaction,
+};
+use crate::data::professors::normalize_professor_names;
+use crate::data::sql::normalize_instruction_method;
+use crate::dsl::codegen::TERM_SPANS_SQL;
+use crate::utils::time::{SchoolTimezone, DEFAULT_TIMEZONE};
 
 /// Configuration for classy-sync
 ///
@@ -20,12 +35,14 @@ use classy_sync::data_stores::sync_requests::{AllSyncResult, SyncOptions};
 /// server_url -> URL of the classy server (default: http://localhost)
 /// server_port -> Port of the classy server (default: from env or 8080)
 /// db_path -> Path to store the synced database
+/// timezone -> Timezone of the synced schools' meeting times (default: America/New_York)
 /// --- ---
 #[derive(Debug, Clone)]
 pub struct SyncConfig {
     pub server_url: String,
     pub server_port: u16,
     pub db_path: PathBuf,
+    pub timezone: SchoolTimezone,
 }
 
 impl SyncConfig {
@@ -52,10 +69,21 @@ impl SyncConfig {
 
         let db_path = db_dir.join("classes.db");
 
+        // classy doesn't report timezones, so the school's zone comes from the environment
+        let timezone_name = std::env::var("CLASSY_SCHOOL_TIMEZONE")
+            .unwrap_or_else(|_| DEFAULT_TIMEZONE.to_string());
+        let timezone = SchoolTimezone::from_name(&timezone_name).ok_or_else(|| {
+            format!(
+                "Unsupported CLASSY_SCHOOL_TIMEZONE '{}' in .env file",
+                timezone_name
+            )
+        })?;
+
         Ok(SyncConfig {
             server_url,
             server_port,
             db_path,
+            timezone,
         })
     }
 
@@ -146,13 +174,14 @@ fn fetch_all_sync_data(
 /// Parameters:
 /// --- ---
 /// config -> Sync configuration
+/// on_retry -> Called with a progress message when the database is busy and the sync is retried
 /// --- ---
 ///
 /// Returns:
 /// --- ---
 /// Result<PathBuf, String> -> Path to the synced database or error message
 /// --- ---
-pub fn sync_all(config: &SyncConfig) -> Result<PathBuf, String> {
+pub fn sync_all(config: &SyncConfig, on_retry: &mut dyn FnMut(&str)) -> Result<PathBuf, String> {
     // set server URL and port in environment for classy-sync to use
     std::env::set_var("CLASSY_SERVER_URL", &config.server_url);
     std::env::set_var("CLASSY_SERVER_PORT", config.server_port.to_string());
@@ -170,28 +199,48 @@ pub fn sync_all(config: &SyncConfig) -> Result<PathBuf, String> {
         .ok_or_else(|| "Invalid database path".to_string())?;
     std::env::set_var("SQLITE_DB_PATH", db_path_str);
 
-    // initialize SQLite datastore
-    let mut datastore =
-        Sqlite::new().map_err(|e| format!("Failed to initialize SQLite datastore: {}", e))?;
+    // a sync that hits a locked database while reading its options is fetched again
+    let sync_result = retry_when_busy(on_retry, || {
+        // switch to WAL before classy-sync opens the file so searches can keep reading during the sync
+        open_write_connection(&config.db_path)?;
+
+        // initialize SQLite datastore
+        let mut datastore =
+            Sqlite::new().map_err(|e| format!("Failed to initialize SQLite datastore: {}", e))?;
 
-    // set sync resources to sync everything
-    datastore
-        .set_request_sync_resources(SyncResources::Everything)
-        .map_err(|e| format!("Failed to set sync resources: {}", e))?;
+        // set sync resources to sync everything
+        datastore
+            .set_request_sync_resources(SyncResources::Everything)
+            .map_err(|e| format!("Failed to set sync resources: {}", e))?;
 
-    // generate sync options (this reads the current state from the database)
-    let sync_options = datastore
-        .generate_sync_options()
-        .map_err(|e| format!("Failed to generate sync options: {}", e))?;
+        // generate sync options (this reads the current state from the database)
+        let sync_options = datastore
+            .generate_sync_options()
+            .map_err(|e| format!("Failed to generate sync options: {}", e))?;
 
-    // fetch sync data from the classy server
-    let endpoint = config.all_sync_endpoint();
-    let sync_result = fetch_all_sync_data(&endpoint, &sync_options)?;
+        // fetch sync data from the classy server
+        let endpoint = config.all_sync_endpoint();
+        fetch_all_sync_data(&endpoint, &sync_options)
+    })?;
 
-    // execute the sync (applies the data to the local database)
-    datastore
-        .execute_all_request_sync(sync_result)
-        .map_err(|e| format!("Failed to execute sync: {}", e))?;
+    // apply the sync's rows and its sequence together, so a failed sync is fetched again
+    write_transaction(&config.db_path, on_retry, |tx| {
+        tx.execute(
+            "INSERT INTO _previous_all_collections (synced_at) VALUES (?1)",
+            [sync_result.new_latest_sync],
+        )
+        .map_err(|e| format!("Failed to record sync sequence: {}", e))?;
+        apply_sync_data(tx, &sync_result.sync_data)
+    })?;
+
+    record_term_calendars(&config.db_path, &config.timezone, on_retry)?;
+    write_transaction(&config.db_path, on_retry, |tx| {
+        normalize_professor_names(tx)
+    })?;
+    write_transaction(&config.db_path, on_retry, |tx| {
+        normalize_instruction_methods(tx)
+    })?;
+    write_transaction(&config.db_path, on_retry, |tx| create_catalog_index(tx))?;
 
     Ok(config.db_path.clone())
 }
@@ -203,13 +252,18 @@ pub fn sync_all(config: &SyncConfig) -> Result<PathBuf, String> {
 /// config -> Sync configuration
 /// schools -> Comma-separated list of school IDs and optional term collection IDs
 ///            Format: "school1;school2,term1;school3,term2"
+/// on_retry -> Called with a progress message when the database is busy and the sync is retried
 /// --- ---
 ///
 /// Returns:
 /// --- ---
 /// Result<PathBuf, String> -> Path to the synced database or error message
 /// --- ---
-pub fn sync_schools(config: &SyncConfig, schools: &str) -> Result<PathBuf, String> {
+pub fn sync_schools(
+    config: &SyncConfig,
+    schools: &str,
+    on_retry: &mut dyn FnMut(&str),
+) -> Result<PathBuf, String> {
     use classy_sync::argument_parser::SelectSyncOptions;
     use classy_sync::data_stores::sync_requests::TermSyncResult;
 
@@ -228,63 +282,1188 @@ pub fn sync_schools(config: &SyncConfig, schools: &str) -> Result<PathBuf, Strin
         .ok_or_else(|| "Invalid database path".to_string())?;
     std::env::set_var("SQLITE_DB_PATH", db_path_str);
 
-    // initialize datastore
-    let mut datastore =
-        Sqlite::new().map_err(|e| format!("Failed to initialize SQLite datastore: {}", e))?;
-
-    // parse the schools string and create SelectSyncOptions
-    // format: "school1;school2,term1;school3,term2"
-    let select_options = SelectSyncOptions::from_input(schools.to_string());
-
-    // set sync resources for selected schools/terms
-    datastore
-        .set_request_sync_resources(SyncResources::Select(select_options))
-        .map_err(|e| format!("Failed to set sync resources: {}", e))?;
-
-    // generate sync options
-    let sync_options = datastore
-        .generate_sync_options()
-        .map_err(|e| format!("Failed to generate sync options: {}", e))?;
-
-    // extract SelectSync from options
-    let select_sync = match sync_options {
-        SyncOptions::Select(select) => select,
-        SyncOptions::All(_) => {
-            return Err("Expected SelectSync options but got AllSync".to_string());
+    let sync_result = retry_when_busy(on_retry, || {
+        open_write_connection(&config.db_path)?;
+
+        // initialize datastore
+        let mut datastore =
+            Sqlite::new().map_err(|e| format!("Failed to initialize SQLite datastore: {}", e))?;
+
+        // parse the schools string and create SelectSyncOptions
+        // format: "school1;school2,term1;school3,term2"
+        let select_options = SelectSyncOptions::from_input(schools.to_string());
+
+        // set sync resources for selected schools/terms
+        datastore
+            .set_request_sync_resources(SyncResources::Select(select_options))
+            .map_err(|e| format!("Failed to set sync resources: {}", e))?;
+
+        // generate sync options
+        let sync_options = datastore
+            .generate_sync_options()
+            .map_err(|e| format!("Failed to generate sync options: {}", e))?;
+
+        // extract SelectSync from options
+        let select_sync = match sync_options {
+            SyncOptions::Select(select) => select,
+            SyncOptions::All(_) => {
+                return Err("Expected SelectSync options but got AllSync".to_string());
+            }
+        };
+
+        // fetch sync data from server
+        let endpoint = format!("{}/sync/select", config.server_url_with_port());
+        let client = reqwest::blocking::Client::new();
+
+        let response = client
+            .post(&endpoint)
+            .json(&select_sync)
+            .send()
+            .map_err(|e| format!("Failed to connect to classy server: {}", e))?;
+
+        if !response.status().is_success() {
+            return Err(format!(
+                "Classy server returned error: {} - {}",
+                response.status(),
+                response
+                    .text()
+                    .unwrap_or_else(|_| "Unknown error".to_string())
+            ));
         }
-    };
 
-    // fetch sync data from server
-    let endpoint = format!("{}/sync/select", config.server_url_with_port());
-    let client = reqwest::blocking::Client::new();
+        // parse the response as TermSyncResult
+        response
+            .json::<TermSyncResult>()
+            .map_err(|e| format!("Failed to parse sync response: {}", e))
+    })?;
 
-    let response = client
-        .post(&endpoint)
-        .json(&select_sync)
-        .send()
-        .map_err(|e| format!("Failed to connect to classy server: {}", e))?;
+    // apply the sync's rows and the sequences synced to together
+    write_transaction(&config.db_path, on_retry, |tx| {
+        record_select_sequences(tx, &sync_result.new_sync_term_sequences)?;
+        apply_sync_data(tx, &sync_result.sync_data)
+    })?;
 
-    if !response.status().is_success() {
+    record_term_calendars(&config.db_path, &config.timezone, on_retry)?;
+    write_transaction(&config.db_path, on_retry, |tx| {
+        normalize_professor_names(tx)
+    })?;
+    write_transaction(&config.db_path, on_retry, |tx| {
+        normalize_instruction_methods(tx)
+    })?;
+    write_transaction(&config.db_path, on_retry, |tx| create_catalog_index(tx))?;
+
+    Ok(config.db_path.clone())
+}
+
+/// Most rows written by one multi-row statement
+///
+/// Keeps the widest table (meeting_times, 19 columns) well under SQLite's limit on bound
+/// parameters
+const SYNC_BATCH_ROWS: usize = 500;
+
+/// Rows a sync changed, and the ones it left alone
+///
+/// Fields:
+/// --- ---
+/// written -> Rows inserted or updated
+/// skipped -> Rows left alone because their content hash matched the last one written
+/// removed -> Rows deleted
+/// --- ---
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AppliedSync {
+    pub written: usize,
+    pub skipped: usize,
+    pub removed: usize,
+}
+
+/// One row of a sync, ready to be written
+///
+/// Fields:
+/// --- ---
+/// row_key -> The row's primary key values as a JSON array, in column order
+/// hash -> Hash of every column the sync sent for the row
+/// values -> The row's values, primary key columns first, in column order
+/// --- ---
+struct SyncRow {
+    row_key: String,
+    hash: i64,
+    values: Vec<SqlValue>,
+}
+
+/// Record the sequences a select sync reached, like classy-sync's datastore does
+///
+/// Parameters:
+/// --- ---
+/// conn -> Connection to the database (or a transaction on it)
+/// sequences -> Each school's sequence, or its terms' sequences
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<(), String> -> Ok on success or error message
+/// --- ---
+fn record_select_sequences(
+    conn: &Connection,
+    sequences: &HashMap<String, SchoolEntry>,
+) -> Result<(), String> {
+    let to_error = |e: rusqlite::Error| format!("Failed to record sync sequence: {}", e);
+    for (school_id, entry) in sequences {
+        match entry {
+            SchoolEntry::TermToSequence(terms) => {
+                for (term_id, sequence) in terms {
+                    conn.execute(
+                        "INSERT INTO _previous_term_collections \
+                         (synced_at, school_id, term_collection_id) VALUES (?1, ?2, ?3)",
+                        rusqlite::params![sequence, school_id, term_id],
+                    )
+                    .map_err(to_error)?;
+                }
+            }
+            SchoolEntry::Sequence(sequence) => {
+                conn.execute(
+                    "INSERT INTO _previous_school_collections (synced_at, school_id) \
+                     VALUES (?1, ?2)",
+                    rusqlite::params![sequence, school_id],
+                )
+                .map_err(to_error)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Apply a sync's rows with multi-row statements, skipping rows that haven't changed
+///
+/// Consecutive changes to the same table with the same action and columns are written
+/// together, so the changes still apply in the order the server sent them. Inserts are
+/// upserts, so a row sent again replaces the old one. Each row's content hash is kept in
+/// `_sync_row_hashes`; a row whose hash matches the last one written for it is skipped.
+/// Hashes of rows removed outside a sync (e.g., by purging a term) are ignored
+///
+/// Parameters:
+/// --- ---
+/// conn -> Connection to the database, normally a transaction the whole sync runs in
+/// sync_data -> The changes the classy server sent, in order
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<AppliedSync, String> -> How many rows were written, skipped, and removed, or
+///                                error message
+/// --- ---
+pub fn apply_sync_data(
+    conn: &Connection,
+    sync_data: &[ClassDataSync],
+) -> Result<AppliedSync, String> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS _sync_row_hashes (
+            table_name TEXT,
+            row_key TEXT,
+            content_hash INTEGER NOT NULL,
+            PRIMARY KEY (table_name, row_key)
+        )",
+    )
+    .map_err(|e| format!("Failed to create sync row hash table: {}", e))?;
+
+    let mut hashes: HashMap<String, HashMap<String, i64>> = HashMap::new();
+    let mut applied = AppliedSync::default();
+    let mut start = 0;
+    while start < sync_data.len() {
+        // the run of changes that can share statements with the first one
+        let first = &sync_data[start];
+        let (pk_columns, columns) = sync_columns(first)?;
+        let mut end = start + 1;
+        while end < sync_data.len() {
+            let next = &sync_data[end];
+            let same_kind = next.table_name.to_string() == first.table_name.to_string()
+                && std::mem::discriminant(&next.sync_action)
+                    == std::mem::discriminant(&first.sync_action);
+            let (next_pk_columns, next_columns) = sync_columns(next)?;
+            if !same_kind || next_pk_columns != pk_columns || next_columns != columns {
+                break;
+            }
+            end += 1;
+        }
+        let table = first.table_name.to_string();
+        let batch = &sync_data[start..end];
+        start = end;
+
+        if !hashes.contains_key(&table) {
+            let stored = load_row_hashes(conn, &table, &pk_columns)?;
+            hashes.insert(table.clone(), stored);
+        }
+        let table_hashes = hashes.entry(table.clone()).or_default();
+
+        if matches!(first.sync_action, SyncAction::Delete) {
+            let rows = batch
+                .iter()
+                .map(|change| sync_row(change, &pk_columns, &[]))
+                .collect::<Result<Vec<_>, String>>()?;
+            for row in &rows {
+                table_hashes.remove(&row.row_key);
+            }
+            applied.removed += delete_rows(conn, &table, &pk_columns, &rows)?;
+            continue;
+        }
+        if columns.is_empty() && matches!(first.sync_action, SyncAction::Update) {
+            // an update with nothing to change
+            applied.skipped += batch.len();
+            continue;
+        }
+
+        // a row changed twice in one run keeps its last change
+        let mut rows: Vec<SyncRow> = Vec::new();
+        let mut positions: HashMap<String, usize> = HashMap::new();
+        for change in batch {
+            let row = sync_row(change, &pk_columns, &columns)?;
+            if table_hashes.get(&row.row_key) == Some(&row.hash) {
+                applied.skipped += 1;
+                continue;
+            }
+            table_hashes.insert(row.row_key.clone(), row.hash);
+            match positions.get(&row.row_key) {
+                Some(&position) => {
+                    rows[position] = row;
+                    applied.skipped += 1;
+                }
+                None => {
+                    positions.insert(row.row_key.clone(), rows.len());
+                    rows.push(row);
+                }
+            }
+        }
+        if rows.is_empty() {
+            continue;
+        }
+
+        match first.sync_action {
+            SyncAction::Update => update_rows(conn, &table, &pk_columns, &columns, &rows)?,
+            _ => upsert_rows(conn, &table, &pk_columns, &columns, &rows)?,
+        }
+        record_row_hashes(conn, &table, &rows)?;
+        applied.written += rows.len();
+    }
+    Ok(applied)
+}
+
+/// Get a change's primary key columns and other columns, each sorted by name
+///
+/// Parameters:
+/// --- ---
+/// change -> The change the server sent
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<(Vec<String>, Vec<String>), String> -> The columns, or an error naming a column
+///                                               that isn't a plain SQL name
+/// --- ---
+fn sync_columns(change: &ClassDataSync) -> Result<(Vec<String>, Vec<String>), String> {
+    let mut pk_columns: Vec<String> = change.pk_fields.keys().cloned().collect();
+    let mut columns: Vec<String> = change
+        .relevant_fields
+        .iter()
+        .flat_map(|fields| fields.keys().cloned())
+        .collect();
+    pk_columns.sort();
+    columns.sort();
+
+    // column names are put into the SQL as sent, so anything but a plain name is refused
+    let is_name = |column: &String| {
+        column.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && column
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_')
+    };
+    if let Some(column) = pk_columns.iter().chain(&columns).find(|c| !is_name(c)) {
         return Err(format!(
-            "Classy server returned error: {} - {}",
-            response.status(),
-            response
-                .text()
-                .unwrap_or_else(|_| "Unknown error".to_string())
+            "Sync sent an invalid column '{}' for {}",
+            column, change.table_name
         ));
     }
+    if pk_columns.is_empty() {
+        return Err(format!(
+            "Sync sent a change to {} without its primary key",
+            change.table_name
+        ));
+    }
+    Ok((pk_columns, columns))
+}
 
-    // parse the response as TermSyncResult
-    let sync_result: TermSyncResult = response
-        .json()
-        .map_err(|e| format!("Failed to parse sync response: {}", e))?;
+/// Read a change into a row with its key and content hash
+///
+/// Parameters:
+/// --- ---
+/// change -> The change the server sent
+/// pk_columns -> The primary key columns, sorted
+/// columns -> The other columns written, sorted
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<SyncRow, String> -> The row, or an error for a value SQLite can't store
+/// --- ---
+fn sync_row(
+    change: &ClassDataSync,
+    pk_columns: &[String],
+    columns: &[String],
+) -> Result<SyncRow, String> {
+    let empty = HashMap::new();
+    let fields = change.relevant_fields.as_ref().unwrap_or(&empty);
+    let key: Vec<&Value> = pk_columns
+        .iter()
+        .map(|column| &change.pk_fields[column])
+        .collect();
+    let content: Vec<(&String, &Value)> = pk_columns
+        .iter()
+        .zip(&key)
+        .map(|(column, value)| (column, *value))
+        .chain(columns.iter().map(|column| (column, &fields[column])))
+        .collect();
 
-    // execute the sync
-    datastore
-        .execute_select_request_sync(select_sync, sync_result)
-        .map_err(|e| format!("Failed to execute sync: {}", e))?;
+    // the key is written the way SQLite's json_array prints the stored values
+    let row_key = Value::Array(key.iter().map(|value| stored_json(value)).collect());
+    let content =
+        serde_json::to_string(&content).map_err(|e| format!("Failed to hash synced row: {}", e))?;
+    let values = pk_columns
+        .iter()
+        .map(|column| &change.pk_fields[column])
+        .chain(columns.iter().map(|column| &fields[column]))
+        .map(|value| sql_value(value, &change.table_name))
+        .collect::<Result<_, _>>()?;
 
-    Ok(config.db_path.clone())
+    Ok(SyncRow {
+        row_key: row_key.to_string(),
+        hash: content_hash(&content),
+        values,
+    })
+}
+
+/// Convert a synced JSON value to the value SQLite stores
+///
+/// Parameters:
+/// --- ---
+/// value -> The value the server sent
+/// table -> The table it is written to, for the error message
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<SqlValue, String> -> The value, or an error for arrays and objects
+/// --- ---
+fn sql_value(value: &Value, table: &TableName) -> Result<SqlValue, String> {
+    match value {
+        Value::Null => Ok(SqlValue::Null),
+        Value::Bool(b) => Ok(SqlValue::Integer(*b as i64)),
+        Value::Number(n) => Ok(n
+            .as_i64()
+            .map(SqlValue::Integer)
+            .or_else(|| n.as_f64().map(SqlValue::Real))
+            .unwrap_or(SqlValue::Null)),
+        Value::String(s) => Ok(SqlValue::Text(s.clone())),
+        _ => Err(format!(
+            "Sync sent an unsupported value for {}: {}",
+            table, value
+        )),
+    }
+}
+
+/// Get a synced JSON value as SQLite would print it back with json_array
+///
+/// Parameters:
+/// --- ---
+/// value -> The value the server sent
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Value -> The value, with booleans stored as 0 and 1
+/// --- ---
+fn stored_json(value: &Value) -> Value {
+    match value {
+        Value::Bool(b) => Value::from(*b as i64),
+        _ => value.clone(),
+    }
+}
+
+/// Hash a row's content with 64-bit FNV-1a, which stays the same between builds
+///
+/// Parameters:
+/// --- ---
+/// content -> The row's columns and values as JSON
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// i64 -> The hash, as SQLite stores integers
+/// --- ---
+fn content_hash(content: &str) -> i64 {
+    let hash = content
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+        });
+    hash as i64
+}
+
+/// Load the content hashes of a table's rows that still exist
+///
+/// Parameters:
+/// --- ---
+/// conn -> Connection to the database
+/// table -> The table
+/// pk_columns -> The table's primary key columns, sorted
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<HashMap<String, i64>, String> -> Each row's key and hash, or error message
+/// --- ---
+fn load_row_hashes(
+    conn: &Connection,
+    table: &str,
+    pk_columns: &[String],
+) -> Result<HashMap<String, i64>, String> {
+    let sql = format!(
+        "SELECT row_key, content_hash FROM _sync_row_hashes \
+         WHERE table_name = ?1 AND row_key IN (SELECT json_array({}) FROM {})",
+        pk_columns.join(", "),
+        table
+    );
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| format!("SQL preparation error: {}", e))?;
+    let hashes = stmt
+        .query_map([table], |row| Ok((row.get(0)?, row.get(1)?)))
+        .map_err(|e| format!("Query execution error: {}", e))?
+        .collect::<Result<_, _>>()
+        .map_err(|e| format!("Failed to read sync row hashes: {}", e))?;
+    Ok(hashes)
+}
+
+/// Get the placeholders of a multi-row VALUES list
+///
+/// Parameters:
+/// --- ---
+/// rows -> Number of rows
+/// columns -> Number of values in each row
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> e.g., "(?, ?), (?, ?)"
+/// --- ---
+fn values_placeholders(rows: usize, columns: usize) -> String {
+    let row = format!("({})", vec!["?"; columns].join(", "));
+    vec![row; rows].join(", ")
+}
+
+/// Insert rows, replacing the columns sent of rows that already exist
+///
+/// Parameters:
+/// --- ---
+/// conn -> Connection to the database
+/// table -> The table
+/// pk_columns -> The primary key columns, sorted
+/// columns -> The other columns, sorted
+/// rows -> The rows
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<(), String> -> Ok on success or error message
+/// --- ---
+fn upsert_rows(
+    conn: &Connection,
+    table: &str,
+    pk_columns: &[String],
+    columns: &[String],
+    rows: &[SyncRow],
+) -> Result<(), String> {
+    let all_columns: Vec<&String> = pk_columns.iter().chain(columns).collect();
+    let on_conflict = if columns.is_empty() {
+        "DO NOTHING".to_string()
+    } else {
+        let updates: Vec<String> = columns
+            .iter()
+            .map(|column| format!("{0} = excluded.{0}", column))
+            .collect();
+        format!("DO UPDATE SET {}", updates.join(", "))
+    };
+    for chunk in rows.chunks(SYNC_BATCH_ROWS) {
+        let sql = format!(
+            "INSERT INTO {} ({}) VALUES {} ON CONFLICT ({}) {}",
+            table,
+            all_columns
+                .iter()
+                .map(|column| column.as_str())
+                .collect::<Vec<_>>()
+                .join(", "),
+            values_placeholders(chunk.len(), all_columns.len()),
+            pk_columns.join(", "),
+            on_conflict
+        );
+        execute_rows(conn, &sql, chunk)
+            .map_err(|e| format!("Failed to insert synced rows into {}: {}", table, e))?;
+    }
+    Ok(())
+}
+
+/// Update the columns sent of existing rows
+///
+/// Parameters:
+/// --- ---
+/// conn -> Connection to the database
+/// table -> The table
+/// pk_columns -> The primary key columns, sorted
+/// columns -> The columns to update, sorted
+/// rows -> The rows
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<(), String> -> Ok on success or error message
+/// --- ---
+fn update_rows(
+    conn: &Connection,
+    table: &str,
+    pk_columns: &[String],
+    columns: &[String],
+    rows: &[SyncRow],
+) -> Result<(), String> {
+    let all_columns: Vec<&str> = pk_columns
+        .iter()
+        .chain(columns)
+        .map(|column| column.as_str())
+        .collect();
+    let updates: Vec<String> = columns
+        .iter()
+        .map(|column| format!("{0} = changes.{0}", column))
+        .collect();
+    let matches: Vec<String> = pk_columns
+        .iter()
+        .map(|column| format!("{0}.{1} = changes.{1}", table, column))
+        .collect();
+    for chunk in rows.chunks(SYNC_BATCH_ROWS) {
+        let sql = format!(
+            "WITH changes ({}) AS (VALUES {}) UPDATE {} SET {} FROM changes WHERE {}",
+            all_columns.join(", "),
+            values_placeholders(chunk.len(), all_columns.len()),
+            table,
+            updates.join(", "),
+            matches.join(" AND ")
+        );
+        execute_rows(conn, &sql, chunk)
+            .map_err(|e| format!("Failed to update synced rows in {}: {}", table, e))?;
+    }
+    Ok(())
+}
+
+/// Delete rows by their primary keys
+///
+/// Parameters:
+/// --- ---
+/// conn -> Connection to the database
+/// table -> The table
+/// pk_columns -> The primary key columns, sorted
+/// rows -> The rows, holding only their primary key values
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<usize, String> -> Number of rows deleted, or error message
+/// --- ---
+fn delete_rows(
+    conn: &Connection,
+    table: &str,
+    pk_columns: &[String],
+    rows: &[SyncRow],
+) -> Result<usize, String> {
+    let mut removed = 0;
+    for chunk in rows.chunks(SYNC_BATCH_ROWS) {
+        let sql = format!(
+            "DELETE FROM {} WHERE ({}) IN (VALUES {})",
+            table,
+            pk_columns.join(", "),
+            values_placeholders(chunk.len(), pk_columns.len())
+        );
+        removed += execute_rows(conn, &sql, chunk)
+            .map_err(|e| format!("Failed to delete synced rows from {}: {}", table, e))?;
+
+        let keys = format!("({})", vec!["?"; chunk.len()].join(", "));
+        let mut forget = conn
+            .prepare_cached(&format!(
+                "DELETE FROM _sync_row_hashes WHERE table_name = ? AND row_key IN {}",
+                keys
+            ))
+            .map_err(|e| format!("SQL preparation error: {}", e))?;
+        let params = std::iter::once(SqlValue::Text(table.to_string()))
+            .chain(chunk.iter().map(|row| SqlValue::Text(row.row_key.clone())));
+        forget
+            .execute(params_from_iter(params))
+            .map_err(|e| format!("Failed to forget synced row hashes: {}", e))?;
+    }
+    Ok(removed)
+}
+
+/// Record the content hashes of rows just written
+///
+/// Parameters:
+/// --- ---
+/// conn -> Connection to the database
+/// table -> The table the rows were written to
+/// rows -> The rows
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<(), String> -> Ok on success or error message
+/// --- ---
+fn record_row_hashes(conn: &Connection, table: &str, rows: &[SyncRow]) -> Result<(), String> {
+    for chunk in rows.chunks(SYNC_BATCH_ROWS) {
+        let mut stmt = conn
+            .prepare_cached(&format!(
+                "INSERT INTO _sync_row_hashes (table_name, row_key, content_hash) VALUES {} \
+                 ON CONFLICT (table_name, row_key) DO UPDATE SET content_hash = excluded.content_hash",
+                values_placeholders(chunk.len(), 3)
+            ))
+            .map_err(|e| format!("SQL preparation error: {}", e))?;
+        let params = chunk.iter().flat_map(|row| {
+            [
+                SqlValue::Text(table.to_string()),
+                SqlValue::Text(row.row_key.clone()),
+                SqlValue::Integer(row.hash),
+            ]
+        });
+        stmt.execute(params_from_iter(params))
+            .map_err(|e| format!("Failed to record synced row hashes: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Run a multi-row statement with the values of each row, in order
+///
+/// Parameters:
+/// --- ---
+/// conn -> Connection to the database
+/// sql -> The statement, with a placeholder for each value of each row
+/// rows -> The rows
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<usize, rusqlite::Error> -> Number of rows the statement changed
+/// --- ---
+fn execute_rows(conn: &Connection, sql: &str, rows: &[SyncRow]) -> Result<usize, rusqlite::Error> {
+    let mut stmt = conn.prepare_cached(sql)?;
+    stmt.execute(params_from_iter(
+        rows.iter().flat_map(|row| row.values.iter().cloned()),
+    ))
+}
+
+/// Record each term's dates and timezone after a sync
+///
+/// Term start/end dates are taken from the span of the term's meeting dates. The first and
+/// last days sessions are worked out from are recorded with them (see record_term_spans)
+///
+/// Parameters:
+/// --- ---
+/// db_path -> Path to the synced database
+/// timezone -> Timezone of the synced schools' meeting times
+/// on_retry -> Called with a progress message when the database is busy
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<(), String> -> Ok on success or error message
+/// --- ---
+pub fn record_term_calendars(
+    db_path: &Path,
+    timezone: &SchoolTimezone,
+    on_retry: &mut dyn FnMut(&str),
+) -> Result<(), String> {
+    write_transaction(db_path, on_retry, |tx| {
+        tx.execute_batch(
+            "CREATE TABLE IF NOT EXISTS term_calendars (
+                term_collection_id TEXT,
+                school_id TEXT,
+                start_date TEXT,
+                end_date TEXT,
+                timezone TEXT NOT NULL,
+                PRIMARY KEY (term_collection_id, school_id)
+            )",
+        )
+        .map_err(|e| format!("Failed to create term calendar table: {}", e))?;
+
+        tx.execute(
+            "INSERT OR REPLACE INTO term_calendars \
+             (term_collection_id, school_id, start_date, end_date, timezone) \
+             SELECT term_collection_id, school_id, MIN(start_date), MAX(end_date), ? \
+             FROM meeting_times GROUP BY term_collection_id, school_id",
+            [&timezone.name],
+        )
+        .map_err(|e| format!("Failed to record term calendars: {}", e))?;
+
+        record_term_spans(tx)
+    })
+}
+
+/// Record the first and last days of each term in the term_spans table
+///
+/// Searches read a section's session from them, so they are counted from the term's
+/// meetings once per sync instead of on every search
+///
+/// Parameters:
+/// --- ---
+/// conn -> Connection to the database (or a transaction on it)
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<(), String> -> Ok on success or error message
+/// --- ---
+pub fn record_term_spans(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS main.term_spans (
+            term_collection_id TEXT,
+            school_id TEXT,
+            first_day TEXT,
+            last_day TEXT,
+            PRIMARY KEY (term_collection_id, school_id)
+        )",
+    )
+    .map_err(|e| format!("Failed to create term span table: {}", e))?;
+
+    // named in main, since connections to a database without the table stand a view in
+    // for it (see pool::define_term_spans); terms no longer in the data drop their span
+    conn.execute("DELETE FROM main.term_spans", [])
+        .map_err(|e| format!("Failed to record term spans: {}", e))?;
+    conn.execute(
+        &format!(
+            "INSERT INTO main.term_spans (term_collection_id, school_id, first_day, last_day) \
+             SELECT term_collection_id, school_id, first_day, last_day FROM {}",
+            TERM_SPANS_SQL
+        ),
+        [],
+    )
+    .map_err(|e| format!("Failed to record term spans: {}", e))?;
+
+    Ok(())
+}
+
+/// Rewrite sections' instruction methods as one of sql::INSTRUCTION_METHODS
+///
+/// Schools spell the same method differently ("WEB", "Online", "ONLN"), so after a sync each
+/// known spelling is replaced with the method it stands for and "method is online" finds the
+/// online sections at every school. Methods that aren't a known spelling are kept as synced
+///
+/// Parameters:
+/// --- ---
+/// conn -> Connection to the database (or a transaction on it)
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<usize, String> -> Number of distinct methods rewritten, or error message
+/// --- ---
+pub fn normalize_instruction_methods(conn: &Connection) -> Result<usize, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT DISTINCT instruction_method FROM sections \
+             WHERE instruction_method IS NOT NULL",
+        )
+        .map_err(|e| format!("SQL preparation error: {}", e))?;
+    let methods: Vec<String> = stmt
+        .query_map([], |row| row.get(0))
+        .map_err(|e| format!("Query execution error: {}", e))?
+        .collect::<Result<_, _>>()
+        .map_err(|e| format!("Failed to read instruction methods: {}", e))?;
+
+    let mut update = conn
+        .prepare("UPDATE sections SET instruction_method = ?1 WHERE instruction_method = ?2")
+        .map_err(|e| format!("SQL preparation error: {}", e))?;
+    let mut changed = 0;
+    for method in methods {
+        let normalized = normalize_instruction_method(&method);
+        if normalized == method {
+            continue;
+        }
+        update
+            .execute([&normalized, &method])
+            .map_err(|e| format!("Failed to normalize instruction methods: {}", e))?;
+        changed += 1;
+    }
+    Ok(changed)
+}
+
+/// Changes a sync would make to one term
+///
+/// Fields:
+/// --- ---
+/// school_id -> School the term belongs to
+/// term_id -> Term collection ID
+/// new_sections -> Sections that would be added
+/// updated_sections -> Sections whose details or meeting times would change
+/// removed_sections -> Sections that would be removed
+/// --- ---
+#[derive(Debug, Clone, Default)]
+pub struct TermDiff {
+    pub school_id: String,
+    pub term_id: String,
+    pub new_sections: usize,
+    pub updated_sections: usize,
+    pub removed_sections: usize,
+}
+
+/// Changes a sync would make to the local database
+///
+/// Fields:
+/// --- ---
+/// new_sections -> Total sections that would be added
+/// updated_sections -> Total sections that would change
+/// removed_sections -> Total sections that would be removed
+/// terms -> Per-term breakdown, only terms with changes
+/// --- ---
+#[derive(Debug, Clone, Default)]
+pub struct SyncDiff {
+    pub new_sections: usize,
+    pub updated_sections: usize,
+    pub removed_sections: usize,
+    pub terms: Vec<TermDiff>,
+}
+
+impl SyncDiff {
+    /// Check whether the sync would change anything
+    ///
+    /// Returns:
+    /// --- ---
+    /// bool -> true if no sections would be added, updated, or removed
+    /// --- ---
+    pub fn is_empty(&self) -> bool {
+        self.new_sections == 0 && self.updated_sections == 0 && self.removed_sections == 0
+    }
+
+    /// Summarize the diff in one line
+    ///
+    /// Returns:
+    /// --- ---
+    /// String -> Summary (e.g., "12 new sections, 3 updated, 1 removed")
+    /// --- ---
+    pub fn summary(&self) -> String {
+        format!(
+            "{} new sections, {} updated, {} removed",
+            self.new_sections, self.updated_sections, self.removed_sections
+        )
+    }
+}
+
+/// A sync fetched into a staging copy of the database, waiting to be applied
+///
+/// Fields:
+/// --- ---
+/// diff -> Changes the sync would make
+/// staged_path -> Path to the staging copy holding the synced data
+/// target_path -> Path to the database the sync would replace
+/// --- ---
+#[derive(Debug)]
+pub struct SyncPreview {
+    pub diff: SyncDiff,
+    pub staged_path: PathBuf,
+    pub target_path: PathBuf,
+}
+
+impl SyncPreview {
+    /// Apply the previewed sync by replacing the local database with the staging copy
+    ///
+    /// Returns:
+    /// --- ---
+    /// Result<PathBuf, String> -> Path to the updated database or error message
+    /// --- ---
+    pub fn commit(self) -> Result<PathBuf, String> {
+        // both files must be fully checkpointed or their write-ahead logs would be lost
+        checkpoint(&self.staged_path)?;
+        if self.target_path.exists() {
+            checkpoint(&self.target_path)?;
+        }
+        remove_wal_files(&self.staged_path);
+        fs::rename(&self.staged_path, &self.target_path)
+            .map_err(|e| format!("Failed to apply synced data: {}", e))?;
+        Ok(self.target_path)
+    }
+
+    /// Throw away the previewed sync, leaving the local database untouched
+    ///
+    /// Returns:
+    /// --- ---
+    /// Result<(), String> -> Ok on success or error message
+    /// --- ---
+    pub fn discard(self) -> Result<(), String> {
+        remove_wal_files(&self.staged_path);
+        fs::remove_file(&self.staged_path)
+            .map_err(|e| format!("Failed to remove staged sync data: {}", e))
+    }
+}
+
+/// Remove the -wal and -shm files SQLite keeps next to a database in WAL mode
+///
+/// Parameters:
+/// --- ---
+/// db_path -> Path to the database whose side files should be removed
+/// --- ---
+fn remove_wal_files(db_path: &Path) {
+    for suffix in ["-wal", "-shm"] {
+        let mut side_file = db_path.as_os_str().to_owned();
+        side_file.push(suffix);
+        let _ = fs::remove_file(side_file);
+    }
+}
+
+/// Sync into a staging copy of the database and report what would change
+///
+/// The local database is not modified; call commit() on the result to apply the sync
+///
+/// Parameters:
+/// --- ---
+/// config -> Sync configuration
+/// on_retry -> Called with a progress message when the database is busy and the sync is retried
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<SyncPreview, String> -> The staged sync and its diff, or error message
+/// --- ---
+pub fn preview_sync_all(
+    config: &SyncConfig,
+    on_retry: &mut dyn FnMut(&str),
+) -> Result<SyncPreview, String> {
+    let staged_path = config.db_path.with_extension("preview.db");
+    if staged_path.exists() {
+        fs::remove_file(&staged_path)
+            .map_err(|e| format!("Failed to remove old staged sync data: {}", e))?;
+    }
+
+    // VACUUM INTO takes a consistent snapshot even while the database is open elsewhere
+    if config.db_path.exists() {
+        let conn = open_connection(&config.db_path)?;
+        conn.execute(
+            "VACUUM INTO ?1",
+            [staged_path.to_string_lossy().to_string()],
+        )
+        .map_err(|e| format!("Failed to stage database copy: {}", e))?;
+    }
+
+    let staged_config = SyncConfig {
+        db_path: staged_path.clone(),
+        ..config.clone()
+    };
+    let diff = sync_all(&staged_config, on_retry)
+        .and_then(|_| diff_databases(&config.db_path, &staged_path))
+        .inspect_err(|_| {
+            remove_wal_files(&staged_path);
+            let _ = fs::remove_file(&staged_path);
+        })?;
+
+    Ok(SyncPreview {
+        diff,
+        staged_path,
+        target_path: config.db_path.clone(),
+    })
+}
+
+/// Compare the sections of two databases
+///
+/// A section counts as updated if any of its columns or meeting times differ. If a table's
+/// columns differ between the two databases (the schema changed between versions), the
+/// sync replaces every section, so every section in both counts as updated
+///
+/// Parameters:
+/// --- ---
+/// current -> Path to the current database (may not exist yet)
+/// staged -> Path to the database with the new data
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<SyncDiff, String> -> Per-term and total section changes or error message
+/// --- ---
+pub fn diff_databases(current: &Path, staged: &Path) -> Result<SyncDiff, String> {
+    let conn = open_connection(staged)?;
+
+    let mut terms: BTreeMap<(String, String), TermDiff> = BTreeMap::new();
+
+    if !current.exists() {
+        // nothing synced yet, every section is new
+        for (school_id, term_id, count) in count_sections_by_term(
+            &conn,
+            "SELECT school_id, term_collection_id, COUNT(*) FROM sections \
+             GROUP BY school_id, term_collection_id",
+        )? {
+            let entry = terms.entry((school_id, term_id)).or_default();
+            entry.new_sections = count;
+        }
+    } else {
+        conn.execute(
+            "ATTACH DATABASE ?1 AS current",
+            [current.to_string_lossy().to_string()],
+        )
+        .map_err(|e| format!("Failed to attach current database: {}", e))?;
+
+        let added = count_sections_by_term(
+            &conn,
+            "SELECT school_id, term_collection_id, COUNT(*) FROM ( \
+                SELECT sequence, term_collection_id, subject_code, course_number, school_id FROM main.sections \
+                EXCEPT \
+                SELECT sequence, term_collection_id, subject_code, course_number, school_id FROM current.sections \
+             ) GROUP BY school_id, term_collection_id",
+        )?;
+        let removed = count_sections_by_term(
+            &conn,
+            "SELECT school_id, term_collection_id, COUNT(*) FROM ( \
+                SELECT sequence, term_collection_id, subject_code, course_number, school_id FROM current.sections \
+                EXCEPT \
+                SELECT sequence, term_collection_id, subject_code, course_number, school_id FROM main.sections \
+             ) GROUP BY school_id, term_collection_id",
+        )?;
+        // sections present in both whose own row or any meeting time differs, or every one of
+        // them if the tables' columns changed and the rows can't be compared
+        let changed = match (
+            matching_columns(&conn, "sections")?,
+            matching_columns(&conn, "meeting_times")?,
+        ) {
+            (Some(section_columns), Some(meeting_columns)) => format!(
+                "SELECT sequence AS seq, term_collection_id, subject_code, course_number, school_id \
+                 FROM (SELECT {s} FROM main.sections EXCEPT SELECT {s} FROM current.sections) \
+                 UNION \
+                 SELECT section_sequence, term_collection_id, subject_code, course_number, school_id \
+                 FROM (SELECT {m} FROM main.meeting_times EXCEPT SELECT {m} FROM current.meeting_times) \
+                 UNION \
+                 SELECT section_sequence, term_collection_id, subject_code, course_number, school_id \
+                 FROM (SELECT {m} FROM current.meeting_times EXCEPT SELECT {m} FROM main.meeting_times)",
+                s = section_columns,
+                m = meeting_columns
+            ),
+            _ => "SELECT sequence AS seq, term_collection_id, subject_code, course_number, school_id \
+                  FROM main.sections"
+                .to_string(),
+        };
+        let updated = count_sections_by_term(
+            &conn,
+            &format!(
+                "SELECT changed.school_id, changed.term_collection_id, COUNT(*) FROM ({}) changed \
+                 WHERE EXISTS (SELECT 1 FROM current.sections c WHERE c.sequence = changed.seq \
+                        AND c.term_collection_id = changed.term_collection_id AND c.subject_code = changed.subject_code \
+                        AND c.course_number = changed.course_number AND c.school_id = changed.school_id) \
+                   AND EXISTS (SELECT 1 FROM main.sections s WHERE s.sequence = changed.seq \
+                        AND s.term_collection_id = changed.term_collection_id AND s.subject_code = changed.subject_code \
+                        AND s.course_number = changed.course_number AND s.school_id = changed.school_id) \
+                 GROUP BY changed.school_id, changed.term_collection_id",
+                changed
+            ),
+        )?;
+
+        for (school_id, term_id, count) in added {
+            terms.entry((school_id, term_id)).or_default().new_sections = count;
+        }
+        for (school_id, term_id, count) in removed {
+            terms
+                .entry((school_id, term_id))
+                .or_default()
+                .removed_sections = count;
+        }
+        for (school_id, term_id, count) in updated {
+            terms
+                .entry((school_id, term_id))
+                .or_default()
+                .updated_sections = count;
+        }
+    }
+
+    let mut diff = SyncDiff::default();
+    for ((school_id, term_id), mut term) in terms {
+        diff.new_sections += term.new_sections;
+        diff.updated_sections += term.updated_sections;
+        diff.removed_sections += term.removed_sections;
+        term.school_id = school_id;
+        term.term_id = term_id;
+        diff.terms.push(term);
+    }
+
+    Ok(diff)
+}
+
+/// List a table's columns if they're the same in the main and attached current databases
+///
+/// Parameters:
+/// --- ---
+/// conn -> Connection with the current database attached
+/// table -> Name of the table
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<Option<String>, String> -> The quoted, comma-separated columns, None if the two
+///                                   tables' columns differ, or error message
+/// --- ---
+fn matching_columns(conn: &Connection, table: &str) -> Result<Option<String>, String> {
+    let columns = |schema: &str| -> Result<Vec<String>, String> {
+        let mut stmt = conn
+            .prepare("SELECT name FROM pragma_table_info(?1, ?2) ORDER BY cid")
+            .map_err(|e| format!("SQL preparation error: {}", e))?;
+        let names = stmt
+            .query_map([table, schema], |row| row.get::<_, String>(0))
+            .map_err(|e| format!("Failed to read the columns of {}: {}", table, e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to read the columns of {}: {}", table, e))?;
+        Ok(names)
+    };
+
+    let main = columns("main")?;
+    let mut current = columns("current")?;
+    let mut sorted = main.clone();
+    sorted.sort();
+    current.sort();
+    if sorted != current {
+        return Ok(None);
+    }
+    Ok(Some(
+        main.iter()
+            .map(|name| format!("\"{}\"", name.replace('"', "\"\"")))
+            .collect::<Vec<_>>()
+            .join(", "),
+    ))
+}
+
+/// Run a query returning (school_id, term_id, count) rows
+///
+/// Parameters:
+/// --- ---
+/// conn -> Open database connection
+/// sql -> Query selecting school ID, term ID, and a count
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<Vec<(String, String, usize)>, String> -> The rows or error message
+/// --- ---
+fn count_sections_by_term(
+    conn: &Connection,
+    sql: &str,
+) -> Result<Vec<(String, String, usize)>, String> {
+    let mut stmt = conn
+        .prepare(sql)
+        .map_err(|e| format!("SQL preparation error: {}", e))?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, i64>(2)? as usize,
+            ))
+        })
+        .map_err(|e| format!("Failed to compare databases: {}", e))?
+        .filter_map(Result::ok)
+        .collect();
+    Ok(rows)
 }
 
 /// Get the synced database path
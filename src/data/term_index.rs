@@ -0,0 +1,387 @@
+/*
+    src/data/term_index.rs
+
+    Module for the in-memory index of the active term
+    Every section of the active term is read once in the background and indexed by subject,
+    professor surname, and the days it meets, so the result filter narrows results without
+    going back to SQLite
+*/
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::Path;
+
+use crate::data::sql::{execute_query, Class};
+use crate::utils::text::fold_text;
+use crate::utils::time::{parse_days, DAY_CODES};
+
+/// Sections of a term with lookup tables over them
+///
+/// Fields:
+/// --- ---
+/// classes -> The indexed sections
+/// by_id -> Position of each section by unique ID
+/// by_subject -> Positions of the sections of each subject code (uppercase)
+/// by_professor -> Positions of the sections taught by each professor, by folded surname
+/// day_masks -> Bitmask of the days each section meets (bit i is DAY_CODES[i])
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for TermIndex
+/// Default -> Default trait for TermIndex
+/// --- ---
+#[derive(Debug, Default)]
+pub struct TermIndex {
+    classes: Vec<Class>,
+    by_id: HashMap<String, usize>,
+    by_subject: HashMap<String, Vec<usize>>,
+    by_professor: BTreeMap<String, Vec<usize>>,
+    day_masks: Vec<u8>,
+}
+
+impl TermIndex {
+    /// Index a list of sections
+    ///
+    /// Parameters:
+    /// --- ---
+    /// classes -> The sections to index
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// TermIndex -> The index
+    /// --- ---
+    pub fn build(classes: Vec<Class>) -> Self {
+        let mut index = TermIndex {
+            day_masks: classes.iter().map(class_day_mask).collect(),
+            ..TermIndex::default()
+        };
+        for (position, class) in classes.iter().enumerate() {
+            index.by_id.insert(class.unique_id(), position);
+            index
+                .by_subject
+                .entry(class.subject_code.to_uppercase())
+                .or_default()
+                .push(position);
+            if let Some(surname) = class.professor_name.as_deref().map(professor_surname) {
+                index
+                    .by_professor
+                    .entry(surname)
+                    .or_default()
+                    .push(position);
+            }
+        }
+        index.classes = classes;
+        index
+    }
+
+    /// Get the number of indexed sections
+    ///
+    /// Returns:
+    /// --- ---
+    /// usize -> The number of sections
+    /// --- ---
+    pub fn len(&self) -> usize {
+        self.classes.len()
+    }
+
+    /// Check whether the index has no sections
+    ///
+    /// Returns:
+    /// --- ---
+    /// bool -> true if nothing is indexed
+    /// --- ---
+    pub fn is_empty(&self) -> bool {
+        self.classes.is_empty()
+    }
+
+    /// Get every indexed section
+    ///
+    /// Returns:
+    /// --- ---
+    /// &[Class] -> The sections, in the order they were indexed
+    /// --- ---
+    pub fn classes(&self) -> &[Class] {
+        &self.classes
+    }
+
+    /// Get a section by unique ID
+    ///
+    /// Parameters:
+    /// --- ---
+    /// unique_id -> The section's unique ID (see Class::unique_id)
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Option<&Class> -> The section, None if it isn't indexed
+    /// --- ---
+    pub fn class(&self, unique_id: &str) -> Option<&Class> {
+        self.by_id
+            .get(unique_id)
+            .map(|&position| &self.classes[position])
+    }
+
+    /// Check whether any section has a subject code, ignoring case
+    ///
+    /// Parameters:
+    /// --- ---
+    /// subject -> The subject code
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// bool -> true if the subject is indexed
+    /// --- ---
+    pub fn has_subject(&self, subject: &str) -> bool {
+        self.by_subject.contains_key(&subject.to_uppercase())
+    }
+
+    /// Get the unique IDs of the sections matching a filter
+    ///
+    /// Candidates come from the subject or professor table when the filter has one, so only
+    /// those sections are checked against the rest of the filter
+    ///
+    /// Parameters:
+    /// --- ---
+    /// filter -> The filter to apply
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// BTreeSet<String> -> Unique IDs of the matching sections
+    /// --- ---
+    pub fn matching_ids(&self, filter: &ClassFilter) -> BTreeSet<String> {
+        let candidates: Vec<usize> = if !filter.subjects.is_empty() {
+            filter
+                .subjects
+                .iter()
+                .filter_map(|subject| self.by_subject.get(subject))
+                .flatten()
+                .copied()
+                .collect()
+        } else if !filter.professors.is_empty() {
+            filter
+                .professors
+                .iter()
+                .flat_map(|prefix| {
+                    self.by_professor
+                        .range(prefix.clone()..)
+                        .take_while(move |(surname, _)| surname.starts_with(prefix.as_str()))
+                        .flat_map(|(_, positions)| positions.iter().copied())
+                })
+                .collect()
+        } else {
+            (0..self.classes.len()).collect()
+        };
+
+        candidates
+            .into_iter()
+            .filter(|&position| {
+                self.day_masks[position] & filter.days == filter.days
+                    && filter.matches_professor(&self.classes[position])
+            })
+            .map(|position| self.classes[position].unique_id())
+            .collect()
+    }
+}
+
+/// A result filter typed as words, e.g., "CMPT MATH Bowen MWF"
+///
+/// Each word is a subject code if the index has that subject, a day string if it only
+/// contains day codes, and otherwise the start of a professor's surname. Words of the same
+/// kind widen the filter, words of different kinds narrow it
+///
+/// Fields:
+/// --- ---
+/// subjects -> Subject codes, uppercase (any of them matches)
+/// professors -> Folded surname prefixes (any of them matches)
+/// days -> Bitmask of days the section must meet on (all of them)
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for ClassFilter
+/// Clone -> Clone trait for ClassFilter
+/// Default -> Default trait for ClassFilter
+/// PartialEq -> PartialEq trait for ClassFilter
+/// --- ---
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ClassFilter {
+    pub subjects: Vec<String>,
+    pub professors: Vec<String>,
+    pub days: u8,
+}
+
+impl ClassFilter {
+    /// Parse filter text
+    ///
+    /// Parameters:
+    /// --- ---
+    /// text -> The filter text
+    /// index -> Index used to recognize subject codes
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// ClassFilter -> The filter (empty text gives a filter that matches everything)
+    /// --- ---
+    pub fn parse(text: &str, index: &TermIndex) -> Self {
+        let mut filter = ClassFilter::default();
+        for word in text.split_whitespace() {
+            if index.has_subject(word) {
+                filter.subjects.push(word.to_uppercase());
+            } else if let Some(mask) = day_mask(word) {
+                filter.days |= mask;
+            } else {
+                filter.professors.push(fold_text(word));
+            }
+        }
+        filter
+    }
+
+    /// Check whether the filter matches everything
+    ///
+    /// Returns:
+    /// --- ---
+    /// bool -> true if the filter has no words
+    /// --- ---
+    pub fn is_empty(&self) -> bool {
+        self.subjects.is_empty() && self.professors.is_empty() && self.days == 0
+    }
+
+    /// Check a section against the filter without an index
+    ///
+    /// Used for sections the term index doesn't have, like results from before a sync
+    ///
+    /// Parameters:
+    /// --- ---
+    /// class -> The section to check
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// bool -> true if the section matches
+    /// --- ---
+    pub fn matches(&self, class: &Class) -> bool {
+        (self.subjects.is_empty() || self.subjects.contains(&class.subject_code.to_uppercase()))
+            && class_day_mask(class) & self.days == self.days
+            && self.matches_professor(class)
+    }
+
+    /// Check a section's professor against the surname prefixes
+    ///
+    /// Parameters:
+    /// --- ---
+    /// class -> The section to check
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// bool -> true if there are no prefixes or the surname starts with one of them
+    /// --- ---
+    fn matches_professor(&self, class: &Class) -> bool {
+        if self.professors.is_empty() {
+            return true;
+        }
+        let Some(surname) = class.professor_name.as_deref().map(professor_surname) else {
+            return false;
+        };
+        self.professors
+            .iter()
+            .any(|prefix| surname.starts_with(prefix.as_str()))
+    }
+}
+
+/// Read every section in a scope and index them
+///
+/// Parameters:
+/// --- ---
+/// db_path -> Path to the SQLite database file (a missing file gives an empty index)
+/// sql -> Query selecting the sections, from codegen::generate_scope_sql
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<TermIndex, String> -> The index or error message
+/// --- ---
+pub fn load_term_index(db_path: &Path, sql: &str) -> Result<TermIndex, String> {
+    // opening a missing file would create an empty database before the first sync
+    if !db_path.exists() {
+        return Ok(TermIndex::default());
+    }
+    Ok(TermIndex::build(execute_query(sql, db_path)?))
+}
+
+/// Turn a day string into a day bitmask
+///
+/// Parameters:
+/// --- ---
+/// days -> Compact day string, any case (e.g., "MWF", "tth")
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Option<u8> -> The bitmask, None if the text isn't made only of day codes
+/// --- ---
+pub fn day_mask(days: &str) -> Option<u8> {
+    let upper = days.to_uppercase();
+    let codes = parse_days(&upper);
+    // parse_days skips characters that aren't day codes, so compare lengths
+    if codes.is_empty() || codes.iter().map(String::len).sum::<usize>() != upper.len() {
+        return None;
+    }
+    Some(codes.iter().fold(0, |mask, code| mask | day_bit(code)))
+}
+
+/// Get the bitmask of the days a section meets
+///
+/// Parameters:
+/// --- ---
+/// class -> The section
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// u8 -> Bit i is set if the section meets on DAY_CODES[i]
+/// --- ---
+fn class_day_mask(class: &Class) -> u8 {
+    class
+        .meeting_times
+        .iter()
+        .flat_map(|meeting| meeting.days.iter())
+        .fold(0, |mask, code| mask | day_bit(code))
+}
+
+/// Get the bit for a day code
+///
+/// Parameters:
+/// --- ---
+/// code -> Day code (M, T, W, TH, F, S, SU)
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// u8 -> The day's bit, 0 for unknown codes
+/// --- ---
+fn day_bit(code: &str) -> u8 {
+    DAY_CODES
+        .iter()
+        .position(|day| *day == code)
+        .map_or(0, |position| 1 << position)
+}
+
+/// Get the folded surname of a "Last, First" professor name
+///
+/// Parameters:
+/// --- ---
+/// name -> The professor's name
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> The surname, folded for case- and accent-insensitive matching
+/// --- ---
+fn professor_surname(name: &str) -> String {
+    fold_text(name.split(',').next().unwrap_or(name).trim())
+}
@@ -0,0 +1,126 @@
+/*
+    src/data/warm_cache.rs
+
+    Module for values loaded from the database in the background
+    The TUI warms its lookup lists and the active term's index this way when it starts, after
+    a school or term is picked, and after a sync, so the UI never waits on the load
+*/
+
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::thread::{self, JoinHandle};
+
+/// A shared value loaded in a background thread
+///
+/// Clones share the same value. Each warm-up gets a generation number and only stores its
+/// value if no newer warm-up has started, so a slow load for the previous school can't
+/// overwrite the current one
+///
+/// Fields:
+/// --- ---
+/// value -> The loaded value, None until the first warm-up finishes
+/// generation -> Number of the most recently started warm-up
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for WarmCache
+/// Clone -> Clone trait for WarmCache (shares the value)
+/// Default -> Default trait for WarmCache
+/// --- ---
+pub struct WarmCache<T> {
+    value: Arc<RwLock<Option<Arc<T>>>>,
+    generation: Arc<AtomicU64>,
+}
+
+impl<T: Send + Sync + 'static> WarmCache<T> {
+    /// Create an empty cache
+    ///
+    /// Returns:
+    /// --- ---
+    /// WarmCache<T> -> A cache with nothing loaded
+    /// --- ---
+    pub fn new() -> Self {
+        Self {
+            value: Arc::new(RwLock::new(None)),
+            generation: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Get the loaded value
+    ///
+    /// Returns:
+    /// --- ---
+    /// Option<Arc<T>> -> The value, None if no warm-up has finished yet
+    /// --- ---
+    pub fn get(&self) -> Option<Arc<T>> {
+        self.value.read().ok().and_then(|value| value.clone())
+    }
+
+    /// Load the value in a background thread
+    ///
+    /// The previous value stays in use until the new one is loaded. A load that fails keeps
+    /// it too, warmed values are a convenience and never block a search
+    ///
+    /// Parameters:
+    /// --- ---
+    /// load -> Function that reads the value from the database
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// JoinHandle<()> -> The loading thread, for callers that need to wait for it
+    /// --- ---
+    pub fn warm_up<F>(&self, load: F) -> JoinHandle<()>
+    where
+        F: FnOnce() -> Result<T, String> + Send + 'static,
+    {
+        let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+        let cache = self.clone();
+        thread::spawn(move || {
+            let Ok(loaded) = load() else {
+                return;
+            };
+            if let Ok(mut value) = cache.value.write() {
+                // a newer warm-up was started while this one was loading
+                if cache.generation.load(Ordering::SeqCst) == generation {
+                    *value = Some(Arc::new(loaded));
+                }
+            }
+        })
+    }
+
+    /// Drop the loaded value, and the value of any warm-up still loading
+    pub fn clear(&self) {
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        if let Ok(mut value) = self.value.write() {
+            *value = None;
+        }
+    }
+}
+
+impl<T> Clone for WarmCache<T> {
+    fn clone(&self) -> Self {
+        Self {
+            value: Arc::clone(&self.value),
+            generation: Arc::clone(&self.generation),
+        }
+    }
+}
+
+impl<T: Send + Sync + 'static> Default for WarmCache<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> fmt::Debug for WarmCache<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let loaded = self.value.read().map(|v| v.is_some()).unwrap_or(false);
+        f.debug_struct("WarmCache")
+            .field("loaded", &loaded)
+            .field("generation", &self.generation.load(Ordering::SeqCst))
+            .finish()
+    }
+}
@@ -0,0 +1,307 @@
+/*
+    src/data/watch.rs
+
+    Module for the watch list of sections
+    Sections are watched from the detail view; each keeps its school, term, and open seats
+    from the last check, so a sync can report the ones that opened up, filled, or were
+    dropped from the schedule
+*/
+
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::data::result_diff::open_seats;
+use crate::data::sql::Class;
+
+/// A watched section
+///
+/// Fields:
+/// --- ---
+/// unique_id -> The section's unique ID (see Class::unique_id)
+/// label -> Display name, e.g., "CMPT 101-001"
+/// school_id -> School the section belongs to
+/// term_id -> Term the section belongs to (None for the test database)
+/// open_seats -> Open seats at the last check, None if unknown
+/// offered -> Whether the section was in the schedule at the last check
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for WatchedSection
+/// Clone -> Clone trait for WatchedSection
+/// PartialEq -> PartialEq trait for WatchedSection
+/// Serialize -> Serialize trait for WatchedSection
+/// Deserialize -> Deserialize trait for WatchedSection
+/// --- ---
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WatchedSection {
+    pub unique_id: String,
+    pub label: String,
+    pub school_id: Option<String>,
+    pub term_id: Option<String>,
+    pub open_seats: Option<i32>,
+    pub offered: bool,
+}
+
+/// What happened to a watched section
+///
+/// Variants:
+/// --- ---
+/// SeatsOpened -> A full (or dropped) section has this many open seats
+/// Filled -> A section with open seats is now full
+/// Dropped -> The section is no longer in the schedule
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for WatchEventKind
+/// Clone -> Clone trait for WatchEventKind
+/// Copy -> Copy trait for WatchEventKind
+/// PartialEq -> PartialEq trait for WatchEventKind
+/// --- ---
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WatchEventKind {
+    SeatsOpened(i32),
+    Filled,
+    Dropped,
+}
+
+/// A change in a watched section found by a check
+///
+/// Fields:
+/// --- ---
+/// section -> The watched section, as it was before the check
+/// kind -> What happened
+/// class -> The section's current data, None if it was dropped
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for WatchEvent
+/// Clone -> Clone trait for WatchEvent
+/// --- ---
+#[derive(Debug, Clone)]
+pub struct WatchEvent {
+    pub section: WatchedSection,
+    pub kind: WatchEventKind,
+    pub class: Option<Class>,
+}
+
+impl WatchEvent {
+    /// Describe the event in one line
+    ///
+    /// Returns:
+    /// --- ---
+    /// String -> e.g., "CMPT 101-001 has 1 seat open"
+    /// --- ---
+    pub fn message(&self) -> String {
+        match self.kind {
+            WatchEventKind::SeatsOpened(1) => format!("{} has 1 seat open", self.section.label),
+            WatchEventKind::SeatsOpened(seats) => {
+                format!("{} has {} seats open", self.section.label, seats)
+            }
+            WatchEventKind::Filled => format!("{} is full", self.section.label),
+            WatchEventKind::Dropped => format!("{} is no longer offered", self.section.label),
+        }
+    }
+}
+
+/// Sections being watched
+///
+/// Fields:
+/// --- ---
+/// sections -> The watched sections, in the order they were added
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for WatchList
+/// Clone -> Clone trait for WatchList
+/// Default -> Default trait for WatchList
+/// Serialize -> Serialize trait for WatchList
+/// Deserialize -> Deserialize trait for WatchList
+/// --- ---
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WatchList {
+    pub sections: Vec<WatchedSection>,
+}
+
+impl WatchList {
+    /// Load the watch list from a file
+    ///
+    /// Parameters:
+    /// --- ---
+    /// path -> Path to the watch list file (a missing file gives an empty list)
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Result<WatchList, String> -> The watch list or error message
+    /// --- ---
+    pub fn load(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(WatchList::default());
+        }
+        let content =
+            fs::read_to_string(path).map_err(|e| format!("Failed to read watch list: {}", e))?;
+        serde_json::from_str(&content).map_err(|e| format!("Failed to parse watch list: {}", e))
+    }
+
+    /// Save the watch list to a file
+    ///
+    /// Parameters:
+    /// --- ---
+    /// path -> Path to the watch list file
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Result<(), String> -> Ok on success, error message on failure
+    /// --- ---
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize watch list: {}", e))?;
+        fs::write(path, content).map_err(|e| format!("Failed to write watch list: {}", e))
+    }
+
+    /// Check whether a section is watched
+    ///
+    /// Parameters:
+    /// --- ---
+    /// unique_id -> The section's unique ID
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// bool -> true if the section is on the list
+    /// --- ---
+    pub fn contains(&self, unique_id: &str) -> bool {
+        self.sections.iter().any(|s| s.unique_id == unique_id)
+    }
+
+    /// Start or stop watching a section
+    ///
+    /// Parameters:
+    /// --- ---
+    /// class -> The section
+    /// school_id -> School the section was found in
+    /// term_id -> Term the section was found in
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// bool -> true if the section is now watched, false if it was removed
+    /// --- ---
+    pub fn toggle(
+        &mut self,
+        class: &Class,
+        school_id: Option<&str>,
+        term_id: Option<&str>,
+    ) -> bool {
+        let unique_id = class.unique_id();
+        if self.contains(&unique_id) {
+            self.sections.retain(|s| s.unique_id != unique_id);
+            return false;
+        }
+        self.sections.push(WatchedSection {
+            unique_id,
+            label: section_label(class),
+            school_id: school_id.map(str::to_string),
+            term_id: term_id.map(str::to_string),
+            open_seats: open_seats(class),
+            offered: true,
+        });
+        true
+    }
+
+    /// Get the school and term pairs the watched sections belong to
+    ///
+    /// Returns:
+    /// --- ---
+    /// Vec<(Option<String>, Option<String>)> -> Each (school, term) pair once, in list order
+    /// --- ---
+    pub fn scopes(&self) -> Vec<(Option<String>, Option<String>)> {
+        let mut scopes = Vec::new();
+        for section in &self.sections {
+            let scope = (section.school_id.clone(), section.term_id.clone());
+            if !scopes.contains(&scope) {
+                scopes.push(scope);
+            }
+        }
+        scopes
+    }
+
+    /// Compare the watched sections of a scope with its current sections
+    ///
+    /// Each watched section's seats are updated, so the next check only reports what
+    /// changed since this one. Seat counts going up or down without the section opening
+    /// or filling aren't events
+    ///
+    /// Parameters:
+    /// --- ---
+    /// school_id -> School of the sections to check
+    /// term_id -> Term of the sections to check
+    /// current -> Every current section of that school and term
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Vec<WatchEvent> -> The events, in list order
+    /// --- ---
+    pub fn check(
+        &mut self,
+        school_id: Option<&str>,
+        term_id: Option<&str>,
+        current: &[Class],
+    ) -> Vec<WatchEvent> {
+        let mut events = Vec::new();
+        for section in self
+            .sections
+            .iter_mut()
+            .filter(|s| s.school_id.as_deref() == school_id && s.term_id.as_deref() == term_id)
+        {
+            let class = current.iter().find(|c| c.unique_id() == section.unique_id);
+            let seats = class.and_then(open_seats);
+            let was_open = section.offered && section.open_seats.is_some_and(|n| n > 0);
+            let kind = match (class, seats) {
+                (None, _) if section.offered => Some(WatchEventKind::Dropped),
+                (None, _) => None,
+                (Some(_), Some(n)) if n > 0 && !was_open => Some(WatchEventKind::SeatsOpened(n)),
+                (Some(_), Some(0)) if was_open => Some(WatchEventKind::Filled),
+                _ => None,
+            };
+            if let Some(kind) = kind {
+                events.push(WatchEvent {
+                    section: section.clone(),
+                    kind,
+                    class: class.cloned(),
+                });
+            }
+            section.offered = class.is_some();
+            if class.is_some() {
+                section.open_seats = seats;
+            }
+        }
+        events
+    }
+}
+
+/// Get the display name of a section
+///
+/// Parameters:
+/// --- ---
+/// class -> The section
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> e.g., "CMPT 101-001"
+/// --- ---
+pub fn section_label(class: &Class) -> String {
+    format!(
+        "{} {}-{}",
+        class.subject_code, class.course_number, class.section_sequence
+    )
+}
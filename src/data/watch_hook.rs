@@ -0,0 +1,257 @@
+/*
+    src/data/watch_hook.rs
+
+    Module for running user hooks on watch-list events
+    A shell command (CLASSQL_WATCH_COMMAND) and/or a webhook URL (CLASSQL_WATCH_WEBHOOK) is
+    invoked once per event with the event and the section's data as JSON, so alerts can be
+    forwarded to Discord, Slack, or anything else that takes a command or an HTTP POST
+*/
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde_json::{json, Value};
+
+use crate::data::result_diff::open_seats;
+use crate::data::sql::Class;
+use crate::data::watch::{WatchEvent, WatchEventKind};
+use crate::utils::time::format_12_hour;
+
+/// How long a hook command or webhook request may take before it is given up on
+pub const HOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Hooks to invoke on watch-list events
+///
+/// Fields:
+/// --- ---
+/// command -> Shell command run with the payload on standard input
+/// webhook_url -> URL the payload is POSTed to
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for WatchHook
+/// Clone -> Clone trait for WatchHook
+/// Default -> Default trait for WatchHook
+/// --- ---
+#[derive(Debug, Clone, Default)]
+pub struct WatchHook {
+    pub command: Option<String>,
+    pub webhook_url: Option<String>,
+}
+
+impl WatchHook {
+    /// Read the hooks from the environment
+    ///
+    /// Returns:
+    /// --- ---
+    /// WatchHook -> CLASSQL_WATCH_COMMAND and CLASSQL_WATCH_WEBHOOK (unset or blank is None)
+    /// --- ---
+    pub fn from_env() -> Self {
+        let read = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+        WatchHook {
+            command: read("CLASSQL_WATCH_COMMAND"),
+            webhook_url: read("CLASSQL_WATCH_WEBHOOK"),
+        }
+    }
+
+    /// Check whether any hook is configured
+    ///
+    /// Returns:
+    /// --- ---
+    /// bool -> true if there is neither a command nor a webhook
+    /// --- ---
+    pub fn is_empty(&self) -> bool {
+        self.command.is_none() && self.webhook_url.is_none()
+    }
+
+    /// Invoke the hooks once for each event
+    ///
+    /// Parameters:
+    /// --- ---
+    /// events -> The watch-list events
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Vec<String> -> A message for each hook that failed, empty if all succeeded
+    /// --- ---
+    pub fn run(&self, events: &[WatchEvent]) -> Vec<String> {
+        let mut failures = Vec::new();
+        for event in events {
+            let payload = event_payload(event).to_string();
+            if let Some(command) = &self.command {
+                if let Err(e) = run_command(command, &payload) {
+                    failures.push(format!("Watch command failed: {}", e));
+                }
+            }
+            if let Some(url) = &self.webhook_url {
+                if let Err(e) = post_webhook(url, &payload) {
+                    failures.push(format!("Watch webhook failed: {}", e));
+                }
+            }
+        }
+        failures
+    }
+}
+
+/// Build the JSON payload of an event
+///
+/// "content" and "text" hold the one-line message, so the payload can be POSTed to a
+/// Discord or Slack incoming webhook as is
+///
+/// Parameters:
+/// --- ---
+/// event -> The watch-list event
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Value -> The event kind, message, watched section, and current class data (null if
+///          the section was dropped)
+/// --- ---
+pub fn event_payload(event: &WatchEvent) -> Value {
+    let (kind, seats) = match event.kind {
+        WatchEventKind::SeatsOpened(seats) => ("seats_opened", Some(seats)),
+        WatchEventKind::Filled => ("filled", Some(0)),
+        WatchEventKind::Dropped => ("dropped", None),
+    };
+    let message = event.message();
+    json!({
+        "event": kind,
+        "message": message,
+        "content": message,
+        "text": message,
+        "open_seats": seats,
+        "section": {
+            "unique_id": event.section.unique_id,
+            "label": event.section.label,
+            "school_id": event.section.school_id,
+            "term_id": event.section.term_id,
+        },
+        "class": event.class.as_ref().map(class_json),
+    })
+}
+
+/// Convert a class to JSON
+///
+/// Parameters:
+/// --- ---
+/// class -> The section
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Value -> The section's fields, with open seats and 12-hour meeting times
+/// --- ---
+pub fn class_json(class: &Class) -> Value {
+    json!({
+        "unique_id": class.unique_id(),
+        "subject_code": class.subject_code,
+        "course_number": class.course_number,
+        "section_sequence": class.section_sequence,
+        "crn": class.crn,
+        "title": class.title,
+        "credit_hours": class.credit_hours,
+        "enrollment": class.enrollment,
+        "max_enrollment": class.max_enrollment,
+        "open_seats": open_seats(class),
+        "instruction_method": class.instruction_method,
+        "campus": class.campus,
+        "professor_name": class.professor_name,
+        "professor_email": class.professor_email,
+        "meetings": class.meeting_times.iter().map(|meeting| json!({
+            "days": meeting.days,
+            "start": format_12_hour(meeting.start_minutes),
+            "end": format_12_hour(meeting.end_minutes),
+        })).collect::<Vec<_>>(),
+    })
+}
+
+/// Run the hook command with the payload on standard input
+///
+/// The command's output is discarded, it would otherwise be drawn over the TUI
+///
+/// Parameters:
+/// --- ---
+/// command -> Shell command (run with sh -c, or cmd /C on Windows)
+/// payload -> The JSON payload
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<(), String> -> Ok if the command exited successfully within HOOK_TIMEOUT
+/// --- ---
+fn run_command(command: &str, payload: &str) -> Result<(), String> {
+    let mut child = if cfg!(windows) {
+        Command::new("cmd")
+            .args(["/C", command])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+    } else {
+        Command::new("sh")
+            .args(["-c", command])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+    }
+    .map_err(|e| format!("could not start '{}': {}", command, e))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // a command that doesn't read its input closes the pipe early, which is fine
+        let _ = stdin.write_all(payload.as_bytes());
+    }
+
+    let started = Instant::now();
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) if status.success() => return Ok(()),
+            Ok(Some(status)) => return Err(format!("'{}' exited with {}", command, status)),
+            Ok(None) if started.elapsed() >= HOOK_TIMEOUT => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!("'{}' timed out", command));
+            }
+            Ok(None) => thread::sleep(Duration::from_millis(20)),
+            Err(e) => return Err(e.to_string()),
+        }
+    }
+}
+
+/// POST the payload to the webhook URL
+///
+/// Parameters:
+/// --- ---
+/// url -> The webhook URL
+/// payload -> The JSON payload
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<(), String> -> Ok if the server answered with a success status
+/// --- ---
+fn post_webhook(url: &str, payload: &str) -> Result<(), String> {
+    let response = reqwest::blocking::Client::builder()
+        .timeout(HOOK_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?
+        .post(url)
+        .header("Content-Type", "application/json")
+        .body(payload.to_string())
+        .send()
+        .map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("{} answered {}", url, response.status()));
+    }
+    Ok(())
+}
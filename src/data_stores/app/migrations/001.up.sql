@@ -0,0 +1,8 @@
+CREATE TABLE records (
+    table_name TEXT NOT NULL,
+    key TEXT NOT NULL,
+    value TEXT NOT NULL,
+    updated_at INTEGER NOT NULL,
+    PRIMARY KEY (table_name, key),
+    CHECK(json_valid(value))
+);
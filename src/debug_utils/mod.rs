@@ -1,8 +1,9 @@
 /*
-    src/utils/mod.rs
+    src/debug_utils/mod.rs
 
-    Module for storing utility functions. Used for our utilities.
+    Module for storing debugging tools. Used to print what the compiler made of a query.
 
 */
 
+pub mod tokenstream;
 pub mod visualizetree;
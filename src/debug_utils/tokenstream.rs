@@ -0,0 +1,57 @@
+/// src/debug_utils/tokenstream.rs
+///
+/// Print the token stream of a query
+///
+/// Responsible for listing the tokens the lexer produced for a query, one per line with its
+/// position, byte span, type, and the text it covers, to diagnose queries that don't lex
+/// or parse the way they were meant to
+///
+/// Contains:
+/// --- ---
+/// token_lines -> Format a token stream as aligned lines
+/// --- ---
+///
+use crate::dsl::token::Token;
+
+/// Format a token stream as aligned lines
+///
+/// Parameters:
+/// --- ---
+/// input_string -> The query the tokens were lexed from
+/// tokens -> The tokens, from Lexer::tokenize or Lexer::analyze
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Vec<String> -> One line per token (e.g., "0  0..4  T_PROFESSOR  `prof`")
+/// --- ---
+///
+pub fn token_lines(input_string: &str, tokens: &[Token]) -> Vec<String> {
+    let spans: Vec<String> = tokens
+        .iter()
+        .map(|token| format!("{}..{}", token.get_start(), token.get_end()))
+        .collect();
+    let types: Vec<String> = tokens
+        .iter()
+        .map(|token| token.get_token_type().to_string())
+        .collect();
+
+    let index_width = tokens.len().saturating_sub(1).to_string().len();
+    let span_width = spans.iter().map(String::len).max().unwrap_or(0);
+    let type_width = types.iter().map(String::len).max().unwrap_or(0);
+
+    tokens
+        .iter()
+        .zip(spans.iter().zip(&types))
+        .enumerate()
+        .map(|(index, (token, (span, token_type)))| {
+            let lexeme = input_string
+                .get(token.get_start()..token.get_end())
+                .unwrap_or("");
+            format!(
+                "{:>index_width$}  {:<span_width$}  {:<type_width$}  `{}`",
+                index, span, token_type, lexeme
+            )
+        })
+        .collect()
+}
@@ -1,26 +1,213 @@
-/// src/utils/visualizetree.rs
+/// src/debug_utils/visualizetree.rs
 ///
 /// Visualize the AST as a graph
 ///
-/// Responsible for visualizing the AST as a graph
+/// Responsible for printing the AST of a query in a format meant for people: a DOT graph
+/// for Graphviz, a Mermaid flowchart for Markdown viewers, or a plain tree for the
+/// terminal. Every node is labelled with its type and the text of the query it was parsed
+/// from, and the graph formats color nodes by category so large queries stay readable
 ///
 /// Contains:
 /// --- ---
+/// TreeFormat -> Output format for a printed AST
+/// render_ast -> Print the AST in a format
 /// ast_to_dot -> Convert the AST to a DOT graph
-/// add_tree_nodes_recursive -> Recursively add TreeNodes and their children to the graph
+/// ast_to_mermaid -> Convert the AST to a Mermaid flowchart
+/// ast_to_tree -> Convert the AST to an indented ASCII tree
+/// Helper functions:
+///      --- ---
+///      NodeCategory -> Kind of node, each drawn in its own color
+///      GraphNode -> A node of the AST flattened for the graph formats
+///      flatten -> Flatten the AST into numbered nodes with their parents
+///      lexeme -> The text of the query a node was parsed from
+///      add_tree_lines -> Recursively add a node and its children to the ASCII tree
+///      --- ---
 /// --- ---
 ///
-use petgraph::dot::Dot;
-use petgraph::graph::DiGraph;
-use petgraph::stable_graph::NodeIndex;
+use std::str::FromStr;
 
-use crate::dsl::parser::{Ast, TreeNode};
+use crate::dsl::parser::{Ast, NodeType, TreeNode};
+use crate::dsl::token::TokenType;
+
+/// Label of the node every AST hangs from
+const HEAD_LABEL: &str = "AST Head";
+
+/// Output format for a printed AST
+///
+/// Variants:
+/// --- ---
+/// Dot -> Graphviz DOT graph (e.g., for `dot -Tpng`)
+/// Mermaid -> Mermaid flowchart
+/// Tree -> Indented ASCII tree
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for TreeFormat
+/// Clone -> Clone trait for TreeFormat
+/// Copy -> Copy trait for TreeFormat
+/// PartialEq -> PartialEq trait for TreeFormat
+/// FromStr -> Parse a format name ("dot", "mermaid", "tree")
+/// --- ---
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TreeFormat {
+    Dot,
+    Mermaid,
+    Tree,
+}
+
+impl FromStr for TreeFormat {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.to_lowercase().as_str() {
+            "dot" => Ok(TreeFormat::Dot),
+            "mermaid" => Ok(TreeFormat::Mermaid),
+            "tree" => Ok(TreeFormat::Tree),
+            _ => Err(format!(
+                "unknown format '{}' (expected dot, mermaid, or tree)",
+                name
+            )),
+        }
+    }
+}
+
+/// Kind of node, each drawn in its own color
+///
+/// Variants:
+/// --- ---
+/// Head -> The node every AST hangs from
+/// Logical -> Query structure and the and/or/not operators
+/// Entity -> A field being searched (e.g., ProfessorQuery, TimeRange)
+/// Condition -> How the field is compared (conditions and binary operators)
+/// Value -> What the field is compared with
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for NodeCategory
+/// Clone -> Clone trait for NodeCategory
+/// Copy -> Copy trait for NodeCategory
+/// PartialEq -> PartialEq trait for NodeCategory
+/// --- ---
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum NodeCategory {
+    Head,
+    Logical,
+    Entity,
+    Condition,
+    Value,
+}
+
+impl NodeCategory {
+    /// Every category, in the order their styles are declared
+    const ALL: [NodeCategory; 5] = [
+        NodeCategory::Head,
+        NodeCategory::Logical,
+        NodeCategory::Entity,
+        NodeCategory::Condition,
+        NodeCategory::Value,
+    ];
+
+    /// Get the category of a node type
+    ///
+    /// Parameters:
+    /// --- ---
+    /// node_type -> The node type
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// NodeCategory -> The node's category
+    /// --- ---
+    ///
+    fn of(node_type: &NodeType) -> Self {
+        match node_type {
+            NodeType::Query
+            | NodeType::LogicalTerm
+            | NodeType::LogicalFactor
+            | NodeType::EntityQuery
+            | NodeType::T(TokenType::And | TokenType::Or | TokenType::Not) => NodeCategory::Logical,
+            NodeType::Condition | NodeType::Binop | NodeType::T(_) => NodeCategory::Condition,
+            NodeType::Time
+            | NodeType::StringList
+            | NodeType::String
+            | NodeType::Integer
+            | NodeType::Identifier
+            | NodeType::EmailIdentifier => NodeCategory::Value,
+            _ => NodeCategory::Entity,
+        }
+    }
+
+    /// Get the category's name, used as the Mermaid class
+    fn name(self) -> &'static str {
+        match self {
+            NodeCategory::Head => "head",
+            NodeCategory::Logical => "logical",
+            NodeCategory::Entity => "entity",
+            NodeCategory::Condition => "condition",
+            NodeCategory::Value => "value",
+        }
+    }
+
+    /// Get the category's fill color
+    fn color(self) -> &'static str {
+        match self {
+            NodeCategory::Head => "#eeeeee",
+            NodeCategory::Logical => "#cfe2f3",
+            NodeCategory::Entity => "#fce5cd",
+            NodeCategory::Condition => "#f4cccc",
+            NodeCategory::Value => "#d9ead3",
+        }
+    }
+}
+
+/// A node of the AST flattened for the graph formats
+///
+/// Fields:
+/// --- ---
+/// parent -> Index of the parent node, None for the head
+/// node_type -> The node's type, as printed (e.g., "ProfessorQuery")
+/// lexeme -> The text of the query the node was parsed from, if any
+/// category -> The node's category
+/// --- ---
+///
+struct GraphNode {
+    parent: Option<usize>,
+    node_type: String,
+    lexeme: Option<String>,
+    category: NodeCategory,
+}
+
+/// Print the AST in a format
+///
+/// Parameters:
+/// --- ---
+/// input_string -> The query the AST was parsed from
+/// ast -> The AST to print
+/// format -> The format to print it in
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> The printed AST
+/// --- ---
+///
+pub fn render_ast(input_string: &str, ast: &Ast, format: TreeFormat) -> String {
+    match format {
+        TreeFormat::Dot => ast_to_dot(input_string, ast),
+        TreeFormat::Mermaid => ast_to_mermaid(input_string, ast),
+        TreeFormat::Tree => ast_to_tree(input_string, ast),
+    }
+}
 
 /// Convert the AST to a DOT graph
 ///
 /// Parameters:
 /// --- ---
-/// input_string -> The input string to convert
+/// input_string -> The query the AST was parsed from
 /// ast -> The AST to convert
 /// --- ---
 ///
@@ -29,27 +216,169 @@ use crate::dsl::parser::{Ast, TreeNode};
 /// String -> The DOT graph
 /// --- ---
 ///
-pub fn ast_to_dot(input_string: String, ast: &Ast) -> String {
-    let mut graph: DiGraph<String, ()> = DiGraph::new();
+pub fn ast_to_dot(input_string: &str, ast: &Ast) -> String {
+    let escape = |text: &str| text.replace('\\', "\\\\").replace('"', "\\\"");
+    let mut lines = vec![
+        "digraph {".to_string(),
+        "    node [shape=box, style=\"rounded,filled\", fontname=\"Helvetica\"]".to_string(),
+    ];
+    let nodes = flatten(input_string, ast);
+    for (index, node) in nodes.iter().enumerate() {
+        let label = match &node.lexeme {
+            Some(lexeme) => format!("{}\\n`{}`", node.node_type, escape(lexeme)),
+            None => node.node_type.clone(),
+        };
+        lines.push(format!(
+            "    {} [label=\"{}\", fillcolor=\"{}\"]",
+            index,
+            label,
+            node.category.color()
+        ));
+    }
+    for (index, node) in nodes.iter().enumerate() {
+        if let Some(parent) = node.parent {
+            lines.push(format!("    {} -> {}", parent, index));
+        }
+    }
+    lines.push("}".to_string());
+    lines.join("\n")
+}
 
-    let ast_head_idx = graph.add_node("AST Head".to_string());
+/// Convert the AST to a Mermaid flowchart
+///
+/// Parameters:
+/// --- ---
+/// input_string -> The query the AST was parsed from
+/// ast -> The AST to convert
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> The flowchart
+/// --- ---
+///
+pub fn ast_to_mermaid(input_string: &str, ast: &Ast) -> String {
+    // Mermaid labels can't hold a bare quote, and < or > would be read as HTML
+    let escape = |text: &str| {
+        text.replace('&', "#amp;")
+            .replace('"', "#quot;")
+            .replace('<', "#lt;")
+            .replace('>', "#gt;")
+    };
+    let mut lines = vec!["flowchart TD".to_string()];
+    let nodes = flatten(input_string, ast);
+    for (index, node) in nodes.iter().enumerate() {
+        let label = match &node.lexeme {
+            Some(lexeme) => format!("{}<br/>`{}`", node.node_type, escape(lexeme)),
+            None => node.node_type.clone(),
+        };
+        lines.push(format!(
+            "    n{}[\"{}\"]:::{}",
+            index,
+            label,
+            node.category.name()
+        ));
+    }
+    for (index, node) in nodes.iter().enumerate() {
+        if let Some(parent) = node.parent {
+            lines.push(format!("    n{} --> n{}", parent, index));
+        }
+    }
+    for category in NodeCategory::ALL {
+        lines.push(format!(
+            "    classDef {} fill:{},stroke:#666666",
+            category.name(),
+            category.color()
+        ));
+    }
+    lines.join("\n")
+}
 
+/// Convert the AST to an indented ASCII tree
+///
+/// Parameters:
+/// --- ---
+/// input_string -> The query the AST was parsed from
+/// ast -> The AST to convert
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> The tree, one node per line
+/// --- ---
+///
+pub fn ast_to_tree(input_string: &str, ast: &Ast) -> String {
+    let mut lines = vec![HEAD_LABEL.to_string()];
     if let Some(ref head_node) = ast.head {
-        add_tree_nodes_recursive(input_string, &mut graph, ast_head_idx, head_node);
+        add_tree_lines(input_string, &mut lines, "", true, head_node);
     }
+    lines.join("\n")
+}
 
-    // generate DOT output
-    format!("{:?}", Dot::new(&graph)).replace("label = \"()\"", "label = \"\"")
+/// Flatten the AST into numbered nodes with their parents
+///
+/// Parameters:
+/// --- ---
+/// input_string -> The query the AST was parsed from
+/// ast -> The AST to flatten
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Vec<GraphNode> -> The head and then every node, parents before their children
+/// --- ---
+///
+fn flatten(input_string: &str, ast: &Ast) -> Vec<GraphNode> {
+    let mut nodes = vec![GraphNode {
+        parent: None,
+        node_type: HEAD_LABEL.to_string(),
+        lexeme: None,
+        category: NodeCategory::Head,
+    }];
+    let mut pending: Vec<(usize, &TreeNode)> = ast.head.iter().map(|head| (0, head)).collect();
+    // a stack, so children are pushed in reverse to come out in order
+    while let Some((parent, tree_node)) = pending.pop() {
+        let index = nodes.len();
+        nodes.push(GraphNode {
+            parent: Some(parent),
+            node_type: tree_node.node_type.to_string(),
+            lexeme: lexeme(input_string, tree_node),
+            category: NodeCategory::of(&tree_node.node_type),
+        });
+        pending.extend(tree_node.children.iter().rev().map(|child| (index, child)));
+    }
+    nodes
 }
 
-/// Recursive helper function to add TreeNodes and their children to the graph
+/// The text of the query a node was parsed from
 ///
 /// Parameters:
 /// --- ---
-/// input_string -> The input string to convert
-/// graph -> The graph to add the TreeNodes to
-/// parent_idx -> The index of the parent TreeNode
-/// tree_node -> The TreeNode to add
+/// input_string -> The query the AST was parsed from
+/// tree_node -> The node
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Option<String> -> The text under the node's token, None for nodes without one
+/// --- ---
+///
+fn lexeme(input_string: &str, tree_node: &TreeNode) -> Option<String> {
+    let token = tree_node.lexical_token?;
+    input_string
+        .get(token.get_start()..token.get_end())
+        .map(str::to_string)
+}
+
+/// Recursively add a node and its children to the ASCII tree
+///
+/// Parameters:
+/// --- ---
+/// input_string -> The query the AST was parsed from
+/// lines -> The tree's lines so far
+/// prefix -> The branch lines drawn before this node's connector
+/// is_last -> Whether the node is its parent's last child
+/// tree_node -> The node to add
 /// --- ---
 ///
 /// Returns:
@@ -57,29 +386,23 @@ pub fn ast_to_dot(input_string: String, ast: &Ast) -> String {
 /// None
 /// --- ---
 ///
-fn add_tree_nodes_recursive(
-    input_string: String,
-    graph: &mut DiGraph<String, ()>,
-    parent_idx: NodeIndex,
+fn add_tree_lines(
+    input_string: &str,
+    lines: &mut Vec<String>,
+    prefix: &str,
+    is_last: bool,
     tree_node: &TreeNode,
 ) {
-    // add the current TreeNode's NodeType as a node in the graph
-    // if it has a lexeme add that or add the production name
-    let current_node_label = match tree_node.lexical_token {
-        Some(t) => format!(
-            "{}=`{}`",
-            tree_node.node_type,
-            &input_string[t.get_start()..t.get_end()],
-        ),
+    let connector = if is_last { "└── " } else { "├── " };
+    let label = match lexeme(input_string, tree_node) {
+        Some(lexeme) => format!("{} `{}`", tree_node.node_type, lexeme),
         None => tree_node.node_type.to_string(),
     };
+    lines.push(format!("{}{}{}", prefix, connector, label));
 
-    let current_idx = graph.add_node(current_node_label);
-
-    graph.add_edge(parent_idx, current_idx, ());
-
-    // add the rest of the TreeNodes
-    for child in &tree_node.children {
-        add_tree_nodes_recursive(input_string.clone(), graph, current_idx, child);
+    let child_prefix = format!("{}{}", prefix, if is_last { "    " } else { "│   " });
+    for (i, child) in tree_node.children.iter().enumerate() {
+        let is_last_child = i + 1 == tree_node.children.len();
+        add_tree_lines(input_string, lines, &child_prefix, is_last_child, child);
     }
 }
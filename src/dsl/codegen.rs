@@ -8,16 +8,43 @@
 /// --- ---
 /// CodeGenResult -> Result type for code generation
 /// CodeGenError -> Error type for code generation
+/// SESSION_SQL -> SQL for the part of the term a section runs
+/// TERM_SPANS_SQL -> Each term's first and last days, recorded at sync time in `term_spans`
+/// RESTRICTED_SQL -> SQL for whether a section has a registration restriction
+/// FEE_SQL -> SQL for a section's lab or course fee
+/// MeetingFilter -> A condition on a section's meeting_times rows, as one EXISTS subquery
+/// ResultOrder -> The order search results are returned in
+///      Methods:
+///      --- ---
+///      all -> Get every ordering, in the order Settings cycles through them
+///      as_str -> Get the ordering's name as shown in Settings
+///      order_by -> Get the ORDER BY clause for the ordering
+///      --- ---
+/// DEFAULT_MAX_ROWS -> Results a search returns unless Settings changes the cap
 ///
 /// generate_sql -> Main function to generate SQL from an AST
+/// limit_clause -> Get the LIMIT clause capping the results of a search
+/// generate_scope_sql -> Generate SQL selecting every section in a school and term
+/// generate_course_sql -> Generate SQL selecting the sections of one course
+/// generate_professor_sections_sql -> Generate SQL selecting the sections a professor teaches
+/// select_sections -> Build the section SELECT around WHERE and HAVING conditions
 /// generate_node -> Generate SQL for a single AST node (dispatcher)
 /// generate_query -> Generate SQL for a Query node
 /// generate_logical_term -> Generate SQL for a LogicalTerm node
+/// combine_and_conditions -> Join the conditions of an AND chain
+/// collect_and_operands -> Collect the operands of an AND chain
+/// unwrap_node -> Look through the single-child wrapper nodes around an expression
+/// generate_clauses -> Generate the WHERE and HAVING conditions for a query
+/// generate_having_and -> Generate a HAVING condition for the operands of an AND chain
+/// is_meeting_predicate -> Check whether an unwrapped node is a single meeting predicate
+/// generate_having -> Generate a HAVING condition for a node
 /// generate_logical_factor -> Generate SQL for a LogicalFactor node
 /// generate_entity_query -> Generate SQL for an EntityQuery node
 /// generate_and -> Generate SQL for AND operation
 /// generate_or -> Generate SQL for OR operation
+/// generate_not -> Generate SQL for NOT operation
 /// generate_professor_query -> Generate SQL for ProfessorQuery node
+/// build_professor_name_condition -> Build SQL matching a professor's name in either order
 /// generate_course_query -> Generate SQL for CourseQuery node
 /// generate_subject_query -> Generate SQL for SubjectQuery node
 /// generate_number_query -> Generate SQL for NumberQuery node
@@ -33,19 +60,45 @@
 /// generate_full_query -> Generate SQL for FullQuery node
 /// generate_meeting_type_query -> Generate SQL for MeetingTypeQuery node
 /// generate_time_query -> Generate SQL for TimeQuery node
+/// generate_date_query -> Generate SQL for DateQuery node
+/// date_expression -> Generate SQL for the date a DateQuery compares with
+/// generate_session_query -> Generate SQL for SessionQuery node
+/// generate_restricted_query -> Generate SQL for RestrictedQuery node
+/// generate_fee_query -> Generate SQL for FeeQuery node
 /// generate_day_query -> Generate SQL for DayQuery node
+/// merge_meeting_filters -> Combine two EXISTS subqueries on meeting_times into one
+/// is_single_expression -> Check that SQL text has no parenthesis closed before it opened
+/// parenthesize -> Wrap SQL in parentheses unless it is already one parenthesized expression
+/// generate_field_query -> Generate SQL for FieldQuery node
 /// extract_condition -> Extract condition type from Condition node
 /// extract_binop -> Extract binary operator from Binop node
+/// comparison_operator -> Get the SQL operator a Binop node compares with
 /// extract_string_value -> Extract string value from Identifier/String node
+/// value_nodes -> Get the values a value node stands for
+/// unescape_string_literal -> Strip quotes and resolve escapes in a string literal
 /// extract_integer_value -> Extract integer value from Integer node
+/// build_numeric_condition -> Build SQL comparing a numeric column with a number or a range
 /// extract_time_value -> Extract time value from Time node
 /// token_to_sql_operator -> Convert token type string to SQL operator
-/// normalize_time -> Normalize time string to HH:MM:SS format
+/// is_negated_condition -> Check whether a condition negates its value
 /// build_string_condition -> Build SQL string condition based on condition type
+/// build_value_condition -> Build SQL comparing a column with a value or a list of values
+/// equality_value -> Get the value a string field query requires its column to equal
+/// equality_values -> Get every value a string field query requires its column to equal
+/// string_condition_sql -> Get the SQL a string field query compares its column with
+/// pattern_value -> Get the pattern a "matches" query compares its column with
+/// entity_columns -> Look up the SQL columns an entity searches
 /// --- ---
 ///
+use crate::data::sql::{normalize_instruction_method, normalize_session};
+use crate::dsl::entities::{self, ValueType};
+use crate::dsl::escape;
 use crate::dsl::parser::{Ast, NodeType, TreeNode};
+use crate::dsl::semantic::{parse_integer_literal, predicate_placement, Placement};
 use crate::dsl::token::TokenType;
+use crate::tui::error_catalog;
+use crate::utils::text::split_name;
+use crate::utils::time::{normalize_time, parse_date_phrase, Date};
 
 /// Type alias for code generation results
 type CodeGenResult = Result<String, CodeGenError>;
@@ -57,6 +110,7 @@ type CodeGenResult = Result<String, CodeGenError>;
 /// EmptyAst -> The AST has no root node
 /// UnsupportedNode -> A node type is not supported for code generation
 /// InvalidStructure -> The AST structure is invalid for the expected node type
+/// UnsafeValue -> A value can't be written into SQL (it has a control character)
 /// --- ---
 ///
 /// Implemented Traits:
@@ -72,26 +126,124 @@ pub enum CodeGenError {
     EmptyAst,
     UnsupportedNode { node_type: String },
     InvalidStructure { message: String },
+    UnsafeValue { value: String },
 }
 
 impl std::fmt::Display for CodeGenError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            CodeGenError::EmptyAst => write!(f, "Cannot generate SQL from an empty AST"),
+        let message = match self {
+            CodeGenError::EmptyAst => error_catalog::message("codegen_empty_ast", &[]),
             CodeGenError::UnsupportedNode { node_type } => {
-                write!(
-                    f,
-                    "Unsupported node type for code generation: {}",
-                    node_type
-                )
+                error_catalog::message("codegen_unsupported_node", &[("node_type", node_type)])
             }
             CodeGenError::InvalidStructure { message } => {
-                write!(f, "Invalid AST structure: {}", message)
+                error_catalog::message("codegen_invalid_structure", &[("message", message)])
+            }
+            CodeGenError::UnsafeValue { value } => {
+                error_catalog::message("codegen_unsafe_value", &[("value", value)])
             }
+        };
+        write!(f, "{}", message)
+    }
+}
+
+/// The order search results are returned in
+///
+/// Queries can't order their own results, so this is chosen in Settings and applied to
+/// every search. Ties are broken by subject, course number, and section, so the order is
+/// the same on every run
+///
+/// Variants:
+/// --- ---
+/// Database -> Whatever order SQLite returns the groups in (no ORDER BY)
+/// Course -> By subject, then course number, then section
+/// StartTime -> By each section's earliest meeting, sections without meetings last
+/// OpenSeats -> Most open seats first, sections without an enrollment cap last
+/// Professor -> By professor name, sections without a professor last
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for ResultOrder
+/// Clone -> Clone trait for ResultOrder
+/// Copy -> Copy trait for ResultOrder
+/// PartialEq -> PartialEq trait for ResultOrder
+/// Default -> Database, the order results came back in before there was a choice
+/// --- ---
+///
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ResultOrder {
+    #[default]
+    Database,
+    Course,
+    StartTime,
+    OpenSeats,
+    Professor,
+}
+
+impl ResultOrder {
+    /// Get every ordering, in the order Settings cycles through them
+    ///
+    /// Returns:
+    /// --- ---
+    /// [ResultOrder; 5] -> Every ordering
+    /// --- ---
+    ///
+    pub fn all() -> [ResultOrder; 5] {
+        [
+            ResultOrder::Database,
+            ResultOrder::Course,
+            ResultOrder::StartTime,
+            ResultOrder::OpenSeats,
+            ResultOrder::Professor,
+        ]
+    }
+
+    /// Get the ordering's name as shown in Settings
+    ///
+    /// Returns:
+    /// --- ---
+    /// &'static str -> The name (e.g., "Start Time")
+    /// --- ---
+    ///
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ResultOrder::Database => "Database Order",
+            ResultOrder::Course => "Subject + Number",
+            ResultOrder::StartTime => "Start Time",
+            ResultOrder::OpenSeats => "Open Seats",
+            ResultOrder::Professor => "Professor",
         }
     }
+
+    /// Get the ORDER BY clause for the ordering
+    ///
+    /// Returns:
+    /// --- ---
+    /// String -> The clause with a leading space, empty for Database
+    /// --- ---
+    ///
+    pub fn order_by(&self) -> String {
+        // NULLs sort first in SQLite, so "x IS NULL" puts the missing values last
+        let first = match self {
+            ResultOrder::Database => return String::new(),
+            ResultOrder::Course => "",
+            ResultOrder::StartTime => "MIN(mt.start_minutes) IS NULL, MIN(mt.start_minutes), ",
+            ResultOrder::OpenSeats => {
+                "s.max_enrollment IS NULL, s.max_enrollment - COALESCE(s.enrollment, 0) DESC, "
+            }
+            ResultOrder::Professor => "p.name IS NULL, p.name COLLATE NOCASE, ",
+        };
+        format!(" ORDER BY {}c.subject_code, c.number, s.sequence", first)
+    }
 }
 
+/// Results a search returns unless Settings changes the cap
+///
+/// Every section of every term of a large school can run to tens of thousands of rows, more
+/// than the results grid needs to hold at once
+pub const DEFAULT_MAX_ROWS: usize = 5_000;
+
 /// Generate SQL from an AST
 ///
 /// Parameters:
@@ -105,7 +257,7 @@ impl std::fmt::Display for CodeGenError {
 /// --- ---
 ///
 pub fn generate_sql(ast: &Ast) -> CodeGenResult {
-    generate_sql_with_filters(ast, None, None)
+    generate_sql_with_filters(ast, None, None, &[], ResultOrder::Database, None)
 }
 
 /// Generate SQL from an AST with optional school filter
@@ -114,6 +266,10 @@ pub fn generate_sql(ast: &Ast) -> CodeGenResult {
 /// --- ---
 /// ast -> The AST to generate SQL from
 /// school_id -> Optional school ID to filter results
+/// term_id -> Optional term ID to filter results
+/// excluded_term_ids -> Archived term IDs to leave out when no term is given
+/// order -> The order to return results in
+/// max_rows -> The most results to return, None for no cap
 /// --- ---
 ///
 /// Returns:
@@ -125,30 +281,196 @@ pub fn generate_sql_with_filters(
     ast: &Ast,
     school_id: Option<&str>,
     term_id: Option<&str>,
+    excluded_term_ids: &[String],
+    order: ResultOrder,
+    max_rows: Option<usize>,
 ) -> CodeGenResult {
     let root = ast.head.as_ref().ok_or(CodeGenError::EmptyAst)?;
 
-    // generate WHERE clause - day queries use the joined mt table directly
-    let where_clause = generate_node(root)?;
+    // predicates on a single meeting go in HAVING, so filtering never drops a matching
+    // section's other meetings from its meeting_times and day columns
+    let (where_clause, having_clause) = generate_clauses(root)?;
+
+    let sql = select_sections(
+        where_clause,
+        having_clause,
+        school_id,
+        term_id,
+        excluded_term_ids,
+    )?;
+    Ok(sql + &order.order_by() + &limit_clause(max_rows))
+}
+
+/// Get the LIMIT clause capping the results of a search
+///
+/// One row past the cap is fetched, so the caller can tell whether the cap cut any off
+///
+/// Parameters:
+/// --- ---
+/// max_rows -> The most results to return, None for no cap
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> The clause with a leading space, empty for no cap
+/// --- ---
+///
+pub fn limit_clause(max_rows: Option<usize>) -> String {
+    match max_rows {
+        Some(max_rows) => format!(" LIMIT {}", max_rows.saturating_add(1)),
+        None => String::new(),
+    }
+}
+
+/// Generate SQL selecting every section in a school and term
+///
+/// Used to load the term index, so its rows have the same columns as search results
+///
+/// Parameters:
+/// --- ---
+/// school_id -> Optional school ID to filter results
+/// term_id -> Optional term ID to filter results
+/// excluded_term_ids -> Archived term IDs to leave out when no term is given
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// CodeGenResult -> The generated SQL query or an error
+/// --- ---
+///
+pub fn generate_scope_sql(
+    school_id: Option<&str>,
+    term_id: Option<&str>,
+    excluded_term_ids: &[String],
+) -> CodeGenResult {
+    select_sections(None, None, school_id, term_id, excluded_term_ids)
+}
+
+/// Generate SQL selecting the sections of one course
+///
+/// Used by the catalog browser, so its sections have the same columns as search results
+///
+/// Parameters:
+/// --- ---
+/// school_id -> Optional school ID to filter results
+/// term_id -> Optional term ID to filter results
+/// subject_code -> The course's subject, e.g., "CMPT"
+/// course_number -> The course's number, e.g., "103L"
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// CodeGenResult -> The generated SQL query or an error
+/// --- ---
+///
+pub fn generate_course_sql(
+    school_id: Option<&str>,
+    term_id: Option<&str>,
+    subject_code: &str,
+    course_number: &str,
+) -> CodeGenResult {
+    let course = format!(
+        "s.subject_code = {} AND s.course_number = {}",
+        escape::string_literal(subject_code)?,
+        escape::string_literal(course_number)?
+    );
+    select_sections(Some(course), None, school_id, term_id, &[])
+}
+
+/// Generate SQL selecting the sections a professor teaches
+///
+/// Used by the professor directory, so its sections have the same columns as search results
+///
+/// Parameters:
+/// --- ---
+/// school_id -> Optional school ID to filter results
+/// term_id -> Optional term ID to filter results
+/// professor_id -> The professor's ID in the synced data
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// CodeGenResult -> The generated SQL query or an error
+/// --- ---
+///
+pub fn generate_professor_sections_sql(
+    school_id: Option<&str>,
+    term_id: Option<&str>,
+    professor_id: &str,
+) -> CodeGenResult {
+    let professor = format!(
+        "s.primary_professor_id = {}",
+        escape::string_literal(professor_id)?
+    );
+    select_sections(Some(professor), None, school_id, term_id, &[])
+}
 
+/// Build the section SELECT around WHERE and HAVING conditions
+///
+/// Parameters:
+/// --- ---
+/// where_clause -> Conditions on section rows (optional)
+/// having_clause -> Conditions on each section's grouped meetings (optional)
+/// school_id -> Optional school ID to filter results
+/// term_id -> Optional term ID to filter results
+/// excluded_term_ids -> Archived term IDs to leave out when no term is given
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// CodeGenResult -> The full SQL query or an error
+/// --- ---
+///
+fn select_sections(
+    where_clause: Option<String>,
+    having_clause: Option<String>,
+    school_id: Option<&str>,
+    term_id: Option<&str>,
+    excluded_term_ids: &[String],
+) -> CodeGenResult {
     // build filter conditions
     let mut filters = Vec::new();
     if let Some(id) = school_id {
-        filters.push(format!("s.school_id = '{}'", id));
+        filters.push(format!("s.school_id = {}", escape::string_literal(id)?));
     }
     if let Some(id) = term_id {
-        filters.push(format!("s.term_collection_id = '{}'", id));
+        filters.push(format!(
+            "s.term_collection_id = {}",
+            escape::string_literal(id)?
+        ));
+    } else if !excluded_term_ids.is_empty() {
+        let excluded = excluded_term_ids
+            .iter()
+            .map(|id| escape::string_literal(id))
+            .collect::<Result<Vec<String>, CodeGenError>>()?;
+        filters.push(format!(
+            "s.term_collection_id NOT IN ({})",
+            excluded.join(", ")
+        ));
     }
 
     // wrap with filters if provided
-    let where_clause = if filters.is_empty() {
-        where_clause
-    } else {
-        format!("{} AND ({})", filters.join(" AND "), where_clause)
+    let where_clause = match (filters.is_empty(), where_clause) {
+        (true, None) => String::new(),
+        (true, Some(conditions)) => format!("WHERE {} ", conditions),
+        (false, None) => format!("WHERE {} ", filters.join(" AND ")),
+        (false, Some(conditions)) => {
+            format!("WHERE {} AND ({}) ", filters.join(" AND "), conditions)
+        }
     };
+    let having_clause = having_clause
+        .map(|conditions| format!(" HAVING {}", conditions))
+        .unwrap_or_default();
 
     // build the full SQL query with joins and aggregation
-    let sql = format!(
+    // groups are keyed on the sections primary key, so every section is one row no matter
+    // how AND/OR conditions combine; courses and professors are joined on their primary
+    // keys, so their columns are the same for every row of a group
+    // the CRN is not a column, sync keeps it in the section's JSON extras under the name
+    // the registrar's API uses (`crn` or Banner's `courseReferenceNumber`); a final exam,
+    // when the source lists one, is there too and comes back packed as "date start-end";
+    // registration restrictions come back as their JSON object, and a fee as its amount
+    Ok(format!(
         "SELECT \
             c.subject_code, \
             c.number AS course_number, \
@@ -172,7 +494,10 @@ pub fn generate_sql_with_filters(
                  CASE WHEN mt.is_friday = 1 THEN 'F' ELSE '' END || \
                  CASE WHEN mt.is_saturday = 1 THEN 'S' ELSE '' END || \
                  CASE WHEN mt.is_sunday = 1 THEN 'SU' ELSE '' END) || \
-                ':' || mt.start_minutes || '-' || mt.end_minutes, \
+                ':' || mt.start_minutes || '-' || mt.end_minutes || \
+                '@' || COALESCE(substr(mt.start_date, 1, 10), '') || \
+                '/' || COALESCE(substr(mt.end_date, 1, 10), '') || \
+                '/' || COALESCE(json_extract(mt.other, '$.weekParity'), ''), \
                 '|' \
             ) AS meeting_times, \
             GROUP_CONCAT(DISTINCT mt.meeting_type) AS meeting_type, \
@@ -182,7 +507,17 @@ pub fn generate_sql_with_filters(
             MAX(mt.is_thursday) AS is_thursday, \
             MAX(mt.is_friday) AS is_friday, \
             MAX(mt.is_saturday) AS is_saturday, \
-            MAX(mt.is_sunday) AS is_sunday \
+            MAX(mt.is_sunday) AS is_sunday, \
+            CAST(COALESCE( \
+                json_extract(s.other, '$.crn'), \
+                json_extract(s.other, '$.courseReferenceNumber') \
+            ) AS TEXT) AS crn, \
+            substr(json_extract(s.other, '$.finalExam.date'), 1, 10) || ' ' || \
+                json_extract(s.other, '$.finalExam.startTime') || '-' || \
+                json_extract(s.other, '$.finalExam.endTime') AS final_exam, \
+            {} AS session, \
+            json_extract(s.other, '$.restrictions') AS restrictions, \
+            json_extract(s.other, '$.fee') AS fee \
         FROM sections s \
         JOIN courses c ON s.school_id = c.school_id \
             AND s.subject_code = c.subject_code \
@@ -194,28 +529,16 @@ pub fn generate_sql_with_filters(
             AND s.school_id = mt.school_id \
             AND s.subject_code = mt.subject_code \
             AND s.course_number = mt.course_number \
-        WHERE {} \
-        GROUP BY \
-            c.subject_code, \
-            c.number, \
-            c.title, \
-            c.description, \
-            c.credit_hours, \
-            c.prerequisites, \
-            c.corequisites, \
-            s.sequence, \
-            s.term_collection_id, \
+        LEFT JOIN term_spans span ON span.term_collection_id = s.term_collection_id \
+            AND span.school_id = s.school_id \
+        {}GROUP BY \
             s.school_id, \
-            s.max_enrollment, \
-            s.enrollment, \
-            s.instruction_method, \
-            s.campus, \
-            p.name, \
-            p.email_address",
-        where_clause
-    );
-
-    Ok(sql)
+            s.term_collection_id, \
+            s.subject_code, \
+            s.course_number, \
+            s.sequence{}",
+        SESSION_SQL, where_clause, having_clause
+    ))
 }
 
 /// Generate SQL for a single AST node
@@ -241,6 +564,7 @@ fn generate_node(node: &TreeNode) -> CodeGenResult {
         NodeType::EntityQuery => generate_entity_query(node),
         NodeType::T(TokenType::And) => generate_and(node),
         NodeType::T(TokenType::Or) => generate_or(node),
+        NodeType::T(TokenType::Not) => generate_not(node),
         NodeType::ProfessorQuery => generate_professor_query(node),
         NodeType::CourseQuery => generate_course_query(node),
         NodeType::SubjectQuery => generate_subject_query(node),
@@ -257,7 +581,12 @@ fn generate_node(node: &TreeNode) -> CodeGenResult {
         NodeType::FullQuery => generate_full_query(node),
         NodeType::MeetingTypeQuery => generate_meeting_type_query(node),
         NodeType::TimeQuery => generate_time_query(node),
+        NodeType::DateQuery => generate_date_query(node),
+        NodeType::SessionQuery => generate_session_query(node),
+        NodeType::RestrictedQuery => generate_restricted_query(node),
+        NodeType::FeeQuery => generate_fee_query(node),
         NodeType::DayQuery => generate_day_query(node),
+        NodeType::FieldQuery => generate_field_query(node),
         _ => Err(CodeGenError::UnsupportedNode {
             node_type: format!("{:?}", node.node_type),
         }),
@@ -305,33 +634,53 @@ fn generate_logical_term(node: &TreeNode) -> CodeGenResult {
     }
 
     // collect all conditions from the AND chain
-    let mut conditions = Vec::new();
-    let mut current = &node.children[0];
-
-    // traverse the AND chain to collect all conditions
-    loop {
-        match &current.node_type {
-            NodeType::T(TokenType::And) => {
-                if current.children.len() >= 2 {
-                    conditions.push(generate_node(&current.children[0])?);
-                    current = &current.children[1];
-                } else {
-                    break;
-                }
-            }
-            _ => {
-                conditions.push(generate_node(current)?);
-                break;
-            }
+    let mut operands = Vec::new();
+    collect_and_operands(&node.children[0], &mut operands);
+    let conditions = operands
+        .into_iter()
+        .map(generate_node)
+        .collect::<Result<Vec<String>, CodeGenError>>()?;
+
+    Ok(combine_and_conditions(conditions))
+}
+
+/// Join the conditions of an AND chain
+///
+/// Parameters:
+/// --- ---
+/// conditions -> SQL for each operand, in query order
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> The conditions joined by AND, with duplicates dropped, meeting_times
+///     subqueries merged, and EXISTS subqueries last
+/// --- ---
+///
+fn combine_and_conditions(conditions: Vec<String>) -> String {
+    // X AND X is X, and every meeting_times subquery in the chain becomes one
+    let mut merged: Vec<String> = Vec::new();
+    for condition in conditions {
+        if merged.contains(&condition) {
+            continue;
+        }
+        let combined = merged.iter().enumerate().find_map(|(i, existing)| {
+            merge_meeting_filters(existing, &condition, TokenType::And).map(|sql| (i, sql))
+        });
+        match combined {
+            Some((i, sql)) => merged[i] = sql,
+            None => merged.push(condition),
         }
     }
+    let mut conditions = merged;
 
     // sort: non-EXISTS conditions first, then EXISTS subqueries
     // this ensures more selective filters (like professor) are evaluated before
     // expensive EXISTS subqueries (like day queries)
+    let is_exists = |sql: &str| sql.starts_with("EXISTS") || sql.starts_with("NOT EXISTS");
     conditions.sort_by(|a, b| {
-        let a_is_exists = a.starts_with("EXISTS");
-        let b_is_exists = b.starts_with("EXISTS");
+        let a_is_exists = is_exists(a);
+        let b_is_exists = is_exists(b);
         match (a_is_exists, b_is_exists) {
             (true, false) => std::cmp::Ordering::Greater, // exists goes after
             (false, true) => std::cmp::Ordering::Less,    // non-exists goes first
@@ -339,9 +688,226 @@ fn generate_logical_term(node: &TreeNode) -> CodeGenResult {
         }
     });
 
+    conditions.join(" AND ")
+}
+
+/// Collect the operands of an AND chain
+///
+/// The parser nests "a and b and c" to the left, as AND(AND(a, b), c), so both sides of
+/// every AND node are walked. Single-child wrapper nodes are looked through, so a
+/// parenthesized chain joins the chain around it
+///
+/// Parameters:
+/// --- ---
+/// node -> The top of the chain
+/// operands -> Where the operands are collected, in query order, without their wrappers
+/// --- ---
+///
+fn collect_and_operands<'a>(node: &'a TreeNode, operands: &mut Vec<&'a TreeNode>) {
+    let node = unwrap_node(node);
+    if node.node_type == NodeType::T(TokenType::And) && node.children.len() == 2 {
+        collect_and_operands(&node.children[0], operands);
+        collect_and_operands(&node.children[1], operands);
+    } else {
+        operands.push(node);
+    }
+}
+
+/// Look through the single-child wrapper nodes around an expression
+///
+/// Parameters:
+/// --- ---
+/// node -> The node to unwrap
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// &TreeNode -> The first node that is not a Query, LogicalTerm, LogicalFactor, or
+///     EntityQuery with one child
+/// --- ---
+///
+fn unwrap_node(node: &TreeNode) -> &TreeNode {
+    match node.node_type {
+        NodeType::Query
+        | NodeType::LogicalTerm
+        | NodeType::LogicalFactor
+        | NodeType::EntityQuery
+            if node.children.len() == 1 =>
+        {
+            unwrap_node(&node.children[0])
+        }
+        _ => node,
+    }
+}
+
+/// Generate the WHERE and HAVING conditions for a query
+///
+/// The top-level AND chain is split with semantic::predicate_placement. Operands that only
+/// depend on the section go in WHERE; operands that test a meeting go in HAVING, where the
+/// section's meetings have already been grouped
+///
+/// Parameters:
+/// --- ---
+/// root -> The root node of the AST
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<(Option<String>, Option<String>), CodeGenError> -> The WHERE and HAVING
+///     conditions, each None if no operand belongs there, or an error
+/// --- ---
+///
+fn generate_clauses(root: &TreeNode) -> Result<(Option<String>, Option<String>), CodeGenError> {
+    let mut operands = Vec::new();
+    collect_and_operands(root, &mut operands);
+    let (having_operands, where_operands): (Vec<&TreeNode>, Vec<&TreeNode>) = operands
+        .into_iter()
+        .partition(|operand| predicate_placement(operand) == Placement::Having);
+
+    let where_clause = if where_operands.is_empty() {
+        None
+    } else {
+        let conditions = where_operands
+            .into_iter()
+            .map(generate_node)
+            .collect::<Result<Vec<String>, CodeGenError>>()?;
+        Some(combine_and_conditions(conditions))
+    };
+    let having_clause = if having_operands.is_empty() {
+        None
+    } else {
+        Some(generate_having_and(&having_operands)?)
+    };
+    Ok((where_clause, having_clause))
+}
+
+/// Generate a HAVING condition for the operands of an AND chain
+///
+/// Meeting predicates joined by AND describe the same meeting, so they share one
+/// MAX(...) = 1 ("some meeting matches all of them"). Other operands are generated on
+/// their own
+///
+/// Parameters:
+/// --- ---
+/// operands -> The unwrapped operands of the chain
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// CodeGenResult -> The operands joined by AND, or an error
+/// --- ---
+///
+fn generate_having_and(operands: &[&TreeNode]) -> CodeGenResult {
+    let mut meeting_predicates: Vec<String> = Vec::new();
+    let mut conditions: Vec<String> = Vec::new();
+    for operand in operands {
+        if is_meeting_predicate(operand) {
+            let predicate = generate_node(operand)?;
+            if !meeting_predicates.contains(&predicate) {
+                meeting_predicates.push(predicate);
+            }
+        } else {
+            let condition = generate_having(operand)?;
+            if !conditions.contains(&condition) {
+                conditions.push(condition);
+            }
+        }
+    }
+
+    if !meeting_predicates.is_empty() {
+        conditions.insert(0, format!("MAX({}) = 1", meeting_predicates.join(" AND ")));
+    }
     Ok(conditions.join(" AND "))
 }
 
+/// Check whether an unwrapped node is a single meeting predicate
+///
+/// Parameters:
+/// --- ---
+/// node -> The node to check
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// bool -> true for a start, end, or meeting type query (not a session, or an AND, OR, or NOT)
+/// --- ---
+///
+fn is_meeting_predicate(node: &TreeNode) -> bool {
+    predicate_placement(node) == Placement::Having
+        && !matches!(
+            node.node_type,
+            NodeType::T(TokenType::And)
+                | NodeType::T(TokenType::Or)
+                | NodeType::T(TokenType::Not)
+                | NodeType::SessionQuery
+        )
+}
+
+/// Generate a HAVING condition for a node
+///
+/// Section conditions are kept as they are, since their columns are the same for every
+/// row of a group. Meeting predicates become MAX(...) = 1, which holds when some meeting
+/// of the section matches
+///
+/// Parameters:
+/// --- ---
+/// node -> The node to generate a condition for
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// CodeGenResult -> The HAVING condition, or an error
+/// --- ---
+///
+fn generate_having(node: &TreeNode) -> CodeGenResult {
+    let node = unwrap_node(node);
+    // a session is worked out over all of the section's meetings, so it is already one
+    // value per group
+    if predicate_placement(node) == Placement::Where || node.node_type == NodeType::SessionQuery {
+        return generate_node(node);
+    }
+
+    match node.node_type {
+        NodeType::T(TokenType::And) => {
+            let mut operands = Vec::new();
+            collect_and_operands(node, &mut operands);
+            Ok(format!("({})", generate_having_and(&operands)?))
+        }
+        NodeType::T(TokenType::Or) => {
+            if node.children.len() != 2 {
+                return Err(CodeGenError::InvalidStructure {
+                    message: "OR node must have exactly 2 children".to_string(),
+                });
+            }
+            let left = generate_having(&node.children[0])?;
+            let right = generate_having(&node.children[1])?;
+            if left == right {
+                return Ok(left);
+            }
+            Ok(format!("({} OR {})", left, right))
+        }
+        NodeType::T(TokenType::Not) => {
+            if node.children.len() != 1 {
+                return Err(CodeGenError::InvalidStructure {
+                    message: "NOT node must have exactly 1 child".to_string(),
+                });
+            }
+            // "not start >= 9:00am" means no meeting starts then, which a section
+            // without meetings (MAX over no rows is NULL) satisfies too
+            let operand = unwrap_node(&node.children[0]);
+            if is_meeting_predicate(operand) {
+                Ok(format!("MAX({}) IS NOT 1", generate_node(operand)?))
+            } else {
+                Ok(format!(
+                    "{} IS NOT 1",
+                    parenthesize(&generate_having(operand)?)
+                ))
+            }
+        }
+        _ => Ok(format!("MAX({}) = 1", generate_node(node)?)),
+    }
+}
+
 /// Generate SQL for a LogicalFactor node
 ///
 /// Parameters:
@@ -405,6 +971,13 @@ fn generate_and(node: &TreeNode) -> CodeGenResult {
     let left = generate_node(&node.children[0])?;
     let right = generate_node(&node.children[1])?;
 
+    if left == right {
+        return Ok(left);
+    }
+    if let Some(merged) = merge_meeting_filters(&left, &right, TokenType::And) {
+        return Ok(merged);
+    }
+
     // put non-EXISTS conditions first so they filter rows before EXISTS subqueries
     let (first, second) = if right.starts_with("EXISTS") && !left.starts_with("EXISTS") {
         (left, right) // non-exists first
@@ -435,58 +1008,183 @@ fn generate_or(node: &TreeNode) -> CodeGenResult {
     }
     let left = generate_node(&node.children[0])?;
     let right = generate_node(&node.children[1])?;
+
+    if left == right {
+        return Ok(left);
+    }
+    if let Some(merged) = merge_meeting_filters(&left, &right, TokenType::Or) {
+        return Ok(merged);
+    }
     Ok(format!("({} OR {})", left, right))
 }
 
-/// Generate SQL for ProfessorQuery node
+/// Generate SQL for NOT operation
 ///
-/// Structure: children[0] = Condition, children[1] = Identifier/String
-/// Searches in both professor name and email address.
+/// A negated meeting_times subquery becomes NOT EXISTS, so "not monday" means the section
+/// has no Monday meeting rather than one meeting off Monday (and "not not monday" is just
+/// "monday" again). Anything else is negated with
+/// IS NOT 1, which is also true when the condition is NULL (e.g., "not prereqs contains
+/// MATH" matches courses without prerequisites)
 ///
 /// Parameters:
 /// --- ---
-/// node -> The ProfessorQuery node to generate SQL for
+/// node -> The NOT node to generate SQL for (must have 1 child)
 /// --- ---
 ///
 /// Returns:
 /// --- ---
-/// CodeGenResult -> The generated SQL fragment (OR condition for name/email) or an error
+/// CodeGenResult -> The generated SQL fragment with the negated condition or an error
 /// --- ---
 ///
-fn generate_professor_query(node: &TreeNode) -> CodeGenResult {
-    if node.children.len() != 2 {
+fn generate_not(node: &TreeNode) -> CodeGenResult {
+    if node.children.len() != 1 {
         return Err(CodeGenError::InvalidStructure {
-            message: "ProfessorQuery must have condition and value".to_string(),
+            message: "NOT node must have exactly 1 child".to_string(),
         });
     }
-    let condition = extract_condition(&node.children[0])?;
-    let value = extract_string_value(&node.children[1])?;
-
-    // search in professor name and email
-    let sql_condition = build_string_condition("p.name", &condition, &value);
-    let email_condition = build_string_condition("p.email_address", &condition, &value);
+    let operand = generate_node(&node.children[0])?;
 
-    Ok(format!("({} OR {})", sql_condition, email_condition))
+    if MeetingFilter::parse(&operand).is_some() {
+        return Ok(format!("NOT {}", operand));
+    }
+    if let Some(filter) = operand
+        .strip_prefix("NOT ")
+        .filter(|sql| MeetingFilter::parse(sql).is_some())
+    {
+        return Ok(filter.to_string());
+    }
+    Ok(format!("{} IS NOT 1", parenthesize(&operand)))
 }
 
-/// Generate SQL for CourseQuery node
+/// Generate SQL for ProfessorQuery node
 ///
-/// Can handle direct conditions or sub-queries.
-/// Searches in both course title and subject code.
+/// Structure: children[0] = Condition, children[1] = Identifier/EmailIdentifier/String
+/// An email address (or a quoted part of one with an "@", like "@marist.edu") searches the
+/// email column, anything else searches the professor's name (see
+/// build_professor_name_condition).
 ///
 /// Parameters:
 /// --- ---
-/// node -> The CourseQuery node to generate SQL for
+/// node -> The ProfessorQuery node to generate SQL for
 /// --- ---
 ///
 /// Returns:
 /// --- ---
-/// CodeGenResult -> The generated SQL fragment or an error
+/// CodeGenResult -> The generated SQL fragment (condition on name or email) or an error
 /// --- ---
 ///
-fn generate_course_query(node: &TreeNode) -> CodeGenResult {
-    if node.children.is_empty() {
-        return Err(CodeGenError::InvalidStructure {
+fn generate_professor_query(node: &TreeNode) -> CodeGenResult {
+    if node.children.len() != 2 {
+        return Err(CodeGenError::InvalidStructure {
+            message: "ProfessorQuery must have condition and value".to_string(),
+        });
+    }
+    let condition = extract_condition(&node.children[0])?;
+
+    // columns are [name, email, first name, last name]
+    let columns = entity_columns("professor")?;
+    // "prof in (Smith, Jones)" matches any of the professors
+    let matches = value_nodes(&node.children[1])
+        .into_iter()
+        .map(|value_node| {
+            let value = extract_string_value(value_node)?;
+            if value_node.node_type == NodeType::EmailIdentifier || value.contains('@') {
+                return build_string_condition(columns[1], &condition, &value);
+            }
+            build_professor_name_condition(columns, &condition, &value)
+        })
+        .collect::<Result<Vec<String>, CodeGenError>>()?;
+    Ok(match matches.as_slice() {
+        [single] => single.clone(),
+        _ => format!("({})", matches.join(" OR ")),
+    })
+}
+
+/// Build SQL matching a professor's name in either order
+///
+/// The name as synced ("Smith, John") always matches. For "is"/"equals", a value with a
+/// comma is also matched as "Last, First", and one without as "First Last" or "Last First".
+/// A single word matches last names first: it only matches first names when no professor
+/// at the school has it as a last name, so "prof is dennis" finds Megan Dennis rather than
+/// every Dennis. Patterns (contains, starts with, ...) match "First Last" too. Negated
+/// conditions match every professor the positive one doesn't
+///
+/// Parameters:
+/// --- ---
+/// columns -> The professor entity's columns (name, email, first name, last name)
+/// condition -> The condition type (e.g., "is", "does not contain")
+/// value -> The name as typed
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// CodeGenResult -> The generated SQL condition, or an error if the value can't be written
+///                  into SQL
+/// --- ---
+///
+fn build_professor_name_condition(columns: &[&str], condition: &str, value: &str) -> CodeGenResult {
+    let (name, first, last) = (columns[0], columns[2], columns[3]);
+    let negated = is_negated_condition(condition);
+    let positive = match negated {
+        false => condition,
+        true if condition.to_uppercase().contains("CONTAIN") => "contains",
+        true => "equals",
+    };
+    let matches = |column: &str, part: &str| build_string_condition(column, positive, part);
+
+    let mut alternatives = vec![matches(name, value)?];
+    let value = value.trim();
+    if !matches("v", value)?.starts_with("LOWER(v) = ") {
+        alternatives.push(matches(&format!("({} || ' ' || {})", first, last), value)?);
+    } else if value.contains(',') || value.contains(char::is_whitespace) {
+        let (first_part, last_part) = split_name(value);
+        let mut orders = vec![(first_part, last_part)];
+        // without a comma the order is unknown, so "Smith John" is tried as well
+        if let Some((last_part, first_part)) = value
+            .split_once(char::is_whitespace)
+            .filter(|_| !value.contains(','))
+        {
+            orders.push((first_part.trim().to_string(), last_part.to_string()));
+        }
+        for (first_part, last_part) in orders {
+            alternatives.push(format!(
+                "({} AND {})",
+                matches(last, &last_part)?,
+                matches(first, &first_part)?
+            ));
+        }
+    } else {
+        alternatives.push(matches(last, value)?);
+        alternatives.push(format!(
+            "({} AND NOT EXISTS (SELECT 1 FROM professors lp \
+             WHERE lp.school_id = p.school_id AND {}))",
+            matches(first, value)?,
+            matches("lp.last_name", value)?
+        ));
+    }
+
+    let sql = format!("({})", alternatives.join(" OR "));
+    Ok(if negated { format!("NOT {}", sql) } else { sql })
+}
+
+/// Generate SQL for CourseQuery node
+///
+/// Can handle direct conditions or sub-queries.
+/// Searches in both course title and subject code.
+///
+/// Parameters:
+/// --- ---
+/// node -> The CourseQuery node to generate SQL for
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// CodeGenResult -> The generated SQL fragment or an error
+/// --- ---
+///
+fn generate_course_query(node: &TreeNode) -> CodeGenResult {
+    if node.children.is_empty() {
+        return Err(CodeGenError::InvalidStructure {
             message: "CourseQuery has no children".to_string(),
         });
     }
@@ -495,11 +1193,13 @@ fn generate_course_query(node: &TreeNode) -> CodeGenResult {
     if node.children.len() == 2 {
         // direct condition: course <condition> <value>
         let condition = extract_condition(&node.children[0])?;
-        let value = extract_string_value(&node.children[1])?;
 
         // search in title and subject code combined
-        let title_cond = build_string_condition("c.title", &condition, &value);
-        let subject_cond = build_string_condition("c.subject_code", &condition, &value);
+        let columns = entity_columns("course")?;
+        let title_cond =
+            build_value_condition(columns[0], &condition, &node.children[1], str::to_string)?;
+        let subject_cond =
+            build_value_condition(columns[1], &condition, &node.children[1], str::to_string)?;
 
         Ok(format!("({} OR {})", title_cond, subject_cond))
     } else {
@@ -527,9 +1227,13 @@ fn generate_subject_query(node: &TreeNode) -> CodeGenResult {
         });
     }
     let condition = extract_condition(&node.children[0])?;
-    let value = extract_string_value(&node.children[1])?;
 
-    Ok(build_string_condition("c.subject_code", &condition, &value))
+    build_value_condition(
+        entity_columns("subject")?[0],
+        &condition,
+        &node.children[1],
+        str::to_string,
+    )
 }
 
 /// Generate SQL for NumberQuery node
@@ -551,9 +1255,13 @@ fn generate_number_query(node: &TreeNode) -> CodeGenResult {
         });
     }
     let condition = extract_condition(&node.children[0])?;
-    let value = extract_string_value(&node.children[1])?;
 
-    Ok(build_string_condition("c.number", &condition, &value))
+    build_value_condition(
+        entity_columns("number")?[0],
+        &condition,
+        &node.children[1],
+        str::to_string,
+    )
 }
 
 /// Generate SQL for TitleQuery node
@@ -575,9 +1283,13 @@ fn generate_title_query(node: &TreeNode) -> CodeGenResult {
         });
     }
     let condition = extract_condition(&node.children[0])?;
-    let value = extract_string_value(&node.children[1])?;
 
-    Ok(build_string_condition("c.title", &condition, &value))
+    build_value_condition(
+        entity_columns("title")?[0],
+        &condition,
+        &node.children[1],
+        str::to_string,
+    )
 }
 
 /// Generate SQL for DescriptionQuery node
@@ -599,14 +1311,19 @@ fn generate_description_query(node: &TreeNode) -> CodeGenResult {
         });
     }
     let condition = extract_condition(&node.children[0])?;
-    let value = extract_string_value(&node.children[1])?;
 
-    Ok(build_string_condition("c.description", &condition, &value))
+    build_value_condition(
+        entity_columns("description")?[0],
+        &condition,
+        &node.children[1],
+        str::to_string,
+    )
 }
 
 /// Generate SQL for CreditHoursQuery node
 ///
 /// Structure: children[0] = Binop, children[1] = Integer
+///            or children[0] = IntegerRange
 ///
 /// Parameters:
 /// --- ---
@@ -619,15 +1336,7 @@ fn generate_description_query(node: &TreeNode) -> CodeGenResult {
 /// --- ---
 ///
 fn generate_credit_hours_query(node: &TreeNode) -> CodeGenResult {
-    if node.children.len() != 2 {
-        return Err(CodeGenError::InvalidStructure {
-            message: "CreditHoursQuery must have operator and value".to_string(),
-        });
-    }
-    let operator = extract_binop(&node.children[0])?;
-    let value = extract_integer_value(&node.children[1])?;
-
-    Ok(format!("c.credit_hours {} {}", operator, value))
+    build_numeric_condition(entity_columns("credit hours")?[0], node)
 }
 
 /// Generate SQL for PrereqsQuery node
@@ -651,13 +1360,13 @@ fn generate_prereqs_query(node: &TreeNode) -> CodeGenResult {
         });
     }
     let condition = extract_condition(&node.children[0])?;
-    let value = extract_string_value(&node.children[1])?;
 
-    Ok(build_string_condition(
-        "c.prerequisites",
+    build_value_condition(
+        entity_columns("prerequisites")?[0],
         &condition,
-        &value,
-    ))
+        &node.children[1],
+        str::to_string,
+    )
 }
 
 /// Generate SQL for CoreqsQuery node
@@ -681,9 +1390,13 @@ fn generate_coreqs_query(node: &TreeNode) -> CodeGenResult {
         });
     }
     let condition = extract_condition(&node.children[0])?;
-    let value = extract_string_value(&node.children[1])?;
 
-    Ok(build_string_condition("c.corequisites", &condition, &value))
+    build_value_condition(
+        entity_columns("corequisites")?[0],
+        &condition,
+        &node.children[1],
+        str::to_string,
+    )
 }
 
 /// Generate SQL for EnrollmentCapQuery node
@@ -699,15 +1412,7 @@ fn generate_coreqs_query(node: &TreeNode) -> CodeGenResult {
 /// --- ---
 ///
 fn generate_enrollment_cap_query(node: &TreeNode) -> CodeGenResult {
-    if node.children.len() != 2 {
-        return Err(CodeGenError::InvalidStructure {
-            message: "EnrollmentCapQuery must have operator and value".to_string(),
-        });
-    }
-    let operator = extract_binop(&node.children[0])?;
-    let value = extract_integer_value(&node.children[1])?;
-
-    Ok(format!("s.max_enrollment {} {}", operator, value))
+    build_numeric_condition(entity_columns("cap")?[0], node)
 }
 
 /// Generate SQL for InstructionMethodQuery node
@@ -729,13 +1434,14 @@ fn generate_instruction_method_query(node: &TreeNode) -> CodeGenResult {
         });
     }
     let condition = extract_condition(&node.children[0])?;
-    let value = extract_string_value(&node.children[1])?;
 
-    Ok(build_string_condition(
-        "s.instruction_method",
+    // "web", "ONLN", and other spellings search the method sync normalized them to
+    build_value_condition(
+        entity_columns("method")?[0],
         &condition,
-        &value,
-    ))
+        &node.children[1],
+        normalize_instruction_method,
+    )
 }
 
 /// Generate SQL for CampusQuery node
@@ -757,9 +1463,13 @@ fn generate_campus_query(node: &TreeNode) -> CodeGenResult {
         });
     }
     let condition = extract_condition(&node.children[0])?;
-    let value = extract_string_value(&node.children[1])?;
 
-    Ok(build_string_condition("s.campus", &condition, &value))
+    build_value_condition(
+        entity_columns("campus")?[0],
+        &condition,
+        &node.children[1],
+        str::to_string,
+    )
 }
 
 /// Generate SQL for EnrollmentQuery node
@@ -775,15 +1485,7 @@ fn generate_campus_query(node: &TreeNode) -> CodeGenResult {
 /// --- ---
 ///
 fn generate_enrollment_query(node: &TreeNode) -> CodeGenResult {
-    if node.children.len() != 2 {
-        return Err(CodeGenError::InvalidStructure {
-            message: "EnrollmentQuery must have operator and value".to_string(),
-        });
-    }
-    let operator = extract_binop(&node.children[0])?;
-    let value = extract_integer_value(&node.children[1])?;
-
-    Ok(format!("s.enrollment {} {}", operator, value))
+    build_numeric_condition(entity_columns("enrollment")?[0], node)
 }
 
 /// Generate SQL for FullQuery node
@@ -808,11 +1510,12 @@ fn generate_full_query(node: &TreeNode) -> CodeGenResult {
     }
     let value = extract_string_value(&node.children[1])?;
     let is_full = value.to_lowercase() == "true";
+    let columns = entity_columns("full")?;
 
     if is_full {
-        Ok("s.enrollment >= s.max_enrollment".to_string())
+        Ok(format!("{} >= {}", columns[0], columns[1]))
     } else {
-        Ok("s.enrollment < s.max_enrollment".to_string())
+        Ok(format!("{} < {}", columns[0], columns[1]))
     }
 }
 
@@ -835,13 +1538,13 @@ fn generate_meeting_type_query(node: &TreeNode) -> CodeGenResult {
         });
     }
     let condition = extract_condition(&node.children[0])?;
-    let value = extract_string_value(&node.children[1])?;
 
-    Ok(build_string_condition(
-        "mt.meeting_type",
+    build_value_condition(
+        entity_columns("meeting type")?[0],
         &condition,
-        &value,
-    ))
+        &node.children[1],
+        str::to_string,
+    )
 }
 
 /// Generate SQL for TimeQuery node
@@ -869,11 +1572,12 @@ fn generate_time_query(node: &TreeNode) -> CodeGenResult {
 
     // determine if this is start or end time
     let time_type = &node.children[0].node_content;
-    let column = if time_type.to_lowercase().contains("start") {
-        "mt.start_minutes"
+    let entity = if time_type.to_lowercase().contains("start") {
+        "start"
     } else {
-        "mt.end_minutes"
+        "end"
     };
+    let column = entity_columns(entity)?[0];
 
     if node.children.len() == 2 {
         // time range: start 9:00 to 17:00
@@ -882,8 +1586,11 @@ fn generate_time_query(node: &TreeNode) -> CodeGenResult {
             let start_time = extract_time_value(&time_range.children[0])?;
             let end_time = extract_time_value(&time_range.children[1])?;
             Ok(format!(
-                "({} >= '{}' AND {} <= '{}')",
-                column, start_time, column, end_time
+                "({} >= {} AND {} <= {})",
+                column,
+                escape::time_literal(&start_time)?,
+                column,
+                escape::time_literal(&end_time)?
             ))
         } else {
             Err(CodeGenError::InvalidStructure {
@@ -894,7 +1601,12 @@ fn generate_time_query(node: &TreeNode) -> CodeGenResult {
         // comparison: start >= 9:00
         let operator = extract_binop(&node.children[1])?;
         let time_value = extract_time_value(&node.children[2])?;
-        Ok(format!("{} {} '{}'", column, operator, time_value))
+        Ok(format!(
+            "{} {} {}",
+            column,
+            operator,
+            escape::time_literal(&time_value)?
+        ))
     } else {
         Err(CodeGenError::InvalidStructure {
             message: "TimeQuery has unexpected number of children".to_string(),
@@ -902,6 +1614,211 @@ fn generate_time_query(node: &TreeNode) -> CodeGenResult {
     }
 }
 
+/// Generate SQL for DateQuery node
+///
+/// Structure: children[0] = String ("after"/"before"/"on")
+///            children[1] = Date
+///
+/// Like day queries this is an EXISTS subquery, so a section matches when one of its
+/// meetings does. A meeting's unknown dates are treated as open, though "on" a date without
+/// a year can't match a meeting without a first date, since its weekday isn't known
+///
+/// Parameters:
+/// --- ---
+/// node -> The DateQuery node to generate SQL for
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// CodeGenResult -> The generated SQL fragment or an error
+/// --- ---
+///
+fn generate_date_query(node: &TreeNode) -> CodeGenResult {
+    if node.children.len() != 2 {
+        return Err(CodeGenError::InvalidStructure {
+            message: "DateQuery must have a comparison and a date".to_string(),
+        });
+    }
+    let columns = entity_columns("meets")?;
+    let (start, end, other) = (columns[0], columns[1], columns[2]);
+    let date = date_expression(&node.children[1], start)?;
+
+    let predicate = match node.children[0].node_content.as_str() {
+        // still meeting after the date
+        "after" => format!("COALESCE(substr({}, 1, 10) > {}, 1) = 1", end, date),
+        // already meeting before the date
+        "before" => format!("COALESCE(substr({}, 1, 10) < {}, 1) = 1", start, date),
+        // held that day: within the dates, on the weekday, and in one of its weeks
+        "on" => {
+            let mut weekday = format!("CASE strftime('%w', {})", date);
+            let days = [
+                "sunday",
+                "monday",
+                "tuesday",
+                "wednesday",
+                "thursday",
+                "friday",
+                "saturday",
+            ];
+            for (number, day) in days.iter().enumerate() {
+                weekday.push_str(&format!(
+                    " WHEN '{}' THEN {}",
+                    number,
+                    entity_columns(day)?[0]
+                ));
+            }
+            weekday.push_str(" END = 1");
+
+            // weeks count from the meeting's first week, the way Meeting::held_in_week does
+            let parity = format!("lower(json_extract({}, '$.weekParity'))", other);
+            let weeks = format!(
+                "CAST((julianday(date({}, '-6 days', 'weekday 1')) - \
+                 julianday(date(substr({}, 1, 10), '-6 days', 'weekday 1'))) / 7 AS INTEGER)",
+                date, start
+            );
+            format!(
+                "COALESCE(substr({start}, 1, 10) <= {date} AND substr({end}, 1, 10) >= {date}, 1) = 1 \
+                 AND {weekday} \
+                 AND COALESCE({parity} NOT IN ('odd', 'even') OR {weeks} % 2 = ({parity} = 'even'), 1) = 1",
+                start = start,
+                end = end,
+                date = date,
+                weekday = weekday,
+                parity = parity,
+                weeks = weeks
+            )
+        }
+        other => {
+            return Err(CodeGenError::InvalidStructure {
+                message: format!("Unknown date comparison: {}", other),
+            })
+        }
+    };
+    Ok(MeetingFilter::Row(predicate).to_sql())
+}
+
+/// Generate SQL for the date a DateQuery compares with
+///
+/// A date without a year falls in the year of the meeting's first date, or the year after
+/// when its month comes earlier, so "jan 10" is in the second year of a term that starts
+/// in December
+///
+/// Parameters:
+/// --- ---
+/// node -> The Date node
+/// start_column -> The meeting's first date column
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// CodeGenResult -> SQL for the date as YYYY-MM-DD text, or an error if it isn't a date
+/// --- ---
+///
+fn date_expression(node: &TreeNode, start_column: &str) -> CodeGenResult {
+    let (year, month, day) =
+        parse_date_phrase(&node.node_content).ok_or_else(|| CodeGenError::InvalidStructure {
+            message: format!("Malformed date: {}", node.node_content),
+        })?;
+    match year {
+        Some(year) => escape::date_literal(&Date { year, month, day }.to_string()),
+        None => Ok(format!(
+            "(CASE WHEN CAST(substr({start}, 6, 2) AS INTEGER) <= {month} \
+             THEN substr({start}, 1, 4) \
+             ELSE CAST(substr({start}, 1, 4) + 1 AS TEXT) END || {month_day})",
+            start = start_column,
+            month = month,
+            month_day = escape::string_literal(&format!("-{:02}-{:02}", month, day))?
+        )),
+    }
+}
+
+/// Generate SQL for SessionQuery node
+///
+/// Values are read the way the session column names them, so "1st half" and "second"
+/// match the same sections as "first half" and "second half"
+///
+/// Parameters:
+/// --- ---
+/// node -> The SessionQuery node to generate SQL for
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// CodeGenResult -> The generated SQL fragment or an error
+/// --- ---
+///
+fn generate_session_query(node: &TreeNode) -> CodeGenResult {
+    if node.children.len() != 2 {
+        return Err(CodeGenError::InvalidStructure {
+            message: "SessionQuery must have condition and value".to_string(),
+        });
+    }
+    let condition = extract_condition(&node.children[0])?;
+
+    build_value_condition(
+        SESSION_SQL,
+        &condition,
+        &node.children[1],
+        normalize_session,
+    )
+}
+
+/// Generate SQL for RestrictedQuery node
+///
+/// "restricted is true" means the section needs instructor consent or is limited to some
+/// majors or class levels
+///
+/// Parameters:
+/// --- ---
+/// node -> The RestrictedQuery node to generate SQL for
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// CodeGenResult -> The generated SQL fragment or an error
+/// --- ---
+///
+fn generate_restricted_query(node: &TreeNode) -> CodeGenResult {
+    if node.children.len() != 2 {
+        return Err(CodeGenError::InvalidStructure {
+            message: "RestrictedQuery must have condition and value".to_string(),
+        });
+    }
+    let condition = extract_condition(&node.children[0])?;
+    let value = extract_string_value(&node.children[1])?;
+    let restricted = (value.to_lowercase() == "true") != is_negated_condition(&condition);
+
+    if restricted {
+        Ok(RESTRICTED_SQL.to_string())
+    } else {
+        Ok(format!("NOT {}", RESTRICTED_SQL))
+    }
+}
+
+/// Generate SQL for FeeQuery node
+///
+/// Parameters:
+/// --- ---
+/// node -> The FeeQuery node to generate SQL for
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// CodeGenResult -> The generated SQL fragment or an error
+/// --- ---
+///
+fn generate_fee_query(node: &TreeNode) -> CodeGenResult {
+    if node.children.len() != 2 {
+        return Err(CodeGenError::InvalidStructure {
+            message: "FeeQuery must have operator and value".to_string(),
+        });
+    }
+    let operator = extract_binop(&node.children[0])?;
+    let value = extract_integer_value(&node.children[1])?;
+
+    Ok(format!("{} {} {}", FEE_SQL, operator, value))
+}
+
 /// Generate SQL for DayQuery node
 ///
 /// Structure: children[0] = String node (day name) with children[0] = Condition, children[1] = value
@@ -927,14 +1844,8 @@ fn generate_day_query(node: &TreeNode) -> CodeGenResult {
     let day_name = day_node.node_content.to_lowercase();
 
     // map day names to column names for the EXISTS subquery
-    let column_filter = match day_name.as_str() {
-        "monday" => "mt_filter.is_monday",
-        "tuesday" => "mt_filter.is_tuesday",
-        "wednesday" => "mt_filter.is_wednesday",
-        "thursday" => "mt_filter.is_thursday",
-        "friday" => "mt_filter.is_friday",
-        "saturday" => "mt_filter.is_saturday",
-        "sunday" => "mt_filter.is_sunday",
+    let column_filter = match entities::entity_named(&day_name) {
+        Some(info) if info.value_type == ValueType::Day => escape::column(info.columns[0])?,
         _ => {
             return Err(CodeGenError::InvalidStructure {
                 message: format!("Unknown day: {}", day_name),
@@ -948,23 +1859,320 @@ fn generate_day_query(node: &TreeNode) -> CodeGenResult {
         });
     }
 
+    let condition = extract_condition(&day_node.children[0])?;
     let value = extract_string_value(&day_node.children[1])?;
-    let is_true = value.to_lowercase() == "true";
-    let day_value = if is_true { 1 } else { 0 };
+    let meets_on_day = (value.to_lowercase() == "true") != is_negated_condition(&condition);
 
     // use EXISTS subquery to filter sections that have at least one meeting_time
     // matching the day condition, but still include ALL meeting_times for those sections
     // this ensures that when filtering by "monday", we still see Thursday times for the same class
-    Ok(format!(
-        "EXISTS (SELECT 1 FROM meeting_times mt_filter \
-         WHERE mt_filter.section_sequence = s.sequence \
-         AND mt_filter.term_collection_id = s.term_collection_id \
-         AND mt_filter.school_id = s.school_id \
-         AND mt_filter.subject_code = s.subject_code \
-         AND mt_filter.course_number = s.course_number \
-         AND {} = {})",
-        column_filter, day_value
-    ))
+    let filter = MeetingFilter::Row(format!("{} = 1", column_filter)).to_sql();
+    if meets_on_day {
+        Ok(filter)
+    } else {
+        // "monday is false" means no Monday meeting, not one meeting that is off Monday
+        Ok(format!("NOT {}", filter))
+    }
+}
+
+/// SQL for the part of the term a section runs: "full", "first half", "second half", or
+/// "partial"
+///
+/// A session the sync provided (`session` in the section's JSON extras) is used when there
+/// is one. Otherwise it is worked out from the section's first and last meeting dates and
+/// the term's, read from the `term_spans` table joined as `span`. A week of slack on each side allows for sections
+/// starting a few days late or ending at the midterm break. It reads the section's grouped
+/// meetings, so it can only be used in the SELECT list and HAVING
+pub const SESSION_SQL: &str = "COALESCE( \
+        CASE \
+            WHEN json_extract(s.other, '$.session') IS NULL THEN NULL \
+            WHEN lower(json_extract(s.other, '$.session')) LIKE '%full%' THEN 'full' \
+            WHEN lower(json_extract(s.other, '$.session')) LIKE '%first%' \
+                OR lower(json_extract(s.other, '$.session')) LIKE '%1st%' THEN 'first half' \
+            WHEN lower(json_extract(s.other, '$.session')) LIKE '%second%' \
+                OR lower(json_extract(s.other, '$.session')) LIKE '%2nd%' THEN 'second half' \
+            ELSE lower(trim(json_extract(s.other, '$.session'))) \
+        END, \
+        CASE \
+            WHEN MIN(substr(mt.start_date, 1, 10)) IS NULL \
+                OR MAX(substr(mt.end_date, 1, 10)) IS NULL \
+                OR span.first_day IS NULL THEN NULL \
+            WHEN julianday(MIN(substr(mt.start_date, 1, 10))) <= julianday(span.first_day) + 7 \
+                AND julianday(MAX(substr(mt.end_date, 1, 10))) >= julianday(span.last_day) - 7 \
+                THEN 'full' \
+            WHEN julianday(MIN(substr(mt.start_date, 1, 10))) >= julianday(span.first_day) - 7 \
+                AND julianday(MAX(substr(mt.end_date, 1, 10))) \
+                    <= (julianday(span.first_day) + julianday(span.last_day)) / 2 + 7 \
+                THEN 'first half' \
+            WHEN julianday(MIN(substr(mt.start_date, 1, 10))) \
+                    >= (julianday(span.first_day) + julianday(span.last_day)) / 2 - 7 \
+                AND julianday(MAX(substr(mt.end_date, 1, 10))) <= julianday(span.last_day) + 7 \
+                THEN 'second half' \
+            ELSE 'partial' \
+        END)";
+
+/// Each term's first and last days, read by SESSION_SQL
+///
+/// They are the dates most of the term's meetings start and end on, so a few intersession
+/// sections don't stretch the term; each is the day with the MAX count, read with SQLite's
+/// bare-column rule for MAX. Counting every meeting is too slow to do on each search, so a
+/// sync records the result in the `term_spans` table (see sync::record_term_spans)
+pub const TERM_SPANS_SQL: &str = "(SELECT term_collection_id, school_id, \
+        MAX(CASE WHEN bound = 'first' THEN day END) AS first_day, \
+        MAX(CASE WHEN bound = 'last' THEN day END) AS last_day \
+    FROM (SELECT term_collection_id, school_id, bound, day, MAX(day_count) \
+        FROM (SELECT term_collection_id, school_id, bound, day, COUNT(*) AS day_count \
+            FROM (SELECT term_collection_id, school_id, 'first' AS bound, \
+                    substr(start_date, 1, 10) AS day FROM meeting_times \
+                UNION ALL SELECT term_collection_id, school_id, 'last', \
+                    substr(end_date, 1, 10) FROM meeting_times) \
+            WHERE day IS NOT NULL \
+            GROUP BY term_collection_id, school_id, bound, day) \
+        GROUP BY term_collection_id, school_id, bound) \
+    GROUP BY term_collection_id, school_id)";
+
+/// SQL for whether a section has a registration restriction
+///
+/// The sync keeps restrictions in the section's JSON extras as a `restrictions` object:
+/// `instructorConsent` (true when the instructor must approve each student), and `majors`
+/// and `classLevels` (the only majors and class levels that may register). Sections
+/// without one are unrestricted
+pub const RESTRICTED_SQL: &str =
+    "(COALESCE(json_extract(s.other, '$.restrictions.instructorConsent'), 0) = 1 \
+        OR COALESCE(json_array_length(s.other, '$.restrictions.majors'), 0) > 0 \
+        OR COALESCE(json_array_length(s.other, '$.restrictions.classLevels'), 0) > 0)";
+
+/// SQL for a section's lab or course fee in dollars
+///
+/// The sync keeps the fee in the section's JSON extras as `fee`; sections without one
+/// have no fee, so "fee <= 50" finds them too
+pub const FEE_SQL: &str = "COALESCE(json_extract(s.other, '$.fee'), 0)";
+
+/// Start of every EXISTS subquery on a section's meeting_times rows
+const MEETING_FILTER_PREFIX: &str = "EXISTS (SELECT 1 FROM meeting_times mt_filter \
+     WHERE mt_filter.section_sequence = s.sequence \
+     AND mt_filter.term_collection_id = s.term_collection_id \
+     AND mt_filter.school_id = s.school_id \
+     AND mt_filter.subject_code = s.subject_code \
+     AND mt_filter.course_number = s.course_number";
+
+/// A condition on a section's meeting_times rows, as one EXISTS subquery
+///
+/// A Row filter holds for a section with at least one meeting matching the predicate. An
+/// Aggregate filter is checked over all of the section's meetings at once, where MAX(p) = 1
+/// means "some meeting matches p", so conditions that different meetings satisfy (a Monday
+/// lecture and a Wednesday lab) can share one subquery without changing their meaning
+///
+/// Variants:
+/// --- ---
+/// Row -> Predicate on a single meeting_times row (e.g., "mt_filter.is_monday = 1")
+/// Aggregate -> HAVING condition over the section's meeting_times rows
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for MeetingFilter
+/// --- ---
+///
+#[derive(Debug)]
+enum MeetingFilter {
+    Row(String),
+    Aggregate(String),
+}
+
+impl MeetingFilter {
+    /// Read a filter back from SQL made by to_sql
+    ///
+    /// Parameters:
+    /// --- ---
+    /// sql -> Generated SQL for a condition
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Option<MeetingFilter> -> The filter, or None if the SQL is anything but a single
+    ///     meeting_times subquery
+    /// --- ---
+    ///
+    fn parse(sql: &str) -> Option<MeetingFilter> {
+        let rest = sql.strip_prefix(MEETING_FILTER_PREFIX)?.strip_suffix(')')?;
+        // "EXISTS (...) AND EXISTS (...)" has the same prefix and suffix
+        if !is_single_expression(rest) {
+            return None;
+        }
+        if let Some(predicate) = rest.strip_prefix(" AND ") {
+            Some(MeetingFilter::Row(predicate.to_string()))
+        } else {
+            rest.strip_prefix(" GROUP BY mt_filter.section_sequence HAVING ")
+                .map(|having| MeetingFilter::Aggregate(having.to_string()))
+        }
+    }
+
+    /// Write the filter as an EXISTS subquery
+    ///
+    /// Returns:
+    /// --- ---
+    /// String -> The SQL condition
+    /// --- ---
+    ///
+    fn to_sql(&self) -> String {
+        match self {
+            MeetingFilter::Row(predicate) => {
+                format!("{} AND {})", MEETING_FILTER_PREFIX, predicate)
+            }
+            MeetingFilter::Aggregate(having) => format!(
+                "{} GROUP BY mt_filter.section_sequence HAVING {})",
+                MEETING_FILTER_PREFIX, having
+            ),
+        }
+    }
+
+    /// Get the filter as a condition over all of the section's meetings
+    ///
+    /// Returns:
+    /// --- ---
+    /// String -> The HAVING condition
+    /// --- ---
+    ///
+    fn having(&self) -> String {
+        match self {
+            MeetingFilter::Row(predicate) => format!("MAX({}) = 1", predicate),
+            MeetingFilter::Aggregate(having) => having.clone(),
+        }
+    }
+}
+
+/// Combine two EXISTS subqueries on meeting_times into one
+///
+/// OR of row predicates stays a row predicate, since a meeting matching either one is a
+/// meeting matching one of them. Anything joined by AND is checked per section instead,
+/// because "monday and wednesday" should still match a Monday lecture with a Wednesday lab
+///
+/// Parameters:
+/// --- ---
+/// left -> SQL for the left condition
+/// right -> SQL for the right condition
+/// operator -> TokenType::And or TokenType::Or
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Option<String> -> The merged subquery, or None if either side is not a meeting_times subquery
+/// --- ---
+///
+fn merge_meeting_filters(left: &str, right: &str, operator: TokenType) -> Option<String> {
+    let left = MeetingFilter::parse(left)?;
+    let right = MeetingFilter::parse(right)?;
+    let merged = match (operator, &left, &right) {
+        (TokenType::Or, MeetingFilter::Row(a), MeetingFilter::Row(b)) => {
+            MeetingFilter::Row(format!("({} OR {})", a, b))
+        }
+        (TokenType::Or, _, _) => {
+            MeetingFilter::Aggregate(format!("({} OR {})", left.having(), right.having()))
+        }
+        _ => MeetingFilter::Aggregate(format!("({} AND {})", left.having(), right.having())),
+    };
+    Some(merged.to_sql())
+}
+
+/// Check that SQL text has no parenthesis closed before it opened
+///
+/// Parentheses inside quoted strings are ignored
+///
+/// Parameters:
+/// --- ---
+/// sql -> The SQL text
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// bool -> true if every parenthesis is balanced without dipping below depth zero
+/// --- ---
+///
+fn is_single_expression(sql: &str) -> bool {
+    let mut depth = 0_i32;
+    let mut in_string = false;
+    for c in sql.chars() {
+        match c {
+            // an escaped '' toggles twice, so it leaves the state unchanged
+            '\'' => in_string = !in_string,
+            '(' if !in_string => depth += 1,
+            ')' if !in_string => {
+                depth -= 1;
+                if depth < 0 {
+                    return false;
+                }
+            }
+            _ => {}
+        }
+    }
+    depth == 0 && !in_string
+}
+
+/// Wrap SQL in parentheses unless it is already one parenthesized expression
+///
+/// Parameters:
+/// --- ---
+/// sql -> The SQL text
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> The SQL, safe to put in front of an operator
+/// --- ---
+///
+fn parenthesize(sql: &str) -> String {
+    let wrapped = sql
+        .strip_prefix('(')
+        .and_then(|inner| inner.strip_suffix(')'))
+        .is_some_and(is_single_expression);
+    if wrapped {
+        sql.to_string()
+    } else {
+        format!("({})", sql)
+    }
+}
+
+/// Generate SQL for FieldQuery node
+///
+/// Fields declared with define_field! hold their name as node content, the column
+/// and value type come from the entity registry
+///
+/// Parameters:
+/// --- ---
+/// node -> The FieldQuery node to generate SQL for
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// CodeGenResult -> The generated SQL fragment or an error
+/// --- ---
+///
+fn generate_field_query(node: &TreeNode) -> CodeGenResult {
+    if node.children.len() != 2 {
+        return Err(CodeGenError::InvalidStructure {
+            message: format!(
+                "{} query must have a comparison and value",
+                node.node_content
+            ),
+        });
+    }
+    let info = entities::entity_named(&node.node_content).ok_or_else(|| {
+        CodeGenError::InvalidStructure {
+            message: format!("Unknown field: {}", node.node_content),
+        }
+    })?;
+
+    let column = escape::column(info.columns[0])?;
+
+    if info.value_type == ValueType::Integer {
+        let operator = extract_binop(&node.children[0])?;
+        let value = extract_integer_value(&node.children[1])?;
+        Ok(format!("{} {} {}", column, operator, value))
+    } else {
+        let condition = extract_condition(&node.children[0])?;
+        build_value_condition(column, &condition, &node.children[1], str::to_string)
+    }
 }
 
 /// Extract the condition type from a Condition node
@@ -1046,6 +2254,25 @@ fn extract_binop(node: &TreeNode) -> CodeGenResult {
     }
 }
 
+/// Get the SQL operator a Binop node compares with
+///
+/// Semantic analysis uses this to check comparisons against a field's range, so it reads
+/// operators the same way the generated SQL does
+///
+/// Parameters:
+/// --- ---
+/// node -> The Binop node
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Option<String> -> The SQL operator (e.g., "<=" for "at most"), None if it isn't a Binop
+/// --- ---
+///
+pub fn comparison_operator(node: &TreeNode) -> Option<String> {
+    extract_binop(node).ok()
+}
+
 /// Convert a token type string to SQL operator
 ///
 /// Parameters:
@@ -1090,8 +2317,13 @@ fn token_to_sql_operator(token: &str) -> String {
 fn extract_string_value(node: &TreeNode) -> CodeGenResult {
     match &node.node_type {
         NodeType::Identifier | NodeType::EmailIdentifier | NodeType::String => {
-            let value = node.node_content.clone();
-            Ok(value.trim_matches('"').to_string())
+            let value = &node.node_content;
+            // quoted values keep their quotes and escapes in the lexeme
+            if value.starts_with('"') {
+                Ok(unescape_string_literal(value))
+            } else {
+                Ok(value.clone())
+            }
         }
         _ => Err(CodeGenError::InvalidStructure {
             message: format!("Expected string-like node, got {:?}", node.node_type),
@@ -1099,6 +2331,57 @@ fn extract_string_value(node: &TreeNode) -> CodeGenResult {
     }
 }
 
+/// Get the values a value node stands for
+///
+/// Parameters:
+/// --- ---
+/// node -> An Identifier, String, or EmailIdentifier node, or a StringList of them
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Vec<&TreeNode> -> A list's values, or the node itself
+/// --- ---
+///
+fn value_nodes(node: &TreeNode) -> Vec<&TreeNode> {
+    match node.node_type {
+        NodeType::StringList => node.children.iter().collect(),
+        _ => vec![node],
+    }
+}
+
+/// Strip the surrounding quotes from a string literal and resolve its escapes
+///
+/// A backslash makes the next character literal, so \" is a quote and \\ is a backslash
+///
+/// Parameters:
+/// --- ---
+/// literal -> The quoted string as written in the query (e.g., "the \"Classics\"")
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> The string's value without quotes or escapes
+/// --- ---
+///
+fn unescape_string_literal(literal: &str) -> String {
+    let inner = literal.strip_prefix('"').unwrap_or(literal);
+    let inner = inner.strip_suffix('"').unwrap_or(inner);
+
+    let mut value = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            if let Some(escaped) = chars.next() {
+                value.push(escaped);
+            }
+        } else {
+            value.push(c);
+        }
+    }
+    value
+}
+
 /// Extract integer value from an Integer node
 ///
 /// Parameters:
@@ -1118,11 +2401,56 @@ fn extract_integer_value(node: &TreeNode) -> Result<i64, CodeGenError> {
         });
     }
 
-    node.node_content
-        .parse()
-        .map_err(|_| CodeGenError::InvalidStructure {
-            message: format!("Cannot parse '{}' as integer", node.node_content),
-        })
+    parse_integer_literal(&node.node_content).ok_or_else(|| CodeGenError::InvalidStructure {
+        message: format!("Cannot parse '{}' as integer", node.node_content),
+    })
+}
+
+/// Build SQL comparing a numeric column with a number or a range
+///
+/// Structure: children[0] = Binop, children[1] = Integer
+///            or children[0] = IntegerRange (children = lower and upper Integer)
+///
+/// A range written high to low ("between 4 and 3") is read low to high, since SQL's
+/// BETWEEN would match nothing
+///
+/// Parameters:
+/// --- ---
+/// column -> The column to compare
+/// node -> The numeric field query node
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// CodeGenResult -> The generated SQL fragment (e.g., "c.credit_hours BETWEEN 3 AND 4") or
+///                  an error
+/// --- ---
+///
+fn build_numeric_condition(column: &str, node: &TreeNode) -> CodeGenResult {
+    match node.children.as_slice() {
+        [range] if range.node_type == NodeType::IntegerRange && range.children.len() == 2 => {
+            let first = extract_integer_value(&range.children[0])?;
+            let second = extract_integer_value(&range.children[1])?;
+            Ok(format!(
+                "{} BETWEEN {} AND {}",
+                column,
+                first.min(second),
+                first.max(second)
+            ))
+        }
+        [operator, value] => Ok(format!(
+            "{} {} {}",
+            column,
+            extract_binop(operator)?,
+            extract_integer_value(value)?
+        )),
+        _ => Err(CodeGenError::InvalidStructure {
+            message: format!(
+                "{} must have operator and value, or a range",
+                node.node_type
+            ),
+        }),
+    }
 }
 
 /// Extract time value from a Time node
@@ -1145,61 +2473,31 @@ fn extract_time_value(node: &TreeNode) -> CodeGenResult {
     }
 
     let time_str = &node.node_content;
-    Ok(normalize_time(time_str))
+    normalize_time(time_str).ok_or_else(|| CodeGenError::InvalidStructure {
+        message: format!("'{}' is not a valid time", time_str),
+    })
 }
 
-/// Normalize time string to HH:MM:SS format
-///
-/// Handles various time formats including am/pm notation and converts to 24-hour format.
+/// Check whether a condition negates its value
 ///
 /// Parameters:
 /// --- ---
-/// time -> The time string to normalize
+/// condition -> The condition type (e.g., "is not", "does not equal", "!=")
 /// --- ---
 ///
 /// Returns:
 /// --- ---
-/// String -> The normalized time string in HH:MM:SS format
+/// bool -> true for "is not", "does not ...", and not-equals conditions
 /// --- ---
 ///
-fn normalize_time(time: &str) -> String {
-    let time_lower = time.to_lowercase();
-    let is_pm = time_lower.contains("pm");
-    let is_am = time_lower.contains("am");
-
-    // remove am/pm suffix
-    let clean = time_lower
-        .replace("am", "")
-        .replace("pm", "")
-        .trim()
-        .to_string();
-
-    // parse hours and minutes
-    let parts: Vec<&str> = clean.split(':').collect();
-    let hours: i32 = parts
-        .first()
-        .and_then(|s| s.trim().parse().ok())
-        .unwrap_or(0);
-    let minutes: i32 = parts
-        .get(1)
-        .and_then(|s| s.trim().parse().ok())
-        .unwrap_or(0);
-
-    // convert to 24-hour format
-    let hours_24 = if is_pm && hours != 12 {
-        hours + 12
-    } else if is_am && hours == 12 {
-        0
-    } else {
-        hours
-    };
-
-    format!("{:02}:{:02}:00", hours_24, minutes)
+fn is_negated_condition(condition: &str) -> bool {
+    let upper = condition.to_uppercase();
+    upper.contains("NOT") || upper.contains("N'T") || upper.contains("DOESNT") || upper == "!="
 }
 
 /// Build a SQL string condition based on the condition type
 ///
-/// Supports various string conditions: equals, contains, starts with, ends with, etc.
+/// Supports various string conditions: equals, contains, starts with, ends with, matches, etc.
 ///
 /// Parameters:
 /// --- ---
@@ -1210,48 +2508,236 @@ fn normalize_time(time: &str) -> String {
 ///
 /// Returns:
 /// --- ---
-/// String -> The generated SQL condition string
+/// Result<String, CodeGenError> -> The generated SQL condition, or an error if the value
+///                                 can't be written into SQL
 /// --- ---
 ///
-fn build_string_condition(column: &str, condition: &str, value: &str) -> String {
-    let escaped_value = value.replace('\'', "''");
+fn build_string_condition(
+    column: &str,
+    condition: &str,
+    value: &str,
+) -> Result<String, CodeGenError> {
+    let literal = escape::string_literal(value)?;
     let upper = condition.to_uppercase();
 
-    match upper.as_str() {
+    // LIKE patterns are quoted with their wildcards, so only the arms using one build it
+    let like = |leading: bool, trailing: bool| -> Result<String, CodeGenError> {
+        let (pattern, escape_clause) = escape::like_literal(value, leading, trailing)?;
+        Ok(format!("{} COLLATE NOCASE{}", pattern, escape_clause))
+    };
+
+    Ok(match upper.as_str() {
+        // GLOB runs inside SQLite, REGEXP calls back into the function from data::pool
+        s if s.contains("MATCHES") => match escape::glob_literal(value)? {
+            Some(glob) => format!("{} GLOB {}", column, glob),
+            None => format!("{} REGEXP {}", column, literal),
+        },
         s if s == "IS NOT" || s.contains("IS NOT") => {
-            format!("LOWER({}) != LOWER('{}')", column, escaped_value)
+            format!("LOWER({}) != LOWER({})", column, literal)
         }
         s if s.contains("DOES NOT CONTAIN")
             || s.contains("DOESN'T CONTAIN")
             || s.contains("DOESNT CONTAIN") =>
         {
-            format!("{} NOT LIKE '%{}%' COLLATE NOCASE", column, escaped_value)
+            format!("{} NOT LIKE {}", column, like(true, true)?)
         }
         s if s.contains("DOES NOT EQUAL")
             || s.contains("DOESN'T EQUAL")
             || s.contains("DOESNT EQUAL") =>
         {
-            format!("LOWER({}) != LOWER('{}')", column, escaped_value)
+            format!("LOWER({}) != LOWER({})", column, literal)
         }
         s if s.contains("NOTEQUALS")
             || (s.contains("NOT") && !s.contains("IS NOT") && !s.contains("DOES NOT")) =>
         {
-            format!("LOWER({}) != LOWER('{}')", column, escaped_value)
+            format!("LOWER({}) != LOWER({})", column, literal)
         }
         s if s.contains("EQUALS") || s.contains("IS") || s.contains("EQUAL") => {
-            format!("LOWER({}) = LOWER('{}')", column, escaped_value)
+            format!("LOWER({}) = LOWER({})", column, literal)
         }
         s if s.contains("CONTAINS") || s.contains("HAS") => {
-            format!("{} LIKE '%{}%' COLLATE NOCASE", column, escaped_value)
+            format!("{} LIKE {}", column, like(true, true)?)
         }
         s if s.contains("STARTS") => {
-            format!("{} LIKE '{}%' COLLATE NOCASE", column, escaped_value)
+            format!("{} LIKE {}", column, like(false, true)?)
         }
         s if s.contains("ENDS") => {
-            format!("{} LIKE '%{}' COLLATE NOCASE", column, escaped_value)
+            format!("{} LIKE {}", column, like(true, false)?)
         }
         _ => {
-            format!("LOWER({}) = LOWER('{}')", column, escaped_value)
+            format!("LOWER({}) = LOWER({})", column, literal)
         }
+    })
+}
+
+/// Build a SQL condition comparing a column with a value node
+///
+/// A single value goes through build_string_condition. A list (from "in") matches a column
+/// equal to any of its values, ignoring case like "is" does
+///
+/// Parameters:
+/// --- ---
+/// column -> The SQL column name
+/// condition -> The condition type (e.g., "contains", "T_IN")
+/// node -> The value node: an Identifier, String, or EmailIdentifier, or a StringList
+/// normalize -> Maps each value to the one searched (e.g., "web" to "Online" for methods)
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<String, CodeGenError> -> The generated SQL condition, or an error if a value
+///                                 can't be written into SQL
+/// --- ---
+///
+fn build_value_condition(
+    column: &str,
+    condition: &str,
+    node: &TreeNode,
+    normalize: fn(&str) -> String,
+) -> Result<String, CodeGenError> {
+    if node.node_type != NodeType::StringList {
+        let value = normalize(&extract_string_value(node)?);
+        return build_string_condition(column, condition, &value);
+    }
+
+    let literals = node
+        .children
+        .iter()
+        .map(|child| {
+            let value = normalize(&extract_string_value(child)?);
+            Ok(format!("LOWER({})", escape::string_literal(&value)?))
+        })
+        .collect::<Result<Vec<String>, CodeGenError>>()?;
+    Ok(format!("LOWER({}) IN ({})", column, literals.join(", ")))
+}
+
+/// Get the value a string field query requires its column to equal
+///
+/// Lookup validation uses this to check exact values (e.g., "subject is CMTP") against the
+/// downloaded data, so it follows the same condition rules as build_string_condition
+///
+/// Parameters:
+/// --- ---
+/// node -> A string field query node (condition and value children)
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Option<String> -> The unquoted value, None for other conditions (contains, is not, ...)
+/// --- ---
+///
+pub fn equality_value(node: &TreeNode) -> Option<String> {
+    if node.children.len() != 2 {
+        return None;
+    }
+    let condition = extract_condition(&node.children[0]).ok()?;
+    let value = extract_string_value(&node.children[1]).ok()?;
+    build_string_condition("v", &condition, &value)
+        .ok()?
+        .starts_with("LOWER(v) = ")
+        .then_some(value)
+}
+
+/// Get every value a string field query requires its column to equal
+///
+/// Like equality_value, but a query comparing with a list ("subject in (CS, MATH)") gives
+/// each of the list's values
+///
+/// Parameters:
+/// --- ---
+/// node -> A string field query node (condition and value children)
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Vec<(String, &TreeNode)> -> Each unquoted value with the node it was written in, empty
+///                             for other conditions (contains, is not, ...)
+/// --- ---
+///
+pub fn equality_values(node: &TreeNode) -> Vec<(String, &TreeNode)> {
+    match node.children.get(1) {
+        Some(list) if list.node_type == NodeType::StringList => list
+            .children
+            .iter()
+            .filter_map(|value_node| Some((extract_string_value(value_node).ok()?, value_node)))
+            .collect(),
+        Some(value_node) => equality_value(node)
+            .map(|value| vec![(value, value_node)])
+            .unwrap_or_default(),
+        None => Vec::new(),
+    }
+}
+
+/// Get the SQL a string field query compares its column with
+///
+/// Cost estimates use this to tell comparisons an index can answer from ones that read
+/// every row (e.g., "description contains data"), so it follows the same condition rules as
+/// build_string_condition
+///
+/// Parameters:
+/// --- ---
+/// node -> A string field query node (condition and value children)
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Option<String> -> The condition on a column named "v" (e.g., "v LIKE '%data%' COLLATE
+///                   NOCASE"), None if the node isn't a condition and a value
+/// --- ---
+///
+pub fn string_condition_sql(node: &TreeNode) -> Option<String> {
+    if node.children.len() != 2 {
+        return None;
+    }
+    let condition = extract_condition(&node.children[0]).ok()?;
+    build_value_condition("v", &condition, &node.children[1], str::to_string).ok()
+}
+
+/// Get the pattern a "matches" query compares its column with
+///
+/// Semantic analysis uses this to refuse patterns that don't compile before the query runs
+///
+/// Parameters:
+/// --- ---
+/// node -> A string field query node (condition and value children)
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Option<String> -> The unquoted pattern, None for other conditions
+/// --- ---
+///
+pub fn pattern_value(node: &TreeNode) -> Option<String> {
+    if node.children.len() != 2 {
+        return None;
+    }
+    let condition = extract_condition(&node.children[0]).ok()?;
+    if !condition.to_uppercase().contains("MATCHES") {
+        return None;
+    }
+    extract_string_value(&node.children[1]).ok()
+}
+
+/// Look up the SQL columns an entity searches
+///
+/// Parameters:
+/// --- ---
+/// name -> Canonical name of the entity in the registry (e.g., "professor", "start")
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<&'static [&'static str], CodeGenError> -> The entity's columns or an error
+/// --- ---
+///
+fn entity_columns(name: &str) -> Result<&'static [&'static str], CodeGenError> {
+    let columns = entities::entity_named(name)
+        .map(|info| info.columns)
+        .ok_or_else(|| CodeGenError::InvalidStructure {
+            message: format!("Unknown entity: {}", name),
+        })?;
+    for column in columns {
+        escape::column(column)?;
     }
+    Ok(columns)
 }
@@ -1,4 +1,9 @@
+use crate::data::archive::fetch_archived_term_ids;
+use crate::data::campus_aliases::CampusAliases;
+use crate::data::lookups::{load_lookups, Lookups};
 use crate::data::sql::{execute_query, get_default_db_path, Class};
+use crate::data::term_index::{load_term_index, TermIndex};
+use crate::data::warm_cache::WarmCache;
 /// src/dsl/compiler.rs
 ///
 /// Compiler for the DSL
@@ -8,32 +13,123 @@ use crate::data::sql::{execute_query, get_default_db_path, Class};
 /// Contains:
 /// --- ---
 /// CompilerResult -> Result types for the compiler
+///      Methods:
+///      --- ---
+///      diagnostic -> Describe a failed result the way the command line prints it
+///      --- ---
+/// TraceEvent -> What a compiler stage did, reported to a trace callback
+/// QueryTiming -> How long a query took to compile and to run
+///      Methods:
+///      --- ---
+///      total -> Get the time spent on the whole query
+///      --- ---
 /// Compiler -> Compiler struct
 ///      Methods:
 ///      --- ---
 ///      new -> Create a new compiler instance
 ///      run -> Compile the DSL into a SQL query
+///      run_traced -> Compile the DSL into a SQL query, reporting each stage to a callback
+///      parse -> Lex and parse a query into its AST, without checking or running it
+///      generate -> Check a parsed query and generate its SQL, without running it
+///      last_timing -> Get the timing of the last successful query
+///      db_path -> Get the database queries run against
+///      warm_up_lookups -> Load the lookup lists and term index in the background
+///      term_index -> Get the active term's index, once loaded
+///      cost_warnings -> Warn about what would make a query slow to run
+///      value_completions -> Get known values to offer after a lookup field's condition
 ///      get_tab_completion -> Get tab completion suggestions for the current input
+///      get_completion_items -> Get tab completion suggestions with their kind and description
+///      --- ---
+/// Helper functions:
+///      --- ---
+///      count_nodes -> Count the nodes of an AST subtree
 ///      --- ---
 /// --- ---
 ///
 use crate::dsl::{
-    codegen::generate_sql_with_filters,
+    codegen::{
+        generate_scope_sql, generate_sql_with_filters, limit_clause, ResultOrder, DEFAULT_MAX_ROWS,
+    },
+    cost::{estimate_cost, CostScope},
+    entities::{self, Completion, CompletionKind},
     lexer::Lexer,
-    parser::{Ast, Parser},
-    semantic::semantic_analysis,
+    parser::{Ast, Parser, TreeNode},
+    semantic::{resolve_campus_aliases, semantic_analysis, validate_lookup_values},
+    token::{Token, TokenType},
 };
+use crate::tui::error_catalog::{self, ErrorCode};
 use crate::tui::errors::AppError;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// Maximum number of known values offered as completions
+const VALUE_COMPLETION_LIMIT: usize = 10;
+
+/// What a compiler stage did, reported to a trace callback
+///
+/// Events:
+/// --- ---
+/// Lexed -> The lexer produced this many tokens
+/// RuleEntered -> The parser entered a grammar rule at the token with this index
+/// Noticed -> The parser assumed something the query left out (see Parser::notices)
+/// Parsed -> The parser built an AST with this many nodes
+/// Checked -> Semantic analysis passed
+/// Generated -> Code generation produced SQL this many characters long
+/// Executed -> The database returned this many rows
+/// Failed -> A stage refused the query ("lexer", "parser", "semantic", "codegen", or
+///           "database")
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for TraceEvent
+/// Clone -> Clone trait for TraceEvent
+/// PartialEq -> PartialEq trait for TraceEvent
+/// Display -> Display trait for TraceEvent
+/// --- ---
+///
+#[derive(Debug, Clone, PartialEq)]
+pub enum TraceEvent {
+    Lexed { tokens: usize },
+    RuleEntered { rule: &'static str, token: usize },
+    Noticed { notice: String },
+    Parsed { nodes: usize },
+    Checked,
+    Generated { chars: usize },
+    Executed { rows: usize },
+    Failed { stage: &'static str },
+}
+
+impl std::fmt::Display for TraceEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TraceEvent::Lexed { tokens } => write!(f, "lexed {} tokens", tokens),
+            TraceEvent::RuleEntered { rule, token } => {
+                write!(f, "parse entered rule {} at token {}", rule, token)
+            }
+            TraceEvent::Noticed { notice } => write!(f, "note: {}", notice),
+            TraceEvent::Parsed { nodes } => write!(f, "parsed {} AST nodes", nodes),
+            TraceEvent::Checked => write!(f, "semantic analysis passed"),
+            TraceEvent::Generated { chars } => write!(f, "codegen produced {} chars", chars),
+            TraceEvent::Executed { rows } => write!(f, "database returned {} rows", rows),
+            TraceEvent::Failed { stage } => write!(f, "{} failed", stage),
+        }
+    }
+}
 
 /// Result Types for the Compiler
 ///
 /// Results:
 /// --- ---
-/// Sucess -> Compilation was successful, contains message, generated SQL, positions and AST
-/// LexerError -> Lexical analysis failed, contains message and problematic positions
-/// ParserError -> Parsing failed, contains message and problematic positions
-/// SemanticError -> Semantic analysis failed, contains message and problematic positions
-/// CodeGenError -> Code generation failed, contains message
+/// Sucess -> Compilation was successful, contains message, generated SQL, positions, AST,
+///           notices of what the parser assumed (non-fatal, e.g., a condition left out), and
+///           whether the result cap cut off more results
+/// LexerError -> Lexical analysis failed, contains error code, message and problematic positions
+/// ParserError -> Parsing failed, contains error code, message and problematic positions
+/// SemanticError -> Semantic analysis failed, contains error code, message and problematic positions
+/// CodeGenError -> Code generation or the database query failed, contains error code and message
 /// --- ---
 ///
 /// Implemented Traits:
@@ -49,24 +145,106 @@ pub enum CompilerResult {
         sql: String,
         classes: Vec<Class>,
         ast: Ast,
+        notices: Vec<String>,
+        truncated: bool,
     },
     LexerError {
+        code: ErrorCode,
         message: String,
         problematic_positions: Vec<(usize, usize)>,
     },
     ParserError {
+        code: ErrorCode,
         message: String,
         problematic_positions: Vec<(usize, usize)>,
     },
     SemanticError {
+        code: ErrorCode,
         message: String,
         problematic_positions: Vec<(usize, usize)>,
     },
     CodeGenError {
+        code: ErrorCode,
         message: String,
     },
 }
 
+impl CompilerResult {
+    /// Describe a failed result the way the command line prints it
+    ///
+    /// Parameters:
+    /// --- ---
+    /// input -> The query that was compiled, for the error's column
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Option<String> -> The error, e.g. "error[E005] at column 3: ...", None for Success
+    /// --- ---
+    ///
+    pub fn diagnostic(&self, input: &str) -> Option<String> {
+        let (code, message, positions) = match self {
+            CompilerResult::Success { .. } => return None,
+            CompilerResult::LexerError {
+                code,
+                message,
+                problematic_positions,
+            }
+            | CompilerResult::ParserError {
+                code,
+                message,
+                problematic_positions,
+            }
+            | CompilerResult::SemanticError {
+                code,
+                message,
+                problematic_positions,
+            } => (*code, message, problematic_positions.as_slice()),
+            CompilerResult::CodeGenError { code, message } => (*code, message, &[][..]),
+        };
+        Some(format!(
+            "{}: {}",
+            error_catalog::diagnostic_header(code, input, positions),
+            message
+        ))
+    }
+}
+
+/// How long a query took to compile and to run
+///
+/// Fields:
+/// --- ---
+/// compile -> Time spent lexing, parsing, checking, and generating SQL
+/// execute -> Time spent running the SQL against the database
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for QueryTiming
+/// Clone -> Clone trait for QueryTiming
+/// Copy -> Copy trait for QueryTiming
+/// PartialEq -> PartialEq trait for QueryTiming
+/// --- ---
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QueryTiming {
+    pub compile: Duration,
+    pub execute: Duration,
+}
+
+impl QueryTiming {
+    /// Get the time spent on the whole query
+    ///
+    /// Returns:
+    /// --- ---
+    /// Duration -> Compile time plus execution time
+    /// --- ---
+    ///
+    pub fn total(&self) -> Duration {
+        self.compile + self.execute
+    }
+}
+
 /// Compiler for the DSL
 ///
 /// Responsible for compiling the DSL into a SQL query
@@ -75,6 +253,12 @@ pub enum CompilerResult {
 /// --- ---
 /// school_id -> Optional school ID to filter results
 /// term_id -> Optional term ID to filter results
+/// result_order -> The order search results are returned in
+/// max_rows -> The most results a search returns, None for no cap
+/// last_timing -> Timing of the last query that ran, None if it failed
+/// lookups -> Subject, campus, method, and professor lists for completion and validation
+/// term_index -> Index of the active term's sections for the result filter
+/// campus_aliases -> Short names for campuses, defined in Settings
 /// --- ---
 ///
 /// Implemented Traits:
@@ -85,6 +269,12 @@ pub enum CompilerResult {
 pub struct Compiler {
     school_id: Option<String>,
     term_id: Option<String>,
+    result_order: ResultOrder,
+    max_rows: Option<usize>,
+    last_timing: Option<QueryTiming>,
+    lookups: WarmCache<Lookups>,
+    term_index: WarmCache<TermIndex>,
+    campus_aliases: CampusAliases,
 }
 
 /// Compiler Implementation
@@ -93,7 +283,18 @@ pub struct Compiler {
 /// --- ---
 /// new -> Create a new compiler instance
 /// run -> Compile the DSL into a SQL query
+/// run_traced -> Compile the DSL into a SQL query, reporting each stage to a callback
+/// parse -> Lex and parse a query into its AST, without checking or running it
+/// generate -> Check a parsed query and generate its SQL, without running it
+/// preview_sql -> Generate the SQL for the live preview of a query, without running it
+/// last_timing -> Get the timing of the last successful query
+/// db_path -> Get the database queries run against
+/// warm_up_lookups -> Load the lookup lists and term index in the background
+/// term_index -> Get the active term's index, once loaded
+/// cost_warnings -> Warn about what would make a query slow to run
 /// get_tab_completion -> Get tab completion suggestions for the current input
+/// get_completion_items -> Get tab completion suggestions with their kind and description
+/// value_completions -> Get known values to offer after a lookup field's condition
 /// --- ---
 ///
 impl Compiler {
@@ -115,6 +316,12 @@ impl Compiler {
         Compiler {
             school_id: None,
             term_id: None,
+            result_order: ResultOrder::Database,
+            max_rows: Some(DEFAULT_MAX_ROWS),
+            last_timing: None,
+            lookups: WarmCache::new(),
+            term_index: WarmCache::new(),
+            campus_aliases: CampusAliases::default(),
         }
     }
 
@@ -140,6 +347,39 @@ impl Compiler {
         self.term_id = term_id;
     }
 
+    /// Set the order search results are returned in
+    ///
+    /// Parameters:
+    /// --- ---
+    /// order -> The ordering chosen in Settings
+    /// --- ---
+    ///
+    pub fn set_result_order(&mut self, order: ResultOrder) {
+        self.result_order = order;
+    }
+
+    /// Set the most results a search returns
+    ///
+    /// Parameters:
+    /// --- ---
+    /// max_rows -> The cap chosen in Settings, None for no cap
+    /// --- ---
+    ///
+    pub fn set_max_rows(&mut self, max_rows: Option<usize>) {
+        self.max_rows = max_rows;
+    }
+
+    /// Set the campus aliases resolved in queries
+    ///
+    /// Parameters:
+    /// --- ---
+    /// aliases -> The aliases defined in Settings
+    /// --- ---
+    ///
+    pub fn set_campus_aliases(&mut self, aliases: CampusAliases) {
+        self.campus_aliases = aliases;
+    }
+
     /// Compile the DSL into a SQL query
     ///
     /// Parameters:
@@ -156,55 +396,252 @@ impl Compiler {
     /// --- ---
     ///
     pub fn run(&mut self, input: &str) -> CompilerResult {
-        // refresh lexer state
-        let mut lexer = Lexer::new(input.to_string());
+        self.run_traced(input, &mut |_| {})
+    }
 
-        // perform lexical analysis
-        let tokens = match lexer.analyze() {
-            Ok(tokens) => tokens,
-            Err(AppError::UnrecognizedTokens(error_msg, problematic_positions)) => {
-                return CompilerResult::LexerError {
-                    message: error_msg,
-                    problematic_positions,
-                };
-            }
-            Err(_) => {
-                return CompilerResult::LexerError {
-                    message: "Unknown lexer error".to_string(),
-                    problematic_positions: Vec::new(),
-                };
+    /// Compile the DSL into a SQL query, reporting each stage to a callback
+    ///
+    /// Parameters:
+    /// --- ---
+    /// input -> The input string to compile
+    /// trace -> Called with each stage's events as it finishes, in order
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// CompilerResult -> The same result run returns
+    /// --- ---
+    ///
+    pub fn run_traced(
+        &mut self,
+        input: &str,
+        trace: &mut dyn FnMut(&TraceEvent),
+    ) -> CompilerResult {
+        self.last_timing = None;
+        let compile_start = Instant::now();
+
+        // the parser's notices travel with the result, so callers can show them
+        let mut notices = Vec::new();
+        let mut collect = |event: &TraceEvent| {
+            if let TraceEvent::Noticed { notice } = event {
+                notices.push(notice.clone());
             }
+            trace(event);
+        };
+        let ast = match self.parse(input, &mut collect) {
+            Ok(ast) => ast,
+            Err(error) => return *error,
+        };
+        let sql = match self.generate(&ast, trace) {
+            Ok(sql) => sql,
+            Err(error) => return *error,
         };
 
-        // perform parsing
-        let mut parser = Parser::new(input.to_string());
+        let compile = compile_start.elapsed();
 
-        // try to parse the tokens
-        let ast = match parser.parse(&tokens) {
-            Ok(ast) => ast,
-            Err(error_tuple) => {
-                let (e, problematic_tokens) = error_tuple;
-                let problematic_positions: Vec<(usize, usize)> = problematic_tokens
-                    .iter()
-                    .map(|token| (token.get_start(), token.get_end()))
-                    .collect();
-                return CompilerResult::ParserError {
-                    message: e.to_string(),
-                    problematic_positions,
+        // execute the SQL query against the database
+        let db_path = self.db_path();
+        let execute_start = Instant::now();
+        let mut classes = match execute_query(&sql, &db_path) {
+            Ok(classes) => classes,
+            Err(e) => {
+                trace(&TraceEvent::Failed { stage: "database" });
+                return CompilerResult::CodeGenError {
+                    code: ErrorCode::DatabaseQuery,
+                    message: error_catalog::message("database_query", &[("error", &e.to_string())]),
                 };
             }
         };
 
+        self.last_timing = Some(QueryTiming {
+            compile,
+            execute: execute_start.elapsed(),
+        });
+
+        // the SQL fetches one row past the cap, which is only there to say results were cut off
+        let truncated = self
+            .max_rows
+            .is_some_and(|max_rows| classes.len() > max_rows);
+        if let Some(max_rows) = self.max_rows {
+            classes.truncate(max_rows);
+        }
+        trace(&TraceEvent::Executed {
+            rows: classes.len(),
+        });
+
+        // return success if all operations were successful
+        CompilerResult::Success {
+            message: "Success".to_string(),
+            sql,
+            classes,
+            ast,
+            notices,
+            truncated,
+        }
+    }
+
+    /// Lex and parse a query into its AST, without checking or running it
+    ///
+    /// Parameters:
+    /// --- ---
+    /// input -> The input string to parse
+    /// trace -> Called with the lexer's and parser's events
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Result<Ast, Box<CompilerResult>> -> The AST, or the LexerError or ParserError result
+    /// --- ---
+    ///
+    pub fn parse(
+        &self,
+        input: &str,
+        trace: &mut dyn FnMut(&TraceEvent),
+    ) -> Result<Ast, Box<CompilerResult>> {
+        // refresh lexer state
+        let mut lexer = Lexer::new(input.to_string());
+
+        // perform lexical analysis
+        let tokens = lexer.analyze().map_err(|error| {
+            trace(&TraceEvent::Failed { stage: "lexer" });
+            let code = error.code().unwrap_or(ErrorCode::UnrecognizedCharacter);
+            let (message, problematic_positions) = match error {
+                AppError::UnrecognizedTokens(message, positions)
+                | AppError::UnclosedString(message, positions) => (message, positions),
+                _ => ("Unknown lexer error".to_string(), Vec::new()),
+            };
+            Box::new(CompilerResult::LexerError {
+                code,
+                message,
+                problematic_positions,
+            })
+        })?;
+        trace(&TraceEvent::Lexed {
+            tokens: tokens.len(),
+        });
+
+        // perform parsing, recording the rules entered so they can be reported either way
+        let mut parser = Parser::new(input.to_string());
+        parser.set_tracing(true);
+        let parsed = parser.parse(&tokens);
+        for &(rule, token) in parser.rules_entered() {
+            trace(&TraceEvent::RuleEntered { rule, token });
+        }
+        let ast = parsed.map_err(|(e, problematic_tokens)| {
+            trace(&TraceEvent::Failed { stage: "parser" });
+            let problematic_positions: Vec<(usize, usize)> = problematic_tokens
+                .iter()
+                .map(|token| (token.get_start(), token.get_end()))
+                .collect();
+            Box::new(CompilerResult::ParserError {
+                code: e.code(),
+                message: e.to_string(),
+                problematic_positions,
+            })
+        })?;
+        for notice in parser.notices() {
+            trace(&TraceEvent::Noticed {
+                notice: notice.clone(),
+            });
+        }
+        trace(&TraceEvent::Parsed {
+            nodes: ast.head.as_ref().map_or(0, count_nodes),
+        });
+        Ok(ast)
+    }
+
+    /// Check a parsed query and generate its SQL, without running it
+    ///
+    /// Parameters:
+    /// --- ---
+    /// ast -> The query's AST, from parse
+    /// trace -> Called with the semantic check's and code generator's events
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Result<String, Box<CompilerResult>> -> The SQL, or the SemanticError or CodeGenError
+    ///                                        result
+    /// --- ---
+    ///
+    pub fn generate(
+        &self,
+        ast: &Ast,
+        trace: &mut dyn FnMut(&TraceEvent),
+    ) -> Result<String, Box<CompilerResult>> {
+        self.generate_with_limit(ast, &limit_clause(self.max_rows), trace)
+    }
+
+    /// Generate the SQL for the live preview of a query, without running it
+    ///
+    /// The preview only shows its first results, so the SQL fetches exactly that many
+    /// instead of the cap in Settings
+    ///
+    /// Parameters:
+    /// --- ---
+    /// input -> The input string to compile
+    /// limit -> The most results the preview shows
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Option<String> -> The SQL, None if the query doesn't compile
+    /// --- ---
+    ///
+    pub fn preview_sql(&self, input: &str, limit: usize) -> Option<String> {
+        let ast = self.parse(input, &mut |_| {}).ok()?;
+        self.generate_with_limit(&ast, &format!(" LIMIT {}", limit), &mut |_| {})
+            .ok()
+    }
+
+    /// Check a parsed query and generate its SQL, ending with the given LIMIT clause
+    ///
+    /// Parameters:
+    /// --- ---
+    /// ast -> The query's AST, from parse
+    /// limit -> The LIMIT clause appended to the SQL, empty for no cap
+    /// trace -> Called with the semantic check's and code generator's events
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Result<String, Box<CompilerResult>> -> The SQL, or the SemanticError or CodeGenError
+    ///                                        result
+    /// --- ---
+    ///
+    fn generate_with_limit(
+        &self,
+        ast: &Ast,
+        limit: &str,
+        trace: &mut dyn FnMut(&TraceEvent),
+    ) -> Result<String, Box<CompilerResult>> {
         // perform semantic analysis
-        match semantic_analysis(&ast) {
-            Ok(()) => {}
-            Err((e, problematic_positions)) => {
-                return CompilerResult::SemanticError {
+        semantic_analysis(ast).map_err(|(e, problematic_positions)| {
+            trace(&TraceEvent::Failed { stage: "semantic" });
+            Box::new(CompilerResult::SemanticError {
+                code: e.code(),
+                message: e.to_string(),
+                problematic_positions,
+            })
+        })?;
+
+        // campus aliases are replaced before values are checked and searched
+        let mut resolved = ast.clone();
+        resolve_campus_aliases(&mut resolved, &self.campus_aliases);
+        let ast = &resolved;
+
+        // once the lookup lists are loaded, exact values must be ones some class has
+        if let Some(lookups) = self.lookups.get() {
+            validate_lookup_values(ast, &lookups).map_err(|(e, problematic_positions)| {
+                trace(&TraceEvent::Failed { stage: "semantic" });
+                Box::new(CompilerResult::SemanticError {
+                    code: e.code(),
                     message: e.to_string(),
                     problematic_positions,
-                };
-            }
+                })
+            })?;
         }
+        trace(&TraceEvent::Checked);
 
         // check if using test database (special "_test" school ID)
         let use_test_db = self.school_id.as_deref() == Some("_test");
@@ -217,37 +654,142 @@ impl Compiler {
             (self.school_id.as_deref(), self.term_id.as_deref())
         };
 
-        let sql = match generate_sql_with_filters(&ast, school_filter, term_filter) {
-            Ok(sql) => sql,
-            Err(e) => {
-                return CompilerResult::CodeGenError {
-                    message: e.to_string(),
-                };
-            }
+        // leave archived terms out of queries that aren't scoped to a single term
+        let excluded_terms = if use_test_db || term_filter.is_some() {
+            Vec::new()
+        } else {
+            fetch_archived_term_ids(&get_default_db_path(), school_filter).unwrap_or_default()
         };
 
-        // execute the SQL query against the database
-        let db_path = if use_test_db {
-            std::path::PathBuf::from("classy/test.db")
+        let sql = generate_sql_with_filters(
+            ast,
+            school_filter,
+            term_filter,
+            &excluded_terms,
+            self.result_order,
+            None,
+        )
+        .map_err(|e| {
+            trace(&TraceEvent::Failed { stage: "codegen" });
+            Box::new(CompilerResult::CodeGenError {
+                code: ErrorCode::CodeGeneration,
+                message: e.to_string(),
+            })
+        })? + limit;
+        trace(&TraceEvent::Generated { chars: sql.len() });
+        Ok(sql)
+    }
+
+    /// Get the timing of the last successful query
+    ///
+    /// Returns:
+    /// --- ---
+    /// Option<&QueryTiming> -> How long the last run took, None if it failed or none has run
+    /// --- ---
+    ///
+    pub fn last_timing(&self) -> Option<&QueryTiming> {
+        self.last_timing.as_ref()
+    }
+
+    /// Get the database queries run against
+    ///
+    /// Returns:
+    /// --- ---
+    /// PathBuf -> classy/test.db for the "_test" school, the default database otherwise
+    /// --- ---
+    ///
+    pub fn db_path(&self) -> PathBuf {
+        if self.school_id.as_deref() == Some("_test") {
+            PathBuf::from("classy/test.db")
         } else {
             get_default_db_path()
+        }
+    }
+
+    /// Load the lookup lists and term index in the background
+    ///
+    /// Called when the TUI starts, when a school or term is picked, and after a sync. Until
+    /// the first load finishes, completion offers only keywords, values aren't validated,
+    /// and the result filter indexes the results themselves
+    ///
+    /// Returns:
+    /// --- ---
+    /// Vec<JoinHandle<()>> -> The loading threads, for callers that need to wait for them
+    /// --- ---
+    ///
+    pub fn warm_up_lookups(&self) -> Vec<JoinHandle<()>> {
+        let db_path = self.db_path();
+        // the test database holds a single school and isn't filtered, like in run
+        let (school_filter, term_filter) = if self.school_id.as_deref() == Some("_test") {
+            (None, None)
+        } else {
+            (self.school_id.clone(), self.term_id.clone())
         };
-        let classes = match execute_query(&sql, &db_path) {
-            Ok(classes) => classes,
-            Err(e) => {
-                return CompilerResult::CodeGenError {
-                    message: format!("Database query error: {}", e),
-                };
-            }
+
+        let lookups = {
+            let db_path = db_path.clone();
+            let school_filter = school_filter.clone();
+            self.lookups
+                .warm_up(move || load_lookups(&db_path, school_filter.as_deref()))
         };
+        let term_index = self.term_index.warm_up(move || {
+            // opening a missing database to look for archived terms would create it
+            let excluded_terms = if term_filter.is_some() || !db_path.exists() {
+                Vec::new()
+            } else {
+                fetch_archived_term_ids(&db_path, school_filter.as_deref()).unwrap_or_default()
+            };
+            let sql = generate_scope_sql(
+                school_filter.as_deref(),
+                term_filter.as_deref(),
+                &excluded_terms,
+            )
+            .map_err(|e| e.to_string())?;
+            load_term_index(&db_path, &sql)
+        });
 
-        // return success if all operations were successful
-        CompilerResult::Success {
-            message: "Success".to_string(),
-            sql,
-            classes,
-            ast,
-        }
+        vec![lookups, term_index]
+    }
+
+    /// Get the active term's index, once loaded
+    ///
+    /// Returns:
+    /// --- ---
+    /// Option<Arc<TermIndex>> -> The index, None until the first warm-up finishes
+    /// --- ---
+    ///
+    pub fn term_index(&self) -> Option<Arc<TermIndex>> {
+        self.term_index.get()
+    }
+
+    /// Warn about what would make a query slow to run
+    ///
+    /// The scope is sized from the term index, so nothing is warned about until it loads.
+    /// Queries that don't parse aren't warned about either, running them reports the error
+    ///
+    /// Parameters:
+    /// --- ---
+    /// input -> The query to check
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Vec<String> -> The warnings from cost::estimate_cost, empty if it should be quick
+    /// --- ---
+    ///
+    pub fn cost_warnings(&self, input: &str) -> Vec<String> {
+        let Some(index) = self.term_index.get() else {
+            return Vec::new();
+        };
+        let Ok(ast) = self.parse(input, &mut |_| {}) else {
+            return Vec::new();
+        };
+        // the test database isn't filtered by term, but holds only one
+        let scope = CostScope {
+            sections: index.len(),
+            all_terms: self.term_id.is_none() && self.school_id.as_deref() != Some("_test"),
+        };
+        estimate_cost(&ast, &scope)
     }
 
     /// Get tab completion suggestions for the current input
@@ -268,6 +810,29 @@ impl Compiler {
     /// --- ---
     ///
     pub fn get_tab_completion(&mut self, input: String) -> Vec<String> {
+        self.get_completion_items(input)
+            .into_iter()
+            .map(|completion| completion.text)
+            .collect()
+    }
+
+    /// Get tab completion suggestions with their kind and description
+    ///
+    /// Known values are described by the field they belong to, everything else by the entity
+    /// registry (see entities::describe_completion). Snippets follow wherever a condition
+    /// can start
+    ///
+    /// Parameters:
+    /// --- ---
+    /// input -> The input string to get completion suggestions for
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Vec<Completion> -> Completion suggestions, known values first and snippets last
+    /// --- ---
+    ///
+    pub fn get_completion_items(&mut self, input: String) -> Vec<Completion> {
         // refresh lexer state
         let mut lexer = Lexer::new(input.to_string());
         let mut parser = Parser::new(input.to_string());
@@ -275,22 +840,165 @@ impl Compiler {
         // try to analyze the input
         match lexer.analyze() {
             Ok(tokens) => {
+                // known values come first, then what the parser expects next
+                let mut suggestions = self.value_completions(&input, &tokens);
                 // lexical analysis succeeded, now try to get completion suggestions from parser
-                parser.get_completion_suggestions(&tokens)
+                let parsed = parser.get_completion_suggestions(&tokens);
+                suggestions.extend(
+                    parsed
+                        .iter()
+                        .map(|suggestion| entities::describe_completion(suggestion)),
+                );
+                // whole patterns fit wherever a condition can start, but "not" only negates
+                // the first condition of one, so they aren't offered after it
+                let condition_starts = entities::query_start_suggestions()
+                    .iter()
+                    .all(|start| parsed.contains(start));
+                let negated = tokens
+                    .last()
+                    .is_some_and(|token| *token.get_token_type() == TokenType::Not);
+                if condition_starts && !negated {
+                    suggestions.extend(entities::snippet_completions());
+                }
+                suggestions
             }
             Err(_) => {
                 // lexical analysis failed, provide basic suggestions
                 if input.trim().is_empty() {
-                    vec![
-                        "professor".to_string(),
-                        "course".to_string(),
-                        "subject".to_string(),
-                        "title".to_string(),
-                    ]
+                    let mut suggestions: Vec<Completion> = entities::query_start_suggestions()
+                        .iter()
+                        .map(|suggestion| entities::describe_completion(suggestion))
+                        .collect();
+                    suggestions.extend(entities::snippet_completions());
+                    suggestions
                 } else {
                     vec![] // can't provide suggestions for invalid tokens
                 }
             }
         }
     }
+
+    /// Get known values to offer after a lookup field's condition
+    ///
+    /// Applies to input like "subject is " or "campus contains Mar", where the field has a
+    /// lookup list; values that need quotes are offered quoted
+    ///
+    /// Parameters:
+    /// --- ---
+    /// input -> The input being completed
+    /// tokens -> The input's tokens
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Vec<Completion> -> Up to VALUE_COMPLETION_LIMIT values starting with the partial value
+    /// --- ---
+    ///
+    fn value_completions(&self, input: &str, tokens: &[Token]) -> Vec<Completion> {
+        let Some(lookups) = self.lookups.get() else {
+            return Vec::new();
+        };
+        let lexeme = |token: &Token| &input[token.get_start()..token.get_end()];
+
+        // an unfinished value is completed, a finished one (followed by a space) is not
+        let mut end = tokens.len();
+        let mut partial = "";
+        if let Some(last) = tokens.last() {
+            if !input.ends_with(' ')
+                && matches!(
+                    last.get_token_type(),
+                    TokenType::Identifier | TokenType::Alphanumeric
+                )
+            {
+                partial = lexeme(last);
+                end -= 1;
+            }
+        }
+
+        // values in a list follow its "in" (e.g., "subject in (CS, MA")
+        if matches!(
+            tokens[..end].last().map(Token::get_token_type),
+            Some(TokenType::LeftParen | TokenType::Comma)
+        ) {
+            let open = tokens[..end]
+                .iter()
+                .rposition(|token| *token.get_token_type() == TokenType::LeftParen);
+            if let Some(open) =
+                open.filter(|&open| open > 0 && *tokens[open - 1].get_token_type() == TokenType::In)
+            {
+                end = open;
+            }
+        }
+
+        // the value must follow a condition (e.g., "is", "does not equal", "starts with")
+        let conditions = tokens[..end]
+            .iter()
+            .rev()
+            .take_while(|token| {
+                matches!(
+                    token.get_token_type(),
+                    TokenType::Is
+                        | TokenType::Not
+                        | TokenType::Equals
+                        | TokenType::NotEquals
+                        | TokenType::EqualsWord
+                        | TokenType::Equal
+                        | TokenType::Does
+                        | TokenType::DoesNotEqual
+                        | TokenType::DoesNotContain
+                        | TokenType::Contains
+                        | TokenType::Has
+                        | TokenType::Starts
+                        | TokenType::Ends
+                        | TokenType::With
+                        | TokenType::In
+                )
+            })
+            .count();
+        if conditions == 0 || conditions == end {
+            return Vec::new();
+        }
+
+        let field = &tokens[end - conditions - 1];
+        let entity = if *field.get_token_type() == TokenType::Field {
+            entities::describe_keyword(lexeme(field))
+        } else {
+            entities::entity_for_token(*field.get_token_type())
+        };
+        let Some(entity) = entity else {
+            return Vec::new();
+        };
+
+        lookups
+            .starting_with(&entity.node, partial)
+            .into_iter()
+            .filter(|value| !value.eq_ignore_ascii_case(partial))
+            .take(VALUE_COMPLETION_LIMIT)
+            .map(|value| Completion {
+                text: if value.chars().all(|c| c.is_ascii_alphanumeric()) {
+                    value.to_string()
+                } else {
+                    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+                },
+                kind: CompletionKind::Value,
+                description: format!("Known {}", entity.name),
+            })
+            .collect()
+    }
+}
+
+/// Count the nodes of an AST subtree
+///
+/// Parameters:
+/// --- ---
+/// node -> The subtree's root
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// usize -> The root and all of its descendants
+/// --- ---
+///
+fn count_nodes(node: &TreeNode) -> usize {
+    1 + node.children.iter().map(count_nodes).sum::<usize>()
 }
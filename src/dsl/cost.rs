@@ -0,0 +1,162 @@
+/// src/dsl/cost.rs
+///
+/// Query cost estimates for the DSL
+///
+/// Responsible for spotting searches that read far more rows than they return before they
+/// run: matching text anywhere inside a long column (descriptions, requisites) no index can
+/// answer, and a search over every term with nothing to narrow it. Both only matter once the
+/// scope holds enough sections for a scan to take noticeable time, so small terms never warn
+///
+/// Contains:
+/// --- ---
+/// LARGE_SCOPE_SECTIONS -> Sections in scope from which slow searches are warned about
+/// LONG_TEXT_NODES -> Entities whose columns hold long text
+/// NARROWING_NODES -> Entities whose conditions narrow a search to a few courses
+/// CostScope -> The sections a search runs over
+/// Helper functions:
+///      --- ---
+///      estimate_cost -> Warn about the parts of a query that make it slow
+///      long_text_scans -> Find long-text comparisons that read every row
+///      narrows -> Check whether a query only matches a few courses
+///      --- ---
+/// --- ---
+///
+use crate::dsl::codegen::string_condition_sql;
+use crate::dsl::entities;
+use crate::dsl::parser::{Ast, NodeType, TreeNode};
+use crate::dsl::token::TokenType;
+
+/// Sections in scope from which slow searches are warned about
+///
+/// A single term of a large school holds a few thousand sections, which scan quickly
+pub const LARGE_SCOPE_SECTIONS: usize = 20_000;
+
+/// Entities whose columns hold long text, read in full by a match anywhere inside them
+const LONG_TEXT_NODES: &[NodeType] = &[
+    NodeType::DescriptionQuery,
+    NodeType::PrereqsQuery,
+    NodeType::CoreqsQuery,
+];
+
+/// Entities whose conditions narrow a search to a few courses
+const NARROWING_NODES: &[NodeType] = &[
+    NodeType::SubjectQuery,
+    NodeType::CourseQuery,
+    NodeType::NumberQuery,
+];
+
+/// The sections a search runs over
+///
+/// Fields:
+/// --- ---
+/// sections -> How many sections are in scope
+/// all_terms -> Whether the search isn't limited to one term
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for CostScope
+/// Clone -> Clone trait for CostScope
+/// PartialEq -> PartialEq trait for CostScope
+/// --- ---
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct CostScope {
+    pub sections: usize,
+    pub all_terms: bool,
+}
+
+/// Warn about the parts of a query that make it slow
+///
+/// Parameters:
+/// --- ---
+/// ast -> The parsed query
+/// scope -> The sections it runs over
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Vec<String> -> One warning per cause, empty if the search should be quick
+/// --- ---
+///
+pub fn estimate_cost(ast: &Ast, scope: &CostScope) -> Vec<String> {
+    let Some(head) = &ast.head else {
+        return Vec::new();
+    };
+    if scope.sections < LARGE_SCOPE_SECTIONS {
+        return Vec::new();
+    }
+
+    let mut warnings = Vec::new();
+    let mut scanned = Vec::new();
+    long_text_scans(head, &mut scanned);
+    for name in scanned {
+        warnings.push(format!(
+            "Matching inside {} text reads it for all {} sections",
+            name, scope.sections
+        ));
+    }
+    if scope.all_terms && !narrows(head) {
+        warnings.push(format!(
+            "Searches every term ({} sections) with no subject or course to narrow it",
+            scope.sections
+        ));
+    }
+    warnings
+}
+
+/// Find long-text comparisons that read every row
+///
+/// Matches with a leading wildcard (contains, ends with, ...) and regular expression patterns
+/// can't use an index; exact and "starts with" comparisons can
+///
+/// Parameters:
+/// --- ---
+/// node -> The subtree to search
+/// scanned -> Names of the entities found so far, each listed once
+/// --- ---
+///
+/// Returns: None
+///
+fn long_text_scans(node: &TreeNode, scanned: &mut Vec<&'static str>) {
+    if LONG_TEXT_NODES.contains(&node.node_type) {
+        let unanchored = string_condition_sql(node).is_some_and(|sql| {
+            sql.contains("LIKE '%") || sql.contains("GLOB '*") || sql.contains(" REGEXP ")
+        });
+        if let Some(info) = entities::entity_for_node(&node.node_type) {
+            if unanchored && !scanned.contains(&info.name) {
+                scanned.push(info.name);
+            }
+        }
+        return;
+    }
+    for child in &node.children {
+        long_text_scans(child, scanned);
+    }
+}
+
+/// Check whether a query only matches a few courses
+///
+/// A query is narrowed by a subject, course, or number condition that must hold for every
+/// result: one side of an "and" is enough, every side of an "or" needs one, and a condition
+/// under "not" or a negated condition ("is not") narrows nothing
+///
+/// Parameters:
+/// --- ---
+/// node -> The subtree to check
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// bool -> true if every match has to meet a narrowing condition
+/// --- ---
+///
+fn narrows(node: &TreeNode) -> bool {
+    match &node.node_type {
+        NodeType::T(TokenType::Or) => node.children.iter().all(narrows),
+        NodeType::T(TokenType::Not) => false,
+        node_type if NARROWING_NODES.contains(node_type) => string_condition_sql(node)
+            .is_some_and(|sql| !sql.contains("!=") && !sql.contains("NOT LIKE")),
+        _ => node.children.iter().any(narrows),
+    }
+}
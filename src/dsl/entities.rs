@@ -0,0 +1,1230 @@
+/// src/dsl/entities.rs
+///
+/// Registry of the DSL's queryable entities
+///
+/// Responsible for describing each entity once: the keywords that introduce it, the type of
+/// value it compares against, the SQL columns it searches, and how to use it. The lexer,
+/// parser suggestions, code generator, query guide, and search hints all read from this table,
+/// so adding a field starts with a single entry here
+///
+/// Contains:
+/// --- ---
+/// ValueType -> Type of value an entity is compared against
+/// KeywordToken -> A keyword token and the spellings that lex to it
+/// EntityInfo -> Metadata for a single queryable entity
+/// define_field! -> Declare a simple search field as a registry entry
+/// ENTITIES -> Table of all queryable entities
+/// SYNONYMS -> Whole-word synonyms for entity keywords
+/// CompletionKind -> What kind of word a completion is
+/// Completion -> A completion with its kind and what it does
+/// COMPLETION_DOCS -> What each condition, operator, and connective completion does
+/// PLACEHOLDER -> What a snippet has in place of each value left to fill in
+/// SNIPPETS -> Whole-query patterns offered as completions
+/// Helper functions:
+///      --- ---
+///      describe_keyword -> Look up the entity introduced by a keyword
+///      entity_for_token -> Look up the entity a keyword token belongs to
+///      entity_named -> Look up an entity by its canonical name
+///      entity_for_node -> Look up the first entity parsed into an AST node type
+///      keyword_patterns -> Build the lexer patterns for every entity keyword
+///      compound_keyword_patterns -> Build the lexer patterns for joined multi-word keywords
+///      synonym_completions -> Synonyms matching a partially typed word
+///      describe_completion -> Describe a completion from the registry
+///      snippet_completions -> The snippets as completions
+///      example_queries -> One example query per entity, to suggest in an empty search box
+///      query_start_suggestions -> Keywords suggested at the start of a query
+///      course_field_suggestions -> Keywords suggested after "course"
+///      --- ---
+/// --- ---
+///
+use crate::dsl::parser::NodeType;
+use crate::dsl::token::TokenType;
+
+/// Type of value an entity is compared against
+///
+/// Variants:
+/// --- ---
+/// Text -> Compared with string conditions (contains, is, starts with, ...)
+/// Integer -> Compared with numeric operators (=, <, at least, ...)
+/// Time -> Compared with numeric operators or a range, times need am/pm
+/// Boolean -> Compared with string conditions against true/false
+/// Day -> A day of the week, true when used on its own
+/// Date -> Compared with after, before, or on a date
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for ValueType
+/// Clone -> Clone trait for ValueType
+/// Copy -> Copy trait for ValueType
+/// PartialEq -> PartialEq trait for ValueType
+/// --- ---
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ValueType {
+    Text,
+    Integer,
+    Time,
+    Boolean,
+    Day,
+    Date,
+}
+
+impl ValueType {
+    /// Get a short label for the value type
+    ///
+    /// Returns:
+    /// --- ---
+    /// &'static str -> Label shown in the query guide
+    /// --- ---
+    ///
+    pub fn label(&self) -> &'static str {
+        match self {
+            ValueType::Text => "text",
+            ValueType::Integer => "number",
+            ValueType::Time => "time",
+            ValueType::Boolean => "true/false",
+            ValueType::Day => "day",
+            ValueType::Date => "date",
+        }
+    }
+
+    /// Get the condition assumed when a query leaves it out
+    ///
+    /// Returns:
+    /// --- ---
+    /// Option<(TokenType, &'static str)> -> The condition's token and how it is written
+    ///                                      ("contains" for text, "=" for numbers), None if
+    ///                                      the type needs one written
+    /// --- ---
+    ///
+    pub fn default_condition(&self) -> Option<(TokenType, &'static str)> {
+        match self {
+            ValueType::Text => Some((TokenType::Contains, "contains")),
+            ValueType::Integer => Some((TokenType::Equals, "=")),
+            _ => None,
+        }
+    }
+}
+
+/// A keyword token and the spellings that lex to it
+///
+/// Fields:
+/// --- ---
+/// token -> The token produced by the lexer
+/// spellings -> Accepted spellings, canonical first (matched case-insensitively as whole words)
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for KeywordToken
+/// --- ---
+///
+#[derive(Debug)]
+pub struct KeywordToken {
+    pub token: TokenType,
+    pub spellings: &'static [&'static str],
+}
+
+impl KeywordToken {
+    /// Get the spellings plus any synonyms that lex to this keyword
+    ///
+    /// Returns:
+    /// --- ---
+    /// Vec<&'static str> -> Spellings followed by synonyms from SYNONYMS
+    /// --- ---
+    ///
+    pub fn accepted_spellings(&self) -> Vec<&'static str> {
+        let mut accepted = self.spellings.to_vec();
+        accepted.extend(
+            SYNONYMS
+                .iter()
+                .filter(|(_, canonical)| self.spellings.contains(canonical))
+                .map(|(synonym, _)| *synonym),
+        );
+        accepted
+    }
+}
+
+/// Metadata for a single queryable entity
+///
+/// Fields:
+/// --- ---
+/// name -> Canonical name of the entity
+/// tokens -> Keyword tokens that introduce the entity, in the order they are written
+/// node -> AST node type the parser builds for the entity
+/// value_type -> Type of value the entity is compared against
+/// columns -> SQL columns the entity searches
+/// range -> Lowest and highest number a numeric entity can hold, None if unbounded
+/// starts_query -> Whether the entity is suggested at the start of a query
+/// course_field -> Whether the entity can follow "course"
+/// description -> One-line description of what the entity searches
+/// examples -> Example queries using the entity
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for EntityInfo
+/// --- ---
+///
+#[derive(Debug)]
+pub struct EntityInfo {
+    pub name: &'static str,
+    pub tokens: &'static [KeywordToken],
+    pub node: NodeType,
+    pub value_type: ValueType,
+    pub columns: &'static [&'static str],
+    pub range: Option<(i64, i64)>,
+    pub starts_query: bool,
+    pub course_field: bool,
+    pub description: &'static str,
+    pub examples: &'static [&'static str],
+}
+
+impl EntityInfo {
+    /// Get the word suggested for the entity in completions
+    ///
+    /// Returns:
+    /// --- ---
+    /// &'static str -> Canonical spelling of the entity's first keyword
+    /// --- ---
+    ///
+    pub fn suggestion(&self) -> &'static str {
+        self.tokens[0].spellings[0]
+    }
+
+    /// Get every spelling of every keyword that introduces the entity
+    ///
+    /// Returns:
+    /// --- ---
+    /// Vec<&'static str> -> All accepted spellings and synonyms, in registry order
+    /// --- ---
+    ///
+    pub fn spellings(&self) -> Vec<&'static str> {
+        self.tokens
+            .iter()
+            .flat_map(|keyword| keyword.accepted_spellings())
+            .collect()
+    }
+
+    /// Get the keyword that must follow a token of this entity, if any
+    ///
+    /// Parameters:
+    /// --- ---
+    /// token -> A token belonging to the entity
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Option<&'static str> -> Canonical spelling of the next keyword (e.g., "hours" after "credit")
+    /// --- ---
+    ///
+    pub fn next_keyword(&self, token: TokenType) -> Option<&'static str> {
+        let position = self.tokens.iter().position(|k| k.token == token)?;
+        self.tokens.get(position + 1).map(|next| next.spellings[0])
+    }
+}
+
+/// Declare a simple search field
+///
+/// Expands to an EntityInfo for a field compared with `<condition> <string>` (type `Str`) or
+/// `<binop> <integer>` (type `Int`) against a single column. An `Int` field may give a
+/// `range: (min, max)` so absurd numbers are refused before searching. The lexer, parser,
+/// semantic analysis, code generator, completions, and guide all pick the field up from
+/// ENTITIES, so declaring one there is the only edit needed
+///
+/// Example:
+/// --- ---
+/// define_field! {
+///     name: "section",
+///     type: Str,
+///     column: "s.sequence",
+///     synonyms: ["sec"],
+///     description: "Section number",
+///     examples: ["section is \"001\""],
+/// }
+/// --- ---
+///
+#[macro_export]
+macro_rules! define_field {
+    (@value_type Str) => {
+        $crate::dsl::entities::ValueType::Text
+    };
+    (@value_type Int) => {
+        $crate::dsl::entities::ValueType::Integer
+    };
+    (@range) => {
+        None
+    };
+    (@range $min:literal $max:literal) => {
+        Some(($min, $max))
+    };
+    {
+        name: $name:literal,
+        type: $value_type:ident,
+        column: $column:literal,
+        $(range: ($min:literal, $max:literal),)?
+        synonyms: [$($synonym:literal),* $(,)?],
+        description: $description:literal,
+        examples: [$($example:literal),* $(,)?] $(,)?
+    } => {
+        $crate::dsl::entities::EntityInfo {
+            name: $name,
+            tokens: &[$crate::dsl::entities::KeywordToken {
+                token: $crate::dsl::token::TokenType::Field,
+                spellings: &[$name, $($synonym),*],
+            }],
+            node: $crate::dsl::parser::NodeType::FieldQuery,
+            value_type: $crate::define_field!(@value_type $value_type),
+            columns: &[$column],
+            range: $crate::define_field!(@range $($min $max)?),
+            starts_query: false,
+            course_field: false,
+            description: $description,
+            examples: &[$($example),*],
+        }
+    };
+}
+
+/// Table of all queryable entities
+pub const ENTITIES: &[EntityInfo] = &[
+    EntityInfo {
+        name: "professor",
+        tokens: &[KeywordToken {
+            token: TokenType::Prof,
+            spellings: &["professor", "prof", "professors", "profs"],
+        }],
+        node: NodeType::ProfessorQuery,
+        value_type: ValueType::Text,
+        columns: &["p.name", "p.email_address", "p.first_name", "p.last_name"],
+        range: None,
+        starts_query: true,
+        course_field: false,
+        description: "Instructor name or email address",
+        examples: &["prof contains Shanley", "prof is \"Shanley, Brigid\""],
+    },
+    EntityInfo {
+        name: "course",
+        tokens: &[KeywordToken {
+            token: TokenType::Course,
+            spellings: &["course", "courses"],
+        }],
+        node: NodeType::CourseQuery,
+        value_type: ValueType::Text,
+        columns: &["c.title", "c.subject_code"],
+        range: None,
+        starts_query: true,
+        course_field: false,
+        description: "Course number, or prefix for a course field",
+        examples: &["course contains 103L", "course title contains data"],
+    },
+    EntityInfo {
+        name: "subject",
+        tokens: &[KeywordToken {
+            token: TokenType::Subject,
+            spellings: &["subject", "sub", "subjects"],
+        }],
+        node: NodeType::SubjectQuery,
+        value_type: ValueType::Text,
+        columns: &["c.subject_code"],
+        range: None,
+        starts_query: true,
+        course_field: true,
+        description: "Subject code of the course",
+        examples: &["subject equals CMPT", "sub is not MATH"],
+    },
+    EntityInfo {
+        name: "title",
+        tokens: &[KeywordToken {
+            token: TokenType::Title,
+            spellings: &["title"],
+        }],
+        node: NodeType::TitleQuery,
+        value_type: ValueType::Text,
+        columns: &["c.title"],
+        range: None,
+        starts_query: true,
+        course_field: true,
+        description: "Course title",
+        examples: &["title contains TECHNOLOGY", "title starts with Intro"],
+    },
+    EntityInfo {
+        name: "number",
+        tokens: &[KeywordToken {
+            token: TokenType::Number,
+            spellings: &["number"],
+        }],
+        node: NodeType::NumberQuery,
+        value_type: ValueType::Text,
+        columns: &["c.number"],
+        range: None,
+        starts_query: true,
+        course_field: true,
+        description: "Course number within the subject",
+        examples: &["number equals 103L"],
+    },
+    EntityInfo {
+        name: "description",
+        tokens: &[KeywordToken {
+            token: TokenType::Description,
+            spellings: &["description"],
+        }],
+        node: NodeType::DescriptionQuery,
+        value_type: ValueType::Text,
+        columns: &["c.description"],
+        range: None,
+        starts_query: true,
+        course_field: true,
+        description: "Full course description text",
+        examples: &["description contains technology"],
+    },
+    EntityInfo {
+        name: "credit hours",
+        tokens: &[
+            KeywordToken {
+                token: TokenType::Credit,
+                spellings: &["credit", "credits"],
+            },
+            KeywordToken {
+                token: TokenType::Hours,
+                spellings: &["hours", "hour"],
+            },
+        ],
+        node: NodeType::CreditHoursQuery,
+        value_type: ValueType::Integer,
+        columns: &["c.credit_hours"],
+        range: Some((0, 30)),
+        starts_query: true,
+        course_field: true,
+        description: "Credit hours awarded (compare with a number)",
+        examples: &[
+            "credit hours = 3",
+            "credit hours >= 4",
+            "credit hours between 3 and 4",
+        ],
+    },
+    EntityInfo {
+        name: "prerequisites",
+        tokens: &[KeywordToken {
+            token: TokenType::Prereqs,
+            spellings: &["prerequisites", "prereqs", "prerequisite", "prereq"],
+        }],
+        node: NodeType::PrereqsQuery,
+        value_type: ValueType::Text,
+        columns: &["c.prerequisites"],
+        range: None,
+        starts_query: true,
+        course_field: true,
+        description: "Prerequisite requirements text",
+        examples: &["prereqs contains CMPT"],
+    },
+    EntityInfo {
+        name: "corequisites",
+        tokens: &[KeywordToken {
+            token: TokenType::Corereqs,
+            spellings: &[
+                "corequisites",
+                "corereqs",
+                "corequisite",
+                "coreqs",
+                "corereq",
+                "coreq",
+            ],
+        }],
+        node: NodeType::CoreqsQuery,
+        value_type: ValueType::Text,
+        columns: &["c.corequisites"],
+        range: None,
+        starts_query: true,
+        course_field: true,
+        description: "Corequisite requirements text",
+        examples: &["corereqs contains LAB"],
+    },
+    EntityInfo {
+        name: "enrollment",
+        tokens: &[KeywordToken {
+            token: TokenType::Enrollment,
+            spellings: &["enrollment"],
+        }],
+        node: NodeType::EnrollmentQuery,
+        value_type: ValueType::Integer,
+        columns: &["s.enrollment"],
+        range: Some((0, 10_000)),
+        starts_query: true,
+        course_field: false,
+        description: "Number of students enrolled in the section",
+        examples: &[
+            "enrollment > 0",
+            "enrollment < 20",
+            "enrollment between 20 and 50",
+        ],
+    },
+    EntityInfo {
+        name: "size",
+        tokens: &[KeywordToken {
+            token: TokenType::Size,
+            spellings: &["size"],
+        }],
+        node: NodeType::EnrollmentQuery,
+        value_type: ValueType::Integer,
+        columns: &["s.enrollment"],
+        range: Some((0, 10_000)),
+        starts_query: false,
+        course_field: false,
+        description: "Number of students enrolled in the section",
+        examples: &["size < 20"],
+    },
+    EntityInfo {
+        name: "cap",
+        tokens: &[KeywordToken {
+            token: TokenType::Cap,
+            spellings: &["cap"],
+        }],
+        node: NodeType::EnrollmentCapQuery,
+        value_type: ValueType::Integer,
+        columns: &["s.max_enrollment"],
+        range: Some((0, 10_000)),
+        starts_query: false,
+        course_field: false,
+        description: "Maximum enrollment of the section",
+        examples: &["cap > 20", "enrollment cap <= 30", "cap between 20 and 40"],
+    },
+    EntityInfo {
+        name: "full",
+        tokens: &[KeywordToken {
+            token: TokenType::Full,
+            spellings: &["full"],
+        }],
+        node: NodeType::FullQuery,
+        value_type: ValueType::Boolean,
+        columns: &["s.enrollment", "s.max_enrollment"],
+        range: None,
+        starts_query: false,
+        course_field: false,
+        description: "Whether the section has no open seats",
+        examples: &["full equals false"],
+    },
+    EntityInfo {
+        name: "method",
+        tokens: &[KeywordToken {
+            token: TokenType::Method,
+            spellings: &["method"],
+        }],
+        node: NodeType::InstructionMethodQuery,
+        value_type: ValueType::Text,
+        columns: &["s.instruction_method"],
+        range: None,
+        starts_query: false,
+        course_field: false,
+        description: "Instruction method (in person, online, hybrid), whatever the school calls it",
+        examples: &[
+            "method is online",
+            "method is in person",
+            "method is hybrid",
+        ],
+    },
+    EntityInfo {
+        name: "campus",
+        tokens: &[KeywordToken {
+            token: TokenType::Campus,
+            spellings: &["campus"],
+        }],
+        node: NodeType::CampusQuery,
+        value_type: ValueType::Text,
+        columns: &["s.campus"],
+        range: None,
+        starts_query: true,
+        course_field: false,
+        description: "Campus where the section is held",
+        examples: &["campus contains Marist"],
+    },
+    EntityInfo {
+        name: "meeting type",
+        tokens: &[
+            KeywordToken {
+                token: TokenType::Meeting,
+                spellings: &["meeting"],
+            },
+            KeywordToken {
+                token: TokenType::Type,
+                spellings: &["type"],
+            },
+        ],
+        node: NodeType::MeetingTypeQuery,
+        value_type: ValueType::Text,
+        columns: &["mt.meeting_type"],
+        range: None,
+        starts_query: true,
+        course_field: false,
+        description: "Meeting type (lecture, lab, ...)",
+        examples: &["type contains LAB", "meeting type is LEC"],
+    },
+    EntityInfo {
+        name: "start",
+        tokens: &[KeywordToken {
+            token: TokenType::Start,
+            spellings: &["start"],
+        }],
+        node: NodeType::TimeQuery,
+        value_type: ValueType::Time,
+        columns: &["mt.start_minutes"],
+        range: None,
+        starts_query: false,
+        course_field: false,
+        description: "Meeting start time (times need am/pm)",
+        examples: &["start >= 9:00am", "start 9:00am to 12:00pm"],
+    },
+    EntityInfo {
+        name: "end",
+        tokens: &[KeywordToken {
+            token: TokenType::End,
+            spellings: &["end"],
+        }],
+        node: NodeType::TimeQuery,
+        value_type: ValueType::Time,
+        columns: &["mt.end_minutes"],
+        range: None,
+        starts_query: false,
+        course_field: false,
+        description: "Meeting end time (times need am/pm)",
+        examples: &["end <= 3:00pm", "end 12:00pm to 3:00pm"],
+    },
+    EntityInfo {
+        name: "meets",
+        tokens: &[KeywordToken {
+            token: TokenType::Meets,
+            spellings: &["meets", "meet"],
+        }],
+        node: NodeType::DateQuery,
+        value_type: ValueType::Date,
+        columns: &[
+            "mt_filter.start_date",
+            "mt_filter.end_date",
+            "mt_filter.other",
+        ],
+        range: None,
+        starts_query: true,
+        course_field: false,
+        description: "Meeting dates, for sections that run part of the term",
+        examples: &[
+            "meets after oct 15",
+            "meets before 2024-10-01",
+            "meets on nov 4",
+        ],
+    },
+    EntityInfo {
+        name: "session",
+        tokens: &[KeywordToken {
+            token: TokenType::Session,
+            spellings: &["session", "sessions"],
+        }],
+        node: NodeType::SessionQuery,
+        value_type: ValueType::Text,
+        columns: &["s.other", "mt.start_date", "mt.end_date"],
+        range: None,
+        starts_query: true,
+        course_field: false,
+        description: "Part of the term: full, first half, second half, or partial",
+        examples: &["session is first half", "session is not full"],
+    },
+    EntityInfo {
+        name: "restricted",
+        tokens: &[KeywordToken {
+            token: TokenType::Restricted,
+            spellings: &["restricted"],
+        }],
+        node: NodeType::RestrictedQuery,
+        value_type: ValueType::Boolean,
+        columns: &["s.other"],
+        range: None,
+        starts_query: true,
+        course_field: false,
+        description:
+            "Whether the section needs consent or is limited to some majors or class levels",
+        examples: &["restricted is false"],
+    },
+    EntityInfo {
+        name: "fee",
+        tokens: &[KeywordToken {
+            token: TokenType::Fee,
+            spellings: &["fee", "fees"],
+        }],
+        node: NodeType::FeeQuery,
+        value_type: ValueType::Integer,
+        columns: &["s.other"],
+        range: Some((0, 100_000)),
+        starts_query: true,
+        course_field: false,
+        description: "Lab or course fee of the section in dollars, 0 if it has none",
+        examples: &["fee <= 50", "fee = 0"],
+    },
+    define_field! {
+        name: "section",
+        type: Str,
+        column: "s.sequence",
+        synonyms: ["sec"],
+        description: "Section number within the course",
+        examples: ["section is \"001\"", "course contains 103L and sec is \"111\""],
+    },
+    EntityInfo {
+        name: "monday",
+        tokens: &[KeywordToken {
+            token: TokenType::Monday,
+            spellings: &["monday", "monda", "mond", "mon", "mo", "m"],
+        }],
+        node: NodeType::DayQuery,
+        value_type: ValueType::Day,
+        columns: &["mt_filter.is_monday"],
+        range: None,
+        starts_query: false,
+        course_field: false,
+        description: "Meets on Monday (defaults to true)",
+        examples: &["monday", "monday equals false"],
+    },
+    EntityInfo {
+        name: "tuesday",
+        tokens: &[KeywordToken {
+            token: TokenType::Tuesday,
+            spellings: &["tuesday", "tuesda", "tuesd", "tues", "tue", "tu"],
+        }],
+        node: NodeType::DayQuery,
+        value_type: ValueType::Day,
+        columns: &["mt_filter.is_tuesday"],
+        range: None,
+        starts_query: false,
+        course_field: false,
+        description: "Meets on Tuesday (defaults to true)",
+        examples: &["tuesday and thursday"],
+    },
+    EntityInfo {
+        name: "wednesday",
+        tokens: &[KeywordToken {
+            token: TokenType::Wednesday,
+            spellings: &[
+                "wednesday",
+                "wednesda",
+                "wednesd",
+                "wednes",
+                "wedne",
+                "wedn",
+                "wed",
+                "we",
+                "w",
+            ],
+        }],
+        node: NodeType::DayQuery,
+        value_type: ValueType::Day,
+        columns: &["mt_filter.is_wednesday"],
+        range: None,
+        starts_query: false,
+        course_field: false,
+        description: "Meets on Wednesday (defaults to true)",
+        examples: &["wednesday"],
+    },
+    EntityInfo {
+        name: "thursday",
+        tokens: &[KeywordToken {
+            token: TokenType::Thursday,
+            spellings: &["thursday", "thursda", "thurs", "thur", "thu", "th"],
+        }],
+        node: NodeType::DayQuery,
+        value_type: ValueType::Day,
+        columns: &["mt_filter.is_thursday"],
+        range: None,
+        starts_query: false,
+        course_field: false,
+        description: "Meets on Thursday (defaults to true)",
+        examples: &["tuesday and thursday"],
+    },
+    EntityInfo {
+        name: "friday",
+        tokens: &[KeywordToken {
+            token: TokenType::Friday,
+            spellings: &["friday", "frida", "frid", "fri", "fr", "f"],
+        }],
+        node: NodeType::DayQuery,
+        value_type: ValueType::Day,
+        columns: &["mt_filter.is_friday"],
+        range: None,
+        starts_query: false,
+        course_field: false,
+        description: "Meets on Friday (defaults to true)",
+        examples: &["friday equals false"],
+    },
+    EntityInfo {
+        name: "saturday",
+        tokens: &[KeywordToken {
+            token: TokenType::Saturday,
+            spellings: &[
+                "saturday", "saturda", "saturd", "satur", "satu", "sat", "sa",
+            ],
+        }],
+        node: NodeType::DayQuery,
+        value_type: ValueType::Day,
+        columns: &["mt_filter.is_saturday"],
+        range: None,
+        starts_query: false,
+        course_field: false,
+        description: "Meets on Saturday (defaults to true)",
+        examples: &["saturday"],
+    },
+    EntityInfo {
+        name: "sunday",
+        tokens: &[KeywordToken {
+            token: TokenType::Sunday,
+            spellings: &["sunday", "sunda", "sund", "sun", "su"],
+        }],
+        node: NodeType::DayQuery,
+        value_type: ValueType::Day,
+        columns: &["mt_filter.is_sunday"],
+        range: None,
+        starts_query: false,
+        course_field: false,
+        description: "Meets on Sunday (defaults to true)",
+        examples: &["sunday"],
+    },
+];
+
+/// Words users type in place of an entity keyword, mapped to the canonical spelling they lex as
+///
+/// Synonyms are whole words only, abbreviations belong in the entity's spellings
+pub const SYNONYMS: &[(&str, &str)] = &[
+    ("teacher", "professor"),
+    ("instructor", "professor"),
+    ("class", "course"),
+];
+
+/// What kind of word a completion is
+///
+/// Variants:
+/// --- ---
+/// Field -> An entity keyword (professor, start, credit, ...)
+/// Condition -> A text condition (contains, starts with, ...)
+/// Operator -> A numeric or date comparison (<, at least, after, ...)
+/// Logic -> A connective or grouping (and, or, not, ")")
+/// Value -> A value to compare against
+/// Snippet -> A whole pattern with placeholders to fill in
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for CompletionKind
+/// Clone -> Clone trait for CompletionKind
+/// Copy -> Copy trait for CompletionKind
+/// PartialEq -> PartialEq trait for CompletionKind
+/// --- ---
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CompletionKind {
+    Field,
+    Condition,
+    Operator,
+    Logic,
+    Value,
+    Snippet,
+}
+
+impl CompletionKind {
+    /// Get a short label for the kind
+    ///
+    /// Returns:
+    /// --- ---
+    /// &'static str -> Label shown in the completion popup
+    /// --- ---
+    ///
+    pub fn label(&self) -> &'static str {
+        match self {
+            CompletionKind::Field => "field",
+            CompletionKind::Condition => "condition",
+            CompletionKind::Operator => "operator",
+            CompletionKind::Logic => "logic",
+            CompletionKind::Value => "value",
+            CompletionKind::Snippet => "snippet",
+        }
+    }
+}
+
+/// A completion with its kind and what it does
+///
+/// Fields:
+/// --- ---
+/// text -> The text inserted when the completion is accepted
+/// kind -> What kind of word it is
+/// description -> One-line description shown next to it, empty if there is nothing to add
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for Completion
+/// Clone -> Clone trait for Completion
+/// PartialEq -> PartialEq trait for Completion
+/// --- ---
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct Completion {
+    pub text: String,
+    pub kind: CompletionKind,
+    pub description: String,
+}
+
+/// What each condition, operator, and connective completion does
+///
+/// Entity keywords are described by their ENTITIES entry, so only the words between them
+/// are listed here
+pub const COMPLETION_DOCS: &[(CompletionKind, &[(&str, &str)])] = &[
+    (
+        CompletionKind::Condition,
+        &[
+            ("is", "Equal to the value"),
+            ("equals", "Equal to the value"),
+            ("=", "Equal to the value"),
+            ("is not", "Anything but the value"),
+            ("!=", "Anything but the value"),
+            ("not equals", "Anything but the value"),
+            ("does not equal", "Anything but the value"),
+            ("doesn't equal", "Anything but the value"),
+            ("contains", "Has the value anywhere in it"),
+            ("has", "Has the value anywhere in it"),
+            ("does not contain", "Lacks the value"),
+            ("doesn't contain", "Lacks the value"),
+            ("starts with", "Begins with the value"),
+            ("starts", "Begins with the value (then 'with')"),
+            ("ends with", "Finishes with the value"),
+            ("ends", "Finishes with the value (then 'with')"),
+            ("with", "Finishes 'starts with' or 'ends with'"),
+            ("in", "Any of the values in parentheses, e.g., (CS, MATH)"),
+            (
+                "matches",
+                "The whole value fits a regular expression, e.g., \"4[0-9]{2}\"",
+            ),
+        ],
+    ),
+    (
+        CompletionKind::Operator,
+        &[
+            ("<", "Less than the number"),
+            ("less than", "Less than the number"),
+            ("fewer than", "Less than the number"),
+            (">", "Greater than the number"),
+            ("greater than", "Greater than the number"),
+            ("more than", "Greater than the number"),
+            ("<=", "The number or less"),
+            ("at most", "The number or less"),
+            (">=", "The number or more"),
+            ("at least", "The number or more"),
+            ("to", "Up to the second time of a range"),
+            (
+                "between",
+                "From the first number to the second, e.g., between 3 and 4",
+            ),
+            ("after", "Later than the date"),
+            ("before", "Earlier than the date"),
+            ("on", "Meets on the date"),
+        ],
+    ),
+    (
+        CompletionKind::Logic,
+        &[
+            ("and", "Both conditions must hold"),
+            ("or", "Either condition may hold"),
+            ("not", "The condition must not hold"),
+            ("(", "Start a group"),
+            (")", "Close the open group"),
+        ],
+    ),
+    (CompletionKind::Value, &[("true", "Yes"), ("false", "No")]),
+];
+
+/// What a snippet has in place of each value left to fill in
+pub const PLACEHOLDER: &str = "__";
+
+/// Whole-query patterns offered as completions wherever a condition can start
+///
+/// Each PLACEHOLDER is a value to type; the search input moves between them with Tab
+pub const SNIPPETS: &[(&str, &str)] = &[
+    (
+        "subject is __ and number is __",
+        "One course by its subject and number",
+    ),
+    ("professor contains __", "Sections taught by an instructor"),
+    ("start __ to __", "Sections starting within a time range"),
+    (
+        "monday and wednesday and start >= __",
+        "Monday and Wednesday sections from a time on",
+    ),
+    ("credit hours >= __", "Courses worth at least some credits"),
+    (
+        "subject is __ and full is false",
+        "Open sections in a subject",
+    ),
+    (
+        "campus contains __ and method is __",
+        "Sections at a campus, taught a certain way",
+    ),
+];
+
+/// Expand completion suggestions with synonyms matching a partially typed word
+///
+/// Parameters:
+/// --- ---
+/// suggestions -> Completion suggestions from the parser
+/// partial -> The partially typed word (lowercase)
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Vec<String> -> Synonyms of suggested keywords that start with the partial word
+/// --- ---
+///
+pub fn synonym_completions(suggestions: &[String], partial: &str) -> Vec<String> {
+    SYNONYMS
+        .iter()
+        .filter(|(synonym, canonical)| {
+            synonym.starts_with(partial) && suggestions.iter().any(|s| s == canonical)
+        })
+        .map(|(synonym, _)| synonym.to_string())
+        .collect()
+}
+
+/// Look up the entity introduced by a keyword
+///
+/// Parameters:
+/// --- ---
+/// word -> The word to look up (case-insensitive)
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Option<&'static EntityInfo> -> The matching entity metadata, if any
+/// --- ---
+///
+pub fn describe_keyword(word: &str) -> Option<&'static EntityInfo> {
+    let word = word.to_lowercase();
+    ENTITIES
+        .iter()
+        .find(|info| info.spellings().contains(&word.as_str()))
+}
+
+/// Describe a completion from the registry
+///
+/// Entity keywords take their ENTITIES description, the words between them take their
+/// COMPLETION_DOCS entry, and anything else is a value
+///
+/// Parameters:
+/// --- ---
+/// text -> The completion as it would be inserted
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Completion -> The completion with its kind and description
+/// --- ---
+///
+pub fn describe_completion(text: &str) -> Completion {
+    let lower = text.to_lowercase();
+    let documented = COMPLETION_DOCS.iter().find_map(|(kind, words)| {
+        words
+            .iter()
+            .find(|(word, _)| *word == lower)
+            .map(|(_, description)| (*kind, description.to_string()))
+    });
+    let (kind, description) = if let Some(documented) = documented {
+        documented
+    } else if let Some(info) = describe_keyword(&lower) {
+        (CompletionKind::Field, info.description.to_string())
+    } else {
+        (CompletionKind::Value, String::new())
+    };
+    Completion {
+        text: text.to_string(),
+        kind,
+        description,
+    }
+}
+
+/// The snippets as completions
+///
+/// Returns:
+/// --- ---
+/// Vec<Completion> -> One snippet completion per SNIPPETS entry, in order
+/// --- ---
+///
+pub fn snippet_completions() -> Vec<Completion> {
+    SNIPPETS
+        .iter()
+        .map(|(pattern, description)| Completion {
+            text: pattern.to_string(),
+            kind: CompletionKind::Snippet,
+            description: description.to_string(),
+        })
+        .collect()
+}
+
+/// One example query per entity, to suggest in an empty search box
+///
+/// Returns:
+/// --- ---
+/// Vec<&'static str> -> Each entity's first example, in registry order
+/// --- ---
+///
+pub fn example_queries() -> Vec<&'static str> {
+    ENTITIES
+        .iter()
+        .filter_map(|info| info.examples.first().copied())
+        .collect()
+}
+
+/// Look up the entity a keyword token belongs to
+///
+/// Parameters:
+/// --- ---
+/// token -> The keyword token
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Option<&'static EntityInfo> -> The entity the token introduces or continues, if any
+/// --- ---
+///
+pub fn entity_for_token(token: TokenType) -> Option<&'static EntityInfo> {
+    ENTITIES
+        .iter()
+        .find(|info| info.tokens.iter().any(|keyword| keyword.token == token))
+}
+
+/// Look up an entity by its canonical name
+///
+/// Parameters:
+/// --- ---
+/// name -> The canonical name (case-insensitive, e.g., "start" or "monday")
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Option<&'static EntityInfo> -> The entity, if any
+/// --- ---
+///
+pub fn entity_named(name: &str) -> Option<&'static EntityInfo> {
+    ENTITIES
+        .iter()
+        .find(|info| info.name.eq_ignore_ascii_case(name))
+}
+
+/// Look up the first entity parsed into an AST node type
+///
+/// Parameters:
+/// --- ---
+/// node -> The AST node type
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Option<&'static EntityInfo> -> The entity, if any
+/// --- ---
+///
+pub fn entity_for_node(node: &NodeType) -> Option<&'static EntityInfo> {
+    ENTITIES.iter().find(|info| &info.node == node)
+}
+
+/// Build the lexer patterns for every entity keyword
+///
+/// Returns:
+/// --- ---
+/// Vec<(TokenType, String)> -> One case-insensitive whole-word pattern per keyword token
+/// --- ---
+///
+pub fn keyword_patterns() -> Vec<(TokenType, String)> {
+    ENTITIES
+        .iter()
+        .flat_map(|info| info.tokens.iter())
+        .map(|keyword| {
+            (
+                keyword.token,
+                format!(r"(?i)\b(?:{})\b", spelling_alternation(keyword)),
+            )
+        })
+        .collect()
+}
+
+/// Build the lexer patterns for multi-word keywords written as one word
+///
+/// Matches forms like "credithours", "credit-hours", and "meeting_type", with one capture
+/// group per keyword token so the lexer can still emit each token separately
+///
+/// Returns:
+/// --- ---
+/// Vec<(Vec<TokenType>, String)> -> The tokens in order and the pattern matching them joined
+/// --- ---
+///
+pub fn compound_keyword_patterns() -> Vec<(Vec<TokenType>, String)> {
+    ENTITIES
+        .iter()
+        .filter(|info| info.tokens.len() > 1)
+        .map(|info| {
+            let groups = info
+                .tokens
+                .iter()
+                .map(|keyword| format!("({})", spelling_alternation(keyword)))
+                .collect::<Vec<_>>()
+                .join("[-_]?");
+            (
+                info.tokens.iter().map(|keyword| keyword.token).collect(),
+                format!(r"(?i)\b{}\b", groups),
+            )
+        })
+        .collect()
+}
+
+/// Join a keyword's accepted spellings into a regex alternation
+///
+/// Longer spellings come first so "credits" is not cut short by "credit"
+///
+/// Parameters:
+/// --- ---
+/// keyword -> The keyword token
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> Escaped spellings joined with "|"
+/// --- ---
+///
+fn spelling_alternation(keyword: &KeywordToken) -> String {
+    let mut spellings = keyword.accepted_spellings();
+    spellings.sort_by_key(|spelling| std::cmp::Reverse(spelling.len()));
+    spellings
+        .iter()
+        .map(|spelling| regex::escape(spelling))
+        .collect::<Vec<_>>()
+        .join("|")
+}
+
+/// Keywords suggested at the start of a query
+///
+/// Returns:
+/// --- ---
+/// Vec<String> -> Suggestions in registry order
+/// --- ---
+///
+pub fn query_start_suggestions() -> Vec<String> {
+    ENTITIES
+        .iter()
+        .filter(|info| info.starts_query)
+        .map(|info| info.suggestion().to_string())
+        .collect()
+}
+
+/// Keywords suggested after "course"
+///
+/// Returns:
+/// --- ---
+/// Vec<String> -> Suggestions in registry order
+/// --- ---
+///
+pub fn course_field_suggestions() -> Vec<String> {
+    ENTITIES
+        .iter()
+        .filter(|info| info.course_field)
+        .map(|info| info.suggestion().to_string())
+        .collect()
+}
@@ -0,0 +1,363 @@
+/// src/dsl/escape.rs
+///
+/// Escaping layer for values written into generated SQL
+///
+/// Responsible for turning every value the code generator writes into SQL text into a
+/// literal that can only ever be read as that value. Queries are built as strings, so
+/// nothing a user types may end a literal early, add a statement, or cut the SQL short:
+/// each value is validated and quoted here, and codegen never quotes a value itself
+///
+/// Contains:
+/// --- ---
+/// string_literal -> Quote a value as a SQL string literal
+/// like_literal -> Quote a value as a LIKE pattern that matches it literally
+/// glob_literal -> Quote a "matches" pattern as a GLOB pattern, when GLOB can express it
+/// time_literal -> Quote a normalized HH:MM:SS time
+/// date_literal -> Quote a YYYY-MM-DD date
+/// column -> Check a column reference before it is written into SQL
+/// Helper functions:
+///      --- ---
+///      glob_pattern -> Translate a regular expression into a GLOB pattern
+///      glob_char -> Write a character so GLOB matches it literally
+///      check_value -> Refuse values SQLite can't hold in a literal
+///      quote -> Wrap a value in single quotes, doubling the quotes inside it
+///      --- ---
+/// --- ---
+///
+use crate::dsl::codegen::CodeGenError;
+use crate::utils::text::parse_pattern;
+
+/// Escape clause for LIKE patterns with escaped wildcards, backslash is the escape character
+const LIKE_ESCAPE: &str = " ESCAPE '\\'";
+
+/// Longest "{n}" repeat written out in a GLOB pattern, longer ones are left to REGEXP
+const GLOB_MAX_REPEAT: usize = 16;
+
+/// Quote a value as a SQL string literal
+///
+/// Parameters:
+/// --- ---
+/// value -> The raw value (e.g., "Women's Literature")
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<String, CodeGenError> -> The literal (e.g., 'Women''s Literature'), or an error
+///                                 if the value has a control character
+/// --- ---
+///
+pub fn string_literal(value: &str) -> Result<String, CodeGenError> {
+    check_value(value)?;
+    Ok(quote(value))
+}
+
+/// Quote a value as a LIKE pattern that matches it literally
+///
+/// The value's %, _, and \ are escaped, so only the wildcards asked for match anything
+///
+/// Parameters:
+/// --- ---
+/// value -> The raw value (e.g., "100%")
+/// leading -> Whether the pattern starts with a % wildcard
+/// trailing -> Whether the pattern ends with a % wildcard
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<(String, &'static str), CodeGenError> -> The pattern literal (e.g., '%100\%%') and
+///                                                 the ESCAPE clause to put after it, empty
+///                                                 when the value had nothing to escape
+/// --- ---
+///
+pub fn like_literal(
+    value: &str,
+    leading: bool,
+    trailing: bool,
+) -> Result<(String, &'static str), CodeGenError> {
+    check_value(value)?;
+    let mut pattern = String::with_capacity(value.len() + 2);
+    let mut escaped = false;
+    if leading {
+        pattern.push('%');
+    }
+    for c in value.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            pattern.push('\\');
+            escaped = true;
+        }
+        pattern.push(c);
+    }
+    if trailing {
+        pattern.push('%');
+    }
+    // only spell out the escape character when the value needed one
+    let escape = if escaped { LIKE_ESCAPE } else { "" };
+    Ok((quote(&pattern), escape))
+}
+
+/// Quote a "matches" pattern as a GLOB pattern, when GLOB can express it
+///
+/// GLOB is built into SQLite and always matches the whole value, so simple patterns like
+/// "4[0-9]{2}" or "1.." run without calling back into the REGEXP function. GLOB compares
+/// case-sensitively, so only patterns without letters are translated
+///
+/// Parameters:
+/// --- ---
+/// pattern -> The regular expression as typed (e.g., "4[0-9]{2}")
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<Option<String>, CodeGenError> -> The GLOB literal (e.g., '4[0-9][0-9]'), None if
+///                                         the pattern needs REGEXP, or an error if it has a
+///                                         control character
+/// --- ---
+///
+pub fn glob_literal(pattern: &str) -> Result<Option<String>, CodeGenError> {
+    check_value(pattern)?;
+    // only translate what REGEXP would accept, so both read a pattern the same way
+    if parse_pattern(pattern).is_err() {
+        return Ok(None);
+    }
+    Ok(glob_pattern(pattern).map(|glob| quote(&glob)))
+}
+
+/// Quote a normalized HH:MM:SS time
+///
+/// Parameters:
+/// --- ---
+/// time -> The time from utils::time::normalize_time (e.g., "09:30:00")
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<String, CodeGenError> -> The literal (e.g., '09:30:00'), or an error if the time
+///                                 isn't in HH:MM:SS form
+/// --- ---
+///
+pub fn time_literal(time: &str) -> Result<String, CodeGenError> {
+    let bytes = time.as_bytes();
+    let well_formed = bytes.len() == 8
+        && bytes.iter().enumerate().all(|(i, b)| match i {
+            2 | 5 => *b == b':',
+            _ => b.is_ascii_digit(),
+        });
+    if !well_formed {
+        return Err(CodeGenError::InvalidStructure {
+            message: format!("Malformed time: {}", time),
+        });
+    }
+    Ok(quote(time))
+}
+
+/// Quote a YYYY-MM-DD date
+///
+/// Parameters:
+/// --- ---
+/// date -> The date from Date's Display (e.g., "2024-10-15")
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<String, CodeGenError> -> The literal (e.g., '2024-10-15'), or an error if the date
+///                                 isn't in YYYY-MM-DD form
+/// --- ---
+///
+pub fn date_literal(date: &str) -> Result<String, CodeGenError> {
+    let bytes = date.as_bytes();
+    let well_formed = bytes.len() == 10
+        && bytes.iter().enumerate().all(|(i, b)| match i {
+            4 | 7 => *b == b'-',
+            _ => b.is_ascii_digit(),
+        });
+    if !well_formed {
+        return Err(CodeGenError::InvalidStructure {
+            message: format!("Malformed date: {}", date),
+        });
+    }
+    Ok(quote(date))
+}
+
+/// Check a column reference before it is written into SQL
+///
+/// Columns come from the entity registry, so this guards against a define_field! entry
+/// that names something other than a plain column (e.g., "c.title" or "s.enrollment")
+///
+/// Parameters:
+/// --- ---
+/// name -> The column, optionally qualified with its table alias
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<&'static str, CodeGenError> -> The column, or an error if it isn't a plain name
+/// --- ---
+///
+pub fn column(name: &'static str) -> Result<&'static str, CodeGenError> {
+    let is_identifier = |part: &str| {
+        part.chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    };
+    let parts: Vec<&str> = name.split('.').collect();
+    if parts.len() > 2 || !parts.iter().all(|part| is_identifier(part)) {
+        return Err(CodeGenError::InvalidStructure {
+            message: format!("Not a column: {}", name),
+        });
+    }
+    Ok(name)
+}
+
+/// Translate a regular expression into a GLOB pattern
+///
+/// Handles literal digits and punctuation, "\d", escaped metacharacters, ".", ".*", ".+",
+/// bracket classes, and "{n}" repeats, with optional "^" and "$" anchors at the ends
+///
+/// Parameters:
+/// --- ---
+/// pattern -> The regular expression
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Option<String> -> The GLOB pattern, None if the pattern has letters or anything else
+///                   GLOB can't express
+/// --- ---
+///
+fn glob_pattern(pattern: &str) -> Option<String> {
+    // the pattern matches the whole value anyway, so anchors at the ends change nothing
+    let body = pattern.strip_prefix('^').unwrap_or(pattern);
+    let body = match body.strip_suffix('$') {
+        Some(inner) if !inner.ends_with('\\') => inner,
+        _ => body,
+    };
+
+    let mut chars = body.chars().peekable();
+    let mut glob = String::with_capacity(body.len());
+    while let Some(c) = chars.next() {
+        let atom = match c {
+            '.' if chars.peek() == Some(&'*') => {
+                chars.next();
+                glob.push('*');
+                continue;
+            }
+            '.' if chars.peek() == Some(&'+') => {
+                chars.next();
+                glob.push_str("?*");
+                continue;
+            }
+            '.' => "?".to_string(),
+            '\\' => match chars.next()? {
+                'd' => "[0-9]".to_string(),
+                escaped if escaped.is_ascii_punctuation() => glob_char(escaped),
+                _ => return None,
+            },
+            '[' => {
+                let mut class = String::from("[");
+                if chars.peek() == Some(&'^') {
+                    chars.next();
+                    class.push('^');
+                }
+                let start = class.len();
+                loop {
+                    match chars.next()? {
+                        ']' if class.len() > start => break,
+                        member
+                            if member.is_ascii_digit()
+                                || (member.is_ascii_punctuation()
+                                    && !matches!(member, '\\' | '[' | ']' | '^')) =>
+                        {
+                            class.push(member)
+                        }
+                        _ => return None,
+                    }
+                }
+                class.push(']');
+                class
+            }
+            '*' | '+' | '?' | '{' | '}' | '(' | ')' | '|' | '^' | '$' => return None,
+            literal if literal.is_ascii() && !literal.is_ascii_alphabetic() => glob_char(literal),
+            _ => return None,
+        };
+
+        // "{n}" right after an atom writes it out n times
+        let mut repeat = 1;
+        if chars.peek() == Some(&'{') {
+            chars.next();
+            let mut digits = String::new();
+            loop {
+                match chars.next()? {
+                    '}' => break,
+                    digit if digit.is_ascii_digit() => digits.push(digit),
+                    _ => return None,
+                }
+            }
+            repeat = digits.parse().ok()?;
+            if repeat == 0 || repeat > GLOB_MAX_REPEAT {
+                return None;
+            }
+        }
+        glob.push_str(&atom.repeat(repeat));
+    }
+    Some(glob)
+}
+
+/// Write a character so GLOB matches it literally
+///
+/// Parameters:
+/// --- ---
+/// c -> The character
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> The character, in brackets if GLOB would read it as a wildcard
+/// --- ---
+///
+fn glob_char(c: char) -> String {
+    match c {
+        '*' | '?' | '[' => format!("[{}]", c),
+        _ => c.to_string(),
+    }
+}
+
+/// Refuse values SQLite can't hold in a literal
+///
+/// SQLite stops reading SQL text at a NUL, so one inside a value would cut the query
+/// short; other control characters can't be typed into a search and are refused with it
+///
+/// Parameters:
+/// --- ---
+/// value -> The raw value
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<(), CodeGenError> -> Ok if the value can be quoted, an error naming it if not
+/// --- ---
+///
+fn check_value(value: &str) -> Result<(), CodeGenError> {
+    if value.chars().any(char::is_control) {
+        return Err(CodeGenError::UnsafeValue {
+            value: value.escape_default().to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Wrap a value in single quotes, doubling the quotes inside it
+///
+/// Parameters:
+/// --- ---
+/// value -> The checked value
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> The quoted literal
+/// --- ---
+///
+fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
@@ -0,0 +1,286 @@
+/// src/dsl/formatter.rs
+///
+/// Formatter for the DSL
+///
+/// Responsible for printing an AST back out as query text. The output is canonical: keywords
+/// use their registry spelling (e.g., "prof" prints as "professor"), conditions and operators
+/// are lowercase, and values, times, and numbers keep the text the user typed. Parsing the
+/// formatted text gives back an equal AST (see TreeNode's PartialEq). Also quotes values for
+/// queries the TUI writes itself
+///
+/// Contains:
+/// --- ---
+/// Helper functions:
+///      --- ---
+///      format_query -> Print an AST as canonical query text
+///      quote_string -> Quote a value as a string literal
+///      format_node -> Print a single AST node
+///      format_comparison -> Print a keyword followed by its comparison children
+///      format_condition -> Print a Condition node
+///      format_binop -> Print a Binop node
+///      entity_keyword -> Get the canonical keyword(s) for an entity node
+///      --- ---
+/// --- ---
+///
+use crate::dsl::entities::{self, EntityInfo};
+use crate::dsl::parser::{Ast, NodeType, TreeNode};
+
+/// Print an AST as canonical query text
+///
+/// Parameters:
+/// --- ---
+/// ast -> The AST to print
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> The query text, empty for an empty AST
+/// --- ---
+///
+pub fn format_query(ast: &Ast) -> String {
+    ast.head.as_ref().map(format_node).unwrap_or_default()
+}
+
+/// Quote a value as a string literal
+///
+/// Quotes and backslashes are escaped, so the value reads back exactly as given, e.g., for
+/// the "prof is" query the detail view runs with a professor's name
+///
+/// Parameters:
+/// --- ---
+/// value -> The value to quote
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> The string literal, e.g., "\"Lown, Cheryl\""
+/// --- ---
+///
+pub fn quote_string(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for ch in value.chars() {
+        if ch == '"' || ch == '\\' {
+            quoted.push('\\');
+        }
+        quoted.push(ch);
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Print a single AST node
+///
+/// Parameters:
+/// --- ---
+/// node -> The node to print
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> The node's query text
+/// --- ---
+///
+fn format_node(node: &TreeNode) -> String {
+    match &node.node_type {
+        NodeType::Query | NodeType::LogicalTerm | NodeType::EntityQuery => {
+            node.children.iter().map(format_node).collect()
+        }
+        NodeType::LogicalFactor => match node.children.first() {
+            // a Query directly under a factor is where the user wrote parentheses
+            Some(query) if query.node_type == NodeType::Query => {
+                format!("({})", format_node(query))
+            }
+            Some(child) => format_node(child),
+            None => String::new(),
+        },
+        NodeType::T(token_type) => {
+            let operands: Vec<String> = node.children.iter().map(format_node).collect();
+            let operator = format!("{:?}", token_type).to_lowercase();
+            if operands.len() == 1 {
+                format!("{} {}", operator, operands[0])
+            } else {
+                operands.join(&format!(" {} ", operator))
+            }
+        }
+        // "course is 101" parses as a number query, so it prints as "course number is 101"
+        NodeType::CourseQuery => {
+            let sub_query = node.children.first().map(format_node);
+            format!("course {}", sub_query.unwrap_or_default())
+        }
+        NodeType::TimeQuery => {
+            // first child records whether this was "start" or "end"
+            let keyword = match node.children.first() {
+                Some(kind) if kind.node_content == "T_END" => "end",
+                _ => "start",
+            };
+            let rest: Vec<String> = node.children.iter().skip(1).map(format_node).collect();
+            format!("{} {}", keyword, rest.join(" "))
+        }
+        NodeType::DateQuery => {
+            // first child records whether this was "after", "before", or "on"
+            let comparison = node
+                .children
+                .first()
+                .map(|kind| kind.node_content.as_str())
+                .unwrap_or("after");
+            let date = node.children.get(1).map(format_node).unwrap_or_default();
+            format!("meets {} {}", comparison, date)
+        }
+        NodeType::TimeRange => {
+            let times: Vec<String> = node.children.iter().map(format_node).collect();
+            times.join(" to ")
+        }
+        NodeType::IntegerRange => {
+            let bounds: Vec<String> = node.children.iter().map(format_node).collect();
+            format!("between {}", bounds.join(" and "))
+        }
+        NodeType::DayQuery => node.children.first().map(format_node).unwrap_or_default(),
+        // the day name node, e.g. "monday" with its condition and value
+        NodeType::String if node.children.len() == 2 => {
+            // a bare day ("monday") gets a "=" condition the parser filled in
+            if node.children[0].node_content == "=" {
+                node.node_content.clone()
+            } else {
+                format_comparison(&node.node_content, node)
+            }
+        }
+        NodeType::FieldQuery => {
+            let keyword = entities::entity_named(&node.node_content)
+                .map(entity_keyword)
+                .unwrap_or_else(|| node.node_content.clone());
+            format_comparison(&keyword, node)
+        }
+        NodeType::Condition => format_condition(node),
+        NodeType::Binop => format_binop(node),
+        NodeType::StringList => {
+            let values: Vec<String> = node.children.iter().map(format_node).collect();
+            format!("({})", values.join(", "))
+        }
+        NodeType::Identifier
+        | NodeType::EmailIdentifier
+        | NodeType::Integer
+        | NodeType::Time
+        | NodeType::Date
+        | NodeType::String => node.node_content.clone(),
+        node_type => match entities::entity_for_node(node_type) {
+            Some(info) => format_comparison(&entity_keyword(info), node),
+            None => node.node_content.clone(),
+        },
+    }
+}
+
+/// Print a keyword followed by its comparison children
+///
+/// Parameters:
+/// --- ---
+/// keyword -> The keyword(s) introducing the comparison (e.g., "credit hours")
+/// node -> The node whose children are the condition/operator and the value
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> The comparison text (e.g., "credit hours >= 3")
+/// --- ---
+///
+fn format_comparison(keyword: &str, node: &TreeNode) -> String {
+    std::iter::once(keyword.to_string())
+        .chain(node.children.iter().map(format_node))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Print a Condition node
+///
+/// Parameters:
+/// --- ---
+/// node -> The Condition node
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> The condition text (e.g., "starts with")
+/// --- ---
+///
+fn format_condition(node: &TreeNode) -> String {
+    // multi-word conditions are stored whole on the node
+    if matches!(
+        node.node_content.as_str(),
+        "is not" | "does not equal" | "does not contain"
+    ) {
+        return node.node_content.clone();
+    }
+
+    let token = node
+        .children
+        .first()
+        .map(|child| child.node_content.as_str())
+        .unwrap_or_default();
+    match token {
+        "T_EQUALS" => "=",
+        "T_NOTEQUALS" => "!=",
+        "T_CONTAINS" => "contains",
+        "T_HAS" => "has",
+        "T_STARTS" => "starts with",
+        "T_ENDS" => "ends with",
+        "T_IS" => "is",
+        "T_EQUAL" => "equal",
+        "T_EQUALSWORD" => "equals",
+        "T_DOES" => "does",
+        "T_IN" => "in",
+        "T_MATCHES" => "matches",
+        _ => "is",
+    }
+    .to_string()
+}
+
+/// Print a Binop node
+///
+/// Parameters:
+/// --- ---
+/// node -> The Binop node
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> The operator text (e.g., ">=")
+/// --- ---
+///
+fn format_binop(node: &TreeNode) -> String {
+    let token = node
+        .children
+        .first()
+        .map(|child| child.node_content.as_str())
+        .unwrap_or_default();
+    match token {
+        "T_EQUALS" => "=".to_string(),
+        "T_NOTEQUALS" => "!=".to_string(),
+        "T_LESSTHAN" => "<".to_string(),
+        "T_GREATERTHAN" => ">".to_string(),
+        "T_LESSEQUAL" => "<=".to_string(),
+        "T_GREATEREQUAL" => ">=".to_string(),
+        "T_EQUALSWORD" => "equals".to_string(),
+        // word operators ("is", "least", "more", ...) print as the word itself
+        other => other.trim_start_matches("T_").to_lowercase(),
+    }
+}
+
+/// Get the canonical keyword(s) for an entity
+///
+/// Parameters:
+/// --- ---
+/// info -> The entity's registry entry
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> The canonical spelling of each of its keywords (e.g., "meeting type")
+/// --- ---
+///
+fn entity_keyword(info: &EntityInfo) -> String {
+    info.tokens
+        .iter()
+        .map(|keyword| keyword.spellings[0])
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
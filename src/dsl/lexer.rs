@@ -12,12 +12,17 @@
 ///      --- ---
 ///      new -> Create a new lexer instance
 ///      get_lexeme -> Get the lexeme of a token
+///      tokenize -> Split the input string into tokens, keeping any it couldn't recognize
 ///      analyze -> Convert the input string into a stream of tokens
 ///      --- ---
+/// describe_byte_range -> Describe a token's byte range for error messages
 /// --- ---
 ///
+use super::entities;
 use super::token::{Token, TokenType};
+use crate::tui::error_catalog;
 use crate::tui::errors::AppError;
+use crate::utils::i18n;
 use regex::Regex;
 
 /// Type alias for lexer results
@@ -47,6 +52,7 @@ pub struct Lexer {
 /// --- ---
 /// new -> Create a new lexer instance
 /// get_lexeme -> Get the lexeme of a token
+/// tokenize -> Split the input string into tokens, keeping any it couldn't recognize
 /// analyze -> Convert the input string into a stream of tokens
 /// --- ---
 ///
@@ -83,7 +89,11 @@ impl Lexer {
         &self.input_string[token.get_start()..token.get_end()]
     }
 
-    /// Convert the input string into a stream of tokens
+    /// Split the input string into tokens, keeping any it couldn't recognize
+    ///
+    /// Unlike analyze this never fails: characters no pattern matches become Unrecognized
+    /// tokens and strings missing their closing quote become UnclosedString tokens, so
+    /// debug output can show exactly where lexing went wrong
     ///
     /// Parameters:
     /// --- ---
@@ -92,25 +102,27 @@ impl Lexer {
     ///
     /// Returns:
     /// --- ---
-    /// Result<Vec<Token>, AppError>:
-    ///     Ok -> The stream of tokens
-    ///     Err -> An error occurred, contains message and problematic tokens
+    /// Vec<Token> -> Every token in the input, in order
     /// --- ---
     ///
-    pub fn analyze(&mut self) -> LexerResult {
+    pub fn tokenize(&self) -> Vec<Token> {
         // Get all patterns in lexing order (longest/most specific first)
         let patterns = TokenType::all_patterns();
 
         // Compile patterns once for efficiency
         let compiled_patterns: Vec<(TokenType, Regex)> = patterns
             .into_iter()
-            .map(|(token_type, pattern)| (token_type, Regex::new(pattern).unwrap()))
+            .map(|(token_type, pattern)| (token_type, Regex::new(&pattern).unwrap()))
+            .collect();
+        let compound_patterns: Vec<(Vec<TokenType>, Regex)> = entities::compound_keyword_patterns()
+            .into_iter()
+            .map(|(token_types, pattern)| (token_types, Regex::new(&pattern).unwrap()))
             .collect();
 
         let mut all_tokens = Vec::new();
         let mut byte_pos = 0;
 
-        // First pass: parse the entire input and collect all tokens
+        // parse the entire input and collect all tokens
         while byte_pos < self.input_string.len() {
             let remaining = &self.input_string[byte_pos..];
 
@@ -121,6 +133,28 @@ impl Lexer {
                 continue;
             }
 
+            // joined multi-word keywords (e.g., "credit-hours") still produce one token per word
+            if let Some((token_types, captures)) =
+                compound_patterns.iter().find_map(|(token_types, regex)| {
+                    regex
+                        .captures(remaining)
+                        .filter(|captures| captures.get(0).is_some_and(|m| m.start() == 0))
+                        .map(|captures| (token_types, captures))
+                })
+            {
+                for (index, token_type) in token_types.iter().enumerate() {
+                    if let Some(part) = captures.get(index + 1) {
+                        all_tokens.push(Token::new(
+                            *token_type,
+                            byte_pos + part.start(),
+                            byte_pos + part.end(),
+                        ));
+                    }
+                }
+                byte_pos += captures[0].len();
+                continue;
+            }
+
             let mut matched = false;
             for (token_type, regex) in &compiled_patterns {
                 if let Some(mat) = regex.find(remaining) {
@@ -152,6 +186,26 @@ impl Lexer {
             }
         }
 
+        all_tokens
+    }
+
+    /// Convert the input string into a stream of tokens
+    ///
+    /// Parameters:
+    /// --- ---
+    /// None
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Result<Vec<Token>, AppError>:
+    ///     Ok -> The stream of tokens
+    ///     Err -> An error occurred, contains message and problematic tokens
+    /// --- ---
+    ///
+    pub fn analyze(&mut self) -> LexerResult {
+        let all_tokens = self.tokenize();
+
         // Check if we found any unclosed string tokens
         let unclosed_strings: Vec<Token> = all_tokens
             .iter()
@@ -166,8 +220,11 @@ impl Lexer {
                 .map(|token| (token.get_start(), token.get_end()))
                 .collect();
 
-            return Err(AppError::UnrecognizedTokens(
-                "Unclosed string - add closing quote \"".to_string(),
+            return Err(AppError::UnclosedString(
+                error_catalog::message(
+                    "unclosed_string",
+                    &[("start", &problematic_positions[0].0.to_string())],
+                ),
                 problematic_positions,
             ));
         }
@@ -188,7 +245,19 @@ impl Lexer {
 
             let unrecognized_chars: Vec<String> = unrecognized_tokens
                 .iter()
-                .map(|token| format!("'{}'", self.get_lexeme(token)))
+                .map(|token| {
+                    i18n::tr(
+                        "lexer-character-at",
+                        "'{character}' at {range}",
+                        &[
+                            ("character", self.get_lexeme(token)),
+                            (
+                                "range",
+                                &describe_byte_range(token.get_start(), token.get_end()),
+                            ),
+                        ],
+                    )
+                })
                 .collect();
 
             // get the actual character(s) for the example
@@ -197,15 +266,17 @@ impl Lexer {
                 .map(|token| self.get_lexeme(token))
                 .unwrap_or("");
 
-            let message = format!(
-                "Unrecognized character{}: {}. Try wrapping in quotes if this is part of a value (e.g., \"{}\")",
-                if unrecognized_chars.len() > 1 {
-                    "s"
-                } else {
-                    ""
-                },
-                unrecognized_chars.join(", "),
-                example_char
+            let key = if unrecognized_chars.len() > 1 {
+                "unrecognized_characters"
+            } else {
+                "unrecognized_character"
+            };
+            let message = error_catalog::message(
+                key,
+                &[
+                    ("characters", &unrecognized_chars.join(", ")),
+                    ("example", example_char),
+                ],
             );
 
             return Err(AppError::UnrecognizedTokens(message, problematic_positions));
@@ -215,3 +286,35 @@ impl Lexer {
         Ok(all_tokens)
     }
 }
+
+/// Describe a token's byte range for error messages
+///
+/// Parameters:
+/// --- ---
+/// start -> Byte offset where the token starts
+/// end -> Byte offset just past the token
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> "byte 4" for single-byte tokens, "bytes 4-5" (inclusive) otherwise
+/// --- ---
+///
+fn describe_byte_range(start: usize, end: usize) -> String {
+    if end <= start + 1 {
+        i18n::tr(
+            "lexer-byte",
+            "byte {start}",
+            &[("start", &start.to_string())],
+        )
+    } else {
+        i18n::tr(
+            "lexer-bytes",
+            "bytes {start}-{end}",
+            &[
+                ("start", &start.to_string()),
+                ("end", &(end - 1).to_string()),
+            ],
+        )
+    }
+}
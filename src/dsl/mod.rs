@@ -7,6 +7,10 @@
 
 pub mod codegen;
 pub mod compiler;
+pub mod cost;
+pub mod entities;
+pub mod escape;
+pub mod formatter;
 pub mod lexer;
 pub mod parser;
 pub mod semantic;
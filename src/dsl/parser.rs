@@ -7,6 +7,10 @@
 /// Contains:
 /// --- ---
 /// ParseResult -> Result type for parser
+/// MAX_NESTING_DEPTH -> Deepest nesting of parentheses and "not" the parser accepts
+/// DATE_COMPARISONS -> Words comparing a meeting's dates with a date
+/// NEGATED_CONDITIONS -> Conditions left out of completions for a field already under "not"
+/// CompletionContext -> Nesting and negation at the end of a partial query
 /// NodeType -> Node types for the AST
 /// TreeNode -> Tree node struct
 /// Ast -> AST struct
@@ -14,11 +18,15 @@
 ///      Methods:
 ///      --- ---
 ///      new -> Create a new parser instance
+///      set_tracing -> Turn recording the grammar rules each parse enters on or off
+///      rules_entered -> Get the grammar rules the last parse entered, in order
+///      notices -> Get what the last parse assumed about the query
 ///      get_completion_suggestions -> Get completion suggestions for the current input
 ///      parse -> Parse the tokens into an AST
 ///      --- ---
 ///--- ---
 ///
+use crate::dsl::entities::{self, ValueType};
 use crate::dsl::token::{Token, TokenType};
 use crate::tui::errors::{make_user_friendly_for_completion, SyntaxError};
 use std::vec;
@@ -26,6 +34,108 @@ use std::vec;
 /// Type alias for parser results
 type ParseResult = Result<TreeNode, (SyntaxError, Vec<Token>)>;
 
+/// Deepest nesting of parentheses and "not" the parser accepts
+///
+/// Each level recurses through several parse functions, so without a limit a long run of
+/// "(" overflows the stack instead of producing an error
+pub const MAX_NESTING_DEPTH: usize = 64;
+
+/// Words comparing a meeting's dates with a date, in the order they are suggested
+const DATE_COMPARISONS: [&str; 3] = ["after", "before", "on"];
+
+/// Conditions left out of completions for a field already under "not"
+///
+/// `not prof is not Smith` is valid but reads as a double negative, so after a negated field
+/// only the positive conditions are offered
+const NEGATED_CONDITIONS: [&str; 7] = [
+    "is not",
+    "!=",
+    "not equals",
+    "does not equal",
+    "doesn't equal",
+    "does not contain",
+    "doesn't contain",
+];
+
+/// Nesting and negation at the end of a partial query
+///
+/// Fields:
+/// --- ---
+/// depth -> How many "(" are still open
+/// negated -> Whether the field being typed is negated, by "not" before it or before a group
+///            it is in (two "not"s cancel out)
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for CompletionContext
+/// PartialEq -> PartialEq trait for CompletionContext
+/// --- ---
+///
+#[derive(Debug, PartialEq)]
+struct CompletionContext {
+    depth: usize,
+    negated: bool,
+}
+
+impl CompletionContext {
+    /// Read the nesting and negation of a partial query
+    ///
+    /// Parameters:
+    /// --- ---
+    /// tokens -> The partial query's tokens
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// CompletionContext -> The open groups and negation at the last token
+    /// --- ---
+    ///
+    fn of(tokens: &[Token]) -> Self {
+        // whether each open group is negated as a whole
+        let mut groups: Vec<bool> = Vec::new();
+        let mut pending_not = false;
+        let mut negated = false;
+        // "not" and "(" only negate or group where a factor starts, not in "is not"
+        let mut at_factor_start = true;
+        // a "(" anywhere else opens a list of values ("subject in (CS, MATH)")
+        let mut in_list = false;
+
+        for token in tokens {
+            let group_negated = groups.last().copied().unwrap_or(false);
+            match token.get_token_type() {
+                TokenType::Not if at_factor_start => pending_not = !pending_not,
+                TokenType::LeftParen if at_factor_start => {
+                    groups.push(group_negated != pending_not);
+                    pending_not = false;
+                }
+                TokenType::LeftParen => in_list = true,
+                TokenType::RightParen if in_list => in_list = false,
+                TokenType::RightParen => {
+                    groups.pop();
+                    at_factor_start = false;
+                }
+                TokenType::And | TokenType::Or => {
+                    at_factor_start = true;
+                    pending_not = false;
+                    negated = false;
+                }
+                _ if at_factor_start => {
+                    negated = group_negated != pending_not;
+                    pending_not = false;
+                    at_factor_start = false;
+                }
+                _ => {}
+            }
+        }
+
+        CompletionContext {
+            depth: groups.len(),
+            negated,
+        }
+    }
+}
+
 /// Node types for the AST
 ///
 /// Node types:
@@ -60,13 +170,20 @@ pub enum NodeType {
     EnrollmentCapQuery,
     InstructionMethodQuery,
     CampusQuery,
+    SessionQuery,
+    RestrictedQuery,
+    FeeQuery,
     EnrollmentQuery,
     FullQuery,
     MeetingTypeQuery,
     TimeQuery,
     TimeRange,
+    IntegerRange,
+    DateQuery,
     DayQuery,
+    FieldQuery,
     Time,
+    Date,
     Condition,
     Binop,
     StringList,
@@ -112,6 +229,7 @@ impl std::fmt::Display for NodeType {
 /// --- ---
 /// Debug -> Debug trait for TreeNode
 /// Clone -> Clone trait for TreeNode
+/// PartialEq -> PartialEq trait for TreeNode (ignores lexical tokens, see below)
 /// --- ---
 ///
 #[derive(Debug, Clone)]
@@ -154,6 +272,31 @@ impl TreeNode {
     }
 }
 
+/// TreeNode PartialEq Trait Implementation
+///
+/// Two nodes are equal when they have the same type, content, and children. Lexical tokens
+/// only record where a node came from in the input, so they are ignored: "prof is Smith" and
+/// "professor   is Smith" parse to equal trees
+///
+/// Parameters:
+/// --- ---
+/// self -> The first TreeNode
+/// other -> The TreeNode to compare against
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// bool -> Whether the two nodes are equal
+/// --- ---
+///
+impl PartialEq for TreeNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.node_type == other.node_type
+            && self.node_content == other.node_content
+            && self.children == other.children
+    }
+}
+
 /// AST for the DSL
 ///
 /// Fields:
@@ -165,9 +308,10 @@ impl TreeNode {
 /// --- ---
 /// Debug -> Debug trait for Ast
 /// Clone -> Clone trait for Ast
+/// PartialEq -> PartialEq trait for Ast
 /// --- ---
 ///
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Ast {
     pub head: Option<TreeNode>,
 }
@@ -204,6 +348,10 @@ impl Ast {
 /// input_string -> The input string to parse
 /// ast -> The AST to parse
 /// token_pointer -> The pointer to the current token
+/// nesting_depth -> How many parentheses and "not"s enclose the current position
+/// rules_entered -> Grammar rules entered by the last parse and the token each started at,
+///                  None unless tracing is on
+/// notices -> What the last parse assumed, e.g., a condition left out after a field
 /// --- ---
 ///
 /// Implemented Traits:
@@ -215,6 +363,9 @@ pub struct Parser {
     input_string: String,
     ast: Ast,
     token_pointer: usize,
+    nesting_depth: usize,
+    rules_entered: Option<Vec<(&'static str, usize)>>,
+    notices: Vec<String>,
 }
 
 /// Parser Implementation
@@ -222,6 +373,9 @@ pub struct Parser {
 /// Methods:
 /// --- ---
 /// new -> Create a new Parser
+/// set_tracing -> Turn recording the grammar rules each parse enters on or off
+/// rules_entered -> Get the grammar rules the last parse entered, in order
+/// notices -> Get what the last parse assumed about the query
 /// get_completion_suggestions -> Get completion suggestions for the current input
 /// parse -> Parse the tokens into an AST
 /// --- ---
@@ -244,6 +398,57 @@ impl Parser {
             input_string,
             ast: Ast::new(),
             token_pointer: 0,
+            nesting_depth: 0,
+            rules_entered: None,
+            notices: Vec::new(),
+        }
+    }
+
+    /// Turn recording the grammar rules each parse enters on or off
+    ///
+    /// Parameters:
+    /// --- ---
+    /// enabled -> Whether to record the rules
+    /// --- ---
+    ///
+    pub fn set_tracing(&mut self, enabled: bool) {
+        self.rules_entered = enabled.then(Vec::new);
+    }
+
+    /// Get the grammar rules the last parse entered, in order
+    ///
+    /// Returns:
+    /// --- ---
+    /// &[(&'static str, usize)] -> Each rule (e.g., "logical_term") and the index of the
+    ///                             token it started at, empty unless tracing is on
+    /// --- ---
+    ///
+    pub fn rules_entered(&self) -> &[(&'static str, usize)] {
+        self.rules_entered.as_deref().unwrap_or(&[])
+    }
+
+    /// Get what the last parse assumed about the query
+    ///
+    /// Returns:
+    /// --- ---
+    /// &[String] -> One notice per assumption (e.g., a condition left out after a field),
+    ///              in the order they were made
+    /// --- ---
+    ///
+    pub fn notices(&self) -> &[String] {
+        &self.notices
+    }
+
+    /// Record entering a grammar rule, when tracing is on
+    ///
+    /// Parameters:
+    /// --- ---
+    /// rule -> The rule's name in the grammar (e.g., "logical_term")
+    /// --- ---
+    ///
+    fn enter_rule(&mut self, rule: &'static str) {
+        if let Some(rules) = &mut self.rules_entered {
+            rules.push((rule, self.token_pointer));
         }
     }
 
@@ -263,7 +468,78 @@ impl Parser {
     pub fn get_completion_suggestions(&mut self, tokens: &Vec<Token>) -> Vec<String> {
         // reset parser state
         self.token_pointer = 0;
+        self.nesting_depth = 0;
         self.ast = Ast::new();
+        self.notices.clear();
+        if let Some(rules) = &mut self.rules_entered {
+            rules.clear();
+        }
+
+        let context = CompletionContext::of(tokens);
+        let mut suggestions = self.parse_suggestions(tokens, &context);
+        if context.negated {
+            suggestions.retain(|suggestion| !NEGATED_CONDITIONS.contains(&suggestion.as_str()));
+        }
+
+        // fields that take a range are offered "between" along with their comparisons
+        let takes_range = tokens
+            .last()
+            .and_then(|token| entities::entity_for_token(*token.get_token_type()))
+            .is_some_and(|entity| Self::takes_range(&entity.node));
+        if takes_range && suggestions.iter().any(|suggestion| suggestion == "<") {
+            suggestions.push("between".to_string());
+        }
+        suggestions
+    }
+
+    /// Check if a numeric field can be compared with a range ("between 3 and 4")
+    ///
+    /// Parameters:
+    /// --- ---
+    /// node_type -> The field's query node type
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// bool -> True for credit hours, enrollment, and enrollment cap
+    /// --- ---
+    ///
+    fn takes_range(node_type: &NodeType) -> bool {
+        matches!(
+            node_type,
+            NodeType::CreditHoursQuery | NodeType::EnrollmentQuery | NodeType::EnrollmentCapQuery
+        )
+    }
+
+    /// Get the suggestions for where the parse of a partial query stops
+    ///
+    /// Parameters:
+    /// --- ---
+    /// mut self -> The Parser
+    /// tokens -> The partial query's tokens
+    /// context -> The query's nesting and negation (see CompletionContext::of)
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Vec<String> -> The suggestions, before negated conditions are left out
+    /// --- ---
+    ///
+    fn parse_suggestions(
+        &mut self,
+        tokens: &Vec<Token>,
+        context: &CompletionContext,
+    ) -> Vec<String> {
+        // inside parentheses a finished condition can be closed as well as continued, which
+        // shows as the query parsing once its groups are closed
+        if context.depth > 0 {
+            let end = self.input_string.len();
+            let mut closed = tokens.clone();
+            closed.extend((0..context.depth).map(|_| Token::new(TokenType::RightParen, end, end)));
+            if self.parse(&closed).is_ok() {
+                return vec!["and".to_string(), "or".to_string(), ")".to_string()];
+            }
+        }
 
         // try to parse and see where it fails
         match self.parse(tokens) {
@@ -288,20 +564,7 @@ impl Parser {
                     }
                     SyntaxError::EmptyQuery => {
                         // empty query - suggest starting entities
-                        vec![
-                            "professor".to_string(),
-                            "course".to_string(),
-                            "subject".to_string(),
-                            "title".to_string(),
-                            "number".to_string(),
-                            "description".to_string(),
-                            "credit".to_string(),
-                            "prerequisites".to_string(),
-                            "corequisites".to_string(),
-                            "enrollment".to_string(),
-                            "campus".to_string(),
-                            "meeting".to_string(),
-                        ]
+                        entities::query_start_suggestions()
                     }
                     _ => vec![],
                 }
@@ -341,7 +604,7 @@ impl Parser {
     ///
     fn get_context_suggestions(&self, tokens: &[Token]) -> Vec<String> {
         // Full condition operators from grammar:
-        // <condition> ::= "=" | "!=" | "contains" | "has" | "starts with" | "ends with" | "is" | "is not" | "equals" | "not equals" | "does not equal" | "doesn't equal" | "doesn't contain"
+        // <condition> ::= "=" | "!=" | "contains" | "has" | "starts with" | "ends with" | "is" | "is not" | "equals" | "not equals" | "does not equal" | "doesn't equal" | "doesn't contain" | "in" | "matches"
         let string_conditions = vec![
             "is".to_string(),
             "is not".to_string(),
@@ -356,6 +619,8 @@ impl Parser {
             "doesn't equal".to_string(),
             "does not contain".to_string(),
             "doesn't contain".to_string(),
+            "in".to_string(),
+            "matches".to_string(),
         ];
 
         // Binary operators for numeric comparisons from grammar:
@@ -379,100 +644,57 @@ impl Parser {
 
         if tokens.is_empty() {
             // start of query
-            vec![
-                "professor".to_string(),
-                "course".to_string(),
-                "subject".to_string(),
-                "title".to_string(),
-                "number".to_string(),
-                "description".to_string(),
-                "credit".to_string(),
-                "prerequisites".to_string(),
-                "corequisites".to_string(),
-                "enrollment".to_string(),
-                "campus".to_string(),
-                "meeting".to_string(),
-            ]
+            entities::query_start_suggestions()
         } else {
             let last_token = &tokens[tokens.len() - 1];
-            match *last_token.get_token_type() {
-                // Entities followed by <condition>
-                TokenType::Prof
-                | TokenType::Subject
-                | TokenType::Title
-                | TokenType::Description
-                | TokenType::Number
-                | TokenType::Campus
-                | TokenType::Method
-                | TokenType::Full
-                | TokenType::Type => string_conditions,
-
-                // Days are followed by <condition>
-                TokenType::Monday
-                | TokenType::Tuesday
-                | TokenType::Wednesday
-                | TokenType::Thursday
-                | TokenType::Friday
-                | TokenType::Saturday
-                | TokenType::Sunday => string_conditions,
-
-                // Prereqs/Corereqs followed by <condition>
-                TokenType::Prereqs | TokenType::Corereqs => string_conditions,
-
-                // Course can be followed by condition OR sub-queries
-                TokenType::Course => {
-                    let mut suggestions = vec![
-                        "subject".to_string(),
-                        "number".to_string(),
-                        "title".to_string(),
-                        "description".to_string(),
-                        "credit".to_string(),
-                        "prereqs".to_string(),
-                        "corereqs".to_string(),
-                    ];
-                    suggestions.extend(string_conditions);
-                    suggestions
-                }
+            let token_type = *last_token.get_token_type();
+
+            // declared fields share one token, so look them up by what was typed
+            let entity = if token_type == TokenType::Field {
+                entities::describe_keyword(self.get_lexeme(last_token))
+            } else {
+                entities::entity_for_token(token_type)
+            };
 
-                // Credit must be followed by "hours"
-                TokenType::Credit => vec!["hours".to_string()],
+            if let Some(entity) = entity {
+                // multi-word keywords must be finished first (e.g., "credit" -> "hours")
+                if let Some(next_keyword) = entity.next_keyword(token_type) {
+                    return vec![next_keyword.to_string()];
+                }
 
-                // Credit hours followed by <binop>
-                TokenType::Hours => numeric_binops.clone(),
+                return match entity.value_type {
+                    // numeric and time entities are followed by <binop>
+                    ValueType::Integer | ValueType::Time => numeric_binops,
 
-                // Meeting must be followed by "type"
-                TokenType::Meeting => vec!["type".to_string()],
+                    // dates are compared by when they fall
+                    ValueType::Date => DATE_COMPARISONS.iter().map(|s| s.to_string()).collect(),
 
-                // Numeric entities followed by <binop>
-                TokenType::Size | TokenType::Enrollment | TokenType::Cap => numeric_binops.clone(),
+                    // course can be followed by condition OR sub-queries
+                    _ if entity.node == NodeType::CourseQuery => {
+                        let mut suggestions = entities::course_field_suggestions();
+                        suggestions.extend(string_conditions);
+                        suggestions
+                    }
 
-                // Time entities followed by <binop> or time value
-                TokenType::Start | TokenType::End => numeric_binops,
+                    // text, true/false, and day entities are followed by <condition>
+                    ValueType::Text | ValueType::Boolean | ValueType::Day => string_conditions,
+                };
+            }
 
+            match token_type {
                 // After values, suggest logical operators
                 TokenType::Identifier
                 | TokenType::Alphanumeric
+                | TokenType::EmailAddress
                 | TokenType::String
                 | TokenType::Integer
-                | TokenType::Time => {
+                | TokenType::Time
+                | TokenType::Date => {
                     vec!["and".to_string(), "or".to_string()]
                 }
 
                 // After logical operators, suggest entities
-                TokenType::And | TokenType::Or => vec![
-                    "professor".to_string(),
-                    "course".to_string(),
-                    "subject".to_string(),
-                    "title".to_string(),
-                    "number".to_string(),
-                    "description".to_string(),
-                    "credit".to_string(),
-                    "prereqs".to_string(),
-                    "corereqs".to_string(),
-                    "enrollment".to_string(),
-                    "campus".to_string(),
-                    "meeting".to_string(),
-                ],
+                TokenType::And | TokenType::Or => entities::query_start_suggestions(),
 
                 _ => vec![],
             }
@@ -515,6 +737,48 @@ impl Parser {
         )
     }
 
+    /// Check if the next token starts a numeric comparison
+    ///
+    /// A number on its own counts, since parse_binop assumes "=" before it (see
+    /// infer_condition)
+    ///
+    /// Parameters:
+    /// --- ---
+    /// self -> The Parser
+    /// tokens -> The tokens being parsed
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// bool -> True if the next token is a binary operator or a number
+    /// --- ---
+    ///
+    fn at_comparison(&self, tokens: &[Token]) -> bool {
+        tokens.get(self.token_pointer).is_some_and(|token| {
+            Self::is_valid_binop_token(token.get_token_type())
+                || *token.get_token_type() == TokenType::Integer
+        })
+    }
+
+    /// Check if the next token starts a range of numbers
+    ///
+    /// Parameters:
+    /// --- ---
+    /// self -> The Parser to check
+    /// tokens -> The tokens being parsed
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// bool -> True if the next token is "between"
+    /// --- ---
+    ///
+    fn at_range(&self, tokens: &[Token]) -> bool {
+        tokens
+            .get(self.token_pointer)
+            .is_some_and(|token| *token.get_token_type() == TokenType::Between)
+    }
+
     /// Get the next token
     ///
     /// Parameters:
@@ -538,6 +802,65 @@ impl Parser {
         }
     }
 
+    /// Get the most recently consumed token
+    ///
+    /// Entity parsers start after their keyword was consumed and use this to get it back,
+    /// so a missing keyword is an error rather than an index underflow
+    ///
+    /// Parameters:
+    /// --- ---
+    /// self -> The Parser to get the previous token for
+    /// tokens -> The tokens being parsed
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Result<Token, (SyntaxError, Vec<Token>)> -> The previous token, or an error if none was consumed
+    /// --- ---
+    ///
+    fn previous_token(&self, tokens: &[Token]) -> Result<Token, (SyntaxError, Vec<Token>)> {
+        self.token_pointer
+            .checked_sub(1)
+            .and_then(|index| tokens.get(index))
+            .copied()
+            .ok_or_else(|| {
+                (
+                    SyntaxError::ExpectedAfter {
+                        expected: entities::query_start_suggestions(),
+                        after: "start of query".to_string(),
+                        position: self.token_pointer,
+                    },
+                    vec![],
+                )
+            })
+    }
+
+    /// Enter a nested parenthesis or "not", failing once MAX_NESTING_DEPTH is passed
+    ///
+    /// Parameters:
+    /// --- ---
+    /// mut self -> The Parser to track the depth for
+    /// token -> The "(" or "not" token opening the level
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Result<(), (SyntaxError, Vec<Token>)> -> Ok if the level is allowed, error pointing at token otherwise
+    /// --- ---
+    ///
+    fn enter_nesting(&mut self, token: Token) -> Result<(), (SyntaxError, Vec<Token>)> {
+        if self.nesting_depth >= MAX_NESTING_DEPTH {
+            return Err((
+                SyntaxError::TooDeeplyNested {
+                    max: MAX_NESTING_DEPTH,
+                },
+                vec![token],
+            ));
+        }
+        self.nesting_depth += 1;
+        Ok(())
+    }
+
     /// Parse the tokens into an AST
     ///
     /// Parameters:
@@ -555,7 +878,12 @@ impl Parser {
     ///
     pub fn parse(&mut self, tokens: &Vec<Token>) -> Result<Ast, (SyntaxError, Vec<Token>)> {
         self.token_pointer = 0;
+        self.nesting_depth = 0;
         self.ast = Ast::new();
+        self.notices.clear();
+        if let Some(rules) = &mut self.rules_entered {
+            rules.clear();
+        }
 
         // check for empty query
         if tokens.is_empty() {
@@ -612,6 +940,7 @@ impl Parser {
     /// --- ---
     ///
     fn parse_query(&mut self, tokens: &Vec<Token>) -> ParseResult {
+        self.enter_rule("query");
         // create query node first
         let mut query_node = TreeNode::new(NodeType::Query, NodeType::Query.to_string(), None);
 
@@ -668,6 +997,7 @@ impl Parser {
     /// ParseResult
     ///     Ok(TreeNode) -> Parsing succeeded, contains the TreeNode
     fn parse_logical_term(&mut self, tokens: &Vec<Token>) -> ParseResult {
+        self.enter_rule("logical_term");
         let mut logical_term_node = TreeNode::new(
             NodeType::LogicalTerm,
             NodeType::LogicalTerm.to_string(),
@@ -731,6 +1061,7 @@ impl Parser {
     /// --- ---
     ///
     fn parse_logical_factor(&mut self, tokens: &Vec<Token>) -> ParseResult {
+        self.enter_rule("logical_factor");
         let mut logical_factor_node = TreeNode::new(
             NodeType::LogicalFactor,
             NodeType::LogicalFactor.to_string(),
@@ -749,7 +1080,9 @@ impl Parser {
                 )
             })?;
 
+            self.enter_nesting(not_token)?;
             let factor = self.parse_logical_factor(tokens)?;
+            self.nesting_depth -= 1;
 
             let mut not_node = TreeNode::new(
                 NodeType::T(TokenType::Not),
@@ -771,7 +1104,9 @@ impl Parser {
                 )
             })?;
 
+            self.enter_nesting(left_paren)?;
             let query = self.parse_query(tokens)?;
+            self.nesting_depth -= 1;
 
             let right_paren = self.next_token(tokens).map_err(|_| {
                 (
@@ -798,7 +1133,7 @@ impl Parser {
     ///
     /// Syntax:
     /// --- ---
-    /// <entity_query> ::= <professor_query> | <course_query> | <meeting_type_query> | <time_query> | <day_query>
+    /// <entity_query> ::= <professor_query> | <course_query> | <meeting_type_query> | <time_query> | <date_query> | <day_query> | <field_query>
     /// --- ---
     ///
     /// Parameters:
@@ -815,6 +1150,7 @@ impl Parser {
     /// --- ---
     ///
     fn parse_entity_query(&mut self, tokens: &[Token]) -> ParseResult {
+        self.enter_rule("entity_query");
         let mut entity_query = TreeNode::new(
             NodeType::EntityQuery,
             NodeType::EntityQuery.to_string(),
@@ -825,20 +1161,7 @@ impl Parser {
         let next_token = self.next_token(tokens).map_err(|_| {
             (
                 SyntaxError::ExpectedAfter {
-                    expected: vec![
-                        "professor".to_string(),
-                        "course".to_string(),
-                        "subject".to_string(),
-                        "title".to_string(),
-                        "number".to_string(),
-                        "description".to_string(),
-                        "credit".to_string(),
-                        "prerequisites".to_string(),
-                        "corequisites".to_string(),
-                        "enrollment".to_string(),
-                        "campus".to_string(),
-                        "meeting".to_string(),
-                    ],
+                    expected: entities::query_start_suggestions(),
                     after: "start of query".to_string(),
                     position: self.token_pointer,
                 },
@@ -901,8 +1224,13 @@ impl Parser {
                 }
             }
             TokenType::Type => self.parse_meeting_type_query(tokens)?,
+            TokenType::Field => self.parse_field_query(tokens)?,
             TokenType::Time => self.parse_time_query(tokens)?,
             TokenType::Start | TokenType::End => self.parse_time_query(tokens)?,
+            TokenType::Meets => self.parse_date_query(tokens)?,
+            TokenType::Session => self.parse_session_query(tokens)?,
+            TokenType::Restricted => self.parse_restricted_query(tokens)?,
+            TokenType::Fee => self.parse_fee_query(tokens)?,
             TokenType::Monday
             | TokenType::Tuesday
             | TokenType::Wednesday
@@ -919,20 +1247,7 @@ impl Parser {
                             self.get_lexeme(&next_token)
                         ),
                         context: "query start".to_string(),
-                        suggestions: vec![
-                            "prof".to_string(),
-                            "course".to_string(),
-                            "subject".to_string(),
-                            "title".to_string(),
-                            "number".to_string(),
-                            "description".to_string(),
-                            "credit".to_string(),
-                            "prereqs".to_string(),
-                            "corereqs".to_string(),
-                            "enrollment".to_string(),
-                            "campus".to_string(),
-                            "meeting".to_string(),
-                        ],
+                        suggestions: entities::query_start_suggestions(),
                     },
                     vec![next_token],
                 ))
@@ -964,7 +1279,8 @@ impl Parser {
     /// --- ---
     ///
     fn parse_professor_query(&mut self, tokens: &[Token]) -> ParseResult {
-        let prof_token = tokens[self.token_pointer - 1];
+        self.enter_rule("professor_query");
+        let prof_token = self.previous_token(tokens)?;
         let mut prof_node = TreeNode::new(
             NodeType::ProfessorQuery,
             NodeType::ProfessorQuery.to_string(),
@@ -1011,7 +1327,8 @@ impl Parser {
     /// --- ---
     ///
     fn parse_course_query(&mut self, tokens: &[Token]) -> ParseResult {
-        let course_token = tokens[self.token_pointer - 1];
+        self.enter_rule("course_query");
+        let course_token = self.previous_token(tokens)?;
         let mut course_node = TreeNode::new(
             NodeType::CourseQuery,
             NodeType::CourseQuery.to_string(),
@@ -1083,7 +1400,9 @@ impl Parser {
             | TokenType::EqualsWord
             | TokenType::Does
             | TokenType::DoesNotEqual
-            | TokenType::DoesNotContain => {
+            | TokenType::DoesNotContain
+            | TokenType::In
+            | TokenType::Matches => {
                 // Parse as direct condition + string for course number/code
                 let condition = self.parse_condition(tokens)?;
                 let string = self.parse_string(tokens)?;
@@ -1168,7 +1487,8 @@ impl Parser {
     /// --- ---
     ///
     fn parse_subject_query(&mut self, tokens: &[Token]) -> ParseResult {
-        let subject_token = tokens[self.token_pointer - 1];
+        self.enter_rule("subject_query");
+        let subject_token = self.previous_token(tokens)?;
         let mut subject_node = TreeNode::new(
             NodeType::SubjectQuery,
             NodeType::SubjectQuery.to_string(),
@@ -1214,7 +1534,8 @@ impl Parser {
     /// --- ---
     ///
     fn parse_number_query(&mut self, tokens: &[Token]) -> ParseResult {
-        let number_token = tokens[self.token_pointer - 1];
+        self.enter_rule("number_query");
+        let number_token = self.previous_token(tokens)?;
         let mut number_node = TreeNode::new(
             NodeType::NumberQuery,
             NodeType::NumberQuery.to_string(),
@@ -1260,7 +1581,8 @@ impl Parser {
     /// --- ---
     ///
     fn parse_title_query(&mut self, tokens: &[Token]) -> ParseResult {
-        let title_token = tokens[self.token_pointer - 1];
+        self.enter_rule("title_query");
+        let title_token = self.previous_token(tokens)?;
         let mut title_node = TreeNode::new(
             NodeType::TitleQuery,
             NodeType::TitleQuery.to_string(),
@@ -1306,7 +1628,8 @@ impl Parser {
     /// --- ---
     ///
     fn parse_description_query(&mut self, tokens: &[Token]) -> ParseResult {
-        let description_token = tokens[self.token_pointer - 1];
+        self.enter_rule("description_query");
+        let description_token = self.previous_token(tokens)?;
         let mut description_node = TreeNode::new(
             NodeType::DescriptionQuery,
             NodeType::DescriptionQuery.to_string(),
@@ -1335,7 +1658,7 @@ impl Parser {
     ///
     /// Syntax:
     /// --- ---
-    /// <credit_hours_query> ::= "credit hours" <binop> <integer>
+    /// <credit_hours_query> ::= "credit hours" (<binop> <integer> | <integer_range>)
     /// --- ---
     ///
     /// Parameters:
@@ -1352,34 +1675,50 @@ impl Parser {
     /// --- ---
     ///
     fn parse_credit_hours_query(&mut self, tokens: &[Token]) -> ParseResult {
-        let credit_token = tokens[self.token_pointer - 1];
+        self.enter_rule("credit_hours_query");
+        let credit_token = self.previous_token(tokens)?;
         let mut credit_node = TreeNode::new(
             NodeType::CreditHoursQuery,
             NodeType::CreditHoursQuery.to_string(),
             Some(credit_token),
         );
 
+        // "credits" on its own already means credit hours
+        let hours_optional = self
+            .get_lexeme(&credit_token)
+            .eq_ignore_ascii_case("credits")
+            && self.token_pointer < tokens.len()
+            && *tokens[self.token_pointer].get_token_type() != TokenType::Hours;
+
         // The "credit" token was already consumed, now consume "hours"
-        let hours_token = self.next_token(tokens).map_err(|_| {
-            (
-                SyntaxError::ExpectedAfter {
-                    expected: vec!["hours".to_string()],
-                    after: "credit".to_string(),
-                    position: self.token_pointer,
-                },
-                vec![],
-            )
-        })?;
+        if !hours_optional {
+            let hours_token = self.next_token(tokens).map_err(|_| {
+                (
+                    SyntaxError::ExpectedAfter {
+                        expected: vec!["hours".to_string()],
+                        after: "credit".to_string(),
+                        position: self.token_pointer,
+                    },
+                    vec![],
+                )
+            })?;
 
-        if *hours_token.get_token_type() != TokenType::Hours {
-            return Err((
-                SyntaxError::ExpectedAfter {
-                    expected: vec!["hours".to_string()],
-                    after: "credit".to_string(),
-                    position: self.token_pointer,
-                },
-                vec![hours_token],
-            ));
+            if *hours_token.get_token_type() != TokenType::Hours {
+                return Err((
+                    SyntaxError::ExpectedAfter {
+                        expected: vec!["hours".to_string()],
+                        after: "credit".to_string(),
+                        position: self.token_pointer,
+                    },
+                    vec![hours_token],
+                ));
+            }
+        }
+
+        // Parse: <integer_range> (credit hours between 3 and 4)
+        if self.at_range(tokens) {
+            credit_node.children.push(self.parse_integer_range(tokens)?);
+            return Ok(credit_node);
         }
 
         let binop_query = self.parse_binop(tokens)?;
@@ -1421,7 +1760,8 @@ impl Parser {
     /// --- ---
     ///
     fn parse_prereqs_query(&mut self, tokens: &[Token]) -> ParseResult {
-        let prereqs_token = tokens[self.token_pointer - 1];
+        self.enter_rule("prereqs_query");
+        let prereqs_token = self.previous_token(tokens)?;
         let mut prereqs_node = TreeNode::new(
             NodeType::PrereqsQuery,
             NodeType::PrereqsQuery.to_string(),
@@ -1467,7 +1807,8 @@ impl Parser {
     /// --- ---
     ///
     fn parse_coreqs_query(&mut self, tokens: &[Token]) -> ParseResult {
-        let coreqs_token = tokens[self.token_pointer - 1];
+        self.enter_rule("coreqs_query");
+        let coreqs_token = self.previous_token(tokens)?;
         let mut coreqs_node = TreeNode::new(
             NodeType::CoreqsQuery,
             NodeType::CoreqsQuery.to_string(),
@@ -1496,7 +1837,7 @@ impl Parser {
     ///
     /// Syntax:
     /// --- ---
-    /// <enrollment_cap_query> ::= "cap" <binop> <integer> | "enrollment cap" <binop> <integer>
+    /// <enrollment_cap_query> ::= ("cap" | "enrollment cap") (<binop> <integer> | <integer_range>)
     /// --- ---
     ///
     /// Parameters:
@@ -1513,7 +1854,8 @@ impl Parser {
     /// --- ---
     ///
     fn parse_enrollment_cap_query(&mut self, tokens: &[Token]) -> ParseResult {
-        let cap_token = tokens[self.token_pointer - 1];
+        self.enter_rule("enrollment_cap_query");
+        let cap_token = self.previous_token(tokens)?;
         let mut cap_node = TreeNode::new(
             NodeType::EnrollmentCapQuery,
             NodeType::EnrollmentCapQuery.to_string(),
@@ -1534,6 +1876,12 @@ impl Parser {
             }
         }
 
+        // Parse: <integer_range> (cap between 20 and 40)
+        if self.at_range(tokens) {
+            cap_node.children.push(self.parse_integer_range(tokens)?);
+            return Ok(cap_node);
+        }
+
         let binop_query = self.parse_binop(tokens)?;
 
         // Provide a user-friendly error message when number is missing
@@ -1556,7 +1904,7 @@ impl Parser {
     ///
     /// Syntax:
     /// --- ---
-    /// <instruction_method_query> ::= "method" <condition> <string>
+    /// <instruction_method_query> ::= "method" <condition> <string> ["person"]
     /// --- ---
     ///
     /// Parameters:
@@ -1573,7 +1921,8 @@ impl Parser {
     /// --- ---
     ///
     fn parse_instruction_method_query(&mut self, tokens: &[Token]) -> ParseResult {
-        let method_token = tokens[self.token_pointer - 1];
+        self.enter_rule("instruction_method_query");
+        let method_token = self.previous_token(tokens)?;
         let mut method_node = TreeNode::new(
             NodeType::InstructionMethodQuery,
             NodeType::InstructionMethodQuery.to_string(),
@@ -1586,13 +1935,26 @@ impl Parser {
         if self.token_pointer >= tokens.len() {
             return Err((
                 SyntaxError::MissingToken(
-                    "instruction method (e.g., 'online', 'in-person', 'hybrid')".into(),
+                    "instruction method (e.g., 'online', 'in person', 'hybrid')".into(),
                 ),
                 vec![],
             ));
         }
 
-        let string_query = self.parse_string(tokens)?;
+        let mut string_query = self.parse_string(tokens)?;
+
+        // "in person" can be written without quotes
+        if string_query.node_content.eq_ignore_ascii_case("in") {
+            if let Some(person_token) = tokens.get(self.token_pointer) {
+                let person = self.get_lexeme(person_token).to_string();
+                if *person_token.get_token_type() == TokenType::Identifier
+                    && person.eq_ignore_ascii_case("person")
+                {
+                    self.token_pointer += 1;
+                    string_query.node_content = format!("{} {}", string_query.node_content, person);
+                }
+            }
+        }
 
         method_node.children.push(condition_query);
         method_node.children.push(string_query);
@@ -1621,7 +1983,8 @@ impl Parser {
     /// --- ---
     ///
     fn parse_campus_query(&mut self, tokens: &[Token]) -> ParseResult {
-        let campus_token = tokens[self.token_pointer - 1];
+        self.enter_rule("campus_query");
+        let campus_token = self.previous_token(tokens)?;
         let mut campus_node = TreeNode::new(
             NodeType::CampusQuery,
             NodeType::CampusQuery.to_string(),
@@ -1646,17 +2009,17 @@ impl Parser {
         Ok(campus_node)
     }
 
-    /// Parse the enrollment query into a TreeNode
+    /// Parse the session query into a TreeNode
     ///
     /// Syntax:
     /// --- ---
-    /// <enrollment_query> ::= "size" <binop> <integer> | "enrollment" <binop> <integer>
+    /// <session_query> ::= "session" <condition> <string> ["half"]
     /// --- ---
     ///
     /// Parameters:
     /// --- ---
-    /// mut self -> The Parser to parse the enrollment query for
-    /// tokens -> The tokens to parse the enrollment query for
+    /// mut self -> The Parser to parse the session query for
+    /// tokens -> The tokens to parse the session query for
     /// --- ---
     ///
     /// Returns:
@@ -1666,62 +2029,299 @@ impl Parser {
     ///     Err((SyntaxError, Vec<Token>)) -> Parsing failed, contains the SyntaxError and the remaining tokens
     /// --- ---
     ///
-    fn parse_enrollment_query(&mut self, tokens: &[Token]) -> ParseResult {
-        let enrollment_token = tokens[self.token_pointer - 1];
-        let mut enrollment_node = TreeNode::new(
-            NodeType::EnrollmentQuery,
-            NodeType::EnrollmentQuery.to_string(),
-            Some(enrollment_token),
+    fn parse_session_query(&mut self, tokens: &[Token]) -> ParseResult {
+        self.enter_rule("session_query");
+        let session_token = self.previous_token(tokens)?;
+        let mut session_node = TreeNode::new(
+            NodeType::SessionQuery,
+            NodeType::SessionQuery.to_string(),
+            Some(session_token),
         );
 
-        // Check if next token is a valid binary operator
-        if self.token_pointer >= tokens.len() {
-            return Err((
-                SyntaxError::MissingToken(
-                    "comparison like '>', '<', '=' followed by a number".into(),
-                ),
-                vec![],
-            ));
-        }
+        let condition_query = self.parse_condition(tokens)?;
 
-        let next_token = &tokens[self.token_pointer];
-        if !Self::is_valid_binop_token(next_token.get_token_type()) {
+        // Provide a user-friendly error message when value is missing
+        if self.token_pointer >= tokens.len() {
             return Err((
                 SyntaxError::MissingToken(
-                    "comparison like '>', '<', '=' followed by a number".into(),
+                    "session (e.g., 'full', 'first half', 'second half')".into(),
                 ),
                 vec![],
             ));
         }
 
-        let binop_query = self.parse_binop(tokens)?;
+        let mut string_query = self.parse_string(tokens)?;
 
-        // Provide a user-friendly error message when number is missing
-        if self.token_pointer >= tokens.len() {
-            return Err((
-                SyntaxError::MissingToken("enrollment count (e.g., 20, 50)".into()),
-                vec![],
-            ));
+        // "first half" and "second half" can be written without quotes
+        let halves = ["first", "second", "1st", "2nd"];
+        if halves.contains(&string_query.node_content.to_lowercase().as_str()) {
+            if let Some(half_token) = tokens.get(self.token_pointer) {
+                let half = self.get_lexeme(half_token).to_string();
+                if *half_token.get_token_type() == TokenType::Identifier
+                    && half.eq_ignore_ascii_case("half")
+                {
+                    self.token_pointer += 1;
+                    string_query.node_content = format!("{} {}", string_query.node_content, half);
+                }
+            }
         }
 
-        let integer_query = self.parse_integer(tokens)?;
-
-        enrollment_node.children.push(binop_query);
-        enrollment_node.children.push(integer_query);
+        session_node.children.push(condition_query);
+        session_node.children.push(string_query);
 
-        Ok(enrollment_node)
+        Ok(session_node)
     }
 
-    /// Parse the full query into a TreeNode
+    /// Parse the restricted query into a TreeNode
     ///
     /// Syntax:
     /// --- ---
-    /// <full_query> ::= "full" <condition> <string>
+    /// <restricted_query> ::= "restricted" <condition> <string>
     /// --- ---
     ///
     /// Parameters:
     /// --- ---
-    /// mut self -> The Parser to parse the full query for
+    /// mut self -> The Parser to parse the restricted query for
+    /// tokens -> The tokens to parse the restricted query for
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// ParseResult
+    ///     Ok(TreeNode) -> Parsing succeeded, contains the TreeNode
+    ///     Err((SyntaxError, Vec<Token>)) -> Parsing failed, contains the SyntaxError and the remaining tokens
+    /// --- ---
+    ///
+    fn parse_restricted_query(&mut self, tokens: &[Token]) -> ParseResult {
+        self.enter_rule("restricted_query");
+        let restricted_token = self.previous_token(tokens)?;
+        let mut restricted_node = TreeNode::new(
+            NodeType::RestrictedQuery,
+            NodeType::RestrictedQuery.to_string(),
+            Some(restricted_token),
+        );
+
+        let condition_query = self.parse_condition(tokens)?;
+
+        // Provide a user-friendly error message when value is missing
+        if self.token_pointer >= tokens.len() {
+            return Err((
+                SyntaxError::MissingToken("'true' or 'false'".into()),
+                vec![],
+            ));
+        }
+
+        let string_query = self.parse_string(tokens)?;
+
+        restricted_node.children.push(condition_query);
+        restricted_node.children.push(string_query);
+
+        Ok(restricted_node)
+    }
+
+    /// Parse the fee query into a TreeNode
+    ///
+    /// Syntax:
+    /// --- ---
+    /// <fee_query> ::= ("fee" | "fees") <binop> <integer>
+    /// --- ---
+    ///
+    /// Parameters:
+    /// --- ---
+    /// mut self -> The Parser to parse the fee query for
+    /// tokens -> The tokens to parse the fee query for
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// ParseResult
+    ///     Ok(TreeNode) -> Parsing succeeded, contains the TreeNode
+    ///     Err((SyntaxError, Vec<Token>)) -> Parsing failed, contains the SyntaxError and the remaining tokens
+    /// --- ---
+    ///
+    fn parse_fee_query(&mut self, tokens: &[Token]) -> ParseResult {
+        self.enter_rule("fee_query");
+        let fee_token = self.previous_token(tokens)?;
+        let mut fee_node = TreeNode::new(
+            NodeType::FeeQuery,
+            NodeType::FeeQuery.to_string(),
+            Some(fee_token),
+        );
+
+        // Check if next token is a valid binary operator
+        if !self.at_comparison(tokens) {
+            return Err((
+                SyntaxError::MissingToken(
+                    "comparison like '<=', '>', '=' followed by a dollar amount".into(),
+                ),
+                vec![],
+            ));
+        }
+
+        let binop_query = self.parse_binop(tokens)?;
+        let integer_query = self.parse_integer(tokens)?;
+
+        fee_node.children.push(binop_query);
+        fee_node.children.push(integer_query);
+
+        Ok(fee_node)
+    }
+
+    /// Parse a field declared with define_field! into a TreeNode
+    ///
+    /// Syntax:
+    /// --- ---
+    /// <field_query> ::= <field> <condition> <string> | <field> <binop> <integer>
+    /// --- ---
+    ///
+    /// Parameters:
+    /// --- ---
+    /// mut self -> The Parser to parse the field query for
+    /// tokens -> The tokens to parse the field query for
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// ParseResult
+    ///     Ok(TreeNode) -> Parsing succeeded, contains the TreeNode (content is the field name)
+    ///     Err((SyntaxError, Vec<Token>)) -> Parsing failed, contains the SyntaxError and the remaining tokens
+    /// --- ---
+    ///
+    fn parse_field_query(&mut self, tokens: &[Token]) -> ParseResult {
+        self.enter_rule("field_query");
+        let field_token = self.previous_token(tokens)?;
+        let info = entities::describe_keyword(self.get_lexeme(&field_token))
+            .filter(|info| info.node == NodeType::FieldQuery)
+            .ok_or_else(|| {
+                (
+                    SyntaxError::MissingToken("a search field".into()),
+                    vec![field_token],
+                )
+            })?;
+        let mut field_node = TreeNode::new(
+            NodeType::FieldQuery,
+            info.name.to_string(),
+            Some(field_token),
+        );
+
+        if info.value_type == ValueType::Integer {
+            // numeric fields compare with <binop> <integer>
+            if !self.at_comparison(tokens) {
+                return Err((
+                    SyntaxError::MissingToken(
+                        "comparison like '>', '<', '=' followed by a number".into(),
+                    ),
+                    vec![],
+                ));
+            }
+
+            let binop_query = self.parse_binop(tokens)?;
+
+            if self.token_pointer >= tokens.len() {
+                return Err((
+                    SyntaxError::MissingToken(format!("{} (a number)", info.name)),
+                    vec![],
+                ));
+            }
+
+            let integer_query = self.parse_integer(tokens)?;
+
+            field_node.children.push(binop_query);
+            field_node.children.push(integer_query);
+        } else {
+            let condition_query = self.parse_condition(tokens)?;
+
+            if self.token_pointer >= tokens.len() {
+                return Err((
+                    SyntaxError::MissingToken(format!("{} value", info.name)),
+                    vec![],
+                ));
+            }
+
+            let string_query = self.parse_string(tokens)?;
+
+            field_node.children.push(condition_query);
+            field_node.children.push(string_query);
+        }
+
+        Ok(field_node)
+    }
+
+    /// Parse the enrollment query into a TreeNode
+    ///
+    /// Syntax:
+    /// --- ---
+    /// <enrollment_query> ::= ("size" | "enrollment") (<binop> <integer> | <integer_range>)
+    /// --- ---
+    ///
+    /// Parameters:
+    /// --- ---
+    /// mut self -> The Parser to parse the enrollment query for
+    /// tokens -> The tokens to parse the enrollment query for
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// ParseResult
+    ///     Ok(TreeNode) -> Parsing succeeded, contains the TreeNode
+    ///     Err((SyntaxError, Vec<Token>)) -> Parsing failed, contains the SyntaxError and the remaining tokens
+    /// --- ---
+    ///
+    fn parse_enrollment_query(&mut self, tokens: &[Token]) -> ParseResult {
+        self.enter_rule("enrollment_query");
+        let enrollment_token = self.previous_token(tokens)?;
+        let mut enrollment_node = TreeNode::new(
+            NodeType::EnrollmentQuery,
+            NodeType::EnrollmentQuery.to_string(),
+            Some(enrollment_token),
+        );
+
+        // Parse: <integer_range> (enrollment between 20 and 50)
+        if self.at_range(tokens) {
+            enrollment_node
+                .children
+                .push(self.parse_integer_range(tokens)?);
+            return Ok(enrollment_node);
+        }
+
+        // Check if next token is a valid binary operator (or a number, read as "= number")
+        if !self.at_comparison(tokens) {
+            return Err((
+                SyntaxError::MissingToken(
+                    "comparison like '>', '<', '=' followed by a number".into(),
+                ),
+                vec![],
+            ));
+        }
+
+        let binop_query = self.parse_binop(tokens)?;
+
+        // Provide a user-friendly error message when number is missing
+        if self.token_pointer >= tokens.len() {
+            return Err((
+                SyntaxError::MissingToken("enrollment count (e.g., 20, 50)".into()),
+                vec![],
+            ));
+        }
+
+        let integer_query = self.parse_integer(tokens)?;
+
+        enrollment_node.children.push(binop_query);
+        enrollment_node.children.push(integer_query);
+
+        Ok(enrollment_node)
+    }
+
+    /// Parse the full query into a TreeNode
+    ///
+    /// Syntax:
+    /// --- ---
+    /// <full_query> ::= "full" <condition> <string>
+    /// --- ---
+    ///
+    /// Parameters:
+    /// --- ---
+    /// mut self -> The Parser to parse the full query for
     /// tokens -> The tokens to parse the full query for
     /// --- ---
     ///
@@ -1733,7 +2333,8 @@ impl Parser {
     /// --- ---
     ///
     fn parse_full_query(&mut self, tokens: &[Token]) -> ParseResult {
-        let full_token = tokens[self.token_pointer - 1];
+        self.enter_rule("full_query");
+        let full_token = self.previous_token(tokens)?;
         let mut full_node = TreeNode::new(
             NodeType::FullQuery,
             NodeType::FullQuery.to_string(),
@@ -1779,12 +2380,13 @@ impl Parser {
     /// --- ---
     ///
     fn parse_meeting_type_query(&mut self, tokens: &[Token]) -> ParseResult {
+        self.enter_rule("meeting_type_query");
         let main_token = if self.token_pointer > 1
             && *tokens[self.token_pointer - 2].get_token_type() == TokenType::Meeting
         {
             tokens[self.token_pointer - 2]
         } else {
-            tokens[self.token_pointer - 1]
+            self.previous_token(tokens)?
         };
         let mut meeting_node = TreeNode::new(
             NodeType::MeetingTypeQuery,
@@ -1833,7 +2435,8 @@ impl Parser {
     /// --- ---
     ///
     fn parse_time_query(&mut self, tokens: &[Token]) -> ParseResult {
-        let time_type_token = &tokens[self.token_pointer - 1];
+        self.enter_rule("time_query");
+        let time_type_token = &self.previous_token(tokens)?;
         let mut time_node = TreeNode::new(
             NodeType::TimeQuery,
             NodeType::TimeQuery.to_string(),
@@ -1927,6 +2530,7 @@ impl Parser {
     /// --- ---
     ///
     fn parse_time_range(&mut self, tokens: &[Token]) -> ParseResult {
+        self.enter_rule("time_range");
         let start_time = self.parse_time(tokens)?;
 
         let to_token = self.next_token(tokens).map_err(|_| {
@@ -1963,6 +2567,140 @@ impl Parser {
         Ok(time_range_node)
     }
 
+    /// Parse the integer range into a TreeNode
+    ///
+    /// Syntax:
+    /// --- ---
+    /// <integer_range> ::= "between" <integer> "and" <integer>
+    /// --- ---
+    ///
+    /// Parameters:
+    /// --- ---
+    /// mut self -> The Parser to parse the integer range for
+    /// tokens -> The tokens to parse the integer range for
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// ParseResult
+    ///     Ok(TreeNode) -> Parsing succeeded, contains the TreeNode
+    ///     Err((SyntaxError, Vec<Token>)) -> Parsing failed, contains the SyntaxError and the remaining tokens
+    /// --- ---
+    ///
+    fn parse_integer_range(&mut self, tokens: &[Token]) -> ParseResult {
+        self.enter_rule("integer_range");
+        let between_token = self
+            .next_token(tokens)
+            .map_err(|_| (SyntaxError::MissingToken("'between'".into()), vec![]))?;
+        let mut integer_range_node = TreeNode::new(
+            NodeType::IntegerRange,
+            NodeType::IntegerRange.to_string(),
+            Some(between_token),
+        );
+
+        if self.token_pointer >= tokens.len() {
+            return Err((
+                SyntaxError::MissingToken(
+                    "a number after 'between' (e.g., 'between 3 and 4')".into(),
+                ),
+                vec![],
+            ));
+        }
+        let low = self.parse_integer(tokens)?;
+
+        // the "and" here joins the two numbers, not two conditions
+        let and_token = self.next_token(tokens).map_err(|_| {
+            (
+                SyntaxError::MissingToken(
+                    "'and' followed by the upper number (e.g., 'between 3 and 4')".into(),
+                ),
+                vec![],
+            )
+        })?;
+        if *and_token.get_token_type() != TokenType::And {
+            return Err((
+                SyntaxError::ExpectedAfter {
+                    expected: vec!["and".to_string()],
+                    after: "lower number".to_string(),
+                    position: self.token_pointer,
+                },
+                vec![and_token],
+            ));
+        }
+
+        if self.token_pointer >= tokens.len() {
+            return Err((
+                SyntaxError::MissingToken("a number after 'and' (e.g., 'between 3 and 4')".into()),
+                vec![],
+            ));
+        }
+        let high = self.parse_integer(tokens)?;
+
+        integer_range_node.children.push(low);
+        integer_range_node.children.push(high);
+
+        Ok(integer_range_node)
+    }
+
+    /// Parse the date query into a TreeNode
+    ///
+    /// Syntax:
+    /// --- ---
+    /// <date_query> ::= "meets" ("after" | "before" | "on") <date>
+    /// --- ---
+    ///
+    /// Parameters:
+    /// --- ---
+    /// mut self -> The Parser to parse the date query for
+    /// tokens -> The tokens to parse the date query for
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// ParseResult
+    ///     Ok(TreeNode) -> Parsing succeeded, contains the TreeNode
+    ///     Err((SyntaxError, Vec<Token>)) -> Parsing failed, contains the SyntaxError and the remaining tokens
+    /// --- ---
+    ///
+    fn parse_date_query(&mut self, tokens: &[Token]) -> ParseResult {
+        self.enter_rule("date_query");
+        let meets_token = self.previous_token(tokens)?;
+        let mut date_node = TreeNode::new(
+            NodeType::DateQuery,
+            NodeType::DateQuery.to_string(),
+            Some(meets_token),
+        );
+
+        let expected_comparison = |position| SyntaxError::ExpectedAfter {
+            expected: DATE_COMPARISONS.iter().map(|s| s.to_string()).collect(),
+            after: "meets".to_string(),
+            position,
+        };
+        let comparison_token = self
+            .next_token(tokens)
+            .map_err(|_| (expected_comparison(self.token_pointer), vec![]))?;
+        // the comparisons aren't keywords, so "on" and "after" still work as search values
+        let comparison = self.get_lexeme(&comparison_token).to_lowercase();
+        if *comparison_token.get_token_type() != TokenType::Identifier
+            || !DATE_COMPARISONS.contains(&comparison.as_str())
+        {
+            return Err((
+                expected_comparison(self.token_pointer),
+                vec![comparison_token],
+            ));
+        }
+
+        // records whether this was "after", "before", or "on"
+        date_node.children.push(TreeNode::new(
+            NodeType::String,
+            comparison,
+            Some(comparison_token),
+        ));
+        date_node.children.push(self.parse_date(tokens)?);
+
+        Ok(date_node)
+    }
+
     /// Parse the day query into a TreeNode
     ///
     /// Syntax:
@@ -1984,8 +2722,9 @@ impl Parser {
     /// --- ---
     ///
     fn parse_day_query(&mut self, tokens: &[Token]) -> ParseResult {
+        self.enter_rule("day_query");
         // the day token was already consumed in parse_entity_query, check which one it was
-        let day_token = &tokens[self.token_pointer - 1];
+        let day_token = &self.previous_token(tokens)?;
         let mut day_node = TreeNode::new(
             NodeType::DayQuery,
             NodeType::DayQuery.to_string(),
@@ -2043,7 +2782,8 @@ impl Parser {
     /// --- ---
     ///
     fn parse_day_query_helper(&mut self, tokens: &[Token], day_name: &str) -> ParseResult {
-        let day_token = tokens[self.token_pointer - 1];
+        self.enter_rule("day_query_helper");
+        let day_token = self.previous_token(tokens)?;
         let mut day_node = TreeNode::new(NodeType::String, day_name.to_string(), Some(day_token));
 
         // check if next token is a logical operator (and/or), closing parenthesis, or end of input
@@ -2110,6 +2850,7 @@ impl Parser {
     /// --- ---
     ///
     fn parse_monday_query(&mut self, tokens: &[Token]) -> ParseResult {
+        self.enter_rule("monday_query");
         self.parse_day_query_helper(tokens, "monday")
     }
 
@@ -2134,6 +2875,7 @@ impl Parser {
     /// --- ---
     ///
     fn parse_tuesday_query(&mut self, tokens: &[Token]) -> ParseResult {
+        self.enter_rule("tuesday_query");
         self.parse_day_query_helper(tokens, "tuesday")
     }
 
@@ -2158,6 +2900,7 @@ impl Parser {
     /// --- ---
     ///
     fn parse_wednesday_query(&mut self, tokens: &[Token]) -> ParseResult {
+        self.enter_rule("wednesday_query");
         self.parse_day_query_helper(tokens, "wednesday")
     }
 
@@ -2182,6 +2925,7 @@ impl Parser {
     /// --- ---
     ///
     fn parse_thursday_query(&mut self, tokens: &[Token]) -> ParseResult {
+        self.enter_rule("thursday_query");
         self.parse_day_query_helper(tokens, "thursday")
     }
 
@@ -2206,6 +2950,7 @@ impl Parser {
     /// --- ---
     ///
     fn parse_friday_query(&mut self, tokens: &[Token]) -> ParseResult {
+        self.enter_rule("friday_query");
         self.parse_day_query_helper(tokens, "friday")
     }
 
@@ -2230,6 +2975,7 @@ impl Parser {
     /// --- ---
     ///
     fn parse_saturday_query(&mut self, tokens: &[Token]) -> ParseResult {
+        self.enter_rule("saturday_query");
         self.parse_day_query_helper(tokens, "saturday")
     }
 
@@ -2254,6 +3000,7 @@ impl Parser {
     /// --- ---
     ///
     fn parse_sunday_query(&mut self, tokens: &[Token]) -> ParseResult {
+        self.enter_rule("sunday_query");
         self.parse_day_query_helper(tokens, "sunday")
     }
 
@@ -2278,6 +3025,7 @@ impl Parser {
     /// --- ---
     ///
     fn parse_time(&mut self, tokens: &[Token]) -> ParseResult {
+        self.enter_rule("time");
         let time_token = self.next_token(tokens).map_err(|_| {
             (
                 SyntaxError::MissingToken("time (e.g., '9:00am', '2:30pm')".into()),
@@ -2299,11 +3047,52 @@ impl Parser {
         Ok(time_node)
     }
 
+    /// Parse the date into a TreeNode
+    ///
+    /// Syntax:
+    /// --- ---
+    /// <date> ::= [0-9]{4}-[0-9]{2}-[0-9]{2} | [0-9]{1,2}/[0-9]{1,2}(/[0-9]{2,4})? | <month> [0-9]{1,2} (","? [0-9]{4})?
+    /// --- ---
+    ///
+    /// Parameters:
+    /// --- ---
+    /// mut self -> The Parser to parse the date for
+    /// tokens -> The tokens to parse the date for
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// ParseResult
+    ///     Ok(TreeNode) -> Parsing succeeded, contains the TreeNode
+    ///     Err((SyntaxError, Vec<Token>)) -> Parsing failed, contains the SyntaxError and the remaining tokens
+    /// --- ---
+    ///
+    fn parse_date(&mut self, tokens: &[Token]) -> ParseResult {
+        self.enter_rule("date");
+        let date_token = self.next_token(tokens).map_err(|_| {
+            (
+                SyntaxError::MissingToken("a date (e.g., 'oct 15' or '2024-10-15')".into()),
+                vec![],
+            )
+        })?;
+        let lexeme = self.get_lexeme(&date_token).to_string();
+        let mut date_node = TreeNode::new(NodeType::Date, lexeme, Some(date_token));
+
+        // like times, any token is accepted so semantic analysis can explain what a date is
+        date_node.children.push(TreeNode::new(
+            NodeType::String,
+            date_token.get_token_type().to_string(),
+            Some(date_token),
+        ));
+
+        Ok(date_node)
+    }
+
     /// Parse the condition into a TreeNode
     ///
     /// Syntax:
     /// --- ---
-    /// <condition> ::= "=" | "!=" | "contains" | "has" | "starts with" | "ends with" | "is" | "equals" | "not equals" | "does not equal" | "does not contain"
+    /// <condition> ::= "=" | "!=" | "contains" | "has" | "starts with" | "ends with" | "is" | "equals" | "not equals" | "does not equal" | "does not contain" | "in" | "matches"
     /// --- ---
     ///
     /// Parameters:
@@ -2320,6 +3109,10 @@ impl Parser {
     /// --- ---
     ///
     fn parse_condition(&mut self, tokens: &[Token]) -> ParseResult {
+        self.enter_rule("condition");
+        if let Some(inferred) = self.infer_condition(tokens, NodeType::Condition) {
+            return Ok(inferred);
+        }
         let condition_token = self.next_token(tokens).map_err(|_| {
             (
                 SyntaxError::ExpectedAfter {
@@ -2337,6 +3130,8 @@ impl Parser {
                         "doesn't equal".to_string(),
                         "does not contain".to_string(),
                         "doesn't contain".to_string(),
+                        "in".to_string(),
+                        "matches".to_string(),
                     ],
                     after: "entity keyword".to_string(),
                     position: self.token_pointer,
@@ -2364,7 +3159,9 @@ impl Parser {
             | TokenType::Has
             | TokenType::Equal
             | TokenType::EqualsWord
-            | TokenType::Does => {
+            | TokenType::Does
+            | TokenType::In
+            | TokenType::Matches => {
                 // "DOES" can be followed by "NOT" to form "is not" (for backwards compatibility)
                 // but "does not equal" and "does not contain" are now handled as single tokens above
                 // else, it's a valid standalone condition
@@ -2458,6 +3255,7 @@ impl Parser {
                                 "doesn't equal".to_string(),
                                 "does not contain".to_string(),
                                 "doesn't contain".to_string(),
+                                "matches".to_string(),
                             ],
                         },
                         vec![condition_token],
@@ -2483,6 +3281,7 @@ impl Parser {
                                 "doesn't equal".to_string(),
                                 "does not contain".to_string(),
                                 "doesn't contain".to_string(),
+                                "matches".to_string(),
                             ],
                         },
                         vec![condition_token],
@@ -2500,6 +3299,68 @@ impl Parser {
         Ok(condition_node)
     }
 
+    /// Assume a field's default condition when a value follows its keyword directly
+    ///
+    /// `prof Smith` reads as `prof contains Smith` and `credit hours 3` as
+    /// `credit hours = 3`, following the field's value type (see
+    /// ValueType::default_condition). Nothing is consumed; a notice saying what was assumed is
+    /// recorded, like day queries defaulting to "= true" but visible to the user
+    ///
+    /// Parameters:
+    /// --- ---
+    /// mut self -> The Parser
+    /// tokens -> The tokens being parsed, the field's keyword just consumed
+    /// node_type -> NodeType::Condition or NodeType::Binop, whichever the field expects
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Option<TreeNode> -> The assumed condition, None if the next token isn't a value the
+    ///                     field takes or the field has no default
+    /// --- ---
+    ///
+    fn infer_condition(&mut self, tokens: &[Token], node_type: NodeType) -> Option<TreeNode> {
+        let keyword = tokens.get(self.token_pointer.checked_sub(1)?)?;
+        let value = tokens.get(self.token_pointer)?;
+        let info = entities::describe_keyword(self.get_lexeme(keyword))?;
+        let (condition, written) = info.value_type.default_condition()?;
+
+        let takes_value = match info.value_type {
+            ValueType::Integer => *value.get_token_type() == TokenType::Integer,
+            _ => matches!(
+                value.get_token_type(),
+                TokenType::String
+                    | TokenType::Alphanumeric
+                    | TokenType::Integer
+                    | TokenType::EmailAddress
+                    | TokenType::Identifier
+            ),
+        };
+        if !takes_value {
+            return None;
+        }
+
+        self.notices.push(format!(
+            "No condition after {}, read as: {} {} {}",
+            info.name,
+            info.name,
+            written,
+            self.get_lexeme(value)
+        ));
+        let condition_token = Token::new(condition, 0, 0);
+        let mut condition_node = TreeNode::new(
+            node_type.clone(),
+            node_type.to_string(),
+            Some(condition_token),
+        );
+        condition_node.children.push(TreeNode::new(
+            NodeType::String,
+            condition.to_string(),
+            Some(condition_token),
+        ));
+        Some(condition_node)
+    }
+
     /// Parse the binop into a TreeNode
     ///
     /// Syntax:
@@ -2521,6 +3382,10 @@ impl Parser {
     /// --- ---
     ///
     fn parse_binop(&mut self, tokens: &[Token]) -> ParseResult {
+        self.enter_rule("binop");
+        if let Some(inferred) = self.infer_condition(tokens, NodeType::Binop) {
+            return Ok(inferred);
+        }
         let operator_token = self.next_token(tokens).map_err(|_| {
             (
                 SyntaxError::ExpectedAfter {
@@ -2623,25 +3488,110 @@ impl Parser {
     /// --- ---
     ///
     fn parse_string(&mut self, tokens: &[Token]) -> ParseResult {
-        // try to parse as email first, fallback to regular identifier
-        if self.token_pointer < tokens.len() {
-            let next_token = &tokens[self.token_pointer];
-            if next_token.get_token_type().to_string().contains("@") {
-                self.parse_email_identifier(tokens)
-            } else {
-                // Alphanumeric tokens (like "424N") should be parsed as identifiers
-                self.parse_identifier(tokens)
+        self.enter_rule("string");
+        // "in" compares with a list of values rather than one
+        let after_in = self
+            .token_pointer
+            .checked_sub(1)
+            .and_then(|previous| tokens.get(previous))
+            .is_some_and(|previous| *previous.get_token_type() == TokenType::In);
+        if after_in {
+            return self.parse_string_list(tokens);
+        }
+        // email addresses get their own node, so codegen can search the email column
+        match tokens.get(self.token_pointer).map(Token::get_token_type) {
+            Some(TokenType::EmailAddress) => self.parse_email_identifier(tokens),
+            // Alphanumeric tokens (like "424N") should be parsed as identifiers
+            _ => self.parse_identifier(tokens),
+        }
+    }
+
+    /// Parse the string list into a TreeNode
+    ///
+    /// Syntax:
+    /// --- ---
+    /// <string_list> ::= "(" <string> ("," <string>)* ")"
+    /// --- ---
+    ///
+    /// Parameters:
+    /// --- ---
+    /// mut self -> The Parser to parse the string list for
+    /// tokens -> The tokens to parse the string list for
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// ParseResult
+    ///     Ok(TreeNode) -> Parsing succeeded, contains the TreeNode with a child for each value
+    ///     Err((SyntaxError, Vec<Token>)) -> Parsing failed, contains the SyntaxError and the remaining tokens
+    /// --- ---
+    ///
+    fn parse_string_list(&mut self, tokens: &[Token]) -> ParseResult {
+        self.enter_rule("string_list");
+        let open_token = match tokens.get(self.token_pointer) {
+            Some(token) if *token.get_token_type() == TokenType::LeftParen => *token,
+            _ => {
+                return Err((
+                    SyntaxError::MissingToken(
+                        "a list of values after 'in' (e.g., '(CS, MATH)')".into(),
+                    ),
+                    vec![],
+                ))
+            }
+        };
+        self.token_pointer += 1;
+        let mut list_node = TreeNode::new(
+            NodeType::StringList,
+            NodeType::StringList.to_string(),
+            Some(open_token),
+        );
+
+        loop {
+            // an empty list or a stray comma leaves a value out
+            match tokens.get(self.token_pointer).map(Token::get_token_type) {
+                None | Some(TokenType::Comma | TokenType::RightParen) => {
+                    return Err((
+                        SyntaxError::MissingToken("a value in the list".into()),
+                        vec![],
+                    ));
+                }
+                _ => list_node.children.push(self.parse_string(tokens)?),
+            }
+
+            let separator = self.next_token(tokens).map_err(|_| {
+                (
+                    SyntaxError::ExpectedAfter {
+                        expected: vec![",".to_string(), ")".to_string()],
+                        after: "list value".to_string(),
+                        position: self.token_pointer,
+                    },
+                    vec![],
+                )
+            })?;
+            match *separator.get_token_type() {
+                TokenType::Comma => {}
+                TokenType::RightParen => break,
+                _ => {
+                    return Err((
+                        SyntaxError::ExpectedAfter {
+                            expected: vec![",".to_string(), ")".to_string()],
+                            after: "list value".to_string(),
+                            position: self.token_pointer - 1,
+                        },
+                        vec![separator],
+                    ));
+                }
             }
-        } else {
-            self.parse_identifier(tokens)
         }
+
+        Ok(list_node)
     }
 
     /// Parse the integer into a TreeNode
     ///
     /// Syntax:
     /// --- ---
-    /// <integer> ::= [0-9]+
+    /// <integer> ::= [0-9]+ ( "_" [0-9]+ )*
     /// --- ---
     ///
     /// Parameters:
@@ -2658,6 +3608,7 @@ impl Parser {
     /// --- ---
     ///
     fn parse_integer(&mut self, tokens: &[Token]) -> ParseResult {
+        self.enter_rule("integer");
         let digit_token = self
             .next_token(tokens)
             .map_err(|_| (SyntaxError::MissingToken("a number".into()), vec![]))?;
@@ -2665,8 +3616,8 @@ impl Parser {
         let lexeme = self.get_lexeme(&digit_token).to_string();
         let mut integer_node = TreeNode::new(NodeType::Integer, lexeme, Some(digit_token));
 
-        // for now, we'll assume any token can be an integer
-        // in a real implementation, you'd validate it's actually numeric
+        // any token is accepted here so semantic analysis can say what a number field
+        // expected, and check the number is in the field's range
         integer_node.children.push(TreeNode::new(
             NodeType::String,
             digit_token.get_token_type().to_string(),
@@ -2697,6 +3648,7 @@ impl Parser {
     /// --- ---
     ///
     fn parse_identifier(&mut self, tokens: &[Token]) -> ParseResult {
+        self.enter_rule("identifier");
         let id_token = self.next_token(tokens).map_err(|_| {
             (
                 SyntaxError::MissingToken("a value to search for".into()),
@@ -2737,7 +3689,8 @@ impl Parser {
             | TokenType::Is
             | TokenType::Equal
             | TokenType::EqualsWord
-            | TokenType::Does => {
+            | TokenType::Does
+            | TokenType::Matches => {
                 return Err((
                     SyntaxError::InvalidContext {
                         token: self.get_lexeme(&id_token).to_string(),
@@ -2769,7 +3722,7 @@ impl Parser {
     ///
     /// Syntax:
     /// --- ---
-    /// <email_identifier> ::= [a-zA-Z_][a-zA-Z0-9_]*@[a-zA-Z0-9_]*\.[a-zA-Z0-9_.]*
+    /// <email_identifier> ::= [A-Za-z0-9._%+-]+ "@" [A-Za-z0-9-]+ ( "." [A-Za-z0-9-]+ )+
     /// --- ---
     ///
     /// Parameters:
@@ -2786,6 +3739,7 @@ impl Parser {
     /// --- ---
     ///
     fn parse_email_identifier(&mut self, tokens: &[Token]) -> ParseResult {
+        self.enter_rule("email_identifier");
         let email_token = self.next_token(tokens).map_err(|_| {
             (
                 SyntaxError::MissingToken("Expected email identifier".into()),
@@ -2796,8 +3750,6 @@ impl Parser {
         let lexeme = self.get_lexeme(&email_token).to_string();
         let mut email_node = TreeNode::new(NodeType::EmailIdentifier, lexeme, Some(email_token));
 
-        // for now, we'll assume any token can be an email identifier
-        // in a real implementation, you'd validate it matches the email pattern
         email_node.children.push(TreeNode::new(
             NodeType::String,
             email_token.get_token_type().to_string(),
@@ -11,17 +11,36 @@
 /// invalid_context -> Helper to build a `SemanticError`
 /// analyze_node -> Analyze a node in the AST (dispatches to specialized analyzers)
 /// analyze_numeric_query -> Validate numeric field queries
+/// check_numeric_range -> Refuse numbers and comparisons outside a field's range
+/// check_in_range -> Refuse a number a field can't hold
 /// analyze_time_query -> Validate time queries
 /// analyze_time_range -> Validate time range nodes
+/// analyze_date_query -> Validate date queries
 /// analyze_day_query -> Validate day queries
 /// analyze_string_field_query -> Validate string-based field queries
+/// analyze_boolean_query -> Validate true/false field queries
 /// analyze_integer -> Validate integer literals
+/// parse_integer_literal -> Read an integer literal, allowing _ digit separators
 /// analyze_time -> Validate time literals
+/// analyze_date -> Validate date literals
+/// validate_lookup_values -> Check exact values against the downloaded lookup lists
+/// validate_node_values -> Check a node's exact values, then its children's
+/// resolve_campus_aliases -> Replace campus aliases with the campuses they stand for
+/// resolve_node_aliases -> Replace a node's campus alias, then its children's
+/// Placement -> Clause of the generated SQL a predicate belongs in
+/// predicate_placement -> Decide which clause a predicate belongs in
 /// --- ---
 ///
+use crate::data::campus_aliases::CampusAliases;
+use crate::data::lookups::Lookups;
+use crate::data::sql::normalize_instruction_method;
+use crate::dsl::codegen::{comparison_operator, equality_values, pattern_value};
+use crate::dsl::entities::{self, EntityInfo, ValueType};
 use crate::dsl::parser::{Ast, NodeType, TreeNode};
 use crate::dsl::token::TokenType;
 use crate::tui::errors::SemanticError;
+use crate::utils::text::compile_pattern;
+use crate::utils::time::parse_date_phrase;
 
 /// Type alias for semantic analysis results
 type SemanticResult = Result<(), (SemanticError, Vec<(usize, usize)>)>;
@@ -33,8 +52,10 @@ type SemanticResult = Result<(), (SemanticError, Vec<(usize, usize)>)>;
 ///
 /// Checks:
 /// --- ---
-/// - Numeric queries (credit hours, enrollment, caps) use numeric operators and integer values
+/// - Numeric queries (credit hours, enrollment, caps) use numeric operators and integer values,
+///   or a range between two integers
 /// - Time queries either use a numeric comparison against a time value or a well‑formed time range
+/// - Date queries compare with after, before, or on a real date
 /// - Day queries and other leaf nodes are structurally consistent
 /// --- ---
 ///
@@ -90,8 +111,11 @@ fn analyze_node(node: &TreeNode) -> SemanticResult {
     use NodeType::*;
 
     match node.node_type {
-        CreditHoursQuery | EnrollmentQuery | EnrollmentCapQuery => {
+        CreditHoursQuery | EnrollmentQuery | EnrollmentCapQuery | FeeQuery => {
             analyze_numeric_query(node)?;
+            if let Some(info) = entities::entity_for_node(&node.node_type) {
+                check_numeric_range(node, info)?;
+            }
         }
 
         TimeQuery => {
@@ -102,10 +126,18 @@ fn analyze_node(node: &TreeNode) -> SemanticResult {
             analyze_time_range(node)?;
         }
 
+        DateQuery => {
+            analyze_date_query(node)?;
+        }
+
         DayQuery => {
             analyze_day_query(node)?;
         }
 
+        RestrictedQuery => {
+            analyze_boolean_query(node)?;
+        }
+
         ProfessorQuery
         | SubjectQuery
         | NumberQuery
@@ -115,11 +147,21 @@ fn analyze_node(node: &TreeNode) -> SemanticResult {
         | CoreqsQuery
         | InstructionMethodQuery
         | CampusQuery
+        | SessionQuery
         | FullQuery
         | MeetingTypeQuery => {
             analyze_string_field_query(node)?;
         }
 
+        // declared fields are numeric or string depending on their registry entry
+        FieldQuery => match entities::entity_named(&node.node_content) {
+            Some(info) if info.value_type == ValueType::Integer => {
+                analyze_numeric_query(node)?;
+                check_numeric_range(node, info)?;
+            }
+            _ => analyze_string_field_query(node)?,
+        },
+
         Integer => {
             analyze_integer(node)?;
         }
@@ -128,6 +170,10 @@ fn analyze_node(node: &TreeNode) -> SemanticResult {
             analyze_time(node)?;
         }
 
+        Date => {
+            analyze_date(node)?;
+        }
+
         // Other node types have no extra semantic rules beyond what
         // the parser already guarantees
         _ => {}
@@ -143,8 +189,28 @@ fn analyze_node(node: &TreeNode) -> SemanticResult {
 
 /// Validate numeric field queries (credit hours, enrollment, caps).
 ///
-/// Expected shape: <Binop> <Integer>
+/// Expected shapes:
+/// - <Binop> <Integer>
+/// - <IntegerRange> with two <Integer> children
 fn analyze_numeric_query(node: &TreeNode) -> SemanticResult {
+    if let [range] = node.children.as_slice() {
+        if matches!(range.node_type, NodeType::IntegerRange)
+            && range.children.len() == 2
+            && range
+                .children
+                .iter()
+                .all(|bound| matches!(bound.node_type, NodeType::Integer))
+        {
+            return Ok(());
+        }
+        let err = invalid_context(
+            range.node_content.clone(),
+            "numeric range",
+            &["between <number> and <number>"],
+        );
+        return Err((err, get_span(range)));
+    }
+
     if node.children.len() != 2 {
         let err = invalid_context(
             node.node_content.clone(),
@@ -177,6 +243,104 @@ fn analyze_numeric_query(node: &TreeNode) -> SemanticResult {
     Ok(())
 }
 
+/// Refuse numbers and comparisons outside a field's range
+///
+/// A number the field can't hold (e.g., "credit hours = 45") is out of range, and so is a
+/// comparison no value in the range satisfies (e.g., "enrollment < 0"), since either would
+/// silently find nothing
+///
+/// Parameters:
+/// --- ---
+/// node -> A numeric field query, already checked by analyze_numeric_query
+/// info -> The field's registry entry
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// SemanticResult -> Ok if the comparison can match, an OutOfRange or NeverMatches error if not
+/// --- ---
+///
+fn check_numeric_range(node: &TreeNode, info: &EntityInfo) -> SemanticResult {
+    // both ends of a range must be numbers the field can hold
+    if let [range] = node.children.as_slice() {
+        for bound in &range.children {
+            check_in_range(bound, info)?;
+        }
+        return Ok(());
+    }
+
+    let value_node = &node.children[1];
+    check_in_range(value_node, info)?;
+    let (Some((min, max)), Some(value)) = (
+        info.range,
+        value_node
+            .lexical_token
+            .filter(|tok| *tok.get_token_type() == TokenType::Integer)
+            .and_then(|_| parse_integer_literal(&value_node.node_content)),
+    ) else {
+        return Ok(());
+    };
+
+    let operator = comparison_operator(&node.children[0]).unwrap_or_default();
+    let never_matches = match operator.as_str() {
+        "<" => value <= min,
+        "<=" => value < min,
+        ">" => value >= max,
+        ">=" => value > max,
+        _ => false,
+    };
+    if never_matches {
+        let err = SemanticError::NeverMatches {
+            comparison: format!("{} {} {}", info.name, operator, value_node.node_content),
+            field: info.name.to_string(),
+            min,
+            max,
+        };
+        let mut span = get_span(&node.children[0]);
+        span.extend(get_span(value_node));
+        return Err((err, span));
+    }
+
+    Ok(())
+}
+
+/// Refuse a number a field can't hold
+///
+/// Parameters:
+/// --- ---
+/// value_node -> An Integer node compared with the field
+/// info -> The field's registry entry
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// SemanticResult -> Ok if the number is in the field's range (or isn't a number), an
+///                   OutOfRange error if not
+/// --- ---
+///
+fn check_in_range(value_node: &TreeNode, info: &EntityInfo) -> SemanticResult {
+    let (Some((min, max)), Some(value)) = (
+        info.range,
+        value_node
+            .lexical_token
+            .filter(|tok| *tok.get_token_type() == TokenType::Integer)
+            .and_then(|_| parse_integer_literal(&value_node.node_content)),
+    ) else {
+        return Ok(());
+    };
+
+    if value < min || value > max {
+        let err = SemanticError::OutOfRange {
+            value: value_node.node_content.clone(),
+            field: info.name.to_string(),
+            min,
+            max,
+        };
+        return Err((err, get_span(value_node)));
+    }
+    Ok(())
+}
+
 /// Validate time queries.
 ///
 /// Expected shapes:
@@ -269,6 +433,28 @@ fn analyze_time_range(node: &TreeNode) -> SemanticResult {
     Ok(())
 }
 
+/// Validate date queries.
+///
+/// Expected shape: "meets" ("after" | "before" | "on") <date>
+fn analyze_date_query(node: &TreeNode) -> SemanticResult {
+    let well_formed = node.children.len() == 2
+        && matches!(node.children[0].node_type, NodeType::String)
+        && matches!(node.children[1].node_type, NodeType::Date);
+    if !well_formed {
+        let err = invalid_context(
+            node.node_content.clone(),
+            "date query",
+            &[
+                "meets after <date>",
+                "meets before <date>",
+                "meets on <date>",
+            ],
+        );
+        return Err((err, get_span(node)));
+    }
+    Ok(())
+}
+
 /// Validate day queries.
 ///
 /// Expected shape: DayQuery -> [ day_node ]
@@ -358,7 +544,7 @@ fn analyze_day_query(node: &TreeNode) -> SemanticResult {
 
 /// Validate string-based field queries.
 ///
-/// Expected shape: [ <Condition>, <Identifier-or-email> ]
+/// Expected shape: [ <Condition>, <Identifier-or-email> ], or a StringList of them after "in"
 fn analyze_string_field_query(node: &TreeNode) -> SemanticResult {
     if node.children.len() != 2 {
         let err = invalid_context(
@@ -390,36 +576,71 @@ fn analyze_string_field_query(node: &TreeNode) -> SemanticResult {
         return Err((err, get_span(child)));
     }
 
-    // Second child must be a string-like value node
-    let value_node = &node.children[1];
-    if !matches!(
-        value_node.node_type,
-        NodeType::Identifier | NodeType::EmailIdentifier | NodeType::String
-    ) {
-        let err = invalid_context(
-            value_node.node_content.clone(),
-            "string field value",
-            &["<text value>", "quoted string"],
-        );
-        return Err((err, get_span(value_node)));
-    }
-
-    // Reject numeric or time values in string fields
-    if let Some(tok) = value_node.lexical_token {
-        if matches!(*tok.get_token_type(), TokenType::Integer | TokenType::Time) {
+    // Second child must be a string-like value node, or a list of them
+    let value_nodes = match node.children[1].node_type {
+        NodeType::StringList => node.children[1].children.iter().collect(),
+        _ => vec![&node.children[1]],
+    };
+    for value_node in value_nodes {
+        if !matches!(
+            value_node.node_type,
+            NodeType::Identifier | NodeType::EmailIdentifier | NodeType::String
+        ) {
             let err = invalid_context(
-                tok.get_token_type().to_string(),
+                value_node.node_content.clone(),
                 "string field value",
                 &["<text value>", "quoted string"],
             );
-            return Err((err, vec![(tok.get_start(), tok.get_end())]));
+            return Err((err, get_span(value_node)));
+        }
+
+        // Reject numeric or time values in string fields
+        if let Some(tok) = value_node.lexical_token {
+            if matches!(*tok.get_token_type(), TokenType::Integer | TokenType::Time) {
+                let err = invalid_context(
+                    tok.get_token_type().to_string(),
+                    "string field value",
+                    &["<text value>", "quoted string"],
+                );
+                return Err((err, vec![(tok.get_start(), tok.get_end())]));
+            }
+        }
+    }
+
+    // a pattern that doesn't compile would fail on every row, so refuse it up front
+    if let Some(pattern) = pattern_value(node) {
+        if let Err(reason) = compile_pattern(&pattern) {
+            let err = SemanticError::InvalidPattern { pattern, reason };
+            return Err((err, get_span(&node.children[1])));
         }
     }
 
     Ok(())
 }
 
+/// Validate true/false field queries.
+///
+/// Expected shape: [ <Condition>, "true" | "false" ]
+fn analyze_boolean_query(node: &TreeNode) -> SemanticResult {
+    analyze_string_field_query(node)?;
+
+    let value_node = &node.children[1];
+    let value_text = value_node.node_content.to_lowercase();
+    if value_text != "true" && value_text != "false" {
+        let err = invalid_context(
+            value_node.node_content.clone(),
+            "true/false value",
+            &["true", "false"],
+        );
+        return Err((err, get_span(value_node)));
+    }
+
+    Ok(())
+}
+
 /// Validate integer literals.
+///
+/// The token must be a number, and one that fits in 64 bits.
 fn analyze_integer(node: &TreeNode) -> SemanticResult {
     if let Some(tok) = node.lexical_token {
         if *tok.get_token_type() != TokenType::Integer {
@@ -430,10 +651,41 @@ fn analyze_integer(node: &TreeNode) -> SemanticResult {
             );
             return Err((err, vec![(tok.get_start(), tok.get_end())]));
         }
+        if parse_integer_literal(&node.node_content).is_none() {
+            let err = invalid_context(
+                node.node_content.clone(),
+                "integer literal (too large)",
+                &["<number>"],
+            );
+            return Err((err, vec![(tok.get_start(), tok.get_end())]));
+        }
     }
     Ok(())
 }
 
+/// Read an integer literal, allowing _ digit separators
+///
+/// Parameters:
+/// --- ---
+/// lexeme -> The literal as written (e.g., "30" or "1_000")
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Option<i64> -> The number, None if the lexeme isn't digits grouped by single underscores
+///                or doesn't fit in an i64
+/// --- ---
+///
+pub fn parse_integer_literal(lexeme: &str) -> Option<i64> {
+    let well_formed = lexeme
+        .split('_')
+        .all(|group| !group.is_empty() && group.chars().all(|c| c.is_ascii_digit()));
+    if !well_formed {
+        return None;
+    }
+    lexeme.replace('_', "").parse().ok()
+}
+
 /// Validate time literals.
 ///
 /// Ensures the time token is correct and includes am/pm suffix.
@@ -462,3 +714,188 @@ fn analyze_time(node: &TreeNode) -> SemanticResult {
 
     Ok(())
 }
+
+/// Validate date literals.
+///
+/// The token must be a date, and a real one (no "feb 30").
+fn analyze_date(node: &TreeNode) -> SemanticResult {
+    let is_date = node
+        .lexical_token
+        .is_none_or(|tok| *tok.get_token_type() == TokenType::Date);
+    if !is_date || parse_date_phrase(&node.node_content).is_none() {
+        let err = invalid_context(
+            node.node_content.clone(),
+            "date (month and day, year optional)",
+            &["oct 15", "10/15", "2024-10-15"],
+        );
+        return Err((err, get_span(node)));
+    }
+    Ok(())
+}
+
+/// Check exact values against the downloaded lookup lists
+///
+/// Runs after semantic_analysis once lookups are loaded. A subject, campus, instruction
+/// method, or meeting type compared with "is"/"equals" has to be one some class actually
+/// has, so a typo like "subject is CMTP" is reported with the closest real values instead
+/// of finding nothing.
+/// Fields whose list is empty (e.g., nothing synced yet) are not checked
+///
+/// Parameters:
+/// --- ---
+/// ast -> The analyzed AST
+/// lookups -> The loaded lookup lists
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<(), (SemanticError, Vec<(usize, usize)>)> -> Ok, or an UnknownValue error with
+///     the value's position
+/// --- ---
+pub fn validate_lookup_values(ast: &Ast, lookups: &Lookups) -> SemanticResult {
+    match &ast.head {
+        Some(root) => validate_node_values(root, lookups),
+        None => Ok(()),
+    }
+}
+
+/// Check a node's exact values, then its children's
+fn validate_node_values(node: &TreeNode, lookups: &Lookups) -> SemanticResult {
+    let checked = matches!(
+        node.node_type,
+        NodeType::SubjectQuery
+            | NodeType::CampusQuery
+            | NodeType::InstructionMethodQuery
+            | NodeType::MeetingTypeQuery
+    );
+    if checked && !lookups.values_for(&node.node_type).is_empty() {
+        // each value of an "in" list is checked on its own
+        for (value, value_node) in equality_values(node) {
+            // a method is searched as the one its spelling stands for (e.g., "web" as "Online")
+            let searched = match node.node_type {
+                NodeType::InstructionMethodQuery => normalize_instruction_method(&value),
+                _ => value.clone(),
+            };
+            if !lookups.contains(&node.node_type, &searched) {
+                let field = entities::entity_for_node(&node.node_type)
+                    .map_or("that value", |info| info.name);
+                let err = SemanticError::UnknownValue {
+                    suggestions: lookups.closest(&node.node_type, &value, 3),
+                    value,
+                    field: field.to_string(),
+                };
+                return Err((err, get_span(value_node)));
+            }
+        }
+    }
+
+    for child in &node.children {
+        validate_node_values(child, lookups)?;
+    }
+    Ok(())
+}
+
+/// Replace campus aliases with the campuses they stand for
+///
+/// Runs before lookup validation and code generation, so "campus is main" is checked and
+/// searched as "campus is \"Poughkeepsie Main Campus\"" once "main" is defined in Settings.
+/// The value keeps its position in the query for error messages
+///
+/// Parameters:
+/// --- ---
+/// ast -> The analyzed AST, rewritten in place
+/// aliases -> The campus aliases defined in Settings
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// usize -> Number of aliases replaced
+/// --- ---
+pub fn resolve_campus_aliases(ast: &mut Ast, aliases: &CampusAliases) -> usize {
+    match ast.head.as_mut() {
+        Some(root) if !aliases.is_empty() => resolve_node_aliases(root, aliases),
+        _ => 0,
+    }
+}
+
+/// Replace a node's campus alias, then its children's
+fn resolve_node_aliases(node: &mut TreeNode, aliases: &CampusAliases) -> usize {
+    if node.node_type == NodeType::CampusQuery && node.children.len() == 2 {
+        let values = match node.children[1].node_type {
+            NodeType::StringList => node.children[1].children.iter_mut().collect(),
+            _ => vec![&mut node.children[1]],
+        };
+        let mut resolved = 0;
+        for value in values {
+            if let Some(campus) = aliases.resolve(value.node_content.trim_matches('"')) {
+                // quoted, so the campus is read back as one value whatever it contains
+                value.node_content =
+                    format!("\"{}\"", campus.replace('\\', "\\\\").replace('"', "\\\""));
+                resolved += 1;
+            }
+        }
+        return resolved;
+    }
+    node.children
+        .iter_mut()
+        .map(|child| resolve_node_aliases(child, aliases))
+        .sum()
+}
+
+/// Clause of the generated SQL a predicate belongs in
+///
+/// Variants:
+/// --- ---
+/// Where -> Depends only on the section, course, or professor, so it can filter joined rows
+/// Having -> Depends on a single meeting_times row, so it must be checked after the section's
+///     meetings are aggregated (filtering rows would drop meetings from the results)
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for Placement
+/// Clone -> Clone trait for Placement
+/// Copy -> Copy trait for Placement
+/// PartialEq -> PartialEq trait for Placement
+/// --- ---
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Placement {
+    Where,
+    Having,
+}
+
+/// Decide which clause a predicate belongs in
+///
+/// Entities searching the joined meeting_times row ("mt." columns, i.e., start, end, and
+/// meeting type) go in HAVING. Day entities use their own EXISTS subquery and stay in WHERE.
+/// Any other node belongs in HAVING if one of its children does
+///
+/// Parameters:
+/// --- ---
+/// node -> The AST node to place
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Placement -> The clause the node's SQL belongs in
+/// --- ---
+///
+pub fn predicate_placement(node: &TreeNode) -> Placement {
+    let entity = match node.node_type {
+        NodeType::FieldQuery => entities::entity_named(&node.node_content),
+        _ => entities::entity_for_node(&node.node_type),
+    };
+    match entity {
+        Some(info) if info.columns.iter().any(|c| c.starts_with("mt.")) => Placement::Having,
+        Some(_) => Placement::Where,
+        None if node
+            .children
+            .iter()
+            .any(|child| predicate_placement(child) == Placement::Having) =>
+        {
+            Placement::Having
+        }
+        None => Placement::Where,
+    }
+}
@@ -17,6 +17,7 @@
 ///      --- ---
 /// --- ---
 ///
+use crate::dsl::entities;
 
 /// Token types for the DSL
 ///
@@ -63,6 +64,13 @@ pub enum TokenType {
     Full,
     Start,
     End,
+    Meets,
+    Session,
+    Restricted,
+    Fee,
+
+    // fields declared with define_field!
+    Field,
 
     // days
     Monday,
@@ -97,6 +105,8 @@ pub enum TokenType {
     EqualsWord,
     DoesNotEqual,
     DoesNotContain,
+    In,
+    Matches,
 
     // binary operators
     Less,
@@ -108,16 +118,20 @@ pub enum TokenType {
     More,
     Fewer,
     To,
+    Between,
 
     // grouping
     LeftParen,
     RightParen,
+    Comma,
 
     // literals
     String,
     Alphanumeric,
     Integer,
     Time,
+    Date,
+    EmailAddress,
     Identifier,
 
     // special
@@ -162,11 +176,22 @@ impl TokenType {
     ///
     /// Returns:
     /// --- ---
-    /// Vec<(TokenType, &'static str)> -> All token patterns in lexing order (longest/most specific first)
+    /// Vec<(TokenType, String)> -> All token patterns in lexing order (longest/most specific first)
     /// --- ---
     ///
-    pub fn all_patterns() -> Vec<(TokenType, &'static str)> {
-        vec![
+    pub fn all_patterns() -> Vec<(TokenType, String)> {
+        let mut patterns: Vec<(TokenType, String)> = [
+            // email addresses - must come first, so an address starting with a keyword
+            // (e.g., "at@school.edu") isn't split into a keyword and stray characters
+            (
+                TokenType::EmailAddress,
+                r"[A-Za-z0-9._%+-]+@[A-Za-z0-9-]+(?:\.[A-Za-z0-9-]+)+",
+            ),
+            // dates - before keywords and numbers, so "oct 15" and "10/15" are one token
+            (
+                TokenType::Date,
+                r"(?i)[0-9]{4}-[0-9]{2}-[0-9]{2}\b|[0-9]{1,2}/[0-9]{1,2}(?:/[0-9]{2,4})?\b|\b(?:jan(?:uary)?|feb(?:ruary)?|mar(?:ch)?|apr(?:il)?|may|june?|july?|aug(?:ust)?|sept?(?:ember)?|oct(?:ober)?|nov(?:ember)?|dec(?:ember)?)\.?\s+[0-9]{1,2}(?:st|nd|rd|th)?(?:,?\s+[0-9]{4})?\b",
+            ),
             // multi-word operators - must come before individual words
             (
                 TokenType::DoesNotEqual,
@@ -191,50 +216,20 @@ impl TokenType {
             (TokenType::More, r"(?i)\bmore\b"),
             (TokenType::Fewer, r"(?i)\bfewer\b"),
             (TokenType::To, r"(?i)\bto\b"),
-            // days
-            (
-                TokenType::Wednesday,
-                r"(?i)\b(wednesday|wednesda|wednesd|wednes|wedne|wedn|wed|we|w)\b",
-            ),
-            (
-                TokenType::Thursday,
-                r"(?i)\b(thursday|thursda|thurs|thur|thu|th)\b",
-            ),
-            (
-                TokenType::Saturday,
-                r"(?i)\b(saturday|saturda|saturd|satur|satu|sat|sa)\b",
-            ),
-            (
-                TokenType::Tuesday,
-                r"(?i)\b(tuesday|tuesda|tuesd|tues|tue|tu)\b",
-            ),
-            (TokenType::Monday, r"(?i)\b(monday|monda|mond|mon|mo|m)\b"),
-            (TokenType::Friday, r"(?i)\b(friday|frida|frid|fri|fr|f)\b"),
-            (TokenType::Sunday, r"(?i)\b(sunday|sunda|sund|sun|su)\b"),
-            // keywords - these must come before the general identifier pattern
+            (TokenType::Between, r"(?i)\bbetween\b"),
+        ]
+        .into_iter()
+        .map(|(token_type, pattern)| (token_type, pattern.to_string()))
+        .collect();
+
+        // entity keywords (including days) come from the entity registry
+        patterns.extend(entities::keyword_patterns());
+
+        patterns.extend([
+            // keywords that don't introduce an entity - these must come before the general identifier pattern
             (TokenType::Contains, r"(?i)\bcontains\b"),
-            (TokenType::Prereqs, r"(?i)\b(?:prerequisites|prereqs)\b"),
-            (TokenType::Corereqs, r"(?i)\b(?:corequisites|corereqs)\b"),
-            (TokenType::Subject, r"(?i)\b(subject|sub)\b"),
-            (TokenType::Course, r"(?i)\bcourse\b"),
-            (TokenType::Method, r"(?i)\bmethod\b"),
-            (TokenType::Campus, r"(?i)\bcampus\b"),
-            (TokenType::Credit, r"(?i)\bcredit\b"),
-            (TokenType::Hours, r"(?i)\bhours\b"),
-            (TokenType::Title, r"(?i)\btitle\b"),
             (TokenType::Term, r"(?i)\bterm\b"),
-            (TokenType::Prof, r"(?i)\b(?:prof|professor)\b"),
-            (TokenType::Number, r"(?i)\bnumber\b"),
-            (TokenType::Description, r"(?i)\bdescription\b"),
-            (TokenType::Enrollment, r"(?i)\benrollment\b"),
-            (TokenType::Cap, r"(?i)\bcap\b"),
-            (TokenType::Size, r"(?i)\bsize\b"),
             (TokenType::Instruction, r"(?i)\binstruction\b"),
-            (TokenType::Meeting, r"(?i)\bmeeting\b"),
-            (TokenType::Type, r"(?i)\btype\b"),
-            (TokenType::Full, r"(?i)\bfull\b"),
-            (TokenType::Start, r"(?i)\bstart\b"),
-            (TokenType::End, r"(?i)\bend\b"),
             (TokenType::Email, r"(?i)\bemail\b"),
             // logical
             (TokenType::And, r"(?i)\band\b"),
@@ -244,6 +239,8 @@ impl TokenType {
             // conditions
             (TokenType::Has, r"(?i)\bhas\b"),
             (TokenType::Is, r"(?i)\bis\b"),
+            (TokenType::In, r"(?i)\bin\b"),
+            (TokenType::Matches, r"(?i)\bmatches\b"),
             // operators
             (TokenType::NotEquals, r"!="),
             (TokenType::LessEqual, r"<="),
@@ -254,21 +251,30 @@ impl TokenType {
             (TokenType::Exclamation, r"!"),
             (TokenType::LeftParen, r"\("),
             (TokenType::RightParen, r"\)"),
+            (TokenType::Comma, r","),
             // literals
-            (TokenType::String, r#""[^"]*""#),
-            (TokenType::UnclosedString, r#""[^"]*$"#),
+            // quoted strings may contain escaped quotes and backslashes (e.g., "say \"hi\"")
+            (TokenType::String, r#""(?:[^"\\]|\\.)*""#),
+            (TokenType::UnclosedString, r#""(?:[^"\\]|\\.)*\\?$"#),
             (
                 TokenType::Time,
                 r"[0-9]+:[0-9]+\s(?:am|pm)|[0-9]+:[0-9]+(?:am|pm)|[0-9]+:[0-9]+|[0-9]+\s(?:am|pm)|[0-9]+(?:am|pm)",
             ),
             // Alphanumeric course numbers (e.g., "424N", "101L") - must come before Integer
             (TokenType::Alphanumeric, r"[0-9]+[A-Za-z]+"),
-            (TokenType::Integer, r"[0-9]+"),
-            // general identifier pattern - must come last
-            (TokenType::Identifier, r"[a-zA-Z_][a-zA-Z0-9_]*"),
+            // digit separators group large numbers (e.g., "1_000")
+            (TokenType::Integer, r"[0-9]+(?:_[0-9]+)*"),
+            // general identifier pattern - must come last, apostrophes allowed inside words (e.g., O'Brien)
+            (
+                TokenType::Identifier,
+                r"[a-zA-Z_][a-zA-Z0-9_]*(?:'[a-zA-Z0-9_]+)*",
+            ),
             // unrecognized characters - must come last to catch anything else
             (TokenType::Unrecognized, r"[^\s]"),
         ]
+        .map(|(token_type, pattern)| (token_type, pattern.to_string())));
+
+        patterns
     }
 }
 
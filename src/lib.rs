@@ -2,3 +2,4 @@ pub mod data;
 pub mod debug_utils;
 pub mod dsl;
 pub mod tui;
+pub mod utils;
@@ -11,21 +11,53 @@
 /// Contains:
 /// --- ---
 /// Args -> CLI arguments struct
+/// Command -> CLI subcommands
+/// DbCommand -> Database management subcommands
+/// ScheduleCommand -> Saved schedule subcommands
+/// ProfileCommand -> Workspace profile subcommands
 /// main -> Main function
+/// run_dry_run_sync -> Preview a sync and apply it if confirmed
+/// run_db_command -> Run a database management subcommand
+/// run_schedule_command -> Run a saved schedule subcommand
+/// run_profile_command -> Run a workspace profile subcommand
+/// run_bench -> Benchmark the compiler and searches on synthetic data
+/// run_sample -> Fill a database with a sample catalog
 /// --- ---
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use dotenv::dotenv;
+use std::io::{self, Write};
+use std::path::PathBuf;
 
-use classql::debug_utils::visualizetree::ast_to_dot;
+use classql::data::archive;
+use classql::data::bench::{self, BenchConfig};
+use classql::data::maintenance::{self, format_bytes};
+use classql::data::profiles::Profile;
+use classql::data::sample::{self, SampleConfig};
+use classql::data::stats;
+use classql::data::sync::{self, SyncConfig};
+use classql::debug_utils::tokenstream::token_lines;
+use classql::debug_utils::visualizetree::{render_ast, TreeFormat};
 use classql::dsl::compiler::{Compiler, CompilerResult};
+use classql::dsl::lexer::Lexer;
+use classql::tui::error_catalog::diagnostic_header;
+use classql::tui::export::{export_lines, ScheduleCrns};
+use classql::tui::save;
+use classql::tui::share::{self, SharedSchedule};
 use classql::tui::TuiApp;
+use classql::utils::i18n;
+use classql::utils::text::render_caret;
 
 /// Args struct
 ///
 /// Fields:
 /// --- ---
 /// query -> The query string to compile and visualize the AST
+/// format -> Format to print the AST in (dot, mermaid, or tree)
+/// tree -> Print the AST as an ASCII tree, short for --format tree
+/// tokens -> Print the query's token stream instead of its AST
 /// sync -> Whether to sync class data from classy server
+/// dry_run -> Preview what a sync would change and ask before applying it
+/// command -> Optional subcommand to run instead of the TUI
 /// --- ---
 ///
 /// Implemented Traits:
@@ -48,8 +80,217 @@ struct Args {
     #[arg(short, long, value_name = "QUERY_STRING")]
     query: Option<String>,
 
+    #[arg(long, value_name = "FORMAT", default_value = "dot", requires = "query")]
+    format: TreeFormat,
+
+    #[arg(long, requires = "query", conflicts_with = "format")]
+    tree: bool,
+
+    #[arg(long, requires = "query", conflicts_with_all = ["format", "tree"])]
+    tokens: bool,
+
     #[arg(short, long)]
     sync: bool,
+
+    #[arg(long, requires = "sync")]
+    dry_run: bool,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+/// Command enum
+///
+/// Variants:
+/// --- ---
+/// Db -> Manage the local class database
+/// Schedule -> List, share, and import saved schedules
+/// Profile -> List, save, and delete workspace profiles
+/// Bench -> Benchmark the compiler and searches on a synthetic database
+/// Sample -> Fill a database with a synthetic sample catalog
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Subcommand -> Subcommand trait for Command
+/// Debug -> Debug trait for Command
+/// --- ---
+///
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Manage the local class database
+    Db {
+        #[command(subcommand)]
+        action: DbCommand,
+    },
+    /// List, share, and import saved schedules
+    Schedule {
+        #[command(subcommand)]
+        action: ScheduleCommand,
+    },
+    /// List, save, and delete workspace profiles (switched between from the main menu)
+    Profile {
+        #[command(subcommand)]
+        action: ProfileCommand,
+    },
+    /// Benchmark the compiler and searches on a synthetic database
+    Bench {
+        /// Number of sections to generate
+        #[arg(long, default_value_t = BenchConfig::default().sections)]
+        sections: usize,
+        /// How many times each query is timed
+        #[arg(long, default_value_t = BenchConfig::default().iterations)]
+        iterations: usize,
+        /// Seed for the generated data
+        #[arg(long, default_value_t = BenchConfig::default().seed)]
+        seed: u64,
+        /// Where to write the synthetic database (defaults to the system temp directory)
+        #[arg(long, value_name = "PATH")]
+        db: Option<PathBuf>,
+        /// Print the report as JSON (e.g., to save as a baseline)
+        #[arg(long)]
+        json: bool,
+        /// Fail if any stage's median is slower than in this saved JSON report
+        #[arg(long, value_name = "PATH")]
+        baseline: Option<PathBuf>,
+        /// Percent slowdown over the baseline that counts as a regression
+        #[arg(long, default_value_t = 20.0, requires = "baseline")]
+        threshold: f64,
+    },
+    /// Fill a database with a synthetic sample catalog (for demos and testing)
+    Sample {
+        /// Number of subjects courses are spread across (at most 16)
+        #[arg(long, default_value_t = SampleConfig::default().subjects)]
+        subjects: usize,
+        /// Number of sections to generate
+        #[arg(long, default_value_t = SampleConfig::default().sections)]
+        sections: usize,
+        /// Seed for the generated data
+        #[arg(long, default_value_t = SampleConfig::default().seed)]
+        seed: u64,
+        /// Where to write the database
+        #[arg(long, value_name = "PATH", default_value = "classy/sample.db")]
+        db: PathBuf,
+        /// Replace the database if it already exists
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+/// DbCommand enum
+///
+/// Variants:
+/// --- ---
+/// Archive -> Hide a term from selection and default queries
+/// Unarchive -> Restore an archived term
+/// Purge -> Delete archived terms' sections, keeping those in saved schedules
+/// Maintain -> Compact, check, and reindex the database
+/// Stats -> Show table counts, per-term sections, indexes, and recent syncs
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Subcommand -> Subcommand trait for DbCommand
+/// Debug -> Debug trait for DbCommand
+/// --- ---
+///
+#[derive(Subcommand, Debug)]
+enum DbCommand {
+    /// Hide a term from selection and default queries
+    Archive { school_id: String, term_id: String },
+    /// Restore an archived term
+    Unarchive { school_id: String, term_id: String },
+    /// Delete archived terms' sections to free space (saved schedules are kept)
+    Purge,
+    /// Compact (VACUUM), check integrity, and reindex the database
+    Maintain,
+    /// Show table counts, per-term sections, file size, index usage, and recent syncs
+    Stats,
+}
+
+/// ScheduleCommand enum
+///
+/// Variants:
+/// --- ---
+/// List -> List saved schedules with their timestamps
+/// Share -> Print a shareable token for a saved schedule
+/// Import -> Save the schedule in a shared token
+/// Export -> Print a saved schedule with each section's CRN for registration
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Subcommand -> Subcommand trait for ScheduleCommand
+/// Debug -> Debug trait for ScheduleCommand
+/// --- ---
+///
+#[derive(Subcommand, Debug)]
+enum ScheduleCommand {
+    /// List saved schedules with their timestamps
+    List,
+    /// Print a token classmates can import to get the same schedule
+    Share {
+        /// Timestamp of the saved schedule (see `schedule list`)
+        timestamp: u64,
+    },
+    /// Save the schedule in a shared token (or a link or message containing one)
+    Import {
+        token: String,
+        /// Name to save the schedule under (defaults to the shared name)
+        #[arg(long)]
+        name: Option<String>,
+    },
+    /// Print a saved schedule with each section's CRN for registration
+    Export {
+        /// Timestamp of the saved schedule (see `schedule list`)
+        timestamp: u64,
+        /// Print only the CRNs, on one line to paste into the registrar's portal
+        #[arg(long)]
+        crns: bool,
+    },
+}
+
+/// ProfileCommand enum
+///
+/// Variants:
+/// --- ---
+/// List -> List workspace profiles
+/// Save -> Add a workspace profile, replacing one with the same name
+/// Delete -> Delete a workspace profile
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Subcommand -> Subcommand trait for ProfileCommand
+/// Debug -> Debug trait for ProfileCommand
+/// --- ---
+///
+#[derive(Subcommand, Debug)]
+enum ProfileCommand {
+    /// List workspace profiles
+    List,
+    /// Add a workspace profile, replacing one with the same name
+    Save {
+        /// Name of the profile, e.g., "CS major"
+        name: String,
+        /// School to select when switching to the profile
+        #[arg(long)]
+        school: Option<String>,
+        /// Term to select when switching to the profile
+        #[arg(long)]
+        term: Option<String>,
+        /// Favorite query, cycled through with Alt+F in the search box (repeatable)
+        #[arg(long = "query", value_name = "QUERY")]
+        queries: Vec<String>,
+        /// Condition every search also has to meet, e.g., "credits >= 3" (repeatable)
+        #[arg(long = "constraint", value_name = "CONDITION")]
+        constraints: Vec<String>,
+        /// Label describing the profile, e.g., "major" (repeatable)
+        #[arg(long = "tag", value_name = "TAG")]
+        tags: Vec<String>,
+    },
+    /// Delete a workspace profile
+    Delete { name: String },
 }
 
 /// Main function
@@ -68,19 +309,98 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // load environment variables from .env file
     dotenv().ok();
 
+    // pick the UI language from CLASSQL_LOCALE or LANG, falling back to English
+    i18n::init();
+
     // parse the cli arguments
     let args = Args::parse();
 
+    if let Some(Command::Db { action }) = args.command {
+        if let Err(e) = run_db_command(action) {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    if let Some(Command::Schedule { action }) = args.command {
+        if let Err(e) = run_schedule_command(action) {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    if let Some(Command::Profile { action }) = args.command {
+        if let Err(e) = run_profile_command(action) {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    if let Some(Command::Bench {
+        sections,
+        iterations,
+        seed,
+        db,
+        json,
+        baseline,
+        threshold,
+    }) = args.command
+    {
+        let config = BenchConfig {
+            sections,
+            iterations,
+            seed,
+        };
+        let db_path = db.unwrap_or_else(|| std::env::temp_dir().join("classql-bench.db"));
+        if let Err(e) = run_bench(&config, &db_path, json, baseline, threshold) {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    if let Some(Command::Sample {
+        subjects,
+        sections,
+        seed,
+        db,
+        force,
+    }) = args.command
+    {
+        let config = SampleConfig {
+            subjects,
+            sections,
+            seed,
+            ..SampleConfig::default()
+        };
+        if let Err(e) = run_sample(&config, &db, force) {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     // handle sync command
     if args.sync {
         let config = classql::data::sync::SyncConfig::from_env()
             .map_err(|e| format!("Failed to load sync config: {}", e))?;
 
+        if args.dry_run {
+            if let Err(e) = run_dry_run_sync(&config) {
+                eprintln!("Sync failed: {}", e);
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+
         println!(
             "Syncing class data from {}:{}...",
             config.server_url, config.server_port
         );
-        match classql::data::sync::sync_all(&config) {
+        match sync::sync_all(&config, &mut |message| eprintln!("{}", message)) {
             Ok(db_path) => {
                 println!("Successfully synced data to: {}", db_path.display());
             }
@@ -93,28 +413,62 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     if let Some(query) = args.query {
+        if args.tokens {
+            // list the tokens even when the query doesn't lex, then say why it doesn't parse
+            for line in token_lines(&query, &Lexer::new(query.clone()).tokenize()) {
+                println!("{}", line);
+            }
+            if let Err(error) = Compiler::new().parse(&query, &mut |_| {}) {
+                if let Some(diagnostic) = error.diagnostic(&query) {
+                    println!("{}", diagnostic);
+                }
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+
         // if a query is provided, compile it and visualize the AST
         let mut compiler = Compiler::new();
 
         // run the compiler and handle the result
         match compiler.run(&query) {
-            CompilerResult::Success { ast, .. } => {
-                println!("{}", ast_to_dot(query.to_string(), &ast))
+            CompilerResult::Success { ast, notices, .. } => {
+                for notice in &notices {
+                    eprintln!("note: {}", notice);
+                }
+                let format = if args.tree {
+                    TreeFormat::Tree
+                } else {
+                    args.format
+                };
+                println!("{}", render_ast(&query, &ast, format))
             }
-            CompilerResult::LexerError { message, .. } => {
-                println!("{}", message);
+            CompilerResult::LexerError {
+                code,
+                message,
+                problematic_positions,
+            } => {
+                let header = diagnostic_header(code, &query, &problematic_positions);
+                println!("{}: {}", header, message);
+                println!("{}", render_caret(&query, &problematic_positions, None));
                 std::process::exit(1);
             }
-            CompilerResult::ParserError { message, .. } => {
-                println!("{}", message);
-                std::process::exit(1);
+            CompilerResult::ParserError {
+                code,
+                message,
+                problematic_positions,
             }
-            CompilerResult::SemanticError { message, .. } => {
-                println!("{}", message);
+            | CompilerResult::SemanticError {
+                code,
+                message,
+                problematic_positions,
+            } => {
+                let header = diagnostic_header(code, &query, &problematic_positions);
+                println!("{}: {}", header, message);
                 std::process::exit(1);
             }
-            CompilerResult::CodeGenError { message } => {
-                println!("{}", message);
+            CompilerResult::CodeGenError { code, message } => {
+                println!("{}: {}", diagnostic_header(code, &query, &[]), message);
                 std::process::exit(1);
             }
         }
@@ -123,9 +477,404 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         let compiler = Compiler::new();
         let mut app = TuiApp::new(compiler)?;
 
+        // profiles that can't be read are skipped rather than keeping the TUI from starting
+        let profiles = save::load_profiles().unwrap_or_default();
+        app.set_profiles(profiles);
+
+        // start where the last session left off, unless turned off in Settings
+        let session = save::load_session().unwrap_or_default();
+        app.restore_session(&session);
+
         app.run()?;
         app.terminate()?;
+        // a session that can't be written only costs the next launch its starting point
+        let _ = save::save_session(&app.session());
+    }
+
+    Ok(())
+}
+
+/// Sync into a staging copy, show what would change, and apply it if confirmed
+///
+/// Parameters:
+/// --- ---
+/// config -> Sync configuration
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<(), String> -> Ok on success (whether or not the sync was applied), error message on failure
+/// --- ---
+///
+fn run_dry_run_sync(config: &SyncConfig) -> Result<(), String> {
+    println!(
+        "Fetching class data from {}:{} (dry run)...",
+        config.server_url, config.server_port
+    );
+    let preview = sync::preview_sync_all(config, &mut |message| eprintln!("{}", message))?;
+
+    if preview.diff.is_empty() {
+        println!("Local data is already up to date");
+        return preview.discard();
+    }
+
+    for term in &preview.diff.terms {
+        println!(
+            "  {} {:<10} +{} ~{} -{}",
+            term.school_id,
+            term.term_id,
+            term.new_sections,
+            term.updated_sections,
+            term.removed_sections
+        );
+    }
+    println!("{}", preview.diff.summary());
+
+    print!("Apply these changes? [y/N] ");
+    io::stdout()
+        .flush()
+        .map_err(|e| format!("Failed to write prompt: {}", e))?;
+    let mut answer = String::new();
+    io::stdin()
+        .read_line(&mut answer)
+        .map_err(|e| format!("Failed to read answer: {}", e))?;
+
+    if answer.trim().eq_ignore_ascii_case("y") {
+        let db_path = preview.commit()?;
+        println!("Successfully synced data to: {}", db_path.display());
+        Ok(())
+    } else {
+        println!("Sync discarded, local data unchanged");
+        preview.discard()
+    }
+}
+
+/// Run a database management subcommand
+///
+/// Parameters:
+/// --- ---
+/// action -> The subcommand to run
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<(), String> -> Ok on success, error message on failure
+/// --- ---
+///
+fn run_db_command(action: DbCommand) -> Result<(), String> {
+    let db_path = classql::data::sync::get_synced_db_path();
+    if !db_path.exists() {
+        return Err("No synced database found. Run `classql --sync` first.".to_string());
+    }
+
+    match action {
+        DbCommand::Archive { school_id, term_id } => {
+            archive::archive_term(&db_path, &school_id, &term_id)?;
+            println!("Archived term {} for {}", term_id, school_id);
+        }
+        DbCommand::Unarchive { school_id, term_id } => {
+            if archive::unarchive_term(&db_path, &school_id, &term_id)? {
+                println!("Restored term {} for {}", term_id, school_id);
+            } else {
+                println!("Term {} for {} was not archived", term_id, school_id);
+            }
+        }
+        DbCommand::Purge => {
+            let keep = classql::tui::save::saved_sections()?;
+            let report = archive::purge_archived_terms(&db_path, &keep)?;
+            println!(
+                "Removed {} sections and {} meeting times ({} kept for saved schedules)",
+                report.sections_removed, report.meetings_removed, report.sections_kept
+            );
+            println!("Reclaimed {}", format_bytes(report.bytes_reclaimed));
+        }
+        DbCommand::Maintain => {
+            println!("Maintaining {}...", db_path.display());
+            let report = maintenance::maintain_database(&db_path)?;
+            for problem in &report.integrity_problems {
+                eprintln!("integrity: {}", problem);
+            }
+            println!("{}", report.summary());
+            if !report.integrity_problems.is_empty() {
+                return Err("Database failed its integrity check, consider re-syncing".to_string());
+            }
+        }
+        DbCommand::Stats => {
+            println!("{}", db_path.display());
+            for line in stats::fetch_database_stats(&db_path)?.report_lines() {
+                println!("{}", line);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Run a saved schedule subcommand
+///
+/// Parameters:
+/// --- ---
+/// action -> The subcommand to run
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<(), String> -> Ok on success, error message on failure
+/// --- ---
+///
+fn run_schedule_command(action: ScheduleCommand) -> Result<(), String> {
+    match action {
+        ScheduleCommand::List => {
+            let schedules = save::load_all_schedules()?;
+            if schedules.is_empty() {
+                println!("No saved schedules");
+            }
+            for schedule in schedules {
+                println!(
+                    "{}  {} ({} classes, {} {})",
+                    schedule.timestamp,
+                    schedule.name,
+                    schedule.classes.len(),
+                    schedule.school_id.as_deref().unwrap_or("-"),
+                    schedule.term_id.as_deref().unwrap_or("-")
+                );
+            }
+        }
+        ScheduleCommand::Share { timestamp } => {
+            let schedule = find_saved_schedule(timestamp)?;
+            for issue in &schedule.issues {
+                eprintln!("note: {}", issue);
+            }
+            println!("{}", SharedSchedule::from_saved(&schedule).encode());
+        }
+        ScheduleCommand::Export { timestamp, crns } => {
+            let schedule = find_saved_schedule(timestamp)?;
+            for issue in &schedule.issues {
+                eprintln!("note: {}", issue);
+            }
+            if crns {
+                let schedule_crns = ScheduleCrns::from_classes(&schedule.classes);
+                for section in &schedule_crns.missing {
+                    eprintln!("note: {} has no CRN in the synced data", section);
+                }
+                println!("{}", schedule_crns.clipboard_text());
+            } else {
+                println!("{}", schedule.name);
+                for line in export_lines(&schedule.classes) {
+                    println!("{}", line);
+                }
+            }
+        }
+        ScheduleCommand::Import { token, name } => {
+            let report = share::import_schedule(&token, name.as_deref())?;
+            println!(
+                "Imported \"{}\" with {} classes",
+                report.name, report.imported
+            );
+            for class_id in &report.missing {
+                eprintln!("{} is not in the local database, skipped", class_id);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Run a workspace profile subcommand
+///
+/// Parameters:
+/// --- ---
+/// action -> The subcommand to run
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<(), String> -> Ok on success, error message on failure
+/// --- ---
+///
+fn run_profile_command(action: ProfileCommand) -> Result<(), String> {
+    let mut profiles = save::load_profiles()?;
+
+    match action {
+        ProfileCommand::List => {
+            if profiles.profiles.is_empty() {
+                println!("No profiles");
+            }
+            for profile in &profiles.profiles {
+                println!(
+                    "{} ({} {})",
+                    profile.summary(),
+                    profile.school_id.as_deref().unwrap_or("-"),
+                    profile.term_id.as_deref().unwrap_or("-")
+                );
+                for query in &profile.queries {
+                    println!("  favorite: {}", query);
+                }
+                for constraint in &profile.constraints {
+                    println!("  constraint: {}", constraint);
+                }
+            }
+        }
+        ProfileCommand::Save {
+            name,
+            school,
+            term,
+            queries,
+            constraints,
+            tags,
+        } => {
+            let name = name.trim().to_string();
+            if name.is_empty() {
+                return Err("A profile needs a name".to_string());
+            }
+            // a bad constraint would otherwise break every search under the profile
+            let mut compiler = Compiler::new();
+            for constraint in &constraints {
+                if !matches!(compiler.run(constraint), CompilerResult::Success { .. }) {
+                    return Err(format!(
+                        "Constraint \"{}\" is not a valid query",
+                        constraint
+                    ));
+                }
+            }
+            let profile = Profile {
+                name: name.clone(),
+                school_id: school,
+                term_id: term,
+                queries,
+                constraints,
+                tags,
+            };
+            let summary = profile.summary();
+            let replaced = profiles.upsert(profile);
+            save::save_profiles(&profiles)?;
+            let verb = if replaced { "Updated" } else { "Saved" };
+            println!("{} profile {}", verb, summary);
+        }
+        ProfileCommand::Delete { name } => {
+            if !profiles.remove(&name) {
+                return Err(format!("No profile named \"{}\"", name));
+            }
+            save::save_profiles(&profiles)?;
+            println!("Deleted profile \"{}\"", name);
+        }
+    }
+
+    Ok(())
+}
+
+/// Find a saved schedule by its timestamp
+///
+/// Parameters:
+/// --- ---
+/// timestamp -> Timestamp of the saved schedule (see `schedule list`)
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<SavedSchedule, String> -> The schedule, or an error if none has that timestamp
+/// --- ---
+///
+fn find_saved_schedule(timestamp: u64) -> Result<save::SavedSchedule, String> {
+    save::load_all_schedules()?
+        .into_iter()
+        .find(|schedule| schedule.timestamp == timestamp)
+        .ok_or_else(|| format!("No saved schedule with timestamp {}", timestamp))
+}
+
+/// Benchmark the compiler and searches on synthetic data
+///
+/// Parameters:
+/// --- ---
+/// config -> Benchmark settings
+/// db_path -> Where to write the synthetic database
+/// json -> Whether to print the report as JSON
+/// baseline -> Saved JSON report to compare medians against, if any
+/// threshold -> Percent slowdown over the baseline that counts as a regression
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<(), String> -> Ok if the run finished without regressions, error message otherwise
+/// --- ---
+///
+fn run_bench(
+    config: &BenchConfig,
+    db_path: &std::path::Path,
+    json: bool,
+    baseline: Option<PathBuf>,
+    threshold: f64,
+) -> Result<(), String> {
+    eprintln!(
+        "Generating {} sections in {}...",
+        config.sections,
+        db_path.display()
+    );
+    bench::generate_dataset(db_path, config.sections, config.seed)?;
+
+    eprintln!("Timing {} iterations...", config.iterations);
+    let report = bench::run_benchmarks(config, db_path)?;
+
+    if json {
+        println!("{:#}", report.to_json());
+    } else {
+        for line in report.report_lines() {
+            println!("{}", line);
+        }
+    }
+
+    if let Some(baseline_path) = baseline {
+        let content = std::fs::read_to_string(&baseline_path)
+            .map_err(|e| format!("Failed to read {}: {}", baseline_path.display(), e))?;
+        let baseline: serde_json::Value = serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse {}: {}", baseline_path.display(), e))?;
+
+        let regressions = report.regressions(&baseline, threshold);
+        if !regressions.is_empty() {
+            for regression in &regressions {
+                eprintln!("regression: {}", regression);
+            }
+            return Err(format!(
+                "{} stage(s) regressed more than {}% against {}",
+                regressions.len(),
+                threshold,
+                baseline_path.display()
+            ));
+        }
+        eprintln!("No stage regressed more than {}%", threshold);
+    }
+
+    Ok(())
+}
+
+/// Fill a database with a sample catalog
+///
+/// Parameters:
+/// --- ---
+/// config -> Catalog settings
+/// db_path -> Where to write the database
+/// force -> Whether an existing database may be replaced
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<(), String> -> Ok once the catalog is written, error message otherwise
+/// --- ---
+///
+fn run_sample(config: &SampleConfig, db_path: &std::path::Path, force: bool) -> Result<(), String> {
+    // never wipe real synced data by accident
+    if db_path.exists() && !force {
+        return Err(format!(
+            "{} already exists, pass --force to replace it",
+            db_path.display()
+        ));
+    }
+    if let Some(parent) = db_path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
     }
 
+    let summary = sample::generate_catalog(db_path, config)?;
+    println!("Generated {} in {}", summary.summary(), db_path.display());
     Ok(())
 }
@@ -3,29 +3,71 @@
 /// Refactored TUI application using widget pattern
 ///
 /// This demonstrates how to use the new widget structs for a cleaner architecture.
-/// Widgets encapsulate their own state and key handling.
+/// Widgets encapsulate their own state and key handling, and emit actions that the app
+/// applies in its update loop along with events from background tasks.
+use crate::data::archive::archive_term;
+use crate::data::catalog::{fetch_sections, CatalogScope};
+use crate::data::index_advisor::{create_indexes, explain_search};
+use crate::data::maintenance::maintain_database;
+use crate::data::profiles::Profiles;
+use crate::data::registrar_import::{match_pasted_rows, parse_pasted_rows, PasteMatch};
 use crate::data::sql::Class;
-use crate::data::sql::{fetch_schools, fetch_terms, get_last_sync_time, School};
+use crate::data::sql::{
+    execute_query, fetch_schools, fetch_term_calendar, fetch_terms, get_default_db_path,
+    get_last_sync_time, get_test_db_path, School,
+};
+use crate::data::stats::fetch_database_stats;
 use crate::data::sync::get_synced_db_path;
-use crate::dsl::compiler::Compiler;
+use crate::data::watch::{section_label, WatchEvent, WatchList};
+use crate::data::watch_hook::WatchHook;
+use crate::dsl::codegen::generate_scope_sql;
+use crate::dsl::compiler::{Compiler, CompilerResult, TraceEvent};
+use crate::dsl::entities::PLACEHOLDER;
+use crate::dsl::formatter::quote_string;
+use crate::dsl::lexer::Lexer;
 use crate::tui::errors::TUIError;
+use crate::tui::events::{paste_keys, AppEvent, EventQueue};
+use crate::tui::export::{copy_to_clipboard, ScheduleCrns};
+use crate::tui::notifier::{Notifier, NOTIFY_INTERVAL};
 use crate::tui::save::{self, SavedSchedule};
-use crate::tui::state::{ErrorType, FocusMode};
+use crate::tui::session::Session;
+use crate::tui::share::{SharedSchedule, SHARE_PREFIX};
+use crate::tui::state::{ErrorType, FocusMode, View};
+use crate::tui::widgets::settings::LAST_SETTINGS_INDEX;
 use crate::tui::widgets::{
-    DetailViewWidget, HelpBarWidget, KeyAction, LogoWidget, MainMenuWidget, QueryGuideWidget,
-    ScheduleAction, ScheduleWidget, SearchWidget, SettingsAction, SettingsWidget, ToastWidget,
-    Widget,
+    AstInspectorWidget, CatalogAction, CatalogWidget, CompareWidget, DetailViewWidget,
+    ExplainAction, ExplainWidget, FreeTimeWidget, HelpBarWidget, KeyAction, LogoWidget,
+    MainMenuWidget, MenuAction, ProfessorsAction, ProfessorsWidget, QueryGuideWidget,
+    ScheduleAction, ScheduleOverlay, ScheduleWidget, SearchWidget, SettingsAction, SettingsWidget,
+    StatsWidget, ToastWidget, Widget,
+};
+use crossterm::event::{
+    self, DisableBracketedPaste, EnableBracketedPaste, Event, KeyCode, KeyEvent, KeyModifiers,
 };
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers};
-use ratatui::layout::{Alignment, Rect};
+use crossterm::execute;
+use ratatui::backend::{Backend, CrosstermBackend};
+use ratatui::layout::{Alignment, Position, Rect};
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Borders, Clear, Paragraph};
-use ratatui::DefaultTerminal;
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+use ratatui::Terminal;
+use std::io::{stdout, Stdout};
+use std::path::PathBuf;
+use std::sync::mpsc::Sender;
+use std::thread;
 use std::time::{Duration, Instant};
 
+/// Most views kept on the back-stack, the oldest is dropped past it
+const VIEW_STACK_LIMIT: usize = 50;
+
+/// Lines of the pasted text shown in the paste import popup, the last ones pasted
+const PASTE_IMPORT_LINES: usize = 6;
+
 /// Refactored TUI application using widget pattern
 ///
+/// Generic over the terminal backend so tests can drive it with ratatui's TestBackend;
+/// the real application uses the crossterm backend
+///
 /// Fields:
 /// --- ---
 /// terminal -> The terminal instance for rendering
@@ -38,6 +80,13 @@ use std::time::{Duration, Instant};
 /// settings -> Settings widget
 /// schedule -> Schedule widget
 /// guide -> Query guide widget
+/// compare -> Side-by-side class comparison widget
+/// stats -> Database statistics widget
+/// explain -> Query plan and index advice widget
+/// ast_inspector -> Syntax tree and generated SQL widget
+/// free_time -> Free time finder widget
+/// catalog -> Course catalog browser widget
+/// professors -> Professor directory widget
 ///
 /// Shared state:
 /// toast_message -> Optional toast notification message
@@ -48,11 +97,24 @@ use std::time::{Duration, Instant};
 /// save_name_input -> Current save name input
 /// save_name_cursor_visible -> Whether save name cursor is visible
 /// save_name_last_blink -> Timestamp of last save name cursor blink
+/// save_name_conflict -> Saved schedule with the typed name, overwritten if Enter is pressed again
+/// renaming_timestamp -> Saved schedule being renamed (None when saving a new one)
+/// overlay_input -> Current overlay schedule input (token or saved schedule name)
+/// paste_import_input -> Schedule text pasted from the registrar
+/// paste_import_matches -> The pasted rows matched against the term's sections
 /// selected_class_for_details -> Class selected for detail view
-/// detail_return_focus -> Focus mode to return to after detail view
+/// view_stack -> Views left for the current one, most recent last, that Esc returns to
+/// compare_candidate -> Class marked as the first side of a comparison
+/// active_schedule_timestamp -> Saved schedule last opened from My Schedules
+/// watch_list -> Sections watched for seat changes
+/// notifier -> Rate-limited desktop notifications for watch-list events
+/// profiles -> Workspace profiles, switched between from the main menu
+/// pending_copy -> Text waiting to be copied to the clipboard by the event loop
+/// events -> Events sent by background tasks, applied on the next tick
+/// sync_retries -> Times the running sync found the database busy and retried
 /// --- ---
 ///
-pub struct TuiApp {
+pub struct TuiApp<B: Backend = CrosstermBackend<Stdout>> {
     pub main_menu: MainMenuWidget,
     pub search: SearchWidget,
     pub settings: SettingsWidget,
@@ -62,7 +124,14 @@ pub struct TuiApp {
     pub help_bar: HelpBarWidget,
     pub toast: ToastWidget,
     pub detail_view: DetailViewWidget,
-    terminal: DefaultTerminal,
+    pub compare: CompareWidget,
+    pub stats: StatsWidget,
+    pub explain: ExplainWidget,
+    pub ast_inspector: AstInspectorWidget,
+    pub free_time: FreeTimeWidget,
+    pub catalog: CatalogWidget,
+    pub professors: ProfessorsWidget,
+    terminal: Terminal<B>,
     compiler: Compiler,
     focus_mode: FocusMode,
     toast_message: Option<String>,
@@ -73,8 +142,21 @@ pub struct TuiApp {
     save_name_input: String,
     save_name_cursor_visible: bool,
     save_name_last_blink: Instant,
+    save_name_conflict: Option<u64>,
+    renaming_timestamp: Option<u64>,
+    overlay_input: String,
+    paste_import_input: String,
+    paste_import_matches: Vec<PasteMatch>,
     selected_class_for_details: Option<Class>,
-    detail_return_focus: FocusMode,
+    view_stack: Vec<View>,
+    compare_candidate: Option<Class>,
+    active_schedule_timestamp: Option<u64>,
+    watch_list: WatchList,
+    notifier: Notifier,
+    profiles: Profiles,
+    pending_copy: Option<String>,
+    events: EventQueue,
+    sync_retries: usize,
 }
 
 impl TuiApp {
@@ -92,8 +174,45 @@ impl TuiApp {
     ///
     pub fn new(compiler: Compiler) -> Result<Self, TUIError> {
         let terminal = ratatui::init();
+        // pastes arrive as one event instead of a key per character
+        execute!(stdout(), EnableBracketedPaste)
+            .map_err(|e| TUIError::TerminalError(e.to_string()))?;
+        Ok(Self::with_terminal(compiler, terminal))
+    }
+
+    /// Terminate the TUI gracefully
+    ///
+    /// Arguments: None
+    ///
+    /// Returns:
+    /// --- ---
+    /// Result<(), TUIError> -> Ok on success, error on failure
+    /// --- ---
+    ///
+    pub fn terminate(&self) -> Result<(), TUIError> {
+        let disabled = execute!(stdout(), DisableBracketedPaste)
+            .map_err(|e| TUIError::TerminalError(e.to_string()));
+        ratatui::restore();
+        disabled
+    }
+}
 
-        Ok(TuiApp {
+impl<B: Backend> TuiApp<B> {
+    /// Create a new TuiApp that renders to the given terminal
+    ///
+    /// Arguments:
+    /// --- ---
+    /// compiler -> The DSL compiler instance to use
+    /// terminal -> The terminal to draw to (e.g., one backed by ratatui's TestBackend)
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Self -> The new TuiApp instance, starting at the main menu
+    /// --- ---
+    ///
+    pub fn with_terminal(compiler: Compiler, terminal: Terminal<B>) -> Self {
+        let mut app = TuiApp {
             terminal,
             compiler,
             focus_mode: FocusMode::MainMenu,
@@ -108,6 +227,13 @@ impl TuiApp {
             help_bar: HelpBarWidget::new(),
             toast: ToastWidget::new(),
             detail_view: DetailViewWidget::new(),
+            compare: CompareWidget::new(),
+            stats: StatsWidget::new(),
+            explain: ExplainWidget::new(),
+            ast_inspector: AstInspectorWidget::new(),
+            catalog: CatalogWidget::new(),
+            professors: ProfessorsWidget::new(),
+            free_time: FreeTimeWidget::new(),
 
             // shared state
             toast_message: None,
@@ -118,9 +244,30 @@ impl TuiApp {
             save_name_input: String::new(),
             save_name_cursor_visible: true,
             save_name_last_blink: Instant::now(),
+            save_name_conflict: None,
+            renaming_timestamp: None,
+            overlay_input: String::new(),
+            paste_import_input: String::new(),
+            paste_import_matches: Vec::new(),
             selected_class_for_details: None,
-            detail_return_focus: FocusMode::ResultsBrowse,
-        })
+            view_stack: Vec::new(),
+            compare_candidate: None,
+            active_schedule_timestamp: None,
+            watch_list: save::load_watch_list().unwrap_or_default(),
+            notifier: Notifier::new(NOTIFY_INTERVAL),
+            profiles: Profiles::default(),
+            pending_copy: None,
+            events: EventQueue::new(),
+            sync_retries: 0,
+        };
+        app.refresh_next_up();
+        let campus_aliases = save::load_campus_aliases().unwrap_or_default();
+        app.compiler.set_campus_aliases(campus_aliases.clone());
+        app.settings.set_campus_aliases(campus_aliases);
+        // completion and validation use the lookup lists once they finish loading
+        app.compiler.warm_up_lookups();
+
+        app
     }
 
     /// Run the TUI event loop
@@ -136,36 +283,322 @@ impl TuiApp {
     ///
     pub fn run(&mut self) -> Result<(), Box<dyn std::error::Error>> {
         loop {
-            // update timers
-            self.update_toast();
-            self.search.update_cursor_blink();
-            self.update_save_name_cursor();
-
-            // sync widget state
-            self.main_menu.set_cart_empty(self.schedule.is_cart_empty());
+            if !self.update(AppEvent::Tick) {
+                break Ok(());
+            }
 
             // draw the current state
             self.draw()?;
 
+            // the copy sequence goes to the real terminal, after the frame it was asked in
+            if let Some(text) = self.pending_copy.take() {
+                if let Err(e) = copy_to_clipboard(&text) {
+                    self.show_toast(e, ErrorType::Warning);
+                }
+            }
+
             // handle input events
             if crossterm::event::poll(Duration::from_millis(100))? {
-                if let Event::Key(key) = event::read()? {
-                    match self.handle_key(key) {
-                        KeyAction::Exit => break Ok(()),
-                        KeyAction::Continue => {}
-                        KeyAction::Navigate(mode) => self.navigate_to(mode),
-                        KeyAction::ShowToast {
-                            message,
-                            error_type,
-                        } => {
-                            self.show_toast(message, error_type);
-                        }
+                let event = match event::read()? {
+                    Event::Key(key) => AppEvent::Key(key),
+                    Event::Paste(text) => AppEvent::Paste(text),
+                    Event::Resize(width, height) => AppEvent::Resize(width, height),
+                    _ => continue,
+                };
+                if !self.update(event) {
+                    break Ok(());
+                }
+            }
+        }
+    }
+
+    /// Apply an event to the app's state
+    ///
+    /// Arguments:
+    /// --- ---
+    /// event -> The key, widget action, tick, or background task result to apply
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// bool -> false if the event asked the application to exit
+    /// --- ---
+    ///
+    /// This is the one place state changes: keys go to the focused widget, and the action it
+    /// emits is applied as its own event. If the focus moved, the widgets losing and gaining
+    /// it are told once the event has been applied
+    ///
+    pub fn update(&mut self, event: AppEvent) -> bool {
+        let previous_focus = self.focus_mode.clone();
+        let keep_running = self.apply_event(event);
+        if self.focus_mode != previous_focus {
+            self.change_focus(&previous_focus);
+        }
+        keep_running
+    }
+
+    /// Apply one event, without the focus hooks
+    ///
+    /// Arguments:
+    /// --- ---
+    /// event -> The event to apply
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// bool -> false if the event asked the application to exit
+    /// --- ---
+    ///
+    fn apply_event(&mut self, event: AppEvent) -> bool {
+        match event {
+            AppEvent::Key(key) => {
+                let action = self.handle_key(key);
+                return self.apply_event(AppEvent::Action(action));
+            }
+            AppEvent::Paste(text) => {
+                if self.focus_mode == FocusMode::PasteImport {
+                    self.paste_import_input.push_str(&text);
+                    self.match_paste_import();
+                    return true;
+                }
+                // elsewhere a paste types its text, as it does without bracketed paste
+                for key in paste_keys(&text) {
+                    if !self.apply_event(AppEvent::Key(key)) {
+                        return false;
+                    }
+                }
+            }
+            AppEvent::Action(action) => match action {
+                KeyAction::Exit => return false,
+                KeyAction::Continue => {}
+                KeyAction::Navigate(mode) => self.navigate_to(mode),
+                KeyAction::Back => self.navigate_back(),
+                KeyAction::ShowToast {
+                    message,
+                    error_type,
+                } => {
+                    self.show_toast(message, error_type);
+                }
+            },
+            AppEvent::Tick => {
+                self.tick();
+                // background tasks' events, in the order they were sent
+                while let Some(event) = self.events.poll() {
+                    if !self.apply_event(event) {
+                        return false;
                     }
                 }
             }
+            AppEvent::Resize(width, height) => {
+                let area = Rect::new(0, 0, width, height);
+                for widget in self.widgets_mut() {
+                    widget.on_resize(area);
+                }
+            }
+            AppEvent::SyncRetried(message) => {
+                self.sync_retries += 1;
+                self.show_toast(message, ErrorType::Info);
+            }
+            AppEvent::SyncFinished(result) => self.finish_sync(result),
+            AppEvent::WatchHooksFailed(failures) => {
+                self.show_toast(failures.join("\n"), ErrorType::Warning);
+            }
+        }
+        true
+    }
+
+    /// Advance timers and sync widget state, once per pass of the event loop
+    ///
+    /// Arguments: None
+    ///
+    /// Returns: None
+    ///
+    pub fn tick(&mut self) {
+        // update timers
+        self.update_toast();
+        self.update_save_name_cursor();
+        self.notifier.flush();
+        if self.focus_mode == FocusMode::QueryInput && self.search_filters_ready() {
+            self.search.update_live_preview(&self.compiler);
+        }
+        if self.focus_mode == FocusMode::QueryInput {
+            self.search.rotate_ghost_example();
+        }
+
+        // sync widget state
+        self.main_menu.set_cart_empty(self.schedule.is_cart_empty());
+    }
+
+    /// Handle a key event and apply the resulting action
+    ///
+    /// Arguments:
+    /// --- ---
+    /// key -> The key event to handle
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// bool -> false if the key asked the application to exit
+    /// --- ---
+    ///
+    pub fn send_key(&mut self, key: KeyEvent) -> bool {
+        self.update(AppEvent::Key(key))
+    }
+
+    /// Get where the user is, to restore on the next launch
+    ///
+    /// Arguments: None
+    ///
+    /// Returns:
+    /// --- ---
+    /// Session -> The screen, query, list positions, school, term, and profile, with the
+    ///            Settings choice of whether to restore them
+    /// --- ---
+    ///
+    pub fn session(&self) -> Session {
+        Session {
+            restore: self.settings.restore_session,
+            focus: self.focus_mode.clone(),
+            query: self.search.input.clone(),
+            school_id: self.settings.selected_school_id.clone(),
+            term_id: self.settings.selected_term_id.clone(),
+            selected_result: self.search.selected_result,
+            results_scroll: self.search.results_scroll,
+            settings_index: self.settings.selected_index,
+            saved_schedule_index: self.selected_saved_schedule_index,
+            profile: self.profiles.active.clone(),
+        }
+    }
+
+    /// Set the workspace profiles that can be switched to from the main menu
+    ///
+    /// Arguments:
+    /// --- ---
+    /// profiles -> The profiles (see save::load_profiles), with the one in use if any
+    /// --- ---
+    ///
+    /// Returns: None
+    ///
+    pub fn set_profiles(&mut self, profiles: Profiles) {
+        self.profiles = profiles;
+        self.apply_profile();
+    }
+
+    /// Start where a previous session left off
+    ///
+    /// Arguments:
+    /// --- ---
+    /// session -> The session saved when the TUI last exited
+    /// --- ---
+    ///
+    /// Returns: None
+    ///
+    /// Does nothing beyond keeping the Settings choice if restoring is turned off. A school
+    /// or term that is no longer synced isn't selected, and results are found by running
+    /// the query again, so they reflect the current data
+    ///
+    pub fn restore_session(&mut self, session: &Session) {
+        self.settings.restore_session = session.restore;
+        if !session.restore {
+            return;
+        }
+
+        // the session's own school and term win over the profile's
+        if let Some(profile) = session
+            .profile
+            .as_deref()
+            .and_then(|name| self.profiles.find(name))
+        {
+            self.profiles.active = Some(profile.name.clone());
+            self.apply_profile();
+        }
+        self.select_school_and_term(session.school_id.as_deref(), session.term_id.as_deref());
+        self.settings.selected_index = session.settings_index.min(LAST_SETTINGS_INDEX);
+
+        self.search.input = session.query.clone();
+        let mut focus = session.restored_focus();
+        if focus == FocusMode::ResultsBrowse {
+            if !self.search_filters_ready() {
+                focus = FocusMode::QueryInput;
+            } else if let Some(KeyAction::ShowToast {
+                message,
+                error_type,
+            }) = self.search.execute_query(&mut self.compiler)
+            {
+                // the query no longer compiles, e.g., a subject was dropped by a sync
+                self.show_toast(message, error_type);
+                focus = FocusMode::QueryInput;
+            } else if self.search.query_results.is_empty() {
+                focus = FocusMode::QueryInput;
+            } else {
+                let last = self.search.query_results.len() - 1;
+                self.search.selected_result = session.selected_result.min(last);
+                self.search.results_scroll =
+                    session.results_scroll.min(self.search.selected_result);
+            }
+        }
+
+        // through the update loop, so the widget restored to is focused like any other
+        self.update(AppEvent::Action(KeyAction::Navigate(focus)));
+        if self.focus_mode == FocusMode::MySchedules {
+            self.selected_saved_schedule_index = session
+                .saved_schedule_index
+                .min(self.saved_schedules.len().saturating_sub(1));
         }
     }
 
+    /// Get the current focus mode
+    ///
+    /// Arguments: None
+    ///
+    /// Returns:
+    /// --- ---
+    /// &FocusMode -> The focus mode that receives key events
+    /// --- ---
+    ///
+    pub fn focus_mode(&self) -> &FocusMode {
+        &self.focus_mode
+    }
+
+    /// Get the toast message currently shown, if any
+    ///
+    /// Arguments: None
+    ///
+    /// Returns:
+    /// --- ---
+    /// Option<&str> -> The toast message
+    /// --- ---
+    ///
+    pub fn toast_message(&self) -> Option<&str> {
+        self.toast_message.as_deref()
+    }
+
+    /// Get the terminal the app draws to
+    ///
+    /// Arguments: None
+    ///
+    /// Returns:
+    /// --- ---
+    /// &Terminal<B> -> The terminal, whose backend holds the last drawn frame
+    /// --- ---
+    ///
+    pub fn terminal(&self) -> &Terminal<B> {
+        &self.terminal
+    }
+
+    /// Get a sender for events from a background task
+    ///
+    /// Arguments: None
+    ///
+    /// Returns:
+    /// --- ---
+    /// Sender<AppEvent> -> Sends events that are applied on the next tick
+    /// --- ---
+    ///
+    pub fn event_sender(&self) -> Sender<AppEvent> {
+        self.events.sender()
+    }
+
     /// Handle a key event based on current focus mode
     ///
     /// Arguments:
@@ -180,7 +613,26 @@ impl TuiApp {
     ///
     fn handle_key(&mut self, key: KeyEvent) -> KeyAction {
         match self.focus_mode {
-            FocusMode::MainMenu => self.main_menu.handle_key(key),
+            FocusMode::MainMenu => {
+                let (action, menu_action) = self.main_menu.handle_key_with_action(key);
+
+                match menu_action {
+                    MenuAction::OpenRecentClass(class) => {
+                        self.selected_class_for_details = Some(*class);
+                    }
+                    MenuAction::SwitchProfile => return self.switch_profile(),
+                    MenuAction::None => {}
+                }
+                match action {
+                    KeyAction::Navigate(FocusMode::CatalogBrowse) => return self.open_catalog(),
+                    KeyAction::Navigate(FocusMode::ProfessorDirectory) => {
+                        return self.open_professors(None)
+                    }
+                    _ => {}
+                }
+
+                action
+            }
 
             FocusMode::QueryInput | FocusMode::ResultsBrowse => {
                 // handle special keys that need app-level coordination
@@ -189,6 +641,21 @@ impl TuiApp {
                     && self.search.is_query_input()
                     && !self.search.completion.show_completions
                 {
+                    // a snippet's blanks would be searched for as typed
+                    if self
+                        .search
+                        .snippet
+                        .as_ref()
+                        .is_some_and(|snippet| snippet.has_unfilled())
+                    {
+                        return KeyAction::ShowToast {
+                            message: format!(
+                                "Fill in the {} placeholders first (Tab moves between them)",
+                                PLACEHOLDER
+                            ),
+                            error_type: ErrorType::Info,
+                        };
+                    }
                     // check if school/term are selected first
                     if self.settings.selected_school_id.is_none() {
                         return KeyAction::ShowToast {
@@ -204,6 +671,10 @@ impl TuiApp {
                             error_type: ErrorType::Warning,
                         };
                     }
+                    // a search that would scan a huge scope asks for a second Enter
+                    if let Some(action) = self.search.check_cost(&self.compiler) {
+                        return action;
+                    }
                     // show "Searching..." indicator before executing query
                     self.search.is_searching = true;
 
@@ -217,10 +688,71 @@ impl TuiApp {
                     if let Some(action) = result {
                         return action;
                     }
+                    // the search ran, but say what it assumed to run it and whether the
+                    // result cap cut it off
+                    let mut messages = self.search.last_notices.clone();
+                    if let Some(shown) = self.search.last_truncated {
+                        messages.push(format!(
+                            "Showing the first {} results: results truncated, refine your query",
+                            shown
+                        ));
+                    }
+                    if !messages.is_empty() {
+                        let error_type = if self.search.last_truncated.is_some() {
+                            ErrorType::Warning
+                        } else {
+                            ErrorType::Info
+                        };
+                        return KeyAction::ShowToast {
+                            message: messages.join("\n"),
+                            error_type,
+                        };
+                    }
+                    return KeyAction::Continue;
+                }
+
+                // mark the selected result for comparison
+                if matches!(key.code, KeyCode::Char('c') | KeyCode::Char('C'))
+                    && key.modifiers.contains(KeyModifiers::ALT)
+                    && !self.search.is_query_input()
+                {
+                    if let Some(class) = self.search.selected_class().cloned() {
+                        return self.mark_for_compare(class);
+                    }
                     return KeyAction::Continue;
                 }
 
-                if key.code == KeyCode::Tab && !self.search.completion.show_completions {
+                // show the query plan and index advice for the last search
+                if matches!(key.code, KeyCode::Char('x') | KeyCode::Char('X'))
+                    && key.modifiers.contains(KeyModifiers::ALT)
+                {
+                    return match &self.search.last_explanation {
+                        Some(explanation) => {
+                            self.explain.open(explanation);
+                            KeyAction::Navigate(FocusMode::QueryExplain)
+                        }
+                        None => KeyAction::ShowToast {
+                            message: "Run a search first to see its query plan".to_string(),
+                            error_type: ErrorType::Info,
+                        },
+                    };
+                }
+
+                // show how the current query is parsed, next to the SQL it generates
+                if matches!(key.code, KeyCode::Char('a') | KeyCode::Char('A'))
+                    && key.modifiers.contains(KeyModifiers::ALT)
+                {
+                    return self.open_ast_inspector();
+                }
+
+                if key.code == KeyCode::Tab
+                    && !self.search.completion.show_completions
+                    && self.search.snippet.is_none()
+                {
+                    // an empty box takes the example query it suggests
+                    if self.search.is_query_input() && self.search.accept_ghost_example() {
+                        return KeyAction::Continue;
+                    }
                     // handle tab completion
                     if let Some(hint) = self.search.handle_tab_completion(&mut self.compiler) {
                         return KeyAction::ShowToast {
@@ -231,7 +763,12 @@ impl TuiApp {
                     return KeyAction::Continue;
                 }
 
+                let filter_before = self.search.result_filter.clone();
                 let action = self.search.handle_key(key);
+                if self.search.result_filter != filter_before {
+                    self.search
+                        .apply_result_filter(self.compiler.term_index().as_deref());
+                }
 
                 // sync the app's focus_mode with the search widget's internal focus
                 self.focus_mode = self.search.current_focus_mode();
@@ -240,7 +777,6 @@ impl TuiApp {
                 if matches!(&action, KeyAction::Navigate(FocusMode::DetailView)) {
                     if let Some(class) = self.search.selected_class() {
                         self.selected_class_for_details = Some(class.clone());
-                        self.detail_return_focus = self.search.current_focus_mode();
                     }
                 }
 
@@ -257,23 +793,107 @@ impl TuiApp {
                         school_name,
                     } => {
                         self.compiler.set_school_id(Some(school_id.clone()));
+                        self.compiler.warm_up_lookups();
                         self.load_terms(&school_id);
                         self.schedule.clear();
-                        self.search.query_results.clear();
+                        self.search.clear_results();
                         self.show_toast(format!("Selected: {}", school_name), ErrorType::Success);
                     }
                     SettingsAction::TermSelected { term_id, term_name } => {
                         self.compiler.set_term_id(Some(term_id));
+                        self.compiler.warm_up_lookups();
                         self.schedule.clear();
-                        self.search.query_results.clear();
+                        self.search.clear_results();
                         self.show_toast(format!("Selected: {}", term_name), ErrorType::Success);
                     }
+                    SettingsAction::TermArchived {
+                        school_id,
+                        term_id,
+                        term_name,
+                    } => {
+                        match archive_term(&get_synced_db_path(), &school_id, &term_id) {
+                            Ok(()) => {
+                                // the archived term may have been the active one
+                                if self.settings.selected_term_id.is_none() {
+                                    self.compiler.set_term_id(None);
+                                }
+                                self.load_terms(&school_id);
+                                self.show_toast(
+                                    format!("Archived: {}", term_name),
+                                    ErrorType::Success,
+                                );
+                            }
+                            Err(e) => {
+                                self.show_toast(
+                                    format!("Failed to archive term: {}", e),
+                                    ErrorType::Warning,
+                                );
+                            }
+                        }
+                    }
                     SettingsAction::ThemeChanged(_theme) => {
                         // theme is stored in settings widget
                     }
+                    SettingsAction::LivePreviewToggled(enabled) => {
+                        self.search.set_live_preview(enabled);
+                    }
+                    SettingsAction::NotificationsToggled(enabled) => {
+                        self.notifier.set_enabled(enabled);
+                    }
+                    SettingsAction::RestoreSessionToggled(_enabled) => {
+                        // read from the settings widget when the session is saved on exit
+                    }
+                    SettingsAction::ScheduleGridChanged {
+                        start_hour,
+                        end_hour,
+                        slot_minutes,
+                    } => {
+                        self.schedule.set_grid(start_hour, end_hour, slot_minutes);
+                    }
+                    SettingsAction::ResultOrderChanged(order) => {
+                        // applies from the next search
+                        self.compiler.set_result_order(order);
+                    }
+                    SettingsAction::MaxRowsChanged(max_rows) => {
+                        // applies from the next search
+                        self.compiler.set_max_rows(max_rows);
+                    }
+                    SettingsAction::MaintenanceRequested => {
+                        match maintain_database(&get_synced_db_path()) {
+                            Ok(report) => {
+                                let error_type = if report.integrity_problems.is_empty() {
+                                    ErrorType::Success
+                                } else {
+                                    ErrorType::Warning
+                                };
+                                self.show_toast(report.summary(), error_type);
+                            }
+                            Err(e) => {
+                                self.show_toast(
+                                    format!("Maintenance failed: {}", e),
+                                    ErrorType::Warning,
+                                );
+                            }
+                        }
+                    }
+                    SettingsAction::StatsRequested => {
+                        self.stats
+                            .set_stats(fetch_database_stats(&get_synced_db_path()));
+                        return KeyAction::Navigate(FocusMode::DatabaseStats);
+                    }
+                    SettingsAction::CampusAliasesChanged(aliases) => {
+                        if let Err(e) = save::save_campus_aliases(&aliases) {
+                            self.show_toast(
+                                format!("Failed to save campus aliases: {}", e),
+                                ErrorType::Warning,
+                            );
+                        }
+                        // applies from the next search
+                        self.compiler.set_campus_aliases(aliases);
+                    }
                     SettingsAction::SyncRequested => {
                         self.show_toast("Starting sync...".to_string(), ErrorType::Info);
-                        self.perform_sync();
+                        self.start_sync();
                     }
                     SettingsAction::None => {}
                 }
@@ -287,11 +907,36 @@ impl TuiApp {
                 match schedule_action {
                     ScheduleAction::OpenDetailView(class) => {
                         self.selected_class_for_details = Some(class);
-                        self.detail_return_focus = FocusMode::ScheduleCreation;
                     }
                     ScheduleAction::SaveSchedule => {
                         // will navigate to SaveNameInput
                         self.save_name_input.clear();
+                        self.save_name_conflict = None;
+                        self.renaming_timestamp = None;
+                    }
+                    ScheduleAction::MarkForCompare(class) => {
+                        return self.mark_for_compare(class);
+                    }
+                    ScheduleAction::OverlaySchedule => {
+                        // will navigate to OverlayInput
+                        self.overlay_input.clear();
+                    }
+                    ScheduleAction::FindFreeTime(schedule) => {
+                        let name = self.schedule.current_saved_schedule_name.clone();
+                        self.free_time.open(schedule, name);
+                    }
+                    ScheduleAction::CopyCrns(schedule) => {
+                        let crns = ScheduleCrns::from_classes(&schedule);
+                        if crns.crns.is_empty() {
+                            self.show_toast(
+                                "No CRNs in this schedule, the synced data doesn't have them"
+                                    .to_string(),
+                                ErrorType::Warning,
+                            );
+                        } else {
+                            self.pending_copy = Some(crns.clipboard_text());
+                            self.show_toast(crns.summary(), ErrorType::Success);
+                        }
                     }
                     _ => {}
                 }
@@ -307,32 +952,119 @@ impl TuiApp {
 
             FocusMode::SaveNameInput => self.handle_save_name_key(key),
 
-            FocusMode::Help => {
-                // help is handled by QueryGuide
-                self.guide.handle_key(key)
+            FocusMode::OverlayInput => self.handle_overlay_input_key(key),
+
+            FocusMode::PasteImport => self.handle_paste_import_key(key),
+
+            FocusMode::CompareView => self.compare.handle_key(key),
+
+            FocusMode::DatabaseStats => self.stats.handle_key(key),
+
+            FocusMode::AstInspector => self.ast_inspector.handle_key(key),
+
+            FocusMode::FreeTime => self.free_time.handle_key(key),
+
+            FocusMode::CatalogBrowse => {
+                let (action, catalog_action) = self.catalog.handle_key_with_action(key);
+
+                match catalog_action {
+                    CatalogAction::OpenDetails(class) => {
+                        self.selected_class_for_details = Some(*class);
+                    }
+                    CatalogAction::AddToCart(class) => return self.add_section_to_cart(*class),
+                    CatalogAction::None => {}
+                }
+
+                action
             }
-        }
-    }
 
-    /// Navigate to a new focus mode with necessary setup
-    ///
-    /// Arguments:
-    /// --- ---
+            FocusMode::ProfessorDirectory => {
+                let (action, professors_action) = self.professors.handle_key_with_action(key);
+
+                match professors_action {
+                    ProfessorsAction::OpenDetails(class) => {
+                        self.selected_class_for_details = Some(*class);
+                    }
+                    ProfessorsAction::AddToCart(class) => return self.add_section_to_cart(*class),
+                    ProfessorsAction::None => {}
+                }
+
+                action
+            }
+
+            FocusMode::QueryExplain => {
+                let (action, explain_action) = self.explain.handle_key_with_action(key);
+
+                if let ExplainAction::CreateIndexes(advice) = explain_action {
+                    let db_path = self.compiler.db_path();
+                    match create_indexes(&db_path, &advice) {
+                        Ok(created) => {
+                            // re-explain so the view shows the plan with the new indexes
+                            if let Some(explanation) = self.search.last_explanation.as_mut() {
+                                if let Ok(updated) = explain_search(&db_path, &explanation.sql) {
+                                    *explanation = updated;
+                                }
+                                self.explain.open(explanation);
+                            }
+                            self.show_toast(
+                                format!("Created {} index(es)", created),
+                                ErrorType::Success,
+                            );
+                        }
+                        Err(e) => {
+                            self.show_toast(
+                                format!("Failed to create indexes: {}", e),
+                                ErrorType::Warning,
+                            );
+                        }
+                    }
+                }
+
+                action
+            }
+
+            FocusMode::Help => {
+                // help is handled by QueryGuide
+                self.guide.handle_key(key)
+            }
+        }
+    }
+
+    /// Navigate to a new focus mode with necessary setup
+    ///
+    /// The view being left goes on the back-stack, so Esc returns to it. The main menu is
+    /// the root and clears the stack, and moving between the query box and the results
+    /// stays in the same view
+    ///
+    /// Arguments:
+    /// --- ---
     /// mode -> The focus mode to navigate to
     /// --- ---
     ///
     /// Returns: None
     ///
     fn navigate_to(&mut self, mode: FocusMode) {
+        let searching =
+            |focus: &FocusMode| matches!(focus, FocusMode::QueryInput | FocusMode::ResultsBrowse);
+        if mode == FocusMode::MainMenu {
+            self.view_stack.clear();
+            self.search.forget_places();
+        } else if mode != self.focus_mode && !(searching(&mode) && searching(&self.focus_mode)) {
+            self.push_view();
+        }
+
         match mode {
             FocusMode::Settings => {
                 self.load_school_data();
             }
             FocusMode::ScheduleCreation => {
-                // only enter creation mode if NOT coming from DetailView, SaveNameInput, or MySchedules
-                // to preserve state when returning from overlays or viewing saved schedules
+                // only enter creation mode if NOT coming from DetailView, SaveNameInput,
+                // OverlayInput, FreeTime, or MySchedules to preserve state when returning from
+                // overlays or viewing saved schedules
                 if self.focus_mode != FocusMode::DetailView
                     && self.focus_mode != FocusMode::SaveNameInput
+                    && self.focus_mode != FocusMode::OverlayInput
+                    && self.focus_mode != FocusMode::FreeTime
                     && self.focus_mode != FocusMode::MySchedules
                     && !self.schedule.is_cart_empty()
                 {
@@ -346,19 +1078,178 @@ impl TuiApp {
                 }
             }
             FocusMode::QueryGuide => {
-                self.guide.open(self.focus_mode.clone());
-            }
-            FocusMode::QueryInput => {
-                self.search.set_focus(FocusMode::QueryInput);
+                self.guide.open();
             }
-            FocusMode::ResultsBrowse => {
-                self.search.set_focus(FocusMode::ResultsBrowse);
+            FocusMode::DetailView => {
+                if let Some(ref class) = self.selected_class_for_details {
+                    self.main_menu.push_recent_class(class);
+                }
             }
             _ => {}
         }
         self.focus_mode = mode;
     }
 
+    /// Put the current view on the back-stack
+    ///
+    /// Arguments: None
+    ///
+    /// Returns: None
+    ///
+    fn push_view(&mut self) {
+        if self.view_stack.len() == VIEW_STACK_LIMIT {
+            self.view_stack.remove(0);
+        }
+        let class = if self.focus_mode == FocusMode::DetailView {
+            self.selected_class_for_details.clone()
+        } else {
+            None
+        };
+        self.view_stack.push(View {
+            focus: self.focus_mode.clone(),
+            class,
+            search_depth: self.search.back_depth(),
+        });
+    }
+
+    /// Return to the previous view on the back-stack, the main menu if there is none
+    ///
+    /// The view comes back as it was left: a detail view shows its class again, and search
+    /// pivots made since are undone
+    ///
+    /// Arguments: None
+    ///
+    /// Returns: None
+    ///
+    fn navigate_back(&mut self) {
+        let Some(view) = self.view_stack.pop() else {
+            self.navigate_to(FocusMode::MainMenu);
+            return;
+        };
+        while self.search.back_depth() > view.search_depth && self.search.go_back() {}
+        if view.class.is_some() {
+            self.selected_class_for_details = view.class;
+        }
+        self.focus_mode = view.focus;
+    }
+
+    /// Get the view Esc returns to, which is drawn under overlays like the detail view
+    ///
+    /// Arguments: None
+    ///
+    /// Returns:
+    /// --- ---
+    /// FocusMode -> Focus mode of the previous view, the main menu if there is none
+    /// --- ---
+    ///
+    fn previous_view(&self) -> FocusMode {
+        self.view_stack
+            .last()
+            .map_or(FocusMode::MainMenu, |view| view.focus.clone())
+    }
+
+    /// Get every widget, to find the one with focus
+    ///
+    /// Arguments: None
+    ///
+    /// Returns:
+    /// --- ---
+    /// [&dyn Widget; 16] -> The widgets, in the order of their fields
+    /// --- ---
+    ///
+    fn widgets(&self) -> [&dyn Widget; 16] {
+        [
+            &self.main_menu,
+            &self.search,
+            &self.settings,
+            &self.schedule,
+            &self.guide,
+            &self.logo,
+            &self.help_bar,
+            &self.toast,
+            &self.detail_view,
+            &self.compare,
+            &self.stats,
+            &self.explain,
+            &self.ast_inspector,
+            &self.free_time,
+            &self.catalog,
+            &self.professors,
+        ]
+    }
+
+    /// Get every widget mutably, in the same order as widgets
+    ///
+    /// Arguments: None
+    ///
+    /// Returns:
+    /// --- ---
+    /// [&mut dyn Widget; 16] -> The widgets, in the order of their fields
+    /// --- ---
+    ///
+    fn widgets_mut(&mut self) -> [&mut dyn Widget; 16] {
+        [
+            &mut self.main_menu,
+            &mut self.search,
+            &mut self.settings,
+            &mut self.schedule,
+            &mut self.guide,
+            &mut self.logo,
+            &mut self.help_bar,
+            &mut self.toast,
+            &mut self.detail_view,
+            &mut self.compare,
+            &mut self.stats,
+            &mut self.explain,
+            &mut self.ast_inspector,
+            &mut self.free_time,
+            &mut self.catalog,
+            &mut self.professors,
+        ]
+    }
+
+    /// Find the widget that handles a focus mode
+    ///
+    /// Arguments:
+    /// --- ---
+    /// mode -> The focus mode
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Option<usize> -> Index of the widget in widgets, None for views the app draws itself
+    /// --- ---
+    ///
+    fn widget_index(&self, mode: &FocusMode) -> Option<usize> {
+        self.widgets()
+            .iter()
+            .position(|widget| widget.focus_modes().contains(mode))
+    }
+
+    /// Tell the widgets that the focus moved
+    ///
+    /// Arguments:
+    /// --- ---
+    /// previous_focus -> Focus mode before the last event
+    /// --- ---
+    ///
+    /// Returns: None
+    ///
+    /// A widget handling both focus modes (e.g., the search's query box and results) isn't
+    /// blurred, it only gets on_focus for the new mode
+    ///
+    fn change_focus(&mut self, previous_focus: &FocusMode) {
+        let focus = self.focus_mode.clone();
+        let previous = self.widget_index(previous_focus);
+        let current = self.widget_index(&focus);
+        if let Some(index) = previous.filter(|&index| Some(index) != current) {
+            self.widgets_mut()[index].on_blur();
+        }
+        if let Some(index) = current {
+            self.widgets_mut()[index].on_focus(&focus);
+        }
+    }
+
     /// Draw the current frame
     ///
     /// Arguments: None
@@ -368,17 +1259,23 @@ impl TuiApp {
     /// Result<(), Box<dyn std::error::Error>> -> Ok on success, error on failure
     /// --- ---
     ///
-    fn draw(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+    pub fn draw(&mut self) -> Result<(), Box<dyn std::error::Error>> {
         // extract all values needed for rendering to avoid borrow conflicts
         let theme = self.settings.current_theme.to_theme();
         let focus_mode = self.focus_mode.clone();
-        let detail_return_focus = self.detail_return_focus.clone();
+        let previous_view = self.previous_view();
         let toast_message = self.toast_message.clone();
         let error_type = self.error_type.clone();
 
         // track values to update after rendering
         let mut new_guide_max_scroll = self.guide.max_scroll;
 
+        // the focused widget places the terminal's cursor, hidden unless it takes text
+        let frame_area = Rect::from((Position::ORIGIN, self.terminal.size()?));
+        let cursor = self
+            .widget_index(&focus_mode)
+            .and_then(|index| self.widgets()[index].cursor_position(frame_area));
+
         let terminal = &mut self.terminal;
         terminal.draw(|frame| {
             // clear with background color
@@ -413,10 +1310,19 @@ impl TuiApp {
                 }
                 FocusMode::DetailView => {
                     // render background based on return focus
-                    match detail_return_focus {
+                    match previous_view {
                         FocusMode::ScheduleCreation | FocusMode::MySchedules => {
                             self.schedule.render(frame, &theme);
                         }
+                        FocusMode::MainMenu => {
+                            self.main_menu.render(frame, &theme);
+                        }
+                        FocusMode::CatalogBrowse => {
+                            self.catalog.render(frame, &theme);
+                        }
+                        FocusMode::ProfessorDirectory => {
+                            self.professors.render(frame, &theme);
+                        }
                         _ => {
                             self.search.render(frame, &theme);
                         }
@@ -424,13 +1330,44 @@ impl TuiApp {
                     // render detail view overlay
                     if let Some(ref class) = self.selected_class_for_details {
                         let in_cart = self.schedule.cart_classes.contains_key(&class.unique_id());
-                        let show_cart_option = detail_return_focus != FocusMode::ScheduleCreation;
+                        let show_cart_option = previous_view != FocusMode::ScheduleCreation;
                         self.detail_view.class = Some(class.clone());
                         self.detail_view.is_in_cart = in_cart;
                         self.detail_view.show_cart_option = show_cart_option;
                         self.detail_view.render(frame, &theme);
                     }
                 }
+                FocusMode::CompareView => {
+                    // render the view the comparison was started from underneath
+                    if previous_view == FocusMode::ScheduleCreation {
+                        self.schedule.render(frame, &theme);
+                    } else {
+                        self.search.render(frame, &theme);
+                    }
+                    self.compare.render(frame, &theme);
+                }
+                FocusMode::DatabaseStats => {
+                    self.stats.render(frame, &theme);
+                }
+                FocusMode::FreeTime => {
+                    self.free_time.render(frame, &theme);
+                }
+                FocusMode::QueryExplain => {
+                    self.search.render(frame, &theme);
+                    self.explain.render(frame, &theme);
+                }
+                FocusMode::AstInspector => {
+                    self.search.render(frame, &theme);
+                    self.ast_inspector.render(frame, &theme);
+                }
+                FocusMode::CatalogBrowse => {
+                    self.catalog.in_cart = self.schedule.cart_classes.keys().cloned().collect();
+                    self.catalog.render(frame, &theme);
+                }
+                FocusMode::ProfessorDirectory => {
+                    self.professors.in_cart = self.schedule.cart_classes.keys().cloned().collect();
+                    self.professors.render(frame, &theme);
+                }
                 FocusMode::MySchedules => {
                     let width = 50_u16.min(frame.area().width.saturating_sub(4));
                     let height = 15_u16.min(frame.area().height.saturating_sub(20));
@@ -461,14 +1398,36 @@ impl TuiApp {
                             } else {
                                 Style::default().fg(theme.text_color)
                             };
-                            lines.push(Line::from(vec![
+                            let mut spans = vec![
                                 Span::styled(prefix, style),
                                 Span::styled(&schedule.name, style),
-                            ]));
+                            ];
+                            if !schedule.issues.is_empty() {
+                                spans.push(Span::styled(
+                                    " ⚠ changed",
+                                    Style::default().fg(theme.warning_color),
+                                ));
+                            }
+                            lines.push(Line::from(spans));
+                        }
+
+                        // explain what changed in the selected schedule since it was saved
+                        if let Some(selected) =
+                            self.saved_schedules.get(self.selected_saved_schedule_index)
+                        {
+                            if !selected.issues.is_empty() {
+                                lines.push(Line::from(""));
+                                for issue in &selected.issues {
+                                    lines.push(Line::from(Span::styled(
+                                        format!("• {}", issue),
+                                        Style::default().fg(theme.warning_color),
+                                    )));
+                                }
+                            }
                         }
                     }
 
-                    let para = Paragraph::new(lines).block(
+                    let para = Paragraph::new(lines).wrap(Wrap { trim: true }).block(
                         Block::default()
                             .borders(Borders::ALL)
                             .title(" My Schedules ")
@@ -482,10 +1441,17 @@ impl TuiApp {
                     frame.render_widget(para, area);
                 }
                 FocusMode::SaveNameInput => {
-                    self.schedule.render(frame, &theme);
+                    // renaming from My Schedules has no schedule behind the popup
+                    if self.renaming_timestamp.is_none() {
+                        self.schedule.render(frame, &theme);
+                    }
 
-                    let width = 40_u16;
-                    let height = 5_u16;
+                    let width = 48_u16.min(frame.area().width);
+                    let height = if self.save_name_conflict.is_some() {
+                        6_u16
+                    } else {
+                        5_u16
+                    };
                     let x = (frame.area().width.saturating_sub(width)) / 2;
                     let y = (frame.area().height.saturating_sub(height)) / 2;
                     let area = Rect {
@@ -507,20 +1473,178 @@ impl TuiApp {
                         Span::styled(cursor, Style::default().fg(theme.selected_color)),
                     ]);
 
-                    let para = Paragraph::new(vec![Line::from(""), input_line])
-                        .alignment(Alignment::Center)
-                        .block(
-                            Block::default()
-                                .borders(Borders::ALL)
-                                .title(" Save Schedule ")
-                                .title_style(
-                                    Style::default()
-                                        .fg(theme.title_color)
-                                        .add_modifier(Modifier::BOLD),
-                                )
-                                .border_style(Style::default().fg(theme.border_color))
-                                .style(Style::default().bg(theme.background_color)),
-                        );
+                    let mut lines = vec![Line::from(""), input_line];
+                    if self.save_name_conflict.is_some() {
+                        lines.push(Line::from(Span::styled(
+                            "Already exists, Enter again to overwrite",
+                            Style::default().fg(theme.warning_color),
+                        )));
+                    }
+                    let title = if self.renaming_timestamp.is_some() {
+                        " Rename Schedule "
+                    } else {
+                        " Save Schedule "
+                    };
+
+                    let para = Paragraph::new(lines).alignment(Alignment::Center).block(
+                        Block::default()
+                            .borders(Borders::ALL)
+                            .title(title)
+                            .title_style(
+                                Style::default()
+                                    .fg(theme.title_color)
+                                    .add_modifier(Modifier::BOLD),
+                            )
+                            .border_style(Style::default().fg(theme.border_color))
+                            .style(Style::default().bg(theme.background_color)),
+                    );
+                    frame.render_widget(para, area);
+                }
+                FocusMode::OverlayInput => {
+                    self.schedule.render(frame, &theme);
+
+                    let width = 60_u16.min(frame.area().width);
+                    let height = 6_u16;
+                    let x = (frame.area().width.saturating_sub(width)) / 2;
+                    let y = (frame.area().height.saturating_sub(height)) / 2;
+                    let area = Rect {
+                        x,
+                        y,
+                        width,
+                        height,
+                    };
+
+                    frame.render_widget(Clear, area);
+
+                    let cursor = if self.save_name_cursor_visible {
+                        "│"
+                    } else {
+                        " "
+                    };
+                    // tokens are long, so keep the end of the input (and the cursor) in view
+                    let visible_width = width.saturating_sub(3) as usize;
+                    let input_chars = self.overlay_input.chars().count();
+                    let visible_input: String = self
+                        .overlay_input
+                        .chars()
+                        .skip(input_chars.saturating_sub(visible_width))
+                        .collect();
+                    let input_line = Line::from(vec![
+                        Span::styled(visible_input, Style::default().fg(theme.text_color)),
+                        Span::styled(cursor, Style::default().fg(theme.selected_color)),
+                    ]);
+
+                    let para = Paragraph::new(vec![
+                        Line::from(Span::styled(
+                            "Paste a schedule token or type a saved schedule's name",
+                            Style::default().fg(theme.muted_color),
+                        )),
+                        Line::from(""),
+                        input_line,
+                    ])
+                    .alignment(Alignment::Center)
+                    .block(
+                        Block::default()
+                            .borders(Borders::ALL)
+                            .title(" Overlay Schedule ")
+                            .title_style(
+                                Style::default()
+                                    .fg(theme.title_color)
+                                    .add_modifier(Modifier::BOLD),
+                            )
+                            .border_style(Style::default().fg(theme.border_color))
+                            .style(Style::default().bg(theme.background_color)),
+                    );
+                    frame.render_widget(para, area);
+                }
+                FocusMode::PasteImport => {
+                    let width = 90_u16.min(frame.area().width.saturating_sub(4));
+                    let height = 24_u16.min(frame.area().height.saturating_sub(4));
+                    let x = (frame.area().width.saturating_sub(width)) / 2;
+                    let y = (frame.area().height.saturating_sub(height)) / 2;
+                    let area = Rect {
+                        x,
+                        y,
+                        width,
+                        height,
+                    };
+
+                    frame.render_widget(Clear, area);
+
+                    let cursor = if self.save_name_cursor_visible {
+                        "│"
+                    } else {
+                        " "
+                    };
+                    let mut lines = vec![
+                        Line::from(Span::styled(
+                            "Paste the rows of your schedule (CRN, course, section, days, times)",
+                            Style::default().fg(theme.muted_color),
+                        )),
+                        Line::from(""),
+                    ];
+                    // keep the end of the paste (and the cursor) in view
+                    let pasted: Vec<String> = self
+                        .paste_import_input
+                        .split('\n')
+                        .map(|line| line.replace('\t', "  "))
+                        .collect();
+                    let shown = pasted.len().saturating_sub(PASTE_IMPORT_LINES);
+                    for (i, line) in pasted.iter().enumerate().skip(shown) {
+                        let mut spans = vec![Span::styled(
+                            line.clone(),
+                            Style::default().fg(theme.text_color),
+                        )];
+                        if i + 1 == pasted.len() {
+                            spans.push(Span::styled(
+                                cursor,
+                                Style::default().fg(theme.selected_color),
+                            ));
+                        }
+                        lines.push(Line::from(spans));
+                    }
+                    lines.push(Line::from(""));
+
+                    for paste_match in &self.paste_import_matches {
+                        let color = if paste_match.is_confident() {
+                            theme.success_color
+                        } else if paste_match.class.is_some() {
+                            theme.warning_color
+                        } else {
+                            theme.error_color
+                        };
+                        let section = paste_match
+                            .class
+                            .as_ref()
+                            .map_or("-".to_string(), Class::unique_id);
+                        lines.push(Line::from(vec![
+                            Span::styled(
+                                format!("{:>3}% ", paste_match.confidence),
+                                Style::default().fg(color).add_modifier(Modifier::BOLD),
+                            ),
+                            Span::styled(
+                                format!("{} → {} ", paste_match.row.label(), section),
+                                Style::default().fg(theme.text_color),
+                            ),
+                            Span::styled(
+                                format!("({})", paste_match.reason),
+                                Style::default().fg(theme.muted_color),
+                            ),
+                        ]));
+                    }
+
+                    let para = Paragraph::new(lines).block(
+                        Block::default()
+                            .borders(Borders::ALL)
+                            .title(" Import from Registrar ")
+                            .title_style(
+                                Style::default()
+                                    .fg(theme.title_color)
+                                    .add_modifier(Modifier::BOLD),
+                            )
+                            .border_style(Style::default().fg(theme.border_color))
+                            .style(Style::default().bg(theme.background_color)),
+                    );
                     frame.render_widget(para, area);
                 }
             }
@@ -529,11 +1653,16 @@ impl TuiApp {
             self.help_bar.toast_message = toast_message.clone();
             self.help_bar.focus_mode = focus_mode.clone();
             self.help_bar.schedule_selection_mode = Some(self.schedule.schedule_selection_mode);
+            self.help_bar.professor_sections = self.professors.in_drill_down();
             self.help_bar.render(frame, &theme);
 
             self.toast.toast_message = toast_message.clone();
             self.toast.error_type = error_type.clone();
             self.toast.render(frame, &theme);
+
+            if let Some(position) = cursor {
+                frame.set_cursor_position(position);
+            }
         })?;
 
         // update values after render
@@ -556,50 +1685,179 @@ impl TuiApp {
     ///
     fn handle_detail_view_key(&mut self, key: KeyEvent) -> KeyAction {
         match key.code {
-            KeyCode::Esc | KeyCode::Backspace | KeyCode::Enter => {
-                let return_to = if self.detail_return_focus == FocusMode::MySchedules {
-                    FocusMode::ScheduleCreation
-                } else {
-                    self.detail_return_focus.clone()
-                };
-                KeyAction::Navigate(return_to)
-            }
+            KeyCode::Esc | KeyCode::Backspace | KeyCode::Enter => KeyAction::Back,
             KeyCode::Char('g') | KeyCode::Char('G')
                 if key.modifiers.contains(KeyModifiers::ALT) =>
             {
-                self.guide.open(FocusMode::DetailView);
                 KeyAction::Navigate(FocusMode::QueryGuide)
             }
             KeyCode::Char('c') | KeyCode::Char('C') => {
-                if self.detail_return_focus != FocusMode::ScheduleCreation {
+                if self.previous_view() != FocusMode::ScheduleCreation {
                     if let Some(ref class) = self.selected_class_for_details {
                         self.schedule.toggle_cart(class);
+                        // adding is still allowed, but a final at the same time is worth a warning
+                        let conflicts = self.schedule.final_conflicts_with(class);
+                        if self.schedule.cart_classes.contains_key(&class.unique_id())
+                            && !conflicts.is_empty()
+                        {
+                            return KeyAction::ShowToast {
+                                message: format!(
+                                    "Final exam conflicts with {}",
+                                    conflicts.join(", ")
+                                ),
+                                error_type: ErrorType::Warning,
+                            };
+                        }
                     }
                 }
                 KeyAction::Continue
             }
+            KeyCode::Char('p') | KeyCode::Char('P') => {
+                let Some(class) = self.selected_class_for_details.clone() else {
+                    return KeyAction::Continue;
+                };
+                self.open_professors(Some(&class))
+            }
+            KeyCode::Char('s') | KeyCode::Char('S') => {
+                let Some(class) = self.selected_class_for_details.clone() else {
+                    return KeyAction::Continue;
+                };
+                self.open_course_sections(&class)
+            }
+            KeyCode::Char('m') | KeyCode::Char('M') => {
+                let Some(class) = self.selected_class_for_details.clone() else {
+                    return KeyAction::Continue;
+                };
+                self.search_professor(&class)
+            }
+            KeyCode::Char('w') | KeyCode::Char('W') => {
+                let Some(ref class) = self.selected_class_for_details else {
+                    return KeyAction::Continue;
+                };
+                let watching = self.watch_list.toggle(
+                    class,
+                    self.settings.selected_school_id.as_deref(),
+                    self.settings.selected_term_id.as_deref(),
+                );
+                let label = section_label(class);
+                if let Err(e) = save::save_watch_list(&self.watch_list) {
+                    return KeyAction::ShowToast {
+                        message: format!("Failed to save watch list: {}", e),
+                        error_type: ErrorType::Warning,
+                    };
+                }
+                let message = if watching {
+                    format!("Watching {} for seat changes after each sync", label)
+                } else {
+                    format!("Stopped watching {}", label)
+                };
+                KeyAction::ShowToast {
+                    message,
+                    error_type: ErrorType::Info,
+                }
+            }
             _ => KeyAction::Continue,
         }
     }
 
-    /// Handle my schedules view key events
+    /// Open the AST inspector for the query in the search box
+    ///
+    /// Arguments: None
+    ///
+    /// Returns:
+    /// --- ---
+    /// KeyAction -> Navigation to the inspector, or a toast if there is no query
+    /// --- ---
+    ///
+    fn open_ast_inspector(&mut self) -> KeyAction {
+        let query = self.search.constrained_input();
+        if query.trim().is_empty() {
+            return KeyAction::ShowToast {
+                message: "Type a query to see how it is parsed".to_string(),
+                error_type: ErrorType::Info,
+            };
+        }
+        let tokens = Lexer::new(query.clone()).tokenize();
+        let diagnostic = |error: Box<CompilerResult>| {
+            error
+                .diagnostic(&query)
+                .unwrap_or_else(|| "SQL could not be generated".to_string())
+        };
+        let mut trace = Vec::new();
+        let mut record = |event: &TraceEvent| trace.push(event.clone());
+        match self.compiler.parse(&query, &mut record) {
+            Ok(ast) => {
+                // a query that parses but fails its checks still has a tree worth showing
+                let sql = self
+                    .compiler
+                    .generate(&ast, &mut record)
+                    .map_err(diagnostic);
+                self.ast_inspector
+                    .open(&query, Some(&ast), tokens, sql, trace);
+            }
+            // one that doesn't parse still has its tokens
+            Err(error) => {
+                self.ast_inspector
+                    .open(&query, None, tokens, Err(diagnostic(error)), trace)
+            }
+        }
+        KeyAction::Navigate(FocusMode::AstInspector)
+    }
+
+    /// Mark a class for comparison, opening the compare view once two are marked
     ///
     /// Arguments:
     /// --- ---
-    /// key -> The key event to handle
+    /// class -> The class to mark
     /// --- ---
     ///
     /// Returns:
     /// --- ---
-    /// KeyAction -> The action to take in response to the key
+    /// KeyAction -> Toast for the first mark, navigation to the compare view for the second
     /// --- ---
     ///
-    fn handle_my_schedules_key(&mut self, key: KeyEvent) -> KeyAction {
-        match key.code {
-            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => KeyAction::Exit,
-            KeyCode::Esc => KeyAction::Navigate(FocusMode::MainMenu),
-            KeyCode::Up => {
-                if self.selected_saved_schedule_index > 0 {
+    fn mark_for_compare(&mut self, class: Class) -> KeyAction {
+        match self.compare_candidate.take() {
+            Some(first) if first.unique_id() != class.unique_id() => {
+                self.compare.open(first, class);
+                KeyAction::Navigate(FocusMode::CompareView)
+            }
+            Some(_) => KeyAction::ShowToast {
+                message: "Comparison cleared".to_string(),
+                error_type: ErrorType::Info,
+            },
+            None => {
+                let message = format!(
+                    "Marked {} {}-{} for comparison. Alt+C another class to compare.",
+                    class.subject_code, class.course_number, class.section_sequence
+                );
+                self.compare_candidate = Some(class);
+                KeyAction::ShowToast {
+                    message,
+                    error_type: ErrorType::Info,
+                }
+            }
+        }
+    }
+
+    /// Handle my schedules view key events
+    ///
+    /// Arguments:
+    /// --- ---
+    /// key -> The key event to handle
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// KeyAction -> The action to take in response to the key
+    /// --- ---
+    ///
+    fn handle_my_schedules_key(&mut self, key: KeyEvent) -> KeyAction {
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => KeyAction::Exit,
+            KeyCode::Esc => KeyAction::Back,
+            KeyCode::Up => {
+                if self.selected_saved_schedule_index > 0 {
                     self.selected_saved_schedule_index -= 1;
                 }
                 KeyAction::Continue
@@ -629,11 +1887,26 @@ impl TuiApp {
                         all_names,
                         self.selected_saved_schedule_index,
                     );
+                    self.active_schedule_timestamp =
+                        Some(self.saved_schedules[self.selected_saved_schedule_index].timestamp);
+                    self.refresh_next_up();
                     KeyAction::Navigate(FocusMode::ScheduleCreation)
                 } else {
                     KeyAction::Continue
                 }
             }
+            KeyCode::Char('g') | KeyCode::Char('G') => self.regenerate_saved_schedule(),
+            KeyCode::Char('p') | KeyCode::Char('P') => self.open_paste_import(),
+            KeyCode::Char('r') | KeyCode::Char('R') => {
+                if let Some(saved) = self.saved_schedules.get(self.selected_saved_schedule_index) {
+                    // reuse the save name popup, starting from the current name
+                    self.save_name_input = saved.name.clone();
+                    self.save_name_conflict = None;
+                    self.renaming_timestamp = Some(saved.timestamp);
+                    return KeyAction::Navigate(FocusMode::SaveNameInput);
+                }
+                KeyAction::Continue
+            }
             KeyCode::Char('d') | KeyCode::Char('D') => {
                 if self.selected_saved_schedule_index < self.saved_schedules.len() {
                     let saved = &self.saved_schedules[self.selected_saved_schedule_index];
@@ -647,6 +1920,7 @@ impl TuiApp {
                         {
                             self.selected_saved_schedule_index = self.saved_schedules.len() - 1;
                         }
+                        self.refresh_next_up();
                         return KeyAction::ShowToast {
                             message: format!("Schedule '{}' deleted", name),
                             error_type: ErrorType::Success,
@@ -676,26 +1950,60 @@ impl TuiApp {
             KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => KeyAction::Exit,
             KeyCode::Esc => {
                 self.save_name_input.clear();
-                KeyAction::Navigate(FocusMode::ScheduleCreation)
+                self.save_name_conflict = None;
+                self.renaming_timestamp = None;
+                KeyAction::Back
             }
             KeyCode::Enter => {
-                if self.save_name_input.trim().is_empty() {
-                    return KeyAction::ShowToast {
-                        message: "Schedule name cannot be empty!".to_string(),
-                        error_type: ErrorType::Semantic,
-                    };
+                let name = match save::validate_schedule_name(&self.save_name_input) {
+                    Ok(name) => name,
+                    Err(e) => {
+                        return KeyAction::ShowToast {
+                            message: e,
+                            error_type: ErrorType::Semantic,
+                        }
+                    }
+                };
+                let names = save::schedule_names().unwrap_or_default();
+                let conflict = save::find_name_conflict(&names, &name, self.renaming_timestamp);
+
+                if let Some(timestamp) = self.renaming_timestamp {
+                    return self.rename_saved_schedule(timestamp, &name, conflict.is_some());
                 }
+                if conflict.is_some() && conflict != self.save_name_conflict {
+                    // ask first, Enter again overwrites and editing the name renames
+                    self.save_name_conflict = conflict;
+                    return KeyAction::Continue;
+                }
+
                 if let Some(schedule) = self.schedule.current_schedule() {
-                    match save::save_schedule(
-                        self.save_name_input.trim(),
-                        self.settings.selected_school_id.as_deref(),
-                        self.settings.selected_term_id.as_deref(),
-                        schedule,
-                    ) {
-                        Ok(_) => {
-                            let msg = format!("Schedule '{}' saved!", self.save_name_input.trim());
+                    let school_id = self.settings.selected_school_id.as_deref();
+                    let term_id = self.settings.selected_term_id.as_deref();
+                    let result = match conflict {
+                        Some(timestamp) => {
+                            save::overwrite_schedule(timestamp, &name, school_id, term_id, schedule)
+                                .map(|_| (timestamp, format!("Schedule '{}' overwritten!", name)))
+                        }
+                        None => save::save_schedule(&name, school_id, term_id, schedule)
+                            .map(|timestamp| (timestamp, format!("Schedule '{}' saved!", name))),
+                    };
+                    match result {
+                        Ok((timestamp, msg)) => {
                             self.save_name_input.clear();
-                            self.focus_mode = FocusMode::ScheduleCreation;
+                            self.save_name_conflict = None;
+                            self.refresh_next_up();
+                            self.navigate_back();
+                            // the schedule itself is saved either way, so this is only a warning
+                            let settings = self.schedule.generated_with.as_ref();
+                            if let Err(e) = save::save_generation_settings(timestamp, settings) {
+                                return KeyAction::ShowToast {
+                                    message: format!(
+                                        "{} Its generation settings weren't kept: {}",
+                                        msg, e
+                                    ),
+                                    error_type: ErrorType::Warning,
+                                };
+                            }
                             return KeyAction::ShowToast {
                                 message: msg,
                                 error_type: ErrorType::Success,
@@ -713,10 +2021,344 @@ impl TuiApp {
             }
             KeyCode::Backspace => {
                 self.save_name_input.pop();
+                self.save_name_conflict = None;
                 KeyAction::Continue
             }
             KeyCode::Char(c) => {
+                if !save::is_schedule_name_char(c) {
+                    return KeyAction::ShowToast {
+                        message: format!("'{}' can't be used in a schedule name", c),
+                        error_type: ErrorType::Semantic,
+                    };
+                }
+                if self.save_name_input.chars().count() >= save::MAX_SCHEDULE_NAME_LEN {
+                    return KeyAction::ShowToast {
+                        message: format!(
+                            "Schedule names can be at most {} characters",
+                            save::MAX_SCHEDULE_NAME_LEN
+                        ),
+                        error_type: ErrorType::Semantic,
+                    };
+                }
                 self.save_name_input.push(c);
+                self.save_name_conflict = None;
+                KeyAction::Continue
+            }
+            _ => KeyAction::Continue,
+        }
+    }
+
+    /// Generate schedules again with the settings the selected saved schedule was generated
+    /// with, using the classes as they are in the current catalog
+    ///
+    /// Arguments: None
+    ///
+    /// Returns:
+    /// --- ---
+    /// KeyAction -> Schedule creation showing the new schedules, or a toast saying why they
+    ///              couldn't be generated
+    /// --- ---
+    ///
+    fn regenerate_saved_schedule(&mut self) -> KeyAction {
+        let Some(saved) = self.saved_schedules.get(self.selected_saved_schedule_index) else {
+            return KeyAction::Continue;
+        };
+        let Some(settings) = saved.settings.clone() else {
+            return KeyAction::ShowToast {
+                message: format!(
+                    "'{}' wasn't generated here, so there are no settings to regenerate with",
+                    saved.name
+                ),
+                error_type: ErrorType::Info,
+            };
+        };
+        // the cart holds classes of the selected school and term only
+        if saved.school_id != self.settings.selected_school_id
+            || saved.term_id != self.settings.selected_term_id
+        {
+            return KeyAction::ShowToast {
+                message: format!(
+                    "'{}' is from another school or term, select it in Settings to regenerate",
+                    saved.name
+                ),
+                error_type: ErrorType::Semantic,
+            };
+        }
+
+        let name = saved.name.clone();
+        let class_ids: Vec<&str> = settings.classes.iter().map(String::as_str).collect();
+        let live = match save::load_classes_by_id(
+            saved.school_id.as_deref(),
+            saved.term_id.as_deref(),
+            &class_ids,
+        ) {
+            Ok(live) => live,
+            Err(e) => {
+                return KeyAction::ShowToast {
+                    message: format!("Failed to load the classes of '{}': {}", name, e),
+                    error_type: ErrorType::Semantic,
+                }
+            }
+        };
+        let (classes, missing) = save::resolve_generation_settings(&settings, live);
+        if classes.is_empty() {
+            return KeyAction::ShowToast {
+                message: format!("None of the classes of '{}' are offered anymore", name),
+                error_type: ErrorType::Semantic,
+            };
+        }
+
+        let (action, _) = self.schedule.regenerate(classes, &settings);
+        match action {
+            KeyAction::ShowToast {
+                message,
+                error_type,
+            } => self.show_toast(message, error_type),
+            _ if !missing.is_empty() => self.show_toast(
+                format!("Regenerated '{}': {}", name, missing.join(", ")),
+                ErrorType::Warning,
+            ),
+            _ => {}
+        }
+        KeyAction::Navigate(FocusMode::ScheduleCreation)
+    }
+
+    /// Rename a saved schedule from My Schedules
+    ///
+    /// Arguments:
+    /// --- ---
+    /// timestamp -> Timestamp of the schedule to rename
+    /// name -> The new (validated) name
+    /// taken -> Whether another saved schedule already has the name
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// KeyAction -> Toast with the result, back in My Schedules if it was renamed
+    /// --- ---
+    ///
+    fn rename_saved_schedule(&mut self, timestamp: u64, name: &str, taken: bool) -> KeyAction {
+        if taken {
+            return KeyAction::ShowToast {
+                message: format!("A schedule named '{}' already exists", name),
+                error_type: ErrorType::Semantic,
+            };
+        }
+        if let Err(e) = save::rename_schedule(timestamp, name) {
+            return KeyAction::ShowToast {
+                message: format!("Failed to rename schedule: {}", e),
+                error_type: ErrorType::Semantic,
+            };
+        }
+
+        if let Some(saved) = self
+            .saved_schedules
+            .iter_mut()
+            .find(|saved| saved.timestamp == timestamp)
+        {
+            saved.name = name.to_string();
+        }
+        self.save_name_input.clear();
+        self.renaming_timestamp = None;
+        self.navigate_back();
+        KeyAction::ShowToast {
+            message: format!("Renamed to '{}'", name),
+            error_type: ErrorType::Success,
+        }
+    }
+
+    /// Open the paste import popup from My Schedules
+    ///
+    /// Arguments: None
+    ///
+    /// Returns:
+    /// --- ---
+    /// KeyAction -> The popup, or a toast saying why a paste can't be matched yet
+    /// --- ---
+    ///
+    fn open_paste_import(&mut self) -> KeyAction {
+        if !self.search_filters_ready() {
+            return KeyAction::ShowToast {
+                message: "Select a school and term in Settings to import a schedule".to_string(),
+                error_type: ErrorType::Semantic,
+            };
+        }
+        if self.compiler.term_index().is_none() {
+            return KeyAction::ShowToast {
+                message: "The term's sections are still loading, try again in a moment".to_string(),
+                error_type: ErrorType::Info,
+            };
+        }
+        self.paste_import_input.clear();
+        self.paste_import_matches.clear();
+        KeyAction::Navigate(FocusMode::PasteImport)
+    }
+
+    /// Match the pasted schedule against the term's sections again
+    ///
+    /// Arguments: None
+    ///
+    /// Returns: None
+    ///
+    fn match_paste_import(&mut self) {
+        let rows = parse_pasted_rows(&self.paste_import_input);
+        self.paste_import_matches = match self.compiler.term_index() {
+            Some(index) => match_pasted_rows(rows, index.classes()),
+            None => Vec::new(),
+        };
+    }
+
+    /// Handle paste import key events
+    ///
+    /// Terminals without bracketed paste send a paste as keys, so Enter and Tab are typed
+    /// into the paste instead of acting on it
+    ///
+    /// Arguments:
+    /// --- ---
+    /// key -> The key event to handle
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// KeyAction -> The action to take in response to the key
+    /// --- ---
+    ///
+    fn handle_paste_import_key(&mut self, key: KeyEvent) -> KeyAction {
+        let control = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Char('c') if control => return KeyAction::Exit,
+            KeyCode::Char('a') if control => return self.import_pasted_schedule(),
+            KeyCode::Char('u') if control => self.paste_import_input.clear(),
+            KeyCode::Esc => {
+                self.paste_import_input.clear();
+                self.paste_import_matches.clear();
+                return KeyAction::Back;
+            }
+            KeyCode::Enter => self.paste_import_input.push('\n'),
+            KeyCode::Tab => self.paste_import_input.push('\t'),
+            KeyCode::Backspace => {
+                self.paste_import_input.pop();
+            }
+            KeyCode::Char(c) => self.paste_import_input.push(c),
+            _ => return KeyAction::Continue,
+        }
+        self.match_paste_import();
+        KeyAction::Continue
+    }
+
+    /// Add the confidently matched sections of the paste to the cart
+    ///
+    /// Rows matched with less than MIN_CONFIDENCE are left out, the toast says how many
+    ///
+    /// Arguments: None
+    ///
+    /// Returns:
+    /// --- ---
+    /// KeyAction -> Schedule creation with the sections checked, or a toast saying why
+    ///              nothing was added
+    /// --- ---
+    ///
+    fn import_pasted_schedule(&mut self) -> KeyAction {
+        let (confident, unsure): (Vec<&PasteMatch>, Vec<&PasteMatch>) = self
+            .paste_import_matches
+            .iter()
+            .partition(|paste_match| paste_match.is_confident());
+        if confident.is_empty() {
+            return KeyAction::ShowToast {
+                message: if unsure.is_empty() {
+                    "Paste the rows of your schedule from the registrar first".to_string()
+                } else {
+                    "No row matched a section confidently, add CRNs or sections to the paste"
+                        .to_string()
+                },
+                error_type: ErrorType::Semantic,
+            };
+        }
+
+        let classes: Vec<Class> = confident
+            .iter()
+            .filter_map(|paste_match| paste_match.class.clone())
+            .collect();
+        let unsure: Vec<String> = unsure
+            .iter()
+            .map(|paste_match| paste_match.row.label())
+            .collect();
+        let count = classes.len();
+        let added = self.schedule.import_to_cart(classes);
+        self.paste_import_input.clear();
+        self.paste_import_matches.clear();
+
+        let mut message = format!("Imported {} section(s), {} new to the cart", count, added);
+        let error_type = if unsure.is_empty() {
+            ErrorType::Success
+        } else {
+            message.push_str(&format!(", left out: {}", unsure.join(", ")));
+            ErrorType::Warning
+        };
+        self.show_toast(message, error_type);
+        // Esc from the schedules returns to My Schedules, not the popup
+        self.navigate_back();
+        KeyAction::Navigate(FocusMode::ScheduleCreation)
+    }
+
+    /// Handle overlay schedule input key events
+    ///
+    /// Arguments:
+    /// --- ---
+    /// key -> The key event to handle
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// KeyAction -> The action to take in response to the key
+    /// --- ---
+    ///
+    fn handle_overlay_input_key(&mut self, key: KeyEvent) -> KeyAction {
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => KeyAction::Exit,
+            KeyCode::Esc => {
+                self.overlay_input.clear();
+                KeyAction::Back
+            }
+            KeyCode::Enter => {
+                let input = self.overlay_input.trim().to_string();
+                if input.is_empty() {
+                    return KeyAction::ShowToast {
+                        message: "Paste a schedule token or type a saved schedule's name!"
+                            .to_string(),
+                        error_type: ErrorType::Semantic,
+                    };
+                }
+                match self.load_overlay(&input) {
+                    Ok((overlay, missing)) => {
+                        let mut msg = format!(
+                            "Overlaying '{}' ({} classes)",
+                            overlay.name,
+                            overlay.classes.len()
+                        );
+                        if !missing.is_empty() {
+                            msg.push_str(&format!(", {} not in the local database", missing.len()));
+                        }
+                        self.schedule.overlay = Some(overlay);
+                        self.overlay_input.clear();
+                        self.navigate_back();
+                        KeyAction::ShowToast {
+                            message: msg,
+                            error_type: ErrorType::Success,
+                        }
+                    }
+                    Err(e) => KeyAction::ShowToast {
+                        message: e,
+                        error_type: ErrorType::Semantic,
+                    },
+                }
+            }
+            KeyCode::Backspace => {
+                self.overlay_input.pop();
+                KeyAction::Continue
+            }
+            KeyCode::Char(c) => {
+                self.overlay_input.push(c);
                 KeyAction::Continue
             }
             _ => KeyAction::Continue,
@@ -749,10 +2391,14 @@ impl TuiApp {
     ///
     /// Returns: None
     ///
-    /// Toggles cursor visibility every 500ms when in SaveNameInput focus mode
+    /// Toggles cursor visibility every 500ms when in SaveNameInput, OverlayInput, or PasteImport
+    /// focus mode
     ///
     fn update_save_name_cursor(&mut self) {
-        if self.focus_mode == FocusMode::SaveNameInput {
+        if matches!(
+            self.focus_mode,
+            FocusMode::SaveNameInput | FocusMode::OverlayInput | FocusMode::PasteImport
+        ) {
             if self.save_name_last_blink.elapsed() > Duration::from_millis(500) {
                 self.save_name_cursor_visible = !self.save_name_cursor_visible;
                 self.save_name_last_blink = Instant::now();
@@ -760,6 +2406,420 @@ impl TuiApp {
         }
     }
 
+    /// Select a school and term, if they are still in the database
+    ///
+    /// Arguments:
+    /// --- ---
+    /// school_id -> The school to select, None to keep the current one
+    /// term_id -> The term to select, None to keep the current one (if the school is the same)
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// bool -> true if the selected school or term changed
+    /// --- ---
+    ///
+    fn select_school_and_term(&mut self, school_id: Option<&str>, term_id: Option<&str>) -> bool {
+        let before = (
+            self.settings.selected_school_id.clone(),
+            self.settings.selected_term_id.clone(),
+        );
+
+        self.load_school_data();
+        if let Some(school_id) = school_id {
+            if let Some(index) = self
+                .settings
+                .available_schools
+                .iter()
+                .position(|s| s.id == school_id)
+            {
+                if self.settings.selected_school_id.as_deref() != Some(school_id) {
+                    // terms belong to one school
+                    self.settings.selected_term_id = None;
+                    self.compiler.set_term_id(None);
+                }
+                self.settings.selected_school_id = Some(school_id.to_string());
+                self.settings.selected_school_index = index;
+                self.compiler.set_school_id(Some(school_id.to_string()));
+                self.load_terms(school_id);
+            }
+        }
+        if let Some(term_id) = term_id {
+            if let Some(index) = self
+                .settings
+                .available_terms
+                .iter()
+                .position(|t| t.id == term_id)
+            {
+                self.settings.selected_term_id = Some(term_id.to_string());
+                self.settings.selected_term_index = index;
+                self.compiler.set_term_id(Some(term_id.to_string()));
+            }
+        }
+        self.compiler.warm_up_lookups();
+
+        before
+            != (
+                self.settings.selected_school_id.clone(),
+                self.settings.selected_term_id.clone(),
+            )
+    }
+
+    /// Use the active workspace profile: its school and term, constraints, and favorites
+    ///
+    /// Arguments: None
+    ///
+    /// Returns: None
+    ///
+    /// With no active profile, searches go back to being unconstrained and the school and
+    /// term are left as they are
+    ///
+    fn apply_profile(&mut self) {
+        let profile = self.profiles.active_profile().cloned();
+        if let Some(profile) = &profile {
+            let changed = self
+                .select_school_and_term(profile.school_id.as_deref(), profile.term_id.as_deref());
+            if changed {
+                // the cart holds classes of the previous term
+                self.schedule.clear();
+            }
+        }
+        self.main_menu.profile_name = profile.as_ref().map(|profile| profile.name.clone());
+        self.search.set_profile(profile);
+    }
+
+    /// Switch to the next workspace profile, or back to none after the last one
+    ///
+    /// Arguments: None
+    ///
+    /// Returns:
+    /// --- ---
+    /// KeyAction -> Toast describing the profile now in use
+    /// --- ---
+    ///
+    fn switch_profile(&mut self) -> KeyAction {
+        if self.profiles.profiles.is_empty() {
+            return KeyAction::ShowToast {
+                message: "No profiles yet, add one with `classql profile save`".to_string(),
+                error_type: ErrorType::Info,
+            };
+        }
+        let message = match self.profiles.switch_next() {
+            Some(profile) => format!("Profile: {}", profile.summary()),
+            None => "No profile, searches are unconstrained".to_string(),
+        };
+        self.apply_profile();
+        KeyAction::ShowToast {
+            message,
+            error_type: ErrorType::Success,
+        }
+    }
+
+    /// Get the database, school, and term the catalog and professor directory list
+    ///
+    /// Arguments: None
+    ///
+    /// Returns:
+    /// --- ---
+    /// Result<CatalogScope, KeyAction> -> The scope, or a toast if nothing can be listed yet
+    /// --- ---
+    ///
+    fn catalog_scope(&self) -> Result<CatalogScope, KeyAction> {
+        if !self.search_filters_ready() {
+            return Err(KeyAction::ShowToast {
+                message: "Please select a school and term first (Settings)".to_string(),
+                error_type: ErrorType::Warning,
+            });
+        }
+        // the test database holds a single school and isn't filtered, like searches
+        let scope = if self.settings.selected_school_id.as_deref() == Some("_test") {
+            CatalogScope {
+                db_path: get_test_db_path(),
+                school_id: None,
+                term_id: None,
+            }
+        } else {
+            CatalogScope {
+                db_path: self.compiler.db_path(),
+                school_id: self.settings.selected_school_id.clone(),
+                term_id: self.settings.selected_term_id.clone(),
+            }
+        };
+        if !scope.db_path.exists() {
+            return Err(KeyAction::ShowToast {
+                message: "No class data yet, sync from Settings first".to_string(),
+                error_type: ErrorType::Warning,
+            });
+        }
+        Ok(scope)
+    }
+
+    /// Open the catalog browser for the selected school and term
+    ///
+    /// Arguments: None
+    ///
+    /// Returns:
+    /// --- ---
+    /// KeyAction -> Navigation to the catalog, or a toast if it can't be opened
+    /// --- ---
+    ///
+    fn open_catalog(&mut self) -> KeyAction {
+        let scope = match self.catalog_scope() {
+            Ok(scope) => scope,
+            Err(toast) => return toast,
+        };
+        match self.catalog.open(scope) {
+            Ok(()) => KeyAction::Navigate(FocusMode::CatalogBrowse),
+            Err(e) => KeyAction::ShowToast {
+                message: format!("Failed to load catalog: {}", e),
+                error_type: ErrorType::Warning,
+            },
+        }
+    }
+
+    /// Open the professor directory for the selected school and term
+    ///
+    /// Arguments:
+    /// --- ---
+    /// class -> A class whose professor's sections to show, None for the list of professors
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// KeyAction -> Navigation to the directory, or a toast if it can't be opened
+    /// --- ---
+    ///
+    fn open_professors(&mut self, class: Option<&Class>) -> KeyAction {
+        let scope = match self.catalog_scope() {
+            Ok(scope) => scope,
+            Err(toast) => return toast,
+        };
+        if let Err(e) = self.professors.open(scope) {
+            return KeyAction::ShowToast {
+                message: format!("Failed to load professors: {}", e),
+                error_type: ErrorType::Warning,
+            };
+        }
+        let Some(class) = class else {
+            return KeyAction::Navigate(FocusMode::ProfessorDirectory);
+        };
+        let Some(name) = class.professor_name.as_deref() else {
+            return KeyAction::ShowToast {
+                message: format!("{} has no professor listed", section_label(class)),
+                error_type: ErrorType::Info,
+            };
+        };
+        match self
+            .professors
+            .open_professor(name, class.professor_email.as_deref())
+        {
+            Ok(true) => KeyAction::Navigate(FocusMode::ProfessorDirectory),
+            Ok(false) => KeyAction::ShowToast {
+                message: format!("{} doesn't teach in this term", name),
+                error_type: ErrorType::Info,
+            },
+            Err(e) => KeyAction::ShowToast {
+                message: format!("Failed to load sections: {}", e),
+                error_type: ErrorType::Warning,
+            },
+        }
+    }
+
+    /// Show every section of a class's course this term in the search results
+    ///
+    /// The sections come from an internal query rather than the search box, so the query
+    /// typed there is kept and Enter runs it again
+    ///
+    /// Arguments:
+    /// --- ---
+    /// class -> The class whose course's sections to show, selected among them
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// KeyAction -> Continue on the results, or a toast if there are no other sections
+    /// --- ---
+    ///
+    fn open_course_sections(&mut self, class: &Class) -> KeyAction {
+        let scope = match self.catalog_scope() {
+            Ok(scope) => scope,
+            Err(toast) => return toast,
+        };
+        let course = format!("{} {}", class.subject_code, class.course_number);
+        let sections = match fetch_sections(&scope, &class.subject_code, &class.course_number) {
+            Ok(sections) => sections,
+            Err(e) => {
+                return KeyAction::ShowToast {
+                    message: format!("Failed to load sections: {}", e),
+                    error_type: ErrorType::Warning,
+                }
+            }
+        };
+        let id = class.unique_id();
+        if sections.iter().all(|section| section.unique_id() == id) {
+            return KeyAction::ShowToast {
+                message: format!("{} has no other sections this term", course),
+                error_type: ErrorType::Info,
+            };
+        }
+        self.start_pivot();
+        self.search
+            .show_pivot(format!("Sections of {}", course), sections, &id);
+        self.show_pivot_results()
+    }
+
+    /// Search for every section a class's professor teaches, from its detail view
+    ///
+    /// Runs `prof is <name>` in the search box, saving the search first so Esc goes back to
+    /// it and to the detail view
+    ///
+    /// Arguments:
+    /// --- ---
+    /// class -> The class whose professor to search for, selected among the results
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// KeyAction -> Continue on the results, or a toast if there are no other sections
+    /// --- ---
+    ///
+    fn search_professor(&mut self, class: &Class) -> KeyAction {
+        let Some(name) = class.professor_name.clone() else {
+            return KeyAction::ShowToast {
+                message: format!("{} has no professor listed", section_label(class)),
+                error_type: ErrorType::Info,
+            };
+        };
+        if !self.search_filters_ready() {
+            return KeyAction::ShowToast {
+                message: "Please select a school and term first (Settings)".to_string(),
+                error_type: ErrorType::Warning,
+            };
+        }
+
+        self.start_pivot();
+        self.search.input = format!("prof is {}", quote_string(&name));
+        if let Some(toast) = self.search.execute_query(&mut self.compiler) {
+            self.cancel_pivot();
+            return toast;
+        }
+        let id = class.unique_id();
+        if self
+            .search
+            .query_results
+            .iter()
+            .all(|section| section.unique_id() == id)
+        {
+            self.cancel_pivot();
+            return KeyAction::ShowToast {
+                message: format!("{} has no other sections this term", name),
+                error_type: ErrorType::Info,
+            };
+        }
+        self.search.select_result(&id);
+        self.show_pivot_results()
+    }
+
+    /// Save the detail view and the search before a pivot replaces the results
+    ///
+    /// The view goes on the back-stack before the search is saved, so going back to it
+    /// undoes the pivot
+    ///
+    /// Arguments: None
+    ///
+    /// Returns: None
+    ///
+    fn start_pivot(&mut self) {
+        self.push_view();
+        self.search.push_place();
+    }
+
+    /// Undo start_pivot for a pivot that found nothing to show
+    ///
+    /// Arguments: None
+    ///
+    /// Returns: None
+    ///
+    fn cancel_pivot(&mut self) {
+        self.search.go_back();
+        self.view_stack.pop();
+    }
+
+    /// Show the results of a pivot, which already put the detail view on the back-stack
+    ///
+    /// Arguments: None
+    ///
+    /// Returns:
+    /// --- ---
+    /// KeyAction -> Continue, the focus is already on the results
+    /// --- ---
+    ///
+    fn show_pivot_results(&mut self) -> KeyAction {
+        self.focus_mode = FocusMode::ResultsBrowse;
+        KeyAction::Continue
+    }
+
+    /// Add a section picked in the catalog or professor directory to the cart
+    ///
+    /// Arguments:
+    /// --- ---
+    /// class -> The section
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// KeyAction -> A toast saying whether it was added or already in the cart, and
+    ///              warning when its final exam conflicts with another cart class's
+    /// --- ---
+    ///
+    fn add_section_to_cart(&mut self, class: Class) -> KeyAction {
+        let label = section_label(&class);
+        if self.schedule.cart_classes.contains_key(&class.unique_id()) {
+            return KeyAction::ShowToast {
+                message: format!("{} is already in your cart", label),
+                error_type: ErrorType::Info,
+            };
+        }
+        let conflicts = self.schedule.final_conflicts_with(&class);
+        self.schedule.add_to_cart(class);
+        if !conflicts.is_empty() {
+            return KeyAction::ShowToast {
+                message: format!(
+                    "Added {} to cart ({} classes), but its final exam conflicts with {}",
+                    label,
+                    self.schedule.cart_classes.len(),
+                    conflicts.join(", ")
+                ),
+                error_type: ErrorType::Warning,
+            };
+        }
+        KeyAction::ShowToast {
+            message: format!(
+                "Added {} to cart ({} classes)",
+                label,
+                self.schedule.cart_classes.len()
+            ),
+            error_type: ErrorType::Success,
+        }
+    }
+
+    /// Check whether a school (and term, unless using the test database) is selected
+    ///
+    /// Arguments: None
+    ///
+    /// Returns:
+    /// --- ---
+    /// bool -> true if queries can be run against the current selection
+    /// --- ---
+    ///
+    fn search_filters_ready(&self) -> bool {
+        match self.settings.selected_school_id.as_deref() {
+            Some("_test") => true,
+            Some(_) => self.settings.selected_term_id.is_some(),
+            None => false,
+        }
+    }
+
     /// Show a toast notification message
     ///
     /// Arguments:
@@ -813,6 +2873,43 @@ impl TuiApp {
         }
     }
 
+    /// Refresh the "What's Next" panel from the active saved schedule
+    ///
+    /// Arguments: None
+    ///
+    /// Returns: None
+    ///
+    /// The active schedule is the one last opened from My Schedules, or the newest
+    /// saved schedule if none has been opened (or it was deleted)
+    ///
+    fn refresh_next_up(&mut self) {
+        let schedules = save::load_all_schedules().unwrap_or_default();
+        let active = self
+            .active_schedule_timestamp
+            .and_then(|timestamp| schedules.iter().find(|s| s.timestamp == timestamp))
+            .or_else(|| schedules.first());
+
+        let next_up = active.and_then(|schedule| {
+            let (db_path, school_id, term_id) = if schedule.school_id.as_deref() == Some("_test") {
+                // the test database holds a single school and term
+                let db_path = get_test_db_path();
+                let school = fetch_schools(&db_path).ok()?.into_iter().next()?;
+                let term = fetch_terms(&db_path, &school.id).ok()?.into_iter().next()?;
+                (db_path, school.id, term.id)
+            } else {
+                (
+                    get_default_db_path(),
+                    schedule.school_id.clone()?,
+                    schedule.term_id.clone()?,
+                )
+            };
+            let calendar = fetch_term_calendar(&db_path, &school_id, &term_id).ok()?;
+            Some((schedule.classes.clone(), calendar))
+        });
+
+        self.main_menu.set_active_schedule(next_up);
+    }
+
     /// Load term data for a specific school
     ///
     /// Arguments:
@@ -839,47 +2936,207 @@ impl TuiApp {
         }
     }
 
-    /// Perform database synchronization
+    /// Start a database synchronization in a background thread
     ///
     /// Arguments: None
     ///
     /// Returns: None
     ///
-    /// Syncs data from remote sources using configuration from environment variables.
+    /// Syncs data from remote sources using configuration from environment variables. The
+    /// UI keeps running while it does: busy retries are sent as they happen and the result
+    /// when it finishes, both as events applied on the next tick
+    ///
+    fn start_sync(&mut self) {
+        let config = match crate::data::sync::SyncConfig::from_env() {
+            Ok(config) => config,
+            Err(e) => {
+                self.show_toast(format!("Config error: {}", e), ErrorType::Warning);
+                self.settings.sync_complete();
+                return;
+            }
+        };
+        self.sync_retries = 0;
+        let sender = self.events.sender();
+        thread::spawn(move || {
+            let retry_sender = sender.clone();
+            let mut on_retry = |message: &str| {
+                let _ = retry_sender.send(AppEvent::SyncRetried(message.to_string()));
+            };
+            let result = crate::data::sync::sync_all(&config, &mut on_retry);
+            // the app may have exited while the sync was running
+            let _ = sender.send(AppEvent::SyncFinished(result));
+        });
+    }
+
+    /// Apply the result of a database synchronization
+    ///
+    /// Arguments:
+    /// --- ---
+    /// result -> The synced database, or the error that stopped the sync
+    /// --- ---
+    ///
+    /// Returns: None
+    ///
     /// Shows toast notifications for success or failure, and reloads school data on success
     ///
-    fn perform_sync(&mut self) {
-        match crate::data::sync::SyncConfig::from_env() {
-            Ok(config) => match crate::data::sync::sync_all(&config) {
-                Ok(_) => {
+    fn finish_sync(&mut self, result: Result<PathBuf, String>) {
+        let retry_note = if self.sync_retries > 0 {
+            format!(
+                " (database was busy, retried {} time(s))",
+                self.sync_retries
+            )
+        } else {
+            String::new()
+        };
+
+        match result {
+            Ok(_) => {
+                // re-validate saved schedules against the freshly synced data
+                let changed = save::load_all_schedules()
+                    .map(|schedules| schedules.iter().filter(|s| !s.issues.is_empty()).count())
+                    .unwrap_or(0);
+                let watch_note: String = self
+                    .check_watch_list()
+                    .iter()
+                    .map(|line| format!("\n{}", line))
+                    .collect();
+                if changed > 0 {
+                    self.show_toast(
+                        format!(
+                            "Sync completed{}, {} saved schedule(s) changed (see My Schedules){}",
+                            retry_note, changed, watch_note
+                        ),
+                        ErrorType::Warning,
+                    );
+                } else {
                     self.show_toast(
-                        "Sync completed successfully!".to_string(),
+                        format!("Sync completed successfully{}!{}", retry_note, watch_note),
                         ErrorType::Success,
                     );
-                    self.load_school_data();
-                }
-                Err(e) => {
-                    self.show_toast(format!("Sync failed: {}", e), ErrorType::Warning);
                 }
-            },
+                self.load_school_data();
+                self.refresh_next_up();
+                self.compiler.warm_up_lookups();
+                // the catalog and directory are read again the next time they're opened
+                self.catalog.reset();
+                self.professors.reset();
+            }
             Err(e) => {
-                self.show_toast(format!("Config error: {}", e), ErrorType::Warning);
+                self.show_toast(
+                    format!("Sync failed{}: {}", retry_note, e),
+                    ErrorType::Warning,
+                );
             }
         }
         self.settings.sync_complete();
     }
 
-    /// Terminate the TUI gracefully
+    /// Check the watch list against the current data
     ///
     /// Arguments: None
     ///
     /// Returns:
     /// --- ---
-    /// Result<(), TUIError> -> Ok on success, error on failure
+    /// Vec<String> -> A line for each watched section that opened up, filled, or was
+    ///                dropped, then one for each hook that failed
     /// --- ---
     ///
-    pub fn terminate(&self) -> Result<(), TUIError> {
-        ratatui::restore();
-        Ok(())
+    /// Each school and term on the list is read once. The updated seats are saved, the
+    /// events are queued as desktop notifications (sent only if turned on in Settings),
+    /// and the watch hooks from the environment are run for each event. Hooks can take a
+    /// while, so they run in the background and report failures with an event
+    ///
+    fn check_watch_list(&mut self) -> Vec<String> {
+        let mut events = Vec::new();
+        for (school_id, term_id) in self.watch_list.scopes() {
+            let (db_path, sql) = if school_id.as_deref() == Some("_test") {
+                (get_test_db_path(), generate_scope_sql(None, None, &[]))
+            } else {
+                (
+                    get_synced_db_path(),
+                    generate_scope_sql(school_id.as_deref(), term_id.as_deref(), &[]),
+                )
+            };
+            let Ok(sql) = sql else {
+                continue;
+            };
+            // a scope that can't be read would report every section as dropped
+            if !db_path.exists() {
+                continue;
+            }
+            let Ok(current) = execute_query(&sql, &db_path) else {
+                continue;
+            };
+            events.extend(self.watch_list.check(
+                school_id.as_deref(),
+                term_id.as_deref(),
+                &current,
+            ));
+        }
+
+        if events.is_empty() {
+            return Vec::new();
+        }
+        let _ = save::save_watch_list(&self.watch_list);
+        let lines: Vec<String> = events.iter().map(WatchEvent::message).collect();
+        self.notifier.push(lines.clone());
+        self.notifier.flush();
+
+        let hook = WatchHook::from_env();
+        if !hook.is_empty() {
+            let sender = self.events.sender();
+            thread::spawn(move || {
+                let failures = hook.run(&events);
+                // the app may have exited while the hooks were running
+                if !failures.is_empty() {
+                    let _ = sender.send(AppEvent::WatchHooksFailed(failures));
+                }
+            });
+        }
+        lines
+    }
+
+    /// Load a schedule to overlay on the one being viewed
+    ///
+    /// Arguments:
+    /// --- ---
+    /// input -> A schedule token (or text containing one), or a saved schedule's name
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Result<(ScheduleOverlay, Vec<String>), String> -> The overlay and the unique IDs of
+    ///                                                   shared sections missing locally,
+    ///                                                   or error message
+    /// --- ---
+    ///
+    fn load_overlay(&self, input: &str) -> Result<(ScheduleOverlay, Vec<String>), String> {
+        match SharedSchedule::decode(input) {
+            Ok(shared) => {
+                let (classes, missing) = shared.load_classes()?;
+                Ok((
+                    ScheduleOverlay {
+                        name: shared.name,
+                        classes,
+                    },
+                    missing,
+                ))
+            }
+            // a damaged token shouldn't be looked up as a name
+            Err(e) if input.contains(SHARE_PREFIX) => Err(e),
+            Err(_) => save::load_all_schedules()?
+                .into_iter()
+                .find(|schedule| schedule.name.eq_ignore_ascii_case(input))
+                .map(|schedule| {
+                    (
+                        ScheduleOverlay {
+                            name: schedule.name,
+                            classes: schedule.classes,
+                        },
+                        Vec::new(),
+                    )
+                })
+                .ok_or_else(|| format!("'{}' is not a schedule token or saved schedule", input)),
+        }
     }
 }
@@ -0,0 +1,411 @@
+/// src/tui/error_catalog.rs
+///
+/// Catalog of compiler error codes and message templates
+///
+/// Responsible for keeping every user-facing compiler error in one place so messages stay
+/// consistent, can be tested by key, and can be translated (see utils::i18n). Each
+/// template belongs to an error code (e.g., E001 UnclosedParenthesis) and uses {name}
+/// placeholders for the parts that vary
+///
+/// Contains:
+/// --- ---
+/// ErrorCode -> Error code enum
+///      Methods:
+///      --- ---
+///      ALL -> Every error code, in code order
+///      code -> Get the short code (e.g., "E001")
+///      name -> Get the code's name (e.g., "UnclosedParenthesis")
+///      --- ---
+/// MessageTemplate -> A keyed message template
+/// CATALOG -> All message templates
+/// Helper functions:
+///      --- ---
+///      template -> Look up a message template by key
+///      message -> Render a message template with arguments
+///      diagnostic_header -> Build the "error[E001] at column 5" prefix for an error
+///      --- ---
+/// --- ---
+///
+use crate::utils::i18n;
+use std::fmt::{Display, Formatter};
+
+/// ErrorCode enum
+///
+/// ErrorCode types:
+/// --- ---
+/// UnclosedParenthesis -> E001, a '(' without its ')'
+/// MissingToken -> E002, the query ended or skipped something required
+/// EmptyQuery -> E003, nothing to search for
+/// ExpectedAfter -> E004, a keyword needs a specific word after it
+/// InvalidToken -> E005, a word the parser doesn't accept in that spot
+/// InvalidValue -> E006, a value of the wrong kind for its field
+/// UnclosedString -> E007, a '"' without its closing quote
+/// UnrecognizedCharacter -> E008, a character the lexer has no token for
+/// CodeGeneration -> E009, the query parsed but SQL couldn't be built
+/// DatabaseQuery -> E010, the SQL failed against the database
+/// NestingTooDeep -> E011, more nested parentheses or 'not's than the parser allows
+/// UnknownValue -> E012, a subject, campus, or method none of the downloaded classes have
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for ErrorCode
+/// Clone -> Clone trait for ErrorCode
+/// Copy -> Copy trait for ErrorCode
+/// PartialEq -> PartialEq trait for ErrorCode
+/// Eq -> Eq trait for ErrorCode
+/// Display -> Display trait for ErrorCode
+/// --- ---
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCode {
+    UnclosedParenthesis,
+    MissingToken,
+    EmptyQuery,
+    ExpectedAfter,
+    InvalidToken,
+    InvalidValue,
+    UnclosedString,
+    UnrecognizedCharacter,
+    CodeGeneration,
+    DatabaseQuery,
+    NestingTooDeep,
+    UnknownValue,
+}
+
+/// ErrorCode Implementation
+///
+/// Methods:
+/// --- ---
+/// ALL -> Every error code, in code order
+/// code -> Get the short code (e.g., "E001")
+/// name -> Get the code's name (e.g., "UnclosedParenthesis")
+/// --- ---
+///
+impl ErrorCode {
+    /// Every error code, in code order
+    pub const ALL: &'static [ErrorCode] = &[
+        ErrorCode::UnclosedParenthesis,
+        ErrorCode::MissingToken,
+        ErrorCode::EmptyQuery,
+        ErrorCode::ExpectedAfter,
+        ErrorCode::InvalidToken,
+        ErrorCode::InvalidValue,
+        ErrorCode::UnclosedString,
+        ErrorCode::UnrecognizedCharacter,
+        ErrorCode::CodeGeneration,
+        ErrorCode::DatabaseQuery,
+        ErrorCode::NestingTooDeep,
+        ErrorCode::UnknownValue,
+    ];
+
+    /// Get the short code (e.g., "E001")
+    ///
+    /// Parameters:
+    /// --- ---
+    /// self -> The error code
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// &'static str -> The short code
+    /// --- ---
+    ///
+    pub fn code(self) -> &'static str {
+        match self {
+            ErrorCode::UnclosedParenthesis => "E001",
+            ErrorCode::MissingToken => "E002",
+            ErrorCode::EmptyQuery => "E003",
+            ErrorCode::ExpectedAfter => "E004",
+            ErrorCode::InvalidToken => "E005",
+            ErrorCode::InvalidValue => "E006",
+            ErrorCode::UnclosedString => "E007",
+            ErrorCode::UnrecognizedCharacter => "E008",
+            ErrorCode::CodeGeneration => "E009",
+            ErrorCode::DatabaseQuery => "E010",
+            ErrorCode::NestingTooDeep => "E011",
+            ErrorCode::UnknownValue => "E012",
+        }
+    }
+
+    /// Get the code's name (e.g., "UnclosedParenthesis")
+    ///
+    /// Parameters:
+    /// --- ---
+    /// self -> The error code
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// &'static str -> The code's name
+    /// --- ---
+    ///
+    pub fn name(self) -> &'static str {
+        match self {
+            ErrorCode::UnclosedParenthesis => "UnclosedParenthesis",
+            ErrorCode::MissingToken => "MissingToken",
+            ErrorCode::EmptyQuery => "EmptyQuery",
+            ErrorCode::ExpectedAfter => "ExpectedAfter",
+            ErrorCode::InvalidToken => "InvalidToken",
+            ErrorCode::InvalidValue => "InvalidValue",
+            ErrorCode::UnclosedString => "UnclosedString",
+            ErrorCode::UnrecognizedCharacter => "UnrecognizedCharacter",
+            ErrorCode::CodeGeneration => "CodeGeneration",
+            ErrorCode::DatabaseQuery => "DatabaseQuery",
+            ErrorCode::NestingTooDeep => "NestingTooDeep",
+            ErrorCode::UnknownValue => "UnknownValue",
+        }
+    }
+}
+
+/// ErrorCode Display Trait Implementation
+///
+/// Parameters:
+/// --- ---
+/// self -> The ErrorCode to display
+/// f -> The formatter to display the ErrorCode
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// std::fmt::Result -> The result of the display
+/// --- ---
+///
+impl Display for ErrorCode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.code(), self.name())
+    }
+}
+
+/// A keyed message template
+///
+/// Fields:
+/// --- ---
+/// key -> Stable key used to look the template up (e.g., "expected_after")
+/// code -> The error code the message reports
+/// text -> The English message, with {name} placeholders for arguments
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for MessageTemplate
+/// --- ---
+///
+#[derive(Debug)]
+pub struct MessageTemplate {
+    pub key: &'static str,
+    pub code: ErrorCode,
+    pub text: &'static str,
+}
+
+/// All message templates
+///
+/// Keys are unique; a code can have several templates (e.g., singular and plural forms)
+///
+pub const CATALOG: &[MessageTemplate] = &[
+    MessageTemplate {
+        key: "unclosed_parenthesis",
+        code: ErrorCode::UnclosedParenthesis,
+        text: "Missing closing parenthesis ')'",
+    },
+    MessageTemplate {
+        key: "missing_token",
+        code: ErrorCode::MissingToken,
+        text: "Missing: {expected}",
+    },
+    MessageTemplate {
+        key: "empty_query",
+        code: ErrorCode::EmptyQuery,
+        text: "Please enter a query to search",
+    },
+    MessageTemplate {
+        key: "expected_after",
+        code: ErrorCode::ExpectedAfter,
+        text: "After '{after}', please add: {expected}",
+    },
+    MessageTemplate {
+        key: "expected_after_one_of",
+        code: ErrorCode::ExpectedAfter,
+        text: "After '{after}', please add one of: {expected}",
+    },
+    MessageTemplate {
+        key: "start_with",
+        code: ErrorCode::ExpectedAfter,
+        text: "Please start with: {expected}",
+    },
+    MessageTemplate {
+        key: "start_with_one_of",
+        code: ErrorCode::ExpectedAfter,
+        text: "Please start with one of: {expected}",
+    },
+    MessageTemplate {
+        key: "invalid_token",
+        code: ErrorCode::InvalidToken,
+        text: "'{token}' is not valid here ({context})",
+    },
+    MessageTemplate {
+        key: "invalid_token_try",
+        code: ErrorCode::InvalidToken,
+        text: "'{token}' is not valid here. Try: {suggestions}",
+    },
+    MessageTemplate {
+        key: "invalid_value",
+        code: ErrorCode::InvalidValue,
+        text: "'{token}' is not valid here ({context})",
+    },
+    MessageTemplate {
+        key: "invalid_value_try",
+        code: ErrorCode::InvalidValue,
+        text: "'{token}' is not valid here. Try: {suggestions}",
+    },
+    MessageTemplate {
+        key: "unclosed_string",
+        code: ErrorCode::UnclosedString,
+        text: "Unclosed string starting at byte {start} - add closing quote \"",
+    },
+    MessageTemplate {
+        key: "unrecognized_character",
+        code: ErrorCode::UnrecognizedCharacter,
+        text: "Unrecognized character: {characters}. Try wrapping in quotes if this is part of a value (e.g., \"{example}\")",
+    },
+    MessageTemplate {
+        key: "unrecognized_characters",
+        code: ErrorCode::UnrecognizedCharacter,
+        text: "Unrecognized characters: {characters}. Try wrapping in quotes if this is part of a value (e.g., \"{example}\")",
+    },
+    MessageTemplate {
+        key: "codegen_empty_ast",
+        code: ErrorCode::CodeGeneration,
+        text: "Cannot generate SQL from an empty AST",
+    },
+    MessageTemplate {
+        key: "codegen_unsupported_node",
+        code: ErrorCode::CodeGeneration,
+        text: "Unsupported node type for code generation: {node_type}",
+    },
+    MessageTemplate {
+        key: "codegen_invalid_structure",
+        code: ErrorCode::CodeGeneration,
+        text: "Invalid AST structure: {message}",
+    },
+    MessageTemplate {
+        key: "codegen_unsafe_value",
+        code: ErrorCode::CodeGeneration,
+        text: "Can't search for \"{value}\": values can't contain control characters",
+    },
+    MessageTemplate {
+        key: "database_query",
+        code: ErrorCode::DatabaseQuery,
+        text: "Database query error: {error}",
+    },
+    MessageTemplate {
+        key: "nesting_too_deep",
+        code: ErrorCode::NestingTooDeep,
+        text: "Too many nested parentheses or 'not's (at most {max} levels)",
+    },
+    MessageTemplate {
+        key: "unknown_value",
+        code: ErrorCode::UnknownValue,
+        text: "No class has {field} '{value}'",
+    },
+    MessageTemplate {
+        key: "unknown_value_try",
+        code: ErrorCode::UnknownValue,
+        text: "No class has {field} '{value}'. Did you mean: {suggestions}?",
+    },
+    MessageTemplate {
+        key: "value_out_of_range",
+        code: ErrorCode::InvalidValue,
+        text: "{value} is out of range for {field} (it must be between {min} and {max})",
+    },
+    MessageTemplate {
+        key: "comparison_never_matches",
+        code: ErrorCode::InvalidValue,
+        text: "'{comparison}' can never match, {field} is always between {min} and {max}",
+    },
+    MessageTemplate {
+        key: "invalid_pattern",
+        code: ErrorCode::InvalidValue,
+        text: "'{pattern}' isn't a valid pattern: {reason}",
+    },
+];
+
+/// Look up a message template by key
+///
+/// Parameters:
+/// --- ---
+/// key -> The template's key (e.g., "missing_token")
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Option<&'static MessageTemplate> -> The template, or None for an unknown key
+/// --- ---
+///
+pub fn template(key: &str) -> Option<&'static MessageTemplate> {
+    CATALOG.iter().find(|template| template.key == key)
+}
+
+/// Render a message template with arguments
+///
+/// Uses the active locale's translation when there is one. Each {name} placeholder is
+/// replaced by the matching argument; an unknown key renders as the key itself so a
+/// missing template is visible rather than silent
+///
+/// Parameters:
+/// --- ---
+/// key -> The template's key (e.g., "expected_after")
+/// args -> (name, value) pairs for the template's placeholders
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> The rendered message
+/// --- ---
+///
+pub fn message(key: &str, args: &[(&str, &str)]) -> String {
+    let Some(template) = template(key) else {
+        return key.to_string();
+    };
+
+    // translations use Fluent ids, so "missing_token" is looked up as "error-missing-token"
+    i18n::tr(
+        &format!("error-{}", key.replace('_', "-")),
+        template.text,
+        args,
+    )
+}
+
+/// Build the "error[E001] at column 5" prefix for an error
+///
+/// The column is 1-based and counts characters, so it matches what the user sees even
+/// after accented or other multibyte characters
+///
+/// Parameters:
+/// --- ---
+/// code -> The error's code
+/// input -> The query the error positions point into
+/// positions -> Byte ranges of the problematic input (the first one sets the column)
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> The diagnostic prefix, without a column when there are no positions
+/// --- ---
+///
+pub fn diagnostic_header(code: ErrorCode, input: &str, positions: &[(usize, usize)]) -> String {
+    match positions.first() {
+        Some(&(start, _)) => {
+            let prefix = input.get(..start.min(input.len())).unwrap_or(input);
+            i18n::tr(
+                "diagnostic-at-column",
+                "error[{code}] at column {column}",
+                &[
+                    ("code", code.code()),
+                    ("column", &(prefix.chars().count() + 1).to_string()),
+                ],
+            )
+        }
+        None => i18n::tr("diagnostic", "error[{code}]", &[("code", code.code())]),
+    }
+}
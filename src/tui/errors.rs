@@ -10,13 +10,16 @@
 /// AppError -> Application error enum
 /// SyntaxError -> Syntax error enum
 /// SemanticError -> Semantic error enum
+/// Each error's code() -> The error_catalog code it reports
 /// Other helper functions:
 ///      --- ---
 ///      extract_user_text -> Extract the user text from the token
 ///      make_user_friendly_for_completion -> Make technical terms more user-friendly for completion
 ///      make_user_friendly -> Make technical terms more user-friendly
+///      invalid_context_message -> Render an invalid-token or invalid-value message
 ///      --- ---
 /// --- ---
+use crate::tui::error_catalog::{self, ErrorCode};
 use std::error::Error;
 use std::fmt::{Display, Formatter};
 
@@ -74,6 +77,7 @@ impl Display for TUIError {
 /// SyntaxError -> Syntax error
 /// SemanticError -> Semantic error
 /// UnrecognizedTokens -> Unrecognized tokens
+/// UnclosedString -> A string without its closing quote
 /// --- ---
 ///
 /// Implemented Traits:
@@ -89,6 +93,38 @@ pub enum AppError {
     SyntaxError(SyntaxError),
     SemanticError(SemanticError),
     UnrecognizedTokens(String, Vec<(usize, usize)>),
+    UnclosedString(String, Vec<(usize, usize)>),
+}
+
+/// AppError Implementation
+///
+/// Methods:
+/// --- ---
+/// code -> Get the error code this error reports
+/// --- ---
+///
+impl AppError {
+    /// Get the error code this error reports
+    ///
+    /// Parameters:
+    /// --- ---
+    /// self -> The AppError
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Option<ErrorCode> -> The error code, or None for AppError::Empty
+    /// --- ---
+    ///
+    pub fn code(&self) -> Option<ErrorCode> {
+        match self {
+            AppError::Empty => None,
+            AppError::SyntaxError(e) => Some(e.code()),
+            AppError::SemanticError(e) => Some(e.code()),
+            AppError::UnrecognizedTokens(..) => Some(ErrorCode::UnrecognizedCharacter),
+            AppError::UnclosedString(..) => Some(ErrorCode::UnclosedString),
+        }
+    }
 }
 
 /// AppError Display Trait Implementation
@@ -111,6 +147,7 @@ impl Display for AppError {
             AppError::SyntaxError(msg) => write!(f, "Syntax error: {}", msg),
             AppError::SemanticError(msg) => write!(f, "Semantic error: {}", msg),
             AppError::UnrecognizedTokens(msg, _) => write!(f, "Unrecognized tokens: {}", msg),
+            AppError::UnclosedString(msg, _) => write!(f, "Unclosed string: {}", msg),
         }
     }
 }
@@ -120,6 +157,10 @@ impl Display for AppError {
 /// SemanticError types:
 /// --- ---
 /// InvalidContext -> Invalid semantic context
+/// UnknownValue -> A value that none of the downloaded classes have (e.g., a misspelled subject)
+/// OutOfRange -> A number a field can't hold (e.g., credit hours = 45)
+/// NeverMatches -> A comparison no number in a field's range satisfies (e.g., enrollment < 0)
+/// InvalidPattern -> A "matches" pattern that isn't a valid regular expression
 /// --- ---
 ///
 /// Implemented Traits:
@@ -136,6 +177,27 @@ pub enum SemanticError {
         context: String,
         suggestions: Vec<String>,
     },
+    UnknownValue {
+        value: String,
+        field: String,
+        suggestions: Vec<String>,
+    },
+    OutOfRange {
+        value: String,
+        field: String,
+        min: i64,
+        max: i64,
+    },
+    NeverMatches {
+        comparison: String,
+        field: String,
+        min: i64,
+        max: i64,
+    },
+    InvalidPattern {
+        pattern: String,
+        reason: String,
+    },
 }
 
 /// SemanticError Display Trait Implementation
@@ -158,29 +220,116 @@ impl Display for SemanticError {
                 token,
                 context,
                 suggestions,
+            } => write!(
+                f,
+                "{}",
+                invalid_context_message("invalid_value", token, context, suggestions)
+            ),
+            SemanticError::UnknownValue {
+                value,
+                field,
+                suggestions,
             } => {
-                let clean_token = extract_user_text(token);
-                let user_friendly_context = make_user_friendly(context);
-
+                // suggestions are real values, so they keep their case
                 if suggestions.is_empty() {
                     write!(
                         f,
-                        "'{}' is not valid here ({})",
-                        clean_token, user_friendly_context
+                        "{}",
+                        error_catalog::message(
+                            "unknown_value",
+                            &[("value", value.as_str()), ("field", field.as_str())]
+                        )
                     )
                 } else {
-                    let user_friendly_suggestions: Vec<String> = suggestions
-                        .iter()
-                        .map(|s| format!("'{}'", make_user_friendly(s)))
-                        .collect();
                     write!(
                         f,
-                        "'{}' is not valid here. Try: {}",
-                        clean_token,
-                        user_friendly_suggestions.join(", ")
+                        "{}",
+                        error_catalog::message(
+                            "unknown_value_try",
+                            &[
+                                ("value", value.as_str()),
+                                ("field", field.as_str()),
+                                ("suggestions", &suggestions.join(", ")),
+                            ]
+                        )
                     )
                 }
             }
+            SemanticError::OutOfRange {
+                value,
+                field,
+                min,
+                max,
+            } => write!(
+                f,
+                "{}",
+                error_catalog::message(
+                    "value_out_of_range",
+                    &[
+                        ("value", value.as_str()),
+                        ("field", field.as_str()),
+                        ("min", &min.to_string()),
+                        ("max", &max.to_string()),
+                    ]
+                )
+            ),
+            SemanticError::NeverMatches {
+                comparison,
+                field,
+                min,
+                max,
+            } => write!(
+                f,
+                "{}",
+                error_catalog::message(
+                    "comparison_never_matches",
+                    &[
+                        ("comparison", comparison.as_str()),
+                        ("field", field.as_str()),
+                        ("min", &min.to_string()),
+                        ("max", &max.to_string()),
+                    ]
+                )
+            ),
+            SemanticError::InvalidPattern { pattern, reason } => write!(
+                f,
+                "{}",
+                error_catalog::message(
+                    "invalid_pattern",
+                    &[("pattern", pattern.as_str()), ("reason", reason.as_str())]
+                )
+            ),
+        }
+    }
+}
+
+/// SemanticError Implementation
+///
+/// Methods:
+/// --- ---
+/// code -> Get the error code this error reports
+/// --- ---
+///
+impl SemanticError {
+    /// Get the error code this error reports
+    ///
+    /// Parameters:
+    /// --- ---
+    /// self -> The SemanticError
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// ErrorCode -> The error code
+    /// --- ---
+    ///
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            SemanticError::InvalidContext { .. } => ErrorCode::InvalidValue,
+            SemanticError::UnknownValue { .. } => ErrorCode::UnknownValue,
+            SemanticError::OutOfRange { .. }
+            | SemanticError::NeverMatches { .. }
+            | SemanticError::InvalidPattern { .. } => ErrorCode::InvalidValue,
         }
     }
 }
@@ -194,6 +343,7 @@ impl Display for SemanticError {
 /// EmptyQuery -> Empty query
 /// ExpectedAfter -> Expected after
 /// InvalidContext -> Invalid context
+/// TooDeeplyNested -> Parentheses or 'not's nested past the parser's limit
 /// --- ---
 ///
 /// Implemented Traits:
@@ -218,6 +368,9 @@ pub enum SyntaxError {
         context: String,
         suggestions: Vec<String>,
     },
+    TooDeeplyNested {
+        max: usize,
+    },
 }
 
 /// SyntaxError Display Trait Implementation
@@ -235,10 +388,12 @@ pub enum SyntaxError {
 ///
 impl Display for SyntaxError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        match self {
-            SyntaxError::MissingToken(token) => write!(f, "Missing: {}", token),
-            SyntaxError::UnclosedParenthesis => write!(f, "Missing closing parenthesis ')'"),
-            SyntaxError::EmptyQuery => write!(f, "Please enter a query to search"),
+        let message = match self {
+            SyntaxError::MissingToken(token) => {
+                error_catalog::message("missing_token", &[("expected", token)])
+            }
+            SyntaxError::UnclosedParenthesis => error_catalog::message("unclosed_parenthesis", &[]),
+            SyntaxError::EmptyQuery => error_catalog::message("empty_query", &[]),
             SyntaxError::ExpectedAfter {
                 expected,
                 after,
@@ -248,66 +403,117 @@ impl Display for SyntaxError {
                     .iter()
                     .map(|s| format!("'{}'", make_user_friendly(s)))
                     .collect();
-
+                let expected_list = user_friendly_expected.join(", ");
                 let user_friendly_after = make_user_friendly(after);
 
                 // special case for start of query - use "Please start with" instead of "After..."
-                if after == "start of query" {
-                    if user_friendly_expected.len() == 1 {
-                        write!(f, "Please start with: {}", user_friendly_expected[0])
-                    } else {
-                        write!(
-                            f,
-                            "Please start with one of: {}",
-                            user_friendly_expected.join(", ")
-                        )
-                    }
-                } else if user_friendly_expected.len() == 1 {
-                    write!(
-                        f,
-                        "After '{}', please add: {}",
-                        user_friendly_after, user_friendly_expected[0]
-                    )
-                } else {
-                    write!(
-                        f,
-                        "After '{}', please add one of: {}",
-                        user_friendly_after,
-                        user_friendly_expected.join(", ")
-                    )
-                }
+                let key = match (after == "start of query", user_friendly_expected.len() == 1) {
+                    (true, true) => "start_with",
+                    (true, false) => "start_with_one_of",
+                    (false, true) => "expected_after",
+                    (false, false) => "expected_after_one_of",
+                };
+                error_catalog::message(
+                    key,
+                    &[
+                        ("after", &user_friendly_after),
+                        ("expected", &expected_list),
+                    ],
+                )
             }
             SyntaxError::InvalidContext {
                 token,
                 context,
                 suggestions,
-            } => {
-                let clean_token = extract_user_text(token);
-                let user_friendly_context = make_user_friendly(context);
-
-                if suggestions.is_empty() {
-                    write!(
-                        f,
-                        "'{}' is not valid here ({})",
-                        clean_token, user_friendly_context
-                    )
-                } else {
-                    let user_friendly_suggestions: Vec<String> = suggestions
-                        .iter()
-                        .map(|s| format!("'{}'", make_user_friendly(s)))
-                        .collect();
-                    write!(
-                        f,
-                        "'{}' is not valid here. Try: {}",
-                        clean_token,
-                        user_friendly_suggestions.join(", ")
-                    )
-                }
+            } => invalid_context_message("invalid_token", token, context, suggestions),
+            SyntaxError::TooDeeplyNested { max } => {
+                error_catalog::message("nesting_too_deep", &[("max", &max.to_string())])
             }
+        };
+        write!(f, "{}", message)
+    }
+}
+
+/// SyntaxError Implementation
+///
+/// Methods:
+/// --- ---
+/// code -> Get the error code this error reports
+/// --- ---
+///
+impl SyntaxError {
+    /// Get the error code this error reports
+    ///
+    /// Parameters:
+    /// --- ---
+    /// self -> The SyntaxError
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// ErrorCode -> The error code
+    /// --- ---
+    ///
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            SyntaxError::MissingToken(_) => ErrorCode::MissingToken,
+            SyntaxError::UnclosedParenthesis => ErrorCode::UnclosedParenthesis,
+            SyntaxError::EmptyQuery => ErrorCode::EmptyQuery,
+            SyntaxError::ExpectedAfter { .. } => ErrorCode::ExpectedAfter,
+            SyntaxError::InvalidContext { .. } => ErrorCode::InvalidToken,
+            SyntaxError::TooDeeplyNested { .. } => ErrorCode::NestingTooDeep,
         }
     }
 }
 
+/// Render an invalid-token or invalid-value message
+///
+/// Uses the "{key}_try" template when there are suggestions to offer
+///
+/// Parameters:
+/// --- ---
+/// key -> The catalog key ("invalid_token" or "invalid_value")
+/// token -> The offending token description (e.g., "T_IDENTIFIER ('man')")
+/// context -> Where the token appeared (e.g., "query start")
+/// suggestions -> What would have been accepted instead
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> The rendered message
+/// --- ---
+///
+fn invalid_context_message(
+    key: &str,
+    token: &str,
+    context: &str,
+    suggestions: &[String],
+) -> String {
+    let clean_token = extract_user_text(token);
+
+    if suggestions.is_empty() {
+        error_catalog::message(
+            key,
+            &[
+                ("token", &clean_token),
+                ("context", &make_user_friendly(context)),
+            ],
+        )
+    } else {
+        let user_friendly_suggestions: Vec<String> = suggestions
+            .iter()
+            .map(|s| format!("'{}'", make_user_friendly(s)))
+            .collect();
+        error_catalog::message(
+            &format!("{}_try", key),
+            &[
+                ("token", &clean_token),
+                ("suggestions", &user_friendly_suggestions.join(", ")),
+            ],
+        )
+    }
+}
+
 /// Helper function to extract the actual user text from technical token descriptions
 ///
 /// Parameters:
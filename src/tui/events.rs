@@ -0,0 +1,138 @@
+/// src/tui/events.rs
+///
+/// Events driving the TUI's update loop
+///
+/// Every change to the app's state goes through TuiApp::update as an AppEvent: keys, pastes,
+/// and resizes read from the terminal, the actions widgets emit for keys, timer ticks, and the results of
+/// tasks running in background threads, which send their events through the EventQueue
+///
+/// Contains:
+/// --- ---
+/// AppEvent -> A message for the app's update loop
+/// EventQueue -> Channel that background tasks send events to
+/// Helper functions:
+///     --- ---
+///     paste_keys -> Turn pasted text into the keys that would type it
+///     --- ---
+/// --- ---
+use crate::tui::widgets::KeyAction;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, Sender};
+
+/// A message for the app's update loop
+///
+/// Variants:
+/// --- ---
+/// Key -> A key pressed in the terminal
+/// Paste -> Text pasted into the terminal, in one piece when the terminal supports bracketed paste
+/// Action -> An action emitted by a widget (navigate, go back, show a toast, exit)
+/// Tick -> One pass of the event loop, advances timers
+/// Resize -> The terminal was resized to a width and height
+/// SyncRetried -> The background sync found the database busy and is retrying
+/// SyncFinished -> The background sync finished, with the synced database or an error
+/// WatchHooksFailed -> The watch hooks started after a sync finished, with a message for each
+///                     hook that failed
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for AppEvent
+/// --- ---
+///
+#[derive(Debug)]
+pub enum AppEvent {
+    Key(KeyEvent),
+    Paste(String),
+    Action(KeyAction),
+    Tick,
+    Resize(u16, u16),
+    SyncRetried(String),
+    SyncFinished(Result<PathBuf, String>),
+    WatchHooksFailed(Vec<String>),
+}
+
+/// Channel that background tasks send events to
+///
+/// The app owns the queue and hands out senders to the threads it starts. Events sent are
+/// applied in order on the next tick, so a task never touches the app's state itself
+///
+/// Fields:
+/// --- ---
+/// sender -> Sending side, cloned for each task
+/// receiver -> Receiving side, drained by the app
+/// --- ---
+///
+pub struct EventQueue {
+    sender: Sender<AppEvent>,
+    receiver: Receiver<AppEvent>,
+}
+
+impl EventQueue {
+    /// Create an empty queue
+    ///
+    /// Returns:
+    /// --- ---
+    /// EventQueue -> A queue with no events
+    /// --- ---
+    ///
+    pub fn new() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self { sender, receiver }
+    }
+
+    /// Get a sender for a background task
+    ///
+    /// Returns:
+    /// --- ---
+    /// Sender<AppEvent> -> Sends events to this queue, from any thread
+    /// --- ---
+    ///
+    pub fn sender(&self) -> Sender<AppEvent> {
+        self.sender.clone()
+    }
+
+    /// Take the next event waiting in the queue
+    ///
+    /// Returns:
+    /// --- ---
+    /// Option<AppEvent> -> The oldest event sent, None if nothing is waiting
+    /// --- ---
+    ///
+    pub fn poll(&self) -> Option<AppEvent> {
+        self.receiver.try_recv().ok()
+    }
+}
+
+impl Default for EventQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Turn pasted text into the keys that would type it
+///
+/// Parameters:
+/// --- ---
+/// text -> The pasted text
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Vec<KeyEvent> -> A key per character, with line breaks as Enter ("\r\n" is one) and
+///                  tabs as Tab
+/// --- ---
+///
+pub fn paste_keys(text: &str) -> Vec<KeyEvent> {
+    text.replace("\r\n", "\n")
+        .chars()
+        .map(|c| {
+            let code = match c {
+                '\n' | '\r' => KeyCode::Enter,
+                '\t' => KeyCode::Tab,
+                c => KeyCode::Char(c),
+            };
+            KeyEvent::new(code, KeyModifiers::NONE)
+        })
+        .collect()
+}
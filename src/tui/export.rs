@@ -0,0 +1,221 @@
+/// src/tui/export.rs
+///
+/// Schedule exports for registration
+///
+/// Responsible for the forms a schedule is taken to the registrar's portal in: the CRNs
+/// (registration numbers) of its sections as one line to paste, a printed table with each
+/// section's CRN first, and copying text to the clipboard through the terminal
+///
+/// Contains:
+/// --- ---
+/// ScheduleCrns -> CRNs of a schedule's sections
+///      Methods:
+///      --- ---
+///      from_classes -> Collect the CRNs of a schedule
+///      clipboard_text -> The CRNs as one line to paste
+///      summary -> What was copied, for a toast
+///      --- ---
+/// Helper functions:
+///      --- ---
+///      export_lines -> A schedule as printed lines, CRN first
+///      osc52_sequence -> Terminal escape sequence that sets the clipboard
+///      copy_to_clipboard -> Copy text to the clipboard through the terminal
+///      --- ---
+/// --- ---
+///
+use crate::data::sql::Class;
+use crate::utils::time::format_12_hour;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use std::io::Write;
+
+/// CRNs of a schedule's sections
+///
+/// Fields:
+/// --- ---
+/// crns -> CRNs in schedule order
+/// missing -> Sections without a CRN in the synced data, e.g., "CMPT 101-001"
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for ScheduleCrns
+/// Clone -> Clone trait for ScheduleCrns
+/// PartialEq -> PartialEq trait for ScheduleCrns
+/// --- ---
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduleCrns {
+    pub crns: Vec<String>,
+    pub missing: Vec<String>,
+}
+
+impl ScheduleCrns {
+    /// Collect the CRNs of a schedule
+    ///
+    /// Arguments:
+    /// --- ---
+    /// classes -> The schedule's classes
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// ScheduleCrns -> Each section's CRN, or its label if it has none
+    /// --- ---
+    ///
+    pub fn from_classes(classes: &[Class]) -> Self {
+        let mut crns = Vec::new();
+        let mut missing = Vec::new();
+        for class in classes {
+            match class.crn.as_deref().map(str::trim) {
+                Some(crn) if !crn.is_empty() => crns.push(crn.to_string()),
+                _ => missing.push(section_label(class)),
+            }
+        }
+        Self { crns, missing }
+    }
+
+    /// The CRNs as one line to paste
+    ///
+    /// Arguments: None
+    ///
+    /// Returns:
+    /// --- ---
+    /// String -> CRNs separated by spaces, e.g., "10042 10518 11003"
+    /// --- ---
+    ///
+    pub fn clipboard_text(&self) -> String {
+        self.crns.join(" ")
+    }
+
+    /// What was copied, for a toast
+    ///
+    /// Arguments: None
+    ///
+    /// Returns:
+    /// --- ---
+    /// String -> e.g., "Copied 2 CRNs: 10042 10518 (no CRN for ACCT 203N-111)"
+    /// --- ---
+    ///
+    pub fn summary(&self) -> String {
+        let mut summary = format!(
+            "Copied {} CRN{}: {}",
+            self.crns.len(),
+            if self.crns.len() == 1 { "" } else { "s" },
+            self.clipboard_text()
+        );
+        if !self.missing.is_empty() {
+            summary.push_str(&format!(" (no CRN for {})", self.missing.join(", ")));
+        }
+        summary
+    }
+}
+
+/// Label of a section, e.g., "CMPT 101-001"
+///
+/// Arguments:
+/// --- ---
+/// class -> The section
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> Subject, course number, and section
+/// --- ---
+///
+fn section_label(class: &Class) -> String {
+    format!(
+        "{} {}-{}",
+        class.subject_code, class.course_number, class.section_sequence
+    )
+}
+
+/// A schedule as printed lines, CRN first
+///
+/// Arguments:
+/// --- ---
+/// classes -> The schedule's classes
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Vec<String> -> A header, one line per section (CRN, section, meetings, title), and
+///                the CRNs on one line to paste
+/// --- ---
+///
+pub fn export_lines(classes: &[Class]) -> Vec<String> {
+    let mut lines = vec![format!(
+        "{:<7} {:<14} {:<32} {}",
+        "CRN", "Section", "Meetings", "Title"
+    )];
+    for class in classes {
+        let meetings = if class.meeting_times.is_empty() {
+            "TBA".to_string()
+        } else {
+            class
+                .meeting_times
+                .iter()
+                .map(|meeting| {
+                    format!(
+                        "{} {}-{}",
+                        meeting.days.join(" "),
+                        format_12_hour(meeting.start_minutes),
+                        format_12_hour(meeting.end_minutes)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        lines.push(format!(
+            "{:<7} {:<14} {:<32} {}",
+            class.crn.as_deref().unwrap_or("-"),
+            section_label(class),
+            meetings,
+            class.title
+        ));
+    }
+
+    let crns = ScheduleCrns::from_classes(classes);
+    lines.push(String::new());
+    lines.push(format!("CRNs: {}", crns.clipboard_text()));
+    lines
+}
+
+/// Terminal escape sequence that sets the clipboard (OSC 52)
+///
+/// Arguments:
+/// --- ---
+/// text -> The text to copy
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> The sequence, with the text base64-encoded
+/// --- ---
+///
+/// Terminals that support it (and tmux with set-clipboard on) copy the text to the system
+/// clipboard, also over SSH; others ignore the sequence
+///
+pub fn osc52_sequence(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", STANDARD.encode(text))
+}
+
+/// Copy text to the clipboard through the terminal
+///
+/// Arguments:
+/// --- ---
+/// text -> The text to copy
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<(), String> -> Ok once the sequence is written to the terminal
+/// --- ---
+///
+pub fn copy_to_clipboard(text: &str) -> Result<(), String> {
+    let mut stdout = std::io::stdout();
+    stdout
+        .write_all(osc52_sequence(text).as_bytes())
+        .and_then(|_| stdout.flush())
+        .map_err(|e| format!("Failed to copy to the clipboard: {}", e))
+}
@@ -2,8 +2,14 @@
 ///
 /// Module for storing terminal user interface logic. Used for our TUI.
 pub mod app;
+pub mod error_catalog;
 pub mod errors;
+pub mod events;
+pub mod export;
+pub mod notifier;
 pub mod save;
+pub mod session;
+pub mod share;
 pub mod state;
 pub mod themes;
 pub mod widgets;
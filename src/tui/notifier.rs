@@ -0,0 +1,161 @@
+/// src/tui/notifier.rs
+///
+/// Desktop notifications for watch-list events
+///
+/// Responsible for turning watch-list events into OS desktop notifications when they are
+/// turned on in Settings. A sync can report many sections at once, so messages are queued
+/// and sent together, at most once per interval; messages that arrive in between wait for
+/// the next tick after the interval
+///
+/// Contains:
+/// --- ---
+/// NOTIFY_INTERVAL -> Shortest time between two desktop notifications
+/// NOTIFY_MAX_LINES -> Most messages listed in one notification
+/// Notifier -> Rate-limited desktop notifier
+///      Methods:
+///      --- ---
+///      new -> Create a notifier with a minimum interval
+///      set_enabled -> Turn notifications on or off
+///      push -> Queue messages
+///      take_due -> Take the queued messages if a notification may be sent
+///      flush -> Send the queued messages if a notification may be sent
+///      --- ---
+/// --- ---
+///
+use notify_rust::Notification;
+use std::time::{Duration, Instant};
+
+/// Shortest time between two desktop notifications
+pub const NOTIFY_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Most messages listed in one notification, the rest are counted
+pub const NOTIFY_MAX_LINES: usize = 4;
+
+/// Rate-limited desktop notifier
+///
+/// Fields:
+/// --- ---
+/// enabled -> Whether notifications are sent (off by default)
+/// interval -> Shortest time between two notifications
+/// last_sent -> When the last notification was sent
+/// pending -> Messages waiting to be sent
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for Notifier
+/// --- ---
+///
+#[derive(Debug)]
+pub struct Notifier {
+    pub enabled: bool,
+    interval: Duration,
+    last_sent: Option<Instant>,
+    pending: Vec<String>,
+}
+
+impl Notifier {
+    /// Create a notifier with a minimum interval
+    ///
+    /// Arguments:
+    /// --- ---
+    /// interval -> Shortest time between two notifications
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Notifier -> A disabled notifier with nothing queued
+    /// --- ---
+    ///
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            enabled: false,
+            interval,
+            last_sent: None,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Turn notifications on or off
+    ///
+    /// Arguments:
+    /// --- ---
+    /// enabled -> Whether notifications are sent
+    /// --- ---
+    ///
+    /// Returns: None
+    ///
+    /// Turning them off drops the queued messages
+    ///
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.pending.clear();
+        }
+    }
+
+    /// Queue messages
+    ///
+    /// Arguments:
+    /// --- ---
+    /// messages -> The messages, ignored while notifications are off
+    /// --- ---
+    ///
+    /// Returns: None
+    ///
+    pub fn push(&mut self, messages: impl IntoIterator<Item = String>) {
+        if self.enabled {
+            self.pending.extend(messages);
+        }
+    }
+
+    /// Take the queued messages if a notification may be sent
+    ///
+    /// Arguments:
+    /// --- ---
+    /// now -> The current time
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Option<String> -> The notification body, None if nothing is queued or the last
+    ///                   notification was sent less than an interval ago
+    /// --- ---
+    ///
+    pub fn take_due(&mut self, now: Instant) -> Option<String> {
+        if self.pending.is_empty() {
+            return None;
+        }
+        if let Some(last) = self.last_sent {
+            if now.saturating_duration_since(last) < self.interval {
+                return None;
+            }
+        }
+        self.last_sent = Some(now);
+
+        let messages = std::mem::take(&mut self.pending);
+        let mut lines: Vec<String> = messages.iter().take(NOTIFY_MAX_LINES).cloned().collect();
+        if messages.len() > NOTIFY_MAX_LINES {
+            lines.push(format!("and {} more", messages.len() - NOTIFY_MAX_LINES));
+        }
+        Some(lines.join("\n"))
+    }
+
+    /// Send the queued messages if a notification may be sent
+    ///
+    /// Arguments: None
+    ///
+    /// Returns: None
+    ///
+    /// A failed notification (e.g., no notification daemon) is dropped, the same events
+    /// are also shown as toasts
+    ///
+    pub fn flush(&mut self) {
+        if let Some(body) = self.take_due(Instant::now()) {
+            let _ = Notification::new()
+                .summary("ClassQL watch list")
+                .body(&body)
+                .show();
+        }
+    }
+}
@@ -2,8 +2,25 @@
 ///
 /// Schedule save/load functionality
 ///
-/// Handles saving and loading schedules to/from .sav files
-use crate::data::sql::{self, Class};
+/// Handles saving and loading schedules to/from .sav files. Each class is saved with a
+/// snapshot of its times, professor, and title, so a schedule shows as it was saved after
+/// later syncs change the catalog, and the changes are listed instead. The watch list,
+/// session, profiles, campus aliases, and the settings each schedule was generated with are
+/// kept in the app database (see data::app_store)
+use crate::data::app_store::{AppStore, Table, DEFAULT_KEY};
+use crate::data::archive::KeptSection;
+use crate::data::campus_aliases::CampusAliases;
+use crate::data::profiles::Profiles;
+use crate::data::sql::{self, Class, Meeting};
+use crate::data::watch::WatchList;
+use crate::dsl::codegen::SESSION_SQL;
+use crate::tui::session::Session;
+use crate::tui::widgets::schedule::{find_conflicting_classes, GenerationSettings};
+use crate::utils::time::format_12_hour;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::borrow::Borrow;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -16,6 +33,9 @@ use std::path::{Path, PathBuf};
 /// school_id -> School ID the schedule belongs to
 /// term_id -> Term ID the schedule belongs to
 /// classes -> Classes in the schedule
+/// issues -> Changes found when re-validating against the current data (empty if none)
+/// settings -> Settings the schedule was generated with (None if it was saved before they
+///             were kept, or came from a share code)
 /// --- ---
 ///
 /// Implemented Traits:
@@ -31,6 +51,33 @@ pub struct SavedSchedule {
     pub school_id: Option<String>,
     pub term_id: Option<String>,
     pub classes: Vec<Class>,
+    pub issues: Vec<String>,
+    pub settings: Option<GenerationSettings>,
+}
+
+/// Number of tab-separated fields in a class line with a full snapshot
+const SNAPSHOT_FIELDS: usize = 11;
+
+/// A class as recorded in a save file
+///
+/// Fields:
+/// --- ---
+/// class_id -> Class unique ID (e.g., "CMPT:101-001")
+/// meeting_times -> Meeting times at save time (None in the oldest save files)
+/// snapshot -> The section's details at save time (None in save files from before snapshots)
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for SavedClass
+/// Clone -> Clone trait for SavedClass
+/// --- ---
+///
+#[derive(Debug, Clone)]
+pub struct SavedClass {
+    pub class_id: String,
+    pub meeting_times: Option<Vec<Meeting>>,
+    pub snapshot: Option<Class>,
 }
 
 /// Get the save directory path (current working directory/save)
@@ -74,7 +121,436 @@ fn ensure_save_dir() -> Result<PathBuf, String> {
     Ok(save_dir)
 }
 
-/// Save a schedule to a .sav file
+/// The watch list, kept in the app database
+const WATCH_LIST: Table<WatchList> = Table::new("watch_list");
+
+/// The session left at the last exit
+const SESSION: Table<Session> = Table::new("session");
+
+/// The workspace profiles
+const PROFILES: Table<Profiles> = Table::new("profiles");
+
+/// The campus aliases defined in Settings
+const CAMPUS_ALIASES: Table<CampusAliases> = Table::new("campus_aliases");
+
+/// The settings each saved schedule was generated with, keyed by the schedule's timestamp
+const GENERATION_SETTINGS: Table<GenerationSettings> = Table::new("generation_settings");
+
+/// Get the path of the app database (save/app.db)
+///
+/// Parameters:
+/// --- ---
+/// None
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<PathBuf, String> -> Path to the app database or error
+/// --- ---
+///
+pub fn app_store_path() -> Result<PathBuf, String> {
+    Ok(get_save_dir()?.join("app.db"))
+}
+
+/// Open the app database, creating the save directory and the database if needed
+///
+/// Parameters:
+/// --- ---
+/// None
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<AppStore, String> -> The app database or error
+/// --- ---
+///
+fn open_app_store() -> Result<AppStore, String> {
+    ensure_save_dir()?;
+    AppStore::open(&app_store_path()?)
+}
+
+/// Load a value kept in the app database
+///
+/// Before the app database, each value had its own JSON file in the save directory. A file
+/// left from then is loaded if the database has no value yet, and copied into it
+///
+/// Parameters:
+/// --- ---
+/// table -> The table holding the value
+/// legacy_file -> Name of the value's old JSON file (e.g., "watch.json")
+/// load_legacy -> Reads the old file
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<T, String> -> The value (the default if there is none yet) or error
+/// --- ---
+///
+fn load_record<T>(
+    table: Table<T>,
+    legacy_file: &str,
+    load_legacy: fn(&Path) -> Result<T, String>,
+) -> Result<T, String>
+where
+    T: Serialize + DeserializeOwned + Default,
+{
+    // reading never creates the database, so a launch that saves nothing leaves no files
+    if app_store_path()?.exists() {
+        if let Some(value) = open_app_store()?.get(table, DEFAULT_KEY)? {
+            return Ok(value);
+        }
+    }
+    let legacy_path = get_save_dir()?.join(legacy_file);
+    if !legacy_path.exists() {
+        return Ok(T::default());
+    }
+    let value = load_legacy(&legacy_path)?;
+    open_app_store()?.put(table, DEFAULT_KEY, &value)?;
+    Ok(value)
+}
+
+/// Load the watch list
+///
+/// Parameters:
+/// --- ---
+/// None
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<WatchList, String> -> The watch list (empty if nothing is watched) or error
+/// --- ---
+///
+pub fn load_watch_list() -> Result<WatchList, String> {
+    load_record(WATCH_LIST, "watch.json", WatchList::load)
+}
+
+/// Save the watch list
+///
+/// Parameters:
+/// --- ---
+/// watch_list -> The watch list to save
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<(), String> -> Success or error message
+/// --- ---
+///
+pub fn save_watch_list(watch_list: &WatchList) -> Result<(), String> {
+    open_app_store()?.put(WATCH_LIST, DEFAULT_KEY, watch_list)
+}
+
+/// Load the session left at the last exit
+///
+/// Parameters:
+/// --- ---
+/// None
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<Session, String> -> The session (a fresh one on the first launch) or error
+/// --- ---
+///
+pub fn load_session() -> Result<Session, String> {
+    load_record(SESSION, "session.json", Session::load)
+}
+
+/// Save the session
+///
+/// Parameters:
+/// --- ---
+/// session -> The session to save
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<(), String> -> Success or error message
+/// --- ---
+///
+pub fn save_session(session: &Session) -> Result<(), String> {
+    open_app_store()?.put(SESSION, DEFAULT_KEY, session)
+}
+
+/// Load the workspace profiles
+///
+/// Parameters:
+/// --- ---
+/// None
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<Profiles, String> -> The profiles (none if never saved) or error
+/// --- ---
+///
+pub fn load_profiles() -> Result<Profiles, String> {
+    load_record(PROFILES, "profiles.json", Profiles::load)
+}
+
+/// Save the workspace profiles
+///
+/// Parameters:
+/// --- ---
+/// profiles -> The profiles to save
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<(), String> -> Success or error message
+/// --- ---
+///
+pub fn save_profiles(profiles: &Profiles) -> Result<(), String> {
+    open_app_store()?.put(PROFILES, DEFAULT_KEY, profiles)
+}
+
+/// Load the campus aliases
+///
+/// Parameters:
+/// --- ---
+/// None
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<CampusAliases, String> -> The aliases (none if never saved) or error
+/// --- ---
+///
+pub fn load_campus_aliases() -> Result<CampusAliases, String> {
+    // aliases came after the app database, so there is no JSON file to bring over
+    if !app_store_path()?.exists() {
+        return Ok(CampusAliases::default());
+    }
+    Ok(open_app_store()?
+        .get(CAMPUS_ALIASES, DEFAULT_KEY)?
+        .unwrap_or_default())
+}
+
+/// Save the campus aliases
+///
+/// Parameters:
+/// --- ---
+/// aliases -> The aliases to save
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<(), String> -> Success or error message
+/// --- ---
+///
+pub fn save_campus_aliases(aliases: &CampusAliases) -> Result<(), String> {
+    open_app_store()?.put(CAMPUS_ALIASES, DEFAULT_KEY, aliases)
+}
+
+/// Longest schedule name, in characters
+pub const MAX_SCHEDULE_NAME_LEN: usize = 40;
+
+/// Punctuation allowed in schedule names, besides letters, digits, and spaces
+const SCHEDULE_NAME_PUNCTUATION: &str = "-_.,'&()#+!:";
+
+/// Check whether a character may be used in a schedule name
+///
+/// Parameters:
+/// --- ---
+/// c -> The character
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// bool -> True for letters, digits, spaces, and SCHEDULE_NAME_PUNCTUATION
+/// --- ---
+///
+pub fn is_schedule_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == ' ' || SCHEDULE_NAME_PUNCTUATION.contains(c)
+}
+
+/// Check a schedule name and put it in its saved form
+///
+/// Parameters:
+/// --- ---
+/// name -> The name as typed
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<String, String> -> The name trimmed with runs of spaces collapsed, or why it
+///                           can't be used
+/// --- ---
+///
+pub fn validate_schedule_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Schedule name cannot be empty!".to_string());
+    }
+    if let Some(c) = name.chars().find(|c| !is_schedule_name_char(*c)) {
+        return Err(format!(
+            "'{}' can't be used in a schedule name",
+            c.escape_default()
+        ));
+    }
+    let name = name
+        .split(' ')
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    if name.chars().count() > MAX_SCHEDULE_NAME_LEN {
+        return Err(format!(
+            "Schedule names can be at most {} characters",
+            MAX_SCHEDULE_NAME_LEN
+        ));
+    }
+    Ok(name)
+}
+
+/// Find a saved schedule that already has a name
+///
+/// Parameters:
+/// --- ---
+/// names -> (timestamp, name) of each saved schedule
+/// name -> The name to look for (compared ignoring case)
+/// except -> Timestamp of a schedule to skip (e.g., the one being renamed)
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Option<u64> -> Timestamp of the schedule with that name, if any
+/// --- ---
+///
+pub fn find_name_conflict(names: &[(u64, String)], name: &str, except: Option<u64>) -> Option<u64> {
+    let name = name.to_lowercase();
+    names
+        .iter()
+        .find(|(timestamp, existing)| Some(*timestamp) != except && existing.to_lowercase() == name)
+        .map(|(timestamp, _)| *timestamp)
+}
+
+/// Make a name unique among the saved schedules
+///
+/// Parameters:
+/// --- ---
+/// names -> (timestamp, name) of each saved schedule
+/// name -> The wanted name
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> The name if it is free, otherwise the name with the first free number, e.g.,
+///           "Fall (2)"
+/// --- ---
+///
+pub fn unique_schedule_name(names: &[(u64, String)], name: &str) -> String {
+    if find_name_conflict(names, name, None).is_none() {
+        return name.to_string();
+    }
+    (2..)
+        .map(|number| format!("{} ({})", name, number))
+        .find(|candidate| find_name_conflict(names, candidate, None).is_none())
+        .unwrap_or_else(|| name.to_string())
+}
+
+/// List the names of the saved schedules
+///
+/// Reads only the first line of each save file, so it doesn't touch the database
+///
+/// Parameters:
+/// --- ---
+/// None
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<Vec<(u64, String)>, String> -> (timestamp, name) of each saved schedule or error
+/// --- ---
+///
+pub fn schedule_names() -> Result<Vec<(u64, String)>, String> {
+    let save_dir = get_save_dir()?;
+
+    if !save_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let entries =
+        fs::read_dir(&save_dir).map_err(|e| format!("Failed to read save directory: {}", e))?;
+
+    let mut names = Vec::new();
+    for entry in entries {
+        let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
+        let path = entry.path();
+        if path.extension().and_then(|s| s.to_str()) != Some("sav") {
+            continue;
+        }
+        let Some(timestamp) = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .and_then(|s| s.parse::<u64>().ok())
+        else {
+            continue;
+        };
+        if let Ok(content) = fs::read_to_string(&path) {
+            names.push((timestamp, content.lines().next().unwrap_or("").to_string()));
+        }
+    }
+
+    Ok(names)
+}
+
+/// Get the path of a save file
+///
+/// Parameters:
+/// --- ---
+/// timestamp -> Timestamp of the schedule
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<PathBuf, String> -> Path to the .sav file or error
+/// --- ---
+///
+fn schedule_path(timestamp: u64) -> Result<PathBuf, String> {
+    Ok(get_save_dir()?.join(format!("{}.sav", timestamp)))
+}
+
+/// Write a schedule to a save file
+///
+/// Parameters:
+/// --- ---
+/// file_path -> Path to the .sav file
+/// name -> Name of the schedule
+/// school_id -> School ID for the schedule
+/// term_id -> Term ID for the schedule
+/// classes -> Classes in the schedule
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<(), String> -> Success or error message
+/// --- ---
+///
+fn write_schedule<C: Borrow<Class>>(
+    file_path: &Path,
+    name: &str,
+    school_id: Option<&str>,
+    term_id: Option<&str>,
+    classes: &[C],
+) -> Result<(), String> {
+    // format:
+    // line 1: name
+    // line 2: school_id (or empty)
+    // line 3: term_id (or empty)
+    // remaining lines: class ID followed by the section's details at save time (see snapshot_line)
+    let mut content = format!("{}\n", name);
+    content.push_str(&format!("{}\n", school_id.unwrap_or("")));
+    content.push_str(&format!("{}\n", term_id.unwrap_or("")));
+    for class in classes {
+        content.push_str(&snapshot_line(class.borrow()));
+        content.push('\n');
+    }
+
+    fs::write(file_path, content).map_err(|e| format!("Failed to write save file: {}", e))
+}
+
+/// Save a schedule to a new .sav file
 ///
 /// Parameters:
 /// --- ---
@@ -86,39 +562,120 @@ fn ensure_save_dir() -> Result<PathBuf, String> {
 ///
 /// Returns:
 /// --- ---
+/// Result<u64, String> -> Timestamp of the new schedule or error message
+/// --- ---
+///
+pub fn save_schedule<C: Borrow<Class>>(
+    name: &str,
+    school_id: Option<&str>,
+    term_id: Option<&str>,
+    classes: &[C],
+) -> Result<u64, String> {
+    let save_dir = ensure_save_dir()?;
+    let mut timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_err(|e| format!("Failed to get timestamp: {}", e))?
+        .as_secs();
+
+    // two saves in the same second would otherwise share a file
+    while save_dir.join(format!("{}.sav", timestamp)).exists() {
+        timestamp += 1;
+    }
+
+    write_schedule(
+        &save_dir.join(format!("{}.sav", timestamp)),
+        name,
+        school_id,
+        term_id,
+        classes,
+    )?;
+
+    Ok(timestamp)
+}
+
+/// Replace the contents of a saved schedule, keeping its timestamp
+///
+/// Parameters:
+/// --- ---
+/// timestamp -> Timestamp of the schedule to replace
+/// name -> Name of the schedule
+/// school_id -> School ID for the schedule
+/// term_id -> Term ID for the schedule
+/// classes -> Classes in the schedule
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<(), String> -> Success or error message
+/// --- ---
+///
+pub fn overwrite_schedule<C: Borrow<Class>>(
+    timestamp: u64,
+    name: &str,
+    school_id: Option<&str>,
+    term_id: Option<&str>,
+    classes: &[C],
+) -> Result<(), String> {
+    ensure_save_dir()?;
+    write_schedule(
+        &schedule_path(timestamp)?,
+        name,
+        school_id,
+        term_id,
+        classes,
+    )
+}
+
+/// Keep the settings a saved schedule was generated with
+///
+/// Parameters:
+/// --- ---
+/// timestamp -> Timestamp of the saved schedule
+/// settings -> The settings, None to forget any kept for an earlier schedule with the
+///             same timestamp (e.g., one that was overwritten)
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<(), String> -> Success or error message
+/// --- ---
+///
+pub fn save_generation_settings(
+    timestamp: u64,
+    settings: Option<&GenerationSettings>,
+) -> Result<(), String> {
+    let key = timestamp.to_string();
+    match settings {
+        Some(settings) => open_app_store()?.put(GENERATION_SETTINGS, &key, settings),
+        None if app_store_path()?.exists() => open_app_store()?
+            .delete(GENERATION_SETTINGS, &key)
+            .map(|_| ()),
+        None => Ok(()),
+    }
+}
+
+/// Rename a saved schedule
+///
+/// Parameters:
+/// --- ---
+/// timestamp -> Timestamp of the schedule to rename
+/// name -> The new name
+/// --- ---
+///
+/// Returns:
+/// --- ---
 /// Result<(), String> -> Success or error message
 /// --- ---
 ///
-pub fn save_schedule(
-    name: &str,
-    school_id: Option<&str>,
-    term_id: Option<&str>,
-    classes: &[Class],
-) -> Result<(), String> {
-    let save_dir = ensure_save_dir()?;
-    let timestamp = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .map_err(|e| format!("Failed to get timestamp: {}", e))?
-        .as_secs();
-
-    let filename = format!("{}.sav", timestamp);
-    let file_path = save_dir.join(&filename);
-
-    // format:
-    // line 1: name
-    // line 2: school_id (or empty)
-    // line 3: term_id (or empty)
-    // remaining lines: class IDs (one per line)
-    let mut content = format!("{}\n", name);
-    content.push_str(&format!("{}\n", school_id.unwrap_or("")));
-    content.push_str(&format!("{}\n", term_id.unwrap_or("")));
-    for class in classes {
-        content.push_str(&format!("{}\n", class.unique_id()));
-    }
-
-    fs::write(&file_path, content).map_err(|e| format!("Failed to write save file: {}", e))?;
+pub fn rename_schedule(timestamp: u64, name: &str) -> Result<(), String> {
+    let file_path = schedule_path(timestamp)?;
+    let content =
+        fs::read_to_string(&file_path).map_err(|e| format!("Failed to read save file: {}", e))?;
 
-    Ok(())
+    // the name is the first line, the rest of the file stays as it is
+    let rest = content.split_once('\n').map_or("", |(_, rest)| rest);
+    fs::write(&file_path, format!("{}\n{}", name, rest))
+        .map_err(|e| format!("Failed to write save file: {}", e))
 }
 
 /// Load all saved schedules
@@ -159,9 +716,77 @@ pub fn load_all_schedules() -> Result<Vec<SavedSchedule>, String> {
     // sort by timestamp (newest first)
     saved_schedules.sort_by_key(|s| std::cmp::Reverse(s.timestamp));
 
+    // reading never creates the database, a schedule without a record just has no settings
+    if app_store_path()?.exists() {
+        let mut settings: HashMap<String, GenerationSettings> = open_app_store()?
+            .list(GENERATION_SETTINGS)?
+            .into_iter()
+            .collect();
+        for schedule in &mut saved_schedules {
+            schedule.settings = settings.remove(&schedule.timestamp.to_string());
+        }
+    }
+
     Ok(saved_schedules)
 }
 
+/// List the sections referenced by every saved schedule
+///
+/// Reads the save files directly, so it works even when the sections are missing
+/// from the database
+///
+/// Parameters:
+/// --- ---
+/// None
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<Vec<KeptSection>, String> -> Sections referenced by saved schedules or error
+/// --- ---
+///
+pub fn saved_sections() -> Result<Vec<KeptSection>, String> {
+    let save_dir = get_save_dir()?;
+
+    if !save_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut sections = Vec::new();
+
+    let entries =
+        fs::read_dir(&save_dir).map_err(|e| format!("Failed to read save directory: {}", e))?;
+
+    for entry in entries {
+        let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
+        let path = entry.path();
+
+        if path.extension().and_then(|s| s.to_str()) != Some("sav") {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(&path) else {
+            continue;
+        };
+
+        // format: line 1 = name, line 2 = school_id, line 3 = term_id, rest = class IDs (+ times)
+        let lines: Vec<&str> = content.lines().collect();
+        if lines.len() < 3 || lines[1].is_empty() {
+            continue;
+        }
+        let term_id = Some(lines[2]).filter(|t| !t.is_empty());
+        for line in lines.iter().skip(3).filter(|line| !line.is_empty()) {
+            let class_id = line.split_once('\t').map_or(*line, |(id, _)| id);
+            sections.push(KeptSection {
+                school_id: lines[1].to_string(),
+                term_id: term_id.map(str::to_string),
+                class_id: class_id.to_string(),
+            });
+        }
+    }
+
+    Ok(sections)
+}
+
 /// Load a single schedule from a file
 ///
 /// Parameters:
@@ -194,7 +819,7 @@ fn load_schedule(file_path: &Path) -> Result<SavedSchedule, String> {
         .parse::<u64>()
         .map_err(|_| "Invalid timestamp in filename".to_string())?;
 
-    // format: line 1 = name, line 2 = school_id, line 3 = term_id, rest = class IDs
+    // format: line 1 = name, line 2 = school_id, line 3 = term_id, rest = class IDs (+ times)
     if lines.len() < 3 {
         return Err("Invalid save file format".to_string());
     }
@@ -211,162 +836,32 @@ fn load_schedule(file_path: &Path) -> Result<SavedSchedule, String> {
     } else {
         Some(term_id_str.to_string())
     };
-    let class_ids: Vec<&str> = lines
+    let saved_classes: Vec<SavedClass> = lines
         .iter()
         .skip(3)
         .filter(|line| !line.is_empty())
-        .copied()
+        .map(|line| parse_saved_class(line))
+        .collect();
+    let class_ids: Vec<&str> = saved_classes
+        .iter()
+        .map(|saved| saved.class_id.as_str())
         .collect();
 
-    // load classes from database by their unique IDs
+    // load the current data of the classes by their unique IDs
     let mut classes = Vec::new();
+    let mut issues = Vec::new();
     if !class_ids.is_empty() {
-        // use test db if school_id is "_test", otherwise use synced db or default
-        let db_path = if school_id.as_deref() == Some("_test") {
-            sql::get_test_db_path()
-        } else {
-            sql::get_default_db_path()
-        };
-
-        // build SQL query to get classes by their unique IDs
-        // unique_id format is "SUBJECT:COURSE-SECTION"
-        let mut conditions = Vec::new();
-
-        for class_id in &class_ids {
-            // parse the unique_id format: "SUBJECT:COURSE-SECTION"
-            let parts: Vec<&str> = class_id.split(':').collect();
-            if parts.len() == 2 {
-                let subject = parts[0];
-                let rest: Vec<&str> = parts[1].split('-').collect();
-                if rest.len() == 2 {
-                    let course = rest[0];
-                    let section = rest[1];
-
-                    // escape single quotes in values (SQL injection protection)
-                    let subject_escaped = subject.replace("'", "''");
-                    let course_escaped = course.replace("'", "''");
-                    let section_escaped = section.replace("'", "''");
-
-                    // use table aliases to avoid ambiguous column names
-                    // s = sections, c = courses
-                    conditions.push(format!(
-                        "(s.subject_code = '{}' AND s.course_number = '{}' AND s.sequence = '{}')",
-                        subject_escaped, course_escaped, section_escaped
-                    ));
-                }
-            }
-        }
-
-        if !conditions.is_empty() {
-            // build additional filters for school and term
-            let mut filters = Vec::new();
-            if let Some(ref sid) = school_id {
-                if sid != "_test" {
-                    filters.push(format!("s.school_id = '{}'", sid.replace("'", "''")));
-                }
-            }
-            if let Some(ref tid) = term_id {
-                filters.push(format!(
-                    "s.term_collection_id = '{}'",
-                    tid.replace("'", "''")
-                ));
+        match load_classes_by_id(school_id.as_deref(), term_id.as_deref(), &class_ids) {
+            Ok(class_map) => {
+                (classes, issues) = resolve_saved_classes(&saved_classes, class_map);
             }
-
-            // combine class conditions with school/term filters
-            let class_conditions = conditions.join(" OR ");
-            let where_clause = if filters.is_empty() {
-                class_conditions
-            } else {
-                format!("({}) AND {}", class_conditions, filters.join(" AND "))
-            };
-
-            // query sections table with joins
-            let sql = format!(
-                "SELECT \
-                    c.subject_code, \
-                    c.number AS course_number, \
-                    c.title, \
-                    c.description, \
-                    c.credit_hours, \
-                    c.prerequisites, \
-                    c.corequisites, \
-                    s.sequence AS section_sequence, \
-                    s.max_enrollment, \
-                    s.enrollment, \
-                    s.instruction_method, \
-                    s.campus, \
-                    p.name AS professor_name, \
-                    p.email_address AS professor_email, \
-                    GROUP_CONCAT( \
-                        (CASE WHEN mt.is_monday = 1 THEN 'M' ELSE '' END || \
-                         CASE WHEN mt.is_tuesday = 1 THEN 'T' ELSE '' END || \
-                         CASE WHEN mt.is_wednesday = 1 THEN 'W' ELSE '' END || \
-                         CASE WHEN mt.is_thursday = 1 THEN 'TH' ELSE '' END || \
-                         CASE WHEN mt.is_friday = 1 THEN 'F' ELSE '' END || \
-                         CASE WHEN mt.is_saturday = 1 THEN 'S' ELSE '' END || \
-                         CASE WHEN mt.is_sunday = 1 THEN 'SU' ELSE '' END) || \
-                        ':' || mt.start_minutes || '-' || mt.end_minutes, \
-                        '|' \
-                    ) AS meeting_times, \
-                    GROUP_CONCAT(DISTINCT mt.meeting_type) AS meeting_type, \
-                    MAX(mt.is_monday) AS is_monday, \
-                    MAX(mt.is_tuesday) AS is_tuesday, \
-                    MAX(mt.is_wednesday) AS is_wednesday, \
-                    MAX(mt.is_thursday) AS is_thursday, \
-                    MAX(mt.is_friday) AS is_friday, \
-                    MAX(mt.is_saturday) AS is_saturday, \
-                    MAX(mt.is_sunday) AS is_sunday \
-                FROM sections s \
-                JOIN courses c ON s.school_id = c.school_id \
-                    AND s.subject_code = c.subject_code \
-                    AND s.course_number = c.number \
-                LEFT JOIN professors p ON s.primary_professor_id = p.id \
-                    AND s.school_id = p.school_id \
-                LEFT JOIN meeting_times mt ON s.sequence = mt.section_sequence \
-                    AND s.term_collection_id = mt.term_collection_id \
-                    AND s.school_id = mt.school_id \
-                    AND s.subject_code = mt.subject_code \
-                    AND s.course_number = mt.course_number \
-                WHERE {} \
-                GROUP BY \
-                    c.subject_code, \
-                    c.number, \
-                    c.title, \
-                    c.description, \
-                    c.credit_hours, \
-                    c.prerequisites, \
-                    c.corequisites, \
-                    s.sequence, \
-                    s.term_collection_id, \
-                    s.school_id, \
-                    s.max_enrollment, \
-                    s.enrollment, \
-                    s.instruction_method, \
-                    s.campus, \
-                    p.name, \
-                    p.email_address",
-                where_clause
-            );
-
-            match sql::execute_query(&sql, &db_path) {
-                Ok(loaded_classes) => {
-                    // create a map for quick lookup
-                    let mut class_map: std::collections::HashMap<String, Class> = loaded_classes
-                        .into_iter()
-                        .map(|c| (c.unique_id(), c))
-                        .collect();
-
-                    // add classes in the order they appear in the save file
-                    for class_id in class_ids {
-                        if let Some(class) = class_map.remove(class_id) {
-                            classes.push(class);
-                        }
-                    }
-                }
-                Err(e) => {
-                    // if query fails, return empty classes but don't fail the whole load
-                    eprintln!("Warning: Failed to load classes from database: {}", e);
-                }
+            Err(e) => {
+                // snapshots can still be shown, older save files come back empty
+                classes = saved_classes
+                    .iter()
+                    .filter_map(|saved| saved.snapshot.clone())
+                    .collect();
+                issues.push(format!("Couldn't check for changes since saving: {}", e));
             }
         }
     }
@@ -377,9 +872,552 @@ fn load_schedule(file_path: &Path) -> Result<SavedSchedule, String> {
         school_id,
         term_id,
         classes,
+        issues,
+        settings: None,
     })
 }
 
+/// Load classes from the database by their unique IDs
+///
+/// Parameters:
+/// --- ---
+/// school_id -> School the classes belong to ("_test" reads the test database)
+/// term_id -> Term the classes belong to
+/// class_ids -> Class unique IDs (e.g., "CMPT:101-001")
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<HashMap<String, Class>, String> -> The classes found, keyed by unique ID, or error
+/// --- ---
+///
+pub fn load_classes_by_id(
+    school_id: Option<&str>,
+    term_id: Option<&str>,
+    class_ids: &[&str],
+) -> Result<HashMap<String, Class>, String> {
+    // use test db if school_id is "_test", otherwise use synced db or default
+    let db_path = if school_id == Some("_test") {
+        sql::get_test_db_path()
+    } else {
+        sql::get_default_db_path()
+    };
+
+    // build SQL query to get classes by their unique IDs
+    // unique_id format is "SUBJECT:COURSE-SECTION"
+    let mut conditions = Vec::new();
+
+    for class_id in class_ids {
+        // parse the unique_id format: "SUBJECT:COURSE-SECTION"
+        let parts: Vec<&str> = class_id.split(':').collect();
+        if parts.len() == 2 {
+            let subject = parts[0];
+            let rest: Vec<&str> = parts[1].split('-').collect();
+            if rest.len() == 2 {
+                let course = rest[0];
+                let section = rest[1];
+
+                // escape single quotes in values (SQL injection protection)
+                let subject_escaped = subject.replace("'", "''");
+                let course_escaped = course.replace("'", "''");
+                let section_escaped = section.replace("'", "''");
+
+                // use table aliases to avoid ambiguous column names
+                // s = sections, c = courses
+                conditions.push(format!(
+                    "(s.subject_code = '{}' AND s.course_number = '{}' AND s.sequence = '{}')",
+                    subject_escaped, course_escaped, section_escaped
+                ));
+            }
+        }
+    }
+
+    if conditions.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    // build additional filters for school and term
+    let mut filters = Vec::new();
+    if let Some(sid) = school_id {
+        if sid != "_test" {
+            filters.push(format!("s.school_id = '{}'", sid.replace("'", "''")));
+        }
+    }
+    if let Some(tid) = term_id {
+        filters.push(format!(
+            "s.term_collection_id = '{}'",
+            tid.replace("'", "''")
+        ));
+    }
+
+    // combine class conditions with school/term filters
+    let class_conditions = conditions.join(" OR ");
+    let where_clause = if filters.is_empty() {
+        class_conditions
+    } else {
+        format!("({}) AND {}", class_conditions, filters.join(" AND "))
+    };
+
+    // query sections table with joins
+    let sql = format!(
+        "SELECT \
+            c.subject_code, \
+            c.number AS course_number, \
+            c.title, \
+            c.description, \
+            c.credit_hours, \
+            c.prerequisites, \
+            c.corequisites, \
+            s.sequence AS section_sequence, \
+            s.max_enrollment, \
+            s.enrollment, \
+            s.instruction_method, \
+            s.campus, \
+            p.name AS professor_name, \
+            p.email_address AS professor_email, \
+            GROUP_CONCAT( \
+                (CASE WHEN mt.is_monday = 1 THEN 'M' ELSE '' END || \
+                 CASE WHEN mt.is_tuesday = 1 THEN 'T' ELSE '' END || \
+                 CASE WHEN mt.is_wednesday = 1 THEN 'W' ELSE '' END || \
+                 CASE WHEN mt.is_thursday = 1 THEN 'TH' ELSE '' END || \
+                 CASE WHEN mt.is_friday = 1 THEN 'F' ELSE '' END || \
+                 CASE WHEN mt.is_saturday = 1 THEN 'S' ELSE '' END || \
+                 CASE WHEN mt.is_sunday = 1 THEN 'SU' ELSE '' END) || \
+                ':' || mt.start_minutes || '-' || mt.end_minutes || \
+                '@' || COALESCE(substr(mt.start_date, 1, 10), '') || \
+                '/' || COALESCE(substr(mt.end_date, 1, 10), '') || \
+                '/' || COALESCE(json_extract(mt.other, '$.weekParity'), ''), \
+                '|' \
+            ) AS meeting_times, \
+            GROUP_CONCAT(DISTINCT mt.meeting_type) AS meeting_type, \
+            MAX(mt.is_monday) AS is_monday, \
+            MAX(mt.is_tuesday) AS is_tuesday, \
+            MAX(mt.is_wednesday) AS is_wednesday, \
+            MAX(mt.is_thursday) AS is_thursday, \
+            MAX(mt.is_friday) AS is_friday, \
+            MAX(mt.is_saturday) AS is_saturday, \
+            MAX(mt.is_sunday) AS is_sunday, \
+            CAST(COALESCE( \
+                json_extract(s.other, '$.crn'), \
+                json_extract(s.other, '$.courseReferenceNumber') \
+            ) AS TEXT) AS crn, \
+            substr(json_extract(s.other, '$.finalExam.date'), 1, 10) || ' ' || \
+                json_extract(s.other, '$.finalExam.startTime') || '-' || \
+                json_extract(s.other, '$.finalExam.endTime') AS final_exam, \
+            {} AS session, \
+            json_extract(s.other, '$.restrictions') AS restrictions, \
+            json_extract(s.other, '$.fee') AS fee \
+        FROM sections s \
+        JOIN courses c ON s.school_id = c.school_id \
+            AND s.subject_code = c.subject_code \
+            AND s.course_number = c.number \
+        LEFT JOIN professors p ON s.primary_professor_id = p.id \
+            AND s.school_id = p.school_id \
+        LEFT JOIN meeting_times mt ON s.sequence = mt.section_sequence \
+            AND s.term_collection_id = mt.term_collection_id \
+            AND s.school_id = mt.school_id \
+            AND s.subject_code = mt.subject_code \
+            AND s.course_number = mt.course_number \
+        LEFT JOIN term_spans span ON span.term_collection_id = s.term_collection_id \
+            AND span.school_id = s.school_id \
+        WHERE {} \
+        GROUP BY \
+            c.subject_code, \
+            c.number, \
+            c.title, \
+            c.description, \
+            c.credit_hours, \
+            c.prerequisites, \
+            c.corequisites, \
+            s.sequence, \
+            s.term_collection_id, \
+            s.school_id, \
+            s.max_enrollment, \
+            s.enrollment, \
+            s.instruction_method, \
+            s.campus, \
+            p.name, \
+            p.email_address, \
+            s.other",
+        SESSION_SQL, where_clause
+    );
+
+    let loaded_classes = sql::execute_query(&sql, &db_path)?;
+    Ok(loaded_classes
+        .into_iter()
+        .map(|c| (c.unique_id(), c))
+        .collect())
+}
+
+/// Build the classes of a saved schedule and describe what changed since it was saved
+///
+/// Parameters:
+/// --- ---
+/// saved -> The classes recorded in the save file, in order
+/// live -> The classes found in the current database, keyed by unique ID
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// (Vec<Class>, Vec<String>) -> The classes as saved (with current seat counts and
+///                              descriptions where the section is still offered), and
+///                              descriptions of dropped sections, moved meetings, professor
+///                              changes, and any conflicts the moves caused
+/// --- ---
+///
+/// Classes from save files without a snapshot use the current data, and are left out
+/// once they are no longer offered
+///
+pub fn resolve_saved_classes(
+    saved: &[SavedClass],
+    mut live: HashMap<String, Class>,
+) -> (Vec<Class>, Vec<String>) {
+    let mut classes = Vec::new();
+    let mut current = Vec::new();
+    let mut issues = Vec::new();
+
+    for saved_class in saved {
+        let live_class = live.remove(&saved_class.class_id);
+        match (&saved_class.snapshot, live_class) {
+            (Some(snapshot), Some(live_class)) => {
+                classes.push(with_live_details(snapshot.clone(), &live_class));
+                current.push(live_class);
+            }
+            (Some(snapshot), None) => {
+                issues.push(format!(
+                    "{} is no longer offered (shown as saved)",
+                    display_class_id(&saved_class.class_id)
+                ));
+                classes.push(snapshot.clone());
+            }
+            (None, Some(live_class)) => {
+                classes.push(live_class.clone());
+                current.push(live_class);
+            }
+            (None, None) => issues.push(format!(
+                "{} is no longer offered",
+                display_class_id(&saved_class.class_id)
+            )),
+        }
+    }
+
+    issues.extend(find_changes(&current, saved));
+    (classes, issues)
+}
+
+/// Find the classes of a schedule's generation settings in the current catalog
+///
+/// Parameters:
+/// --- ---
+/// settings -> The settings the schedule was generated with
+/// live -> The classes found in the current database, keyed by unique ID
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// (Vec<Class>, Vec<String>) -> The classes still offered, in the settings' order, and
+///                              descriptions of the ones that are not
+/// --- ---
+///
+pub fn resolve_generation_settings(
+    settings: &GenerationSettings,
+    mut live: HashMap<String, Class>,
+) -> (Vec<Class>, Vec<String>) {
+    let mut classes = Vec::new();
+    let mut issues = Vec::new();
+    for class_id in &settings.classes {
+        match live.remove(class_id) {
+            Some(class) => classes.push(class),
+            None => issues.push(format!(
+                "{} is no longer offered",
+                display_class_id(class_id)
+            )),
+        }
+    }
+    (classes, issues)
+}
+
+/// Fill a snapshot with the details that are meant to stay current
+///
+/// Parameters:
+/// --- ---
+/// snapshot -> The class as saved
+/// live -> The class in the current database
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Class -> The snapshot with the current seat counts, description, and requisites
+/// --- ---
+///
+fn with_live_details(snapshot: Class, live: &Class) -> Class {
+    Class {
+        description: live.description.clone(),
+        prerequisites: live.prerequisites.clone(),
+        corequisites: live.corequisites.clone(),
+        enrollment: live.enrollment,
+        max_enrollment: live.max_enrollment,
+        ..snapshot
+    }
+}
+
+/// Compare current classes against what was recorded when they were saved
+///
+/// Parameters:
+/// --- ---
+/// classes -> Classes loaded from the current database
+/// saved -> The classes recorded in the save file
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Vec<String> -> Descriptions of moved meetings, professor changes, and any conflicts
+///                the moves caused
+/// --- ---
+///
+fn find_changes(classes: &[Class], saved: &[SavedClass]) -> Vec<String> {
+    let mut issues = Vec::new();
+    let mut moved = HashSet::new();
+
+    for class in classes {
+        let unique_id = class.unique_id();
+        let Some(saved_class) = saved.iter().find(|saved| saved.class_id == unique_id) else {
+            continue;
+        };
+        let display_id = display_class_id(&unique_id);
+        if let Some(saved_times) = &saved_class.meeting_times {
+            if meetings_changed(saved_times, &class.meeting_times) {
+                issues.push(format!(
+                    "{} moved from {} to {}",
+                    display_id,
+                    describe_meetings(saved_times),
+                    describe_meetings(&class.meeting_times)
+                ));
+                moved.insert(display_id.clone());
+            }
+        }
+        if let Some(snapshot) = &saved_class.snapshot {
+            if snapshot.professor_name != class.professor_name {
+                issues.push(format!(
+                    "{} is now taught by {} (was {})",
+                    display_id,
+                    class.professor_name.as_deref().unwrap_or("TBA"),
+                    snapshot.professor_name.as_deref().unwrap_or("TBA")
+                ));
+            }
+        }
+    }
+
+    // only report conflicts caused by a move, the schedule may have been saved with conflicts
+    for (first, second) in find_conflicting_classes(classes) {
+        if moved.contains(&first) || moved.contains(&second) {
+            issues.push(format!("{} now conflicts with {}", first, second));
+        }
+    }
+
+    issues
+}
+
+/// Record a class as a line of a save file
+///
+/// Parameters:
+/// --- ---
+/// class -> The class to record
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> Tab-separated class ID, packed meeting times, days, title, professor name,
+///           professor email, credit hours, CRN, meeting type, instruction method, campus,
+///           packed final exam, session, packed restrictions, and fee (missing values are
+///           empty)
+/// --- ---
+///
+pub fn snapshot_line(class: &Class) -> String {
+    let optional = |value: &Option<String>| value.as_deref().map(clean_field).unwrap_or_default();
+    [
+        class.unique_id(),
+        sql::pack_meeting_times(&class.meeting_times),
+        clean_field(&class.days),
+        clean_field(&class.title),
+        optional(&class.professor_name),
+        optional(&class.professor_email),
+        class.credit_hours.to_string(),
+        optional(&class.crn),
+        optional(&class.meeting_type),
+        optional(&class.instruction_method),
+        optional(&class.campus),
+        class
+            .final_exam
+            .map(|exam| sql::pack_final_exam(&exam))
+            .unwrap_or_default(),
+        optional(&class.session),
+        sql::pack_restrictions(&class.restrictions),
+        class.fee.map(|fee| fee.to_string()).unwrap_or_default(),
+    ]
+    .join("\t")
+}
+
+/// Keep a value on one field of a save file line
+///
+/// Parameters:
+/// --- ---
+/// value -> The value to store
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> The value with tabs and line breaks replaced by spaces
+/// --- ---
+///
+fn clean_field(value: &str) -> String {
+    value.replace(['\t', '\n', '\r'], " ")
+}
+
+/// Read a class line of a save file
+///
+/// Parameters:
+/// --- ---
+/// line -> The line, in any of the formats save files have used
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// SavedClass -> The class ID, with the meeting times and snapshot if the line has them
+/// --- ---
+///
+/// The oldest save files only list class IDs, later ones add the packed meeting times,
+/// and current ones the full snapshot written by snapshot_line
+///
+pub fn parse_saved_class(line: &str) -> SavedClass {
+    let fields: Vec<&str> = line.split('\t').collect();
+    let class_id = fields[0].to_string();
+    let meeting_times = fields.get(1).map(|times| sql::parse_meeting_times(times));
+
+    let snapshot = if fields.len() >= SNAPSHOT_FIELDS {
+        let (subject_code, rest) = class_id.split_once(':').unwrap_or((&class_id, ""));
+        let (course_number, section_sequence) = rest.split_once('-').unwrap_or((rest, ""));
+        let optional = |value: &str| Some(value.to_string()).filter(|value| !value.is_empty());
+        Some(Class {
+            subject_code: subject_code.to_string(),
+            course_number: course_number.to_string(),
+            section_sequence: section_sequence.to_string(),
+            meeting_times: meeting_times.clone().unwrap_or_default(),
+            days: fields[2].to_string(),
+            title: fields[3].to_string(),
+            professor_name: optional(fields[4]),
+            professor_email: optional(fields[5]),
+            credit_hours: fields[6].parse().unwrap_or(0.0),
+            crn: optional(fields[7]),
+            meeting_type: optional(fields[8]),
+            instruction_method: optional(fields[9]),
+            campus: optional(fields[10]),
+            // saves from before finals, sessions, restrictions, and fees were recorded
+            // stop at the campus
+            final_exam: fields.get(11).and_then(|exam| sql::parse_final_exam(exam)),
+            session: fields.get(12).and_then(|session| optional(session)),
+            restrictions: fields
+                .get(13)
+                .map(|restrictions| sql::parse_restrictions(restrictions))
+                .unwrap_or_default(),
+            fee: fields.get(14).and_then(|fee| fee.parse().ok()),
+            ..Class::default()
+        })
+    } else {
+        None
+    };
+
+    SavedClass {
+        class_id,
+        meeting_times,
+        snapshot,
+    }
+}
+
+/// Convert a class unique ID to its display form
+///
+/// Parameters:
+/// --- ---
+/// class_id -> Class unique ID (e.g., "CMPT:101-001")
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> Display ID (e.g., "CMPT 101-001")
+/// --- ---
+///
+fn display_class_id(class_id: &str) -> String {
+    class_id.replacen(':', " ", 1)
+}
+
+/// Check whether a section's meetings changed since they were saved
+///
+/// Saves from before meetings had dates only recorded days and times, so those are
+/// compared without the section's current dates
+///
+/// Parameters:
+/// --- ---
+/// saved -> The meetings at save time
+/// current -> The section's meetings now
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// bool -> true if the meetings differ
+/// --- ---
+///
+fn meetings_changed(saved: &[Meeting], current: &[Meeting]) -> bool {
+    let undated = saved.iter().all(|meeting| {
+        meeting.start_date.is_none() && meeting.end_date.is_none() && meeting.week_parity.is_none()
+    });
+    if !undated {
+        return saved != current;
+    }
+    let times = |meetings: &[Meeting]| -> Vec<(Vec<String>, i32, i32)> {
+        meetings
+            .iter()
+            .map(|meeting| {
+                (
+                    meeting.days.clone(),
+                    meeting.start_minutes,
+                    meeting.end_minutes,
+                )
+            })
+            .collect()
+    };
+    times(saved) != times(current)
+}
+
+/// Describe a list of meetings in a compact form
+///
+/// Parameters:
+/// --- ---
+/// meetings -> The meetings to describe
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> Compact description (e.g., "MW 9:00am-10:15am"), "TBA" if empty
+/// --- ---
+///
+fn describe_meetings(meetings: &[Meeting]) -> String {
+    if meetings.is_empty() {
+        return "TBA".to_string();
+    }
+    meetings
+        .iter()
+        .map(|meeting| {
+            format!(
+                "{} {}-{}",
+                meeting.days_compact(),
+                format_12_hour(meeting.start_minutes),
+                format_12_hour(meeting.end_minutes)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 /// Delete a saved schedule
 ///
 /// Parameters:
@@ -401,5 +1439,5 @@ pub fn delete_schedule(timestamp: u64) -> Result<(), String> {
         fs::remove_file(&file_path).map_err(|e| format!("Failed to delete save file: {}", e))?;
     }
 
-    Ok(())
+    save_generation_settings(timestamp, None)
 }
@@ -0,0 +1,170 @@
+/// src/tui/session.rs
+///
+/// Session restore for the TUI
+///
+/// Responsible for remembering where the user was when they quit (the screen, the last
+/// query, the selected result and list positions, the school and term, and the workspace
+/// profile) so the next launch can start there. The session is kept in save/session.json
+/// next to the saved schedules, along with the Settings choice to start fresh instead
+///
+/// Contains:
+/// --- ---
+/// Session -> Where the user was when the TUI last exited
+///      Methods:
+///      --- ---
+///      load -> Load a session from a file, the default if there is none
+///      save -> Save the session to a file
+///      restored_focus -> The screen to start on
+///      --- ---
+/// --- ---
+///
+use crate::tui::state::FocusMode;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Where the user was when the TUI last exited
+///
+/// Fields:
+/// --- ---
+/// restore -> Whether to start where the session left off (Settings → Restore Session)
+/// focus -> The screen the user was on
+/// query -> The query in the search box
+/// school_id -> The selected school
+/// term_id -> The selected term
+/// selected_result -> Index of the selected search result
+/// results_scroll -> Scroll offset of the search results
+/// settings_index -> Index of the selected settings option
+/// saved_schedule_index -> Index of the selected schedule in My Schedules
+/// profile -> Name of the workspace profile in use
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for Session
+/// Clone -> Clone trait for Session
+/// PartialEq -> PartialEq trait for Session
+/// Default -> A fresh session at the main menu, restored on the next launch
+/// Serialize -> Serialize trait for Session
+/// Deserialize -> Deserialize trait for Session (missing fields take their defaults)
+/// --- ---
+///
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Session {
+    pub restore: bool,
+    pub focus: FocusMode,
+    pub query: String,
+    pub school_id: Option<String>,
+    pub term_id: Option<String>,
+    pub selected_result: usize,
+    pub results_scroll: usize,
+    pub settings_index: usize,
+    pub saved_schedule_index: usize,
+    pub profile: Option<String>,
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Self {
+            restore: true,
+            focus: FocusMode::MainMenu,
+            query: String::new(),
+            school_id: None,
+            term_id: None,
+            selected_result: 0,
+            results_scroll: 0,
+            settings_index: 0,
+            saved_schedule_index: 0,
+            profile: None,
+        }
+    }
+}
+
+impl Session {
+    /// Load a session from a file
+    ///
+    /// Parameters:
+    /// --- ---
+    /// path -> Path to the session file
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Result<Self, String> -> The session (the default if the file doesn't exist) or error
+    /// --- ---
+    ///
+    pub fn load(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(Session::default());
+        }
+        let content =
+            fs::read_to_string(path).map_err(|e| format!("Failed to read session: {}", e))?;
+        serde_json::from_str(&content).map_err(|e| format!("Failed to parse session: {}", e))
+    }
+
+    /// Save the session to a file
+    ///
+    /// Parameters:
+    /// --- ---
+    /// path -> Path to the session file
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Result<(), String> -> Ok on success, error message on failure
+    /// --- ---
+    ///
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize session: {}", e))?;
+        fs::write(path, content).map_err(|e| format!("Failed to write session: {}", e))
+    }
+
+    /// The screen to start on
+    ///
+    /// Screens that only make sense with state that isn't kept (the cart, a generated
+    /// schedule, an open popup or class) fall back to the nearest one that does
+    ///
+    /// Parameters: None
+    ///
+    /// Returns:
+    /// --- ---
+    /// FocusMode -> MainMenu, QueryInput, ResultsBrowse, Settings, or MySchedules
+    /// --- ---
+    ///
+    pub fn restored_focus(&self) -> FocusMode {
+        let has_query = !self.query.trim().is_empty();
+        match self.focus {
+            FocusMode::Settings | FocusMode::DatabaseStats => FocusMode::Settings,
+            FocusMode::MySchedules => FocusMode::MySchedules,
+            FocusMode::QueryInput => FocusMode::QueryInput,
+            // results come back by running the query again
+            FocusMode::ResultsBrowse if !has_query => FocusMode::QueryInput,
+            FocusMode::ResultsBrowse
+            | FocusMode::DetailView
+            | FocusMode::CompareView
+            | FocusMode::QueryExplain
+            | FocusMode::AstInspector
+                if has_query =>
+            {
+                FocusMode::ResultsBrowse
+            }
+            FocusMode::MainMenu
+            | FocusMode::ResultsBrowse
+            | FocusMode::DetailView
+            | FocusMode::CompareView
+            | FocusMode::QueryExplain
+            | FocusMode::AstInspector
+            | FocusMode::QueryGuide
+            | FocusMode::Help
+            | FocusMode::ScheduleCreation
+            | FocusMode::SaveNameInput
+            | FocusMode::OverlayInput
+            | FocusMode::PasteImport
+            | FocusMode::FreeTime
+            | FocusMode::CatalogBrowse
+            | FocusMode::ProfessorDirectory => FocusMode::MainMenu,
+        }
+    }
+}
@@ -0,0 +1,306 @@
+/// src/tui/share.rs
+///
+/// Shareable schedule tokens
+///
+/// Responsible for turning a saved schedule into a compact, URL-safe token that can be
+/// pasted into a chat or a link, and for importing such a token back into a saved
+/// schedule. The token only names the school, term, and sections, so importing reads the
+/// sections from the local database and nothing but the schedule is shared
+///
+/// Contains:
+/// --- ---
+/// SHARE_PREFIX -> Prefix (and format version) of every token
+/// SharedSchedule -> Schedule contents carried by a token
+///      Methods:
+///      --- ---
+///      from_saved -> Take the contents of a saved schedule
+///      encode -> Encode as a token
+///      decode -> Decode a token, or text containing one
+///      load_classes -> Read the shared sections from the local database
+///      --- ---
+/// ImportReport -> Result of importing a token
+/// Helper functions:
+///      --- ---
+///      import_schedule -> Save the schedule in a token as a new saved schedule
+///      checksum -> 16-bit checksum of the token's contents
+///      --- ---
+/// --- ---
+///
+use crate::data::sql::Class;
+use crate::tui::save::{self, SavedSchedule};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+
+/// Prefix of every token, the digit is the format version
+pub const SHARE_PREFIX: &str = "cq1.";
+
+/// Schedule contents carried by a token
+///
+/// Fields:
+/// --- ---
+/// name -> Name of the schedule
+/// school_id -> School the schedule belongs to
+/// term_id -> Term the schedule belongs to
+/// class_ids -> Unique IDs of the sections, in schedule order
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for SharedSchedule
+/// Clone -> Clone trait for SharedSchedule
+/// PartialEq -> PartialEq trait for SharedSchedule
+/// --- ---
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct SharedSchedule {
+    pub name: String,
+    pub school_id: Option<String>,
+    pub term_id: Option<String>,
+    pub class_ids: Vec<String>,
+}
+
+/// Result of importing a token
+///
+/// Fields:
+/// --- ---
+/// name -> Name the schedule was saved under
+/// imported -> Number of sections found and saved
+/// missing -> Unique IDs of sections that aren't in the local database
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for ImportReport
+/// --- ---
+///
+#[derive(Debug)]
+pub struct ImportReport {
+    pub name: String,
+    pub imported: usize,
+    pub missing: Vec<String>,
+}
+
+impl SharedSchedule {
+    /// Take the contents of a saved schedule
+    ///
+    /// Arguments:
+    /// --- ---
+    /// schedule -> The saved schedule
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// SharedSchedule -> Its name, school, term, and sections
+    /// --- ---
+    ///
+    pub fn from_saved(schedule: &SavedSchedule) -> Self {
+        Self {
+            name: schedule.name.clone(),
+            school_id: schedule.school_id.clone(),
+            term_id: schedule.term_id.clone(),
+            class_ids: schedule.classes.iter().map(|c| c.unique_id()).collect(),
+        }
+    }
+
+    /// Encode as a token
+    ///
+    /// Arguments: None
+    ///
+    /// Returns:
+    /// --- ---
+    /// String -> SHARE_PREFIX followed by URL-safe base64 (no padding)
+    /// --- ---
+    ///
+    /// The contents are lines of name, school, term, and then sections; consecutive
+    /// sections of one subject share a line ("CMPT:101-001,220-002") to keep tokens short.
+    /// A checksum is appended so a token cut off while copying is rejected
+    ///
+    pub fn encode(&self) -> String {
+        let mut text = format!(
+            "{}\n{}\n{}",
+            self.name.replace(['\n', '\r'], " "),
+            self.school_id.as_deref().unwrap_or(""),
+            self.term_id.as_deref().unwrap_or("")
+        );
+        let mut current_subject: Option<&str> = None;
+        for class_id in &self.class_ids {
+            let (subject, rest) = class_id.split_once(':').unwrap_or(("", class_id));
+            if current_subject == Some(subject) {
+                text.push(',');
+            } else {
+                text.push('\n');
+                text.push_str(subject);
+                text.push(':');
+                current_subject = Some(subject);
+            }
+            text.push_str(rest);
+        }
+
+        let mut bytes = text.into_bytes();
+        let sum = checksum(&bytes);
+        bytes.extend_from_slice(&sum.to_be_bytes());
+        format!("{}{}", SHARE_PREFIX, URL_SAFE_NO_PAD.encode(bytes))
+    }
+
+    /// Decode a token, or text containing one
+    ///
+    /// Arguments:
+    /// --- ---
+    /// text -> The token, or e.g. a link or message it was pasted into
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Result<SharedSchedule, String> -> The schedule contents or error message
+    /// --- ---
+    ///
+    pub fn decode(text: &str) -> Result<Self, String> {
+        let start = text
+            .find(SHARE_PREFIX)
+            .ok_or_else(|| "Not a ClassQL schedule token".to_string())?;
+        let encoded: String = text[start + SHARE_PREFIX.len()..]
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+            .collect();
+        let damaged = || "Schedule token is damaged (was it copied completely?)".to_string();
+
+        let mut bytes = URL_SAFE_NO_PAD.decode(&encoded).map_err(|_| damaged())?;
+        if bytes.len() < 2 {
+            return Err(damaged());
+        }
+        let sum_bytes = bytes.split_off(bytes.len() - 2);
+        if checksum(&bytes).to_be_bytes() != sum_bytes[..] {
+            return Err(damaged());
+        }
+        let text = String::from_utf8(bytes).map_err(|_| damaged())?;
+
+        let mut lines = text.split('\n');
+        let (Some(name), Some(school_id), Some(term_id)) =
+            (lines.next(), lines.next(), lines.next())
+        else {
+            return Err(damaged());
+        };
+        let mut class_ids = Vec::new();
+        for line in lines {
+            let (subject, sections) = line.split_once(':').ok_or_else(damaged)?;
+            for section in sections.split(',') {
+                class_ids.push(format!("{}:{}", subject, section));
+            }
+        }
+
+        let optional = |value: &str| Some(value.to_string()).filter(|v| !v.is_empty());
+        Ok(Self {
+            name: name.to_string(),
+            school_id: optional(school_id),
+            term_id: optional(term_id),
+            class_ids,
+        })
+    }
+
+    /// Read the shared sections from the local database
+    ///
+    /// Arguments: None
+    ///
+    /// Returns:
+    /// --- ---
+    /// Result<(Vec<Class>, Vec<String>), String> -> The sections found, in schedule order, and
+    ///                                             the unique IDs of those that aren't in the
+    ///                                             local database, or error message if none are
+    /// --- ---
+    ///
+    pub fn load_classes(&self) -> Result<(Vec<Class>, Vec<String>), String> {
+        let class_ids: Vec<&str> = self.class_ids.iter().map(String::as_str).collect();
+        let mut found = save::load_classes_by_id(
+            self.school_id.as_deref(),
+            self.term_id.as_deref(),
+            &class_ids,
+        )?;
+
+        let mut classes = Vec::new();
+        let mut missing = Vec::new();
+        for class_id in &self.class_ids {
+            match found.remove(class_id) {
+                Some(class) => classes.push(class),
+                None => missing.push(class_id.clone()),
+            }
+        }
+        if classes.is_empty() && !missing.is_empty() {
+            return Err(format!(
+                "None of the shared sections are in the local database, sync {} {} first",
+                self.school_id.as_deref().unwrap_or("the school"),
+                self.term_id.as_deref().unwrap_or("")
+            )
+            .trim_end()
+            .to_string());
+        }
+        Ok((classes, missing))
+    }
+}
+
+/// Save the schedule in a token as a new saved schedule
+///
+/// Arguments:
+/// --- ---
+/// token -> The token, or text containing one
+/// name -> Name to save the schedule under (defaults to the shared name)
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<ImportReport, String> -> What was imported, or error message if the token is
+///                                 invalid, the name can't be used, or none of its sections
+///                                 are in the local database
+/// --- ---
+///
+/// A name already used by a saved schedule gets a number, e.g., "Fall (2)"
+///
+pub fn import_schedule(token: &str, name: Option<&str>) -> Result<ImportReport, String> {
+    let shared = SharedSchedule::decode(token)?;
+    let (classes, missing) = shared.load_classes()?;
+
+    let name = match name {
+        Some(name) => save::validate_schedule_name(name)?,
+        // the sender's name may use characters or a length this version doesn't allow
+        None => {
+            let cleaned: String = shared
+                .name
+                .chars()
+                .filter(|c| save::is_schedule_name_char(*c))
+                .take(save::MAX_SCHEDULE_NAME_LEN)
+                .collect();
+            save::validate_schedule_name(&cleaned).unwrap_or_else(|_| "Shared schedule".to_string())
+        }
+    };
+    // a classmate's "Fall" shouldn't be confused with your own
+    let name = save::unique_schedule_name(&save::schedule_names()?, &name);
+    save::save_schedule(
+        &name,
+        shared.school_id.as_deref(),
+        shared.term_id.as_deref(),
+        &classes,
+    )?;
+    Ok(ImportReport {
+        name,
+        imported: classes.len(),
+        missing,
+    })
+}
+
+/// 16-bit checksum of the token's contents (FNV-1a, folded)
+///
+/// Arguments:
+/// --- ---
+/// bytes -> The contents
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// u16 -> The checksum
+/// --- ---
+///
+fn checksum(bytes: &[u8]) -> u16 {
+    let hash = bytes.iter().fold(0x811c_9dc5_u32, |hash, &byte| {
+        (hash ^ byte as u32).wrapping_mul(0x0100_0193)
+    });
+    (hash ^ (hash >> 16)) as u16
+}
@@ -8,7 +8,10 @@
 /// --- ---
 /// ErrorType -> Type of error (Lexer, Parser, Semantic)
 /// FocusMode -> Current UI focus mode
+/// View -> A view on the navigation back-stack
 /// --- ---
+use crate::data::sql::Class;
+use serde::{Deserialize, Serialize};
 
 /// ErrorType enum
 ///
@@ -52,9 +55,18 @@ pub enum ErrorType {
 /// ScheduleCreation -> User is creating a schedule
 /// MySchedules -> User is viewing saved schedules
 /// SaveNameInput -> User is entering a name for saving a schedule
+/// OverlayInput -> User is entering a schedule to overlay on the one being viewed
+/// PasteImport -> User is pasting a schedule copied from the registrar to add to the cart
+/// FreeTime -> User is finding free time in the schedule being viewed
+/// CompareView -> User is comparing two class sections side by side
+/// DatabaseStats -> User is viewing database statistics
+/// QueryExplain -> User is viewing the query plan and index advice for the last search
+/// AstInspector -> User is exploring the current query's syntax tree and generated SQL
+/// CatalogBrowse -> User is browsing the course catalog by subject and course
+/// ProfessorDirectory -> User is browsing the professor directory or a professor's sections
 /// --- ---
 ///
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum FocusMode {
     MainMenu,
     QueryInput,
@@ -66,4 +78,36 @@ pub enum FocusMode {
     ScheduleCreation,
     MySchedules,
     SaveNameInput,
+    OverlayInput,
+    PasteImport,
+    FreeTime,
+    CompareView,
+    DatabaseStats,
+    QueryExplain,
+    AstInspector,
+    CatalogBrowse,
+    ProfessorDirectory,
+}
+
+/// A view left for another, kept on the navigation back-stack so Esc can return to it
+///
+/// Fields:
+/// --- ---
+/// focus -> Focus mode of the view
+/// class -> The class shown, for a detail view
+/// search_depth -> Number of search pivots made when the view was left, later ones are
+///                 undone on returning to it
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for View
+/// Clone -> Clone trait for View
+/// --- ---
+///
+#[derive(Debug, Clone)]
+pub struct View {
+    pub focus: FocusMode,
+    pub class: Option<Class>,
+    pub search_depth: usize,
 }
@@ -47,6 +47,28 @@ pub struct Theme {
     pub muted_color: Color,
 }
 
+impl Theme {
+    /// Get the colors used to tell courses apart in the schedule grid
+    ///
+    /// Arguments: None
+    ///
+    /// Returns:
+    /// --- ---
+    /// [Color; 6] -> Accent colors from this theme that stand out against the background
+    /// --- ---
+    ///
+    pub fn course_palette(&self) -> [Color; 6] {
+        [
+            self.info_color,
+            self.success_color,
+            self.warning_color,
+            self.title_color,
+            self.logo_color,
+            self.error_color,
+        ]
+    }
+}
+
 /// ThemePalette enum
 ///
 /// Available theme palette options
@@ -66,6 +88,9 @@ pub struct Theme {
 /// Forest -> Forest/nature-inspired green theme
 /// Ocean -> Ocean-inspired blue-green theme
 /// Sunset -> Sunset-inspired warm color theme
+/// HighContrast -> Maximum-contrast theme with bright colors on black
+/// Deuteranopia -> Colorblind-safe theme for red-green (green-weak) color vision
+/// Protanopia -> Colorblind-safe theme for red-green (red-weak) color vision
 /// --- ---
 ///
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -83,6 +108,9 @@ pub enum ThemePalette {
     Forest,
     Ocean,
     Sunset,
+    HighContrast,
+    Deuteranopia,
+    Protanopia,
 }
 
 impl ThemePalette {
@@ -108,6 +136,9 @@ impl ThemePalette {
             ThemePalette::Forest,
             ThemePalette::Ocean,
             ThemePalette::Sunset,
+            ThemePalette::HighContrast,
+            ThemePalette::Deuteranopia,
+            ThemePalette::Protanopia,
         ]
     }
 
@@ -133,6 +164,9 @@ impl ThemePalette {
             ThemePalette::Forest => "Forest",
             ThemePalette::Ocean => "Ocean",
             ThemePalette::Sunset => "Sunset",
+            ThemePalette::HighContrast => "High Contrast",
+            ThemePalette::Deuteranopia => "Deuteranopia",
+            ThemePalette::Protanopia => "Protanopia",
         }
     }
 
@@ -327,6 +361,50 @@ impl ThemePalette {
                 info_color: Color::Rgb(255, 150, 150), // light pink
                 muted_color: Color::Rgb(120, 70, 80), // purple-red-gray
             },
+            ThemePalette::HighContrast => Theme {
+                name: "High Contrast",
+                logo_color: Color::Rgb(255, 255, 255), // white
+                border_color: Color::Rgb(255, 255, 255), // white
+                title_color: Color::Rgb(255, 255, 0),  // yellow
+                text_color: Color::Rgb(255, 255, 255), // white
+                selected_color: Color::Rgb(0, 255, 255), // cyan
+                background_color: Color::Rgb(0, 0, 0), // black
+                error_color: Color::Rgb(255, 90, 90),  // bright red
+                warning_color: Color::Rgb(255, 176, 0), // amber
+                success_color: Color::Rgb(0, 255, 120), // bright green
+                info_color: Color::Rgb(110, 190, 255), // light blue
+                muted_color: Color::Rgb(190, 190, 190), // light gray, still readable on black
+            },
+            // the colorblind-safe themes use the Okabe-Ito palette, which stays distinct
+            // under red-green color blindness
+            ThemePalette::Deuteranopia => Theme {
+                name: "Deuteranopia",
+                logo_color: Color::Rgb(204, 121, 167), // reddish purple
+                border_color: Color::Rgb(0, 114, 178), // blue
+                title_color: Color::Rgb(240, 228, 66), // yellow
+                text_color: Color::Rgb(210, 210, 210), // light gray
+                selected_color: Color::Rgb(255, 255, 255), // white, brighter than text
+                background_color: Color::Rgb(15, 15, 20), // near black
+                error_color: Color::Rgb(213, 94, 0),   // vermillion
+                warning_color: Color::Rgb(230, 159, 0), // orange
+                success_color: Color::Rgb(0, 158, 115), // bluish green
+                info_color: Color::Rgb(86, 180, 233),  // sky blue
+                muted_color: Color::Rgb(130, 130, 140), // gray
+            },
+            ThemePalette::Protanopia => Theme {
+                name: "Protanopia",
+                logo_color: Color::Rgb(204, 121, 167), // reddish purple
+                border_color: Color::Rgb(0, 114, 178), // blue
+                title_color: Color::Rgb(86, 180, 233), // sky blue
+                text_color: Color::Rgb(210, 210, 210), // light gray
+                selected_color: Color::Rgb(255, 255, 255), // white, brighter than text
+                background_color: Color::Rgb(15, 15, 20), // near black
+                error_color: Color::Rgb(255, 140, 0),  // bright orange, reds look dark to protans
+                warning_color: Color::Rgb(240, 228, 66), // yellow
+                success_color: Color::Rgb(0, 158, 115), // bluish green
+                info_color: Color::Rgb(150, 160, 255), // light blue
+                muted_color: Color::Rgb(130, 130, 140), // gray
+            },
         }
     }
 }
@@ -0,0 +1,703 @@
+/// src/tui/widgets/ast_inspector.rs
+///
+/// AST inspector widget rendering
+///
+/// Renders the current query's AST as an expandable tree next to the SQL generated for it,
+/// highlighting the part of the query the selected node was parsed from, so it is clear how
+/// a query was interpreted. A second pane lists the lexed token stream, which is all there
+/// is to show for a query that doesn't parse, and a third the compiler's trace of each stage
+///
+/// Contains:
+/// --- ---
+/// InspectorPane -> Which list the left pane shows
+/// InspectorNode -> A node of the AST, flattened for the tree
+/// AstInspectorWidget -> Widget for the AST inspector
+/// --- ---
+///
+use crate::debug_utils::tokenstream::token_lines;
+use crate::dsl::compiler::TraceEvent;
+use crate::dsl::parser::{Ast, TreeNode};
+use crate::dsl::token::{Token, TokenType};
+use crate::tui::state::FocusMode;
+use crate::tui::themes::Theme;
+use crate::tui::widgets::traits::{KeyAction, Widget};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+use ratatui::Frame;
+
+/// Width of the inspector box
+const INSPECTOR_WIDTH: u16 = 120;
+
+/// Number of rows moved by Page Up/Down
+const PAGE_SIZE: usize = 10;
+
+/// Which list the left pane shows
+///
+/// Variants:
+/// --- ---
+/// Tree -> The AST as an expandable tree
+/// Tokens -> The token stream, one token per row
+/// Trace -> The compiler's trace events, one per row
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for InspectorPane
+/// Clone -> Clone trait for InspectorPane
+/// Copy -> Copy trait for InspectorPane
+/// PartialEq -> PartialEq trait for InspectorPane
+/// --- ---
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InspectorPane {
+    Tree,
+    Tokens,
+    Trace,
+}
+
+/// A node of the AST, flattened for the tree
+///
+/// Fields:
+/// --- ---
+/// node_type -> The node's type, as printed (e.g., "ProfessorQuery")
+/// span -> Byte range of the query the node was parsed from, None if it has no token
+/// depth -> Depth in the tree, 0 for the root
+/// parent -> Index of the parent node, None for the root
+/// has_children -> Whether the node has children to expand
+/// expanded -> Whether the node's children are shown
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for InspectorNode
+/// Clone -> Clone trait for InspectorNode
+/// --- ---
+///
+#[derive(Debug, Clone)]
+pub struct InspectorNode {
+    pub node_type: String,
+    pub span: Option<(usize, usize)>,
+    pub depth: usize,
+    pub parent: Option<usize>,
+    pub has_children: bool,
+    pub expanded: bool,
+}
+
+/// AST inspector widget for exploring how a query was parsed
+///
+/// Fields:
+/// --- ---
+/// query -> The query the AST was parsed from
+/// nodes -> Every node of the AST, parents before their children, empty if it didn't parse
+/// tokens -> The query's tokens, including any the lexer couldn't recognize
+/// token_lines -> The tokens formatted for display, one per token
+/// sql -> The SQL generated for the query, or the error that stopped it
+/// trace -> The events the compiler reported while parsing and generating the query
+/// pane -> Which list the left pane shows
+/// selected -> Index of the selected visible row of the tree
+/// token_selected -> Index of the selected token
+/// trace_selected -> Index of the selected trace event
+/// --- ---
+///
+pub struct AstInspectorWidget {
+    pub query: String,
+    pub nodes: Vec<InspectorNode>,
+    pub tokens: Vec<Token>,
+    pub token_lines: Vec<String>,
+    pub sql: Result<String, String>,
+    pub trace: Vec<TraceEvent>,
+    pub pane: InspectorPane,
+    pub selected: usize,
+    pub token_selected: usize,
+    pub trace_selected: usize,
+}
+
+impl AstInspectorWidget {
+    /// Create a new AstInspectorWidget
+    ///
+    /// Returns:
+    /// --- ---
+    /// AstInspectorWidget -> The new AstInspectorWidget
+    /// --- ---
+    ///
+    pub fn new() -> Self {
+        Self {
+            query: String::new(),
+            nodes: Vec::new(),
+            tokens: Vec::new(),
+            token_lines: Vec::new(),
+            sql: Ok(String::new()),
+            trace: Vec::new(),
+            pane: InspectorPane::Tree,
+            selected: 0,
+            token_selected: 0,
+            trace_selected: 0,
+        }
+    }
+
+    /// Load a query with its tree fully expanded, or its tokens if it didn't parse
+    ///
+    /// Arguments:
+    /// --- ---
+    /// query -> The query
+    /// ast -> The query's AST, None if it didn't parse
+    /// tokens -> The query's tokens, from Lexer::tokenize
+    /// sql -> The SQL generated for the query, or the error that stopped it
+    /// trace -> The events the compiler reported for the query
+    /// --- ---
+    ///
+    /// Returns: None
+    ///
+    pub fn open(
+        &mut self,
+        query: &str,
+        ast: Option<&Ast>,
+        tokens: Vec<Token>,
+        sql: Result<String, String>,
+        trace: Vec<TraceEvent>,
+    ) {
+        self.query = query.to_string();
+        self.nodes.clear();
+        if let Some(head) = ast.and_then(|ast| ast.head.as_ref()) {
+            self.add_nodes(head, 0, None);
+        }
+        self.token_lines = token_lines(query, &tokens);
+        self.tokens = tokens;
+        self.sql = sql;
+        self.trace = trace;
+        self.pane = if self.nodes.is_empty() {
+            InspectorPane::Tokens
+        } else {
+            InspectorPane::Tree
+        };
+        self.selected = 0;
+        self.token_selected = 0;
+        self.trace_selected = 0;
+    }
+
+    /// Recursively add a node and its children
+    ///
+    /// Arguments:
+    /// --- ---
+    /// tree_node -> The node to add
+    /// depth -> The node's depth
+    /// parent -> Index of the node's parent
+    /// --- ---
+    ///
+    /// Returns: None
+    ///
+    fn add_nodes(&mut self, tree_node: &TreeNode, depth: usize, parent: Option<usize>) {
+        let index = self.nodes.len();
+        self.nodes.push(InspectorNode {
+            node_type: tree_node.node_type.to_string(),
+            span: tree_node
+                .lexical_token
+                .map(|token| (token.get_start(), token.get_end())),
+            depth,
+            parent,
+            has_children: !tree_node.children.is_empty(),
+            expanded: true,
+        });
+        for child in &tree_node.children {
+            self.add_nodes(child, depth + 1, Some(index));
+        }
+    }
+
+    /// Get the nodes shown, those whose ancestors are all expanded
+    ///
+    /// Returns:
+    /// --- ---
+    /// Vec<usize> -> Indexes of the visible nodes, in tree order
+    /// --- ---
+    ///
+    pub fn rows(&self) -> Vec<usize> {
+        let mut rows = Vec::new();
+        // a node is hidden under the shallowest collapsed node above it
+        let mut hidden_below: Option<usize> = None;
+        for (index, node) in self.nodes.iter().enumerate() {
+            if let Some(depth) = hidden_below {
+                if node.depth > depth {
+                    continue;
+                }
+                hidden_below = None;
+            }
+            rows.push(index);
+            if node.has_children && !node.expanded {
+                hidden_below = Some(node.depth);
+            }
+        }
+        rows
+    }
+
+    /// Get the text of the query a node was parsed from
+    ///
+    /// Arguments:
+    /// --- ---
+    /// node -> The node
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Option<&str> -> The text, None for nodes without a token
+    /// --- ---
+    ///
+    fn lexeme(&self, node: &InspectorNode) -> Option<&str> {
+        node.span
+            .and_then(|(start, end)| self.query.get(start..end))
+    }
+
+    /// Expand or collapse every node
+    ///
+    /// Arguments:
+    /// --- ---
+    /// expanded -> Whether to expand the nodes
+    /// --- ---
+    ///
+    /// Returns: None
+    ///
+    fn set_all(&mut self, expanded: bool) {
+        // the selected node stays selected, or its root once collapsed
+        let selected = self.rows().get(self.selected).copied();
+        for node in &mut self.nodes {
+            node.expanded = expanded;
+        }
+        self.selected = selected
+            .and_then(|node| self.rows().iter().position(|row| *row == node))
+            .unwrap_or(0);
+    }
+
+    /// Build the line drawn for a node
+    ///
+    /// Arguments:
+    /// --- ---
+    /// node -> Index of the node
+    /// is_selected -> Whether the node is selected
+    /// theme -> The theme to use for styling
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Line -> The styled row
+    /// --- ---
+    ///
+    fn row_line(&self, node: usize, is_selected: bool, theme: &Theme) -> Line<'static> {
+        let tree_node = &self.nodes[node];
+        let text_style = if is_selected {
+            Style::default()
+                .fg(theme.selected_color)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.text_color)
+        };
+        let prefix = if is_selected { "> " } else { "  " };
+        let marker = match (tree_node.has_children, tree_node.expanded) {
+            (false, _) => " ",
+            (true, true) => "▾",
+            (true, false) => "▸",
+        };
+
+        let mut spans = vec![Span::styled(
+            format!(
+                "{}{}{} {}",
+                prefix,
+                "  ".repeat(tree_node.depth),
+                marker,
+                tree_node.node_type
+            ),
+            text_style,
+        )];
+        if let Some(lexeme) = self.lexeme(tree_node) {
+            spans.push(Span::styled(
+                format!(" `{}`", lexeme),
+                Style::default().fg(theme.muted_color),
+            ));
+        }
+        Line::from(spans)
+    }
+
+    /// Build the line drawn for a token
+    ///
+    /// Arguments:
+    /// --- ---
+    /// index -> Index of the token
+    /// is_selected -> Whether the token is selected
+    /// theme -> The theme to use for styling
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Line -> The styled row
+    /// --- ---
+    ///
+    fn token_line(&self, index: usize, is_selected: bool, theme: &Theme) -> Line<'static> {
+        // tokens the lexer refused are what a "why didn't it parse" question is usually about
+        let refused = matches!(
+            self.tokens[index].get_token_type(),
+            TokenType::Unrecognized | TokenType::UnclosedString
+        );
+        let style = match (is_selected, refused) {
+            (true, _) => Style::default()
+                .fg(theme.selected_color)
+                .add_modifier(Modifier::BOLD),
+            (false, true) => Style::default().fg(theme.error_color),
+            (false, false) => Style::default().fg(theme.text_color),
+        };
+        let prefix = if is_selected { "> " } else { "  " };
+        Line::from(Span::styled(
+            format!("{}{}", prefix, self.token_lines[index]),
+            style,
+        ))
+    }
+
+    /// Build the line drawn for a trace event
+    ///
+    /// Arguments:
+    /// --- ---
+    /// index -> Index of the event
+    /// is_selected -> Whether the event is selected
+    /// theme -> The theme to use for styling
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Line -> The styled row
+    /// --- ---
+    ///
+    fn trace_line(&self, index: usize, is_selected: bool, theme: &Theme) -> Line<'static> {
+        let event = &self.trace[index];
+        let style = match (is_selected, event) {
+            (true, _) => Style::default()
+                .fg(theme.selected_color)
+                .add_modifier(Modifier::BOLD),
+            (false, TraceEvent::Failed { .. }) => Style::default().fg(theme.error_color),
+            // rules are the bulk of a trace, so the stage summaries stand out from them
+            (false, TraceEvent::RuleEntered { .. }) => Style::default().fg(theme.muted_color),
+            (false, TraceEvent::Noticed { .. }) => Style::default().fg(theme.warning_color),
+            (false, _) => Style::default().fg(theme.text_color),
+        };
+        let prefix = if is_selected { "> " } else { "  " };
+        Line::from(Span::styled(format!("{}{}", prefix, event), style))
+    }
+
+    /// Get the byte range of the query under the selected node, token, or rule
+    ///
+    /// Returns:
+    /// --- ---
+    /// Option<(usize, usize)> -> The range, None if the selection has no token
+    /// --- ---
+    ///
+    fn selected_span(&self) -> Option<(usize, usize)> {
+        match self.pane {
+            InspectorPane::Tree => self
+                .rows()
+                .get(self.selected)
+                .and_then(|node| self.nodes[*node].span),
+            InspectorPane::Tokens => self
+                .tokens
+                .get(self.token_selected)
+                .map(|token| (token.get_start(), token.get_end())),
+            // a rule starts at a token, or at the end of the query once they run out
+            InspectorPane::Trace => match self.trace.get(self.trace_selected) {
+                Some(TraceEvent::RuleEntered { token, .. }) => Some(
+                    self.tokens
+                        .get(*token)
+                        .map(|token| (token.get_start(), token.get_end()))
+                        .unwrap_or((self.query.len(), self.query.len())),
+                ),
+                _ => None,
+            },
+        }
+    }
+
+    /// Build the query line, with the selected node's, token's, or rule's text highlighted
+    ///
+    /// Arguments:
+    /// --- ---
+    /// theme -> The theme to use for styling
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Line -> The styled query
+    /// --- ---
+    ///
+    fn query_line(&self, theme: &Theme) -> Line<'static> {
+        let text_style = Style::default().fg(theme.text_color);
+        let span = self
+            .selected_span()
+            .filter(|(start, end)| self.query.get(*start..*end).is_some());
+
+        let mut spans = vec![Span::styled(
+            "Query: ",
+            Style::default()
+                .fg(theme.info_color)
+                .add_modifier(Modifier::BOLD),
+        )];
+        match span {
+            Some((start, end)) => {
+                spans.push(Span::styled(self.query[..start].to_string(), text_style));
+                spans.push(Span::styled(
+                    self.query[start..end].to_string(),
+                    Style::default()
+                        .fg(theme.selected_color)
+                        .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+                ));
+                spans.push(Span::styled(self.query[end..].to_string(), text_style));
+            }
+            None => spans.push(Span::styled(self.query.clone(), text_style)),
+        }
+        Line::from(spans)
+    }
+}
+
+impl Default for AstInspectorWidget {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget for AstInspectorWidget {
+    /// Render the AST inspector
+    ///
+    /// Arguments:
+    /// --- ---
+    /// frame -> The frame to render to
+    /// theme -> The theme to use for styling
+    /// --- ---
+    ///
+    /// Returns: None
+    ///
+    fn render(&self, frame: &mut Frame, theme: &Theme) {
+        // position below the logo like the explain screen, leaving room for the help bar
+        let inspector_y = 13_u16;
+        let inspector_area = Rect {
+            x: frame.area().width.saturating_sub(INSPECTOR_WIDTH) / 2,
+            y: inspector_y,
+            width: INSPECTOR_WIDTH,
+            height: frame.area().height.saturating_sub(inspector_y + 3),
+        }
+        .intersection(frame.area());
+
+        let block_style = Style::default().bg(theme.background_color);
+        let border_style = Style::default().fg(theme.border_color);
+        let title_style = Style::default()
+            .fg(theme.title_color)
+            .add_modifier(Modifier::BOLD);
+
+        frame.render_widget(Clear, inspector_area);
+        let outer = Block::default()
+            .borders(Borders::ALL)
+            .title(" Query Inspector ")
+            .title_style(title_style)
+            .border_style(border_style)
+            .style(block_style);
+        let inner = outer.inner(inspector_area);
+        frame.render_widget(outer, inspector_area);
+
+        // the query on top, the tree and the SQL side by side below it
+        let rows_area = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(2), Constraint::Min(0)])
+            .split(inner);
+        frame.render_widget(
+            Paragraph::new(self.query_line(theme)).wrap(Wrap { trim: false }),
+            rows_area[0],
+        );
+        let panes = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(rows_area[1]);
+
+        // keep the selected row in view by scrolling just far enough
+        let visible = panes[0].height.saturating_sub(2).max(1) as usize;
+        let (title, lines): (&str, Vec<Line>) = match self.pane {
+            InspectorPane::Tree => {
+                let rows = self.rows();
+                let selected = self.selected.min(rows.len().saturating_sub(1));
+                let first = (selected + 1).saturating_sub(visible);
+                let lines = rows
+                    .iter()
+                    .enumerate()
+                    .skip(first)
+                    .take(visible)
+                    .map(|(i, node)| self.row_line(*node, i == selected, theme))
+                    .collect();
+                (" AST (Tab: Tokens) ", lines)
+            }
+            InspectorPane::Tokens => {
+                let selected = self.token_selected.min(self.tokens.len().saturating_sub(1));
+                let first = (selected + 1).saturating_sub(visible);
+                let lines = (first..self.tokens.len())
+                    .take(visible)
+                    .map(|i| self.token_line(i, i == selected, theme))
+                    .collect();
+                let title = if self.nodes.is_empty() {
+                    " Tokens (no syntax tree, Tab: Trace) "
+                } else {
+                    " Tokens (Tab: Trace) "
+                };
+                (title, lines)
+            }
+            InspectorPane::Trace => {
+                let selected = self.trace_selected.min(self.trace.len().saturating_sub(1));
+                let first = (selected + 1).saturating_sub(visible);
+                let lines = (first..self.trace.len())
+                    .take(visible)
+                    .map(|i| self.trace_line(i, i == selected, theme))
+                    .collect();
+                let title = if self.nodes.is_empty() {
+                    " Trace (Tab: Tokens) "
+                } else {
+                    " Trace (Tab: AST) "
+                };
+                (title, lines)
+            }
+        };
+        frame.render_widget(
+            Paragraph::new(lines).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(title)
+                    .title_style(title_style)
+                    .border_style(border_style),
+            ),
+            panes[0],
+        );
+
+        let (sql_title, sql_text, sql_style) = match &self.sql {
+            Ok(sql) => (" SQL ", sql.as_str(), Style::default().fg(theme.text_color)),
+            Err(error) => (
+                " SQL (not generated) ",
+                error.as_str(),
+                Style::default().fg(theme.error_color),
+            ),
+        };
+        frame.render_widget(
+            Paragraph::new(Span::styled(sql_text.to_string(), sql_style))
+                .wrap(Wrap { trim: false })
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title(sql_title)
+                        .title_style(title_style)
+                        .border_style(border_style),
+                ),
+            panes[1],
+        );
+    }
+
+    /// Handle a key event and return an action
+    ///
+    /// Arguments:
+    /// --- ---
+    /// key -> The key event to handle
+    /// --- ---
+    ///
+    /// Returns: KeyAction -> The action to take in response to the key
+    ///
+    fn handle_key(&mut self, key: KeyEvent) -> KeyAction {
+        let rows = self.rows();
+        let node = rows
+            .get(self.selected.min(rows.len().saturating_sub(1)))
+            .copied();
+
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => KeyAction::Exit,
+            KeyCode::Char('a') | KeyCode::Char('A')
+                if key.modifiers.contains(KeyModifiers::ALT) =>
+            {
+                KeyAction::Back
+            }
+            KeyCode::Esc | KeyCode::Backspace => KeyAction::Back,
+            KeyCode::Tab => {
+                // a query that didn't parse only has its tokens and trace to show
+                self.pane = match self.pane {
+                    InspectorPane::Tree => InspectorPane::Tokens,
+                    InspectorPane::Tokens => InspectorPane::Trace,
+                    InspectorPane::Trace if self.nodes.is_empty() => InspectorPane::Tokens,
+                    InspectorPane::Trace => InspectorPane::Tree,
+                };
+                KeyAction::Continue
+            }
+            KeyCode::Up
+            | KeyCode::Down
+            | KeyCode::PageUp
+            | KeyCode::PageDown
+            | KeyCode::Home
+            | KeyCode::End => {
+                let (selected, count) = match self.pane {
+                    InspectorPane::Tree => (&mut self.selected, rows.len()),
+                    InspectorPane::Tokens => (&mut self.token_selected, self.tokens.len()),
+                    InspectorPane::Trace => (&mut self.trace_selected, self.trace.len()),
+                };
+                let last = count.saturating_sub(1);
+                *selected = match key.code {
+                    KeyCode::Up => selected.saturating_sub(1),
+                    KeyCode::Down => (*selected + 1).min(last),
+                    KeyCode::PageUp => selected.saturating_sub(PAGE_SIZE),
+                    KeyCode::PageDown => (*selected + PAGE_SIZE).min(last),
+                    KeyCode::Home => 0,
+                    _ => last,
+                };
+                KeyAction::Continue
+            }
+            // the rest expand and collapse the tree
+            _ if self.pane != InspectorPane::Tree => KeyAction::Continue,
+            KeyCode::Right => {
+                if let Some(node) = node {
+                    if self.nodes[node].has_children && !self.nodes[node].expanded {
+                        self.nodes[node].expanded = true;
+                    } else if self.nodes[node].has_children {
+                        // step into the first child
+                        self.selected = (self.selected + 1).min(rows.len().saturating_sub(1));
+                    }
+                }
+                KeyAction::Continue
+            }
+            KeyCode::Left => {
+                if let Some(node) = node {
+                    if self.nodes[node].has_children && self.nodes[node].expanded {
+                        self.nodes[node].expanded = false;
+                    } else if let Some(parent) = self.nodes[node].parent {
+                        // step out to the parent row
+                        if let Some(index) = rows.iter().position(|row| *row == parent) {
+                            self.selected = index;
+                        }
+                    }
+                }
+                KeyAction::Continue
+            }
+            KeyCode::Enter | KeyCode::Char(' ') => {
+                if let Some(node) = node {
+                    if self.nodes[node].has_children {
+                        self.nodes[node].expanded = !self.nodes[node].expanded;
+                    }
+                }
+                KeyAction::Continue
+            }
+            KeyCode::Char('e') | KeyCode::Char('E') => {
+                self.set_all(true);
+                KeyAction::Continue
+            }
+            KeyCode::Char('c') | KeyCode::Char('C') => {
+                self.set_all(false);
+                KeyAction::Continue
+            }
+            _ => KeyAction::Continue,
+        }
+    }
+
+    /// Return the focus mode(s) this widget handles
+    ///
+    /// Returns:
+    /// --- ---
+    /// Vec<FocusMode> -> The focus modes this widget handles
+    /// --- ---
+    ///
+    fn focus_modes(&self) -> Vec<FocusMode> {
+        vec![FocusMode::AstInspector]
+    }
+}
@@ -0,0 +1,684 @@
+/// src/tui/widgets/catalog.rs
+///
+/// Course catalog browser widget
+///
+/// Renders the active term as a tree of subjects, their courses, and each course's sections,
+/// so classes can be found without writing a query. Each level is loaded from the database
+/// the first time it is expanded
+///
+/// Contains:
+/// --- ---
+/// CatalogAction -> Action returned by the catalog widget for app-level handling
+/// CatalogRow -> A visible row of the tree
+/// CatalogWidget -> Widget for the catalog browser
+/// Helper functions:
+///      --- ---
+///      count_label -> Format a count with its noun, e.g., "1 course" or "3 courses"
+///      format_credits -> Format credit hours without a trailing ".0"
+///      --- ---
+/// --- ---
+///
+use crate::data::catalog::{
+    fetch_courses, fetch_sections, fetch_subjects, CatalogCourse, CatalogScope, CatalogSubject,
+};
+use crate::data::sql::Class;
+use crate::tui::state::{ErrorType, FocusMode};
+use crate::tui::themes::Theme;
+use crate::tui::widgets::helpers::truncate;
+use crate::tui::widgets::traits::{KeyAction, Widget};
+use crate::utils::i18n;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+use std::collections::HashSet;
+
+/// Width of the catalog box
+const CATALOG_WIDTH: u16 = 100;
+
+/// Number of rows moved by Page Up/Down
+const PAGE_SIZE: usize = 10;
+
+/// Action returned by the catalog widget for app-level handling
+///
+/// Variants:
+/// --- ---
+/// None -> No action needed
+/// OpenDetails -> A section was picked, caller should open its detail view
+/// AddToCart -> The user asked to add a section to the cart
+/// --- ---
+///
+#[derive(Debug, Clone)]
+pub enum CatalogAction {
+    None,
+    OpenDetails(Box<Class>),
+    AddToCart(Box<Class>),
+}
+
+/// A visible row of the tree
+///
+/// Variants:
+/// --- ---
+/// Subject -> A subject, by its index
+/// Course -> A course, by its subject's and its own index
+/// Section -> A section, by its subject's, course's, and its own index
+/// --- ---
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CatalogRow {
+    Subject(usize),
+    Course(usize, usize),
+    Section(usize, usize, usize),
+}
+
+/// A course and its sections, once loaded
+///
+/// Fields:
+/// --- ---
+/// course -> The course
+/// sections -> Its sections, None until it is first expanded
+/// expanded -> Whether its sections are shown
+/// --- ---
+///
+struct CourseNode {
+    course: CatalogCourse,
+    sections: Option<Vec<Class>>,
+    expanded: bool,
+}
+
+/// A subject and its courses, once loaded
+///
+/// Fields:
+/// --- ---
+/// subject -> The subject
+/// courses -> Its courses, None until it is first expanded
+/// expanded -> Whether its courses are shown
+/// --- ---
+///
+struct SubjectNode {
+    subject: CatalogSubject,
+    courses: Option<Vec<CourseNode>>,
+    expanded: bool,
+}
+
+/// Catalog widget for browsing subjects, courses, and sections
+///
+/// Fields:
+/// --- ---
+/// scope -> The database, school, and term being browsed, None until opened
+/// subjects -> The subjects of the term, with whatever has been expanded
+/// selected -> Index of the selected visible row
+/// in_cart -> Unique IDs of the classes in the cart, to mark their sections
+/// --- ---
+///
+pub struct CatalogWidget {
+    pub scope: Option<CatalogScope>,
+    subjects: Vec<SubjectNode>,
+    pub selected: usize,
+    pub in_cart: HashSet<String>,
+}
+
+impl CatalogWidget {
+    /// Create a new CatalogWidget
+    ///
+    /// Returns:
+    /// --- ---
+    /// CatalogWidget -> The new CatalogWidget
+    /// --- ---
+    ///
+    pub fn new() -> Self {
+        Self {
+            scope: None,
+            subjects: Vec::new(),
+            selected: 0,
+            in_cart: HashSet::new(),
+        }
+    }
+
+    /// Open the catalog of a term
+    ///
+    /// Reopening the same term keeps the tree as it was left
+    ///
+    /// Arguments:
+    /// --- ---
+    /// scope -> The database, school, and term to browse
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Result<(), String> -> Ok once the subjects are loaded, error message otherwise
+    /// --- ---
+    ///
+    pub fn open(&mut self, scope: CatalogScope) -> Result<(), String> {
+        if self.scope.as_ref() == Some(&scope) {
+            return Ok(());
+        }
+        let subjects = fetch_subjects(&scope)?;
+        self.subjects = subjects
+            .into_iter()
+            .map(|subject| SubjectNode {
+                subject,
+                courses: None,
+                expanded: false,
+            })
+            .collect();
+        self.selected = 0;
+        self.scope = Some(scope);
+        Ok(())
+    }
+
+    /// Forget the loaded tree, so the next open reads the database again (e.g., after a sync)
+    ///
+    /// Returns: None
+    ///
+    pub fn reset(&mut self) {
+        self.scope = None;
+        self.subjects.clear();
+        self.selected = 0;
+    }
+
+    /// Get the visible rows, in order
+    ///
+    /// Returns:
+    /// --- ---
+    /// Vec<CatalogRow> -> Every subject, then the courses and sections of expanded nodes
+    /// --- ---
+    ///
+    pub fn rows(&self) -> Vec<CatalogRow> {
+        let mut rows = Vec::new();
+        for (s, subject) in self.subjects.iter().enumerate() {
+            rows.push(CatalogRow::Subject(s));
+            let Some(courses) = subject.courses.as_ref().filter(|_| subject.expanded) else {
+                continue;
+            };
+            for (c, course) in courses.iter().enumerate() {
+                rows.push(CatalogRow::Course(s, c));
+                let Some(sections) = course.sections.as_ref().filter(|_| course.expanded) else {
+                    continue;
+                };
+                rows.extend((0..sections.len()).map(|i| CatalogRow::Section(s, c, i)));
+            }
+        }
+        rows
+    }
+
+    /// Get the section a row shows
+    ///
+    /// Arguments:
+    /// --- ---
+    /// row -> The row
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Option<&Class> -> The section, None for subject and course rows
+    /// --- ---
+    ///
+    fn section(&self, row: CatalogRow) -> Option<&Class> {
+        let CatalogRow::Section(s, c, i) = row else {
+            return None;
+        };
+        self.subjects[s].courses.as_ref()?[c]
+            .sections
+            .as_ref()?
+            .get(i)
+    }
+
+    /// Expand or collapse the node on a row, loading its children the first time
+    ///
+    /// Arguments:
+    /// --- ---
+    /// row -> The subject or course row
+    /// expand -> true to expand, false to collapse
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Result<(), String> -> Ok on success, error message if the children couldn't be loaded
+    /// --- ---
+    ///
+    fn set_expanded(&mut self, row: CatalogRow, expand: bool) -> Result<(), String> {
+        let Some(scope) = self.scope.clone() else {
+            return Ok(());
+        };
+        match row {
+            CatalogRow::Subject(s) => {
+                let node = &mut self.subjects[s];
+                if expand && node.courses.is_none() {
+                    let courses = fetch_courses(&scope, &node.subject.code)?;
+                    node.courses = Some(
+                        courses
+                            .into_iter()
+                            .map(|course| CourseNode {
+                                course,
+                                sections: None,
+                                expanded: false,
+                            })
+                            .collect(),
+                    );
+                }
+                node.expanded = expand;
+            }
+            CatalogRow::Course(s, c) => {
+                let Some(node) = self.subjects[s]
+                    .courses
+                    .as_mut()
+                    .and_then(|courses| courses.get_mut(c))
+                else {
+                    return Ok(());
+                };
+                if expand && node.sections.is_none() {
+                    node.sections = Some(fetch_sections(
+                        &scope,
+                        &node.course.subject_code,
+                        &node.course.number,
+                    )?);
+                }
+                node.expanded = expand;
+            }
+            CatalogRow::Section(..) => {}
+        }
+        Ok(())
+    }
+
+    /// Check whether the node on a row is expanded
+    ///
+    /// Arguments:
+    /// --- ---
+    /// row -> The row
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// bool -> true if the subject or course shows its children
+    /// --- ---
+    ///
+    fn is_expanded(&self, row: CatalogRow) -> bool {
+        match row {
+            CatalogRow::Subject(s) => self.subjects[s].expanded,
+            CatalogRow::Course(s, c) => self.subjects[s]
+                .courses
+                .as_ref()
+                .is_some_and(|courses| courses[c].expanded),
+            CatalogRow::Section(..) => false,
+        }
+    }
+
+    /// Expand or collapse a row, turning a loading failure into a toast
+    ///
+    /// Arguments:
+    /// --- ---
+    /// row -> The subject or course row
+    /// expand -> true to expand, false to collapse
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// KeyAction -> Continue, or a toast if the row's children couldn't be loaded
+    /// --- ---
+    ///
+    fn toggle(&mut self, row: CatalogRow, expand: bool) -> KeyAction {
+        match self.set_expanded(row, expand) {
+            Ok(()) => KeyAction::Continue,
+            Err(e) => KeyAction::ShowToast {
+                message: format!("Failed to load catalog: {}", e),
+                error_type: ErrorType::Warning,
+            },
+        }
+    }
+
+    /// Handle a key event and return both KeyAction and CatalogAction
+    ///
+    /// Arguments:
+    /// --- ---
+    /// key -> The key event to handle
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// (KeyAction, CatalogAction) -> The key action and the catalog-specific action
+    /// --- ---
+    ///
+    pub fn handle_key_with_action(&mut self, key: KeyEvent) -> (KeyAction, CatalogAction) {
+        let rows = self.rows();
+        let last = rows.len().saturating_sub(1);
+        let Some(&row) = rows.get(self.selected.min(last)) else {
+            let action = match key.code {
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    KeyAction::Exit
+                }
+                KeyCode::Esc | KeyCode::Backspace => KeyAction::Back,
+                _ => KeyAction::Continue,
+            };
+            return (action, CatalogAction::None);
+        };
+
+        let action = match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => KeyAction::Exit,
+            KeyCode::Esc | KeyCode::Backspace => KeyAction::Back,
+            KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+                KeyAction::Continue
+            }
+            KeyCode::Down => {
+                self.selected = (self.selected + 1).min(last);
+                KeyAction::Continue
+            }
+            KeyCode::PageUp => {
+                self.selected = self.selected.saturating_sub(PAGE_SIZE);
+                KeyAction::Continue
+            }
+            KeyCode::PageDown => {
+                self.selected = (self.selected + PAGE_SIZE).min(last);
+                KeyAction::Continue
+            }
+            KeyCode::Home => {
+                self.selected = 0;
+                KeyAction::Continue
+            }
+            KeyCode::End => {
+                self.selected = last;
+                KeyAction::Continue
+            }
+            KeyCode::Right => self.toggle(row, true),
+            KeyCode::Left => {
+                if self.is_expanded(row) {
+                    self.toggle(row, false)
+                } else {
+                    // step out to the parent row
+                    let parent = match row {
+                        CatalogRow::Subject(_) => row,
+                        CatalogRow::Course(s, _) => CatalogRow::Subject(s),
+                        CatalogRow::Section(s, c, _) => CatalogRow::Course(s, c),
+                    };
+                    if let Some(index) = rows.iter().position(|r| *r == parent) {
+                        self.selected = index;
+                    }
+                    KeyAction::Continue
+                }
+            }
+            KeyCode::Enter => {
+                if let Some(class) = self.section(row) {
+                    return (
+                        KeyAction::Navigate(FocusMode::DetailView),
+                        CatalogAction::OpenDetails(Box::new(class.clone())),
+                    );
+                }
+                let expand = !self.is_expanded(row);
+                self.toggle(row, expand)
+            }
+            KeyCode::Char('a') | KeyCode::Char('A') => {
+                if let Some(class) = self.section(row) {
+                    return (
+                        KeyAction::Continue,
+                        CatalogAction::AddToCart(Box::new(class.clone())),
+                    );
+                }
+                KeyAction::ShowToast {
+                    message: "Expand a course and pick one of its sections to add".to_string(),
+                    error_type: ErrorType::Info,
+                }
+            }
+            _ => KeyAction::Continue,
+        };
+        (action, CatalogAction::None)
+    }
+
+    /// Build the line drawn for a row
+    ///
+    /// Arguments:
+    /// --- ---
+    /// row -> The row
+    /// is_selected -> Whether the row is selected
+    /// theme -> The theme to use for styling
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Line -> The styled row
+    /// --- ---
+    ///
+    fn row_line(&self, row: CatalogRow, is_selected: bool, theme: &Theme) -> Line<'static> {
+        let text_style = if is_selected {
+            Style::default()
+                .fg(theme.selected_color)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.text_color)
+        };
+        let muted = Style::default().fg(theme.muted_color);
+        let prefix = if is_selected { "> " } else { "  " };
+        let marker = if self.is_expanded(row) { "▾" } else { "▸" };
+
+        match row {
+            CatalogRow::Subject(s) => {
+                let subject = &self.subjects[s].subject;
+                let name = subject.description.as_deref().unwrap_or("");
+                Line::from(vec![
+                    Span::styled(
+                        format!(
+                            "{}{} {:<6} {}",
+                            prefix,
+                            marker,
+                            subject.code,
+                            truncate(name, 40)
+                        ),
+                        text_style,
+                    ),
+                    Span::styled(
+                        format!(
+                            "  {} · {}",
+                            count_label(subject.courses, "course"),
+                            count_label(subject.sections, "section")
+                        ),
+                        muted,
+                    ),
+                ])
+            }
+            CatalogRow::Course(s, c) => {
+                let course = &self.subjects[s].courses.as_ref().expect("course row")[c].course;
+                Line::from(vec![
+                    Span::styled(
+                        format!(
+                            "{}    {} {:<6} {}",
+                            prefix,
+                            marker,
+                            course.number,
+                            truncate(&course.title, 40)
+                        ),
+                        text_style,
+                    ),
+                    Span::styled(
+                        format!(
+                            "  {}",
+                            i18n::tr(
+                                "catalog-course-summary",
+                                "{credits} cr · {sections}, {open} open",
+                                &[
+                                    ("credits", &format_credits(course.credit_hours)),
+                                    ("sections", &count_label(course.sections, "section")),
+                                    ("open", &course.open_sections.to_string()),
+                                ],
+                            )
+                        ),
+                        muted,
+                    ),
+                ])
+            }
+            CatalogRow::Section(..) => {
+                let class = self.section(row).expect("section row");
+                let times = class
+                    .format_for_display()
+                    .get(3)
+                    .cloned()
+                    .unwrap_or_else(|| "TBA".to_string());
+                let seats = match (class.enrollment, class.max_enrollment) {
+                    (Some(e), Some(m)) => format!("{}/{}", e, m),
+                    _ => "-".to_string(),
+                };
+                let mut spans = vec![Span::styled(
+                    format!(
+                        "{}          {:<5} {:<22} {:<30} {}",
+                        prefix,
+                        class.section_sequence,
+                        truncate(class.professor_name.as_deref().unwrap_or("TBA"), 22),
+                        truncate(&times, 30),
+                        seats
+                    ),
+                    text_style,
+                )];
+                if self.in_cart.contains(&class.unique_id()) {
+                    spans.push(Span::styled(
+                        format!("  {}", i18n::tr("in-cart", "✓ in cart", &[])),
+                        Style::default().fg(theme.success_color),
+                    ));
+                }
+                Line::from(spans)
+            }
+        }
+    }
+}
+
+impl Default for CatalogWidget {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Format a count with its noun
+///
+/// Translated through the "count-<noun>" or "count-<noun>s" message
+///
+/// Arguments:
+/// --- ---
+/// count -> The count
+/// noun -> The singular noun, e.g., "course"
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> e.g., "1 course" or "3 courses"
+/// --- ---
+///
+fn count_label(count: usize, noun: &str) -> String {
+    let plural = if count == 1 { "" } else { "s" };
+    i18n::tr(
+        &format!("count-{}{}", noun, plural),
+        &format!("{{count}} {}{}", noun, plural),
+        &[("count", &count.to_string())],
+    )
+}
+
+/// Format credit hours without a trailing ".0"
+///
+/// Arguments:
+/// --- ---
+/// credits -> The credit hours
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> e.g., "3" or "1.5"
+/// --- ---
+///
+fn format_credits(credits: f64) -> String {
+    if credits.fract() == 0.0 {
+        format!("{:.0}", credits)
+    } else {
+        format!("{:.1}", credits)
+    }
+}
+
+impl Widget for CatalogWidget {
+    /// Render the catalog tree
+    ///
+    /// Arguments:
+    /// --- ---
+    /// frame -> The frame to render to
+    /// theme -> The theme to use for styling
+    /// --- ---
+    ///
+    /// Returns: None
+    ///
+    fn render(&self, frame: &mut Frame, theme: &Theme) {
+        // position below the logo like the explain screen, leaving room for the help bar
+        let catalog_y = 13_u16;
+        let catalog_area = Rect {
+            x: frame.area().width.saturating_sub(CATALOG_WIDTH) / 2,
+            y: catalog_y,
+            width: CATALOG_WIDTH,
+            height: frame.area().height.saturating_sub(catalog_y + 3),
+        }
+        .intersection(frame.area());
+
+        let rows = self.rows();
+        let lines: Vec<Line> = if rows.is_empty() {
+            vec![Line::from(Span::styled(
+                i18n::tr(
+                    "catalog-empty",
+                    "No sections in this term. Sync class data from Settings.",
+                    &[],
+                ),
+                Style::default().fg(theme.muted_color),
+            ))]
+        } else {
+            // keep the selected row in view by scrolling just far enough
+            let visible = catalog_area.height.saturating_sub(2).max(1) as usize;
+            let selected = self.selected.min(rows.len() - 1);
+            let first = (selected + 1).saturating_sub(visible);
+            rows.iter()
+                .enumerate()
+                .skip(first)
+                .take(visible)
+                .map(|(i, row)| self.row_line(*row, i == selected, theme))
+                .collect()
+        };
+
+        frame.render_widget(Clear, catalog_area);
+
+        let catalog_paragraph = Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(
+                    " {} ",
+                    i18n::tr("catalog-title", "Course Catalog", &[])
+                ))
+                .title_style(
+                    Style::default()
+                        .fg(theme.title_color)
+                        .add_modifier(Modifier::BOLD),
+                )
+                .border_style(Style::default().fg(theme.border_color))
+                .style(Style::default().bg(theme.background_color)),
+        );
+
+        frame.render_widget(catalog_paragraph, catalog_area);
+    }
+
+    /// Handle a key event and return an action
+    ///
+    /// Arguments:
+    /// --- ---
+    /// key -> The key event to handle
+    /// --- ---
+    ///
+    /// Returns: KeyAction -> The action to take in response to the key
+    ///
+    fn handle_key(&mut self, key: KeyEvent) -> KeyAction {
+        let (action, _catalog_action) = self.handle_key_with_action(key);
+        action
+    }
+
+    /// Return the focus mode(s) this widget handles
+    ///
+    /// Returns:
+    /// --- ---
+    /// Vec<FocusMode> -> The focus modes this widget handles
+    /// --- ---
+    ///
+    fn focus_modes(&self) -> Vec<FocusMode> {
+        vec![FocusMode::CatalogBrowse]
+    }
+}
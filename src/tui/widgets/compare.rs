@@ -0,0 +1,288 @@
+/// src/tui/widgets/compare.rs
+///
+/// Compare view widget rendering
+///
+/// Renders two class sections side by side, highlighting fields that differ
+///
+/// Contains:
+/// --- ---
+/// CompareWidget -> Widget for the side-by-side comparison overlay
+/// --- ---
+use crate::data::sql::Class;
+use crate::tui::state::FocusMode;
+use crate::tui::themes::Theme;
+use crate::tui::widgets::traits::{KeyAction, Widget};
+use crate::utils::i18n;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+/// Width of the field label column
+const LABEL_WIDTH: usize = 12;
+
+/// Width of each class value column
+const VALUE_WIDTH: usize = 34;
+
+/// Compare widget for rendering two classes side by side
+///
+/// Fields:
+/// --- ---
+/// left -> The first class marked for comparison
+/// right -> The second class marked for comparison
+/// --- ---
+///
+pub struct CompareWidget {
+    pub left: Option<Class>,
+    pub right: Option<Class>,
+}
+
+impl CompareWidget {
+    /// Create a new CompareWidget
+    ///
+    /// Returns:
+    /// --- ---
+    /// CompareWidget -> The new CompareWidget
+    /// --- ---
+    ///
+    pub fn new() -> Self {
+        Self {
+            left: None,
+            right: None,
+        }
+    }
+
+    /// Open the comparison for two classes
+    ///
+    /// Arguments:
+    /// --- ---
+    /// left -> The first class
+    /// right -> The second class
+    /// --- ---
+    ///
+    /// Returns: None
+    ///
+    pub fn open(&mut self, left: Class, right: Class) {
+        self.left = Some(left);
+        self.right = Some(right);
+    }
+}
+
+impl Default for CompareWidget {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Build the (label, value) rows compared for a class
+///
+/// Arguments:
+/// --- ---
+/// class -> The class to describe
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Vec<(String, String)> -> The translated field labels and values in display order
+/// --- ---
+///
+fn comparison_rows(class: &Class) -> Vec<(String, String)> {
+    let seats = match (class.enrollment, class.max_enrollment) {
+        (Some(e), Some(m)) => i18n::tr(
+            "compare-seats",
+            "{enrolled} / {max} ({open} open)",
+            &[
+                ("enrolled", &e.to_string()),
+                ("max", &m.to_string()),
+                ("open", &(m - e).max(0).to_string()),
+            ],
+        ),
+        _ => i18n::tr("compare-unknown", "Unknown", &[]),
+    };
+    let times = class
+        .format_for_display()
+        .get(3)
+        .cloned()
+        .unwrap_or_else(|| "TBA".to_string());
+
+    vec![
+        (
+            i18n::tr("compare-section", "Section", &[]),
+            format!(
+                "{} {}-{}",
+                class.subject_code, class.course_number, class.section_sequence
+            ),
+        ),
+        (
+            i18n::tr("compare-course-title", "Title", &[]),
+            class.title.clone(),
+        ),
+        (
+            i18n::tr("compare-professor", "Professor", &[]),
+            class
+                .professor_name
+                .clone()
+                .unwrap_or_else(|| "TBA".to_string()),
+        ),
+        (i18n::tr("compare-time", "Time", &[]), times),
+        (
+            i18n::tr("compare-type", "Type", &[]),
+            class
+                .meeting_type
+                .clone()
+                .unwrap_or_else(|| "N/A".to_string()),
+        ),
+        (i18n::tr("compare-seats-label", "Seats", &[]), seats),
+        (
+            i18n::tr("compare-campus", "Campus", &[]),
+            class.campus.clone().unwrap_or_else(|| "N/A".to_string()),
+        ),
+        (
+            i18n::tr("compare-method", "Method", &[]),
+            class
+                .instruction_method
+                .clone()
+                .unwrap_or_else(|| "N/A".to_string()),
+        ),
+        (
+            i18n::tr("compare-credits", "Credits", &[]),
+            format!("{}", class.credit_hours),
+        ),
+    ]
+}
+
+/// Truncate a value so it fits in a value column
+///
+/// Arguments:
+/// --- ---
+/// value -> The value to fit
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> The value padded or truncated to VALUE_WIDTH characters
+/// --- ---
+///
+fn fit_value(value: &str) -> String {
+    if value.chars().count() > VALUE_WIDTH {
+        let truncated: String = value.chars().take(VALUE_WIDTH - 3).collect();
+        format!("{}...", truncated)
+    } else {
+        format!("{:<width$}", value, width = VALUE_WIDTH)
+    }
+}
+
+impl Widget for CompareWidget {
+    /// Render the comparison overlay
+    ///
+    /// Arguments:
+    /// --- ---
+    /// frame -> The frame to render to
+    /// theme -> The theme to use for styling
+    /// --- ---
+    ///
+    /// Returns: None
+    ///
+    fn render(&self, frame: &mut Frame, theme: &Theme) {
+        let (left, right) = match (&self.left, &self.right) {
+            (Some(l), Some(r)) => (l, r),
+            _ => return,
+        };
+
+        let left_rows = comparison_rows(left);
+        let right_rows = comparison_rows(right);
+
+        let compare_width = (LABEL_WIDTH + VALUE_WIDTH * 2 + 6) as u16;
+        let compare_height = left_rows.len() as u16 + 5;
+
+        let compare_area = Rect {
+            x: (frame.area().width.saturating_sub(compare_width)) / 2,
+            y: (frame.area().height.saturating_sub(compare_height)) / 2,
+            width: compare_width,
+            height: compare_height,
+        }
+        .intersection(frame.area());
+
+        let mut lines: Vec<Line> = vec![
+            Line::from(Span::styled(
+                i18n::tr("compare-hint", "Differences are highlighted", &[]),
+                Style::default().fg(theme.muted_color),
+            )),
+            Line::from(""),
+        ];
+
+        for ((label, left_value), (_, right_value)) in left_rows.iter().zip(right_rows.iter()) {
+            let differs = left_value != right_value;
+            let value_style = if differs {
+                Style::default()
+                    .fg(theme.warning_color)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.text_color)
+            };
+            // mark differing rows with more than color so they stand out in any theme
+            let marker = if differs { "≠ " } else { "  " };
+
+            lines.push(Line::from(vec![
+                Span::styled(marker, Style::default().fg(theme.warning_color)),
+                Span::styled(
+                    format!("{:<width$}", label, width = LABEL_WIDTH - 2),
+                    Style::default().fg(theme.info_color),
+                ),
+                Span::styled(fit_value(left_value), value_style),
+                Span::raw("  "),
+                Span::styled(fit_value(right_value), value_style),
+            ]));
+        }
+
+        frame.render_widget(Clear, compare_area);
+
+        let compare_paragraph = Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(
+                    " {} ",
+                    i18n::tr("compare-title", "Compare Sections", &[])
+                ))
+                .title_style(
+                    Style::default()
+                        .fg(theme.title_color)
+                        .add_modifier(Modifier::BOLD),
+                )
+                .border_style(Style::default().fg(theme.border_color))
+                .style(Style::default().bg(theme.background_color)),
+        );
+
+        frame.render_widget(compare_paragraph, compare_area);
+    }
+
+    /// Handle a key event and return an action
+    ///
+    /// Arguments:
+    /// --- ---
+    /// key -> The key event to handle
+    /// --- ---
+    ///
+    /// Returns: KeyAction -> The action to take in response to the key
+    ///
+    fn handle_key(&mut self, key: KeyEvent) -> KeyAction {
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => KeyAction::Exit,
+            KeyCode::Esc | KeyCode::Backspace | KeyCode::Enter => KeyAction::Back,
+            _ => KeyAction::Continue,
+        }
+    }
+
+    /// Return the focus mode(s) this widget handles
+    ///
+    /// Returns:
+    /// --- ---
+    /// Vec<FocusMode> -> The focus modes this widget handles
+    /// --- ---
+    ///
+    fn focus_modes(&self) -> Vec<FocusMode> {
+        vec![FocusMode::CompareView]
+    }
+}
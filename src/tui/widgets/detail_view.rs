@@ -6,8 +6,9 @@
 use crate::data::sql::Class;
 use crate::tui::state::FocusMode;
 use crate::tui::themes::Theme;
-use crate::tui::widgets::helpers::{format_day_for_display, get_day_order};
+use crate::tui::widgets::helpers::format_day_for_display;
 use crate::tui::widgets::traits::{KeyAction, Widget};
+use crate::utils::time::format_12_hour;
 use crossterm::event::KeyEvent;
 use ratatui::layout::Rect;
 use ratatui::style::{Modifier, Style};
@@ -107,6 +108,10 @@ impl Widget for DetailViewWidget {
 
         // calculate base content lines (without description)
         let mut base_lines = 2; // course code + title
+        base_lines += 1; // CRN
+        if class.restrictions.is_restricted() {
+            base_lines += 1 + class.restrictions.labels().len(); // badge + one per restriction
+        }
         base_lines += 1; // blank line
         base_lines += 1; // professor
         if class.professor_email.is_some() {
@@ -115,17 +120,12 @@ impl Widget for DetailViewWidget {
         base_lines += 1; // blank line
         base_lines += 1; // "Schedule:" label
                          // count schedule lines
-        if let Some(meeting_times_str) = &class.meeting_times {
-            if !meeting_times_str.is_empty() {
-                base_lines += meeting_times_str
-                    .split('|')
-                    .filter(|mt| !mt.is_empty())
-                    .count();
-            } else {
-                base_lines += 1; // "TBD"
-            }
-        } else {
-            base_lines += 1; // "TBD"
+        base_lines += class.meeting_times.len().max(1); // one line per meeting, or "TBD"
+        if class.final_exam.is_some() {
+            base_lines += 1; // final exam
+        }
+        if class.session_label().is_some() {
+            base_lines += 1; // session
         }
         if class.meeting_type.is_some() {
             base_lines += 1; // type
@@ -137,6 +137,9 @@ impl Widget for DetailViewWidget {
         base_lines += 1; // blank line
         base_lines += 1; // enrollment
         base_lines += 1; // credits
+        if class.fee_label().is_some() {
+            base_lines += 1; // fee
+        }
         base_lines += 2; // blank line + "Description:" label
 
         // total content lines = base + description lines
@@ -205,6 +208,37 @@ impl Widget for DetailViewWidget {
                 .fg(theme.text_color)
                 .add_modifier(Modifier::BOLD),
         )));
+
+        // registration number, what the registrar's portal asks for
+        lines.push(Line::from(vec![
+            Span::styled("CRN: ", Style::default().fg(theme.warning_color)),
+            match &class.crn {
+                Some(crn) => Span::styled(
+                    crn.clone(),
+                    Style::default()
+                        .fg(theme.selected_color)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                None => Span::styled("Not in synced data", Style::default().fg(theme.muted_color)),
+            },
+        ]));
+
+        // registration restrictions, flagged so they aren't missed before adding to the cart
+        if class.restrictions.is_restricted() {
+            lines.push(Line::from(Span::styled(
+                " ! RESTRICTED ",
+                Style::default()
+                    .fg(theme.background_color)
+                    .bg(theme.warning_color)
+                    .add_modifier(Modifier::BOLD),
+            )));
+            for label in class.restrictions.labels() {
+                lines.push(Line::from(vec![
+                    Span::styled("    ", Style::default().fg(theme.text_color)), // 4 spaces for indentation
+                    Span::styled(label, Style::default().fg(theme.warning_color)),
+                ]));
+            }
+        }
         lines.push(Line::from("")); // blank line
 
         // professor
@@ -232,92 +266,54 @@ impl Widget for DetailViewWidget {
             Style::default().fg(theme.success_color),
         )]));
 
-        // helper function to format time
-        let format_time = |time: &str| -> String {
-            let parts: Vec<&str> = time.split(':').collect();
-            if parts.len() >= 2 {
-                let hours: i32 = parts[0].parse().unwrap_or(0);
-                let minutes: i32 = parts[1].parse().unwrap_or(0);
-
-                let (display_hour, period) = if hours == 0 {
-                    (12, "am")
-                } else if hours < 12 {
-                    (hours, "am")
-                } else if hours == 12 {
-                    (12, "pm")
-                } else {
-                    (hours - 12, "pm")
-                };
-
-                format!("{}:{:02}{}", display_hour, minutes, period)
-            } else {
-                time.to_string()
-            }
-        };
-
-        // parse meeting_times if available, otherwise fall back to old format
-        if let Some(meeting_times_str) = &class.meeting_times {
-            if !meeting_times_str.is_empty() {
-                // parse meeting times: "M:08:00:00-10:45:00|TH:08:00:00-09:15:00"
-                // collect all meeting times with their day codes for sorting
-                let mut meeting_times: Vec<(u8, String, String, String)> = Vec::new(); // (day_order, days_part, start, end)
-
-                for mt in meeting_times_str.split('|') {
-                    if mt.is_empty() {
-                        continue;
-                    }
-                    if let Some(colon_pos) = mt.find(':') {
-                        let days_part = &mt[..colon_pos];
-                        let time_part = &mt[colon_pos + 1..];
-                        if let Some(dash_pos) = time_part.find('-') {
-                            let start = format_time(&time_part[..dash_pos]);
-                            let end = format_time(&time_part[dash_pos + 1..]);
-                            if !days_part.is_empty() && !start.is_empty() && !end.is_empty() {
-                                // get the first day code for sorting (in case of multiple days like "MW")
-                                let first_day = if days_part.starts_with("TH") {
-                                    "TH"
-                                } else if days_part.starts_with("SU") {
-                                    "SU"
-                                } else if days_part.len() > 0 {
-                                    &days_part[..1]
-                                } else {
-                                    days_part
-                                };
-                                let day_order = get_day_order(first_day);
-                                meeting_times.push((day_order, days_part.to_string(), start, end));
-                            }
-                        }
-                    }
-                }
-
-                // sort by day order (Monday first)
-                meeting_times.sort_by_key(|(day_order, _, _, _)| *day_order);
-
-                // display sorted meeting times
-                for (_, days_part, start, end) in meeting_times {
-                    // format day code for display (add space after single letters)
-                    let formatted_days = format_day_for_display(&days_part);
-                    lines.push(Line::from(vec![
-                        Span::styled("    ", Style::default().fg(theme.text_color)), // 4 spaces for indentation
-                        Span::styled(
-                            format!("{} {}-{}", formatted_days, start, end),
-                            Style::default().fg(theme.text_color),
-                        ),
-                    ]));
-                }
-            } else {
-                // empty meeting_times
-                lines.push(Line::from(vec![
-                    Span::styled("    ", Style::default().fg(theme.text_color)), // 4 spaces for indentation
-                    Span::styled("TBD", Style::default().fg(theme.text_color)),
-                ]));
-            }
-        } else {
+        // display meeting times (already sorted Monday first)
+        if class.meeting_times.is_empty() {
             // no meeting_times available
             lines.push(Line::from(vec![
                 Span::styled("    ", Style::default().fg(theme.text_color)), // 4 spaces for indentation
                 Span::styled("TBD", Style::default().fg(theme.text_color)),
             ]));
+        } else {
+            for meeting in &class.meeting_times {
+                // format day code for display (add space after single letters)
+                let formatted_days = format_day_for_display(&meeting.days_compact());
+                let mut spans = vec![
+                    Span::styled("    ", Style::default().fg(theme.text_color)), // 4 spaces for indentation
+                    Span::styled(
+                        format!(
+                            "{} {}-{}",
+                            formatted_days,
+                            format_12_hour(meeting.start_minutes),
+                            format_12_hour(meeting.end_minutes)
+                        ),
+                        Style::default().fg(theme.text_color),
+                    ),
+                ];
+                // dates matter for half-term and every-other-week meetings
+                if let Some(note) = meeting.schedule_note() {
+                    spans.push(Span::styled(
+                        format!(" ({})", note),
+                        Style::default().fg(theme.muted_color),
+                    ));
+                }
+                lines.push(Line::from(spans));
+            }
+        }
+
+        // part of the term, for sections that don't run all of it
+        if let Some(session) = class.session_label() {
+            lines.push(Line::from(vec![
+                Span::styled("Session: ", Style::default().fg(theme.success_color)),
+                Span::styled(session, Style::default().fg(theme.text_color)),
+            ]));
+        }
+
+        // final exam, when the source lists one
+        if let Some(exam) = &class.final_exam {
+            lines.push(Line::from(vec![
+                Span::styled("Final: ", Style::default().fg(theme.success_color)),
+                Span::styled(exam.label(), Style::default().fg(theme.text_color)),
+            ]));
         }
 
         // meeting type
@@ -368,6 +364,14 @@ impl Widget for DetailViewWidget {
             ),
         ]));
 
+        // lab or course fee, charged on top of tuition
+        if let Some(fee) = class.fee_label() {
+            lines.push(Line::from(vec![
+                Span::styled("Fee: ", Style::default().fg(theme.info_color)),
+                Span::styled(fee, Style::default().fg(theme.text_color)),
+            ]));
+        }
+
         // description
         lines.push(Line::from("")); // blank line
         lines.push(Line::from(vec![Span::styled(
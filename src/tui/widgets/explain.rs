@@ -0,0 +1,253 @@
+/// src/tui/widgets/explain.rs
+///
+/// Query explain widget rendering
+///
+/// Renders the index advice, query plan, and SQL for the last search, and lets the user
+/// create the suggested indexes
+///
+/// Contains:
+/// --- ---
+/// ExplainAction -> Action returned by the explain widget for app-level handling
+/// ExplainWidget -> Widget for the query explain screen
+/// --- ---
+use crate::data::index_advisor::{IndexAdvice, QueryExplanation};
+use crate::tui::state::FocusMode;
+use crate::tui::themes::Theme;
+use crate::tui::widgets::traits::{KeyAction, Widget};
+use crate::utils::i18n;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+use ratatui::Frame;
+
+/// Width of the explain box
+const EXPLAIN_WIDTH: u16 = 100;
+
+/// Number of lines moved by Page Up/Down
+const PAGE_SIZE: usize = 10;
+
+/// Action returned by the explain widget for app-level handling
+///
+/// Variants:
+/// --- ---
+/// None -> No action needed
+/// CreateIndexes -> The user asked to create the suggested indexes
+/// --- ---
+///
+#[derive(Debug, Clone)]
+pub enum ExplainAction {
+    None,
+    CreateIndexes(Vec<IndexAdvice>),
+}
+
+/// Explain widget for rendering a search's query plan and index advice
+///
+/// Fields:
+/// --- ---
+/// lines -> Report lines built from the explanation, followed by the SQL
+/// advice -> Indexes suggested for the explained search
+/// scroll -> Index of the first visible line
+/// --- ---
+///
+pub struct ExplainWidget {
+    pub lines: Vec<String>,
+    pub advice: Vec<IndexAdvice>,
+    pub scroll: usize,
+}
+
+impl ExplainWidget {
+    /// Create a new ExplainWidget
+    ///
+    /// Returns:
+    /// --- ---
+    /// ExplainWidget -> The new ExplainWidget
+    /// --- ---
+    ///
+    pub fn new() -> Self {
+        Self {
+            lines: Vec::new(),
+            advice: Vec::new(),
+            scroll: 0,
+        }
+    }
+
+    /// Load an explanation and scroll back to the top
+    ///
+    /// Arguments:
+    /// --- ---
+    /// explanation -> The query plan and advice to show
+    /// --- ---
+    ///
+    /// Returns: None
+    ///
+    pub fn open(&mut self, explanation: &QueryExplanation) {
+        self.lines = explanation.report_lines();
+        self.lines.push(String::new());
+        self.lines.push("SQL".to_string());
+        self.lines.push(format!("  {}", explanation.sql));
+        self.advice = explanation.advice.clone();
+        self.scroll = 0;
+    }
+
+    /// Get the largest scroll offset that still shows content
+    ///
+    /// Returns:
+    /// --- ---
+    /// usize -> The maximum scroll offset
+    /// --- ---
+    ///
+    fn max_scroll(&self) -> usize {
+        self.lines.len().saturating_sub(1)
+    }
+
+    /// Handle a key event and return both KeyAction and ExplainAction
+    ///
+    /// Arguments:
+    /// --- ---
+    /// key -> The key event to handle
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// (KeyAction, ExplainAction) -> The key action and the explain-specific action
+    /// --- ---
+    ///
+    pub fn handle_key_with_action(&mut self, key: KeyEvent) -> (KeyAction, ExplainAction) {
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                (KeyAction::Exit, ExplainAction::None)
+            }
+            KeyCode::Char('i') | KeyCode::Char('I') if !self.advice.is_empty() => (
+                KeyAction::Continue,
+                ExplainAction::CreateIndexes(self.advice.clone()),
+            ),
+            KeyCode::Esc | KeyCode::Backspace | KeyCode::Enter => {
+                (KeyAction::Back, ExplainAction::None)
+            }
+            KeyCode::Up => {
+                self.scroll = self.scroll.saturating_sub(1);
+                (KeyAction::Continue, ExplainAction::None)
+            }
+            KeyCode::Down => {
+                self.scroll = (self.scroll + 1).min(self.max_scroll());
+                (KeyAction::Continue, ExplainAction::None)
+            }
+            KeyCode::PageUp => {
+                self.scroll = self.scroll.saturating_sub(PAGE_SIZE);
+                (KeyAction::Continue, ExplainAction::None)
+            }
+            KeyCode::PageDown => {
+                self.scroll = (self.scroll + PAGE_SIZE).min(self.max_scroll());
+                (KeyAction::Continue, ExplainAction::None)
+            }
+            KeyCode::Home => {
+                self.scroll = 0;
+                (KeyAction::Continue, ExplainAction::None)
+            }
+            KeyCode::End => {
+                self.scroll = self.max_scroll();
+                (KeyAction::Continue, ExplainAction::None)
+            }
+            _ => (KeyAction::Continue, ExplainAction::None),
+        }
+    }
+}
+
+impl Default for ExplainWidget {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget for ExplainWidget {
+    /// Render the explain screen
+    ///
+    /// Arguments:
+    /// --- ---
+    /// frame -> The frame to render to
+    /// theme -> The theme to use for styling
+    /// --- ---
+    ///
+    /// Returns: None
+    ///
+    fn render(&self, frame: &mut Frame, theme: &Theme) {
+        // position below the logo like the stats screen, leaving room for the help bar
+        let explain_y = 13_u16;
+        let explain_area = Rect {
+            x: frame.area().width.saturating_sub(EXPLAIN_WIDTH) / 2,
+            y: explain_y,
+            width: EXPLAIN_WIDTH,
+            height: frame.area().height.saturating_sub(explain_y + 3),
+        }
+        .intersection(frame.area());
+
+        let lines: Vec<Line> = self
+            .lines
+            .iter()
+            .skip(self.scroll)
+            .map(|line| {
+                // unindented lines are section headers
+                if line.starts_with(' ') || line.is_empty() {
+                    Line::from(Span::styled(
+                        line.clone(),
+                        Style::default().fg(theme.text_color),
+                    ))
+                } else {
+                    Line::from(Span::styled(
+                        line.clone(),
+                        Style::default()
+                            .fg(theme.info_color)
+                            .add_modifier(Modifier::BOLD),
+                    ))
+                }
+            })
+            .collect();
+
+        frame.render_widget(Clear, explain_area);
+
+        let explain_paragraph = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(
+                    " {} ",
+                    i18n::tr("explain-title", "Query Plan", &[])
+                ))
+                .title_style(
+                    Style::default()
+                        .fg(theme.title_color)
+                        .add_modifier(Modifier::BOLD),
+                )
+                .border_style(Style::default().fg(theme.border_color))
+                .style(Style::default().bg(theme.background_color)),
+        );
+
+        frame.render_widget(explain_paragraph, explain_area);
+    }
+
+    /// Handle a key event and return an action
+    ///
+    /// Arguments:
+    /// --- ---
+    /// key -> The key event to handle
+    /// --- ---
+    ///
+    /// Returns: KeyAction -> The action to take in response to the key
+    ///
+    fn handle_key(&mut self, key: KeyEvent) -> KeyAction {
+        let (action, _explain_action) = self.handle_key_with_action(key);
+        action
+    }
+
+    /// Return the focus mode(s) this widget handles
+    ///
+    /// Returns:
+    /// --- ---
+    /// Vec<FocusMode> -> The focus modes this widget handles
+    /// --- ---
+    ///
+    fn focus_modes(&self) -> Vec<FocusMode> {
+        vec![FocusMode::QueryExplain]
+    }
+}
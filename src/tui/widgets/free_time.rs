@@ -0,0 +1,284 @@
+/// src/tui/widgets/free_time.rs
+///
+/// Free time finder widget
+///
+/// Answers requests like "1 hour MWF between 10am and 3pm" for the schedule being viewed,
+/// updating the free blocks as the request is typed
+///
+/// Contains:
+/// --- ---
+/// FreeTimeWidget -> Widget for the free time finder
+/// --- ---
+use crate::data::free_time::{
+    common_free_time, find_free_time, format_length, format_range, FreeTimeRequest,
+    DEFAULT_FREE_TIME_REQUEST,
+};
+use crate::data::sql::Class;
+use crate::tui::state::FocusMode;
+use crate::tui::themes::Theme;
+use crate::tui::widgets::traits::{KeyAction, Widget};
+use crate::utils::time::DAY_CODES;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+/// Width of the free time box
+const FREE_TIME_WIDTH: u16 = 78;
+
+/// Names of the days in DAY_CODES order
+const DAY_NAMES: [&str; 7] = [
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+
+/// Free time finder widget
+///
+/// Fields:
+/// --- ---
+/// input -> The request being typed
+/// schedule -> Classes of the schedule being searched
+/// schedule_name -> Name of the schedule (if it is saved)
+/// --- ---
+///
+pub struct FreeTimeWidget {
+    pub input: String,
+    pub schedule: Vec<Class>,
+    pub schedule_name: Option<String>,
+}
+
+impl FreeTimeWidget {
+    /// Create a new FreeTimeWidget
+    ///
+    /// Returns:
+    /// --- ---
+    /// FreeTimeWidget -> The new FreeTimeWidget with the example request
+    /// --- ---
+    ///
+    pub fn new() -> Self {
+        Self {
+            input: DEFAULT_FREE_TIME_REQUEST.to_string(),
+            schedule: Vec::new(),
+            schedule_name: None,
+        }
+    }
+
+    /// Open the finder for a schedule, keeping the last request
+    ///
+    /// Arguments:
+    /// --- ---
+    /// schedule -> Classes of the schedule to search
+    /// schedule_name -> Name of the schedule (if it is saved)
+    /// --- ---
+    ///
+    /// Returns: None
+    ///
+    pub fn open(&mut self, schedule: Vec<Class>, schedule_name: Option<String>) {
+        self.schedule = schedule;
+        self.schedule_name = schedule_name;
+    }
+
+    /// Build the result lines for the current request
+    ///
+    /// Arguments:
+    /// --- ---
+    /// theme -> The theme to use for styling
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Vec<Line> -> The request as understood, then the free blocks of each day and those
+    ///              free on every day, or the parse error
+    /// --- ---
+    ///
+    fn result_lines(&self, theme: &Theme) -> Vec<Line<'static>> {
+        let request = match FreeTimeRequest::parse(&self.input) {
+            Ok(request) => request,
+            Err(e) => {
+                return vec![Line::from(Span::styled(
+                    e,
+                    Style::default().fg(theme.error_color),
+                ))]
+            }
+        };
+
+        let mut lines = vec![
+            Line::from(Span::styled(
+                format!("Looking for {}", request.describe()),
+                Style::default().fg(theme.muted_color),
+            )),
+            Line::from(""),
+        ];
+
+        let blocks = find_free_time(&self.schedule, &request);
+        for day in &request.days {
+            let day_name = DAY_CODES
+                .iter()
+                .position(|code| code == day)
+                .map_or(day.as_str(), |idx| DAY_NAMES[idx]);
+            let day_blocks: Vec<String> = blocks
+                .iter()
+                .filter(|block| &block.day == day)
+                .map(|block| {
+                    format!(
+                        "{} ({})",
+                        block.label(),
+                        format_length(block.end_minutes - block.start_minutes)
+                    )
+                })
+                .collect();
+            let (text, color) = if day_blocks.is_empty() {
+                ("no free block".to_string(), theme.muted_color)
+            } else {
+                (day_blocks.join(", "), theme.success_color)
+            };
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("{:<11}", day_name),
+                    Style::default()
+                        .fg(theme.info_color)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(text, Style::default().fg(color)),
+            ]));
+        }
+
+        // blocks that repeat on every day are the ones worth planning around
+        if request.days.len() > 1 {
+            let common: Vec<String> = common_free_time(&self.schedule, &request)
+                .iter()
+                .map(|(start, end)| format_range(*start, *end))
+                .collect();
+            lines.push(Line::from(""));
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("Every {}: ", request.days.join(" ")),
+                    Style::default()
+                        .fg(theme.title_color)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    if common.is_empty() {
+                        "no common block".to_string()
+                    } else {
+                        common.join(", ")
+                    },
+                    Style::default().fg(theme.text_color),
+                ),
+            ]));
+        }
+        lines
+    }
+}
+
+impl Default for FreeTimeWidget {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget for FreeTimeWidget {
+    /// Render the free time finder
+    ///
+    /// Arguments:
+    /// --- ---
+    /// frame -> The frame to render to
+    /// theme -> The theme to use for styling
+    /// --- ---
+    ///
+    /// Returns: None
+    ///
+    fn render(&self, frame: &mut Frame, theme: &Theme) {
+        // position below the logo like the stats screen, leaving room for the help bar
+        let free_time_y = 13_u16;
+        let area = Rect {
+            x: frame.area().width.saturating_sub(FREE_TIME_WIDTH) / 2,
+            y: free_time_y,
+            width: FREE_TIME_WIDTH,
+            height: frame.area().height.saturating_sub(free_time_y + 3),
+        }
+        .intersection(frame.area());
+
+        let mut lines = vec![
+            Line::from(vec![
+                Span::styled(
+                    "Free: ",
+                    Style::default()
+                        .fg(theme.title_color)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(self.input.clone(), Style::default().fg(theme.text_color)),
+                Span::styled("│", Style::default().fg(theme.selected_color)),
+            ]),
+            Line::from(""),
+        ];
+        lines.extend(self.result_lines(theme));
+
+        let title = match &self.schedule_name {
+            Some(name) => format!(" Free Time: {} ", name),
+            None => " Free Time ".to_string(),
+        };
+
+        frame.render_widget(Clear, area);
+        let paragraph = Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title)
+                .title_style(
+                    Style::default()
+                        .fg(theme.title_color)
+                        .add_modifier(Modifier::BOLD),
+                )
+                .border_style(Style::default().fg(theme.border_color))
+                .style(Style::default().bg(theme.background_color)),
+        );
+        frame.render_widget(paragraph, area);
+    }
+
+    /// Handle a key event and return an action
+    ///
+    /// Arguments:
+    /// --- ---
+    /// key -> The key event to handle
+    /// --- ---
+    ///
+    /// Returns: KeyAction -> The action to take in response to the key
+    ///
+    fn handle_key(&mut self, key: KeyEvent) -> KeyAction {
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => KeyAction::Exit,
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.input.clear();
+                KeyAction::Continue
+            }
+            KeyCode::Esc | KeyCode::Enter => KeyAction::Back,
+            KeyCode::Backspace => {
+                self.input.pop();
+                KeyAction::Continue
+            }
+            KeyCode::Char(c) => {
+                self.input.push(c);
+                KeyAction::Continue
+            }
+            _ => KeyAction::Continue,
+        }
+    }
+
+    /// Return the focus mode(s) this widget handles
+    ///
+    /// Returns:
+    /// --- ---
+    /// Vec<FocusMode> -> The focus modes this widget handles
+    /// --- ---
+    ///
+    fn focus_modes(&self) -> Vec<FocusMode> {
+        vec![FocusMode::FreeTime]
+    }
+}
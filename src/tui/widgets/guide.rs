@@ -8,9 +8,11 @@
 /// --- ---
 /// QueryGuideWidget -> Widget for the scrollable query guide
 /// --- ---
+use crate::dsl::entities::{ValueType, ENTITIES};
 use crate::tui::state::FocusMode;
 use crate::tui::themes::Theme;
 use crate::tui::widgets::traits::{KeyAction, Widget};
+use crate::utils::i18n;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::layout::Rect;
 use ratatui::style::{Modifier, Style};
@@ -27,13 +29,11 @@ use ratatui::Frame;
 /// --- ---
 /// scroll -> Current scroll position in the guide content
 /// max_scroll -> Maximum scroll value (computed during render)
-/// return_focus -> Focus mode to return to when closing the guide
 /// --- ---
 ///
 pub struct QueryGuideWidget {
     pub scroll: usize,
     pub max_scroll: usize,
-    pub return_focus: FocusMode,
 }
 
 impl QueryGuideWidget {
@@ -50,22 +50,17 @@ impl QueryGuideWidget {
         Self {
             scroll: 0,
             max_scroll: 0,
-            return_focus: FocusMode::MainMenu,
         }
     }
 
-    /// Open the guide with a specific return focus mode
+    /// Open the guide at the top
     ///
-    /// Arguments:
-    /// --- ---
-    /// return_focus -> the focus mode to return to when closing
-    /// --- ---
+    /// Arguments: None
     ///
     /// Returns: None
     ///
-    pub fn open(&mut self, return_focus: FocusMode) {
+    pub fn open(&mut self) {
         self.scroll = 0;
-        self.return_focus = return_focus;
     }
 
     /// Reset scroll position
@@ -129,7 +124,7 @@ impl QueryGuideWidget {
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .title(" Query Guide ")
+                    .title(format!(" {} ", i18n::tr("guide-title", "Query Guide", &[])))
                     .title_style(
                         Style::default()
                             .fg(theme.title_color)
@@ -318,7 +313,7 @@ impl QueryGuideWidget {
 
         // basic query structure
         lines.push(Line::from(vec![Span::styled(
-            "BASIC QUERY STRUCTURE",
+            i18n::tr("guide-basic-structure", "BASIC QUERY STRUCTURE", &[]),
             Style::default()
                 .fg(theme.success_color)
                 .add_modifier(Modifier::BOLD),
@@ -351,16 +346,65 @@ impl QueryGuideWidget {
         )));
         lines.push(Line::from(""));
 
+        // every searchable field, straight from the entity registry
+        lines.push(Line::from(vec![Span::styled(
+            i18n::tr("guide-search-fields", "SEARCH FIELDS", &[]),
+            Style::default()
+                .fg(theme.info_color)
+                .add_modifier(Modifier::BOLD),
+        )]));
+        lines.push(Line::from(""));
+        for info in ENTITIES
+            .iter()
+            .filter(|info| info.value_type != ValueType::Day)
+        {
+            let keywords = info
+                .tokens
+                .iter()
+                .map(|keyword| keyword.accepted_spellings().join("/"))
+                .collect::<Vec<_>>()
+                .join(" ");
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("  {}", keywords),
+                    Style::default().fg(theme.text_color),
+                ),
+                Span::styled(
+                    format!(" ({})", info.value_type.label()),
+                    Style::default().fg(theme.muted_color),
+                ),
+            ]));
+            lines.push(Line::from(Span::styled(
+                format!("    {}", info.description),
+                Style::default().fg(theme.muted_color),
+            )));
+        }
+        let days = ENTITIES
+            .iter()
+            .filter(|info| info.value_type == ValueType::Day)
+            .map(|info| info.name)
+            .collect::<Vec<_>>()
+            .join("/");
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {}", days), Style::default().fg(theme.text_color)),
+            Span::styled(" (day)", Style::default().fg(theme.muted_color)),
+        ]));
+        lines.push(Line::from(Span::styled(
+            "    Meets on the given day, abbreviations like mon, tu, and th also work",
+            Style::default().fg(theme.muted_color),
+        )));
+        lines.push(Line::from(""));
+
         // professor queries
         lines.push(Line::from(vec![Span::styled(
-            "PROFESSOR QUERIES",
+            i18n::tr("guide-professor-queries", "PROFESSOR QUERIES", &[]),
             Style::default()
                 .fg(theme.info_color)
                 .add_modifier(Modifier::BOLD),
         )]));
         lines.push(Line::from(""));
         lines.push(Line::from(Span::styled(
-            "Examples:",
+            i18n::tr("guide-examples", "Examples:", &[]),
             Style::default()
                 .fg(theme.warning_color)
                 .add_modifier(Modifier::BOLD),
@@ -397,7 +441,7 @@ impl QueryGuideWidget {
 
         // course queries
         lines.push(Line::from(vec![Span::styled(
-            "COURSE QUERIES",
+            i18n::tr("guide-course-queries", "COURSE QUERIES", &[]),
             Style::default()
                 .fg(theme.info_color)
                 .add_modifier(Modifier::BOLD),
@@ -523,7 +567,7 @@ impl QueryGuideWidget {
 
         // time queries
         lines.push(Line::from(vec![Span::styled(
-            "TIME QUERIES",
+            i18n::tr("guide-time-queries", "TIME QUERIES", &[]),
             Style::default()
                 .fg(theme.info_color)
                 .add_modifier(Modifier::BOLD),
@@ -583,7 +627,7 @@ impl QueryGuideWidget {
 
         // day queries
         lines.push(Line::from(vec![Span::styled(
-            "DAY QUERIES",
+            i18n::tr("guide-day-queries", "DAY QUERIES", &[]),
             Style::default()
                 .fg(theme.info_color)
                 .add_modifier(Modifier::BOLD),
@@ -625,7 +669,7 @@ impl QueryGuideWidget {
         )));
         lines.push(Line::from(""));
         lines.push(Line::from(Span::styled(
-            "Examples:",
+            i18n::tr("guide-examples", "Examples:", &[]),
             Style::default()
                 .fg(theme.warning_color)
                 .add_modifier(Modifier::BOLD),
@@ -654,7 +698,7 @@ impl QueryGuideWidget {
 
         // enrollment queries
         lines.push(Line::from(vec![Span::styled(
-            "ENROLLMENT QUERIES",
+            i18n::tr("guide-enrollment-queries", "ENROLLMENT QUERIES", &[]),
             Style::default()
                 .fg(theme.info_color)
                 .add_modifier(Modifier::BOLD),
@@ -705,7 +749,7 @@ impl QueryGuideWidget {
 
         // other queries
         lines.push(Line::from(vec![Span::styled(
-            "OTHER QUERIES",
+            i18n::tr("guide-other-queries", "OTHER QUERIES", &[]),
             Style::default()
                 .fg(theme.info_color)
                 .add_modifier(Modifier::BOLD),
@@ -774,7 +818,7 @@ impl QueryGuideWidget {
 
         // conditions and operators
         lines.push(Line::from(vec![Span::styled(
-            "CONDITIONS & OPERATORS",
+            i18n::tr("guide-conditions", "CONDITIONS & OPERATORS", &[]),
             Style::default()
                 .fg(theme.info_color)
                 .add_modifier(Modifier::BOLD),
@@ -833,7 +877,7 @@ impl QueryGuideWidget {
 
         // complex examples
         lines.push(Line::from(vec![Span::styled(
-            "COMPLEX EXAMPLES",
+            i18n::tr("guide-complex-examples", "COMPLEX EXAMPLES", &[]),
             Style::default()
                 .fg(theme.info_color)
                 .add_modifier(Modifier::BOLD),
@@ -906,7 +950,7 @@ impl QueryGuideWidget {
         // footer
         lines.push(Line::from(""));
         lines.push(Line::from(vec![Span::styled(
-            "PROJECT & LICENSE",
+            i18n::tr("guide-project-license", "PROJECT & LICENSE", &[]),
             Style::default()
                 .fg(theme.success_color)
                 .add_modifier(Modifier::BOLD),
@@ -955,16 +999,14 @@ impl Widget for QueryGuideWidget {
     fn handle_key(&mut self, key: KeyEvent) -> KeyAction {
         match key.code {
             KeyCode::Esc => {
-                let return_to = self.return_focus.clone();
                 self.reset();
-                KeyAction::Navigate(return_to)
+                KeyAction::Back
             }
             KeyCode::Char('g') | KeyCode::Char('G')
                 if key.modifiers.contains(KeyModifiers::ALT) =>
             {
-                let return_to = self.return_focus.clone();
                 self.reset();
-                KeyAction::Navigate(return_to)
+                KeyAction::Back
             }
             KeyCode::Up => {
                 if self.scroll > 0 {
@@ -6,6 +6,7 @@
 use crate::tui::state::FocusMode;
 use crate::tui::themes::Theme;
 use crate::tui::widgets::traits::{KeyAction, Widget};
+use crate::utils::i18n;
 use crossterm::event::KeyEvent;
 use ratatui::layout::Rect;
 use ratatui::style::Style;
@@ -19,12 +20,14 @@ use ratatui::Frame;
 /// toast_message -> optional toast message (help hidden when present)
 /// focus_mode -> current focus mode to determine help text
 /// schedule_selection_mode -> optional schedule mode for context
+/// professor_sections -> whether the professor directory shows a professor's sections
 /// --- ---
 ///
 pub struct HelpBarWidget {
     pub toast_message: Option<String>,
     pub focus_mode: FocusMode,
     pub schedule_selection_mode: Option<bool>,
+    pub professor_sections: bool,
 }
 
 impl HelpBarWidget {
@@ -40,6 +43,7 @@ impl HelpBarWidget {
             toast_message: None,
             focus_mode: FocusMode::MainMenu,
             schedule_selection_mode: None,
+            professor_sections: false,
         }
     }
 }
@@ -61,31 +65,94 @@ impl Widget for HelpBarWidget {
             return;
         }
 
-        let help_text = match self.focus_mode {
-            FocusMode::MainMenu => "↑↓ Navigate | Enter: Select | Esc: Quit",
-            FocusMode::Settings => "Esc: Back to Main Menu | Ctrl+C: Quit",
-            FocusMode::DetailView => "Press Esc or Enter to close detail view | C: Toggle Cart",
-            FocusMode::ResultsBrowse => {
-                "←↑↓→ Navigate | Enter: Details | Esc: Main Menu | Type to Search | Alt+G: Guide"
-            }
-            FocusMode::QueryInput => {
-                "Enter: Search | Tab: Completions | ↓: Browse Results | Esc: Main Menu | Alt+G: Guide"
-            }
-            FocusMode::QueryGuide => "↑↓ Scroll | Page Up/Down | Home/End | Alt+G or Esc: Close",
-            FocusMode::Help => "↑↓ Scroll | Page Up/Down | Home/End | Esc: Close",
+        let (id, english) = match self.focus_mode {
+            FocusMode::MainMenu => (
+                "help-main-menu",
+                "↑↓ Navigate | Enter: Select | 1-5: Recent Class | p: Profile | Esc: Quit",
+            ),
+            FocusMode::Settings => ("help-settings", "Esc: Back | Ctrl+C: Quit"),
+            FocusMode::DetailView => (
+                "help-detail-view",
+                "Press Esc or Enter to close detail view | C: Toggle Cart | W: Watch | P: Professor | S: Sections | M: More by Prof",
+            ),
+            FocusMode::ResultsBrowse => (
+                "help-results-browse",
+                "←↑↓→ Navigate | Enter: Details | /: Filter | Alt+C: Compare | Alt+D: Diff | Alt+U: Dupes | Alt+X: Plan | Alt+A: AST | Esc: Back | Alt+G: Guide",
+            ),
+            FocusMode::QueryInput => (
+                "help-query-input",
+                "Enter: Search | Tab: Completions | ↓: Results | Alt+F: Favorite | Alt+D: Diff | Alt+X: Plan | Alt+A: AST | Esc: Back | Alt+G: Guide",
+            ),
+            FocusMode::QueryGuide => (
+                "help-query-guide",
+                "↑↓ Scroll | Page Up/Down | Home/End | Alt+G or Esc: Close",
+            ),
+            FocusMode::Help => (
+                "help-help",
+                "↑↓ Scroll | Page Up/Down | Home/End | Esc: Close",
+            ),
             FocusMode::ScheduleCreation => {
                 // show different help text based on whether we're in selection mode or viewing mode
                 if self.schedule_selection_mode == Some(true) {
-                    "↑↓ Navigate | Space: Toggle | Tab: Details | Enter: Continue | d: Delete | Esc: Back"
+                    (
+                        "help-schedule-selection",
+                        "↑↓ Navigate | Space: Toggle | Tab: Details | Alt+C: Compare | g: One per course | Enter: Continue | d: Delete | Esc: Back",
+                    )
+                } else {
+                    (
+                        "help-schedule-view",
+                        "←→↑↓ Move | Tab: Next | Enter: Info | PgUp/PgDn: Switch | v: View | x: Finals | s: Save | o: Overlay | f: Free | y: CRNs | Esc: Back",
+                    )
+                }
+            }
+            FocusMode::MySchedules => (
+                "help-my-schedules",
+                "↑↓ Navigate | Enter: View | g: Regenerate | p: Paste Import | r: Rename | d: Delete | Esc: Back",
+            ),
+            FocusMode::SaveNameInput => ("help-save-name", "Enter: Save | Esc: Cancel"),
+            FocusMode::OverlayInput => ("help-overlay-input", "Enter: Overlay | Esc: Cancel"),
+            FocusMode::PasteImport => (
+                "help-paste-import",
+                "Paste your registrar schedule | Ctrl+A: Add to Cart | Ctrl+U: Clear | Esc: Cancel",
+            ),
+            FocusMode::FreeTime => (
+                "help-free-time",
+                "Type a request, e.g. 90 min TTH 9am-5pm | Ctrl+U: Clear | Esc: Back",
+            ),
+            FocusMode::CompareView => ("help-compare", "Esc or Enter: Close Comparison"),
+            FocusMode::DatabaseStats => (
+                "help-database-stats",
+                "↑↓ Scroll | Page Up/Down | Home/End | Esc: Back to Settings",
+            ),
+            FocusMode::CatalogBrowse => (
+                "help-catalog",
+                "↑↓ Navigate | →: Expand | ←: Collapse | Enter: Expand/Details | a: Add to Cart | Esc: Back",
+            ),
+            FocusMode::ProfessorDirectory => {
+                if self.professor_sections {
+                    (
+                        "help-professor-sections",
+                        "↑↓ Navigate | Enter: Details | a: Add to Cart | ←/Esc: Back to Professors",
+                    )
                 } else {
-                    "←→ Days | ↑↓ Time | Enter: Details | Page Up/Down: Schedules | s: Save | Esc: Back"
+                    (
+                        "help-professors",
+                        "Type to Search | ↑↓ Navigate | Enter: Sections | Ctrl+U: Clear | Esc: Back",
+                    )
                 }
             }
-            FocusMode::MySchedules => "↑↓ Navigate | Enter: View | d: Delete | Esc: Back",
-            FocusMode::SaveNameInput => "Enter: Save | Esc: Cancel",
+            FocusMode::QueryExplain => (
+                "help-query-explain",
+                "↑↓ Scroll | Page Up/Down | Home/End | i: Create Suggested Indexes | Esc: Back to Search",
+            ),
+            FocusMode::AstInspector => (
+                "help-ast-inspector",
+                "↑↓ Navigate | →: Expand | ←: Collapse | e/c: Expand/Collapse All | Tab: AST/Tokens | Esc: Back to Search",
+            ),
         };
+        let help_text = i18n::tr(id, english, &[]);
 
-        let help_width = help_text.len() as u16;
+        let help_width = help_text.chars().count() as u16;
 
         // position navigation controls at the bottom of the screen
         let help_y = frame.area().height.saturating_sub(2);
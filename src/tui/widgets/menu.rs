@@ -6,11 +6,19 @@
 /// --- ---
 /// MainMenuWidget -> Widget for the main menu with navigation
 /// MenuOption -> Enum for menu options
+/// MenuAction -> Actions returned by the main menu widget
+/// UpcomingMeeting -> The next meeting from the active saved schedule
+/// Helper functions:
+///      --- ---
+///      next_meeting -> Find the next meeting of a schedule after a moment
+///      --- ---
 /// --- ---
 ///
+use crate::data::sql::{Class, Meeting, TermCalendar};
 use crate::tui::state::{ErrorType, FocusMode};
 use crate::tui::themes::Theme;
 use crate::tui::widgets::traits::{KeyAction, Widget};
+use crate::utils::time::{format_12_hour, Date};
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::layout::Rect;
 use ratatui::style::{Modifier, Style};
@@ -23,6 +31,8 @@ use ratatui::Frame;
 /// Variants:
 /// --- ---
 /// Search -> Navigate to class search view
+/// Catalog -> Browse the course catalog by subject and course
+/// Professors -> Browse the professor directory
 /// ScheduleCreation -> Navigate to schedule creation (requires cart items)
 /// MySchedules -> View saved schedules
 /// Help -> View the query guide/help
@@ -33,6 +43,8 @@ use ratatui::Frame;
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum MenuOption {
     Search,
+    Catalog,
+    Professors,
     ScheduleCreation,
     MySchedules,
     Help,
@@ -51,6 +63,8 @@ impl MenuOption {
     pub fn as_str(&self) -> &'static str {
         match self {
             MenuOption::Search => "Search Classes",
+            MenuOption::Catalog => "Browse Catalog",
+            MenuOption::Professors => "Professors",
             MenuOption::ScheduleCreation => "Create Schedule",
             MenuOption::MySchedules => "My Schedules",
             MenuOption::Help => "Help",
@@ -69,6 +83,8 @@ impl MenuOption {
     pub fn all() -> Vec<MenuOption> {
         vec![
             MenuOption::Search,
+            MenuOption::Catalog,
+            MenuOption::Professors,
             MenuOption::ScheduleCreation,
             MenuOption::MySchedules,
             MenuOption::Help,
@@ -87,6 +103,8 @@ impl MenuOption {
     pub fn to_focus_mode(&self) -> Option<FocusMode> {
         match self {
             MenuOption::Search => Some(FocusMode::QueryInput),
+            MenuOption::Catalog => Some(FocusMode::CatalogBrowse),
+            MenuOption::Professors => Some(FocusMode::ProfessorDirectory),
             MenuOption::ScheduleCreation => Some(FocusMode::ScheduleCreation),
             MenuOption::MySchedules => Some(FocusMode::MySchedules),
             MenuOption::Help => Some(FocusMode::QueryGuide),
@@ -96,6 +114,134 @@ impl MenuOption {
     }
 }
 
+/// Maximum number of recently opened classes shown on the main menu
+const MAX_RECENT_CLASSES: usize = 5;
+
+/// Minutes in a day
+const MINUTES_PER_DAY: i64 = 24 * 60;
+
+/// The next meeting from the active saved schedule
+///
+/// Fields:
+/// --- ---
+/// class -> The class that meets
+/// meeting -> The meeting that comes next
+/// date -> Local date of the meeting
+/// minutes_until -> Minutes until the meeting starts (0 if it is in progress)
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for UpcomingMeeting
+/// --- ---
+///
+#[derive(Debug)]
+pub struct UpcomingMeeting<'a> {
+    pub class: &'a Class,
+    pub meeting: &'a Meeting,
+    pub date: Date,
+    pub minutes_until: i64,
+}
+
+/// Find the next meeting of a schedule after a moment
+///
+/// Looks at most a week ahead, starting from the first day of the term if it
+/// hasn't begun yet. A meeting already in progress counts as the next one.
+///
+/// Parameters:
+/// --- ---
+/// classes -> The classes in the schedule
+/// calendar -> The term calendar the schedule belongs to
+/// date -> Local date at the school
+/// minutes -> Local time at the school in minutes since midnight
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Option<UpcomingMeeting> -> The next meeting, or None if nothing meets within the window
+/// --- ---
+///
+pub fn next_meeting<'a>(
+    classes: &'a [Class],
+    calendar: &TermCalendar,
+    date: Date,
+    minutes: i32,
+) -> Option<UpcomingMeeting<'a>> {
+    let now = date.days_since_epoch() * MINUTES_PER_DAY + minutes as i64;
+    let first_day = calendar
+        .start_date
+        .filter(|start| *start > date)
+        .unwrap_or(date)
+        .days_since_epoch();
+
+    for day in first_day..first_day + 7 {
+        let day_date = Date::from_days_since_epoch(day);
+        if !calendar.is_in_session(day_date) {
+            continue;
+        }
+
+        // earliest meeting on this day that hasn't ended yet
+        let upcoming = classes
+            .iter()
+            .flat_map(|class| class.meeting_times.iter().map(move |m| (class, m)))
+            .filter(|(_, meeting)| meeting.meets_on_date(day_date))
+            .filter(|(_, meeting)| day * MINUTES_PER_DAY + meeting.end_minutes as i64 > now)
+            .min_by_key(|(_, meeting)| meeting.start_minutes);
+
+        if let Some((class, meeting)) = upcoming {
+            let start = day * MINUTES_PER_DAY + meeting.start_minutes as i64;
+            return Some(UpcomingMeeting {
+                class,
+                meeting,
+                date: day_date,
+                minutes_until: (start - now).max(0),
+            });
+        }
+    }
+
+    None
+}
+
+/// Get a short weekday name for a day code
+///
+/// Arguments:
+/// --- ---
+/// day_code -> Day code (M, T, W, TH, F, S, SU)
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// &'static str -> Short weekday name (e.g., "Tue")
+/// --- ---
+///
+fn short_day_name(day_code: &str) -> &'static str {
+    match day_code {
+        "M" => "Mon",
+        "T" => "Tue",
+        "W" => "Wed",
+        "TH" => "Thu",
+        "F" => "Fri",
+        "S" => "Sat",
+        _ => "Sun",
+    }
+}
+
+/// Action returned by main menu widget for app-level handling
+///
+/// Variants:
+/// --- ---
+/// None -> No action needed
+/// OpenRecentClass -> A recent class was picked, caller should open its detail view
+/// SwitchProfile -> Switch to the next workspace profile
+/// --- ---
+///
+#[derive(Debug, Clone)]
+pub enum MenuAction {
+    None,
+    OpenRecentClass(Box<Class>),
+    SwitchProfile,
+}
+
 /// Main menu widget with encapsulated state
 ///
 /// Handles navigation between the main application views and validates
@@ -105,11 +251,17 @@ impl MenuOption {
 /// --- ---
 /// selected_index -> Index of currently selected menu option
 /// cart_empty -> Whether the cart is empty (for schedule creation validation)
+/// recent_classes -> Classes most recently opened in the detail view (newest first)
+/// active_schedule -> Classes and term calendar of the active saved schedule
+/// profile_name -> Name of the workspace profile in use, shown in the menu
 /// --- ---
 ///
 pub struct MainMenuWidget {
     pub selected_index: usize,
     pub cart_empty: bool,
+    pub recent_classes: Vec<Class>,
+    pub active_schedule: Option<(Vec<Class>, TermCalendar)>,
+    pub profile_name: Option<String>,
 }
 
 impl MainMenuWidget {
@@ -124,9 +276,25 @@ impl MainMenuWidget {
         Self {
             selected_index: 0,
             cart_empty: true,
+            recent_classes: Vec::new(),
+            active_schedule: None,
+            profile_name: None,
         }
     }
 
+    /// Set the saved schedule the "What's Next" panel follows
+    ///
+    /// Arguments:
+    /// --- ---
+    /// schedule -> Classes and term calendar of the schedule, or None to hide the panel
+    /// --- ---
+    ///
+    /// Returns: None
+    ///
+    pub fn set_active_schedule(&mut self, schedule: Option<(Vec<Class>, TermCalendar)>) {
+        self.active_schedule = schedule;
+    }
+
     /// Update cart status
     ///
     /// Arguments:
@@ -140,6 +308,197 @@ impl MainMenuWidget {
         self.cart_empty = empty;
     }
 
+    /// Record a class as recently opened
+    ///
+    /// Arguments:
+    /// --- ---
+    /// class -> The class that was opened in the detail view
+    /// --- ---
+    ///
+    /// Returns: None
+    ///
+    pub fn push_recent_class(&mut self, class: &Class) {
+        let id = class.unique_id();
+        self.recent_classes.retain(|c| c.unique_id() != id);
+        self.recent_classes.insert(0, class.clone());
+        self.recent_classes.truncate(MAX_RECENT_CLASSES);
+    }
+
+    /// Handle key and return any action that needs to be taken
+    ///
+    /// Arguments:
+    /// --- ---
+    /// key -> the key event to handle
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// (KeyAction, MenuAction) -> tuple of key action and menu action
+    /// --- ---
+    ///
+    pub fn handle_key_with_action(&mut self, key: KeyEvent) -> (KeyAction, MenuAction) {
+        if let KeyCode::Char(c) = key.code {
+            // number keys jump straight back to a recent class
+            if let Some(class) = c
+                .to_digit(10)
+                .and_then(|d| (d as usize).checked_sub(1))
+                .and_then(|i| self.recent_classes.get(i))
+            {
+                return (
+                    KeyAction::Navigate(FocusMode::DetailView),
+                    MenuAction::OpenRecentClass(Box::new(class.clone())),
+                );
+            }
+            if c == 'p' || c == 'P' {
+                return (KeyAction::Continue, MenuAction::SwitchProfile);
+            }
+        }
+        (self.handle_key(key), MenuAction::None)
+    }
+
+    /// Render the next meeting of the active saved schedule beneath the menu
+    ///
+    /// Arguments:
+    /// --- ---
+    /// frame -> The frame to render to
+    /// theme -> The theme to use for styling
+    /// area_y -> The row to start rendering at
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// u16 -> The row below the panel (area_y if nothing was rendered)
+    /// --- ---
+    ///
+    fn render_next_up(&self, frame: &mut Frame, theme: &Theme, area_y: u16) -> u16 {
+        let Some((classes, calendar)) = &self.active_schedule else {
+            return area_y;
+        };
+
+        let (date, minutes) = calendar.local_now();
+        let line = match next_meeting(classes, calendar, date, minutes) {
+            Some(upcoming) => {
+                let when = if upcoming.minutes_until == 0 {
+                    format!("now until {}", format_12_hour(upcoming.meeting.end_minutes))
+                } else if upcoming.minutes_until < 60 {
+                    format!("in {} min", upcoming.minutes_until)
+                } else if upcoming.date == date {
+                    format!(
+                        "in {}h {}m",
+                        upcoming.minutes_until / 60,
+                        upcoming.minutes_until % 60
+                    )
+                } else {
+                    format!(
+                        "{} {}",
+                        short_day_name(upcoming.date.day_code()),
+                        format_12_hour(upcoming.meeting.start_minutes)
+                    )
+                };
+                let location = upcoming.class.campus.as_deref().unwrap_or("TBA");
+
+                Line::from(vec![
+                    Span::styled(
+                        format!(
+                            "{} {} ",
+                            upcoming.class.subject_code, upcoming.class.course_number
+                        ),
+                        Style::default()
+                            .fg(theme.title_color)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(when, Style::default().fg(theme.selected_color)),
+                    Span::styled(
+                        format!(", {}", location),
+                        Style::default().fg(theme.muted_color),
+                    ),
+                ])
+            }
+            None => Line::from(Span::styled(
+                "No classes in the next week",
+                Style::default().fg(theme.muted_color),
+            )),
+        };
+
+        let next_width = 40_u16;
+        let frame_width = frame.area().width;
+        let next_area = Rect {
+            x: (frame_width.saturating_sub(next_width.min(frame_width))) / 2,
+            y: area_y,
+            width: next_width.min(frame_width),
+            height: 3,
+        }
+        .intersection(frame.area());
+
+        let next_paragraph = Paragraph::new(line).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" What's Next ")
+                .title_style(Style::default().fg(theme.title_color))
+                .border_style(Style::default().fg(theme.muted_color)),
+        );
+
+        frame.render_widget(next_paragraph, next_area);
+        next_area.bottom()
+    }
+
+    /// Render the recently opened classes beneath the menu
+    ///
+    /// Arguments:
+    /// --- ---
+    /// frame -> The frame to render to
+    /// theme -> The theme to use for styling
+    /// area_y -> The row to start rendering at
+    /// --- ---
+    ///
+    /// Returns: None
+    ///
+    fn render_recent(&self, frame: &mut Frame, theme: &Theme, area_y: u16) {
+        if self.recent_classes.is_empty() {
+            return;
+        }
+
+        let recent_width = 40_u16;
+        let recent_height = self.recent_classes.len() as u16 + 2;
+        let frame_width = frame.area().width;
+
+        let recent_area = Rect {
+            x: (frame_width.saturating_sub(recent_width.min(frame_width))) / 2,
+            y: area_y,
+            width: recent_width.min(frame_width),
+            height: recent_height,
+        }
+        .intersection(frame.area());
+
+        let mut styled_lines = Vec::new();
+        for (i, class) in self.recent_classes.iter().enumerate() {
+            let code = format!(
+                "{} {}-{} ",
+                class.subject_code, class.course_number, class.section_sequence
+            );
+            styled_lines.push(Line::from(vec![
+                Span::styled(
+                    format!("{} ", i + 1),
+                    Style::default()
+                        .fg(theme.selected_color)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(code, Style::default().fg(theme.title_color)),
+                Span::styled(class.title.clone(), Style::default().fg(theme.muted_color)),
+            ]));
+        }
+
+        let recent_paragraph = Paragraph::new(styled_lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Recent ")
+                .title_style(Style::default().fg(theme.title_color))
+                .border_style(Style::default().fg(theme.muted_color)),
+        );
+
+        frame.render_widget(recent_paragraph, recent_area);
+    }
+
     /// Get the currently selected menu option
     ///
     /// Returns:
@@ -167,7 +526,7 @@ impl Widget for MainMenuWidget {
     fn render(&self, frame: &mut Frame, theme: &Theme) {
         let menu_options = MenuOption::all();
         let menu_width = 40_u16;
-        let menu_height = (menu_options.len() as u16 + 4).min(10);
+        let menu_height = menu_options.len() as u16 + 4;
 
         // position menu below the logo
         let logo_height = 7_u16;
@@ -203,6 +562,13 @@ impl Widget for MainMenuWidget {
                 Span::styled(option.as_str(), style),
             ]));
         }
+        if let Some(name) = &self.profile_name {
+            styled_lines.push(Line::from(""));
+            styled_lines.push(Line::from(vec![
+                Span::styled("  Profile: ", Style::default().fg(theme.muted_color)),
+                Span::styled(name.as_str(), Style::default().fg(theme.info_color)),
+            ]));
+        }
 
         let menu_paragraph = Paragraph::new(styled_lines).block(
             Block::default()
@@ -217,6 +583,9 @@ impl Widget for MainMenuWidget {
         );
 
         frame.render_widget(menu_paragraph, menu_area);
+
+        let recent_y = self.render_next_up(frame, theme, menu_area.bottom());
+        self.render_recent(frame, theme, recent_y);
     }
 
     /// Handle a key event and return an action
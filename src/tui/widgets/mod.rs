@@ -8,11 +8,18 @@
 pub mod traits;
 
 // widget structs with encapsulated state and interaction
+pub mod ast_inspector;
+pub mod catalog;
+pub mod compare;
+pub mod explain;
+pub mod free_time;
 pub mod guide;
 pub mod menu;
+pub mod professors;
 pub mod schedule;
 pub mod search;
 pub mod settings;
+pub mod stats;
 
 // render-only widget modules
 pub mod detail_view;
@@ -25,12 +32,19 @@ pub mod toast;
 pub use traits::{KeyAction, Widget};
 
 // re-export widget structs
+pub use ast_inspector::AstInspectorWidget;
+pub use catalog::{CatalogAction, CatalogWidget};
+pub use compare::CompareWidget;
 pub use detail_view::DetailViewWidget;
+pub use explain::{ExplainAction, ExplainWidget};
+pub use free_time::FreeTimeWidget;
 pub use guide::QueryGuideWidget;
 pub use help_bar::HelpBarWidget;
 pub use logo::LogoWidget;
-pub use menu::MainMenuWidget;
-pub use schedule::{ScheduleAction, ScheduleWidget};
+pub use menu::{MainMenuWidget, MenuAction};
+pub use professors::{ProfessorsAction, ProfessorsWidget};
+pub use schedule::{ScheduleAction, ScheduleOverlay, ScheduleWidget};
 pub use search::{CompletionState, SearchFocus, SearchWidget};
 pub use settings::{SettingsAction, SettingsWidget};
+pub use stats::StatsWidget;
 pub use toast::ToastWidget;
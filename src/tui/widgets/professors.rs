@@ -0,0 +1,626 @@
+/// src/tui/widgets/professors.rs
+///
+/// Professor directory widget
+///
+/// Renders the professors teaching in the active term with their department, email, and
+/// number of sections, filtered as the user types. Enter drills into a professor to list
+/// every section they teach, where a section's details can be opened or it can be added
+/// to the cart
+///
+/// Contains:
+/// --- ---
+/// ProfessorsAction -> Action returned by the professor directory for app-level handling
+/// ProfessorsWidget -> Widget for the professor directory
+/// Helper functions:
+///      --- ---
+///      section_count -> Format a number of sections, e.g., "1 section" or "3 sections"
+///      --- ---
+/// --- ---
+///
+use crate::data::catalog::CatalogScope;
+use crate::data::professors::{
+    fetch_professor_sections, fetch_professors, filter_professors, ProfessorEntry,
+};
+use crate::data::sql::Class;
+use crate::tui::state::{ErrorType, FocusMode};
+use crate::tui::themes::Theme;
+use crate::tui::widgets::helpers::truncate;
+use crate::tui::widgets::traits::{KeyAction, Widget};
+use crate::utils::i18n;
+use crate::utils::text::fold_text;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+use std::collections::HashSet;
+
+/// Width of the directory box
+const DIRECTORY_WIDTH: u16 = 110;
+
+/// Number of rows moved by Page Up/Down
+const PAGE_SIZE: usize = 10;
+
+/// Action returned by the professor directory for app-level handling
+///
+/// Variants:
+/// --- ---
+/// None -> No action needed
+/// OpenDetails -> A section was picked, caller should open its detail view
+/// AddToCart -> The user asked to add a section to the cart
+/// --- ---
+///
+#[derive(Debug, Clone)]
+pub enum ProfessorsAction {
+    None,
+    OpenDetails(Box<Class>),
+    AddToCart(Box<Class>),
+}
+
+/// Professor directory widget
+///
+/// Fields:
+/// --- ---
+/// scope -> The database, school, and term listed, None until opened
+/// professors -> The professors teaching in the term
+/// filter -> Text typed in the search box
+/// matches -> Indexes of the professors matching the filter
+/// selected -> Index of the selected professor in matches
+/// drill_down -> The professor being viewed and their sections, None on the list
+/// section_selected -> Index of the selected section in the drill-down
+/// in_cart -> Unique IDs of the classes in the cart, to mark their sections
+/// --- ---
+///
+pub struct ProfessorsWidget {
+    pub scope: Option<CatalogScope>,
+    professors: Vec<ProfessorEntry>,
+    pub filter: String,
+    matches: Vec<usize>,
+    pub selected: usize,
+    drill_down: Option<(usize, Vec<Class>)>,
+    pub section_selected: usize,
+    pub in_cart: HashSet<String>,
+}
+
+impl ProfessorsWidget {
+    /// Create a new ProfessorsWidget
+    ///
+    /// Returns:
+    /// --- ---
+    /// ProfessorsWidget -> The new ProfessorsWidget
+    /// --- ---
+    ///
+    pub fn new() -> Self {
+        Self {
+            scope: None,
+            professors: Vec::new(),
+            filter: String::new(),
+            matches: Vec::new(),
+            selected: 0,
+            drill_down: None,
+            section_selected: 0,
+            in_cart: HashSet::new(),
+        }
+    }
+
+    /// Open the directory of a term on its list of professors
+    ///
+    /// Reopening the same term keeps the search and selection as they were left
+    ///
+    /// Arguments:
+    /// --- ---
+    /// scope -> The database, school, and term to list
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Result<(), String> -> Ok once the professors are loaded, error message otherwise
+    /// --- ---
+    ///
+    pub fn open(&mut self, scope: CatalogScope) -> Result<(), String> {
+        self.drill_down = None;
+        if self.scope.as_ref() == Some(&scope) {
+            return Ok(());
+        }
+        self.professors = fetch_professors(&scope)?;
+        self.scope = Some(scope);
+        self.filter.clear();
+        self.refilter();
+        Ok(())
+    }
+
+    /// Open a class's professor, clearing the search so they're selected on the list
+    ///
+    /// Arguments:
+    /// --- ---
+    /// name -> The professor's name, as on the class
+    /// email -> The professor's email, as on the class
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Result<bool, String> -> Whether the professor teaches in the term, or error message
+    /// --- ---
+    ///
+    pub fn open_professor(&mut self, name: &str, email: Option<&str>) -> Result<bool, String> {
+        let folded = fold_text(name);
+        let found = self.professors.iter().position(|professor| {
+            fold_text(&professor.name) == folded
+                && (email.is_none() || professor.email.as_deref() == email)
+        });
+        let Some(index) = found else {
+            return Ok(false);
+        };
+        self.filter.clear();
+        self.refilter();
+        self.selected = index;
+        self.drill_into(index)?;
+        Ok(true)
+    }
+
+    /// Forget the loaded directory, so the next open reads the database again
+    ///
+    /// Returns: None
+    ///
+    pub fn reset(&mut self) {
+        self.scope = None;
+        self.professors.clear();
+        self.matches.clear();
+        self.drill_down = None;
+        self.selected = 0;
+    }
+
+    /// Check whether a professor's sections are being viewed
+    ///
+    /// Returns:
+    /// --- ---
+    /// bool -> true in the drill-down, false on the list
+    /// --- ---
+    ///
+    pub fn in_drill_down(&self) -> bool {
+        self.drill_down.is_some()
+    }
+
+    /// Match the professors against the filter again, selecting the first match
+    ///
+    /// Returns: None
+    ///
+    fn refilter(&mut self) {
+        self.matches = filter_professors(&self.professors, &self.filter);
+        self.selected = 0;
+    }
+
+    /// Load a professor's sections and show them
+    ///
+    /// Arguments:
+    /// --- ---
+    /// index -> Index of the professor in the directory
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Result<(), String> -> Ok on success, error message if the sections couldn't be loaded
+    /// --- ---
+    ///
+    fn drill_into(&mut self, index: usize) -> Result<(), String> {
+        let Some(scope) = self.scope.as_ref() else {
+            return Ok(());
+        };
+        let sections = fetch_professor_sections(scope, &self.professors[index])?;
+        self.drill_down = Some((index, sections));
+        self.section_selected = 0;
+        Ok(())
+    }
+
+    /// Move a selection by a key, within a number of rows
+    ///
+    /// Arguments:
+    /// --- ---
+    /// selected -> The selection to move
+    /// code -> The key pressed
+    /// count -> Number of rows
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// bool -> true if the key moves the selection
+    /// --- ---
+    ///
+    fn move_selection(selected: &mut usize, code: KeyCode, count: usize) -> bool {
+        let last = count.saturating_sub(1);
+        *selected = match code {
+            KeyCode::Up => selected.saturating_sub(1),
+            KeyCode::Down => (*selected + 1).min(last),
+            KeyCode::PageUp => selected.saturating_sub(PAGE_SIZE),
+            KeyCode::PageDown => (*selected + PAGE_SIZE).min(last),
+            KeyCode::Home => 0,
+            KeyCode::End => last,
+            _ => return false,
+        };
+        true
+    }
+
+    /// Handle a key event and return both KeyAction and ProfessorsAction
+    ///
+    /// Arguments:
+    /// --- ---
+    /// key -> The key event to handle
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// (KeyAction, ProfessorsAction) -> The key action and the directory-specific action
+    /// --- ---
+    ///
+    pub fn handle_key_with_action(&mut self, key: KeyEvent) -> (KeyAction, ProfessorsAction) {
+        if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            return (KeyAction::Exit, ProfessorsAction::None);
+        }
+
+        // a professor's sections
+        if let Some((_, sections)) = &self.drill_down {
+            let selected = sections.get(self.section_selected).cloned();
+            let count = sections.len();
+            if Self::move_selection(&mut self.section_selected, key.code, count) {
+                return (KeyAction::Continue, ProfessorsAction::None);
+            }
+            let action = match key.code {
+                KeyCode::Esc | KeyCode::Backspace | KeyCode::Left => {
+                    self.drill_down = None;
+                    return (KeyAction::Continue, ProfessorsAction::None);
+                }
+                KeyCode::Enter => match selected {
+                    Some(class) => {
+                        return (
+                            KeyAction::Navigate(FocusMode::DetailView),
+                            ProfessorsAction::OpenDetails(Box::new(class)),
+                        )
+                    }
+                    None => KeyAction::Continue,
+                },
+                KeyCode::Char('a') | KeyCode::Char('A') => match selected {
+                    Some(class) => {
+                        return (
+                            KeyAction::Continue,
+                            ProfessorsAction::AddToCart(Box::new(class)),
+                        )
+                    }
+                    None => KeyAction::Continue,
+                },
+                _ => KeyAction::Continue,
+            };
+            return (action, ProfessorsAction::None);
+        }
+
+        // the list, where typing searches it
+        if Self::move_selection(&mut self.selected, key.code, self.matches.len()) {
+            return (KeyAction::Continue, ProfessorsAction::None);
+        }
+        let action = match key.code {
+            KeyCode::Esc => KeyAction::Back,
+            KeyCode::Backspace => {
+                if self.filter.pop().is_some() {
+                    self.refilter();
+                    KeyAction::Continue
+                } else {
+                    KeyAction::Back
+                }
+            }
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.filter.clear();
+                self.refilter();
+                KeyAction::Continue
+            }
+            KeyCode::Char(c) => {
+                self.filter.push(c);
+                self.refilter();
+                KeyAction::Continue
+            }
+            KeyCode::Enter | KeyCode::Right => match self.matches.get(self.selected) {
+                Some(&index) => match self.drill_into(index) {
+                    Ok(()) => KeyAction::Continue,
+                    Err(e) => KeyAction::ShowToast {
+                        message: format!("Failed to load sections: {}", e),
+                        error_type: ErrorType::Warning,
+                    },
+                },
+                None => KeyAction::Continue,
+            },
+            _ => KeyAction::Continue,
+        };
+        (action, ProfessorsAction::None)
+    }
+
+    /// Build the lines of the list of professors
+    ///
+    /// Arguments:
+    /// --- ---
+    /// visible -> Number of rows that fit
+    /// theme -> The theme to use for styling
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Vec<Line> -> The search box, the match count, and the visible professors
+    /// --- ---
+    ///
+    fn list_lines(&self, visible: usize, theme: &Theme) -> Vec<Line<'static>> {
+        let muted = Style::default().fg(theme.muted_color);
+        let mut lines = vec![
+            Line::from(vec![
+                Span::styled(
+                    format!("{} ", i18n::tr("professors-search", "Search:", &[])),
+                    muted,
+                ),
+                Span::styled(
+                    format!("{}▏", self.filter),
+                    Style::default().fg(theme.text_color),
+                ),
+            ]),
+            Line::from(Span::styled(
+                i18n::tr(
+                    "professors-count",
+                    "{shown} of {total} professors",
+                    &[
+                        ("shown", &self.matches.len().to_string()),
+                        ("total", &self.professors.len().to_string()),
+                    ],
+                ),
+                muted,
+            )),
+            Line::from(""),
+        ];
+        if self.matches.is_empty() {
+            let message = if self.professors.is_empty() {
+                i18n::tr(
+                    "professors-empty",
+                    "No professors in this term. Sync class data from Settings.",
+                    &[],
+                )
+            } else {
+                i18n::tr(
+                    "professors-no-match",
+                    "No professors match your search.",
+                    &[],
+                )
+            };
+            lines.push(Line::from(Span::styled(message, muted)));
+            return lines;
+        }
+
+        // keep the selected row in view by scrolling just far enough
+        let visible = visible.saturating_sub(lines.len()).max(1);
+        let selected = self.selected.min(self.matches.len() - 1);
+        let first = (selected + 1).saturating_sub(visible);
+        for (i, &index) in self.matches.iter().enumerate().skip(first).take(visible) {
+            let professor = &self.professors[index];
+            let is_selected = i == selected;
+            let style = if is_selected {
+                Style::default()
+                    .fg(theme.selected_color)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.text_color)
+            };
+            let prefix = if is_selected { "> " } else { "  " };
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!(
+                        "{}{:<28} {:<28} {:<32}",
+                        prefix,
+                        truncate(&professor.name, 28),
+                        truncate(professor.department.as_deref().unwrap_or("-"), 28),
+                        truncate(professor.email.as_deref().unwrap_or("-"), 32)
+                    ),
+                    style,
+                ),
+                Span::styled(format!(" {}", section_count(professor.sections)), muted),
+            ]));
+        }
+        lines
+    }
+
+    /// Build the lines of a professor's sections
+    ///
+    /// Arguments:
+    /// --- ---
+    /// professor -> The professor
+    /// sections -> Their sections
+    /// visible -> Number of rows that fit
+    /// theme -> The theme to use for styling
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Vec<Line> -> The professor's department and email, then the visible sections
+    /// --- ---
+    ///
+    fn section_lines(
+        &self,
+        professor: &ProfessorEntry,
+        sections: &[Class],
+        visible: usize,
+        theme: &Theme,
+    ) -> Vec<Line<'static>> {
+        let muted = Style::default().fg(theme.muted_color);
+        let no_email = i18n::tr("professors-no-email", "no email", &[]);
+        let mut lines = vec![
+            Line::from(Span::styled(
+                i18n::tr(
+                    "professors-summary",
+                    "{department} · {email} · {sections} this term",
+                    &[
+                        ("department", professor.department.as_deref().unwrap_or("-")),
+                        ("email", professor.email.as_deref().unwrap_or(&no_email)),
+                        ("sections", &section_count(sections.len())),
+                    ],
+                ),
+                muted,
+            )),
+            Line::from(""),
+        ];
+
+        let visible = visible.saturating_sub(lines.len()).max(1);
+        let selected = self.section_selected.min(sections.len().saturating_sub(1));
+        let first = (selected + 1).saturating_sub(visible);
+        for (i, class) in sections.iter().enumerate().skip(first).take(visible) {
+            let is_selected = i == selected;
+            let style = if is_selected {
+                Style::default()
+                    .fg(theme.selected_color)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.text_color)
+            };
+            let prefix = if is_selected { "> " } else { "  " };
+            let times = class
+                .format_for_display()
+                .get(3)
+                .cloned()
+                .unwrap_or_else(|| "TBA".to_string());
+            let seats = match (class.enrollment, class.max_enrollment) {
+                (Some(e), Some(m)) => format!("{}/{}", e, m),
+                _ => "-".to_string(),
+            };
+            let mut spans = vec![Span::styled(
+                format!(
+                    "{}{:<16} {:<32} {:<30} {}",
+                    prefix,
+                    format!(
+                        "{} {}-{}",
+                        class.subject_code, class.course_number, class.section_sequence
+                    ),
+                    truncate(&class.title, 32),
+                    truncate(&times, 30),
+                    seats
+                ),
+                style,
+            )];
+            if self.in_cart.contains(&class.unique_id()) {
+                spans.push(Span::styled(
+                    format!("  {}", i18n::tr("in-cart", "✓ in cart", &[])),
+                    Style::default().fg(theme.success_color),
+                ));
+            }
+            lines.push(Line::from(spans));
+        }
+        lines
+    }
+}
+
+impl Default for ProfessorsWidget {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Format a number of sections
+///
+/// Arguments:
+/// --- ---
+/// count -> The number of sections
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> e.g., "1 section" or "3 sections"
+/// --- ---
+///
+fn section_count(count: usize) -> String {
+    let count_text = count.to_string();
+    if count == 1 {
+        i18n::tr(
+            "count-section",
+            "{count} section",
+            &[("count", &count_text)],
+        )
+    } else {
+        i18n::tr(
+            "count-sections",
+            "{count} sections",
+            &[("count", &count_text)],
+        )
+    }
+}
+
+impl Widget for ProfessorsWidget {
+    /// Render the directory, or the sections of the professor being viewed
+    ///
+    /// Arguments:
+    /// --- ---
+    /// frame -> The frame to render to
+    /// theme -> The theme to use for styling
+    /// --- ---
+    ///
+    /// Returns: None
+    ///
+    fn render(&self, frame: &mut Frame, theme: &Theme) {
+        // position below the logo like the catalog, leaving room for the help bar
+        let directory_y = 13_u16;
+        let directory_area = Rect {
+            x: frame.area().width.saturating_sub(DIRECTORY_WIDTH) / 2,
+            y: directory_y,
+            width: DIRECTORY_WIDTH,
+            height: frame.area().height.saturating_sub(directory_y + 3),
+        }
+        .intersection(frame.area());
+        let visible = directory_area.height.saturating_sub(2).max(1) as usize;
+
+        let (title, lines) = match &self.drill_down {
+            Some((index, sections)) => {
+                let professor = &self.professors[*index];
+                (
+                    format!(" {} ", professor.name),
+                    self.section_lines(professor, sections, visible, theme),
+                )
+            }
+            None => (
+                format!(" {} ", i18n::tr("professors-title", "Professors", &[])),
+                self.list_lines(visible, theme),
+            ),
+        };
+
+        frame.render_widget(Clear, directory_area);
+
+        let directory_paragraph = Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title)
+                .title_style(
+                    Style::default()
+                        .fg(theme.title_color)
+                        .add_modifier(Modifier::BOLD),
+                )
+                .border_style(Style::default().fg(theme.border_color))
+                .style(Style::default().bg(theme.background_color)),
+        );
+
+        frame.render_widget(directory_paragraph, directory_area);
+    }
+
+    /// Handle a key event and return an action
+    ///
+    /// Arguments:
+    /// --- ---
+    /// key -> The key event to handle
+    /// --- ---
+    ///
+    /// Returns: KeyAction -> The action to take in response to the key
+    ///
+    fn handle_key(&mut self, key: KeyEvent) -> KeyAction {
+        let (action, _professors_action) = self.handle_key_with_action(key);
+        action
+    }
+
+    /// Return the focus mode(s) this widget handles
+    ///
+    /// Returns:
+    /// --- ---
+    /// Vec<FocusMode> -> The focus modes this widget handles
+    /// --- ---
+    ///
+    fn focus_modes(&self) -> Vec<FocusMode> {
+        vec![FocusMode::ProfessorDirectory]
+    }
+}
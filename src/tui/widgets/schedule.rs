@@ -8,18 +8,58 @@
 /// --- ---
 /// ScheduleWidget -> Widget for schedule functionality
 /// ScheduleAction -> Actions returned by schedule widget
+/// ScheduleOverlay -> Another schedule drawn over the current one
+/// ConflictGraph -> Pairwise meeting conflicts between the cart's classes
+/// GenerationSettings -> What schedules were generated from, kept with saved schedules
 /// --- ---
-use crate::data::sql::Class;
+use crate::data::sql::{self, Class, FinalExam};
+use crate::data::warm_cache::WarmCache;
 use crate::tui::state::{ErrorType, FocusMode};
 use crate::tui::themes::Theme;
 use crate::tui::widgets::traits::{KeyAction, Widget};
+use crate::utils::time::{format_12_hour_padded, Date, DAY_CODES};
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
-use ratatui::style::{Modifier, Style};
+use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph};
 use ratatui::Frame;
+use serde::{Deserialize, Serialize};
+use std::borrow::Borrow;
 use std::collections::{HashMap, HashSet};
+use std::iter::Peekable;
+use std::sync::Arc;
+use std::thread::JoinHandle;
+
+/// First hour shown in the cart's mini week-grid (8am)
+const CART_PREVIEW_FIRST_HOUR: usize = 8;
+
+/// Number of hourly rows in the cart's mini week-grid (8am - 10pm)
+const CART_PREVIEW_HOURS: usize = 15;
+
+/// Width of the cart's mini week-grid (time label, 7 day columns, borders)
+const CART_PREVIEW_WIDTH: u16 = 4 + 7 * 3 + 2;
+
+/// Height of the cart's mini week-grid (header, hourly rows, borders)
+const CART_PREVIEW_HEIGHT: u16 = 1 + CART_PREVIEW_HOURS as u16 + 2;
+
+/// Default first hour of the schedule grid (8am)
+pub const DEFAULT_GRID_START_HOUR: usize = 8;
+
+/// Default hour the schedule grid ends at (11pm, so the last slot starts at 10:30pm)
+pub const DEFAULT_GRID_END_HOUR: usize = 23;
+
+/// Default length of a schedule grid slot in minutes
+pub const DEFAULT_SLOT_MINUTES: usize = 30;
+
+/// Number of schedules generated at a time when paging through results
+const SCHEDULE_BATCH_SIZE: usize = 50;
+
+/// Shortest gap between classes that counts as shared free time in an overlay
+pub const MIN_SHARED_FREE_MINUTES: i32 = 30;
+
+/// Length of a finals-week calendar slot in minutes
+const FINALS_SLOT_MINUTES: i32 = 30;
 
 /// Schedule widget with encapsulated state
 ///
@@ -28,7 +68,8 @@ use std::collections::{HashMap, HashSet};
 ///
 /// Fields:
 /// --- ---
-/// cart_classes -> Map of all classes in the cart (ID -> Class)
+/// cart_classes -> Map of all classes in the cart (ID -> Class), shared with the schedules
+///                 generated from them rather than copied into each
 /// selected_for_schedule -> Set of class IDs selected for schedule generation
 /// generated_schedules -> All generated non-conflicting schedules
 /// current_schedule_index -> Index of currently displayed schedule
@@ -37,26 +78,99 @@ use std::collections::{HashMap, HashSet};
 /// schedule_selection_mode -> Whether in class selection mode (true) or viewing mode (false)
 /// selected_time_block_day -> Index of currently selected day in schedule viewing mode
 /// selected_time_block_slot -> Index of currently selected time slot
+/// day_view -> Whether viewing mode shows a single-day agenda instead of the week grid
+/// finals_view -> Whether viewing mode shows the finals-week calendar instead of the week grid
+/// selected_agenda_index -> Index of currently selected entry in the day agenda
+/// grid_start_hour -> First hour shown in the schedule grid
+/// grid_end_hour -> Hour the schedule grid ends at
+/// slot_minutes -> Length of each schedule grid slot in minutes
+/// schedule_generator -> Lazy generator for further schedules (None once all are generated)
 /// current_saved_schedule_name -> Name of currently viewed saved schedule (if any)
 /// saved_schedule_names -> All saved schedule names (for viewing saved schedules)
 /// viewing_saved_schedules -> Whether viewing saved schedules (vs generated schedules)
 /// detail_return_focus -> Focus mode to return to after detail view
+/// overlay -> Friend's schedule drawn over the one being viewed (if any)
+/// conflict_graph -> Pairwise conflicts between the cart's classes, rebuilt in the background
+///                   whenever the cart changes
+/// one_per_course -> Whether generation picks exactly one section of each checked course
+///                   instead of any combination of the checked sections
+/// generated_with -> Settings the schedules being viewed were generated with (None for
+///                   saved schedules)
 /// --- ---
 ///
 pub struct ScheduleWidget {
-    pub cart_classes: HashMap<String, Class>,
+    pub cart_classes: HashMap<String, Arc<Class>>,
     pub selected_for_schedule: HashSet<String>,
-    pub generated_schedules: Vec<Vec<Class>>,
+    pub generated_schedules: Vec<Vec<Arc<Class>>>,
     pub current_schedule_index: usize,
     pub schedule_cart_focus: bool,
     pub selected_cart_index: usize,
     pub schedule_selection_mode: bool,
     pub selected_time_block_day: usize,
     pub selected_time_block_slot: usize,
+    pub day_view: bool,
+    pub finals_view: bool,
+    pub selected_agenda_index: usize,
+    pub grid_start_hour: usize,
+    pub grid_end_hour: usize,
+    pub slot_minutes: usize,
+    pub schedule_generator: Option<Peekable<ScheduleGenerator>>,
     pub current_saved_schedule_name: Option<String>,
     pub saved_schedule_names: Vec<String>,
     pub viewing_saved_schedules: bool,
     pub detail_return_focus: FocusMode,
+    pub overlay: Option<ScheduleOverlay>,
+    pub conflict_graph: WarmCache<ConflictGraph>,
+    pub one_per_course: bool,
+    pub generated_with: Option<GenerationSettings>,
+}
+
+/// Another schedule (e.g., a friend's) drawn over the one being viewed
+///
+/// Fields:
+/// --- ---
+/// name -> Name shown in the legend
+/// classes -> The schedule's classes
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for ScheduleOverlay
+/// Clone -> Clone trait for ScheduleOverlay
+/// --- ---
+///
+#[derive(Debug, Clone)]
+pub struct ScheduleOverlay {
+    pub name: String,
+    pub classes: Vec<Class>,
+}
+
+/// What a set of schedules was generated from
+///
+/// Kept with a saved schedule, so the same classes can be generated again once a sync
+/// has changed their times or seats
+///
+/// Fields:
+/// --- ---
+/// classes -> Unique IDs of the classes checked for generation, sorted
+/// one_per_course -> Whether generation took one section of each checked course
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for GenerationSettings
+/// Clone -> Clone trait for GenerationSettings
+/// PartialEq -> PartialEq trait for GenerationSettings
+/// Default -> No classes, any combination of sections
+/// Serialize -> Serialize trait for GenerationSettings
+/// Deserialize -> Deserialize trait for GenerationSettings (missing fields are empty)
+/// --- ---
+///
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GenerationSettings {
+    pub classes: Vec<String>,
+    pub one_per_course: bool,
 }
 
 /// Action returned by schedule widget for app-level handling
@@ -67,6 +181,10 @@ pub struct ScheduleWidget {
 /// OpenDetailView -> Open detail view for a class
 /// SaveSchedule -> Request to save current schedule
 /// RefreshSavedSchedules -> Need to refresh saved schedules from MySchedules navigation
+/// MarkForCompare -> Mark a cart class for side-by-side comparison
+/// OverlaySchedule -> Request to pick a schedule to overlay
+/// FindFreeTime -> Open the free time finder for the schedule being viewed
+/// CopyCrns -> Copy the CRNs of the schedule being viewed
 /// --- ---
 ///
 #[derive(Debug, Clone)]
@@ -75,6 +193,10 @@ pub enum ScheduleAction {
     OpenDetailView(Class),
     SaveSchedule,
     RefreshSavedSchedules,
+    MarkForCompare(Class),
+    OverlaySchedule,
+    FindFreeTime(Vec<Class>),
+    CopyCrns(Vec<Class>),
 }
 
 impl ScheduleWidget {
@@ -98,10 +220,21 @@ impl ScheduleWidget {
             schedule_selection_mode: true,
             selected_time_block_day: 0,
             selected_time_block_slot: 0,
+            day_view: false,
+            finals_view: false,
+            selected_agenda_index: 0,
+            grid_start_hour: DEFAULT_GRID_START_HOUR,
+            grid_end_hour: DEFAULT_GRID_END_HOUR,
+            slot_minutes: DEFAULT_SLOT_MINUTES,
+            schedule_generator: None,
             current_saved_schedule_name: None,
             saved_schedule_names: Vec::new(),
             viewing_saved_schedules: false,
             detail_return_focus: FocusMode::ScheduleCreation,
+            overlay: None,
+            conflict_graph: WarmCache::new(),
+            one_per_course: false,
+            generated_with: None,
         }
     }
 
@@ -129,7 +262,79 @@ impl ScheduleWidget {
     ///
     pub fn add_to_cart(&mut self, class: Class) {
         let id = class.unique_id();
-        self.cart_classes.insert(id, class);
+        self.cart_classes.insert(id, Arc::new(class));
+        self.refresh_conflict_graph();
+    }
+
+    /// Rebuild the cart's conflict graph in the background
+    ///
+    /// Called whenever the cart changes, so entering schedule creation and marking
+    /// conflicts in the cart don't wait on checking every pair of classes
+    ///
+    /// Arguments: None
+    ///
+    /// Returns:
+    /// --- ---
+    /// JoinHandle<()> -> the building thread, for callers that need to wait for it
+    /// --- ---
+    ///
+    pub fn refresh_conflict_graph(&self) -> JoinHandle<()> {
+        let classes: Vec<Arc<Class>> = self.cart_classes.values().cloned().collect();
+        self.conflict_graph
+            .warm_up(move || Ok(ConflictGraph::new(classes)))
+    }
+
+    /// Get the conflict matrix for some cart classes
+    ///
+    /// Arguments:
+    /// --- ---
+    /// classes -> cart classes to check
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Vec<Vec<bool>> -> matrix where [i][j] is true if classes i and j overlap, taken from
+    ///                   the conflict graph if it is built, otherwise checked now
+    /// --- ---
+    ///
+    fn cart_conflict_matrix(&self, classes: &[Arc<Class>]) -> Vec<Vec<bool>> {
+        self.conflict_graph
+            .get()
+            .and_then(|graph| graph.matrix_for(classes))
+            .unwrap_or_else(|| conflict_matrix(classes))
+    }
+
+    /// Find the cart classes whose final exam conflicts with a class's
+    ///
+    /// Arguments:
+    /// --- ---
+    /// class -> the class to check
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Vec<String> -> display IDs (e.g., "CMPT 220L-111") of the other cart classes with an
+    ///                overlapping final, sorted
+    /// --- ---
+    ///
+    pub fn final_conflicts_with(&self, class: &Class) -> Vec<String> {
+        let Some(exam) = class.final_exam else {
+            return Vec::new();
+        };
+        let id = class.unique_id();
+        let mut conflicts: Vec<String> = self
+            .cart_classes
+            .iter()
+            .filter(|(other_id, other)| {
+                **other_id != id
+                    && other
+                        .final_exam
+                        .is_some_and(|other_exam| other_exam.overlaps(&exam))
+            })
+            .map(|(_, other)| display_id(other))
+            .collect();
+        conflicts.sort();
+        conflicts
     }
 
     /// Remove a class from the cart
@@ -144,6 +349,7 @@ impl ScheduleWidget {
     pub fn remove_from_cart(&mut self, class_id: &str) {
         self.cart_classes.remove(class_id);
         self.selected_for_schedule.remove(class_id);
+        self.refresh_conflict_graph();
     }
 
     /// Toggle cart status for a class
@@ -161,8 +367,9 @@ impl ScheduleWidget {
             self.cart_classes.remove(&id);
             self.selected_for_schedule.remove(&id);
         } else {
-            self.cart_classes.insert(id, class.clone());
+            self.cart_classes.insert(id, Arc::new(class.clone()));
         }
+        self.refresh_conflict_graph();
     }
 
     /// Clear cart and related data (when switching schools/terms)
@@ -175,8 +382,10 @@ impl ScheduleWidget {
         self.cart_classes.clear();
         self.selected_for_schedule.clear();
         self.generated_schedules.clear();
+        self.schedule_generator = None;
         self.current_schedule_index = 0;
         self.selected_cart_index = 0;
+        self.refresh_conflict_graph();
     }
 
     /// Enter schedule creation mode from main menu
@@ -218,17 +427,88 @@ impl ScheduleWidget {
         all_names: Vec<String>,
         selected_index: usize,
     ) {
-        self.generated_schedules = all_schedules;
+        self.generated_schedules = all_schedules
+            .into_iter()
+            .map(|schedule| schedule.into_iter().map(Arc::new).collect())
+            .collect();
+        self.schedule_generator = None;
         self.saved_schedule_names = all_names;
         self.current_schedule_index = selected_index;
         self.schedule_selection_mode = false;
         self.viewing_saved_schedules = true;
+        self.generated_with = None;
         self.selected_time_block_day = 0;
         self.selected_time_block_slot = 0;
+        self.selected_agenda_index = 0;
         self.current_saved_schedule_name = self.saved_schedule_names.get(selected_index).cloned();
         self.detail_return_focus = FocusMode::MySchedules;
     }
 
+    /// Generate schedules again from the settings a saved schedule was generated with
+    ///
+    /// The classes are put in the cart (replacing older copies of them), checked instead of
+    /// whatever was checked before, and generated from as if Enter was pressed
+    ///
+    /// Arguments:
+    /// --- ---
+    /// classes -> the settings' classes as they are in the current catalog
+    /// settings -> the settings to generate with
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// (KeyAction, ScheduleAction) -> toast naming the conflicting classes if no schedule
+    ///                                fits, otherwise continue action
+    /// --- ---
+    ///
+    pub fn regenerate(
+        &mut self,
+        classes: Vec<Class>,
+        settings: &GenerationSettings,
+    ) -> (KeyAction, ScheduleAction) {
+        self.selected_for_schedule.clear();
+        for class in classes {
+            let id = class.unique_id();
+            self.selected_for_schedule.insert(id.clone());
+            self.cart_classes.insert(id, Arc::new(class));
+        }
+        self.refresh_conflict_graph();
+        self.one_per_course = settings.one_per_course;
+        self.enter_creation_mode();
+        self.handle_enter()
+    }
+
+    /// Add classes imported from a pasted schedule to the cart and enter creation mode
+    ///
+    /// The imported classes are checked for generation along with whatever was checked
+    /// before (when nothing was, entering creation mode checks the whole cart)
+    ///
+    /// Arguments:
+    /// --- ---
+    /// classes -> the matched sections
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// usize -> number of classes that weren't already in the cart
+    /// --- ---
+    ///
+    pub fn import_to_cart(&mut self, classes: Vec<Class>) -> usize {
+        let mut added = 0;
+        for class in classes {
+            let id = class.unique_id();
+            if !self.selected_for_schedule.is_empty() {
+                self.selected_for_schedule.insert(id.clone());
+            }
+            if self.cart_classes.insert(id, Arc::new(class)).is_none() {
+                added += 1;
+            }
+        }
+        self.refresh_conflict_graph();
+        self.enter_creation_mode();
+        added
+    }
+
     /// Get sorted cart class IDs (for consistent ordering)
     ///
     /// Arguments: None
@@ -239,7 +519,8 @@ impl ScheduleWidget {
     /// --- ---
     ///
     fn sorted_cart_ids(&self) -> Vec<String> {
-        let mut cart_classes_vec: Vec<&Class> = self.cart_classes.values().collect();
+        let mut cart_classes_vec: Vec<&Class> =
+            self.cart_classes.values().map(Arc::as_ref).collect();
         cart_classes_vec.sort_by_key(|class| class.unique_id());
         cart_classes_vec
             .iter()
@@ -274,14 +555,29 @@ impl ScheduleWidget {
             KeyCode::Enter => self.handle_enter(),
             KeyCode::Char('s') | KeyCode::Char('S') => self.handle_save(),
             KeyCode::Char(' ') => self.handle_space(),
+            KeyCode::Char('v') | KeyCode::Char('V') => self.handle_toggle_day_view(),
+            KeyCode::Char('x') | KeyCode::Char('X') => self.handle_toggle_finals_view(),
+            KeyCode::Char('o') | KeyCode::Char('O') => self.handle_overlay(),
+            KeyCode::Char('f') | KeyCode::Char('F') => self.handle_free_time(),
+            KeyCode::Char('y') | KeyCode::Char('Y') => self.handle_copy_crns(),
+            KeyCode::Char('g') | KeyCode::Char('G')
+                if !key.modifiers.contains(KeyModifiers::ALT) =>
+            {
+                self.handle_toggle_one_per_course()
+            }
             KeyCode::Char('d') | KeyCode::Char('D') | KeyCode::Char('c') | KeyCode::Char('C') => {
                 if key.modifiers.contains(KeyModifiers::CONTROL) {
                     (KeyAction::Exit, ScheduleAction::None)
+                } else if key.modifiers.contains(KeyModifiers::ALT)
+                    && matches!(key.code, KeyCode::Char('c') | KeyCode::Char('C'))
+                {
+                    self.handle_compare()
                 } else {
                     self.handle_delete()
                 }
             }
             KeyCode::Tab => self.handle_tab(),
+            KeyCode::BackTab => self.handle_back_tab(),
             _ => (KeyAction::Continue, ScheduleAction::None),
         }
     }
@@ -297,25 +593,20 @@ impl ScheduleWidget {
     ///
     fn handle_esc(&mut self) -> (KeyAction, ScheduleAction) {
         if self.schedule_selection_mode {
-            // exit schedule creation, go back to main menu
-            (
-                KeyAction::Navigate(FocusMode::MainMenu),
-                ScheduleAction::None,
-            )
+            // exit schedule creation, go back to the previous view
+            (KeyAction::Back, ScheduleAction::None)
         } else {
             // check if we came from MySchedules
             if self.detail_return_focus == FocusMode::MySchedules {
                 // go back to MySchedules view
                 self.current_saved_schedule_name = None;
-                (
-                    KeyAction::Navigate(FocusMode::MySchedules),
-                    ScheduleAction::None,
-                )
+                (KeyAction::Back, ScheduleAction::None)
             } else {
                 // go back to class selection mode
                 self.schedule_selection_mode = true;
                 self.schedule_cart_focus = true;
                 self.generated_schedules.clear();
+                self.schedule_generator = None;
                 (KeyAction::Continue, ScheduleAction::None)
             }
         }
@@ -336,13 +627,16 @@ impl ScheduleWidget {
             if !self.cart_classes.is_empty() && self.selected_cart_index > 0 {
                 self.selected_cart_index -= 1;
             }
+        } else if self.day_view {
+            // navigate agenda entries up
+            self.selected_agenda_index = self.selected_agenda_index.saturating_sub(1);
         } else {
             // navigate time blocks: up = previous time slot
             if self.selected_time_block_slot > 0 {
                 self.selected_time_block_slot -= 1;
             } else {
                 // wrap to last time slot
-                self.selected_time_block_slot = 28; // 29 time slots (0-28) for 8am-10:30pm
+                self.selected_time_block_slot = self.slot_count() - 1;
             }
         }
         (KeyAction::Continue, ScheduleAction::None)
@@ -364,9 +658,18 @@ impl ScheduleWidget {
             if !cart_ids.is_empty() && self.selected_cart_index < cart_ids.len() - 1 {
                 self.selected_cart_index += 1;
             }
+        } else if self.day_view {
+            // navigate agenda entries down
+            let agenda_len = self
+                .current_schedule()
+                .map(|schedule| day_agenda(schedule, self.selected_time_block_day).len())
+                .unwrap_or(0);
+            if self.selected_agenda_index + 1 < agenda_len {
+                self.selected_agenda_index += 1;
+            }
         } else {
             // navigate time blocks: down = next time slot
-            if self.selected_time_block_slot < 28 {
+            if self.selected_time_block_slot + 1 < self.slot_count() {
                 self.selected_time_block_slot += 1;
             } else {
                 // wrap to first time slot
@@ -394,6 +697,7 @@ impl ScheduleWidget {
                 // wrap to Sunday
                 self.selected_time_block_day = 6;
             }
+            self.selected_agenda_index = 0;
         }
         (KeyAction::Continue, ScheduleAction::None)
     }
@@ -416,6 +720,7 @@ impl ScheduleWidget {
                 // wrap to Monday
                 self.selected_time_block_day = 0;
             }
+            self.selected_agenda_index = 0;
         }
         (KeyAction::Continue, ScheduleAction::None)
     }
@@ -436,6 +741,9 @@ impl ScheduleWidget {
             } else {
                 self.current_schedule_index = self.generated_schedules.len() - 1;
             }
+            self.selected_agenda_index = 0;
+            self.selected_time_block_slot =
+                self.selected_time_block_slot.min(self.slot_count() - 1);
             // update current saved schedule name when viewing saved schedules
             if self.viewing_saved_schedules {
                 self.current_saved_schedule_name = self
@@ -460,9 +768,15 @@ impl ScheduleWidget {
         if !self.schedule_selection_mode && !self.generated_schedules.is_empty() {
             if self.current_schedule_index < self.generated_schedules.len() - 1 {
                 self.current_schedule_index += 1;
+            } else if self.load_more_schedules() {
+                // generate the next batch on demand when paging past the last one
+                self.current_schedule_index += 1;
             } else {
                 self.current_schedule_index = 0;
             }
+            self.selected_agenda_index = 0;
+            self.selected_time_block_slot =
+                self.selected_time_block_slot.min(self.slot_count() - 1);
             // update current saved schedule name when viewing saved schedules
             if self.viewing_saved_schedules {
                 self.current_saved_schedule_name = self
@@ -496,19 +810,34 @@ impl ScheduleWidget {
                 );
             }
 
-            // generate valid (non-conflicting) schedules
-            self.generated_schedules =
-                generate_schedules(&self.cart_classes, &self.selected_for_schedule, false);
+            // generate the first batch of valid (non-conflicting) schedules
+            let selected_classes =
+                selected_cart_classes(&self.cart_classes, &self.selected_for_schedule);
+            let conflicts = self.cart_conflict_matrix(&selected_classes);
+            self.generated_schedules.clear();
+            let generator = if self.one_per_course {
+                ScheduleGenerator::one_per_course(selected_classes.clone(), conflicts.clone())
+            } else {
+                ScheduleGenerator::with_conflicts(selected_classes.clone(), conflicts.clone())
+            };
+            self.schedule_generator = Some(generator.peekable());
+            self.load_more_schedules();
 
             if self.generated_schedules.is_empty() {
-                // no valid schedules found - show which classes conflict
-                let selected_classes: Vec<Class> = self
-                    .selected_for_schedule
-                    .iter()
-                    .filter_map(|class_id| self.cart_classes.get(class_id))
-                    .cloned()
-                    .collect();
-                let conflicts = find_conflicting_classes(&selected_classes);
+                // no valid schedules found - show which classes conflict; sections of the same
+                // course never share a schedule with one section per course, so they don't count
+                let mut conflicts = conflicts;
+                if self.one_per_course {
+                    for (i, row) in conflicts.iter_mut().enumerate() {
+                        for (j, overlap) in row.iter_mut().enumerate() {
+                            if course_key(&selected_classes[i]) == course_key(&selected_classes[j])
+                            {
+                                *overlap = false;
+                            }
+                        }
+                    }
+                }
+                let conflicts = conflicting_pairs(&selected_classes, &conflicts);
                 let conflict_msg = if conflicts.len() == 1 {
                     format!(
                         "No valid schedules. Classes conflict: {} and {}",
@@ -534,10 +863,17 @@ impl ScheduleWidget {
             }
 
             // valid schedules found - proceed to viewing mode
+            let mut classes: Vec<String> = self.selected_for_schedule.iter().cloned().collect();
+            classes.sort();
+            self.generated_with = Some(GenerationSettings {
+                classes,
+                one_per_course: self.one_per_course,
+            });
             self.schedule_selection_mode = false;
             self.current_schedule_index = 0;
             self.selected_time_block_day = 0;
             self.selected_time_block_slot = 0;
+            self.selected_agenda_index = 0;
             (KeyAction::Continue, ScheduleAction::None)
         } else {
             // show class details in detail view
@@ -545,11 +881,37 @@ impl ScheduleWidget {
                 && self.current_schedule_index < self.generated_schedules.len()
             {
                 let schedule = &self.generated_schedules[self.current_schedule_index];
-                if let Some(class) = find_class_at_time_block(
+                if self.day_view {
+                    let agenda = day_agenda(schedule, self.selected_time_block_day);
+                    if let Some((_, _, class)) = agenda.get(self.selected_agenda_index) {
+                        return (
+                            KeyAction::Navigate(FocusMode::DetailView),
+                            ScheduleAction::OpenDetailView((*class).clone()),
+                        );
+                    }
+                    return (KeyAction::Continue, ScheduleAction::None);
+                }
+                let (grid_start, _) = self.grid_bounds(schedule);
+                let slot = self.slot_minutes as i32;
+                let slot_start = grid_start + self.selected_time_block_slot as i32 * slot;
+                // a free block of this schedule can still open a class of the overlay
+                let class = find_class_at_time_block(
                     schedule,
                     self.selected_time_block_day,
-                    self.selected_time_block_slot,
-                ) {
+                    slot_start,
+                    slot,
+                )
+                .or_else(|| {
+                    self.overlay.as_ref().and_then(|overlay| {
+                        find_class_at_time_block(
+                            &overlay.classes,
+                            self.selected_time_block_day,
+                            slot_start,
+                            slot,
+                        )
+                    })
+                });
+                if let Some(class) = class {
                     return (
                         KeyAction::Navigate(FocusMode::DetailView),
                         ScheduleAction::OpenDetailView(class.clone()),
@@ -560,6 +922,113 @@ impl ScheduleWidget {
         }
     }
 
+    /// Handle V key - toggle between the week grid and the single-day agenda
+    ///
+    /// Arguments: None
+    ///
+    /// Returns:
+    /// --- ---
+    /// (KeyAction, ScheduleAction) -> continue action
+    /// --- ---
+    ///
+    fn handle_toggle_day_view(&mut self) -> (KeyAction, ScheduleAction) {
+        if !self.schedule_selection_mode {
+            self.day_view = !self.day_view;
+            self.finals_view = false;
+            self.selected_agenda_index = 0;
+        }
+        (KeyAction::Continue, ScheduleAction::None)
+    }
+
+    /// Handle X key - toggle between the week grid and the finals-week calendar
+    ///
+    /// Arguments: None
+    ///
+    /// Returns:
+    /// --- ---
+    /// (KeyAction, ScheduleAction) -> continue action
+    /// --- ---
+    ///
+    fn handle_toggle_finals_view(&mut self) -> (KeyAction, ScheduleAction) {
+        if !self.schedule_selection_mode {
+            self.finals_view = !self.finals_view;
+            self.day_view = false;
+        }
+        (KeyAction::Continue, ScheduleAction::None)
+    }
+
+    /// Handle O key - pick a schedule to overlay, or stop overlaying one
+    ///
+    /// Arguments: None
+    ///
+    /// Returns:
+    /// --- ---
+    /// (KeyAction, ScheduleAction) -> navigation to overlay input, toast, or continue
+    /// --- ---
+    ///
+    fn handle_overlay(&mut self) -> (KeyAction, ScheduleAction) {
+        if self.schedule_selection_mode || self.generated_schedules.is_empty() {
+            return (KeyAction::Continue, ScheduleAction::None);
+        }
+        if let Some(overlay) = self.overlay.take() {
+            return (
+                KeyAction::ShowToast {
+                    message: format!("Stopped overlaying '{}'", overlay.name),
+                    error_type: ErrorType::Info,
+                },
+                ScheduleAction::None,
+            );
+        }
+        (
+            KeyAction::Navigate(FocusMode::OverlayInput),
+            ScheduleAction::OverlaySchedule,
+        )
+    }
+
+    /// Handle F key - open the free time finder for the schedule being viewed
+    ///
+    /// Arguments: None
+    ///
+    /// Returns:
+    /// --- ---
+    /// (KeyAction, ScheduleAction) -> navigation to the free time finder or continue
+    /// --- ---
+    ///
+    fn handle_free_time(&mut self) -> (KeyAction, ScheduleAction) {
+        if self.schedule_selection_mode {
+            return (KeyAction::Continue, ScheduleAction::None);
+        }
+        match self.current_schedule() {
+            Some(schedule) => (
+                KeyAction::Navigate(FocusMode::FreeTime),
+                ScheduleAction::FindFreeTime(classes(schedule).cloned().collect()),
+            ),
+            None => (KeyAction::Continue, ScheduleAction::None),
+        }
+    }
+
+    /// Handle Y key - copy the CRNs of the schedule being viewed
+    ///
+    /// Arguments: None
+    ///
+    /// Returns:
+    /// --- ---
+    /// (KeyAction, ScheduleAction) -> the copy request or continue
+    /// --- ---
+    ///
+    fn handle_copy_crns(&mut self) -> (KeyAction, ScheduleAction) {
+        if self.schedule_selection_mode {
+            return (KeyAction::Continue, ScheduleAction::None);
+        }
+        match self.current_schedule() {
+            Some(schedule) => (
+                KeyAction::Continue,
+                ScheduleAction::CopyCrns(classes(schedule).cloned().collect()),
+            ),
+            None => (KeyAction::Continue, ScheduleAction::None),
+        }
+    }
+
     /// Handle Save key - save current schedule
     ///
     /// Arguments: None
@@ -585,6 +1054,34 @@ impl ScheduleWidget {
         }
     }
 
+    /// Handle G key - toggle generating one section per course
+    ///
+    /// Arguments: None
+    ///
+    /// Returns:
+    /// --- ---
+    /// (KeyAction, ScheduleAction) -> toast describing the new mode, or continue action
+    /// --- ---
+    ///
+    fn handle_toggle_one_per_course(&mut self) -> (KeyAction, ScheduleAction) {
+        if !self.schedule_selection_mode {
+            return (KeyAction::Continue, ScheduleAction::None);
+        }
+        self.one_per_course = !self.one_per_course;
+        let message = if self.one_per_course {
+            "Schedules will take one section of each checked course"
+        } else {
+            "Schedules will take any combination of the checked sections"
+        };
+        (
+            KeyAction::ShowToast {
+                message: message.to_string(),
+                error_type: ErrorType::Info,
+            },
+            ScheduleAction::None,
+        )
+    }
+
     /// Handle Space key - toggle class selection
     ///
     /// Arguments: None
@@ -627,6 +1124,7 @@ impl ScheduleWidget {
                 let class_id = cart_ids[self.selected_cart_index].clone();
                 self.cart_classes.remove(&class_id);
                 self.selected_for_schedule.remove(&class_id);
+                self.refresh_conflict_graph();
 
                 // adjust selected index if needed
                 if self.selected_cart_index >= self.cart_classes.len()
@@ -641,6 +1139,31 @@ impl ScheduleWidget {
         (KeyAction::Continue, ScheduleAction::None)
     }
 
+    /// Handle Alt+C key - mark selected cart class for comparison
+    ///
+    /// Arguments: None
+    ///
+    /// Returns:
+    /// --- ---
+    /// (KeyAction, ScheduleAction) -> compare action for the selected class or continue
+    /// --- ---
+    ///
+    fn handle_compare(&mut self) -> (KeyAction, ScheduleAction) {
+        if self.schedule_selection_mode {
+            let cart_ids = self.sorted_cart_ids();
+            if let Some(class) = cart_ids
+                .get(self.selected_cart_index)
+                .and_then(|class_id| self.cart_classes.get(class_id))
+            {
+                return (
+                    KeyAction::Continue,
+                    ScheduleAction::MarkForCompare(class.as_ref().clone()),
+                );
+            }
+        }
+        (KeyAction::Continue, ScheduleAction::None)
+    }
+
     /// Handle Tab key - open detail view for selected class
     ///
     /// Arguments: None
@@ -660,61 +1183,280 @@ impl ScheduleWidget {
                     self.detail_return_focus = FocusMode::ScheduleCreation;
                     return (
                         KeyAction::Navigate(FocusMode::DetailView),
-                        ScheduleAction::OpenDetailView(class.clone()),
+                        ScheduleAction::OpenDetailView(class.as_ref().clone()),
                     );
                 }
             }
+        } else if self.day_view {
+            return self.handle_down();
+        } else {
+            self.jump_to_class_block(true);
         }
         (KeyAction::Continue, ScheduleAction::None)
     }
 
-    /// Get current schedule for saving
+    /// Handle Shift+Tab key - jump to the previous class block in schedule view
     ///
     /// Arguments: None
     ///
     /// Returns:
     /// --- ---
-    /// Option<&Vec<Class>> -> reference to current schedule or None
+    /// (KeyAction, ScheduleAction) -> continue action
     /// --- ---
     ///
-    pub fn current_schedule(&self) -> Option<&Vec<Class>> {
-        if !self.generated_schedules.is_empty()
-            && self.current_schedule_index < self.generated_schedules.len()
-        {
-            Some(&self.generated_schedules[self.current_schedule_index])
-        } else {
-            None
+    fn handle_back_tab(&mut self) -> (KeyAction, ScheduleAction) {
+        if self.schedule_selection_mode {
+            return (KeyAction::Continue, ScheduleAction::None);
+        }
+        if self.day_view {
+            return self.handle_up();
         }
+        self.jump_to_class_block(false);
+        (KeyAction::Continue, ScheduleAction::None)
     }
 
-    /// Render the schedule creation interface
+    /// Move the grid selection to the start of the next or previous class block
     ///
     /// Arguments:
     /// --- ---
-    /// frame -> the frame to render to
-    /// theme -> the current theme
+    /// forward -> whether to jump to the next (true) or previous (false) block
     /// --- ---
     ///
     /// Returns: None
     ///
-    fn render_schedule(&self, frame: &mut Frame, theme: &Theme) {
-        let frame_width = frame.area().width;
-        let frame_height = frame.area().height;
-
-        // position below logo at top (logo is 7 lines tall, add spacing)
-        let logo_height = 7_u16;
-        let spacing = 6_u16;
-        let start_y = logo_height + spacing;
+    fn jump_to_class_block(&mut self, forward: bool) {
+        let Some(schedule) = self.current_schedule() else {
+            return;
+        };
+        let (grid_start, _) = self.grid_bounds(schedule);
+        let slot = self.slot_minutes as i32;
+
+        // starting (day, slot) of every meeting, in reading order (day, then time)
+        let mut block_starts: Vec<(usize, usize)> = (0..7)
+            .flat_map(|day| {
+                day_agenda(schedule, day)
+                    .into_iter()
+                    .map(move |(start_minutes, _, _)| {
+                        (day, ((start_minutes - grid_start) / slot) as usize)
+                    })
+            })
+            .collect();
+        block_starts.sort();
+        block_starts.dedup();
 
-        // calculate size - use full available height for schedule viewing
-        let max_width = 90_u16.min(frame_width.saturating_sub(4)); // leave margins, max 90 chars wide
-        let max_height = if self.schedule_selection_mode {
-            // in selection mode, limit height for cart
-            (frame_height.saturating_sub(start_y + 3)).min(20)
+        let current = (self.selected_time_block_day, self.selected_time_block_slot);
+        let target = if forward {
+            block_starts
+                .iter()
+                .find(|&&block| block > current)
+                .or(block_starts.first())
         } else {
-            // in viewing mode, use full available height for calendar
-            // only reserve minimal space for help text (1 line) and gap/counter (2 lines)
-            frame_height.saturating_sub(start_y + 1 + 2) // start_y + help text + gap/counter
+            block_starts
+                .iter()
+                .rev()
+                .find(|&&block| block < current)
+                .or(block_starts.last())
+        };
+
+        if let Some(&(day, slot_idx)) = target {
+            self.selected_time_block_day = day;
+            self.selected_time_block_slot = slot_idx;
+        }
+    }
+
+    /// Generate the next batch of schedules from the lazy generator
+    ///
+    /// Arguments: None
+    ///
+    /// Returns:
+    /// --- ---
+    /// bool -> true if any new schedules were generated
+    /// --- ---
+    ///
+    fn load_more_schedules(&mut self) -> bool {
+        let Some(generator) = self.schedule_generator.as_mut() else {
+            return false;
+        };
+        let before = self.generated_schedules.len();
+        self.generated_schedules
+            .extend(generator.by_ref().take(SCHEDULE_BATCH_SIZE));
+
+        // drop the generator once it's exhausted so the counter stops showing "+"
+        if generator.peek().is_none() {
+            self.schedule_generator = None;
+        }
+        self.generated_schedules.len() > before
+    }
+
+    /// Get the total schedule count label for the counter
+    ///
+    /// Arguments: None
+    ///
+    /// Returns:
+    /// --- ---
+    /// String -> number of generated schedules, with "+" if more can still be generated
+    /// --- ---
+    ///
+    fn total_schedules_label(&self) -> String {
+        if self.schedule_generator.is_some() {
+            format!("{}+", self.generated_schedules.len())
+        } else {
+            self.generated_schedules.len().to_string()
+        }
+    }
+
+    /// Get the counter shown below a schedule
+    ///
+    /// Arguments:
+    /// --- ---
+    /// schedule -> the schedule classes
+    /// current_index -> index of currently displayed schedule
+    /// saved_schedule_index -> optional index for saved schedules
+    /// total_saved_schedules -> optional total saved schedules count
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// String -> the schedule's position (e.g., "Schedule 2 of 5"), followed by the total of
+    ///           its fees when any of its classes charge one
+    /// --- ---
+    ///
+    fn counter_text(
+        &self,
+        schedule: &[Arc<Class>],
+        current_index: usize,
+        saved_schedule_index: Option<usize>,
+        total_saved_schedules: Option<usize>,
+    ) -> String {
+        // if viewing from saved schedules, show saved schedule index instead
+        let mut counter = if let (Some(saved_idx), Some(total_saved)) =
+            (saved_schedule_index, total_saved_schedules)
+        {
+            format!("Schedule {} of {}", saved_idx + 1, total_saved)
+        } else {
+            format!(
+                "Schedule {} of {}",
+                current_index + 1,
+                self.total_schedules_label()
+            )
+        };
+        let fees = total_fees(schedule);
+        if fees > 0.0 {
+            counter.push_str(&format!(" · {} in fees", sql::format_dollars(fees)));
+        }
+        counter
+    }
+
+    /// Set the schedule grid time range and slot size
+    ///
+    /// Arguments:
+    /// --- ---
+    /// start_hour -> first hour shown in the grid
+    /// end_hour -> hour the grid ends at
+    /// slot_minutes -> length of each slot in minutes
+    /// --- ---
+    ///
+    /// Returns: None
+    ///
+    pub fn set_grid(&mut self, start_hour: usize, end_hour: usize, slot_minutes: usize) {
+        self.grid_start_hour = start_hour;
+        self.grid_end_hour = end_hour.max(start_hour + 1);
+        self.slot_minutes = slot_minutes.max(5);
+        self.selected_time_block_slot = 0;
+    }
+
+    /// Get the grid's time range for a schedule, expanded to cover every meeting
+    /// (including those of the overlay)
+    ///
+    /// Arguments:
+    /// --- ---
+    /// schedule -> the schedule classes to display
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// (i32, i32) -> (start_minutes, end_minutes) aligned to slot boundaries
+    /// --- ---
+    ///
+    fn grid_bounds(&self, schedule: &[Arc<Class>]) -> (i32, i32) {
+        let slot = self.slot_minutes as i32;
+        let mut start = (self.grid_start_hour * 60) as i32;
+        let mut end = (self.grid_end_hour * 60) as i32;
+
+        // auto-expand so classes outside the configured range aren't cut off
+        let overlay = self.overlay.iter().flat_map(|overlay| &overlay.classes);
+        for meeting in classes(schedule)
+            .chain(overlay)
+            .flat_map(|class| &class.meeting_times)
+        {
+            start = start.min(meeting.start_minutes / slot * slot);
+            end = end.max((meeting.end_minutes + slot - 1) / slot * slot);
+        }
+
+        (start, end)
+    }
+
+    /// Get the number of grid slots for the current schedule
+    ///
+    /// Arguments: None
+    ///
+    /// Returns:
+    /// --- ---
+    /// usize -> number of time slots in the grid
+    /// --- ---
+    ///
+    fn slot_count(&self) -> usize {
+        let (start, end) = self.grid_bounds(self.current_schedule().map_or(&[], |s| s.as_slice()));
+        ((end - start) / self.slot_minutes as i32).max(1) as usize
+    }
+
+    /// Get current schedule for saving
+    ///
+    /// Arguments: None
+    ///
+    /// Returns:
+    /// --- ---
+    /// Option<&Vec<Arc<Class>>> -> reference to current schedule or None
+    /// --- ---
+    ///
+    pub fn current_schedule(&self) -> Option<&Vec<Arc<Class>>> {
+        if !self.generated_schedules.is_empty()
+            && self.current_schedule_index < self.generated_schedules.len()
+        {
+            Some(&self.generated_schedules[self.current_schedule_index])
+        } else {
+            None
+        }
+    }
+
+    /// Render the schedule creation interface
+    ///
+    /// Arguments:
+    /// --- ---
+    /// frame -> the frame to render to
+    /// theme -> the current theme
+    /// --- ---
+    ///
+    /// Returns: None
+    ///
+    fn render_schedule(&self, frame: &mut Frame, theme: &Theme) {
+        let frame_width = frame.area().width;
+        let frame_height = frame.area().height;
+
+        // position below logo at top (logo is 7 lines tall, add spacing)
+        let logo_height = 7_u16;
+        let spacing = 6_u16;
+        let start_y = logo_height + spacing;
+
+        // calculate size - use full available height for schedule viewing
+        let max_width = 90_u16.min(frame_width.saturating_sub(4)); // leave margins, max 90 chars wide
+        let max_height = if self.schedule_selection_mode {
+            // in selection mode, limit height for cart
+            (frame_height.saturating_sub(start_y + 3)).min(20)
+        } else {
+            // in viewing mode, use full available height for calendar
+            // only reserve minimal space for help text (1 line) and gap/counter (2 lines)
+            frame_height.saturating_sub(start_y + 1 + 2) // start_y + help text + gap/counter
         };
         let time_col_width = 7_u16;
         let logo_shift = 1_u16; // logo is shifted 1 space to the right
@@ -752,6 +1494,18 @@ impl ScheduleWidget {
                 height: 3, // 3 lines for messages
             };
             self.render_cart_section(frame, cart_area, message_area, theme);
+
+            // show a mini week-grid of the checked classes to the right of the cart if it fits
+            let preview_x = cart_x + cart_width + 2;
+            if preview_x + CART_PREVIEW_WIDTH <= frame_width {
+                let preview_area = Rect {
+                    x: preview_x,
+                    y: start_y,
+                    width: CART_PREVIEW_WIDTH,
+                    height: CART_PREVIEW_HEIGHT.min(frame_height.saturating_sub(start_y)),
+                };
+                self.render_cart_preview(frame, preview_area, theme);
+            }
         } else {
             // in viewing mode, show time-block calendar
             // if schedule name is provided, render it above the schedule with a gap
@@ -807,12 +1561,33 @@ impl ScheduleWidget {
             if !self.generated_schedules.is_empty()
                 && self.current_schedule_index < self.generated_schedules.len()
             {
+                if self.finals_view {
+                    self.render_finals_calendar(
+                        frame,
+                        schedule_area,
+                        &self.generated_schedules[self.current_schedule_index],
+                        saved_idx,
+                        total_saved,
+                        theme,
+                    );
+                    return;
+                }
+                if self.day_view {
+                    self.render_day_agenda(
+                        frame,
+                        schedule_area,
+                        &self.generated_schedules[self.current_schedule_index],
+                        saved_idx,
+                        total_saved,
+                        theme,
+                    );
+                    return;
+                }
                 self.render_time_block_calendar(
                     frame,
                     schedule_area,
                     &self.generated_schedules[self.current_schedule_index],
                     self.current_schedule_index,
-                    self.generated_schedules.len(),
                     self.selected_time_block_day,
                     self.selected_time_block_slot,
                     saved_idx,
@@ -865,10 +1640,21 @@ impl ScheduleWidget {
         };
 
         // cart items - get classes from cart_classes map, sorted by ID for consistent ordering
-        let mut cart_classes_vec: Vec<&Class> = self.cart_classes.values().collect();
+        let mut cart_classes_vec: Vec<&Class> =
+            self.cart_classes.values().map(Arc::as_ref).collect();
         // sort by unique_id for consistent ordering
         cart_classes_vec.sort_by_key(|class| class.unique_id());
 
+        // checked classes that meet at the same time as another checked class, once the
+        // conflict graph has been built
+        let checked_classes =
+            selected_cart_classes(&self.cart_classes, &self.selected_for_schedule);
+        let conflicted = self
+            .conflict_graph
+            .get()
+            .and_then(|graph| graph.conflicted_ids(&checked_classes))
+            .unwrap_or_default();
+
         let cart_text = if cart_classes_vec.is_empty() {
             vec![Line::from(Span::styled(
                 "Cart is empty",
@@ -896,6 +1682,11 @@ impl ScheduleWidget {
                             .fg(theme.text_color)
                             .add_modifier(Modifier::BOLD)
                     };
+                    let conflict_marker = if conflicted.contains(&class_id) {
+                        " ✗"
+                    } else {
+                        ""
+                    };
                     Line::from(vec![
                         Span::styled(prefix, base_style),
                         Span::styled(checkbox, base_style),
@@ -906,6 +1697,7 @@ impl ScheduleWidget {
                             ),
                             base_style,
                         ),
+                        Span::styled(conflict_marker, Style::default().fg(theme.error_color)),
                     ])
                 })
                 .collect()
@@ -919,7 +1711,11 @@ impl ScheduleWidget {
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .title(" Cart ")
+                    .title(if self.one_per_course {
+                        " Cart (one section per course) "
+                    } else {
+                        " Cart "
+                    })
                     .title_style(
                         Style::default()
                             .fg(theme.title_color)
@@ -937,9 +1733,30 @@ impl ScheduleWidget {
             .alignment(Alignment::Center);
         frame.render_widget(message1, message_chunks[0]);
 
-        // empty line for gap
-        let empty_line = Paragraph::new("").style(Style::default().fg(theme.background_color));
-        frame.render_widget(empty_line, message_chunks[1]);
+        // the gap line warns about checked classes whose finals are at the same time
+        let checked: Vec<&Class> = cart_classes_vec
+            .iter()
+            .filter(|class| self.selected_for_schedule.contains(&class.unique_id()))
+            .copied()
+            .collect();
+        let final_conflicts = find_final_conflicts(&checked);
+        let gap_line = match final_conflicts.split_first() {
+            Some(((first, second), rest)) => {
+                let more = if rest.is_empty() {
+                    String::new()
+                } else {
+                    format!(" (+{} more)", rest.len())
+                };
+                Paragraph::new(format!(
+                    "⚠ Final exams conflict: {} & {}{}",
+                    first, second, more
+                ))
+                .style(Style::default().fg(theme.warning_color))
+                .alignment(Alignment::Center)
+            }
+            None => Paragraph::new("").style(Style::default().fg(theme.background_color)),
+        };
+        frame.render_widget(gap_line, message_chunks[1]);
 
         // message to press enter to continue
         let message2 = Paragraph::new("Press Enter to continue")
@@ -948,17 +1765,112 @@ impl ScheduleWidget {
         frame.render_widget(message2, message_chunks[2]);
     }
 
-    /// Render time-block calendar view
+    /// Render a mini week-grid shading the meeting times of checked cart classes
     ///
     /// Arguments:
     /// --- ---
     /// frame -> the frame to render to
-    /// area -> the area to render the calendar in
+    /// area -> the area to render the preview in
+    /// theme -> the current theme
+    /// --- ---
+    ///
+    /// Returns: None
+    ///
+    fn render_cart_preview(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let day_labels = ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"];
+
+        // count how many checked classes meet in each (day, hour) cell
+        let mut occupancy: HashMap<(usize, usize), usize> = HashMap::new();
+        let checked_meetings = self
+            .selected_for_schedule
+            .iter()
+            .filter_map(|class_id| self.cart_classes.get(class_id))
+            .flat_map(|class| &class.meeting_times);
+        for meeting in checked_meetings {
+            for day_code in &meeting.days {
+                let Some(day_idx) = DAY_CODES.iter().position(|&d| d == day_code) else {
+                    continue;
+                };
+                for hour_idx in 0..CART_PREVIEW_HOURS {
+                    let hour_start = ((CART_PREVIEW_FIRST_HOUR + hour_idx) * 60) as i32;
+                    if hour_start < meeting.end_minutes && hour_start + 60 > meeting.start_minutes {
+                        *occupancy.entry((day_idx, hour_idx)).or_insert(0) += 1;
+                    }
+                }
+            }
+        }
+
+        let has_conflict = occupancy.values().any(|&count| count > 1);
+
+        // header row with day labels
+        let mut header = vec![Span::raw("    ")];
+        for label in day_labels {
+            header.push(Span::styled(
+                format!("{:<3}", label),
+                Style::default()
+                    .fg(theme.title_color)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+        let mut lines = vec![Line::from(header)];
+
+        for hour_idx in 0..CART_PREVIEW_HOURS {
+            let hour = CART_PREVIEW_FIRST_HOUR + hour_idx;
+            let hour_label = match hour {
+                0 | 12 => format!("{:>2}{} ", 12, if hour == 0 { "a" } else { "p" }),
+                h if h < 12 => format!("{:>2}a ", h),
+                h => format!("{:>2}p ", h - 12),
+            };
+            let mut spans = vec![Span::styled(
+                hour_label,
+                Style::default().fg(theme.muted_color),
+            )];
+            for day_idx in 0..DAY_CODES.len() {
+                // shade busy hours, mark hours where checked classes overlap with "!!"
+                let span = match occupancy.get(&(day_idx, hour_idx)).copied().unwrap_or(0) {
+                    0 => Span::styled("·  ", Style::default().fg(theme.muted_color)),
+                    1 => Span::styled("██ ", Style::default().fg(theme.info_color)),
+                    _ => Span::styled(
+                        "!! ",
+                        Style::default()
+                            .fg(theme.error_color)
+                            .add_modifier(Modifier::BOLD | Modifier::REVERSED),
+                    ),
+                };
+                spans.push(span);
+            }
+            lines.push(Line::from(spans));
+        }
+
+        let (title, title_color) = if has_conflict {
+            (" Week (conflict) ", theme.error_color)
+        } else {
+            (" Week ", theme.title_color)
+        };
+
+        let preview_widget = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(title)
+                    .title_style(
+                        Style::default()
+                            .fg(title_color)
+                            .add_modifier(Modifier::BOLD),
+                    )
+                    .border_style(Style::default().fg(theme.border_color)),
+            )
+            .style(Style::default().bg(theme.background_color));
+        frame.render_widget(preview_widget, area);
+    }
+
+    /// Render single-day agenda view
+    ///
+    /// Arguments:
+    /// --- ---
+    /// frame -> the frame to render to
+    /// area -> the area to render the agenda in
     /// schedule -> the schedule classes to display
-    /// current_index -> index of currently displayed schedule
-    /// total_schedules -> total number of schedules available
-    /// selected_day -> selected day index (0-6 for Mon-Sun)
-    /// selected_slot -> selected time slot index
     /// saved_schedule_index -> optional index for saved schedules
     /// total_saved_schedules -> optional total saved schedules count
     /// theme -> the current theme
@@ -966,110 +1878,481 @@ impl ScheduleWidget {
     ///
     /// Returns: None
     ///
-    fn render_time_block_calendar(
+    fn render_day_agenda(
         &self,
         frame: &mut Frame,
         area: Rect,
-        schedule: &[Class],
-        current_index: usize,
-        total_schedules: usize,
-        selected_day: usize,
-        selected_slot: usize,
+        schedule: &[Arc<Class>],
         saved_schedule_index: Option<usize>,
         total_saved_schedules: Option<usize>,
         theme: &Theme,
     ) {
-        // use the full area for the calendar, we'll position the counter manually
-        let calendar_area = area;
+        let day_names = [
+            "Monday",
+            "Tuesday",
+            "Wednesday",
+            "Thursday",
+            "Friday",
+            "Saturday",
+            "Sunday",
+        ];
+        let day_name = day_names
+            .get(self.selected_time_block_day)
+            .copied()
+            .unwrap_or("Monday");
+
+        let agenda = day_agenda(schedule, self.selected_time_block_day);
+        let selected_index = self
+            .selected_agenda_index
+            .min(agenda.len().saturating_sub(1));
+
+        let mut lines = vec![
+            Line::from(Span::styled(
+                format!("← {} →", day_name),
+                Style::default()
+                    .fg(theme.title_color)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .alignment(Alignment::Center),
+            Line::from(""),
+        ];
+
+        if agenda.is_empty() {
+            lines.push(
+                Line::from(Span::styled(
+                    "No classes on this day",
+                    Style::default().fg(theme.muted_color),
+                ))
+                .alignment(Alignment::Center),
+            );
+        }
 
-        // time slots: 8am to 10:30pm, 30-minute intervals = 30 slots
-        let time_slots: Vec<(i32, String)> = (16..46) // 8:00 am - 10:30pm
-            .map(|half_hour| {
-                let hours = half_hour / 2;
-                let minutes = (half_hour % 2) * 30;
-                let (display_hour, period) = if hours == 0 {
-                    (12, "am")
-                } else if hours < 12 {
-                    (hours, "am")
-                } else if hours == 12 {
-                    (12, "pm")
-                } else {
-                    (hours - 12, "pm")
-                };
-                // format with leading zero for single-digit hours (1-9) to make all times 5 digits
-                let time_str = format!("{:02}:{:02}{}", display_hour, minutes, period);
-                (half_hour * 30, time_str) // minutes since midnight
-            })
-            .collect();
+        for (idx, (start_minutes, end_minutes, class)) in agenda.iter().enumerate() {
+            let is_selected = idx == selected_index;
+            let prefix = if is_selected { "> " } else { "  " };
+            let class_style = if is_selected {
+                Style::default()
+                    .fg(theme.selected_color)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+                    .fg(theme.info_color)
+                    .add_modifier(Modifier::BOLD)
+            };
+            let detail_style = Style::default().fg(theme.text_color);
 
-        // day names
-        let day_names = vec!["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
-        let day_codes = vec!["M", "T", "W", "TH", "F", "S", "SU"];
-
-        // build time block grid: map (day, slot) -> class
-        let mut time_blocks: HashMap<(usize, usize), &Class> = HashMap::new();
-
-        for class in schedule {
-            if let Some(meeting_times_str) = &class.meeting_times {
-                if !meeting_times_str.is_empty() {
-                    let meetings = parse_meeting_times(meeting_times_str);
-                    for (days, start_minutes, end_minutes) in meetings {
-                        for day_code in &days {
-                            // find day index
-                            if let Some(day_idx) = day_codes.iter().position(|&d| d == day_code) {
-                                // find time slots that overlap with this meeting
-                                for (slot_idx, (slot_start, _)) in time_slots.iter().enumerate() {
-                                    let slot_end = *slot_start + 30; // 30-minute slots
-                                                                     // check if meeting overlaps with this time slot
-                                    if *slot_start < end_minutes && slot_end > start_minutes {
-                                        time_blocks.insert((day_idx, slot_idx), class);
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
+            lines.push(Line::from(vec![
+                Span::styled(prefix, class_style),
+                Span::styled(
+                    format!(
+                        "{:<16}",
+                        format!(
+                            "{}-{}",
+                            format_12_hour_padded(*start_minutes),
+                            format_12_hour_padded(*end_minutes)
+                        )
+                    ),
+                    Style::default().fg(theme.muted_color),
+                ),
+                Span::styled(
+                    format!(
+                        "{:<16}",
+                        format!(
+                            "{} {}-{}",
+                            class.subject_code, class.course_number, class.section_sequence
+                        )
+                    ),
+                    class_style,
+                ),
+                Span::styled(
+                    format!("{:<20}", class.campus.as_deref().unwrap_or("TBA")),
+                    detail_style,
+                ),
+                Span::styled(
+                    class.professor_name.as_deref().unwrap_or("TBA").to_string(),
+                    detail_style,
+                ),
+            ]));
         }
 
-        // calculate column widths
-        // find maximum time string width to ensure "am"/"pm" is never cut off
-        let time_col_width = time_slots
-            .iter()
-            .map(|(_, time_str)| time_str.len() as u16)
-            .max()
-            .unwrap_or(7) // default to 7 if empty (covers "08:00am", "10:00am", "12:00pm")
-            .max(7); // ensure at least 7 to cover all formatted times (all are 7 chars: "08:00am")
-        let day_col_width = (calendar_area.width.saturating_sub(time_col_width + 2)) / 7; // 7 days
+        // the overlay's classes that day and the free time both schedules share
+        if let Some(overlay) = &self.overlay {
+            let muted_style = Style::default().fg(theme.muted_color);
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                format!("  {}", overlay.name),
+                muted_style.add_modifier(Modifier::BOLD),
+            )));
+            let overlay_agenda = day_agenda(&overlay.classes, self.selected_time_block_day);
+            if overlay_agenda.is_empty() {
+                lines.push(Line::from(Span::styled(
+                    "  No classes on this day",
+                    muted_style,
+                )));
+            }
+            for (start_minutes, end_minutes, class) in overlay_agenda {
+                lines.push(Line::from(vec![
+                    Span::styled(
+                        format!(
+                            "  {:<16}",
+                            format!(
+                                "{}-{}",
+                                format_12_hour_padded(start_minutes),
+                                format_12_hour_padded(end_minutes)
+                            )
+                        ),
+                        muted_style,
+                    ),
+                    Span::styled(
+                        format!(
+                            "{} {}-{}",
+                            class.subject_code, class.course_number, class.section_sequence
+                        ),
+                        muted_style.add_modifier(Modifier::ITALIC),
+                    ),
+                ]));
+            }
 
-        // create header row with day names
-        let header_y = calendar_area.y;
-        for (idx, day_name) in day_names.iter().enumerate() {
-            // day headers are never highlighted, only time slots are highlighted
-            let style = Style::default()
-                .fg(theme.title_color)
-                .add_modifier(Modifier::BOLD);
-            // render day header
-            let day_x = calendar_area.x + time_col_width + (idx as u16 * day_col_width);
-            let day_area = Rect {
-                x: day_x,
-                y: header_y,
-                width: day_col_width,
-                height: 1,
+            let free_blocks = shared_free_blocks(
+                schedule,
+                &overlay.classes,
+                self.selected_time_block_day,
+                MIN_SHARED_FREE_MINUTES,
+            );
+            let free_text = if free_blocks.is_empty() {
+                "none".to_string()
+            } else {
+                free_blocks
+                    .iter()
+                    .map(|(start_minutes, end_minutes)| {
+                        format!(
+                            "{}-{}",
+                            format_12_hour_padded(*start_minutes),
+                            format_12_hour_padded(*end_minutes)
+                        )
+                    })
+                    .collect::<Vec<String>>()
+                    .join(", ")
             };
-            let day_para = Paragraph::new(day_name.to_string())
-                .style(style)
-                .alignment(Alignment::Center);
-            frame.render_widget(day_para, day_area);
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                format!("  Free together: {}", free_text),
+                Style::default().fg(theme.success_color),
+            )));
         }
 
-        // render time slots and track the last rendered row
-        let mut last_rendered_y = header_y;
-        for (slot_idx, (_, time_str)) in time_slots.iter().enumerate() {
-            let slot_y = header_y + 1 + slot_idx as u16;
-            if slot_y >= calendar_area.y + calendar_area.height {
-                break;
-            }
+        // schedule counter below the agenda (with 1 line gap)
+        let counter_text = self.counter_text(
+            schedule,
+            self.current_schedule_index,
+            saved_schedule_index,
+            total_saved_schedules,
+        );
+        lines.push(Line::from(""));
+        lines.push(
+            Line::from(Span::styled(
+                counter_text,
+                Style::default().fg(theme.info_color),
+            ))
+            .alignment(Alignment::Center),
+        );
+
+        let agenda_widget =
+            Paragraph::new(lines).style(Style::default().bg(theme.background_color));
+        frame.render_widget(agenda_widget, area);
+    }
+
+    /// Render the finals-week calendar: one column per exam date, in half-hour rows
+    ///
+    /// Arguments:
+    /// --- ---
+    /// frame -> the frame to render to
+    /// area -> the area to render the calendar in
+    /// schedule -> the schedule classes to display
+    /// saved_schedule_index -> optional index for saved schedules
+    /// total_saved_schedules -> optional total saved schedules count
+    /// theme -> the current theme
+    /// --- ---
+    ///
+    /// Returns: None
+    ///
+    fn render_finals_calendar(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        schedule: &[Arc<Class>],
+        saved_schedule_index: Option<usize>,
+        total_saved_schedules: Option<usize>,
+        theme: &Theme,
+    ) {
+        let days = finals_by_date(schedule);
+        let muted_style = Style::default().fg(theme.muted_color);
+        let mut lines = vec![
+            Line::from(Span::styled(
+                "Finals Week",
+                Style::default()
+                    .fg(theme.title_color)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .alignment(Alignment::Center),
+            Line::from(""),
+        ];
+
+        if days.is_empty() {
+            lines.push(
+                Line::from(Span::styled(
+                    "No final exams listed for this schedule",
+                    muted_style,
+                ))
+                .alignment(Alignment::Center),
+            );
+        } else {
+            let exams = || days.iter().flat_map(|(_, exams)| exams.iter());
+            // whole hours around the earliest start and latest end
+            let first_minute = exams()
+                .map(|(exam, _)| exam.start_minutes)
+                .min()
+                .unwrap_or(0);
+            let last_minute = exams().map(|(exam, _)| exam.end_minutes).max().unwrap_or(0);
+            let grid_start = first_minute / 60 * 60;
+            let grid_end = (last_minute + 59) / 60 * 60;
+
+            let time_col_width = 8;
+            let col_width =
+                ((area.width as usize).saturating_sub(time_col_width) / days.len()).clamp(10, 16);
+            let fit = |text: &str| {
+                let text: String = text.chars().take(col_width - 1).collect();
+                format!("{:^width$}", text, width = col_width)
+            };
+            let course_colors = course_colors(schedule, &theme.course_palette());
+
+            let mut header = vec![Span::raw(" ".repeat(time_col_width))];
+            for (date, exams) in &days {
+                let label = exams
+                    .first()
+                    .map(|(exam, _)| exam.day_label())
+                    .unwrap_or_else(|| date.short_label());
+                header.push(Span::styled(
+                    fit(&label),
+                    Style::default()
+                        .fg(theme.title_color)
+                        .add_modifier(Modifier::BOLD),
+                ));
+            }
+            lines.push(Line::from(header));
+
+            for slot_start in (grid_start..grid_end).step_by(FINALS_SLOT_MINUTES as usize) {
+                let slot_end = slot_start + FINALS_SLOT_MINUTES;
+                let mut spans = vec![Span::styled(
+                    format!(
+                        "{:<width$}",
+                        format_12_hour_padded(slot_start),
+                        width = time_col_width
+                    ),
+                    muted_style,
+                )];
+                for (_, exams) in &days {
+                    let here: Vec<&(FinalExam, &Class)> = exams
+                        .iter()
+                        .filter(|(exam, _)| {
+                            exam.start_minutes < slot_end && exam.end_minutes > slot_start
+                        })
+                        .collect();
+                    let span = match here.as_slice() {
+                        [] => Span::styled(fit("·"), muted_style),
+                        [(exam, class)] => {
+                            let key = course_key(class);
+                            // the course is named in its first slot, later ones are shaded
+                            let text = if exam.start_minutes >= slot_start {
+                                fit(&key)
+                            } else {
+                                fit(&"░".repeat(col_width - 2))
+                            };
+                            let color =
+                                course_colors.get(&key).copied().unwrap_or(theme.info_color);
+                            Span::styled(
+                                text,
+                                Style::default().fg(color).add_modifier(Modifier::BOLD),
+                            )
+                        }
+                        _ => Span::styled(
+                            fit("✗ conflict"),
+                            Style::default()
+                                .fg(theme.error_color)
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                    };
+                    spans.push(span);
+                }
+                lines.push(Line::from(spans));
+            }
+        }
+
+        // conflicting finals, then the classes with no final listed
+        let conflicts = find_final_conflicts(schedule);
+        let without: Vec<String> = classes(schedule)
+            .filter(|class| class.final_exam.is_none())
+            .map(display_id)
+            .collect();
+        if !conflicts.is_empty() || (!without.is_empty() && !days.is_empty()) {
+            lines.push(Line::from(""));
+        }
+        for (first, second) in &conflicts {
+            lines.push(
+                Line::from(Span::styled(
+                    format!("✗ Final exams conflict: {} & {}", first, second),
+                    Style::default().fg(theme.error_color),
+                ))
+                .alignment(Alignment::Center),
+            );
+        }
+        if !without.is_empty() && !days.is_empty() {
+            lines.push(
+                Line::from(Span::styled(
+                    format!("No final listed: {}", without.join(", ")),
+                    muted_style,
+                ))
+                .alignment(Alignment::Center),
+            );
+        }
+
+        // schedule counter below the calendar (with 1 line gap)
+        let counter_text = self.counter_text(
+            schedule,
+            self.current_schedule_index,
+            saved_schedule_index,
+            total_saved_schedules,
+        );
+        lines.push(Line::from(""));
+        lines.push(
+            Line::from(Span::styled(
+                counter_text,
+                Style::default().fg(theme.info_color),
+            ))
+            .alignment(Alignment::Center),
+        );
+
+        let finals_widget =
+            Paragraph::new(lines).style(Style::default().bg(theme.background_color));
+        frame.render_widget(finals_widget, area);
+    }
+
+    /// Render time-block calendar view
+    ///
+    /// Arguments:
+    /// --- ---
+    /// frame -> the frame to render to
+    /// area -> the area to render the calendar in
+    /// schedule -> the schedule classes to display
+    /// current_index -> index of currently displayed schedule
+    /// selected_day -> selected day index (0-6 for Mon-Sun)
+    /// selected_slot -> selected time slot index
+    /// saved_schedule_index -> optional index for saved schedules
+    /// total_saved_schedules -> optional total saved schedules count
+    /// theme -> the current theme
+    /// --- ---
+    ///
+    /// Returns: None
+    ///
+    fn render_time_block_calendar(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        schedule: &[Arc<Class>],
+        current_index: usize,
+        selected_day: usize,
+        selected_slot: usize,
+        saved_schedule_index: Option<usize>,
+        total_saved_schedules: Option<usize>,
+        theme: &Theme,
+    ) {
+        // use the full area for the calendar, we'll position the counter manually
+        let calendar_area = area;
+
+        // time slots: configured range (expanded to fit every meeting) in configured intervals
+        let slot_minutes = self.slot_minutes as i32;
+        let (grid_start, grid_end) = self.grid_bounds(schedule);
+        let time_slots: Vec<(i32, String)> = (grid_start..grid_end)
+            .step_by(self.slot_minutes)
+            .map(|slot_start| (slot_start, format_12_hour_padded(slot_start))) // minutes since midnight
+            .collect();
+
+        // day names
+        let day_names = vec!["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+        // build time block grid: map (day, slot) -> classes meeting in that block
+        let slot_starts: Vec<i32> = time_slots.iter().map(|(start, _)| *start).collect();
+        let time_blocks = slot_blocks(schedule, &slot_starts, slot_minutes);
+
+        // the overlay's classes, the free slots it shares with this schedule, and the
+        // sections both schedules have
+        let overlay_classes = self.overlay.as_ref().map_or(&[][..], |o| &o.classes[..]);
+        let overlay_blocks = slot_blocks(overlay_classes, &slot_starts, slot_minutes);
+        let mut free_slots: HashSet<(usize, usize)> = HashSet::new();
+        if self.overlay.is_some() {
+            for day_idx in 0..DAY_CODES.len() {
+                for (free_start, free_end) in
+                    shared_free_blocks(schedule, overlay_classes, day_idx, MIN_SHARED_FREE_MINUTES)
+                {
+                    for (slot_idx, slot_start) in slot_starts.iter().enumerate() {
+                        let block = (day_idx, slot_idx);
+                        if *slot_start < free_end
+                            && slot_start + slot_minutes > free_start
+                            && !time_blocks.contains_key(&block)
+                            && !overlay_blocks.contains_key(&block)
+                        {
+                            free_slots.insert(block);
+                        }
+                    }
+                }
+            }
+        }
+        let overlay_ids: HashSet<String> = overlay_classes.iter().map(Class::unique_id).collect();
+        let has_shared_sections = schedule
+            .iter()
+            .any(|class| overlay_ids.contains(&class.unique_id()));
+
+        // assign each course a stable color from the theme palette
+        let course_colors = course_colors(schedule, &theme.course_palette());
+
+        // calculate column widths
+        // find maximum time string width to ensure "am"/"pm" is never cut off
+        let time_col_width = time_slots
+            .iter()
+            .map(|(_, time_str)| time_str.len() as u16)
+            .max()
+            .unwrap_or(7) // default to 7 if empty (covers "08:00am", "10:00am", "12:00pm")
+            .max(7); // ensure at least 7 to cover all formatted times (all are 7 chars: "08:00am")
+        let day_col_width = (calendar_area.width.saturating_sub(time_col_width + 2)) / 7; // 7 days
+
+        // create header row with day names
+        let header_y = calendar_area.y;
+        for (idx, day_name) in day_names.iter().enumerate() {
+            // day headers are never highlighted, only time slots are highlighted
+            let style = Style::default()
+                .fg(theme.title_color)
+                .add_modifier(Modifier::BOLD);
+            // render day header
+            let day_x = calendar_area.x + time_col_width + (idx as u16 * day_col_width);
+            let day_area = Rect {
+                x: day_x,
+                y: header_y,
+                width: day_col_width,
+                height: 1,
+            };
+            let day_para = Paragraph::new(day_name.to_string())
+                .style(style)
+                .alignment(Alignment::Center);
+            frame.render_widget(day_para, day_area);
+        }
+
+        // render time slots and track the last rendered row
+        let mut last_rendered_y = header_y;
+        for (slot_idx, (_, time_str)) in time_slots.iter().enumerate() {
+            let slot_y = header_y + 1 + slot_idx as u16;
+            if slot_y >= calendar_area.y + calendar_area.height {
+                break;
+            }
             last_rendered_y = slot_y;
 
             // render time label
@@ -1097,37 +2380,123 @@ impl ScheduleWidget {
                 let has_class = time_blocks.contains_key(&(day_idx, slot_idx));
 
                 if has_class {
-                    let class = time_blocks[&(day_idx, slot_idx)];
-                    let class_code = format!("{}{}", class.subject_code, class.course_number);
-                    let display_text = if class_code.len() <= day_col_width as usize {
-                        class_code
-                    } else {
-                        class_code[..day_col_width as usize].to_string()
-                    };
+                    let classes = &time_blocks[&(day_idx, slot_idx)];
+
+                    // only label the first slot of a block so multi-slot blocks read as one
+                    let continues_block = slot_idx > 0
+                        && time_blocks
+                            .get(&(day_idx, slot_idx - 1))
+                            .is_some_and(|prev| {
+                                prev.len() == classes.len()
+                                    && prev
+                                        .iter()
+                                        .zip(classes.iter())
+                                        .all(|(a, b)| a.unique_id() == b.unique_id())
+                            });
+
+                    // split the cell between overlapping classes instead of hiding all but one
+                    let part_width = (day_col_width / classes.len() as u16).max(1);
+                    for (part_idx, class) in classes.iter().enumerate() {
+                        let part_x = day_x + part_idx as u16 * part_width;
+                        if part_x >= day_x + day_col_width {
+                            break;
+                        }
+                        // the last part takes any leftover width
+                        let width = if part_idx == classes.len() - 1 {
+                            day_x + day_col_width - part_x
+                        } else {
+                            part_width
+                        };
+                        let part_area = Rect {
+                            x: part_x,
+                            y: slot_y,
+                            width,
+                            height: 1,
+                        };
+
+                        let course_color = course_colors
+                            .get(&course_key(class))
+                            .copied()
+                            .unwrap_or(theme.info_color);
+
+                        let display_text = if continues_block {
+                            String::new()
+                        } else {
+                            // mark sections the overlay has too
+                            let shared_mark = if overlay_ids.contains(&class.unique_id()) {
+                                "*"
+                            } else {
+                                ""
+                            };
+                            let class_code = format!(
+                                "{}{}{}",
+                                shared_mark, class.subject_code, class.course_number
+                            );
+                            class_code.chars().take(width as usize).collect()
+                        };
+
+                        let mut style = if is_selected {
+                            Style::default()
+                                .fg(theme.selected_color)
+                                .bg(theme.background_color)
+                                .add_modifier(Modifier::BOLD | Modifier::REVERSED)
+                        } else {
+                            Style::default()
+                                .fg(theme.background_color)
+                                .bg(course_color)
+                                .add_modifier(Modifier::BOLD)
+                        };
+                        // mark conflicting blocks so they are distinguishable from adjacent classes
+                        if classes.len() > 1 {
+                            style = style.add_modifier(Modifier::UNDERLINED);
+                        }
 
-                    let style = if is_selected {
-                        Style::default()
+                        let block_para = Paragraph::new(display_text)
+                            .style(style)
+                            .alignment(Alignment::Center);
+                        frame.render_widget(block_para, part_area);
+                    }
+                } else {
+                    let block = (day_idx, slot_idx);
+                    let (display_text, mut style) =
+                        if let Some(classes) = overlay_blocks.get(&block) {
+                            // overlay classes are shaded, and labeled on the first slot of a block
+                            let continues_block = slot_idx > 0
+                                && overlay_blocks.get(&(day_idx, slot_idx - 1)).is_some_and(
+                                    |prev| {
+                                        prev.iter().any(|c| c.unique_id() == classes[0].unique_id())
+                                    },
+                                );
+                            let label = if continues_block {
+                                String::new()
+                            } else {
+                                format!("{}{}", classes[0].subject_code, classes[0].course_number)
+                            };
+                            (
+                                shaded_label(&label, day_col_width as usize),
+                                Style::default()
+                                    .fg(theme.muted_color)
+                                    .add_modifier(Modifier::ITALIC),
+                            )
+                        } else if free_slots.contains(&block) {
+                            (
+                                "·".repeat(day_col_width as usize),
+                                Style::default().fg(theme.success_color),
+                            )
+                        } else {
+                            (" ".to_string(), Style::default())
+                        };
+
+                    // show selection indicator for blocks without a class of this schedule
+                    if is_selected {
+                        style = style
                             .fg(theme.selected_color)
-                            .bg(theme.background_color)
-                            .add_modifier(Modifier::BOLD | Modifier::REVERSED)
-                    } else {
-                        Style::default()
-                            .fg(theme.info_color)
-                            .bg(theme.background_color)
-                            .add_modifier(Modifier::BOLD)
-                    };
-
-                    let block_para = Paragraph::new(display_text.as_str())
-                        .style(style)
-                        .alignment(Alignment::Center);
-                    frame.render_widget(block_para, block_area);
-                } else if is_selected {
-                    // show selection indicator for empty blocks
-                    let style = Style::default()
-                        .fg(theme.selected_color)
-                        .add_modifier(Modifier::REVERSED);
-                    let block_para = Paragraph::new(" ").style(style);
-                    frame.render_widget(block_para, block_area);
+                            .add_modifier(Modifier::REVERSED);
+                    }
+                    if is_selected || !display_text.trim().is_empty() {
+                        let block_para = Paragraph::new(display_text).style(style);
+                        frame.render_widget(block_para, block_area);
+                    }
                 }
             }
         }
@@ -1142,19 +2511,69 @@ impl ScheduleWidget {
                 height: 1,
             };
 
-            // if viewing from saved schedules, show saved schedule index instead
-            let counter_text = if let (Some(saved_idx), Some(total_saved)) =
-                (saved_schedule_index, total_saved_schedules)
-            {
-                format!("Schedule {} of {}", saved_idx + 1, total_saved)
-            } else {
-                format!("Schedule {} of {}", current_index + 1, total_schedules)
-            };
+            let counter_text = self.counter_text(
+                schedule,
+                current_index,
+                saved_schedule_index,
+                total_saved_schedules,
+            );
             let counter_para = Paragraph::new(counter_text)
                 .style(Style::default().fg(theme.info_color))
                 .alignment(Alignment::Center);
             frame.render_widget(counter_para, counter_area);
         }
+
+        // render legend of course colors (and the overlay) below the counter
+        let legend_y = counter_y + 1;
+        if legend_y < frame.area().height && (!course_colors.is_empty() || self.overlay.is_some()) {
+            let mut courses: Vec<(&String, &Color)> = course_colors.iter().collect();
+            courses.sort_by_key(|(key, _)| key.as_str());
+
+            let mut legend_spans = Vec::new();
+            for (idx, (key, color)) in courses.into_iter().enumerate() {
+                if idx > 0 {
+                    legend_spans.push(Span::raw("  "));
+                }
+                legend_spans.push(Span::styled("■ ", Style::default().fg(*color)));
+                legend_spans.push(Span::styled(
+                    key.clone(),
+                    Style::default().fg(theme.text_color),
+                ));
+            }
+            if let Some(overlay) = &self.overlay {
+                if !legend_spans.is_empty() {
+                    legend_spans.push(Span::styled(
+                        "  │  ",
+                        Style::default().fg(theme.muted_color),
+                    ));
+                }
+                legend_spans.push(Span::styled(
+                    format!("░ {}", overlay.name),
+                    Style::default()
+                        .fg(theme.muted_color)
+                        .add_modifier(Modifier::ITALIC),
+                ));
+                legend_spans.push(Span::styled(
+                    "  · Free together",
+                    Style::default().fg(theme.success_color),
+                ));
+                if has_shared_sections {
+                    legend_spans.push(Span::styled(
+                        "  * Shared section",
+                        Style::default().fg(theme.text_color),
+                    ));
+                }
+            }
+
+            let legend_area = Rect {
+                x: calendar_area.x,
+                y: legend_y,
+                width: calendar_area.width,
+                height: 1,
+            };
+            let legend_para = Paragraph::new(Line::from(legend_spans)).alignment(Alignment::Center);
+            frame.render_widget(legend_para, legend_area);
+        }
     }
 
     /// Render empty schedule section
@@ -1242,7 +2661,8 @@ impl Widget for ScheduleWidget {
 /// --- ---
 /// schedule -> the schedule classes
 /// day -> day index (0-6 for Mon-Sun)
-/// slot -> time slot index (0-23 for 8am-8pm in 30-min intervals)
+/// slot_start_minutes -> start of the time slot in minutes since midnight
+/// slot_minutes -> length of the time slot in minutes
 /// --- ---
 ///
 /// Returns:
@@ -1250,425 +2670,834 @@ impl Widget for ScheduleWidget {
 /// Option<&Class> -> the class at that time block, if any
 /// --- ---
 ///
-pub fn find_class_at_time_block(schedule: &[Class], day: usize, slot: usize) -> Option<&Class> {
-    let day_codes = vec!["M", "T", "W", "TH", "F", "S", "SU"];
-    let day_code = day_codes.get(day)?;
-
-    // time slot: 0-28 represents 8am-10:30pm in 30-minute intervals
-    // slot 0 = 8:00am = 16 half-hours = 480 minutes
-    let slot_start_minutes = ((16 + slot) * 30) as i32;
-    let slot_end_minutes = slot_start_minutes + 30;
-
-    for class in schedule {
-        if let Some(meeting_times_str) = &class.meeting_times {
-            if !meeting_times_str.is_empty() {
-                let meetings = parse_meeting_times(meeting_times_str);
-                for (days, start_minutes, end_minutes) in meetings {
-                    if days.contains(&day_code.to_string()) {
-                        // check if meeting overlaps with this time slot
-                        if slot_start_minutes < end_minutes && slot_end_minutes > start_minutes {
-                            return Some(class);
-                        }
-                    }
-                }
-            }
-        }
-    }
-    None
+pub fn find_class_at_time_block<C: Borrow<Class>>(
+    schedule: &[C],
+    day: usize,
+    slot_start_minutes: i32,
+    slot_minutes: i32,
+) -> Option<&Class> {
+    let day_code = DAY_CODES.get(day)?;
+
+    let slot_end_minutes = slot_start_minutes + slot_minutes;
+
+    classes(schedule).find(|class| {
+        class.meeting_times.iter().any(|meeting| {
+            // check if meeting overlaps with this time slot
+            meeting.meets_on(day_code)
+                && slot_start_minutes < meeting.end_minutes
+                && slot_end_minutes > meeting.start_minutes
+        })
+    })
 }
 
-/// Generate all possible non-conflicting schedules from classes in the cart
+/// Collect the meetings of a schedule on a single day, ordered by start time
 ///
 /// Arguments:
 /// --- ---
-/// cart_classes -> map of all classes in the cart (ID -> Class)
-/// selected_for_schedule -> set of class IDs selected for schedule generation
-/// allow_conflicts -> whether to allow conflicting schedules
+/// schedule -> the schedule classes
+/// day -> day index (0-6 for Mon-Sun)
 /// --- ---
 ///
 /// Returns:
 /// --- ---
-/// Vec<Vec<Class>> -> all valid schedule combinations
+/// Vec<(i32, i32, &Class)> -> (start_minutes, end_minutes, class) for each meeting that day
 /// --- ---
 ///
-pub fn generate_schedules(
-    cart_classes: &HashMap<String, Class>,
-    selected_for_schedule: &HashSet<String>,
-    allow_conflicts: bool,
-) -> Vec<Vec<Class>> {
-    // get all classes from selected_for_schedule
-    let selected_classes: Vec<Class> = selected_for_schedule
-        .iter()
-        .filter_map(|class_id| cart_classes.get(class_id))
-        .cloned()
-        .collect();
-
-    if selected_classes.is_empty() {
+pub fn day_agenda<C: Borrow<Class>>(schedule: &[C], day: usize) -> Vec<(i32, i32, &Class)> {
+    let Some(day_code) = DAY_CODES.get(day) else {
         return Vec::new();
-    }
+    };
 
-    if allow_conflicts {
-        // generate all possible combinations including conflicts
-        generate_all_schedules(&selected_classes)
-    } else {
-        // generate all possible combinations and filter out conflicts
-        find_valid_schedules(&selected_classes)
+    let mut agenda = Vec::new();
+    for class in classes(schedule) {
+        for meeting in &class.meeting_times {
+            if meeting.meets_on(day_code) {
+                agenda.push((meeting.start_minutes, meeting.end_minutes, class));
+            }
+        }
     }
+    agenda.sort_by_key(|(start_minutes, _, _)| *start_minutes);
+    agenda
 }
 
-/// Find all conflicting class pairs
+/// Total the fees of a schedule's classes
 ///
 /// Arguments:
 /// --- ---
-/// classes -> list of classes to check
+/// schedule -> the schedule classes
 /// --- ---
 ///
 /// Returns:
 /// --- ---
-/// Vec<(String, String)> -> list of (class1_id, class2_id) pairs that conflict
+/// f64 -> the sum of the classes' fees in dollars, 0 if none charge one
 /// --- ---
 ///
-pub fn find_conflicting_classes(classes: &[Class]) -> Vec<(String, String)> {
-    let mut conflicts = Vec::new();
-    for i in 0..classes.len() {
-        for j in (i + 1)..classes.len() {
-            if classes_conflict(&classes[i], &classes[j]) {
-                let class1_id = format!(
-                    "{} {}-{}",
-                    classes[i].subject_code, classes[i].course_number, classes[i].section_sequence
-                );
-                let class2_id = format!(
-                    "{} {}-{}",
-                    classes[j].subject_code, classes[j].course_number, classes[j].section_sequence
-                );
-                conflicts.push((class1_id, class2_id));
-            }
-        }
-    }
-    conflicts
+pub fn total_fees<C: Borrow<Class>>(schedule: &[C]) -> f64 {
+    classes(schedule).filter_map(|class| class.fee).sum()
 }
 
-/// Generate all possible schedules from classes (including conflicting ones)
+/// Collect the final exams of a schedule by date
 ///
 /// Arguments:
 /// --- ---
-/// classes -> list of classes to generate schedules from
+/// schedule -> the schedule classes
 /// --- ---
 ///
 /// Returns:
 /// --- ---
-/// Vec<Vec<Class>> -> all schedule combinations (including conflicts)
+/// Vec<(Date, Vec<(FinalExam, &Class)>)> -> each exam date in order, with its exams ordered
+///                                          by start time; classes without a final are left out
 /// --- ---
 ///
-fn generate_all_schedules(classes: &[Class]) -> Vec<Vec<Class>> {
-    let mut all_schedules = Vec::new();
+pub fn finals_by_date<C: Borrow<Class>>(schedule: &[C]) -> Vec<(Date, Vec<(FinalExam, &Class)>)> {
+    let mut exams: Vec<(FinalExam, &Class)> = classes(schedule)
+        .filter_map(|class| class.final_exam.map(|exam| (exam, class)))
+        .collect();
+    exams.sort_by_key(|(exam, _)| (exam.date, exam.start_minutes));
 
-    // use backtracking to generate all combinations (without conflict checking)
-    fn backtrack(
-        classes: &[Class],
-        current_schedule: &mut Vec<Class>,
-        index: usize,
-        all_schedules: &mut Vec<Vec<Class>>,
-    ) {
-        if index >= classes.len() {
-            // we've considered all classes
-            if !current_schedule.is_empty() {
-                all_schedules.push(current_schedule.clone());
-            }
-            return;
+    let mut days: Vec<(Date, Vec<(FinalExam, &Class)>)> = Vec::new();
+    for (exam, class) in exams {
+        match days.last_mut() {
+            Some((date, day_exams)) if *date == exam.date => day_exams.push((exam, class)),
+            _ => days.push((exam.date, vec![(exam, class)])),
         }
-
-        // try adding current class (no conflict check)
-        current_schedule.push(classes[index].clone());
-        backtrack(classes, current_schedule, index + 1, all_schedules);
-        current_schedule.pop();
-
-        // try without adding current class
-        backtrack(classes, current_schedule, index + 1, all_schedules);
     }
-
-    let mut current = Vec::new();
-    backtrack(classes, &mut current, 0, &mut all_schedules);
-
-    all_schedules
+    days
 }
 
-/// Find all valid (non-conflicting) schedules from a list of classes
+/// Find the free time two schedules share on a single day
 ///
 /// Arguments:
 /// --- ---
-/// classes -> list of classes to generate schedules from
+/// schedule -> the first schedule's classes
+/// other -> the second schedule's classes
+/// day -> day index (0-6 for Mon-Sun)
+/// min_minutes -> shortest gap that counts
 /// --- ---
 ///
 /// Returns:
 /// --- ---
-/// Vec<Vec<Class>> -> all valid schedule combinations
+/// Vec<(i32, i32)> -> (start_minutes, end_minutes) of each gap, in time order
 /// --- ---
 ///
-fn find_valid_schedules(classes: &[Class]) -> Vec<Vec<Class>> {
-    let mut all_valid_schedules = Vec::new();
-
-    // use backtracking to generate all valid combinations
-    fn backtrack(
-        classes: &[Class],
-        current_schedule: &mut Vec<Class>,
-        index: usize,
-        valid_schedules: &mut Vec<Vec<Class>>,
-    ) {
-        if index >= classes.len() {
-            // we've considered all classes
-            if !current_schedule.is_empty() {
-                valid_schedules.push(current_schedule.clone());
-            }
-            return;
-        }
-
-        // try adding current class
-        let current_class = &classes[index];
-        let mut can_add = true;
-
-        // check for conflicts with existing classes in schedule
-        for existing_class in current_schedule.iter() {
-            if classes_conflict(current_class, existing_class) {
-                can_add = false;
-                break;
+/// Only gaps between the day's first and last class of either schedule count, so the blocks
+/// are the times both people are free while already on campus.
+pub fn shared_free_blocks<A: Borrow<Class>, B: Borrow<Class>>(
+    schedule: &[A],
+    other: &[B],
+    day: usize,
+    min_minutes: i32,
+) -> Vec<(i32, i32)> {
+    let mut busy: Vec<(i32, i32)> = day_agenda(schedule, day)
+        .into_iter()
+        .chain(day_agenda(other, day))
+        .map(|(start_minutes, end_minutes, _)| (start_minutes, end_minutes))
+        .collect();
+    busy.sort_unstable();
+
+    let mut free = Vec::new();
+    let mut busy_until: Option<i32> = None;
+    for (start_minutes, end_minutes) in busy {
+        if let Some(until) = busy_until {
+            if start_minutes - until >= min_minutes {
+                free.push((until, start_minutes));
             }
         }
-
-        if can_add {
-            current_schedule.push(current_class.clone());
-            backtrack(classes, current_schedule, index + 1, valid_schedules);
-            current_schedule.pop();
-        }
-
-        // try without adding current class
-        backtrack(classes, current_schedule, index + 1, valid_schedules);
+        busy_until = Some(busy_until.map_or(end_minutes, |until| until.max(end_minutes)));
     }
-
-    let mut current = Vec::new();
-    backtrack(classes, &mut current, 0, &mut all_valid_schedules);
-
-    // filter to keep only maximal schedules (schedules that are not subsets of other schedules)
-    filter_maximal_schedules(&all_valid_schedules)
+    free
 }
 
-/// Filter schedules to keep only maximal ones (remove schedules that are subsets of others)
+/// Map each grid block to the classes meeting in it
 ///
 /// Arguments:
 /// --- ---
-/// schedules -> all valid schedules
+/// schedule -> the schedule classes
+/// slot_starts -> start of each grid slot in minutes since midnight
+/// slot_minutes -> length of each slot in minutes
 /// --- ---
 ///
 /// Returns:
 /// --- ---
-/// Vec<Vec<Class>> -> only maximal schedules
+/// HashMap<(usize, usize), Vec<&Class>> -> (day index, slot index) -> classes, each once
 /// --- ---
 ///
-fn filter_maximal_schedules(schedules: &[Vec<Class>]) -> Vec<Vec<Class>> {
-    let mut maximal_schedules = Vec::new();
-
-    for schedule in schedules {
-        let schedule_ids: HashSet<String> = schedule.iter().map(|c| c.unique_id()).collect();
-
-        // check if this schedule is a subset of any other schedule
-        let is_subset = schedules.iter().any(|other_schedule| {
-            if other_schedule.len() <= schedule.len() {
-                return false; // can't be a subset if other is same size or smaller
+fn slot_blocks<'a, C: Borrow<Class>>(
+    schedule: &'a [C],
+    slot_starts: &[i32],
+    slot_minutes: i32,
+) -> HashMap<(usize, usize), Vec<&'a Class>> {
+    let mut blocks: HashMap<(usize, usize), Vec<&Class>> = HashMap::new();
+    for class in classes(schedule) {
+        for meeting in &class.meeting_times {
+            for day_code in &meeting.days {
+                let Some(day_idx) = DAY_CODES.iter().position(|&d| d == day_code) else {
+                    continue;
+                };
+                // find time slots that overlap with this meeting
+                for (slot_idx, slot_start) in slot_starts.iter().enumerate() {
+                    if *slot_start < meeting.end_minutes
+                        && slot_start + slot_minutes > meeting.start_minutes
+                    {
+                        let classes = blocks.entry((day_idx, slot_idx)).or_default();
+                        // keep each class once even if it has several meetings
+                        if !classes.iter().any(|c| c.unique_id() == class.unique_id()) {
+                            classes.push(class);
+                        }
+                    }
+                }
             }
-            let other_ids: HashSet<String> = other_schedule.iter().map(|c| c.unique_id()).collect();
-            // this schedule is a subset if all its classes are in the other schedule
-            schedule_ids.is_subset(&other_ids)
-        });
-
-        // only keep if it's not a subset (i.e., it's maximal)
-        if !is_subset {
-            maximal_schedules.push(schedule.clone());
         }
     }
-
-    maximal_schedules
+    blocks
 }
 
-/// Check if two classes conflict (overlap in time)
+/// Center a label in a shaded grid cell
 ///
 /// Arguments:
 /// --- ---
-/// class1 -> first class
-/// class2 -> second class
+/// label -> the label (may be empty)
+/// width -> width of the cell
 /// --- ---
 ///
 /// Returns:
 /// --- ---
-/// bool -> true if classes conflict, false otherwise
+/// String -> the label padded with shading to the cell's width
 /// --- ---
 ///
-fn classes_conflict(class1: &Class, class2: &Class) -> bool {
-    // if either class has no meeting times, they don't conflict
-    let times1 = match &class1.meeting_times {
-        Some(t) if !t.is_empty() => t,
-        _ => return false,
-    };
-    let times2 = match &class2.meeting_times {
-        Some(t) if !t.is_empty() => t,
-        _ => return false,
-    };
-
-    // parse meeting times for both classes
-    let meetings1 = parse_meeting_times(times1);
-    let meetings2 = parse_meeting_times(times2);
-
-    // check for any overlap
-    for m1 in &meetings1 {
-        for m2 in &meetings2 {
-            if meetings_overlap(m1, m2) {
-                return true;
-            }
-        }
-    }
-
-    false
+fn shaded_label(label: &str, width: usize) -> String {
+    let label: String = label.chars().take(width).collect();
+    let padding = width - label.chars().count();
+    format!(
+        "{}{}{}",
+        "░".repeat(padding / 2),
+        label,
+        "░".repeat(padding - padding / 2)
+    )
 }
 
-/// Parse meeting times string into structured format
+/// Get the key used to group sections of the same course
 ///
 /// Arguments:
 /// --- ---
-/// times_str -> meeting times string (e.g., "M:08:00:00-10:45:00|TH:08:00:00-09:15:00")
+/// class -> the class
 /// --- ---
 ///
 /// Returns:
 /// --- ---
-/// Vec<(Vec<String>, i32, i32)> -> list of (days, start_minutes, end_minutes)
+/// String -> subject code and course number (e.g., "CMPT 220L")
 /// --- ---
 ///
-fn parse_meeting_times(times_str: &str) -> Vec<(Vec<String>, i32, i32)> {
-    let mut meetings = Vec::new();
-
-    for mt in times_str.split('|') {
-        if mt.is_empty() {
-            continue;
-        }
-
-        if let Some(colon_pos) = mt.find(':') {
-            let days_part = &mt[..colon_pos];
-            let time_part = &mt[colon_pos + 1..];
-
-            if let Some(dash_pos) = time_part.find('-') {
-                let start_str = &time_part[..dash_pos];
-                let end_str = &time_part[dash_pos + 1..];
-
-                let start_minutes = time_to_minutes(start_str);
-                let end_minutes = time_to_minutes(end_str);
-
-                // parse days (handle "MW", "TTH", etc.)
-                let days = parse_days(days_part);
+fn course_key(class: &Class) -> String {
+    format!("{} {}", class.subject_code, class.course_number)
+}
 
-                if !days.is_empty() && start_minutes > 0 && end_minutes > start_minutes {
-                    meetings.push((days, start_minutes, end_minutes));
-                }
-            }
-        }
+/// Assign each course in a schedule a stable color from a palette
+///
+/// Arguments:
+/// --- ---
+/// schedule -> the schedule classes
+/// palette -> the colors to choose from
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// HashMap<String, Color> -> course key -> assigned color
+/// --- ---
+///
+/// Each course starts from a color picked by hashing its key, so the same course keeps its
+/// color across schedules; collisions within a schedule move to the next free color.
+fn course_colors<C: Borrow<Class>>(schedule: &[C], palette: &[Color]) -> HashMap<String, Color> {
+    let mut keys: Vec<String> = classes(schedule).map(course_key).collect();
+    keys.sort();
+    keys.dedup();
+
+    let mut used = vec![false; palette.len()];
+    let mut colors = HashMap::new();
+    for key in keys {
+        // simple FNV-1a hash so colors don't depend on std's hasher
+        let hash = key.bytes().fold(0xcbf29ce484222325_u64, |h, b| {
+            (h ^ b as u64).wrapping_mul(0x100000001b3)
+        });
+        let preferred = (hash % palette.len() as u64) as usize;
+        let idx = (0..palette.len())
+            .map(|offset| (preferred + offset) % palette.len())
+            .find(|&i| !used[i])
+            .unwrap_or(preferred);
+        used[idx] = true;
+        colors.insert(key, palette[idx]);
+    }
+    colors
+}
+
+/// Generate all possible non-conflicting schedules from classes in the cart
+///
+/// Arguments:
+/// --- ---
+/// cart_classes -> map of all classes in the cart (ID -> Class)
+/// selected_for_schedule -> set of class IDs selected for schedule generation
+/// allow_conflicts -> whether to allow conflicting schedules
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Vec<Vec<Arc<Class>>> -> all valid schedule combinations, sharing the cart's classes
+/// --- ---
+///
+pub fn generate_schedules(
+    cart_classes: &HashMap<String, Arc<Class>>,
+    selected_for_schedule: &HashSet<String>,
+    allow_conflicts: bool,
+) -> Vec<Vec<Arc<Class>>> {
+    let selected_classes = selected_cart_classes(cart_classes, selected_for_schedule);
+    if selected_classes.is_empty() {
+        return Vec::new();
     }
 
-    meetings
+    ScheduleGenerator::new(selected_classes, allow_conflicts).collect()
 }
 
-/// Parse day codes into individual days
+/// Generate all schedules that take exactly one section of each selected course
 ///
 /// Arguments:
 /// --- ---
-/// days_str -> day string (e.g., "MW", "TTH")
+/// cart_classes -> map of all classes in the cart (ID -> Class)
+/// selected_for_schedule -> set of class IDs selected for schedule generation
 /// --- ---
 ///
 /// Returns:
 /// --- ---
-/// Vec<String> -> list of day codes
+/// Vec<Vec<Arc<Class>>> -> every non-conflicting choice of one section per course, sharing
+///                         the cart's classes
 /// --- ---
 ///
-fn parse_days(days_str: &str) -> Vec<String> {
-    let mut days = Vec::new();
-    let mut i = 0;
-    let chars: Vec<char> = days_str.chars().collect();
+pub fn generate_course_schedules(
+    cart_classes: &HashMap<String, Arc<Class>>,
+    selected_for_schedule: &HashSet<String>,
+) -> Vec<Vec<Arc<Class>>> {
+    let selected_classes = selected_cart_classes(cart_classes, selected_for_schedule);
+    if selected_classes.is_empty() {
+        return Vec::new();
+    }
 
-    while i < chars.len() {
-        if i + 1 < chars.len() {
-            let two_char = format!("{}{}", chars[i], chars[i + 1]);
-            match two_char.as_str() {
-                "TH" => {
-                    days.push("TH".to_string());
-                    i += 2;
-                    continue;
+    let conflicts = conflict_matrix(&selected_classes);
+    ScheduleGenerator::one_per_course(selected_classes, conflicts).collect()
+}
+
+/// Get the selected cart classes in a deterministic order
+///
+/// Arguments:
+/// --- ---
+/// cart_classes -> map of all classes in the cart (ID -> Class)
+/// selected_for_schedule -> set of class IDs selected for schedule generation
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Vec<Arc<Class>> -> selected classes sorted by ID, so generation order is stable between
+///                    runs
+/// --- ---
+///
+fn selected_cart_classes(
+    cart_classes: &HashMap<String, Arc<Class>>,
+    selected_for_schedule: &HashSet<String>,
+) -> Vec<Arc<Class>> {
+    let mut selected_ids: Vec<&String> = selected_for_schedule.iter().collect();
+    selected_ids.sort();
+    selected_ids
+        .into_iter()
+        .filter_map(|class_id| cart_classes.get(class_id))
+        .cloned()
+        .collect()
+}
+
+/// Lazy generator of schedule combinations
+///
+/// Walks the include/exclude decision tree depth-first with an explicit stack so schedules
+/// can be produced on demand instead of materializing every combination up front. Search
+/// states hold class indices and schedules hold shared classes, so a large cart isn't copied
+/// into every schedule. With courses set, each step instead picks one section of the next
+/// course, so every schedule has all of them.
+///
+/// Fields:
+/// --- ---
+/// classes -> classes to build schedules from
+/// conflicts -> memoized pairwise conflict matrix (conflicts[i][j] -> classes i and j overlap)
+/// allow_conflicts -> whether conflicting classes may share a schedule
+/// courses -> indices of each course's sections when picking one section per course
+/// stack -> pending (next class or course index, chosen class indices) search states
+/// --- ---
+///
+pub struct ScheduleGenerator {
+    classes: Vec<Arc<Class>>,
+    conflicts: Vec<Vec<bool>>,
+    allow_conflicts: bool,
+    courses: Option<Vec<Vec<usize>>>,
+    stack: Vec<(usize, Vec<usize>)>,
+}
+
+impl ScheduleGenerator {
+    /// Create a new ScheduleGenerator
+    ///
+    /// Arguments:
+    /// --- ---
+    /// classes -> classes to build schedules from
+    /// allow_conflicts -> whether to allow conflicting schedules
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// ScheduleGenerator -> generator positioned before the first schedule
+    /// --- ---
+    ///
+    pub fn new(classes: Vec<Arc<Class>>, allow_conflicts: bool) -> Self {
+        if allow_conflicts {
+            return Self {
+                classes,
+                conflicts: Vec::new(),
+                allow_conflicts,
+                courses: None,
+                stack: vec![(0, Vec::new())],
+            };
+        }
+        let conflicts = conflict_matrix(&classes);
+        Self::with_conflicts(classes, conflicts)
+    }
+
+    /// Create a ScheduleGenerator from an already built conflict matrix
+    ///
+    /// Arguments:
+    /// --- ---
+    /// classes -> classes to build schedules from
+    /// conflicts -> matrix where [i][j] is true if classes i and j overlap (e.g., from the
+    ///              cart's ConflictGraph)
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// ScheduleGenerator -> generator that skips conflicting schedules, positioned before the
+    ///                      first one
+    /// --- ---
+    ///
+    pub fn with_conflicts(classes: Vec<Arc<Class>>, conflicts: Vec<Vec<bool>>) -> Self {
+        Self {
+            classes,
+            conflicts,
+            allow_conflicts: false,
+            courses: None,
+            stack: vec![(0, Vec::new())],
+        }
+    }
+
+    /// Create a ScheduleGenerator that picks exactly one section of each course
+    ///
+    /// Arguments:
+    /// --- ---
+    /// classes -> sections to build schedules from, grouped into courses by subject and
+    ///            course number
+    /// conflicts -> matrix where [i][j] is true if classes i and j overlap
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// ScheduleGenerator -> generator of non-conflicting schedules with one section of every
+    ///                      course, in the order the courses first appear
+    /// --- ---
+    ///
+    pub fn one_per_course(classes: Vec<Arc<Class>>, conflicts: Vec<Vec<bool>>) -> Self {
+        let mut course_order: Vec<String> = Vec::new();
+        let mut courses: Vec<Vec<usize>> = Vec::new();
+        for (idx, class) in classes.iter().enumerate() {
+            let key = course_key(class);
+            match course_order.iter().position(|course| *course == key) {
+                Some(course) => courses[course].push(idx),
+                None => {
+                    course_order.push(key);
+                    courses.push(vec![idx]);
                 }
-                "SU" => {
-                    days.push("SU".to_string());
-                    i += 2;
-                    continue;
+            }
+        }
+        Self {
+            classes,
+            conflicts,
+            allow_conflicts: false,
+            courses: Some(courses),
+            stack: vec![(0, Vec::new())],
+        }
+    }
+
+    /// Check whether a finished combination is maximal
+    ///
+    /// Arguments:
+    /// --- ---
+    /// chosen -> indices of the classes in the combination
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// bool -> true if no left-out class could be added without a conflict
+    /// --- ---
+    ///
+    fn is_maximal(&self, chosen: &[usize]) -> bool {
+        (0..self.classes.len())
+            .filter(|idx| !chosen.contains(idx))
+            .all(|idx| chosen.iter().any(|&c| self.conflicts[idx][c]))
+    }
+
+    /// Produce the next schedule with one section of each course
+    ///
+    /// Arguments: None
+    ///
+    /// Returns:
+    /// --- ---
+    /// Option<Vec<Arc<Class>>> -> the next schedule, or None when every choice has been
+    ///                            visited
+    /// --- ---
+    ///
+    fn next_per_course(&mut self) -> Option<Vec<Arc<Class>>> {
+        let courses = self.courses.as_ref()?;
+        if courses.is_empty() {
+            return None;
+        }
+        while let Some((course, chosen)) = self.stack.pop() {
+            if course >= courses.len() {
+                return Some(
+                    chosen
+                        .iter()
+                        .map(|&i| Arc::clone(&self.classes[i]))
+                        .collect(),
+                );
+            }
+
+            // push in reverse so the course's first section is explored first
+            for &section in courses[course].iter().rev() {
+                if chosen.iter().all(|&c| !self.conflicts[section][c]) {
+                    let mut with_section = chosen.clone();
+                    with_section.push(section);
+                    self.stack.push((course + 1, with_section));
                 }
-                _ => {}
             }
         }
+        None
+    }
+}
+
+impl Iterator for ScheduleGenerator {
+    type Item = Vec<Arc<Class>>;
+
+    /// Produce the next schedule, or None when every combination has been visited
+    fn next(&mut self) -> Option<Vec<Arc<Class>>> {
+        if self.courses.is_some() {
+            return self.next_per_course();
+        }
+        while let Some((index, chosen)) = self.stack.pop() {
+            if index >= self.classes.len() {
+                // we've considered all classes; with conflict checking, only keep maximal
+                // schedules (ones that aren't a subset of another valid schedule)
+                if !chosen.is_empty() && (self.allow_conflicts || self.is_maximal(&chosen)) {
+                    return Some(
+                        chosen
+                            .iter()
+                            .map(|&i| Arc::clone(&self.classes[i]))
+                            .collect(),
+                    );
+                }
+                continue;
+            }
+
+            // push "without" first so the "with" branch is explored first
+            self.stack.push((index + 1, chosen.clone()));
 
-        match chars[i] {
-            'M' => days.push("M".to_string()),
-            'T' => days.push("T".to_string()),
-            'W' => days.push("W".to_string()),
-            'F' => days.push("F".to_string()),
-            'S' => days.push("S".to_string()),
-            _ => {}
+            let can_add = self.allow_conflicts || chosen.iter().all(|&c| !self.conflicts[index][c]);
+            if can_add {
+                let mut with_class = chosen;
+                with_class.push(index);
+                self.stack.push((index + 1, with_class));
+            }
         }
-        i += 1;
+        None
     }
+}
 
-    days
+/// Build the pairwise conflict matrix for a list of classes
+///
+/// Arguments:
+/// --- ---
+/// classes -> list of classes to check
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Vec<Vec<bool>> -> matrix where [i][j] is true if classes i and j overlap
+/// --- ---
+///
+/// Each pair is checked once up front rather than on every backtracking step.
+fn conflict_matrix(classes: &[Arc<Class>]) -> Vec<Vec<bool>> {
+    let mut conflicts = vec![vec![false; classes.len()]; classes.len()];
+    for i in 0..classes.len() {
+        for j in (i + 1)..classes.len() {
+            let overlap = classes_conflict(&classes[i], &classes[j]);
+            conflicts[i][j] = overlap;
+            conflicts[j][i] = overlap;
+        }
+    }
+    conflicts
+}
+
+/// Pairwise meeting conflicts between the classes in the cart
+///
+/// Built in the background whenever the cart changes (see
+/// ScheduleWidget::refresh_conflict_graph). Classes are matched by the shared Arc rather
+/// than by ID, so a graph built from an older cart is never used for a class it didn't see.
+///
+/// Fields:
+/// --- ---
+/// classes -> the cart's classes when the graph was built, sorted by ID
+/// conflicts -> matrix where [i][j] is true if classes i and j overlap
+/// --- ---
+///
+pub struct ConflictGraph {
+    classes: Vec<Arc<Class>>,
+    conflicts: Vec<Vec<bool>>,
+}
+
+impl ConflictGraph {
+    /// Build the conflict graph for a set of classes
+    ///
+    /// Arguments:
+    /// --- ---
+    /// classes -> the cart's classes
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// ConflictGraph -> graph with every pair checked
+    /// --- ---
+    ///
+    pub fn new(mut classes: Vec<Arc<Class>>) -> Self {
+        classes.sort_by_key(|class| class.unique_id());
+        let conflicts = conflict_matrix(&classes);
+        Self { classes, conflicts }
+    }
+
+    /// Get the conflict matrix for some of the graph's classes
+    ///
+    /// Arguments:
+    /// --- ---
+    /// classes -> classes to check, in the order the matrix should use
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Option<Vec<Vec<bool>>> -> matrix where [i][j] is true if classes i and j overlap, None
+    ///                           if a class isn't in the graph
+    /// --- ---
+    ///
+    pub fn matrix_for(&self, classes: &[Arc<Class>]) -> Option<Vec<Vec<bool>>> {
+        let indices: Vec<usize> = classes
+            .iter()
+            .map(|class| {
+                self.classes
+                    .iter()
+                    .position(|known| Arc::ptr_eq(known, class))
+            })
+            .collect::<Option<_>>()?;
+        Some(
+            indices
+                .iter()
+                .map(|&i| indices.iter().map(|&j| self.conflicts[i][j]).collect())
+                .collect(),
+        )
+    }
+
+    /// Find all conflicting pairs among some of the graph's classes
+    ///
+    /// Arguments:
+    /// --- ---
+    /// classes -> classes to check
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Option<Vec<(String, String)>> -> list of (class1_id, class2_id) pairs that conflict,
+    ///                                  None if a class isn't in the graph
+    /// --- ---
+    ///
+    pub fn conflicting_pairs(&self, classes: &[Arc<Class>]) -> Option<Vec<(String, String)>> {
+        self.matrix_for(classes)
+            .map(|conflicts| conflicting_pairs(classes, &conflicts))
+    }
+
+    /// Find the classes that conflict with at least one of the others
+    ///
+    /// Arguments:
+    /// --- ---
+    /// classes -> classes to check
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Option<HashSet<String>> -> unique IDs of the conflicting classes, None if a class
+    ///                            isn't in the graph
+    /// --- ---
+    ///
+    pub fn conflicted_ids(&self, classes: &[Arc<Class>]) -> Option<HashSet<String>> {
+        let conflicts = self.matrix_for(classes)?;
+        Some(
+            classes
+                .iter()
+                .zip(&conflicts)
+                .filter(|(_, row)| row.iter().any(|&overlap| overlap))
+                .map(|(class, _)| class.unique_id())
+                .collect(),
+        )
+    }
+}
+
+/// List the conflicting pairs of a conflict matrix
+///
+/// Arguments:
+/// --- ---
+/// classes -> the classes the matrix was built for
+/// conflicts -> matrix where [i][j] is true if classes i and j overlap
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Vec<(String, String)> -> list of (class1_id, class2_id) pairs that conflict
+/// --- ---
+///
+fn conflicting_pairs(classes: &[Arc<Class>], conflicts: &[Vec<bool>]) -> Vec<(String, String)> {
+    let mut pairs = Vec::new();
+    for i in 0..classes.len() {
+        for j in (i + 1)..classes.len() {
+            if conflicts[i][j] {
+                pairs.push((display_id(&classes[i]), display_id(&classes[j])));
+            }
+        }
+    }
+    pairs
 }
 
-/// Convert time string (HH:MM:SS) to minutes since midnight
+/// Find all conflicting class pairs
 ///
 /// Arguments:
 /// --- ---
-/// time_str -> time string
+/// classes -> list of classes to check
 /// --- ---
 ///
 /// Returns:
 /// --- ---
-/// i32 -> minutes since midnight
+/// Vec<(String, String)> -> list of (class1_id, class2_id) pairs that conflict
 /// --- ---
 ///
-fn time_to_minutes(time_str: &str) -> i32 {
-    let parts: Vec<&str> = time_str.split(':').collect();
-    if parts.len() >= 2 {
-        let hours: i32 = parts[0].parse().unwrap_or(0);
-        let minutes: i32 = parts[1].parse().unwrap_or(0);
-        hours * 60 + minutes
-    } else {
-        0
+pub fn find_conflicting_classes<C: Borrow<Class>>(classes: &[C]) -> Vec<(String, String)> {
+    let classes: Vec<&Class> = classes.iter().map(Borrow::borrow).collect();
+    let mut conflicts = Vec::new();
+    for i in 0..classes.len() {
+        for j in (i + 1)..classes.len() {
+            if classes_conflict(classes[i], classes[j]) {
+                let class1_id = format!(
+                    "{} {}-{}",
+                    classes[i].subject_code, classes[i].course_number, classes[i].section_sequence
+                );
+                let class2_id = format!(
+                    "{} {}-{}",
+                    classes[j].subject_code, classes[j].course_number, classes[j].section_sequence
+                );
+                conflicts.push((class1_id, class2_id));
+            }
+        }
     }
+    conflicts
 }
 
-/// Check if two meetings overlap
+/// Find all class pairs whose final exams overlap
 ///
 /// Arguments:
 /// --- ---
-/// m1 -> first meeting (days, start, end)
-/// m2 -> second meeting (days, start, end)
+/// classes -> list of classes to check
 /// --- ---
 ///
 /// Returns:
 /// --- ---
-/// bool -> true if meetings overlap, false otherwise
+/// Vec<(String, String)> -> list of (class1_id, class2_id) pairs with conflicting finals
 /// --- ---
 ///
-fn meetings_overlap(m1: &(Vec<String>, i32, i32), m2: &(Vec<String>, i32, i32)) -> bool {
-    // check if they share any day
-    let days_overlap = m1.0.iter().any(|d| m2.0.contains(d));
-    if !days_overlap {
-        return false;
+/// Unlike meeting conflicts these don't stop a schedule from being generated, since
+/// registrars usually reschedule one of two finals at the same time on request
+pub fn find_final_conflicts<C: Borrow<Class>>(classes: &[C]) -> Vec<(String, String)> {
+    let classes: Vec<&Class> = classes.iter().map(Borrow::borrow).collect();
+    let mut conflicts = Vec::new();
+    for i in 0..classes.len() {
+        for j in (i + 1)..classes.len() {
+            if let (Some(first), Some(second)) = (classes[i].final_exam, classes[j].final_exam) {
+                if first.overlaps(&second) {
+                    conflicts.push((display_id(classes[i]), display_id(classes[j])));
+                }
+            }
+        }
     }
+    conflicts
+}
 
-    // check if time ranges overlap
-    let (_, start1, end1) = m1;
-    let (_, start2, end2) = m2;
+/// Iterate over the classes of a schedule, whether it holds them or shares them
+///
+/// Arguments:
+/// --- ---
+/// schedule -> the schedule classes (Class, or Arc<Class> shared with the cart)
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// impl Iterator<Item = &Class> -> the classes, in order
+/// --- ---
+///
+fn classes<C: Borrow<Class>>(schedule: &[C]) -> impl Iterator<Item = &Class> {
+    schedule.iter().map(Borrow::borrow)
+}
 
-    // overlap if: start1 < end2 && start2 < end1
-    start1 < end2 && start2 < end1
+/// Format a class's display ID
+///
+/// Arguments:
+/// --- ---
+/// class -> the class
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> subject, course number, and section (e.g., "CMPT 220L-111")
+/// --- ---
+///
+fn display_id(class: &Class) -> String {
+    format!(
+        "{} {}-{}",
+        class.subject_code, class.course_number, class.section_sequence
+    )
+}
+
+/// Check if two classes conflict (overlap in time)
+///
+/// Arguments:
+/// --- ---
+/// class1 -> first class
+/// class2 -> second class
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// bool -> true if classes conflict, false otherwise
+/// --- ---
+///
+fn classes_conflict(class1: &Class, class2: &Class) -> bool {
+    // a first half and a second half section never meet in the same week, even when the
+    // sync gave their session but not their meeting dates
+    if !sql::sessions_overlap(class1.session.as_deref(), class2.session.as_deref()) {
+        return false;
+    }
+    // classes without meeting times never conflict
+    class1
+        .meeting_times
+        .iter()
+        .any(|m1| class2.meeting_times.iter().any(|m2| m1.overlaps(m2)))
 }
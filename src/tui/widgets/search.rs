@@ -2,26 +2,67 @@
 ///
 /// Search widget with encapsulated state, input handling, and rendering
 ///
-/// Handles query input, tab completion, results browsing, and search bar rendering
+/// Handles query input, tab completion, results browsing and filtering, diffing results
+/// with the previous run of the same query, collapsing duplicate sections, going back from
+/// detail view pivots, and search bar rendering
 ///
 /// Contains:
 /// --- ---
 /// SearchWidget -> Widget for search functionality
 /// CompletionState -> State for tab completion dropdown
+/// SnippetStops -> Placeholders left to fill in an accepted snippet
+/// SearchPlace -> Search state saved before a pivot, to go back to
 /// --- ---
-use crate::data::sql::Class;
-use crate::dsl::compiler::{Compiler, CompilerResult};
+use crate::data::bench::format_duration;
+use crate::data::duplicates::{collapse_duplicates, CollapsedResults};
+use crate::data::index_advisor::{explain_search, QueryExplanation};
+use crate::data::profiles::{unconstrain_positions, Profile};
+use crate::data::query_log::{self, get_slow_query_log_path, slow_query_threshold};
+use crate::data::result_diff::{diff_results, ResultDiff, RowChange};
+use crate::data::sql::{execute_query, Class};
+use crate::data::term_index::{ClassFilter, TermIndex};
+use crate::data::warm_cache::WarmCache;
+use crate::dsl::compiler::{Compiler, CompilerResult, QueryTiming};
+use crate::dsl::entities::{self, Completion, CompletionKind, EntityInfo, PLACEHOLDER};
+use crate::dsl::formatter::format_query;
+use crate::tui::error_catalog::diagnostic_header;
 use crate::tui::state::{ErrorType, FocusMode};
 use crate::tui::themes::Theme;
+use crate::tui::widgets::helpers::truncate;
+use crate::tui::widgets::toast::LEXER_TOAST_WIDTH;
 use crate::tui::widgets::traits::{KeyAction, Widget};
+use crate::utils::i18n;
+use crate::utils::text::render_caret;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
-use ratatui::layout::Rect;
+use ratatui::layout::{Position, Rect};
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::widgets::{Block, BorderType, Borders, Clear, Paragraph};
 use ratatui::Frame;
 use std::cell::Cell;
-use std::time::Instant;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+/// Maximum number of classes shown in the live results preview
+const LIVE_PREVIEW_LIMIT: usize = 10;
+
+/// Delay after the last keystroke before the live preview query runs
+const LIVE_PREVIEW_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Maximum number of gone sections named in the diff toast
+const DIFF_GONE_LIMIT: usize = 5;
+
+/// Maximum number of pivots that can be gone back from, the oldest is dropped past it
+const BACK_STACK_LIMIT: usize = 20;
+
+/// How long an example query is suggested in the empty search box before the next one
+const GHOST_EXAMPLE_INTERVAL: Duration = Duration::from_secs(4);
+
+/// Width of the completion popup, narrowed to fit the frame
+const COMPLETION_POPUP_WIDTH: u16 = 96;
+
+/// Widest the suggestion column of the completion popup gets before it is cut short
+const COMPLETION_TEXT_WIDTH: usize = 36;
 
 /// State for tab completion dropdown
 ///
@@ -30,7 +71,7 @@ use std::time::Instant;
 ///
 /// Fields:
 /// --- ---
-/// completions -> List of completion suggestions
+/// completions -> List of completion suggestions, with their kind and description
 /// completion_index -> Currently selected completion index
 /// show_completions -> Whether completion dropdown is visible
 /// partial_word -> The partial word being completed
@@ -38,7 +79,7 @@ use std::time::Instant;
 ///
 #[derive(Debug, Clone)]
 pub struct CompletionState {
-    pub completions: Vec<String>,
+    pub completions: Vec<Completion>,
     pub completion_index: Option<usize>,
     pub show_completions: bool,
     pub partial_word: String,
@@ -62,9 +103,244 @@ impl CompletionState {
     }
 }
 
+/// Placeholders left to fill in a snippet accepted from the completions
+///
+/// Positions are in characters of the search input, like problematic_positions
+///
+/// Fields:
+/// --- ---
+/// stops -> Each placeholder's first character and length, in input order
+/// filled -> Whether each placeholder has been typed into, until then it reads PLACEHOLDER
+/// current -> Index of the placeholder being typed into
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for SnippetStops
+/// Clone -> Clone trait for SnippetStops
+/// --- ---
+///
+#[derive(Debug, Clone)]
+pub struct SnippetStops {
+    stops: Vec<(usize, usize)>,
+    filled: Vec<bool>,
+    pub current: usize,
+}
+
+impl SnippetStops {
+    /// Find the placeholders of a snippet just put in the input
+    ///
+    /// Parameters:
+    /// --- ---
+    /// input -> The search input, ending with the snippet
+    /// start -> The character the snippet starts at
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Option<SnippetStops> -> The placeholders, starting at the first, None if it has none
+    /// --- ---
+    ///
+    pub fn new(input: &str, start: usize) -> Option<Self> {
+        let placeholder_len = PLACEHOLDER.chars().count();
+        let chars: Vec<char> = input.chars().collect();
+        let placeholder: Vec<char> = PLACEHOLDER.chars().collect();
+        let mut stops = Vec::new();
+        let mut i = start;
+        while i + placeholder_len <= chars.len() {
+            if chars[i..i + placeholder_len] == placeholder[..] {
+                stops.push((i, placeholder_len));
+                i += placeholder_len;
+            } else {
+                i += 1;
+            }
+        }
+        if stops.is_empty() {
+            return None;
+        }
+        Some(Self {
+            filled: vec![false; stops.len()],
+            stops,
+            current: 0,
+        })
+    }
+
+    /// Get how many placeholders the snippet has
+    pub fn len(&self) -> usize {
+        self.stops.len()
+    }
+
+    /// Check whether the snippet has no placeholders (never true once created)
+    pub fn is_empty(&self) -> bool {
+        self.stops.is_empty()
+    }
+
+    /// Get where the cursor goes: after what has been typed into the current placeholder
+    pub fn cursor(&self) -> usize {
+        let (start, len) = self.stops[self.current];
+        start + len
+    }
+
+    /// Check whether any placeholder hasn't been typed into yet
+    pub fn has_unfilled(&self) -> bool {
+        self.filled.contains(&false)
+    }
+
+    /// Get the placeholder a character of the input belongs to
+    ///
+    /// Parameters:
+    /// --- ---
+    /// index -> The character's index in the input
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Option<(usize, bool)> -> The placeholder's index and whether it has been typed into
+    /// --- ---
+    ///
+    pub fn stop_at(&self, index: usize) -> Option<(usize, bool)> {
+        self.stops
+            .iter()
+            .position(|&(start, len)| index >= start && index < start + len)
+            .map(|stop| (stop, self.filled[stop]))
+    }
+
+    /// Move to the next placeholder
+    ///
+    /// Returns:
+    /// --- ---
+    /// bool -> false if the current placeholder was the last
+    /// --- ---
+    pub fn next_stop(&mut self) -> bool {
+        if self.current + 1 < self.stops.len() {
+            self.current += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Move to the previous placeholder, staying on the first
+    pub fn previous_stop(&mut self) {
+        self.current = self.current.saturating_sub(1);
+    }
+
+    /// Type a character into the current placeholder, replacing it if it is still blank
+    ///
+    /// Parameters:
+    /// --- ---
+    /// input -> The search input
+    /// c -> The character typed
+    /// --- ---
+    ///
+    /// Returns: None
+    pub fn type_char(&mut self, input: &mut String, c: char) {
+        let (start, len) = self.stops[self.current];
+        if self.filled[self.current] {
+            input.insert(byte_index(input, start + len), c);
+            self.resize_current(len + 1);
+        } else {
+            input.replace_range(byte_index(input, start)..byte_index(input, start + len), "");
+            input.insert(byte_index(input, start), c);
+            self.resize_current(1);
+        }
+    }
+
+    /// Erase the last character typed into the current placeholder, or the blank placeholder
+    ///
+    /// Parameters:
+    /// --- ---
+    /// input -> The search input
+    /// --- ---
+    ///
+    /// Returns: None
+    pub fn erase(&mut self, input: &mut String) {
+        let (start, len) = self.stops[self.current];
+        let kept = if self.filled[self.current] {
+            len.saturating_sub(1)
+        } else {
+            0
+        };
+        input.replace_range(
+            byte_index(input, start + kept)..byte_index(input, start + len),
+            "",
+        );
+        self.resize_current(kept);
+    }
+
+    /// Set the current placeholder's length, moving the placeholders after it along
+    ///
+    /// Parameters:
+    /// --- ---
+    /// len -> The new length in characters
+    /// --- ---
+    ///
+    /// Returns: None
+    fn resize_current(&mut self, len: usize) {
+        let old_len = self.stops[self.current].1;
+        self.stops[self.current].1 = len;
+        self.filled[self.current] = true;
+        for stop in self.stops.iter_mut().skip(self.current + 1) {
+            stop.0 = stop.0 + len - old_len;
+        }
+    }
+}
+
+/// Get the byte index of a character of a string
+///
+/// Parameters:
+/// --- ---
+/// text -> The string
+/// index -> The character's index, or the character count for the end
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// usize -> Its byte index
+/// --- ---
+///
+fn byte_index(text: &str, index: usize) -> usize {
+    text.char_indices()
+        .nth(index)
+        .map_or(text.len(), |(byte, _)| byte)
+}
+
+/// Search state saved before a pivot, to go back to
+///
+/// Fields:
+/// --- ---
+/// input -> The query typed
+/// filtered_results -> The results after the result filter, before collapsing duplicates
+/// expanded_duplicates -> Groups of duplicate sections shown in full
+/// unfiltered_results -> The results before the result filter
+/// selected_result -> Index of the selected result
+/// results_scroll -> Scroll offset of the results
+/// result_filter -> The result filter, None when not filtering
+/// pivot -> What the results showed if they came from a pivot
+/// focus -> Whether the query box or the results had focus
+/// last_timing -> Timing of the search that found the results
+/// last_explanation -> Query plan of the search that found the results
+/// last_diff -> Differences of the results with the previous run of their query
+/// --- ---
+///
+pub struct SearchPlace {
+    input: String,
+    filtered_results: Vec<Class>,
+    expanded_duplicates: HashSet<String>,
+    unfiltered_results: Vec<Class>,
+    selected_result: usize,
+    results_scroll: usize,
+    result_filter: Option<String>,
+    pivot: Option<String>,
+    focus: SearchFocus,
+    last_timing: Option<QueryTiming>,
+    last_explanation: Option<QueryExplanation>,
+    last_diff: Option<ResultDiff>,
+}
+
 /// Search widget with encapsulated state
 ///
-/// Manages the query input interface including text entry, cursor blinking,
+/// Manages the query input interface including text entry, cursor placement,
 /// tab completion dropdown, query execution, and results browsing with
 /// keyboard navigation and add-to-cart functionality.
 ///
@@ -74,14 +350,40 @@ impl CompletionState {
 /// user_query -> The last executed query string
 /// problematic_positions -> Byte ranges of problematic tokens for highlighting
 /// completion -> Tab completion state (suggestions, selection, visibility)
+/// snippet -> Placeholders left to fill in an accepted snippet, None when not filling one
 /// query_results -> The list of Class results from the last query
 /// results_scroll -> Scroll offset for results display
 /// selected_result -> Index of currently selected result
-/// cursor_visible -> Whether the input cursor is visible (for blinking)
-/// last_cursor_blink -> Timestamp of last cursor blink toggle
 /// max_items_that_fit -> Maximum number of items that fit on screen
 /// focus -> Current focus mode (QueryInput or ResultsBrowse)
 /// is_searching -> Whether a query is currently being executed
+/// live_preview -> Whether search-as-you-type preview is enabled
+/// preview_results -> Top results for the current (unsubmitted) input, loaded in the
+///                    background so a slow preview query never holds up typing
+/// preview_pending_since -> Timestamp of the last edit awaiting a preview refresh
+/// last_timing -> Compile and execution time of the last search, shown in the status bar
+/// slow_query_threshold -> Search time at which a query is written to the slow query log
+/// last_explanation -> Query plan and index advice for the last search
+/// last_notices -> What the parser assumed to run the last search (e.g., a condition left out)
+/// last_truncated -> Results the last search was cut off at by the cap in Settings, None if
+///                   it returned every result
+/// result_filter -> Words narrowing the results (see ClassFilter), None when not filtering
+/// editing_filter -> Whether keys are typed into the result filter
+/// unfiltered_results -> The results of the last query before the filter was applied
+/// filtered_results -> The results after the filter, before duplicates are collapsed
+/// collapse_duplicates -> Whether sections of a course at the same time share one card
+/// duplicates -> The groups of duplicate sections among the filtered results
+/// expanded_duplicates -> Unique IDs of the first sections of groups shown in full
+/// run_history -> Results of the last run of each query, by formatted query
+/// last_diff -> Differences between the last search and the previous run of the same query
+/// show_diff -> Whether result cards are marked with the differences
+/// profile -> Workspace profile in use, whose constraints every search also has to meet
+/// favorite_index -> Index of the profile's favorite query last put in the search box
+/// pivot -> What the results show when they came from a detail view pivot instead of the query
+/// back_stack -> Search state before each pivot, most recent last
+/// cost_confirmed -> Query warned about being slow, which Enter runs the next time
+/// ghost_index -> Which example query the empty search box suggests
+/// ghost_shown_at -> When the suggested example was last changed, or the box last had input
 /// --- ---
 ///
 pub struct SearchWidget {
@@ -89,16 +391,40 @@ pub struct SearchWidget {
     pub user_query: String,
     pub problematic_positions: Vec<(usize, usize)>,
     pub completion: CompletionState,
+    pub snippet: Option<SnippetStops>,
     pub query_results: Vec<Class>,
     pub results_scroll: usize,
     pub selected_result: usize,
-    pub cursor_visible: bool,
-    pub last_cursor_blink: Instant,
     pub max_items_that_fit: Cell<usize>,
     /// Internal focus: QueryInput or ResultsBrowse
     focus: SearchFocus,
     /// Whether a search is currently in progress
     pub is_searching: bool,
+    pub live_preview: bool,
+    pub preview_results: WarmCache<Vec<Class>>,
+    preview_pending_since: Option<Instant>,
+    pub last_timing: Option<QueryTiming>,
+    pub slow_query_threshold: Duration,
+    pub last_explanation: Option<QueryExplanation>,
+    pub last_notices: Vec<String>,
+    pub last_truncated: Option<usize>,
+    pub result_filter: Option<String>,
+    pub editing_filter: bool,
+    unfiltered_results: Vec<Class>,
+    filtered_results: Vec<Class>,
+    pub collapse_duplicates: bool,
+    duplicates: CollapsedResults,
+    expanded_duplicates: HashSet<String>,
+    run_history: HashMap<String, Vec<Class>>,
+    pub last_diff: Option<ResultDiff>,
+    pub show_diff: bool,
+    pub profile: Option<Profile>,
+    favorite_index: Option<usize>,
+    pub pivot: Option<String>,
+    back_stack: Vec<SearchPlace>,
+    pub cost_confirmed: Option<String>,
+    pub ghost_index: usize,
+    ghost_shown_at: Instant,
 }
 
 /// Internal focus state for SearchWidget
@@ -116,22 +442,38 @@ impl SearchWidget {
             user_query: String::new(),
             problematic_positions: Vec::new(),
             completion: CompletionState::new(),
+            snippet: None,
             query_results: Vec::new(),
             results_scroll: 0,
             selected_result: 0,
-            cursor_visible: true,
-            last_cursor_blink: Instant::now(),
             max_items_that_fit: Cell::new(0),
             focus: SearchFocus::QueryInput,
             is_searching: false,
-        }
-    }
-
-    /// Update cursor blink state
-    pub fn update_cursor_blink(&mut self) {
-        if self.last_cursor_blink.elapsed() > std::time::Duration::from_millis(500) {
-            self.cursor_visible = !self.cursor_visible;
-            self.last_cursor_blink = Instant::now();
+            live_preview: false,
+            preview_results: WarmCache::new(),
+            preview_pending_since: None,
+            last_timing: None,
+            slow_query_threshold: slow_query_threshold(),
+            last_explanation: None,
+            last_notices: Vec::new(),
+            last_truncated: None,
+            result_filter: None,
+            editing_filter: false,
+            unfiltered_results: Vec::new(),
+            filtered_results: Vec::new(),
+            collapse_duplicates: false,
+            duplicates: CollapsedResults::default(),
+            expanded_duplicates: HashSet::new(),
+            run_history: HashMap::new(),
+            last_diff: None,
+            show_diff: false,
+            profile: None,
+            favorite_index: None,
+            pivot: None,
+            back_stack: Vec::new(),
+            cost_confirmed: None,
+            ghost_index: 0,
+            ghost_shown_at: Instant::now(),
         }
     }
 
@@ -167,6 +509,341 @@ impl SearchWidget {
         self.problematic_positions.clear();
     }
 
+    /// Record that the input was edited so the live preview refreshes after the debounce
+    fn mark_input_changed(&mut self) {
+        if self.live_preview {
+            self.preview_pending_since = Some(Instant::now());
+        }
+    }
+
+    /// Enable or disable the live results preview
+    pub fn set_live_preview(&mut self, enabled: bool) {
+        self.live_preview = enabled;
+        self.preview_results.clear();
+        self.preview_pending_since = if enabled { Some(Instant::now()) } else { None };
+    }
+
+    /// Switch the workspace profile whose constraints and favorites searches use
+    ///
+    /// Results of the previous profile are cleared, since they weren't constrained the same way
+    pub fn set_profile(&mut self, profile: Option<Profile>) {
+        self.profile = profile;
+        self.favorite_index = None;
+        self.clear_results();
+    }
+
+    /// Show sections found by an internal query in place of the search results
+    ///
+    /// Used by the detail view's pivot to the other sections of a course, so they can be
+    /// browsed and compared without typing a search. The query box keeps what was typed
+    ///
+    /// Arguments:
+    /// --- ---
+    /// label -> What the sections are, shown on the query box, e.g., "Sections of CMPT 306N"
+    /// classes -> The sections
+    /// selected_id -> Unique ID of the section to select, the first one if it isn't listed
+    /// --- ---
+    ///
+    /// Returns: None
+    ///
+    pub fn show_pivot(&mut self, label: String, classes: Vec<Class>, selected_id: &str) {
+        self.last_timing = None;
+        self.last_explanation = None;
+        self.last_diff = None;
+        self.last_truncated = None;
+        self.result_filter = None;
+        self.editing_filter = false;
+        self.problematic_positions.clear();
+        self.preview_results.clear();
+        self.preview_pending_since = None;
+        self.unfiltered_results = classes.clone();
+        self.expanded_duplicates.clear();
+        self.show_results(classes);
+        self.select_result(selected_id);
+        self.pivot = Some(label);
+        self.focus = SearchFocus::ResultsBrowse;
+    }
+
+    /// Select a result by its class, scrolled into view
+    ///
+    /// Arguments:
+    /// --- ---
+    /// unique_id -> Unique ID of the class to select, the first result if it isn't listed
+    /// --- ---
+    ///
+    /// Returns: None
+    ///
+    pub fn select_result(&mut self, unique_id: &str) {
+        // a section folded under another's card is shown by expanding its group
+        let shown = self
+            .query_results
+            .iter()
+            .any(|class| class.unique_id() == unique_id);
+        if !shown {
+            if let Some(first) = self.duplicates.group_of(unique_id) {
+                self.expanded_duplicates.insert(first.to_string());
+                self.collapse_results();
+            }
+        }
+        self.selected_result = self
+            .query_results
+            .iter()
+            .position(|class| class.unique_id() == unique_id)
+            .unwrap_or(0);
+        // scroll by whole rows of the 3-column grid, like browsing does
+        self.results_scroll = self.selected_result - self.selected_result % 3;
+    }
+
+    /// Save the search state before a pivot replaces it, so it can be gone back to
+    ///
+    /// Returns: None
+    ///
+    pub fn push_place(&mut self) {
+        if self.back_stack.len() == BACK_STACK_LIMIT {
+            self.back_stack.remove(0);
+        }
+        self.back_stack.push(SearchPlace {
+            input: self.input.clone(),
+            filtered_results: self.filtered_results.clone(),
+            expanded_duplicates: self.expanded_duplicates.clone(),
+            unfiltered_results: self.unfiltered_results.clone(),
+            selected_result: self.selected_result,
+            results_scroll: self.results_scroll,
+            result_filter: self.result_filter.clone(),
+            pivot: self.pivot.clone(),
+            focus: self.focus.clone(),
+            last_timing: self.last_timing,
+            last_explanation: self.last_explanation.clone(),
+            last_diff: self.last_diff.clone(),
+        });
+    }
+
+    /// Go back to the search state saved before the last pivot
+    ///
+    /// Returns:
+    /// --- ---
+    /// bool -> false if there was no pivot to go back from
+    /// --- ---
+    ///
+    pub fn go_back(&mut self) -> bool {
+        let Some(place) = self.back_stack.pop() else {
+            return false;
+        };
+        self.input = place.input;
+        self.snippet = None;
+        self.unfiltered_results = place.unfiltered_results;
+        self.filtered_results = place.filtered_results;
+        self.expanded_duplicates = place.expanded_duplicates;
+        // collapsing may have been turned on or off since
+        self.collapse_results();
+        self.selected_result = place
+            .selected_result
+            .min(self.query_results.len().saturating_sub(1));
+        self.results_scroll = place.results_scroll;
+        self.result_filter = place.result_filter;
+        self.editing_filter = false;
+        self.pivot = place.pivot;
+        self.focus = place.focus;
+        self.last_timing = place.last_timing;
+        self.last_explanation = place.last_explanation;
+        self.last_diff = place.last_diff;
+        self.problematic_positions.clear();
+        self.preview_results.clear();
+        self.preview_pending_since = None;
+        self.completion.clear();
+        true
+    }
+
+    /// Number of pivots that can be gone back from
+    ///
+    /// Returns:
+    /// --- ---
+    /// usize -> Depth of the back-stack
+    /// --- ---
+    ///
+    pub fn back_depth(&self) -> usize {
+        self.back_stack.len()
+    }
+
+    /// Forget the search state saved before pivots, keeping what is shown
+    ///
+    /// Called when the views the pivots were made from are left for the main menu
+    ///
+    /// Returns: None
+    ///
+    pub fn forget_places(&mut self) {
+        self.back_stack.clear();
+    }
+
+    /// The query that is run for the input: the input with the profile's constraints added
+    pub fn constrained_input(&self) -> String {
+        match &self.profile {
+            Some(profile) => profile.constrain(&self.input),
+            None => self.input.clone(),
+        }
+    }
+
+    /// Put the profile's next favorite query in the search box
+    ///
+    /// Returns a toast naming the favorite, or explaining why there is none
+    fn next_favorite(&mut self) -> KeyAction {
+        let Some(profile) = &self.profile else {
+            return KeyAction::ShowToast {
+                message: "No profile in use, press p on the main menu to pick one".to_string(),
+                error_type: ErrorType::Info,
+            };
+        };
+        if profile.queries.is_empty() {
+            return KeyAction::ShowToast {
+                message: format!("Profile '{}' has no favorite queries", profile.name),
+                error_type: ErrorType::Info,
+            };
+        }
+
+        let index = self
+            .favorite_index
+            .map_or(0, |index| (index + 1) % profile.queries.len());
+        let message = format!(
+            "Favorite {} of {}: {}",
+            index + 1,
+            profile.queries.len(),
+            profile.queries[index]
+        );
+        self.input = profile.queries[index].clone();
+        self.snippet = None;
+        self.favorite_index = Some(index);
+        self.focus = SearchFocus::QueryInput;
+        self.completion.clear();
+        self.clear_error_state();
+        self.mark_input_changed();
+        KeyAction::ShowToast {
+            message,
+            error_type: ErrorType::Info,
+        }
+    }
+
+    /// Warn before running a query that would be slow, unless it was just warned about
+    ///
+    /// The first Enter on a slow query shows why it is slow; a second Enter on the same query
+    /// runs it anyway
+    ///
+    /// Parameters:
+    /// --- ---
+    /// compiler -> The compiler the query would run with
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Option<KeyAction> -> A warning toast, None if the query should run
+    /// --- ---
+    ///
+    pub fn check_cost(&mut self, compiler: &Compiler) -> Option<KeyAction> {
+        let query = self.constrained_input();
+        if self.cost_confirmed.as_deref() == Some(query.as_str()) {
+            return None;
+        }
+        let warnings = compiler.cost_warnings(&query);
+        if warnings.is_empty() {
+            return None;
+        }
+        self.cost_confirmed = Some(query);
+        Some(KeyAction::ShowToast {
+            message: format!(
+                "This search may be slow:\n{}\nPress Enter again to run it anyway",
+                warnings.join("\n")
+            ),
+            error_type: ErrorType::Warning,
+        })
+    }
+
+    /// Get the example query suggested while the search box is empty
+    ///
+    /// Returns:
+    /// --- ---
+    /// Option<&'static str> -> The example, None if the box has input or a snippet is open
+    /// --- ---
+    ///
+    pub fn ghost_example(&self) -> Option<&'static str> {
+        if !self.input.is_empty() || self.snippet.is_some() {
+            return None;
+        }
+        let examples = entities::example_queries();
+        examples
+            .get(self.ghost_index % examples.len().max(1))
+            .copied()
+    }
+
+    /// Move on to the next example query once the current one has been shown long enough
+    ///
+    /// Typing restarts the wait, so a box just emptied shows its example for the full interval
+    pub fn rotate_ghost_example(&mut self) {
+        if !self.input.is_empty() {
+            self.ghost_shown_at = Instant::now();
+            return;
+        }
+        if self.ghost_shown_at.elapsed() >= GHOST_EXAMPLE_INTERVAL {
+            self.ghost_index = (self.ghost_index + 1) % entities::example_queries().len().max(1);
+            self.ghost_shown_at = Instant::now();
+        }
+    }
+
+    /// Put the suggested example query in the empty search box
+    ///
+    /// Returns:
+    /// --- ---
+    /// bool -> false if no example was suggested
+    /// --- ---
+    ///
+    pub fn accept_ghost_example(&mut self) -> bool {
+        let Some(example) = self.ghost_example() else {
+            return false;
+        };
+        self.input = example.to_string();
+        self.clear_error_state();
+        self.mark_input_changed();
+        true
+    }
+
+    /// Refresh the live preview once the input has been idle for the debounce period
+    ///
+    /// The preview query runs in the background and its results replace the preview when it
+    /// finishes, unless a newer preview was started meanwhile. Errors are not surfaced while
+    /// typing; an invalid partial query just clears the preview
+    pub fn update_live_preview(&mut self, compiler: &Compiler) {
+        if !self.live_preview {
+            return;
+        }
+        match self.preview_pending_since {
+            Some(edited_at) if edited_at.elapsed() >= LIVE_PREVIEW_DEBOUNCE => {}
+            _ => return,
+        }
+        self.preview_pending_since = None;
+
+        if self.input.trim().is_empty() {
+            self.preview_results.clear();
+            return;
+        }
+
+        let Some(sql) = compiler.preview_sql(&self.constrained_input(), LIVE_PREVIEW_LIMIT) else {
+            self.preview_results.clear();
+            return;
+        };
+        let db_path = compiler.db_path();
+        self.preview_results
+            .warm_up(move || Ok(execute_query(&sql, &db_path).unwrap_or_default()));
+    }
+
+    /// Check if the live preview should replace the results grid
+    fn is_showing_preview(&self) -> bool {
+        self.live_preview
+            && self.focus == SearchFocus::QueryInput
+            && self
+                .preview_results
+                .get()
+                .is_some_and(|classes| !classes.is_empty())
+            && self.input != self.user_query
+    }
+
     /// Render the "Searching..." indicator in the results area
     pub fn render_searching_indicator(frame: &mut Frame, theme: &Theme) {
         use ratatui::layout::{Alignment, Rect};
@@ -199,56 +876,342 @@ impl SearchWidget {
 
     /// Execute a query using the compiler
     pub fn execute_query(&mut self, compiler: &mut Compiler) -> Option<KeyAction> {
+        self.snippet = None;
         self.user_query = self.input.clone();
-
-        match compiler.run(&self.input) {
-            CompilerResult::Success { classes, .. } => {
+        self.pivot = None;
+
+        let query = self.constrained_input();
+        let result = compiler.run(&query);
+        // errors point into the query as typed, not the constraints added to it
+        let typed = self.input.clone();
+        let typed_positions = |positions: Vec<(usize, usize)>| {
+            if query == typed {
+                positions
+            } else {
+                unconstrain_positions(&typed, &positions)
+            }
+        };
+        self.last_timing = compiler.last_timing().copied();
+        self.last_explanation = None;
+        self.last_notices.clear();
+        self.last_diff = None;
+        self.last_truncated = None;
+        match result {
+            CompilerResult::Success {
+                classes,
+                sql,
+                ast,
+                notices,
+                truncated,
+                ..
+            } => {
+                if let Some(timing) = &self.last_timing {
+                    // a log that can't be written shouldn't get in the way of the search
+                    let _ = query_log::record_if_slow(
+                        &get_slow_query_log_path(),
+                        self.slow_query_threshold,
+                        &self.input,
+                        timing,
+                        classes.len(),
+                    );
+                }
+                // the plan only feeds the explain view, so a search that ran still counts
+                self.last_explanation = explain_search(&compiler.db_path(), &sql).ok();
                 self.problematic_positions.clear();
-                self.query_results = classes;
+                self.preview_results.clear();
+                self.preview_pending_since = None;
+                // the same query written differently formats the same, so it still diffs
+                self.last_diff = self
+                    .run_history
+                    .insert(format_query(&ast), classes.clone())
+                    .map(|previous| diff_results(&previous, &classes));
+                self.result_filter = None;
+                self.editing_filter = false;
+                self.last_truncated = truncated.then_some(classes.len());
+                self.unfiltered_results = classes.clone();
+                self.expanded_duplicates.clear();
+                self.show_results(classes);
                 self.results_scroll = 0;
                 self.selected_result = 0;
+                self.last_notices = notices;
                 None
             }
             CompilerResult::LexerError {
+                code,
                 message,
                 problematic_positions,
             } => {
+                let problematic_positions = typed_positions(problematic_positions);
+                // show the query with carets under the offending characters, sized to the toast
+                let caret = render_caret(
+                    &self.input,
+                    &problematic_positions,
+                    Some(LEXER_TOAST_WIDTH.saturating_sub(4) as usize),
+                );
+                let header = diagnostic_header(code, &self.input, &problematic_positions);
                 self.problematic_positions = problematic_positions;
                 Some(KeyAction::ShowToast {
-                    message,
+                    message: format!("{}: {}\n{}", header, message, caret),
                     error_type: ErrorType::Lexer,
                 })
             }
             CompilerResult::ParserError {
+                code,
                 message,
                 problematic_positions,
             } => {
+                let problematic_positions = typed_positions(problematic_positions);
+                let header = diagnostic_header(code, &self.input, &problematic_positions);
                 self.problematic_positions = problematic_positions;
                 Some(KeyAction::ShowToast {
-                    message,
+                    message: format!("{}: {}", header, message),
                     error_type: ErrorType::Parser,
                 })
             }
             CompilerResult::SemanticError {
+                code,
                 message,
                 problematic_positions,
             } => {
+                let problematic_positions = typed_positions(problematic_positions);
+                let header = diagnostic_header(code, &self.input, &problematic_positions);
                 self.problematic_positions = problematic_positions;
                 Some(KeyAction::ShowToast {
-                    message,
+                    message: format!("{}: {}", header, message),
                     error_type: ErrorType::Semantic,
                 })
             }
-            CompilerResult::CodeGenError { message } => {
+            CompilerResult::CodeGenError { code, message } => {
                 self.problematic_positions.clear();
                 Some(KeyAction::ShowToast {
-                    message,
+                    message: format!("{}: {}", diagnostic_header(code, &self.input, &[]), message),
                     error_type: ErrorType::Semantic,
                 })
             }
         }
     }
 
+    /// Clear the results, the live preview, and the result filter
+    ///
+    /// Called when the school or term changes, so old results aren't shown or filtered
+    pub fn clear_results(&mut self) {
+        self.query_results.clear();
+        self.unfiltered_results.clear();
+        self.filtered_results.clear();
+        self.duplicates = CollapsedResults::default();
+        self.expanded_duplicates.clear();
+        self.preview_results.clear();
+        self.result_filter = None;
+        self.editing_filter = false;
+        self.run_history.clear();
+        self.last_diff = None;
+        self.last_truncated = None;
+        self.pivot = None;
+        self.back_stack.clear();
+    }
+
+    /// Turn marking the differences with the previous run on or off
+    ///
+    /// The toast summarizes the diff and lists the sections that are gone, since those
+    /// aren't among the results to mark
+    fn toggle_diff(&mut self) -> KeyAction {
+        self.show_diff = !self.show_diff;
+        let message = match (&self.last_diff, self.show_diff) {
+            (_, false) => "Diff with last run off".to_string(),
+            (None, true) => {
+                "Diff with last run on, run the same search again to compare".to_string()
+            }
+            (Some(diff), true) => {
+                let mut message = format!("Diff with last run: {}", diff.summary());
+                if !diff.removed.is_empty() {
+                    let gone: Vec<String> = diff
+                        .removed
+                        .iter()
+                        .take(DIFF_GONE_LIMIT)
+                        .map(|class| {
+                            format!(
+                                "{} {}-{}",
+                                class.subject_code, class.course_number, class.section_sequence
+                            )
+                        })
+                        .collect();
+                    message.push_str(&format!("\nGone: {}", gone.join(", ")));
+                    if diff.removed.len() > DIFF_GONE_LIMIT {
+                        message.push_str(&format!(
+                            " and {} more",
+                            diff.removed.len() - DIFF_GONE_LIMIT
+                        ));
+                    }
+                }
+                message
+            }
+        };
+        KeyAction::ShowToast {
+            message,
+            error_type: ErrorType::Info,
+        }
+    }
+
+    /// Narrow the last query's results to the ones matching the result filter
+    ///
+    /// Results the term index has are matched through its subject, professor, and day
+    /// tables. Until the index loads, the results themselves are indexed instead, and
+    /// results the index doesn't have (like ones from before a sync) are checked one by one
+    ///
+    /// Arguments:
+    /// --- ---
+    /// term_index -> The active term's index, if it has loaded
+    /// --- ---
+    ///
+    /// Returns: None
+    ///
+    pub fn apply_result_filter(&mut self, term_index: Option<&TermIndex>) {
+        self.results_scroll = 0;
+        self.selected_result = 0;
+        let Some(text) = self.result_filter.as_deref() else {
+            self.show_results(self.unfiltered_results.clone());
+            return;
+        };
+
+        let results_index;
+        let index = match term_index {
+            Some(index) if !index.is_empty() => index,
+            _ => {
+                results_index = TermIndex::build(self.unfiltered_results.clone());
+                &results_index
+            }
+        };
+        let filter = ClassFilter::parse(text, index);
+        let matching_ids = index.matching_ids(&filter);
+        let filtered = self
+            .unfiltered_results
+            .iter()
+            .filter(|class| {
+                let id = class.unique_id();
+                if index.class(&id).is_some() {
+                    matching_ids.contains(&id)
+                } else {
+                    filter.matches(class)
+                }
+            })
+            .cloned()
+            .collect();
+        self.show_results(filtered);
+    }
+
+    /// Show results, with their duplicate sections collapsed when that is on
+    ///
+    /// Arguments:
+    /// --- ---
+    /// classes -> The results, after the result filter
+    /// --- ---
+    ///
+    /// Returns: None
+    ///
+    fn show_results(&mut self, classes: Vec<Class>) {
+        self.filtered_results = classes;
+        self.collapse_results();
+    }
+
+    /// Collapse the duplicate sections of the filtered results, or show them all
+    ///
+    /// Returns: None
+    ///
+    fn collapse_results(&mut self) {
+        if self.collapse_duplicates {
+            self.duplicates =
+                collapse_duplicates(&self.filtered_results, &self.expanded_duplicates);
+            self.query_results = self.duplicates.shown.clone();
+        } else {
+            self.duplicates = CollapsedResults::default();
+            self.query_results = self.filtered_results.clone();
+        }
+    }
+
+    /// Turn collapsing duplicate sections on or off
+    ///
+    /// Sections are duplicates when they are the same course meeting at the same times on
+    /// the same campus (see data::duplicates); the selected section stays selected, or the
+    /// card it was folded into
+    fn toggle_duplicates(&mut self) -> KeyAction {
+        let selected = self.selected_class().map(Class::unique_id);
+        self.collapse_duplicates = !self.collapse_duplicates;
+        self.expanded_duplicates.clear();
+        self.collapse_results();
+        if let Some(id) = selected {
+            let id = self
+                .duplicates
+                .group_of(&id)
+                .map_or(id.clone(), str::to_string);
+            self.select_result(&id);
+        }
+
+        let hidden = self.duplicates.hidden(&self.expanded_duplicates);
+        let message = match (self.collapse_duplicates, hidden) {
+            (false, _) => "Duplicate sections shown".to_string(),
+            (true, 0) => "Duplicate sections collapsed, none share a course and time".to_string(),
+            (true, hidden) => format!(
+                "Duplicate sections collapsed: {} folded into other cards, Alt+E expands one",
+                hidden
+            ),
+        };
+        KeyAction::ShowToast {
+            message,
+            error_type: ErrorType::Info,
+        }
+    }
+
+    /// Expand or fold the group of duplicate sections the selected card belongs to
+    fn toggle_duplicate_group(&mut self) -> KeyAction {
+        let Some(id) = self.selected_class().map(Class::unique_id) else {
+            return KeyAction::Continue;
+        };
+        let Some(first) = self.duplicates.group_of(&id).map(str::to_string) else {
+            let message = if self.collapse_duplicates {
+                "No other section is this course at the same time"
+            } else {
+                "Collapse duplicate sections with Alt+U first"
+            };
+            return KeyAction::ShowToast {
+                message: message.to_string(),
+                error_type: ErrorType::Info,
+            };
+        };
+        if !self.expanded_duplicates.remove(&first) {
+            self.expanded_duplicates.insert(first.clone());
+        }
+        self.collapse_results();
+        self.select_result(&first);
+        KeyAction::Continue
+    }
+
+    /// Handle a key typed into the result filter
+    ///
+    /// Returns None for keys the filter doesn't use, so they still navigate the results
+    fn handle_filter_key(&mut self, key: KeyEvent) -> Option<KeyAction> {
+        match key.code {
+            KeyCode::Char(c)
+                if !key
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                self.result_filter.get_or_insert_with(String::new).push(c);
+            }
+            KeyCode::Backspace => {
+                if let Some(filter) = self.result_filter.as_mut() {
+                    filter.pop();
+                }
+            }
+            KeyCode::Enter => self.editing_filter = false,
+            KeyCode::Esc => {
+                self.editing_filter = false;
+                self.result_filter = None;
+            }
+            _ => return None,
+        }
+        Some(KeyAction::Continue)
+    }
+
     /// Handle tab completion
     ///
     /// Returns a toast message if no completions are available
@@ -268,15 +1231,22 @@ impl SearchWidget {
         };
 
         // get completion suggestions from compiler
-        let suggestions = compiler.get_tab_completion(self.input.clone());
+        let suggestions = compiler.get_completion_items(self.input.clone());
 
         // if there's a potential partial word, check if any suggestions match it
         if !potential_partial.is_empty() {
-            let matching: Vec<String> = suggestions
+            let mut matching: Vec<Completion> = suggestions
                 .iter()
-                .filter(|s| s.to_lowercase().starts_with(&potential_partial))
+                .filter(|s| s.text.to_lowercase().starts_with(&potential_partial))
                 .cloned()
                 .collect();
+            // offer synonyms too, so "teach" completes to "teacher" where "professor" fits
+            let texts: Vec<String> = suggestions.iter().map(|s| s.text.clone()).collect();
+            matching.extend(
+                entities::synonym_completions(&texts, &potential_partial)
+                    .iter()
+                    .map(|synonym| entities::describe_completion(synonym)),
+            );
 
             if !matching.is_empty() {
                 // partial word matches some suggestions - filter to those
@@ -332,12 +1302,19 @@ impl SearchWidget {
     }
 
     /// Apply selected completion to input
+    ///
+    /// The cursor sits at the end of the input, so it lands after the completion, past the
+    /// space that separates it from the next word. A snippet puts it on its first placeholder
     pub fn apply_completion(&mut self) {
         if let Some(index) = self.completion.completion_index {
             if index < self.completion.completions.len() {
-                let completion = &self.completion.completions[index].clone();
-                // don't add placeholders like <value>
-                if !completion.starts_with('<') {
+                let completion = &self.completion.completions[index].text.clone();
+                let is_snippet = self.completion.completions[index].kind == CompletionKind::Snippet;
+                // don't add placeholders like <value>, but do add "<" and "<="
+                let placeholder = completion.len() > 2
+                    && completion.starts_with('<')
+                    && completion.ends_with('>');
+                if !placeholder {
                     // only replace if there's a partial word that matches
                     if !self.completion.partial_word.is_empty()
                         && completion
@@ -348,17 +1325,26 @@ impl SearchWidget {
                         let trim_len = self.completion.partial_word.len();
                         let new_len = self.input.len().saturating_sub(trim_len);
                         self.input.truncate(new_len);
+                    } else if completion == ")" {
+                        // a closing parenthesis goes right after what it closes
+                        let trimmed_len = self.input.trim_end().len();
+                        self.input.truncate(trimmed_len);
                     } else {
                         // no partial word - just append with space
                         if !self.input.is_empty() && !self.input.ends_with(' ') {
                             self.input.push(' ');
                         }
                     }
+                    let start = self.input.chars().count();
                     self.input.push_str(completion);
-                    if !completion.starts_with('"') {
+                    if is_snippet {
+                        // the cursor goes to the first placeholder instead of the end
+                        self.snippet = SnippetStops::new(&self.input, start);
+                    } else if !completion.starts_with('"') {
                         // add space after completion for next word
                         self.input.push(' ');
                     }
+                    self.mark_input_changed();
                 }
             }
         }
@@ -366,65 +1352,139 @@ impl SearchWidget {
     }
 
     /// Handle completion navigation
+    ///
+    /// Up/Down and Shift+Tab cycle through the suggestions, Tab or Enter accepts the one
+    /// selected, Esc closes the popup, and any other key closes it and is dropped
     fn handle_completion_key(&mut self, key: KeyEvent) -> KeyAction {
         match key.code {
             KeyCode::Esc => {
                 self.completion.clear();
                 KeyAction::Continue
             }
-            KeyCode::Up => {
-                if let Some(index) = self.completion.completion_index {
-                    if index > 0 {
-                        self.completion.completion_index = Some(index - 1);
-                    }
-                }
+            KeyCode::Up | KeyCode::BackTab => {
+                self.cycle_completion(false);
                 KeyAction::Continue
             }
             KeyCode::Down => {
-                if let Some(index) = self.completion.completion_index {
-                    let len = self.completion.completions.len();
-                    if index < len - 1 {
-                        self.completion.completion_index = Some(index + 1);
-                    } else {
-                        self.completion.completion_index = Some(0);
-                    }
-                }
+                self.cycle_completion(true);
                 KeyAction::Continue
             }
-            KeyCode::Enter => {
+            KeyCode::Enter | KeyCode::Tab => {
                 self.apply_completion();
                 KeyAction::Continue
             }
+            _ => {
+                // any other key hides completions
+                self.completion.clear();
+                KeyAction::Continue
+            }
+        }
+    }
+
+    /// Move the completion selection, wrapping around at either end
+    ///
+    /// Parameters:
+    /// --- ---
+    /// forward -> true to move down the list, false to move up
+    /// --- ---
+    ///
+    /// Returns: None
+    ///
+    fn cycle_completion(&mut self, forward: bool) {
+        let len = self.completion.completions.len();
+        if let Some(index) = self.completion.completion_index {
+            if len > 0 {
+                self.completion.completion_index = Some(if forward {
+                    (index + 1) % len
+                } else {
+                    (index + len - 1) % len
+                });
+            }
+        }
+    }
+
+    /// Handle a key typed while filling a snippet's placeholders
+    ///
+    /// Typing and Backspace edit the current placeholder, Tab and Shift+Tab move between them,
+    /// and Tab past the last or Esc stops filling them. Returns None for keys the snippet
+    /// doesn't use, which stop filling it and are handled as usual
+    fn handle_snippet_key(&mut self, key: KeyEvent) -> Option<KeyAction> {
+        let snippet = self.snippet.as_mut()?;
+        match key.code {
+            KeyCode::Char(c)
+                if !key
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                snippet.type_char(&mut self.input, c);
+            }
+            KeyCode::Backspace => snippet.erase(&mut self.input),
             KeyCode::Tab => {
-                // tab moves down through completions
-                if let Some(index) = self.completion.completion_index {
-                    let len = self.completion.completions.len();
-                    if index < len - 1 {
-                        self.completion.completion_index = Some(index + 1);
-                    } else {
-                        self.completion.completion_index = Some(0);
+                if !snippet.next_stop() {
+                    // past the last placeholder, carry on typing after the snippet
+                    self.snippet = None;
+                    if !self.input.ends_with(' ') {
+                        self.input.push(' ');
                     }
                 }
-                KeyAction::Continue
             }
+            KeyCode::BackTab => snippet.previous_stop(),
+            KeyCode::Esc => self.snippet = None,
             _ => {
-                // any other key hides completions
-                self.completion.clear();
-                KeyAction::Continue
+                self.snippet = None;
+                return None;
             }
         }
+        self.clear_error_state();
+        self.mark_input_changed();
+        Some(KeyAction::Continue)
     }
 
     /// Handle results browse navigation
     fn handle_results_browse_key(&mut self, key: KeyEvent) -> KeyAction {
+        if self.editing_filter {
+            if let Some(action) = self.handle_filter_key(key) {
+                return action;
+            }
+        }
+
         match key.code {
             KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => KeyAction::Exit,
-            KeyCode::Esc => KeyAction::Navigate(FocusMode::MainMenu),
+            KeyCode::Esc if self.result_filter.is_some() => {
+                self.result_filter = None;
+                KeyAction::Continue
+            }
+            KeyCode::Esc => KeyAction::Back,
+            KeyCode::Char('/') => {
+                self.editing_filter = true;
+                self.result_filter.get_or_insert_with(String::new);
+                KeyAction::Continue
+            }
             KeyCode::Char('g') | KeyCode::Char('G')
                 if key.modifiers.contains(KeyModifiers::ALT) =>
             {
                 KeyAction::Navigate(FocusMode::QueryGuide)
             }
+            KeyCode::Char('d') | KeyCode::Char('D')
+                if key.modifiers.contains(KeyModifiers::ALT) =>
+            {
+                self.toggle_diff()
+            }
+            KeyCode::Char('u') | KeyCode::Char('U')
+                if key.modifiers.contains(KeyModifiers::ALT) =>
+            {
+                self.toggle_duplicates()
+            }
+            KeyCode::Char('e') | KeyCode::Char('E')
+                if key.modifiers.contains(KeyModifiers::ALT) =>
+            {
+                self.toggle_duplicate_group()
+            }
+            KeyCode::Char('f') | KeyCode::Char('F')
+                if key.modifiers.contains(KeyModifiers::ALT) =>
+            {
+                self.next_favorite()
+            }
             KeyCode::Up => {
                 if self.selected_result == 0 {
                     self.focus = SearchFocus::QueryInput;
@@ -495,12 +1555,14 @@ impl SearchWidget {
                 self.focus = SearchFocus::QueryInput;
                 self.clear_error_state();
                 self.input.push(c);
+                self.mark_input_changed();
                 KeyAction::Continue
             }
             KeyCode::Backspace => {
                 self.focus = SearchFocus::QueryInput;
                 self.clear_error_state();
                 self.input.pop();
+                self.mark_input_changed();
                 KeyAction::Continue
             }
             KeyCode::Tab => {
@@ -514,14 +1576,34 @@ impl SearchWidget {
 
     /// Handle query input key events
     fn handle_query_input_key(&mut self, key: KeyEvent) -> KeyAction {
+        if self.snippet.is_some() {
+            if let Some(action) = self.handle_snippet_key(key) {
+                return action;
+            }
+        }
         match key.code {
             KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => KeyAction::Exit,
-            KeyCode::Esc => KeyAction::Navigate(FocusMode::MainMenu),
+            KeyCode::Esc => KeyAction::Back,
             KeyCode::Char('g') | KeyCode::Char('G')
                 if key.modifiers.contains(KeyModifiers::ALT) =>
             {
                 KeyAction::Navigate(FocusMode::QueryGuide)
             }
+            KeyCode::Char('d') | KeyCode::Char('D')
+                if key.modifiers.contains(KeyModifiers::ALT) =>
+            {
+                self.toggle_diff()
+            }
+            KeyCode::Char('u') | KeyCode::Char('U')
+                if key.modifiers.contains(KeyModifiers::ALT) =>
+            {
+                self.toggle_duplicates()
+            }
+            KeyCode::Char('f') | KeyCode::Char('F')
+                if key.modifiers.contains(KeyModifiers::ALT) =>
+            {
+                self.next_favorite()
+            }
             KeyCode::Down => {
                 if !self.query_results.is_empty() {
                     self.focus = SearchFocus::ResultsBrowse;
@@ -540,6 +1622,7 @@ impl SearchWidget {
             KeyCode::Backspace => {
                 self.clear_error_state();
                 self.input.pop();
+                self.mark_input_changed();
                 KeyAction::Continue
             }
             KeyCode::Tab => {
@@ -550,6 +1633,7 @@ impl SearchWidget {
             KeyCode::Char(c) => {
                 self.clear_error_state();
                 self.input.push(c);
+                self.mark_input_changed();
                 KeyAction::Continue
             }
             KeyCode::PageUp => {
@@ -573,52 +1657,99 @@ impl SearchWidget {
         }
     }
 
+    /// Get the entity metadata for the keyword at the cursor, if any
+    ///
+    /// The cursor always sits at the end of the input, so this looks at the last word typed
+    pub fn keyword_at_cursor(&self) -> Option<&'static EntityInfo> {
+        let last_word = self.input.split_whitespace().last()?;
+        entities::describe_keyword(last_word.trim_start_matches('('))
+    }
+
     /// Get the currently selected class (for detail view)
     pub fn selected_class(&self) -> Option<&Class> {
         self.query_results.get(self.selected_result)
     }
 
-    /// Render the search bar with syntax highlighting
+    /// Get where the search bar is drawn
     ///
     /// Arguments:
     /// --- ---
-    /// frame -> the frame to render to
-    /// theme -> the current theme
+    /// area -> The area of the frame
     /// --- ---
     ///
-    /// Returns: None
+    /// Returns:
+    /// --- ---
+    /// Rect -> The search bar, below the logo and clamped to the frame
+    /// --- ---
     ///
-    fn render_search_bar(&self, frame: &mut Frame, theme: &Theme) {
-        let search_width = 50;
-        let is_focused = self.focus == SearchFocus::QueryInput;
+    fn search_bar_area(area: Rect) -> Rect {
+        let search_width = 50_u16;
 
         // position search bar below the logo with same vertical gap as menus
         let logo_height = 7;
         let search_y = logo_height + 6;
-
-        let frame_width = frame.area().width;
-        let frame_height = frame.area().height;
         let search_height = 3_u16;
 
         // clamp search bar dimensions to fit within frame
-        let search_area = Rect {
-            x: (frame_width.saturating_sub(search_width.min(frame_width) as u16)) / 2,
-            y: search_y.min(frame_height.saturating_sub(search_height)),
-            width: (search_width as u16).min(frame_width),
-            height: search_height.min(frame_height),
+        Rect {
+            x: area.width.saturating_sub(search_width.min(area.width)) / 2,
+            y: search_y.min(area.height.saturating_sub(search_height)),
+            width: search_width.min(area.width),
+            height: search_height.min(area.height),
         }
-        .intersection(frame.area());
+        .intersection(area)
+    }
 
-        // calculate visible width (minus borders and "> " prefix and cursor)
-        let visible_width = search_width.saturating_sub(5) as usize;
-        let input_len = self.input.chars().count();
+    /// Get the part of the input shown in the search bar
+    ///
+    /// Arguments: None
+    ///
+    /// Returns:
+    /// --- ---
+    /// (usize, usize) -> Characters scrolled off the start, and how many fit in the bar
+    /// --- ---
+    ///
+    fn visible_input(&self) -> (usize, usize) {
+        // the bar is 50 wide, minus borders, the "> " prefix, and the cursor
+        let visible_width = 45;
+
+        // scroll to keep the cursor visible
+        (
+            self.cursor_index().saturating_sub(visible_width),
+            visible_width,
+        )
+    }
 
-        // calculate scroll offset to keep cursor (end of input) visible
-        let scroll_offset = if input_len > visible_width {
-            input_len - visible_width
-        } else {
-            0
-        };
+    /// Get the character of the input the cursor is before
+    ///
+    /// Arguments: None
+    ///
+    /// Returns:
+    /// --- ---
+    /// usize -> The end of the current snippet placeholder, otherwise the end of the input
+    /// --- ---
+    ///
+    fn cursor_index(&self) -> usize {
+        match &self.snippet {
+            Some(snippet) => snippet.cursor(),
+            None => self.input.chars().count(),
+        }
+    }
+
+    /// Render the search bar with syntax highlighting
+    ///
+    /// Arguments:
+    /// --- ---
+    /// frame -> the frame to render to
+    /// theme -> the current theme
+    /// --- ---
+    ///
+    /// Returns: None
+    ///
+    fn render_search_bar(&self, frame: &mut Frame, theme: &Theme) {
+        let is_focused = self.focus == SearchFocus::QueryInput;
+        let search_area = Self::search_bar_area(frame.area());
+        let (scroll_offset, visible_width) = self.visible_input();
 
         // create styled spans for the input with highlighted problematic positions
         let mut styled_spans = Vec::new();
@@ -637,6 +1768,18 @@ impl SearchWidget {
             ));
         }
 
+        // an empty box suggests an example query, accepted with Tab
+        if is_focused {
+            if let Some(example) = self.ghost_example() {
+                styled_spans.push(Span::styled(
+                    format!("try: {}", example),
+                    Style::default()
+                        .fg(theme.muted_color)
+                        .add_modifier(Modifier::ITALIC),
+                ));
+            }
+        }
+
         // process only the visible portion of the input
         for (i, ch) in self.input.chars().enumerate().skip(scroll_offset) {
             if i - scroll_offset >= visible_width {
@@ -648,8 +1791,30 @@ impl SearchWidget {
                 .iter()
                 .any(|&(start, end)| i >= start && i < end);
 
+            let stop = self.snippet.as_ref().and_then(|snippet| {
+                snippet
+                    .stop_at(i)
+                    .map(|(stop, filled)| (stop == snippet.current, filled))
+            });
+
+            // underline as well as color so errors show in any theme
             let style = if is_problematic {
-                Style::default().fg(theme.error_color)
+                Style::default()
+                    .fg(theme.error_color)
+                    .add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+            } else if let Some((current, filled)) = stop {
+                // snippet placeholders are underlined, the one being filled in bold
+                let color = if filled {
+                    theme.text_color
+                } else {
+                    theme.warning_color
+                };
+                let modifier = if current {
+                    Modifier::BOLD | Modifier::UNDERLINED
+                } else {
+                    Modifier::UNDERLINED
+                };
+                Style::default().fg(color).add_modifier(modifier)
             } else {
                 Style::default().fg(theme.text_color)
             };
@@ -657,13 +1822,6 @@ impl SearchWidget {
             styled_spans.push(Span::styled(ch.to_string(), style));
         }
 
-        // add flashing cursor if focused
-        if is_focused && self.cursor_visible {
-            styled_spans.push(Span::styled("|", Style::default().fg(theme.selected_color)));
-        } else if is_focused {
-            styled_spans.push(Span::styled(" ", Style::default()));
-        }
-
         let styled_line = Line::from(styled_spans);
 
         // border color depends on focus state
@@ -681,12 +1839,21 @@ impl SearchWidget {
             Style::default().fg(theme.muted_color)
         };
 
+        // results from a pivot aren't the query's, so the box says what they are
+        let mut title = match &self.pivot {
+            Some(pivot) => pivot.clone(),
+            None => "ClassQL Query".to_string(),
+        };
+        if !self.back_stack.is_empty() {
+            title.push_str(" | Esc: Back");
+        }
+
         let search_paragraph = Paragraph::new(styled_line)
             .style(Style::default().fg(theme.text_color))
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .title("ClassQL Query")
+                    .title(title)
                     .title_style(title_style)
                     .border_style(Style::default().fg(border_color)),
             );
@@ -694,6 +1861,29 @@ impl SearchWidget {
         frame.render_widget(search_paragraph, search_area);
     }
 
+    /// Get how many results fit in the grid
+    ///
+    /// Arguments:
+    /// --- ---
+    /// area -> The area of the frame
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// usize -> Results in the rows of the 3-column grid that fit, at least one row
+    /// --- ---
+    ///
+    fn results_that_fit(area: Rect) -> usize {
+        // the grid starts below the search bar and leaves room for the status and help bars
+        let logo_height = 7;
+        let search_y = logo_height + 6;
+        let search_height = 3;
+        let results_y: u16 = search_y + search_height + 1;
+        let cell_height = 7_u16;
+        let rows = (area.height.saturating_sub(results_y + 10) / cell_height).max(1) as usize;
+        rows * 3
+    }
+
     /// Render the query results in a 3-column grid
     ///
     /// Arguments:
@@ -720,10 +1910,7 @@ impl SearchWidget {
         let search_height = 3;
         let results_y = search_y + search_height + 1;
 
-        // calculate available space for results
-        let available_height = frame.area().height.saturating_sub(results_y + 10);
         let cell_height = 7_u16;
-        let rows_to_show = (available_height / cell_height).max(1) as usize;
 
         // calculate grid dimensions
         let cell_width = 26_u16;
@@ -732,7 +1919,7 @@ impl SearchWidget {
         let grid_x = frame.area().width.saturating_sub(grid_width) / 2;
 
         // calculate how many items can actually fit
-        let max_items_that_fit = rows_to_show * cols;
+        let max_items_that_fit = Self::results_that_fit(frame.area());
 
         // apply scroll offset and get visible classes
         let visible_classes: Vec<(usize, &Class)> = self
@@ -801,18 +1988,61 @@ impl SearchWidget {
             }
             .intersection(frame.area());
 
-            // border color depends on selection state
-            let border_color = if is_selected {
-                theme.selected_color
+            // the selected card gets a thick border too, not just a different color
+            let (border_color, border_type) = if is_selected {
+                (theme.selected_color, BorderType::Thick)
             } else {
-                theme.muted_color
+                (theme.muted_color, BorderType::Plain)
             };
 
-            let card = Paragraph::new(styled_lines).block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .border_style(Style::default().fg(border_color)),
-            );
+            let mut block = Block::default()
+                .borders(Borders::ALL)
+                .border_type(border_type)
+                .border_style(Style::default().fg(border_color));
+
+            // with the diff on, changed cards are labeled in their top border
+            let change = match (&self.last_diff, self.show_diff) {
+                (Some(diff), true) => diff.change_for(class),
+                _ => None,
+            };
+            if let Some(change) = change {
+                let (label, color) = match change {
+                    RowChange::Added => ("+ new".to_string(), theme.success_color),
+                    RowChange::Seats(seats) => (seats.label(), theme.warning_color),
+                };
+                block = block.title(Span::styled(
+                    format!(" {} ", label),
+                    Style::default().fg(color).add_modifier(Modifier::BOLD),
+                ));
+            }
+
+            // a group of duplicates is counted on its first card
+            if let Some(group) = self.duplicates.groups.get(&class.unique_id()) {
+                let label = if self.expanded_duplicates.contains(&class.unique_id()) {
+                    format!(" ▾ {} same time ", group.len())
+                } else {
+                    format!(" +{} same time ", group.len() - 1)
+                };
+                block = block.title_bottom(
+                    Line::from(Span::styled(
+                        label,
+                        Style::default()
+                            .fg(theme.info_color)
+                            .add_modifier(Modifier::BOLD),
+                    ))
+                    .right_aligned(),
+                );
+            }
+
+            // sections running only part of the term say which part on their card
+            if let Some(session) = class.session_label() {
+                block = block.title_bottom(Line::from(Span::styled(
+                    format!(" {} ", session),
+                    Style::default().fg(theme.warning_color),
+                )));
+            }
+
+            let card = Paragraph::new(styled_lines).block(block);
 
             frame.render_widget(card, cell_area);
         }
@@ -820,7 +2050,263 @@ impl SearchWidget {
         max_items_that_fit
     }
 
-    /// Render the completion dropdown
+    /// Render the status bar with the last search's result count and timings
+    ///
+    /// Sits just above the help bar and turns the warning color when the search was slow
+    /// enough to be logged
+    ///
+    /// Arguments:
+    /// --- ---
+    /// frame -> the frame to render to
+    /// theme -> the current theme
+    /// --- ---
+    ///
+    /// Returns: None
+    ///
+    fn render_status_bar(&self, frame: &mut Frame, theme: &Theme) {
+        let timing = match &self.last_timing {
+            Some(timing) => timing,
+            None => return,
+        };
+
+        let results = self.query_results.len().to_string();
+        let compile = format_duration(timing.compile);
+        let execute = format_duration(timing.execute);
+        let mut status_text = String::new();
+        if let Some(profile) = self.profile.as_ref().filter(|p| !p.constraints.is_empty()) {
+            let count = profile.constraints.len().to_string();
+            status_text.push_str(&i18n::tr(
+                "status-profile",
+                "Profile: {name} (+{count} constraints)",
+                &[("name", &profile.name), ("count", &count)],
+            ));
+            status_text.push_str(" | ");
+        }
+        if let Some(filter) = &self.result_filter {
+            let caret = if self.editing_filter { "_" } else { "" };
+            let filter = format!("{}{}", filter, caret);
+            let total = self.unfiltered_results.len().to_string();
+            status_text.push_str(&i18n::tr(
+                "status-result-filter",
+                "Filter: {filter} ({results} of {total})",
+                &[
+                    ("filter", &filter),
+                    ("results", &results),
+                    ("total", &total),
+                ],
+            ));
+            status_text.push_str(" | ");
+        }
+        status_text.push_str(&i18n::tr(
+            "status-query-timing",
+            "{results} results | compiled in {compile} | ran in {execute}",
+            &[
+                ("results", &results),
+                ("compile", &compile),
+                ("execute", &execute),
+            ],
+        ));
+        if self.last_truncated.is_some() {
+            status_text.push_str(" | ");
+            status_text.push_str(&i18n::tr(
+                "status-truncated",
+                "results truncated, refine your query",
+                &[],
+            ));
+        }
+        let hidden = self.duplicates.hidden(&self.expanded_duplicates);
+        if hidden > 0 {
+            let count = hidden.to_string();
+            status_text.push_str(" | ");
+            status_text.push_str(&i18n::tr(
+                "status-duplicates",
+                "{count} duplicate sections collapsed",
+                &[("count", &count)],
+            ));
+        }
+        if let (Some(diff), true) = (&self.last_diff, self.show_diff) {
+            let added = diff.added.len().to_string();
+            let removed = diff.removed.len().to_string();
+            let seats = diff.seat_changes.len().to_string();
+            status_text.push_str(" | ");
+            status_text.push_str(&i18n::tr(
+                "status-result-diff",
+                "vs last run: {added} new, {removed} gone, {seats} seat changes",
+                &[("added", &added), ("removed", &removed), ("seats", &seats)],
+            ));
+        }
+        let advice = self
+            .last_explanation
+            .as_ref()
+            .map_or(0, |explanation| explanation.advice.len());
+        if advice > 0 {
+            let count = advice.to_string();
+            status_text.push_str(" | ");
+            status_text.push_str(&i18n::tr(
+                "status-index-advice",
+                "{count} index suggestions (Alt+X)",
+                &[("count", &count)],
+            ));
+        }
+        let color = if timing.total() >= self.slow_query_threshold {
+            theme.warning_color
+        } else {
+            theme.muted_color
+        };
+
+        let status_width = (status_text.chars().count() as u16).min(frame.area().width);
+        let status_area = Rect {
+            x: frame.area().width.saturating_sub(status_width) / 2,
+            y: frame.area().height.saturating_sub(3),
+            width: status_width,
+            height: 1,
+        }
+        .intersection(frame.area());
+
+        frame.render_widget(
+            Paragraph::new(status_text).style(Style::default().fg(color)),
+            status_area,
+        );
+    }
+
+    /// Render a one-line description of the keyword at the cursor beneath the search bar, or
+    /// how to move between a snippet's placeholders while filling them
+    ///
+    /// Arguments:
+    /// --- ---
+    /// frame -> the frame to render to
+    /// theme -> the current theme
+    /// --- ---
+    ///
+    /// Returns: None
+    ///
+    fn render_keyword_hint(&self, frame: &mut Frame, theme: &Theme) {
+        // sits in the gap between the search bar and the results
+        let logo_height = 7;
+        let search_y = logo_height + 6;
+        let search_height = 3;
+        let hint_y = search_y + search_height;
+
+        let label_style = Style::default()
+            .fg(theme.info_color)
+            .add_modifier(Modifier::BOLD);
+        let spans = if let Some(snippet) = &self.snippet {
+            vec![
+                Span::styled("snippet", label_style),
+                Span::styled(
+                    format!(": placeholder {} of {}", snippet.current + 1, snippet.len()),
+                    Style::default().fg(theme.text_color),
+                ),
+                Span::styled(
+                    "  Tab: next | Shift+Tab: previous | Esc: done",
+                    Style::default().fg(theme.muted_color),
+                ),
+            ]
+        } else {
+            let info = match self.keyword_at_cursor() {
+                Some(info) => info,
+                None => return,
+            };
+            let mut spans = vec![
+                Span::styled(info.name, label_style),
+                Span::styled(
+                    format!(": {}", info.description),
+                    Style::default().fg(theme.text_color),
+                ),
+            ];
+            if let Some(example) = info.examples.first() {
+                spans.push(Span::styled(
+                    format!("  e.g. {}", example),
+                    Style::default().fg(theme.muted_color),
+                ));
+            }
+            spans
+        };
+
+        let hint_line = Line::from(spans);
+        let hint_width = (hint_line.width() as u16).min(frame.area().width);
+        let hint_area = Rect {
+            x: frame.area().width.saturating_sub(hint_width) / 2,
+            y: hint_y,
+            width: hint_width,
+            height: 1,
+        }
+        .intersection(frame.area());
+
+        frame.render_widget(Paragraph::new(hint_line), hint_area);
+    }
+
+    /// Render the live results preview beneath the search bar
+    ///
+    /// Arguments:
+    /// --- ---
+    /// frame -> the frame to render to
+    /// theme -> the current theme
+    /// --- ---
+    ///
+    /// Returns: None
+    ///
+    fn render_live_preview(&self, frame: &mut Frame, theme: &Theme) {
+        // position the preview list where the results grid would start
+        let logo_height = 7;
+        let search_y = logo_height + 6;
+        let search_height = 3;
+        let preview_y = search_y + search_height + 1;
+
+        // match the width of the 3-column results grid
+        let preview_width = 82_u16;
+        let preview_results = self.preview_results.get().unwrap_or_default();
+        let preview_height = (preview_results.len() as u16 + 2)
+            .min(frame.area().height.saturating_sub(preview_y + 2));
+
+        let preview_area = Rect {
+            x: frame.area().width.saturating_sub(preview_width) / 2,
+            y: preview_y,
+            width: preview_width,
+            height: preview_height,
+        }
+        .intersection(frame.area());
+
+        let mut styled_lines = Vec::new();
+        for class in preview_results.iter() {
+            let code = format!(
+                "{} {}-{}",
+                class.subject_code, class.course_number, class.section_sequence
+            );
+            let professor = class.professor_name.as_deref().unwrap_or("TBA");
+            styled_lines.push(Line::from(vec![
+                Span::styled(
+                    format!("{:<14}", code),
+                    Style::default()
+                        .fg(theme.title_color)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!("{:<40.38}", class.title),
+                    Style::default().fg(theme.text_color),
+                ),
+                Span::styled(
+                    professor.to_string(),
+                    Style::default().fg(theme.warning_color),
+                ),
+            ]));
+        }
+
+        let preview_paragraph = Paragraph::new(styled_lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Preview (Enter for all results) ")
+                .title_style(Style::default().fg(theme.muted_color))
+                .border_style(Style::default().fg(theme.muted_color)),
+        );
+
+        frame.render_widget(preview_paragraph, preview_area);
+    }
+
+    /// Render the completion popup
+    ///
+    /// Each suggestion gets a row of three columns: the text inserted, what kind of word it
+    /// is, and what it does. The list scrolls to keep the selection in view
     ///
     /// Arguments:
     /// --- ---
@@ -835,7 +2321,7 @@ impl SearchWidget {
             return;
         }
 
-        let dropdown_width = 50;
+        let dropdown_width = COMPLETION_POPUP_WIDTH.min(frame.area().width);
 
         // position below the search bar
         let logo_height = 7;
@@ -858,18 +2344,65 @@ impl SearchWidget {
         }
         .intersection(frame.area());
 
+        // scroll so the selection is the last row shown once it passes the bottom
+        let rows = dropdown_area.height.saturating_sub(2) as usize;
+        let selected = self.completion.completion_index.unwrap_or(0);
+        let scroll = (selected + 1).saturating_sub(rows);
+
+        // size the columns to the widest suggestion and kind, the description gets the rest
+        let text_width = self
+            .completion
+            .completions
+            .iter()
+            .map(|completion| completion.text.chars().count())
+            .max()
+            .unwrap_or(0)
+            .min(COMPLETION_TEXT_WIDTH);
+        let kind_width = self
+            .completion
+            .completions
+            .iter()
+            .map(|completion| completion.kind.label().len())
+            .max()
+            .unwrap_or(0);
+        let description_width =
+            (dropdown_area.width as usize).saturating_sub(2 + 1 + text_width + 2 + kind_width + 2);
+
         let mut styled_lines = Vec::new();
-        for (i, completion) in self.completion.completions.iter().enumerate() {
-            let style = if Some(i) == self.completion.completion_index {
-                Style::default()
-                    .fg(theme.background_color)
-                    .bg(theme.selected_color)
-            } else {
-                Style::default()
-                    .fg(theme.text_color)
-                    .bg(theme.background_color)
+        for (i, completion) in self
+            .completion
+            .completions
+            .iter()
+            .enumerate()
+            .skip(scroll)
+            .take(rows)
+        {
+            let is_selected = Some(i) == self.completion.completion_index;
+            let column_style = |color| {
+                if is_selected {
+                    Style::default()
+                        .fg(theme.background_color)
+                        .bg(theme.selected_color)
+                } else {
+                    Style::default().fg(color).bg(theme.background_color)
+                }
             };
-            styled_lines.push(Line::from(Span::styled(completion.clone(), style)));
+            let text = truncate(&completion.text, text_width);
+            let description = truncate(&completion.description, description_width);
+            styled_lines.push(Line::from(vec![
+                Span::styled(
+                    format!(" {:<width$}  ", text, width = text_width),
+                    column_style(theme.text_color),
+                ),
+                Span::styled(
+                    format!("{:<width$}  ", completion.kind.label(), width = kind_width),
+                    column_style(theme.info_color),
+                ),
+                Span::styled(
+                    format!("{:<width$}", description, width = description_width),
+                    column_style(theme.muted_color),
+                ),
+            ]));
         }
 
         // first, clear the area to cover results below with solid background
@@ -878,7 +2411,7 @@ impl SearchWidget {
         let dropdown_paragraph = Paragraph::new(styled_lines).block(
             Block::default()
                 .borders(Borders::ALL)
-                .title("Suggestions (↑↓ to navigate, Enter to select)")
+                .title("Suggestions (↑↓ to move, Tab to accept, Esc to close)")
                 .title_style(Style::default().fg(theme.warning_color))
                 .border_style(Style::default().fg(theme.warning_color))
                 .style(Style::default().bg(theme.background_color)),
@@ -931,10 +2464,19 @@ impl Widget for SearchWidget {
         // show "Searching..." indicator OR results
         if self.is_searching {
             Self::render_searching_indicator(frame, theme);
+        } else if self.is_showing_preview() {
+            self.render_live_preview(frame, theme);
+            self.max_items_that_fit.set(0);
         } else {
             // render results and update max_items_that_fit
             let max_items = self.render_query_results(frame, theme);
             self.max_items_that_fit.set(max_items);
+            self.render_status_bar(frame, theme);
+        }
+
+        // describe the keyword being typed
+        if self.is_query_input() && !self.completion.show_completions {
+            self.render_keyword_hint(frame, theme);
         }
 
         // render completion dropdown if visible
@@ -950,10 +2492,15 @@ impl Widget for SearchWidget {
         }
 
         // handle based on current focus
-        match self.focus {
+        let action = match self.focus {
             SearchFocus::QueryInput => self.handle_query_input_key(key),
             SearchFocus::ResultsBrowse => self.handle_results_browse_key(key),
+        };
+        // leaving the results stops typing into the filter
+        if self.focus != SearchFocus::ResultsBrowse {
+            self.editing_filter = false;
         }
+        action
     }
 
     /// Return the focus mode(s) this widget handles
@@ -962,4 +2509,50 @@ impl Widget for SearchWidget {
     fn focus_modes(&self) -> Vec<FocusMode> {
         vec![FocusMode::QueryInput, FocusMode::ResultsBrowse]
     }
+
+    /// Follow the app to the query box or the results
+    ///
+    fn on_focus(&mut self, mode: &FocusMode) {
+        self.set_focus(mode.clone());
+    }
+
+    /// Close the completion dropdown, stop typing into the result filter, and stop filling a
+    /// snippet's placeholders, so none is still open when the search is come back to
+    ///
+    fn on_blur(&mut self) {
+        self.completion.show_completions = false;
+        self.editing_filter = false;
+        self.snippet = None;
+    }
+
+    /// Keep the selected result in view when the grid gets fewer rows
+    ///
+    fn on_resize(&mut self, area: Rect) {
+        let fit = Self::results_that_fit(area);
+        self.max_items_that_fit.set(fit);
+        if self.selected_result >= self.results_scroll + fit {
+            // scroll by whole rows, leaving the selection on the last row shown
+            let selected_row = self.selected_result / 3;
+            self.results_scroll = (selected_row + 1).saturating_sub(fit / 3) * 3;
+        }
+    }
+
+    /// Put the cursor after the input while typing a query, or in the snippet placeholder
+    /// being filled
+    ///
+    fn cursor_position(&self, area: Rect) -> Option<Position> {
+        if self.focus != SearchFocus::QueryInput {
+            return None;
+        }
+        let search_area = Self::search_bar_area(area);
+        let (scroll_offset, visible_width) = self.visible_input();
+        let shown = (self.cursor_index() - scroll_offset).min(visible_width);
+        // "…" replaces the "> " prefix once the input scrolls
+        let prefix = if scroll_offset > 0 { 1 } else { 2 };
+        Some(Position {
+            x: (search_area.x + 1 + prefix + shown as u16)
+                .min(search_area.right().saturating_sub(2)),
+            y: search_area.y + 1,
+        })
+    }
 }
@@ -2,16 +2,21 @@
 ///
 /// Settings widget with encapsulated state, input handling, and rendering
 ///
-/// Handles theme selection, school/term pickers, and sync functionality
+/// Handles theme selection, school/term pickers, sync functionality, and campus aliases
 ///
 /// Contains:
 /// --- ---
 /// SettingsWidget -> Widget for settings functionality
 /// SettingsAction -> Actions returned by settings widget
 /// --- ---
+use crate::data::campus_aliases::{parse_campus_alias, CampusAliases};
 use crate::data::sql::{School, Term};
+use crate::dsl::codegen::{ResultOrder, DEFAULT_MAX_ROWS};
 use crate::tui::state::{ErrorType, FocusMode};
 use crate::tui::themes::{Theme, ThemePalette};
+use crate::tui::widgets::schedule::{
+    DEFAULT_GRID_END_HOUR, DEFAULT_GRID_START_HOUR, DEFAULT_SLOT_MINUTES,
+};
 use crate::tui::widgets::traits::{KeyAction, Widget};
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::layout::Rect;
@@ -23,6 +28,21 @@ use ratatui::Frame;
 /// Maximum visible items in picker dropdowns
 const PICKER_MAX_VISIBLE: usize = 6;
 
+/// Index of the last settings option (Campus Aliases)
+pub const LAST_SETTINGS_INDEX: usize = 14;
+
+/// Slot sizes (in minutes) selectable for the schedule grid
+const SLOT_SIZE_OPTIONS: [usize; 3] = [15, 30, 60];
+
+/// Result caps selectable for searches, None for no cap
+const MAX_ROWS_OPTIONS: [Option<usize>; 5] = [
+    Some(500),
+    Some(1_000),
+    Some(DEFAULT_MAX_ROWS),
+    Some(20_000),
+    None,
+];
+
 /// Settings widget with encapsulated state
 ///
 /// Manages application settings including theme selection, school/term pickers
@@ -31,7 +51,7 @@ const PICKER_MAX_VISIBLE: usize = 6;
 /// Fields:
 /// --- ---
 /// current_theme -> The current theme palette
-/// selected_index -> Index of currently selected settings option (0=theme, 1=school, 2=term, 3=sync)
+/// selected_index -> Index of currently selected settings option (0=theme, 1=school, 2=term, 3=sync, 4=live preview, 5=notifications, 6=restore session, 7=grid start, 8=grid end, 9=slot size, 10=result order, 11=max results, 12=maintain database, 13=stats, 14=campus aliases)
 /// available_schools -> List of available schools from database
 /// selected_school_index -> Index of currently selected school in picker
 /// selected_school_id -> ID of the currently selected school
@@ -44,6 +64,17 @@ const PICKER_MAX_VISIBLE: usize = 6;
 /// is_syncing -> Whether a sync operation is currently in progress
 /// school_picker_open -> Whether school picker dropdown is open
 /// term_picker_open -> Whether term picker dropdown is open
+/// live_preview -> Whether search-as-you-type results preview is enabled
+/// notifications -> Whether watch-list events are sent as desktop notifications
+/// restore_session -> Whether the next launch starts where this session left off
+/// grid_start_hour -> First hour shown in the schedule grid
+/// grid_end_hour -> Hour the schedule grid ends at
+/// slot_minutes -> Length of each schedule grid slot in minutes
+/// result_order -> The order search results are returned in
+/// max_rows -> The most results a search returns, None for no cap
+/// campus_aliases -> Short names for campuses, resolved in campus queries
+/// selected_alias_index -> Index of the alias picked for removal
+/// alias_input -> Text of the alias being typed ("alias = campus"), None when not adding one
 /// --- ---
 ///
 pub struct SettingsWidget {
@@ -61,6 +92,17 @@ pub struct SettingsWidget {
     pub is_syncing: bool,
     pub school_picker_open: bool,
     pub term_picker_open: bool,
+    pub live_preview: bool,
+    pub notifications: bool,
+    pub restore_session: bool,
+    pub grid_start_hour: usize,
+    pub grid_end_hour: usize,
+    pub slot_minutes: usize,
+    pub result_order: ResultOrder,
+    pub max_rows: Option<usize>,
+    pub campus_aliases: CampusAliases,
+    pub selected_alias_index: usize,
+    pub alias_input: Option<String>,
 }
 
 /// Action returned by settings widget for app-level handling
@@ -72,6 +114,16 @@ pub struct SettingsWidget {
 /// TermSelected -> Term was selected
 /// SyncRequested -> Database sync was requested
 /// ThemeChanged -> Theme palette was changed
+/// LivePreviewToggled -> Live results preview was turned on or off
+/// NotificationsToggled -> Desktop notifications were turned on or off
+/// RestoreSessionToggled -> Session restore on the next launch was turned on or off
+/// ScheduleGridChanged -> Schedule grid time range or slot size was changed
+/// ResultOrderChanged -> The order search results are returned in was changed
+/// MaxRowsChanged -> The most results a search returns was changed
+/// TermArchived -> Term was archived from the picker, caller should persist it and reload terms
+/// MaintenanceRequested -> Database maintenance (vacuum, integrity check, reindex) was requested
+/// StatsRequested -> Database statistics screen was requested
+/// CampusAliasesChanged -> A campus alias was added or removed, caller should save them
 /// --- ---
 ///
 #[derive(Debug, Clone)]
@@ -87,6 +139,24 @@ pub enum SettingsAction {
     },
     SyncRequested,
     ThemeChanged(ThemePalette),
+    LivePreviewToggled(bool),
+    NotificationsToggled(bool),
+    RestoreSessionToggled(bool),
+    ScheduleGridChanged {
+        start_hour: usize,
+        end_hour: usize,
+        slot_minutes: usize,
+    },
+    ResultOrderChanged(ResultOrder),
+    MaxRowsChanged(Option<usize>),
+    TermArchived {
+        school_id: String,
+        term_id: String,
+        term_name: String,
+    },
+    MaintenanceRequested,
+    StatsRequested,
+    CampusAliasesChanged(CampusAliases),
 }
 
 impl SettingsWidget {
@@ -115,6 +185,17 @@ impl SettingsWidget {
             is_syncing: false,
             school_picker_open: false,
             term_picker_open: false,
+            live_preview: false,
+            notifications: false,
+            restore_session: true,
+            grid_start_hour: DEFAULT_GRID_START_HOUR,
+            grid_end_hour: DEFAULT_GRID_END_HOUR,
+            slot_minutes: DEFAULT_SLOT_MINUTES,
+            result_order: ResultOrder::Database,
+            max_rows: Some(DEFAULT_MAX_ROWS),
+            campus_aliases: CampusAliases::default(),
+            selected_alias_index: 0,
+            alias_input: None,
         }
     }
 
@@ -168,6 +249,20 @@ impl SettingsWidget {
         self.last_sync_time = time;
     }
 
+    /// Set the campus aliases
+    ///
+    /// Arguments:
+    /// --- ---
+    /// aliases -> the aliases loaded from the app database
+    /// --- ---
+    ///
+    /// Returns: None
+    ///
+    pub fn set_campus_aliases(&mut self, aliases: CampusAliases) {
+        self.campus_aliases = aliases;
+        self.selected_alias_index = 0;
+    }
+
     /// Handle key and return any action that needs to be taken
     ///
     /// Arguments:
@@ -185,6 +280,7 @@ impl SettingsWidget {
             KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                 (KeyAction::Exit, SettingsAction::None)
             }
+            _ if self.alias_input.is_some() => self.handle_alias_input(key),
             KeyCode::Esc => {
                 if self.school_picker_open {
                     self.school_picker_open = false;
@@ -193,10 +289,7 @@ impl SettingsWidget {
                     self.term_picker_open = false;
                     (KeyAction::Continue, SettingsAction::None)
                 } else {
-                    (
-                        KeyAction::Navigate(FocusMode::MainMenu),
-                        SettingsAction::None,
-                    )
+                    (KeyAction::Back, SettingsAction::None)
                 }
             }
             KeyCode::Up => {
@@ -242,13 +335,37 @@ impl SettingsWidget {
                         }
                     }
                 } else {
-                    let max_index = 3; // theme, school, term, sync
-                    if self.selected_index < max_index {
+                    // theme, school, term, sync, live preview, notifications, restore session,
+                    // grid start/end, slot size, result order, max results, maintain, stats
+                    if self.selected_index < LAST_SETTINGS_INDEX {
                         self.selected_index += 1;
                     }
                 }
                 (KeyAction::Continue, SettingsAction::None)
             }
+            KeyCode::Char('a') | KeyCode::Char('A') if self.term_picker_open => {
+                let archived = self
+                    .selected_school_id
+                    .clone()
+                    .zip(self.available_terms.get(self.selected_term_index));
+                let Some((school_id, term)) = archived else {
+                    return (KeyAction::Continue, SettingsAction::None);
+                };
+                let term_id = term.id.clone();
+                let term_name = term.name.clone();
+                if self.selected_term_id.as_ref() == Some(&term_id) {
+                    self.selected_term_id = None;
+                }
+                self.term_picker_open = false;
+                (
+                    KeyAction::Continue,
+                    SettingsAction::TermArchived {
+                        school_id,
+                        term_id,
+                        term_name,
+                    },
+                )
+            }
             KeyCode::Left | KeyCode::Right => {
                 // change theme when on Theme option
                 if self.selected_index == 0 {
@@ -275,6 +392,75 @@ impl SettingsWidget {
                         KeyAction::Continue,
                         SettingsAction::ThemeChanged(self.current_theme),
                     )
+                } else if self.selected_index == 4 {
+                    self.live_preview = !self.live_preview;
+                    (
+                        KeyAction::Continue,
+                        SettingsAction::LivePreviewToggled(self.live_preview),
+                    )
+                } else if self.selected_index == 5 {
+                    self.notifications = !self.notifications;
+                    (
+                        KeyAction::Continue,
+                        SettingsAction::NotificationsToggled(self.notifications),
+                    )
+                } else if self.selected_index == 6 {
+                    self.restore_session = !self.restore_session;
+                    (
+                        KeyAction::Continue,
+                        SettingsAction::RestoreSessionToggled(self.restore_session),
+                    )
+                } else if (7..=9).contains(&self.selected_index) {
+                    self.adjust_grid(key.code == KeyCode::Right);
+                    (
+                        KeyAction::Continue,
+                        SettingsAction::ScheduleGridChanged {
+                            start_hour: self.grid_start_hour,
+                            end_hour: self.grid_end_hour,
+                            slot_minutes: self.slot_minutes,
+                        },
+                    )
+                } else if self.selected_index == 14 {
+                    let count = self.campus_aliases.aliases.len();
+                    if count > 0 {
+                        self.selected_alias_index = if key.code == KeyCode::Left {
+                            (self.selected_alias_index + count - 1) % count
+                        } else {
+                            (self.selected_alias_index + 1) % count
+                        };
+                    }
+                    (KeyAction::Continue, SettingsAction::None)
+                } else if self.selected_index == 10 {
+                    let orders = ResultOrder::all();
+                    let current_idx = orders
+                        .iter()
+                        .position(|&o| o == self.result_order)
+                        .unwrap_or(0);
+                    let new_idx = if key.code == KeyCode::Left {
+                        (current_idx + orders.len() - 1) % orders.len()
+                    } else {
+                        (current_idx + 1) % orders.len()
+                    };
+                    self.result_order = orders[new_idx];
+                    (
+                        KeyAction::Continue,
+                        SettingsAction::ResultOrderChanged(self.result_order),
+                    )
+                } else if self.selected_index == 11 {
+                    let current_idx = MAX_ROWS_OPTIONS
+                        .iter()
+                        .position(|&o| o == self.max_rows)
+                        .unwrap_or(0);
+                    let new_idx = if key.code == KeyCode::Left {
+                        current_idx.saturating_sub(1)
+                    } else {
+                        (current_idx + 1).min(MAX_ROWS_OPTIONS.len() - 1)
+                    };
+                    self.max_rows = MAX_ROWS_OPTIONS[new_idx];
+                    (
+                        KeyAction::Continue,
+                        SettingsAction::MaxRowsChanged(self.max_rows),
+                    )
                 } else {
                     (KeyAction::Continue, SettingsAction::None)
                 }
@@ -376,13 +562,194 @@ impl SettingsWidget {
                             (KeyAction::Continue, SettingsAction::None)
                         }
                     }
+                    4 => {
+                        // toggle live preview
+                        self.live_preview = !self.live_preview;
+                        (
+                            KeyAction::Continue,
+                            SettingsAction::LivePreviewToggled(self.live_preview),
+                        )
+                    }
+                    5 => {
+                        // toggle desktop notifications
+                        self.notifications = !self.notifications;
+                        (
+                            KeyAction::Continue,
+                            SettingsAction::NotificationsToggled(self.notifications),
+                        )
+                    }
+                    6 => {
+                        // toggle session restore
+                        self.restore_session = !self.restore_session;
+                        (
+                            KeyAction::Continue,
+                            SettingsAction::RestoreSessionToggled(self.restore_session),
+                        )
+                    }
+                    12 => {
+                        // maintain database, not while a sync is writing to it
+                        if self.is_syncing {
+                            (KeyAction::Continue, SettingsAction::None)
+                        } else {
+                            (KeyAction::Continue, SettingsAction::MaintenanceRequested)
+                        }
+                    }
+                    13 => (KeyAction::Continue, SettingsAction::StatsRequested),
+                    14 => {
+                        // start typing a new alias
+                        self.alias_input = Some(String::new());
+                        (KeyAction::Continue, SettingsAction::None)
+                    }
                     _ => (KeyAction::Continue, SettingsAction::None),
                 }
             }
+            KeyCode::Delete | KeyCode::Backspace if self.selected_index == 14 => {
+                let Some(alias) = self
+                    .campus_aliases
+                    .aliases
+                    .get(self.selected_alias_index)
+                    .map(|entry| entry.alias.clone())
+                else {
+                    return (KeyAction::Continue, SettingsAction::None);
+                };
+                self.campus_aliases.remove(&alias);
+                self.selected_alias_index = self
+                    .selected_alias_index
+                    .min(self.campus_aliases.aliases.len().saturating_sub(1));
+                (
+                    KeyAction::ShowToast {
+                        message: format!("Removed campus alias: {}", alias),
+                        error_type: ErrorType::Success,
+                    },
+                    SettingsAction::CampusAliasesChanged(self.campus_aliases.clone()),
+                )
+            }
+            _ => (KeyAction::Continue, SettingsAction::None),
+        }
+    }
+
+    /// Handle a key while a campus alias is being typed
+    ///
+    /// Arguments:
+    /// --- ---
+    /// key -> the key event to handle
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// (KeyAction, SettingsAction) -> tuple of key action and settings action
+    /// --- ---
+    ///
+    fn handle_alias_input(&mut self, key: KeyEvent) -> (KeyAction, SettingsAction) {
+        let Some(input) = self.alias_input.as_mut() else {
+            return (KeyAction::Continue, SettingsAction::None);
+        };
+        match key.code {
+            KeyCode::Esc => {
+                self.alias_input = None;
+                (KeyAction::Continue, SettingsAction::None)
+            }
+            KeyCode::Backspace => {
+                input.pop();
+                (KeyAction::Continue, SettingsAction::None)
+            }
+            KeyCode::Char(c) => {
+                input.push(c);
+                (KeyAction::Continue, SettingsAction::None)
+            }
+            KeyCode::Enter => match parse_campus_alias(input) {
+                Ok(entry) => {
+                    self.alias_input = None;
+                    self.campus_aliases.set(&entry.alias, &entry.campus);
+                    self.selected_alias_index = self
+                        .campus_aliases
+                        .aliases
+                        .iter()
+                        .position(|known| known.alias == entry.alias)
+                        .unwrap_or(0);
+                    (
+                        KeyAction::ShowToast {
+                            message: format!("Campus alias: {} → {}", entry.alias, entry.campus),
+                            error_type: ErrorType::Success,
+                        },
+                        SettingsAction::CampusAliasesChanged(self.campus_aliases.clone()),
+                    )
+                }
+                // keep the text so it can be fixed
+                Err(message) => (
+                    KeyAction::ShowToast {
+                        message,
+                        error_type: ErrorType::Warning,
+                    },
+                    SettingsAction::None,
+                ),
+            },
             _ => (KeyAction::Continue, SettingsAction::None),
         }
     }
 
+    /// Adjust the selected schedule grid option by one step
+    ///
+    /// Arguments:
+    /// --- ---
+    /// increase -> whether to step the value up (true) or down (false)
+    /// --- ---
+    ///
+    /// Returns: None
+    ///
+    fn adjust_grid(&mut self, increase: bool) {
+        match self.selected_index {
+            7 => {
+                // start hour must stay before the end hour
+                if increase && self.grid_start_hour + 1 < self.grid_end_hour {
+                    self.grid_start_hour += 1;
+                } else if !increase && self.grid_start_hour > 0 {
+                    self.grid_start_hour -= 1;
+                }
+            }
+            8 => {
+                // end hour must stay after the start hour
+                if increase && self.grid_end_hour < 24 {
+                    self.grid_end_hour += 1;
+                } else if !increase && self.grid_end_hour > self.grid_start_hour + 1 {
+                    self.grid_end_hour -= 1;
+                }
+            }
+            9 => {
+                let current_idx = SLOT_SIZE_OPTIONS
+                    .iter()
+                    .position(|&m| m == self.slot_minutes)
+                    .unwrap_or(1);
+                let new_idx = if increase {
+                    (current_idx + 1).min(SLOT_SIZE_OPTIONS.len() - 1)
+                } else {
+                    current_idx.saturating_sub(1)
+                };
+                self.slot_minutes = SLOT_SIZE_OPTIONS[new_idx];
+            }
+            _ => {}
+        }
+    }
+
+    /// Count the lines the alias list and input add below the Campus Aliases option
+    ///
+    /// Arguments: None
+    ///
+    /// Returns:
+    /// --- ---
+    /// usize -> lines for the aliases (shown while the option is selected) and the input
+    /// --- ---
+    ///
+    fn alias_lines(&self) -> usize {
+        let listed = if self.selected_index == 14 {
+            self.campus_aliases.aliases.len()
+        } else {
+            0
+        };
+        // the option itself and its blank line fit in the base height
+        listed + usize::from(self.alias_input.is_some())
+    }
+
     /// Mark sync as complete
     ///
     /// Arguments: None
@@ -405,7 +772,7 @@ impl SettingsWidget {
     ///
     fn render_settings(&self, frame: &mut Frame, theme: &Theme) {
         let settings_width = 60_u16;
-        let base_height = 16_u16;
+        let base_height = 32_u16;
 
         // expand height if school or term picker is open
         let school_picker_items = self.available_schools.len().min(8);
@@ -416,7 +783,7 @@ impl SettingsWidget {
             base_height + term_picker_items as u16 + 2
         } else {
             base_height
-        };
+        } + self.alias_lines() as u16;
 
         // position settings below the logo
         let logo_height = 7_u16;
@@ -576,7 +943,7 @@ impl SettingsWidget {
         };
 
         let term_hint = if self.term_picker_open {
-            " (↑↓ pick, Enter confirm)"
+            " (↑↓ pick, Enter confirm, a archive)"
         } else {
             " (Enter to select)"
         };
@@ -665,6 +1032,278 @@ impl SettingsWidget {
         ]));
         lines.push(Line::from(""));
 
+        // --- live preview option ---
+        let preview_prefix = if self.selected_index == 4 {
+            "▸ "
+        } else {
+            "  "
+        };
+        let preview_style = if self.selected_index == 4 {
+            Style::default()
+                .fg(theme.selected_color)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.text_color)
+        };
+        let preview_value = if self.live_preview { "On" } else { "Off" };
+        lines.push(Line::from(vec![
+            Span::styled(preview_prefix, preview_style),
+            Span::styled("Live Preview: ", preview_style),
+            Span::styled(preview_value, Style::default().fg(theme.warning_color)),
+            Span::styled(" (Enter to toggle)", Style::default().fg(theme.muted_color)),
+        ]));
+        lines.push(Line::from(""));
+
+        // --- desktop notifications option ---
+        let notify_prefix = if self.selected_index == 5 {
+            "▸ "
+        } else {
+            "  "
+        };
+        let notify_style = if self.selected_index == 5 {
+            Style::default()
+                .fg(theme.selected_color)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.text_color)
+        };
+        let notify_value = if self.notifications { "On" } else { "Off" };
+        lines.push(Line::from(vec![
+            Span::styled(notify_prefix, notify_style),
+            Span::styled("Desktop Notifications: ", notify_style),
+            Span::styled(notify_value, Style::default().fg(theme.warning_color)),
+            Span::styled(" (Enter to toggle)", Style::default().fg(theme.muted_color)),
+        ]));
+        // watch hooks are set in .env, so the option says where while it is selected
+        if self.selected_index == 5 {
+            lines.push(Line::from(Span::styled(
+                "    Watch hooks: set CLASSQL_WATCH_COMMAND or CLASSQL_WATCH_WEBHOOK in .env",
+                Style::default().fg(theme.muted_color),
+            )));
+        } else {
+            lines.push(Line::from(""));
+        }
+
+        // --- session restore option ---
+        let restore_prefix = if self.selected_index == 6 {
+            "▸ "
+        } else {
+            "  "
+        };
+        let restore_style = if self.selected_index == 6 {
+            Style::default()
+                .fg(theme.selected_color)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.text_color)
+        };
+        let restore_value = if self.restore_session {
+            "On"
+        } else {
+            "Off (start fresh)"
+        };
+        lines.push(Line::from(vec![
+            Span::styled(restore_prefix, restore_style),
+            Span::styled("Restore Session: ", restore_style),
+            Span::styled(restore_value, Style::default().fg(theme.warning_color)),
+            Span::styled(" (Enter to toggle)", Style::default().fg(theme.muted_color)),
+        ]));
+        lines.push(Line::from(""));
+
+        // --- schedule grid options ---
+        let format_hour = |hour: usize| match hour {
+            0 | 24 => "12am".to_string(),
+            12 => "12pm".to_string(),
+            h if h < 12 => format!("{}am", h),
+            h => format!("{}pm", h - 12),
+        };
+        let grid_options = [
+            (7, "Grid Start: ", format_hour(self.grid_start_hour)),
+            (8, "Grid End: ", format_hour(self.grid_end_hour)),
+            (9, "Slot Size: ", format!("{} min", self.slot_minutes)),
+        ];
+        for (index, label, value) in grid_options {
+            let prefix = if self.selected_index == index {
+                "▸ "
+            } else {
+                "  "
+            };
+            let style = if self.selected_index == index {
+                Style::default()
+                    .fg(theme.selected_color)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.text_color)
+            };
+            lines.push(Line::from(vec![
+                Span::styled(prefix, style),
+                Span::styled(label, style),
+                Span::styled(value, Style::default().fg(theme.warning_color)),
+                Span::styled(" (← → to change)", Style::default().fg(theme.muted_color)),
+            ]));
+        }
+        lines.push(Line::from(""));
+
+        // --- result order option ---
+        let order_prefix = if self.selected_index == 10 {
+            "▸ "
+        } else {
+            "  "
+        };
+        let order_style = if self.selected_index == 10 {
+            Style::default()
+                .fg(theme.selected_color)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.text_color)
+        };
+        lines.push(Line::from(vec![
+            Span::styled(order_prefix, order_style),
+            Span::styled("Result Order: ", order_style),
+            Span::styled(
+                self.result_order.as_str(),
+                Style::default().fg(theme.warning_color),
+            ),
+            Span::styled(" (← → to change)", Style::default().fg(theme.muted_color)),
+        ]));
+        lines.push(Line::from(""));
+
+        // --- max results option ---
+        let max_rows_prefix = if self.selected_index == 11 {
+            "▸ "
+        } else {
+            "  "
+        };
+        let max_rows_style = if self.selected_index == 11 {
+            Style::default()
+                .fg(theme.selected_color)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.text_color)
+        };
+        let max_rows = match self.max_rows {
+            Some(max_rows) => max_rows.to_string(),
+            None => "No limit".to_string(),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(max_rows_prefix, max_rows_style),
+            Span::styled("Max Results: ", max_rows_style),
+            Span::styled(max_rows, Style::default().fg(theme.warning_color)),
+            Span::styled(" (← → to change)", Style::default().fg(theme.muted_color)),
+        ]));
+        lines.push(Line::from(""));
+
+        // --- maintenance option ---
+        let maintain_prefix = if self.selected_index == 12 {
+            "▸ "
+        } else {
+            "  "
+        };
+        let maintain_style = if self.selected_index == 12 {
+            Style::default()
+                .fg(theme.selected_color)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.text_color)
+        };
+        lines.push(Line::from(vec![
+            Span::styled(maintain_prefix, maintain_style),
+            Span::styled("Maintain Database: ", maintain_style),
+            Span::styled(
+                "[Press Enter to compact]",
+                Style::default().fg(theme.success_color),
+            ),
+        ]));
+        lines.push(Line::from(""));
+
+        // --- statistics option ---
+        let stats_prefix = if self.selected_index == 13 {
+            "▸ "
+        } else {
+            "  "
+        };
+        let stats_style = if self.selected_index == 13 {
+            Style::default()
+                .fg(theme.selected_color)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.text_color)
+        };
+        lines.push(Line::from(vec![
+            Span::styled(stats_prefix, stats_style),
+            Span::styled("Database Stats: ", stats_style),
+            Span::styled(
+                "[Press Enter to view]",
+                Style::default().fg(theme.success_color),
+            ),
+        ]));
+        lines.push(Line::from(""));
+
+        // --- campus aliases option ---
+        let aliases_selected = self.selected_index == 14;
+        let aliases_prefix = if aliases_selected { "▸ " } else { "  " };
+        let aliases_style = if aliases_selected {
+            Style::default()
+                .fg(theme.selected_color)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.text_color)
+        };
+        let alias_count = self.campus_aliases.aliases.len();
+        let aliases_hint = if self.alias_input.is_some() {
+            " (Enter save, Esc cancel)"
+        } else if aliases_selected && alias_count > 0 {
+            " (Enter add, ← → pick, Del remove)"
+        } else {
+            " (Enter to add)"
+        };
+        lines.push(Line::from(vec![
+            Span::styled(aliases_prefix, aliases_style),
+            Span::styled("Campus Aliases: ", aliases_style),
+            Span::styled(
+                format!("{} defined", alias_count),
+                Style::default().fg(theme.warning_color),
+            ),
+            Span::styled(aliases_hint, Style::default().fg(theme.muted_color)),
+        ]));
+
+        // list the aliases while the option is selected
+        if aliases_selected {
+            for (i, entry) in self.campus_aliases.aliases.iter().enumerate() {
+                let is_selected = i == self.selected_alias_index && self.alias_input.is_none();
+                let prefix = if is_selected { "   ● " } else { "   ○ " };
+                let style = if is_selected {
+                    Style::default()
+                        .fg(theme.success_color)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(theme.text_color)
+                };
+                lines.push(Line::from(vec![
+                    Span::styled(prefix, style),
+                    Span::styled(format!("{} → {}", entry.alias, entry.campus), style),
+                ]));
+            }
+        }
+        if let Some(input) = &self.alias_input {
+            lines.push(Line::from(vec![
+                Span::styled("   + ", Style::default().fg(theme.info_color)),
+                Span::styled(
+                    if input.is_empty() {
+                        "main = Poughkeepsie Main Campus".to_string()
+                    } else {
+                        format!("{}_", input)
+                    },
+                    if input.is_empty() {
+                        Style::default().fg(theme.muted_color)
+                    } else {
+                        Style::default().fg(theme.text_color)
+                    },
+                ),
+            ]));
+        }
+        lines.push(Line::from(""));
+
         // --- last sync time ---
         let sync_time_display = match &self.last_sync_time {
             Some(time) => time.to_string(),
@@ -0,0 +1,224 @@
+/// src/tui/widgets/stats.rs
+///
+/// Database statistics widget rendering
+///
+/// Renders row counts, per-term section counts, file size, index usage, and
+/// recent syncs for the local database
+///
+/// Contains:
+/// --- ---
+/// StatsWidget -> Widget for the database statistics screen
+/// --- ---
+use crate::data::stats::DatabaseStats;
+use crate::tui::state::FocusMode;
+use crate::tui::themes::Theme;
+use crate::tui::widgets::traits::{KeyAction, Widget};
+use crate::utils::i18n;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+/// Width of the statistics box
+const STATS_WIDTH: u16 = 78;
+
+/// Number of lines moved by Page Up/Down
+const PAGE_SIZE: usize = 10;
+
+/// Stats widget for rendering database statistics
+///
+/// Fields:
+/// --- ---
+/// lines -> Report lines built from the last loaded statistics
+/// error -> Error message if the statistics could not be loaded
+/// scroll -> Index of the first visible line
+/// --- ---
+///
+pub struct StatsWidget {
+    pub lines: Vec<String>,
+    pub error: Option<String>,
+    pub scroll: usize,
+}
+
+impl StatsWidget {
+    /// Create a new StatsWidget
+    ///
+    /// Returns:
+    /// --- ---
+    /// StatsWidget -> The new StatsWidget
+    /// --- ---
+    ///
+    pub fn new() -> Self {
+        Self {
+            lines: Vec::new(),
+            error: None,
+            scroll: 0,
+        }
+    }
+
+    /// Load freshly collected statistics and scroll back to the top
+    ///
+    /// Arguments:
+    /// --- ---
+    /// stats -> The collected statistics, or the error that prevented collecting them
+    /// --- ---
+    ///
+    /// Returns: None
+    ///
+    pub fn set_stats(&mut self, stats: Result<DatabaseStats, String>) {
+        match stats {
+            Ok(stats) => {
+                self.lines = stats.report_lines();
+                self.error = None;
+            }
+            Err(e) => {
+                self.lines.clear();
+                self.error = Some(e);
+            }
+        }
+        self.scroll = 0;
+    }
+
+    /// Get the largest scroll offset that still shows content
+    ///
+    /// Returns:
+    /// --- ---
+    /// usize -> The maximum scroll offset
+    /// --- ---
+    ///
+    fn max_scroll(&self) -> usize {
+        self.lines.len().saturating_sub(1)
+    }
+}
+
+impl Default for StatsWidget {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget for StatsWidget {
+    /// Render the statistics screen
+    ///
+    /// Arguments:
+    /// --- ---
+    /// frame -> The frame to render to
+    /// theme -> The theme to use for styling
+    /// --- ---
+    ///
+    /// Returns: None
+    ///
+    fn render(&self, frame: &mut Frame, theme: &Theme) {
+        // position below the logo like settings, leaving room for the help bar
+        let stats_y = 13_u16;
+        let stats_area = Rect {
+            x: frame.area().width.saturating_sub(STATS_WIDTH) / 2,
+            y: stats_y,
+            width: STATS_WIDTH,
+            height: frame.area().height.saturating_sub(stats_y + 3),
+        }
+        .intersection(frame.area());
+
+        let lines: Vec<Line> = if let Some(ref error) = self.error {
+            vec![Line::from(Span::styled(
+                error.clone(),
+                Style::default().fg(theme.error_color),
+            ))]
+        } else {
+            self.lines
+                .iter()
+                .skip(self.scroll)
+                .map(|line| {
+                    // unindented lines are section headers
+                    if line.starts_with(' ') || line.is_empty() {
+                        Line::from(Span::styled(
+                            line.clone(),
+                            Style::default().fg(theme.text_color),
+                        ))
+                    } else {
+                        Line::from(Span::styled(
+                            line.clone(),
+                            Style::default()
+                                .fg(theme.info_color)
+                                .add_modifier(Modifier::BOLD),
+                        ))
+                    }
+                })
+                .collect()
+        };
+
+        frame.render_widget(Clear, stats_area);
+
+        let stats_paragraph = Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(
+                    " {} ",
+                    i18n::tr("stats-title", "Database Stats", &[])
+                ))
+                .title_style(
+                    Style::default()
+                        .fg(theme.title_color)
+                        .add_modifier(Modifier::BOLD),
+                )
+                .border_style(Style::default().fg(theme.border_color))
+                .style(Style::default().bg(theme.background_color)),
+        );
+
+        frame.render_widget(stats_paragraph, stats_area);
+    }
+
+    /// Handle a key event and return an action
+    ///
+    /// Arguments:
+    /// --- ---
+    /// key -> The key event to handle
+    /// --- ---
+    ///
+    /// Returns: KeyAction -> The action to take in response to the key
+    ///
+    fn handle_key(&mut self, key: KeyEvent) -> KeyAction {
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => KeyAction::Exit,
+            KeyCode::Esc | KeyCode::Backspace | KeyCode::Enter => KeyAction::Back,
+            KeyCode::Up => {
+                self.scroll = self.scroll.saturating_sub(1);
+                KeyAction::Continue
+            }
+            KeyCode::Down => {
+                self.scroll = (self.scroll + 1).min(self.max_scroll());
+                KeyAction::Continue
+            }
+            KeyCode::PageUp => {
+                self.scroll = self.scroll.saturating_sub(PAGE_SIZE);
+                KeyAction::Continue
+            }
+            KeyCode::PageDown => {
+                self.scroll = (self.scroll + PAGE_SIZE).min(self.max_scroll());
+                KeyAction::Continue
+            }
+            KeyCode::Home => {
+                self.scroll = 0;
+                KeyAction::Continue
+            }
+            KeyCode::End => {
+                self.scroll = self.max_scroll();
+                KeyAction::Continue
+            }
+            _ => KeyAction::Continue,
+        }
+    }
+
+    /// Return the focus mode(s) this widget handles
+    ///
+    /// Returns:
+    /// --- ---
+    /// Vec<FocusMode> -> The focus modes this widget handles
+    /// --- ---
+    ///
+    fn focus_modes(&self) -> Vec<FocusMode> {
+        vec![FocusMode::DatabaseStats]
+    }
+}
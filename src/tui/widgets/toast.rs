@@ -6,6 +6,7 @@
 use crate::tui::state::{ErrorType, FocusMode};
 use crate::tui::themes::Theme;
 use crate::tui::widgets::traits::{KeyAction, Widget};
+use crate::utils::i18n;
 use crossterm::event::KeyEvent;
 use ratatui::layout::Rect;
 use ratatui::style::Style;
@@ -13,6 +14,9 @@ use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph};
 use ratatui::Frame;
 
+/// Width of lexer error toasts, which show the query with carets under the bad characters
+pub const LEXER_TOAST_WIDTH: u16 = 60;
+
 /// Toast widget for rendering notifications
 ///
 /// Fields:
@@ -55,29 +59,53 @@ impl Widget for ToastWidget {
     ///
     fn render(&self, frame: &mut Frame, theme: &Theme) {
         if let Some(message) = &self.toast_message {
-            // use the passed error type to determine toast dimensions and styling
-            let is_parser_error = matches!(self.error_type, Some(ErrorType::Parser));
-
             // calculate toast dimensions based on error type
-            let (toast_width, max_toast_height) = if is_parser_error {
+            let (toast_width, max_toast_height) = match self.error_type {
                 // parser errors need more space for context and suggestions
-                (80_u16, 15)
-            } else {
+                Some(ErrorType::Parser) => (80_u16, 15),
+                // lexer errors include the query with carets under the bad characters
+                Some(ErrorType::Lexer) => (LEXER_TOAST_WIDTH, 10),
                 // other messages are typically shorter
-                (60, 8)
+                _ => (60, 8),
             };
 
             // determine title and colors based on error type
             let (title, title_color, border_color) = match self.error_type {
-                Some(ErrorType::Lexer) => ("Lexer Error", theme.error_color, theme.error_color),
-                Some(ErrorType::Parser) => ("Parser Error", theme.error_color, theme.error_color),
-                Some(ErrorType::Semantic) => {
-                    ("Semantic Error", theme.error_color, theme.error_color)
-                }
-                Some(ErrorType::Info) => ("Info", theme.info_color, theme.info_color),
-                Some(ErrorType::Success) => ("Success", theme.success_color, theme.success_color),
-                Some(ErrorType::Warning) => ("Warning", theme.warning_color, theme.warning_color),
-                None => ("Notice", theme.muted_color, theme.muted_color),
+                Some(ErrorType::Lexer) => (
+                    i18n::tr("toast-lexer-error", "Lexer Error", &[]),
+                    theme.error_color,
+                    theme.error_color,
+                ),
+                Some(ErrorType::Parser) => (
+                    i18n::tr("toast-parser-error", "Parser Error", &[]),
+                    theme.error_color,
+                    theme.error_color,
+                ),
+                Some(ErrorType::Semantic) => (
+                    i18n::tr("toast-semantic-error", "Semantic Error", &[]),
+                    theme.error_color,
+                    theme.error_color,
+                ),
+                Some(ErrorType::Info) => (
+                    i18n::tr("toast-info", "Info", &[]),
+                    theme.info_color,
+                    theme.info_color,
+                ),
+                Some(ErrorType::Success) => (
+                    i18n::tr("toast-success", "Success", &[]),
+                    theme.success_color,
+                    theme.success_color,
+                ),
+                Some(ErrorType::Warning) => (
+                    i18n::tr("toast-warning", "Warning", &[]),
+                    theme.warning_color,
+                    theme.warning_color,
+                ),
+                None => (
+                    i18n::tr("toast-notice", "Notice", &[]),
+                    theme.muted_color,
+                    theme.muted_color,
+                ),
             };
 
             // wrap text to fit within the toast width (account for borders and padding)
@@ -85,7 +113,8 @@ impl Widget for ToastWidget {
             let mut wrapped_lines = Vec::new();
 
             for line in message.lines() {
-                if line.len() <= content_width {
+                // count characters so accented queries above a caret line aren't split
+                if line.chars().count() <= content_width {
                     wrapped_lines.push(line.to_string());
                 } else {
                     // split long lines into multiple lines
@@ -121,9 +150,19 @@ impl Widget for ToastWidget {
             .intersection(frame.area());
 
             // create styled lines for the toast
+            // caret lines point at the problem, so they use the error color
             let styled_lines: Vec<Line> = wrapped_lines
                 .iter()
-                .map(|line| Line::from(Span::styled(line, Style::default().fg(theme.text_color))))
+                .map(|line| {
+                    let is_caret_line =
+                        line.contains('^') && line.chars().all(|c| c == '^' || c == ' ');
+                    let color = if is_caret_line {
+                        theme.error_color
+                    } else {
+                        theme.text_color
+                    };
+                    Line::from(Span::styled(line, Style::default().fg(color)))
+                })
                 .collect();
 
             let toast_paragraph = Paragraph::new(styled_lines).block(
@@ -2,10 +2,12 @@
 ///
 /// Widget trait definition for TUI components
 ///
-/// Provides a unified interface for widgets that can render and handle input
+/// Provides a unified interface for widgets that can render and handle input, and react
+/// to gaining or losing focus and to the terminal being resized
 use crate::tui::state::{ErrorType, FocusMode};
 use crate::tui::themes::Theme;
 use crossterm::event::KeyEvent;
+use ratatui::layout::{Position, Rect};
 use ratatui::Frame;
 
 /// Result of handling a key event in a widget
@@ -15,6 +17,7 @@ use ratatui::Frame;
 /// Continue -> Stay in the current widget, no state change
 /// Exit -> Exit the application
 /// Navigate -> Navigate to a different focus mode
+/// Back -> Return to the previous view on the navigation back-stack
 /// ShowToast -> Show a toast notification
 /// --- ---
 ///
@@ -26,6 +29,8 @@ pub enum KeyAction {
     Exit,
     /// Navigate to a different focus mode
     Navigate(FocusMode),
+    /// Return to the previous view
+    Back,
     /// Show a toast notification
     ShowToast {
         message: String,
@@ -39,9 +44,16 @@ pub enum KeyAction {
 /// --- ---
 /// render -> Render the widget to the frame
 /// handle_key -> Handle a key event and return an action
-/// focus_mode -> Return the focus mode(s) this widget handles
+/// focus_modes -> Return the focus mode(s) this widget handles
+/// on_focus -> Called when focus moves to the widget
+/// on_blur -> Called when focus moves away from the widget
+/// on_resize -> Called when the terminal is resized
+/// cursor_position -> Where the terminal's cursor goes while the widget has focus
 /// --- ---
 ///
+/// The hooks and cursor_position do nothing by default, so only widgets that need them
+/// implement them
+///
 pub trait Widget {
     /// Render the widget to the frame
     ///
@@ -75,4 +87,42 @@ pub trait Widget {
     /// --- ---
     ///
     fn focus_modes(&self) -> Vec<FocusMode>;
+
+    /// Called when focus moves to the widget from another one
+    ///
+    /// Parameters:
+    /// --- ---
+    /// mode -> The focus mode the widget was given
+    /// --- ---
+    ///
+    fn on_focus(&mut self, _mode: &FocusMode) {}
+
+    /// Called when focus moves away from the widget to another one
+    ///
+    fn on_blur(&mut self) {}
+
+    /// Called when the terminal is resized, before the next frame is drawn
+    ///
+    /// Parameters:
+    /// --- ---
+    /// area -> The terminal's new area
+    /// --- ---
+    ///
+    fn on_resize(&mut self, _area: Rect) {}
+
+    /// Where the terminal's cursor goes while the widget has focus
+    ///
+    /// Parameters:
+    /// --- ---
+    /// area -> The area of the frame being drawn
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Option<Position> -> The cell the cursor is shown in, None to hide it
+    /// --- ---
+    ///
+    fn cursor_position(&self, _area: Rect) -> Option<Position> {
+        None
+    }
 }
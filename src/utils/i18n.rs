@@ -0,0 +1,342 @@
+/// src/utils/i18n.rs
+///
+/// Localization of UI and error strings
+///
+/// Responsible for picking the user's locale and translating strings through Fluent
+/// resources in locales/<lang>.ftl. English is the source language: callers pass the
+/// English text along with a message id, and it is used whenever the active locale has no
+/// translation for that id
+///
+/// Contains:
+/// --- ---
+/// AVAILABLE_LOCALES -> Locales with a bundled translation file
+/// Localizer -> A locale and its Fluent bundle
+///      Methods:
+///      --- ---
+///      new -> Create a localizer for a locale
+///      locale -> Get the localizer's locale
+///      translate -> Look up a translation
+///      tr -> Translate a string, falling back to its English text
+///      --- ---
+/// Helper functions:
+///      --- ---
+///      localizer -> The process-wide localizer
+///      init -> Select the locale from config or the environment
+///      detect_locale -> Read the requested locale from CLASSQL_LOCALE or LANG
+///      set_locale -> Switch the active locale
+///      current_locale -> Get the active locale
+///      tr -> Translate a string for the active locale, falling back to its English text
+///      fill_placeholders -> Substitute {name} placeholders in English text
+///      normalize_locale -> Reduce a locale like "es_MX.UTF-8" to a bundled locale
+///      build_bundle -> Build the Fluent bundle for a bundled locale
+///      --- ---
+/// --- ---
+///
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource};
+use std::sync::{OnceLock, RwLock};
+use unic_langid::LanguageIdentifier;
+
+/// Locales with a bundled translation file (English needs none)
+pub const AVAILABLE_LOCALES: &[(&str, &str)] =
+    &[("en", ""), ("es", include_str!("../../locales/es.ftl"))];
+
+/// A locale and its Fluent bundle
+///
+/// Fields:
+/// --- ---
+/// locale -> The locale (e.g., "es")
+/// bundle -> Translations for the locale, or None for English
+/// --- ---
+///
+pub struct Localizer {
+    locale: &'static str,
+    bundle: Option<FluentBundle<FluentResource>>,
+}
+
+/// Localizer Implementation
+///
+/// Methods:
+/// --- ---
+/// new -> Create a localizer for a locale
+/// locale -> Get the localizer's locale
+/// translate -> Look up a translation
+/// tr -> Translate a string, falling back to its English text
+/// --- ---
+///
+impl Localizer {
+    /// Create a localizer for a locale
+    ///
+    /// Unsupported locales, and translation files that fail to parse, fall back to English
+    ///
+    /// Parameters:
+    /// --- ---
+    /// requested -> The locale to use (e.g., "es", "es_MX.UTF-8")
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Localizer -> The new localizer
+    /// --- ---
+    ///
+    pub fn new(requested: &str) -> Self {
+        let locale = normalize_locale(requested);
+        let source = AVAILABLE_LOCALES
+            .iter()
+            .find(|(name, _)| *name == locale)
+            .map(|(_, source)| *source)
+            .unwrap_or_default();
+
+        let bundle = if source.is_empty() {
+            None
+        } else {
+            build_bundle(locale, source)
+        };
+        let locale = if bundle.is_some() { locale } else { "en" };
+
+        Self { locale, bundle }
+    }
+
+    /// Get the localizer's locale
+    ///
+    /// Parameters:
+    /// --- ---
+    /// self -> The localizer
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// &'static str -> The locale (e.g., "en")
+    /// --- ---
+    ///
+    pub fn locale(&self) -> &'static str {
+        self.locale
+    }
+
+    /// Look up a translation
+    ///
+    /// Parameters:
+    /// --- ---
+    /// self -> The localizer
+    /// id -> The Fluent message id (e.g., "help-query-input")
+    /// args -> (name, value) pairs for the message's variables
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Option<String> -> The translated text, or None if the locale has no translation
+    /// --- ---
+    ///
+    pub fn translate(&self, id: &str, args: &[(&str, &str)]) -> Option<String> {
+        let bundle = self.bundle.as_ref()?;
+        let pattern = bundle.get_message(id)?.value()?;
+
+        let mut fluent_args = FluentArgs::new();
+        for (name, value) in args {
+            fluent_args.set(*name, *value);
+        }
+
+        let mut errors = Vec::new();
+        let text = bundle.format_pattern(pattern, Some(&fluent_args), &mut errors);
+        errors.is_empty().then(|| text.into_owned())
+    }
+
+    /// Translate a string, falling back to its English text
+    ///
+    /// Parameters:
+    /// --- ---
+    /// self -> The localizer
+    /// id -> The Fluent message id (e.g., "help-query-input")
+    /// english -> The English text, with {name} placeholders for args
+    /// args -> (name, value) pairs for the placeholders
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// String -> The translated text, or the English text with placeholders filled
+    /// --- ---
+    ///
+    pub fn tr(&self, id: &str, english: &str, args: &[(&str, &str)]) -> String {
+        self.translate(id, args)
+            .unwrap_or_else(|| fill_placeholders(english, args))
+    }
+}
+
+/// The process-wide localizer, English until init or set_locale runs
+fn localizer() -> &'static RwLock<Localizer> {
+    static LOCALIZER: OnceLock<RwLock<Localizer>> = OnceLock::new();
+    LOCALIZER.get_or_init(|| RwLock::new(Localizer::new("en")))
+}
+
+/// Select the locale from config or the environment
+///
+/// Parameters:
+/// --- ---
+/// None
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// &'static str -> The locale that was selected
+/// --- ---
+///
+pub fn init() -> &'static str {
+    set_locale(&detect_locale())
+}
+
+/// Read the requested locale from CLASSQL_LOCALE or LANG
+///
+/// CLASSQL_LOCALE (e.g., from .env) wins, then the usual LC_ALL, LC_MESSAGES, and LANG
+///
+/// Parameters:
+/// --- ---
+/// None
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> The requested locale as written (e.g., "es_MX.UTF-8"), or "en" if unset
+/// --- ---
+///
+pub fn detect_locale() -> String {
+    ["CLASSQL_LOCALE", "LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| "en".to_string())
+}
+
+/// Switch the active locale
+///
+/// Parameters:
+/// --- ---
+/// requested -> The locale to use (e.g., "es", "es_MX.UTF-8")
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// &'static str -> The locale that is now active ("en" if the request isn't bundled)
+/// --- ---
+///
+pub fn set_locale(requested: &str) -> &'static str {
+    let new_localizer = Localizer::new(requested);
+    let locale = new_localizer.locale();
+    if let Ok(mut localizer) = localizer().write() {
+        *localizer = new_localizer;
+    }
+    locale
+}
+
+/// Get the active locale
+///
+/// Parameters:
+/// --- ---
+/// None
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// &'static str -> The active locale (e.g., "en")
+/// --- ---
+///
+pub fn current_locale() -> &'static str {
+    localizer()
+        .read()
+        .map(|localizer| localizer.locale())
+        .unwrap_or("en")
+}
+
+/// Translate a string for the active locale, falling back to its English text
+///
+/// Parameters:
+/// --- ---
+/// id -> The Fluent message id (e.g., "help-query-input")
+/// english -> The English text, with {name} placeholders for args
+/// args -> (name, value) pairs for the placeholders
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> The translated text, or the English text with placeholders filled
+/// --- ---
+///
+pub fn tr(id: &str, english: &str, args: &[(&str, &str)]) -> String {
+    match localizer().read() {
+        Ok(localizer) => localizer.tr(id, english, args),
+        Err(_) => fill_placeholders(english, args),
+    }
+}
+
+/// Substitute {name} placeholders in English text
+///
+/// Parameters:
+/// --- ---
+/// text -> The text containing placeholders
+/// args -> (name, value) pairs for the placeholders
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> The text with every matching placeholder replaced
+/// --- ---
+///
+pub fn fill_placeholders(text: &str, args: &[(&str, &str)]) -> String {
+    args.iter().fold(text.to_string(), |text, (name, value)| {
+        text.replace(&format!("{{{}}}", name), value)
+    })
+}
+
+/// Reduce a locale like "es_MX.UTF-8" to a bundled locale
+///
+/// Parameters:
+/// --- ---
+/// requested -> The locale as written in the environment or config
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// &'static str -> The matching bundled locale, or "en" if none matches
+/// --- ---
+///
+pub fn normalize_locale(requested: &str) -> &'static str {
+    // drop the encoding and modifier ("es_MX.UTF-8@euro" -> "es_MX")
+    let tag = requested
+        .split(['.', '@'])
+        .next()
+        .unwrap_or_default()
+        .replace('_', "-");
+
+    tag.parse::<LanguageIdentifier>()
+        .ok()
+        .and_then(|language_id| {
+            AVAILABLE_LOCALES
+                .iter()
+                .map(|(name, _)| *name)
+                .find(|name| *name == language_id.language.as_str())
+        })
+        .unwrap_or("en")
+}
+
+/// Build the Fluent bundle for a bundled locale
+///
+/// Parameters:
+/// --- ---
+/// locale -> The bundled locale (e.g., "es")
+/// source -> The locale's .ftl contents
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Option<FluentBundle<FluentResource>> -> The bundle, or None if the file doesn't parse
+/// --- ---
+///
+fn build_bundle(locale: &str, source: &str) -> Option<FluentBundle<FluentResource>> {
+    let language_id: LanguageIdentifier = locale.parse().ok()?;
+    let resource = FluentResource::try_new(source.to_string()).ok()?;
+
+    let mut bundle = FluentBundle::new_concurrent(vec![language_id]);
+    // terminals don't render the bidi isolation marks Fluent adds around variables
+    bundle.set_use_isolating(false);
+    bundle.add_resource(resource).ok()?;
+    Some(bundle)
+}
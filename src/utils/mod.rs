@@ -0,0 +1,10 @@
+/*
+    src/utils/mod.rs
+
+    Module for shared helpers used across the DSL, data, and TUI layers.
+
+*/
+
+pub mod i18n;
+pub mod text;
+pub mod time;
@@ -0,0 +1,341 @@
+/// src/utils/text.rs
+///
+/// Text helpers shared by the data layer and error reporting
+///
+/// Responsible for comparing text the way users expect: case-insensitive across all of
+/// Unicode and ignoring diacritics, so "munoz" matches "Muñoz". Also renders carets under
+/// the parts of a query an error points at
+///
+/// Contains:
+/// --- ---
+/// fold_text -> Fold text for accent- and case-insensitive comparison
+/// like_match -> Match text against a SQL LIKE pattern after folding both
+/// parse_pattern -> Parse a "matches" pattern on its own, before it is wrapped in anchors
+/// compile_pattern -> Compile a "matches" pattern into a regex over the whole value
+/// edit_distance -> Count the edits between two strings after folding both
+/// split_name -> Split a person's name into first and last name
+/// render_caret -> Render a query with carets under the given byte ranges
+/// Helper functions:
+///      --- ---
+///      pattern_error_reason -> Keep only the reason from a multi-line regex error
+///      --- ---
+/// --- ---
+///
+use regex::{Regex, RegexBuilder};
+use regex_syntax::hir::Hir;
+use regex_syntax::ParserBuilder;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+use unicode_width::UnicodeWidthChar;
+
+/// Most memory a compiled "matches" pattern may use, so a pattern like "(a{1000}){1000}"
+/// is refused instead of stalling the search
+const PATTERN_SIZE_LIMIT: usize = 1 << 20;
+
+/// Fold text for accent- and case-insensitive comparison
+///
+/// Decomposes the text (NFKD), drops combining marks, and lowercases what is left
+///
+/// Parameters:
+/// --- ---
+/// text -> The text to fold
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> The folded text (e.g., "Muñoz" -> "munoz")
+/// --- ---
+///
+pub fn fold_text(text: &str) -> String {
+    text.nfkd()
+        .filter(|c| !is_combining_mark(*c))
+        .collect::<String>()
+        .to_lowercase()
+}
+
+/// Match text against a SQL LIKE pattern after folding both
+///
+/// Supports "%" (any run of characters) and "_" (any single character), with an optional
+/// escape character that makes the next pattern character literal
+///
+/// Parameters:
+/// --- ---
+/// pattern -> The LIKE pattern
+/// text -> The text to match
+/// escape -> Escape character from a LIKE ... ESCAPE clause, if any
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// bool -> true if the whole text matches the pattern
+/// --- ---
+///
+pub fn like_match(pattern: &str, text: &str, escape: Option<char>) -> bool {
+    // pattern elements: None is "%", Some(None) is "_", Some(Some(c)) is a literal
+    let mut elements: Vec<Option<Option<char>>> = Vec::new();
+    let folded_pattern = fold_text(pattern);
+    let mut pattern_chars = folded_pattern.chars();
+    let escape = escape.map(|c| fold_text(&c.to_string()).chars().next().unwrap_or(c));
+    while let Some(c) = pattern_chars.next() {
+        if Some(c) == escape {
+            if let Some(literal) = pattern_chars.next() {
+                elements.push(Some(Some(literal)));
+            }
+        } else if c == '%' {
+            elements.push(None);
+        } else if c == '_' {
+            elements.push(Some(None));
+        } else {
+            elements.push(Some(Some(c)));
+        }
+    }
+
+    let text: Vec<char> = fold_text(text).chars().collect();
+
+    // greedy wildcard matching, backtracking to the most recent "%"
+    let (mut p, mut t) = (0, 0);
+    let mut last_wildcard: Option<(usize, usize)> = None;
+    while t < text.len() {
+        match elements.get(p) {
+            Some(Some(None)) => {
+                p += 1;
+                t += 1;
+            }
+            Some(Some(Some(c))) if *c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            Some(None) => {
+                last_wildcard = Some((p, t));
+                p += 1;
+            }
+            _ => match last_wildcard {
+                Some((wildcard_p, wildcard_t)) => {
+                    last_wildcard = Some((wildcard_p, wildcard_t + 1));
+                    p = wildcard_p + 1;
+                    t = wildcard_t + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+
+    elements[p..].iter().all(|element| element.is_none())
+}
+
+/// Parse a "matches" pattern on its own, before it is wrapped in anchors
+///
+/// Parsing the bare pattern refuses unbalanced groups, so text like "a)|(b" can't close the
+/// group compile_pattern puts around it and match only part of a value
+///
+/// Parameters:
+/// --- ---
+/// pattern -> The regular expression as typed (e.g., "4[0-9]{2}")
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<Hir, String> -> The parsed pattern, ignoring case unless it turns that off with
+///                        "(?-i)", or a short reason the pattern isn't valid
+/// --- ---
+///
+pub fn parse_pattern(pattern: &str) -> Result<Hir, String> {
+    ParserBuilder::new()
+        .case_insensitive(true)
+        .build()
+        .parse(pattern)
+        .map_err(|e| pattern_error_reason(&e.to_string()))
+}
+
+/// Compile a "matches" pattern into a regex over the whole value
+///
+/// The pattern must match all of the value, ignoring case, so "4[0-9]{2}" matches "401" but
+/// not "1401"
+///
+/// Parameters:
+/// --- ---
+/// pattern -> The regular expression as typed (e.g., "4[0-9]{2}")
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<Regex, String> -> The regex, or a short reason the pattern isn't valid
+/// --- ---
+///
+pub fn compile_pattern(pattern: &str) -> Result<Regex, String> {
+    // the parsed pattern prints back as a self-contained regex, so the anchors always apply
+    // to all of it
+    let hir = parse_pattern(pattern)?;
+    RegexBuilder::new(&format!("^(?:{})$", hir))
+        .size_limit(PATTERN_SIZE_LIMIT)
+        .build()
+        .map_err(|e| match e {
+            regex::Error::Syntax(message) => pattern_error_reason(&message),
+            _ => "pattern is too large".to_string(),
+        })
+}
+
+/// Keep only the reason from a multi-line regex error
+///
+/// Syntax errors repeat the pattern with a caret under the problem, which doesn't fit in a
+/// one-line error message
+///
+/// Parameters:
+/// --- ---
+/// message -> The error as the regex crates print it
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> The reason (e.g., "unopened group")
+/// --- ---
+///
+fn pattern_error_reason(message: &str) -> String {
+    message
+        .lines()
+        .find_map(|line| line.strip_prefix("error: "))
+        .unwrap_or("invalid syntax")
+        .to_string()
+}
+
+/// Count the edits between two strings after folding both
+///
+/// Each inserted, deleted, or replaced character is one edit, and so is swapping two
+/// neighbouring characters, the most common typo (e.g., "CMTP" is one edit from "CMPT")
+///
+/// Parameters:
+/// --- ---
+/// a -> The first string
+/// b -> The second string
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// usize -> The number of edits (e.g., "Dwntown" -> "Downtown" is 1)
+/// --- ---
+///
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = fold_text(a).chars().collect();
+    let b: Vec<char> = fold_text(b).chars().collect();
+
+    // rows[i][j] is the distance between the first i characters of a and the first j of b
+    let mut rows: Vec<Vec<usize>> = (0..=a.len())
+        .map(|i| (0..=b.len()).map(|j| if i == 0 { j } else { i }).collect())
+        .collect();
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = distance;
+        }
+    }
+    rows[a.len()][b.len()]
+}
+
+/// Split a person's name into first and last name
+///
+/// A comma separates the last name from the rest ("Smith, John"). Without one the last
+/// word is the last name ("John Smith"), and a single word is taken as a last name
+///
+/// Parameters:
+/// --- ---
+/// name -> The name (e.g., "Labouseur, Alan" or "Alan Labouseur")
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// (String, String) -> The first and last name (e.g., ("Alan", "Labouseur")), the first
+///                     name empty if there is none
+/// --- ---
+///
+pub fn split_name(name: &str) -> (String, String) {
+    let name = name.trim();
+    let (first, last) = match name.split_once(',') {
+        Some((last, first)) => (first, last),
+        None => match name.rsplit_once(char::is_whitespace) {
+            Some((first, last)) => (first, last),
+            None => ("", name),
+        },
+    };
+    (first.trim().to_string(), last.trim().to_string())
+}
+
+/// Render a query with carets under the given byte ranges
+///
+/// Columns follow display width, so carets stay aligned after wide or accented
+/// characters. An empty range (e.g., end of input) gets a single caret. When the query is
+/// wider than max_width, only a window around the first range is shown, with "..."
+/// marking the cut ends
+///
+/// Parameters:
+/// --- ---
+/// input -> The query the byte ranges point into
+/// positions -> Byte ranges (start, end) to underline
+/// max_width -> Maximum display width of each line, if limited
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> Two lines: the (possibly windowed) query, then the carets
+/// --- ---
+///
+pub fn render_caret(input: &str, positions: &[(usize, usize)], max_width: Option<usize>) -> String {
+    // one cell per character: (display char, width, whether it is underlined)
+    let mut cells: Vec<(char, usize, bool)> = input
+        .char_indices()
+        .map(|(start, c)| {
+            let end = start + c.len_utf8();
+            let display = if c.is_control() { ' ' } else { c };
+            let width = display.width().unwrap_or(1);
+            let marked = positions
+                .iter()
+                .any(|&(from, to)| from < end && start < to.max(from + 1));
+            (display, width, marked)
+        })
+        .collect();
+
+    // errors past the last character (e.g., a missing token) point just after the input
+    if positions.iter().any(|&(from, _)| from >= input.len()) {
+        cells.push((' ', 1, true));
+    }
+
+    let total_width: usize = cells.iter().map(|(_, width, _)| width).sum();
+    let (mut low, mut high) = (0, cells.len());
+    if let Some(max_width) = max_width.filter(|max_width| total_width > *max_width) {
+        // leave room for "..." on both sides and keep the first caret in the left third
+        let budget = max_width.saturating_sub(6).max(1);
+        let first = cells.iter().position(|(_, _, marked)| *marked).unwrap_or(0);
+
+        let mut used = 0;
+        low = first;
+        while low > 0 && used + cells[low - 1].1 <= budget / 3 {
+            low -= 1;
+            used += cells[low].1;
+        }
+        high = low;
+        used = 0;
+        while high < cells.len() && used + cells[high].1 <= budget {
+            used += cells[high].1;
+            high += 1;
+        }
+    }
+
+    let (prefix, suffix) = (
+        if low > 0 { "..." } else { "" },
+        if high < cells.len() { "..." } else { "" },
+    );
+    let mut text_line = prefix.to_string();
+    let mut caret_line = " ".repeat(prefix.len());
+    for &(display, width, marked) in &cells[low..high] {
+        text_line.push(display);
+        caret_line.push_str(&(if marked { "^" } else { " " }).repeat(width));
+    }
+    text_line.push_str(suffix);
+
+    format!("{}\n{}", text_line.trim_end(), caret_line.trim_end())
+}
@@ -0,0 +1,671 @@
+/// src/utils/time.rs
+///
+/// Shared time and day utilities
+///
+/// Responsible for parsing and formatting clock times and day codes so the compiler,
+/// data layer, and TUI all agree on how meeting times are interpreted
+///
+/// Contains:
+/// --- ---
+/// DAY_CODES -> Day codes in week order (Monday first)
+/// MONTH_NAMES -> Month names in calendar order
+/// DEFAULT_TIMEZONE -> Timezone assumed when a school has none configured
+/// Date -> A calendar date without a time zone
+/// SchoolTimezone -> A school's UTC offset and daylight saving rules
+/// Helper functions:
+///      --- ---
+///      parse_time -> Parse a 12- or 24-hour clock time into minutes since midnight
+///      normalize_time -> Normalize a clock time to 24-hour HH:MM:SS
+///      format_12_hour -> Format minutes since midnight as "9:00am"
+///      format_12_hour_padded -> Format minutes since midnight as "09:00am"
+///      parse_days -> Split a compact day string into day codes
+///      day_order -> Get the week position of a day code
+///      ranges_overlap -> Check if two time ranges overlap
+///      parse_date_phrase -> Parse a date as typed in a query, with or without a year
+///      now_utc_seconds -> Get the current time as seconds since the Unix epoch
+///      --- ---
+/// --- ---
+///
+/// Day codes in week order (Monday first)
+pub const DAY_CODES: [&str; 7] = ["M", "T", "W", "TH", "F", "S", "SU"];
+
+/// Month names in calendar order
+pub const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// Timezone assumed when a school has none configured
+pub const DEFAULT_TIMEZONE: &str = "America/New_York";
+
+/// Supported timezones as (name, standard UTC offset in minutes, observes US daylight saving)
+const TIMEZONES: &[(&str, i32, bool)] = &[
+    ("America/New_York", -300, true),
+    ("America/Chicago", -360, true),
+    ("America/Denver", -420, true),
+    ("America/Phoenix", -420, false),
+    ("America/Los_Angeles", -480, true),
+    ("America/Anchorage", -540, true),
+    ("Pacific/Honolulu", -600, false),
+    ("UTC", 0, false),
+];
+
+/// Seconds in a day
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Parse a clock time into minutes since midnight
+///
+/// Accepts 24-hour times ("14:30", "14:30:00") and 12-hour times with an am/pm
+/// suffix ("2:30pm", "2:30 PM", "12:00am", or just the hour, "10am").
+///
+/// Parameters:
+/// --- ---
+/// time -> The time string to parse
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Option<i32> -> Minutes since midnight, or None if the time is malformed or out of range
+/// --- ---
+///
+pub fn parse_time(time: &str) -> Option<i32> {
+    let time_lower = time.trim().to_lowercase();
+    let is_pm = time_lower.ends_with("pm");
+    let is_am = time_lower.ends_with("am");
+
+    // remove am/pm suffix
+    let clean = if is_pm || is_am {
+        time_lower[..time_lower.len() - 2].trim()
+    } else {
+        time_lower.as_str()
+    };
+
+    // parse hours, minutes, and optional seconds
+    let parts: Vec<&str> = clean.split(':').collect();
+    // a bare hour is only a time with am/pm, "10" on its own could be anything
+    let hour_only = parts.len() == 1 && (is_pm || is_am);
+    if (parts.len() < 2 && !hour_only) || parts.len() > 3 {
+        return None;
+    }
+    let hours: i32 = parts[0].trim().parse().ok()?;
+    let minutes: i32 = match parts.get(1) {
+        Some(minutes) => minutes.trim().parse().ok()?,
+        None => 0,
+    };
+    if !(0..60).contains(&minutes) {
+        return None;
+    }
+
+    // convert to 24-hour format
+    let hours_24 = if is_pm || is_am {
+        if !(1..=12).contains(&hours) {
+            return None;
+        }
+        match (is_pm, hours) {
+            (true, 12) => 12,
+            (true, h) => h + 12,
+            (false, 12) => 0,
+            (false, h) => h,
+        }
+    } else {
+        if !(0..24).contains(&hours) {
+            return None;
+        }
+        hours
+    };
+
+    Some(hours_24 * 60 + minutes)
+}
+
+/// Normalize a clock time to 24-hour HH:MM:SS format
+///
+/// Parameters:
+/// --- ---
+/// time -> The time string to normalize (12- or 24-hour)
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Option<String> -> The normalized time string (e.g., "14:30:00"), None if unparseable
+/// --- ---
+///
+pub fn normalize_time(time: &str) -> Option<String> {
+    let minutes = parse_time(time)?;
+    Some(format!("{:02}:{:02}:00", minutes / 60, minutes % 60))
+}
+
+/// Split minutes since midnight into a 12-hour clock hour, minute, and period
+///
+/// Parameters:
+/// --- ---
+/// minutes -> Minutes since midnight
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// (i32, i32, &'static str) -> (display hour 1-12, minute, "am" or "pm")
+/// --- ---
+///
+fn to_12_hour(minutes: i32) -> (i32, i32, &'static str) {
+    let hours = minutes / 60;
+    let mins = minutes % 60;
+    let (display_hour, period) = if hours == 0 {
+        (12, "am")
+    } else if hours < 12 {
+        (hours, "am")
+    } else if hours == 12 {
+        (12, "pm")
+    } else {
+        (hours - 12, "pm")
+    };
+    (display_hour, mins, period)
+}
+
+/// Format minutes since midnight as a 12-hour time
+///
+/// Parameters:
+/// --- ---
+/// minutes -> Minutes since midnight
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> Formatted time (e.g., "9:00am", "2:30pm")
+/// --- ---
+///
+pub fn format_12_hour(minutes: i32) -> String {
+    let (hour, mins, period) = to_12_hour(minutes);
+    format!("{}:{:02}{}", hour, mins, period)
+}
+
+/// Format minutes since midnight as a fixed-width 12-hour time
+///
+/// Parameters:
+/// --- ---
+/// minutes -> Minutes since midnight
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> Formatted time with a zero-padded hour (e.g., "09:00am", "02:30pm")
+/// --- ---
+///
+pub fn format_12_hour_padded(minutes: i32) -> String {
+    let (hour, mins, period) = to_12_hour(minutes);
+    format!("{:02}:{:02}{}", hour, mins, period)
+}
+
+/// Split a compact day string into individual day codes
+///
+/// Parameters:
+/// --- ---
+/// days_str -> Compact day string (e.g., "MW", "TTH", "SSU")
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Vec<String> -> Day codes in the order they appear (unknown characters are skipped)
+/// --- ---
+///
+pub fn parse_days(days_str: &str) -> Vec<String> {
+    let mut days = Vec::new();
+    let chars: Vec<char> = days_str.chars().collect();
+    let mut i = 0;
+
+    while i < chars.len() {
+        // two-letter codes take priority over their single-letter prefixes
+        if i + 1 < chars.len() {
+            match (chars[i], chars[i + 1]) {
+                ('T', 'H') => {
+                    days.push("TH".to_string());
+                    i += 2;
+                    continue;
+                }
+                ('S', 'U') => {
+                    days.push("SU".to_string());
+                    i += 2;
+                    continue;
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(code) = DAY_CODES
+            .iter()
+            .find(|code| code.len() == 1 && code.starts_with(chars[i]))
+        {
+            days.push(code.to_string());
+        }
+        i += 1;
+    }
+
+    days
+}
+
+/// Get the week position of a day code for sorting (Monday = 0, Sunday = 6)
+///
+/// Parameters:
+/// --- ---
+/// day_code -> Day code string (M, T, W, TH, F, S, SU)
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// u8 -> Day order (0-6 for valid days, 99 for unknown)
+/// --- ---
+///
+pub fn day_order(day_code: &str) -> u8 {
+    DAY_CODES
+        .iter()
+        .position(|&code| code == day_code)
+        .map(|idx| idx as u8)
+        .unwrap_or(99) // unknown days go last
+}
+
+/// Check if two half-open time ranges overlap
+///
+/// Parameters:
+/// --- ---
+/// start1 -> Start of the first range in minutes
+/// end1 -> End of the first range in minutes
+/// start2 -> Start of the second range in minutes
+/// end2 -> End of the second range in minutes
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// bool -> true if the ranges share any time (touching ranges don't overlap)
+/// --- ---
+///
+pub fn ranges_overlap(start1: i32, end1: i32, start2: i32, end2: i32) -> bool {
+    start1 < end2 && start2 < end1
+}
+
+/// Parse a date as typed in a query, with or without a year
+///
+/// Accepts ISO dates ("2024-10-15"), month/day with an optional year ("10/15", "10/15/2024"),
+/// and a month name or its first three or more letters before the day, with an optional
+/// year after it ("oct 15", "October 15th, 2024")
+///
+/// Parameters:
+/// --- ---
+/// text -> The date as typed
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Option<(Option<i32>, u32, u32)> -> The year if one was given, the month (1-12), and the
+///                                    day, or None if it isn't a real date
+/// --- ---
+///
+pub fn parse_date_phrase(text: &str) -> Option<(Option<i32>, u32, u32)> {
+    let text = text.trim().to_lowercase();
+    if let Some(date) = Date::parse(&text).filter(|_| text.len() == 10) {
+        return Some((Some(date.year), date.month, date.day));
+    }
+
+    let (year, month, day) = if text.contains('/') {
+        let parts: Vec<&str> = text.split('/').collect();
+        let year = match parts.get(2) {
+            // two-digit years are this century (e.g., "10/15/24")
+            Some(year) if year.len() == 2 => Some(2000 + year.parse::<i32>().ok()?),
+            Some(year) => Some(year.parse().ok()?),
+            None => None,
+        };
+        if parts.len() > 3 {
+            return None;
+        }
+        (year, parts[0].parse().ok()?, parts.get(1)?.parse().ok()?)
+    } else {
+        let text = text.replace(',', " ");
+        let mut words = text.split_whitespace();
+        let name = words.next()?.trim_end_matches('.');
+        let month = MONTH_NAMES
+            .iter()
+            .position(|month| name.len() >= 3 && month.to_lowercase().starts_with(name))?
+            as u32
+            + 1;
+        let day = words
+            .next()?
+            .trim_end_matches(['s', 't', 'n', 'd', 'r', 'h']);
+        let year = words.next().map(str::parse).transpose().ok()?;
+        if words.next().is_some() {
+            return None;
+        }
+        (year, month, day.parse().ok()?)
+    };
+
+    // a date without a year may be Feb 29, so check it against a leap year
+    let date = Date {
+        year: year.unwrap_or(2024),
+        month,
+        day,
+    };
+    Date::parse(&date.to_string())?;
+    Some((year, month, day))
+}
+
+/// Get the current time as seconds since the Unix epoch
+///
+/// Returns:
+/// --- ---
+/// i64 -> Seconds since 1970-01-01T00:00:00Z (0 if the system clock is before the epoch)
+/// --- ---
+///
+pub fn now_utc_seconds() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+/// A calendar date without a time zone
+///
+/// Fields:
+/// --- ---
+/// year -> Calendar year
+/// month -> Month of the year (1-12)
+/// day -> Day of the month (1-31)
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for Date
+/// Clone -> Clone trait for Date
+/// Copy -> Copy trait for Date
+/// PartialEq -> PartialEq trait for Date
+/// Eq -> Eq trait for Date
+/// PartialOrd -> PartialOrd trait for Date
+/// Ord -> Ord trait for Date
+/// Display -> Formats the date as YYYY-MM-DD
+/// --- ---
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Date {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl Date {
+    /// Parse a date from an ISO 8601 string
+    ///
+    /// Parameters:
+    /// --- ---
+    /// date_str -> Date or datetime string (e.g., "2024-08-26" or "2024-08-26T00:00:00")
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Option<Date> -> The parsed date, or None if malformed
+    /// --- ---
+    ///
+    pub fn parse(date_str: &str) -> Option<Date> {
+        // ignore any time component after the date
+        let date_part = date_str.trim().get(..10)?;
+        let mut parts = date_part.split('-');
+        let year: i32 = parts.next()?.parse().ok()?;
+        let month: u32 = parts.next()?.parse().ok()?;
+        let day: u32 = parts.next()?.parse().ok()?;
+
+        let date = Date { year, month, day };
+        // reject dates that don't survive a round trip (e.g., 2024-02-30)
+        if (1..=12).contains(&month)
+            && day >= 1
+            && Date::from_days_since_epoch(date.days_since_epoch()) == date
+        {
+            Some(date)
+        } else {
+            None
+        }
+    }
+
+    /// Get the number of days between 1970-01-01 and this date
+    ///
+    /// Returns:
+    /// --- ---
+    /// i64 -> Days since the Unix epoch (negative before 1970)
+    /// --- ---
+    ///
+    pub fn days_since_epoch(&self) -> i64 {
+        // shift the year so it starts in March, putting the leap day last
+        let year = if self.month <= 2 {
+            self.year as i64 - 1
+        } else {
+            self.year as i64
+        };
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let shifted_month = (self.month as i64 + 9) % 12;
+        let day_of_year = (153 * shifted_month + 2) / 5 + self.day as i64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    /// Build a date from the number of days since 1970-01-01
+    ///
+    /// Parameters:
+    /// --- ---
+    /// days -> Days since the Unix epoch
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Date -> The corresponding calendar date
+    /// --- ---
+    ///
+    pub fn from_days_since_epoch(days: i64) -> Date {
+        let shifted = days + 719_468;
+        let era = shifted.div_euclid(146_097);
+        let day_of_era = shifted - era * 146_097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        } as u32;
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+        Date {
+            year: year as i32,
+            month,
+            day,
+        }
+    }
+
+    /// Get the day code for this date's weekday
+    ///
+    /// Returns:
+    /// --- ---
+    /// &'static str -> Day code (M, T, W, TH, F, S, SU)
+    /// --- ---
+    ///
+    pub fn day_code(&self) -> &'static str {
+        // 1970-01-01 was a Thursday
+        DAY_CODES[(self.days_since_epoch() + 3).rem_euclid(7) as usize]
+    }
+
+    /// Get the Monday-to-Sunday week this date falls in
+    ///
+    /// Returns:
+    /// --- ---
+    /// i64 -> Weeks since the week of 1970-01-01, so consecutive weeks differ by one
+    /// --- ---
+    ///
+    pub fn week_index(&self) -> i64 {
+        (self.days_since_epoch() + 3).div_euclid(7)
+    }
+
+    /// Format the date as a short month and day
+    ///
+    /// Returns:
+    /// --- ---
+    /// String -> The date without its year (e.g., "Oct 15")
+    /// --- ---
+    ///
+    pub fn short_label(&self) -> String {
+        let month = MONTH_NAMES[(self.month as usize).clamp(1, 12) - 1];
+        format!("{} {}", &month[..3], self.day)
+    }
+
+    /// Get the date of the nth Sunday of a month
+    ///
+    /// Parameters:
+    /// --- ---
+    /// year -> Calendar year
+    /// month -> Month of the year (1-12)
+    /// n -> Which Sunday (1 for the first)
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Date -> The date of that Sunday
+    /// --- ---
+    ///
+    fn nth_sunday(year: i32, month: u32, n: i64) -> Date {
+        let first = Date {
+            year,
+            month,
+            day: 1,
+        }
+        .days_since_epoch();
+        let first_weekday = (first + 3).rem_euclid(7); // monday = 0, sunday = 6
+        let first_sunday = first + (6 - first_weekday);
+        Date::from_days_since_epoch(first_sunday + 7 * (n - 1))
+    }
+}
+
+impl std::fmt::Display for Date {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// A school's UTC offset and daylight saving rules
+///
+/// Fields:
+/// --- ---
+/// name -> IANA timezone name (e.g., "America/New_York")
+/// standard_offset_minutes -> UTC offset outside daylight saving time
+/// observes_dst -> Whether the zone follows US daylight saving rules
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for SchoolTimezone
+/// Clone -> Clone trait for SchoolTimezone
+/// PartialEq -> PartialEq trait for SchoolTimezone
+/// Default -> Defaults to DEFAULT_TIMEZONE
+/// --- ---
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchoolTimezone {
+    pub name: String,
+    pub standard_offset_minutes: i32,
+    pub observes_dst: bool,
+}
+
+impl SchoolTimezone {
+    /// Look up a supported timezone by name
+    ///
+    /// Parameters:
+    /// --- ---
+    /// name -> IANA timezone name (case-insensitive)
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Option<SchoolTimezone> -> The timezone, or None if it isn't supported
+    /// --- ---
+    ///
+    pub fn from_name(name: &str) -> Option<SchoolTimezone> {
+        TIMEZONES
+            .iter()
+            .find(|(zone, _, _)| zone.eq_ignore_ascii_case(name.trim()))
+            .map(|&(zone, offset, dst)| SchoolTimezone {
+                name: zone.to_string(),
+                standard_offset_minutes: offset,
+                observes_dst: dst,
+            })
+    }
+
+    /// Get the UTC offset in effect at an instant
+    ///
+    /// Daylight saving starts at 2:00am local time on the second Sunday of March
+    /// and ends at 2:00am local time on the first Sunday of November.
+    ///
+    /// Parameters:
+    /// --- ---
+    /// utc_seconds -> Instant as seconds since the Unix epoch
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// i32 -> UTC offset in minutes (e.g., -240 for EDT, -300 for EST)
+    /// --- ---
+    ///
+    pub fn offset_minutes_at(&self, utc_seconds: i64) -> i32 {
+        let standard = self.standard_offset_minutes;
+        if !self.observes_dst {
+            return standard;
+        }
+
+        let standard_seconds = standard as i64 * 60;
+        let year = Date::from_days_since_epoch(
+            (utc_seconds + standard_seconds).div_euclid(SECONDS_PER_DAY),
+        )
+        .year;
+
+        // transition instants converted from local wall-clock time to utc
+        let dst_start = Date::nth_sunday(year, 3, 2).days_since_epoch() * SECONDS_PER_DAY
+            + 2 * 3600
+            - standard_seconds;
+        let dst_end = Date::nth_sunday(year, 11, 1).days_since_epoch() * SECONDS_PER_DAY + 2 * 3600
+            - (standard_seconds + 3600);
+
+        if (dst_start..dst_end).contains(&utc_seconds) {
+            standard + 60
+        } else {
+            standard
+        }
+    }
+
+    /// Convert an instant to the local date and time of day
+    ///
+    /// Parameters:
+    /// --- ---
+    /// utc_seconds -> Instant as seconds since the Unix epoch
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// (Date, i32) -> Local date and minutes since local midnight
+    /// --- ---
+    ///
+    pub fn local_datetime(&self, utc_seconds: i64) -> (Date, i32) {
+        let local_seconds = utc_seconds + self.offset_minutes_at(utc_seconds) as i64 * 60;
+        let date = Date::from_days_since_epoch(local_seconds.div_euclid(SECONDS_PER_DAY));
+        let minutes = (local_seconds.rem_euclid(SECONDS_PER_DAY) / 60) as i32;
+        (date, minutes)
+    }
+}
+
+impl Default for SchoolTimezone {
+    fn default() -> Self {
+        Self::from_name(DEFAULT_TIMEZONE).expect("default timezone is supported")
+    }
+}
@@ -0,0 +1,191 @@
+use crate::utils;
+/// tests/advisor/advisor_tests.rs
+///
+/// Index advisor tests
+///
+/// Responsible for testing the query plan index advisor in data::index_advisor using
+/// JSON-defined test cases: each query is compiled, planned against the test database, and
+/// the suggested index names are compared with the expected ones. Creating the suggestions
+/// is tested on a copy of the test database so the original stays untouched
+///
+/// Contains:
+/// --- ---
+/// AdvisorTestCase -> Index advisor test case struct
+/// AdvisorTestHelper -> Index advisor test helper struct
+///     Methods:
+///     --- ---
+///     new -> Create a new AdvisorTestHelper
+///     run_test -> Run an index advisor test case
+///     --- ---
+/// Helper functions:
+///     --- ---
+///     run_test_file -> Run the test file
+///     --- ---
+/// --- ---
+///
+use classql::data::index_advisor::{create_indexes, explain_search};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Database the advisor is tested against
+const TEST_DB: &str = "classy/test.db";
+
+/// Index advisor test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// query -> The query to explain
+/// expected_advice -> Names of the indexes that should be suggested, in order
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for AdvisorTestCase
+/// Deserialize -> Deserialize trait for AdvisorTestCase
+/// Serialize -> Serialize trait for AdvisorTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct AdvisorTestCase {
+    test_name: String,
+    description: String,
+    query: String,
+    expected_advice: Vec<String>,
+}
+
+/// Index advisor test helper struct
+///
+/// Fields:
+/// --- ---
+/// None
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Default -> Default trait for AdvisorTestHelper
+/// --- ---
+///
+#[derive(Default)]
+struct AdvisorTestHelper {}
+
+/// Index advisor test helper implementation
+///
+/// Methods:
+/// --- ---
+/// new -> Create a new AdvisorTestHelper
+/// run_test -> Run an index advisor test case
+/// --- ---
+///
+impl AdvisorTestHelper {
+    /// Create a new AdvisorTestHelper
+    ///
+    /// Parameters:
+    /// --- ---
+    /// None
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// AdvisorTestHelper -> The new AdvisorTestHelper
+    /// --- ---
+    ///
+    fn new() -> Self {
+        Self {}
+    }
+
+    /// Run an index advisor test case
+    ///
+    /// Parameters:
+    /// --- ---
+    /// self -> The AdvisorTestHelper instance
+    /// test_case -> The index advisor test case to run
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// None
+    /// --- ---
+    ///
+    fn run_test(&mut self, test_case: &AdvisorTestCase) {
+        println!("Running advisor test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+        println!("Query: {}", test_case.query);
+
+        let sql = utils::compile(&test_case.query).unwrap_or_else(|(stage, e)| {
+            panic!("Test '{}': {} failed: {}", test_case.test_name, stage, e)
+        });
+        let explanation = explain_search(Path::new(TEST_DB), &sql)
+            .unwrap_or_else(|e| panic!("Test '{}' failed: {}", test_case.test_name, e));
+        let advice: Vec<String> = explanation.advice.iter().map(|a| a.name()).collect();
+        println!("Plan: {:?}", explanation.plan);
+
+        assert_eq!(
+            advice, test_case.expected_advice,
+            "Test '{}' suggested the wrong indexes for plan {:?}",
+            test_case.test_name, explanation.plan
+        );
+        println!("Advice: {:?}\n", advice);
+    }
+}
+
+/// Run the advisor test file
+///
+/// Parameters:
+/// --- ---
+/// filename -> The filename to run
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// None
+/// --- ---
+///
+fn run_test_file(filename: &str) {
+    let mut helper = AdvisorTestHelper::new();
+    let content = utils::load_test_file("advisor", filename);
+    let test_cases: Vec<AdvisorTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse advisor JSON test file");
+
+    for test_case in test_cases {
+        helper.run_test(&test_case);
+    }
+}
+
+#[test]
+fn test_advisor_suggestions() {
+    run_test_file("advice.json");
+}
+
+#[test]
+fn test_advisor_created_indexes_replace_scans() {
+    let db_path =
+        std::env::temp_dir().join(format!("classql-advisor-test-{}.db", std::process::id()));
+    std::fs::copy(TEST_DB, &db_path).expect("test database should copy");
+
+    let sql = utils::compile("campus is \"On-Line\" and credit hours >= 4")
+        .expect("query should compile");
+    let before = explain_search(&db_path, &sql).expect("search should explain");
+    let created = create_indexes(&db_path, &before.advice);
+    let after = explain_search(&db_path, &sql);
+    let _ = std::fs::remove_file(&db_path);
+
+    assert!(!before.advice.is_empty(), "plan {:?}", before.plan);
+    assert_eq!(created, Ok(before.advice.len()));
+    let after = after.expect("search should explain after indexing");
+    assert!(
+        after.advice.is_empty(),
+        "created indexes should satisfy the advisor, got {:?} for plan {:?}",
+        after.advice,
+        after.plan
+    );
+    assert!(
+        after
+            .plan
+            .iter()
+            .any(|step| before.advice.iter().any(|a| step.contains(&a.name()))),
+        "plan should use a created index: {:?}",
+        after.plan
+    );
+}
@@ -0,0 +1,3 @@
+// Include the advisor_tests module
+#[path = "advisor_tests.rs"]
+mod advisor_tests;
@@ -0,0 +1,174 @@
+use crate::utils;
+/// tests/aliases/aliases_tests.rs
+///
+/// Campus alias tests
+///
+/// Responsible for testing campus aliases using JSON-defined test cases. Each parse case
+/// checks what data::campus_aliases::parse_campus_alias reads from the text typed in Settings.
+/// Each query case defines aliases, resolves them in a campus query, and checks how many
+/// sections of the test database it returns
+///
+/// Contains:
+/// --- ---
+/// ParseTestCase -> Campus alias parse test case struct
+/// QueryTestCase -> Campus alias query test case struct
+/// --- ---
+///
+use classql::data::campus_aliases::{parse_campus_alias, CampusAliases};
+use classql::data::sql::{execute_query, get_test_db_path};
+use classql::dsl::codegen::generate_sql;
+use classql::dsl::semantic::resolve_campus_aliases;
+use serde::{Deserialize, Serialize};
+
+/// Campus alias parse test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// input -> The text typed in Settings
+/// expected_alias -> The alias read, None if the text should be rejected
+/// expected_campus -> The campus read, None if the text should be rejected
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for ParseTestCase
+/// Deserialize -> Deserialize trait for ParseTestCase
+/// Serialize -> Serialize trait for ParseTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct ParseTestCase {
+    test_name: String,
+    description: String,
+    input: String,
+    expected_alias: Option<String>,
+    expected_campus: Option<String>,
+}
+
+/// Campus alias query test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// aliases -> The aliases defined, each as typed in Settings
+/// input -> The query
+/// expected_resolved -> Expected number of campus values replaced by a campus
+/// expected_count -> Expected number of results
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for QueryTestCase
+/// Deserialize -> Deserialize trait for QueryTestCase
+/// Serialize -> Serialize trait for QueryTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct QueryTestCase {
+    test_name: String,
+    description: String,
+    aliases: Vec<String>,
+    input: String,
+    expected_resolved: usize,
+    expected_count: usize,
+}
+
+#[test]
+fn test_parse_campus_aliases() {
+    let content = utils::load_test_file("aliases", "parse.json");
+    let test_cases: Vec<ParseTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse parse JSON test file");
+
+    for test_case in test_cases {
+        println!("Running campus alias parse test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+
+        let result = parse_campus_alias(&test_case.input);
+        println!("Result: {:?}\n", result);
+        match (&test_case.expected_alias, &test_case.expected_campus) {
+            (Some(alias), Some(campus)) => {
+                let entry = result
+                    .unwrap_or_else(|e| panic!("Test '{}': rejected: {}", test_case.test_name, e));
+                assert_eq!(
+                    &entry.alias, alias,
+                    "Test '{}': wrong alias",
+                    test_case.test_name
+                );
+                assert_eq!(
+                    &entry.campus, campus,
+                    "Test '{}': wrong campus",
+                    test_case.test_name
+                );
+            }
+            _ => assert!(
+                result.is_err(),
+                "Test '{}': expected the text to be rejected, got {:?}",
+                test_case.test_name,
+                result
+            ),
+        }
+    }
+}
+
+#[test]
+fn test_set_replaces_alias() {
+    let mut aliases = CampusAliases::default();
+    aliases.set("main", "Marist College Campus");
+    aliases.set("abroad", "Marist Abroad");
+    // same alias in another case replaces the first
+    aliases.set("MAIN", "On-Line");
+
+    assert_eq!(aliases.aliases.len(), 2);
+    assert_eq!(aliases.resolve("Main"), Some("On-Line"));
+    // kept sorted by alias
+    assert_eq!(aliases.aliases[0].alias, "abroad");
+
+    assert!(aliases.remove("ABROAD"));
+    assert!(!aliases.remove("abroad"));
+    assert_eq!(aliases.resolve("abroad"), None);
+}
+
+#[test]
+fn test_campus_alias_queries() {
+    let content = utils::load_test_file("aliases", "queries.json");
+    let test_cases: Vec<QueryTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse queries JSON test file");
+
+    for test_case in test_cases {
+        println!("Running campus alias query test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+
+        let mut aliases = CampusAliases::default();
+        for text in &test_case.aliases {
+            let entry = parse_campus_alias(text)
+                .unwrap_or_else(|e| panic!("Test '{}': {}", test_case.test_name, e));
+            aliases.set(&entry.alias, &entry.campus);
+        }
+
+        // aliases are resolved between semantic analysis and code generation
+        let mut ast = utils::analyze(&test_case.input).unwrap_or_else(|(stage, e)| {
+            panic!("Test '{}': {} failed: {}", test_case.test_name, stage, e)
+        });
+        let resolved = resolve_campus_aliases(&mut ast, &aliases);
+        let sql =
+            generate_sql(&ast).unwrap_or_else(|e| panic!("Test '{}': {}", test_case.test_name, e));
+        assert_eq!(
+            resolved, test_case.expected_resolved,
+            "Test '{}': wrong number of aliases resolved",
+            test_case.test_name
+        );
+
+        let results = execute_query(&sql, &get_test_db_path())
+            .unwrap_or_else(|e| panic!("Test '{}': query failed: {}", test_case.test_name, e));
+        assert_eq!(
+            results.len(),
+            test_case.expected_count,
+            "Test '{}': wrong number of results",
+            test_case.test_name
+        );
+        println!("Results: {}\n", results.len());
+    }
+}
@@ -0,0 +1,3 @@
+// Include the aliases_tests module
+#[path = "aliases_tests.rs"]
+mod aliases_tests;
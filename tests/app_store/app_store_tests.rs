@@ -0,0 +1,220 @@
+use crate::utils;
+/// tests/app_store/app_store_tests.rs
+///
+/// App state database tests
+///
+/// Responsible for testing the app state database in data::app_store using JSON-defined
+/// test cases: each case puts and deletes records in a fresh database and compares every
+/// table's records afterwards. Also tests that migrations run once, that records survive
+/// reopening, and that a database from a newer version is refused
+///
+/// Contains:
+/// --- ---
+/// RecordsTestCase -> Record operations test case struct
+/// Operation -> A put or delete in a test case
+/// Helper functions:
+///     --- ---
+///     fresh_store -> Open a new database in the temp directory
+///     remove_store -> Remove a database and its write-ahead log
+///     table -> Look up a test table by name
+///     --- ---
+/// --- ---
+///
+use classql::data::app_store::{AppStore, Table};
+use classql::data::pool::open_connection;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Notes kept on sections, keyed by section
+const NOTES: Table<Value> = Table::new("notes");
+
+/// Queries searched, keyed by when
+const HISTORY: Table<Value> = Table::new("history");
+
+/// Record operations test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// operations -> Puts and deletes, in order
+/// expected -> Expected records of each table, as [key, value] pairs in key order
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for RecordsTestCase
+/// Deserialize -> Deserialize trait for RecordsTestCase
+/// Serialize -> Serialize trait for RecordsTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct RecordsTestCase {
+    test_name: String,
+    description: String,
+    operations: Vec<Operation>,
+    expected: BTreeMap<String, Vec<(String, Value)>>,
+}
+
+/// A put or delete in a test case
+///
+/// Fields:
+/// --- ---
+/// op -> "put" or "delete"
+/// table -> "notes" or "history"
+/// key -> The record's key
+/// value -> The record to put
+/// deleted -> Whether a delete is expected to find the record
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for Operation
+/// Deserialize -> Deserialize trait for Operation
+/// Serialize -> Serialize trait for Operation
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct Operation {
+    op: String,
+    table: String,
+    key: String,
+    #[serde(default)]
+    value: Value,
+    #[serde(default)]
+    deleted: bool,
+}
+
+/// Open a new database in the temp directory
+///
+/// Parameters:
+/// --- ---
+/// name -> Name of the test, so tests running at once use different files
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// (AppStore, PathBuf) -> The store and its file, to be removed by the test
+/// --- ---
+///
+fn fresh_store(name: &str) -> (AppStore, PathBuf) {
+    let path = std::env::temp_dir().join(format!("classql-app-store-{}.db", name));
+    remove_store(&path);
+    let store = AppStore::open(&path).expect("app store should open");
+    (store, path)
+}
+
+/// Remove a database and its write-ahead log
+///
+/// Parameters:
+/// --- ---
+/// path -> The database file
+/// --- ---
+///
+fn remove_store(path: &Path) {
+    for suffix in ["", "-wal", "-shm"] {
+        let _ = std::fs::remove_file(format!("{}{}", path.display(), suffix));
+    }
+}
+
+/// Look up a test table by name
+///
+/// Parameters:
+/// --- ---
+/// name -> "notes" or "history"
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Table<Value> -> The table
+/// --- ---
+///
+fn table(name: &str) -> Table<Value> {
+    match name {
+        "notes" => NOTES,
+        "history" => HISTORY,
+        other => panic!("unknown test table {}", other),
+    }
+}
+
+#[test]
+fn test_app_store_records() {
+    let content = utils::load_test_file("app_store", "records.json");
+    let test_cases: Vec<RecordsTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse records JSON test file");
+
+    for test_case in test_cases {
+        println!("Running records test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+
+        let (store, path) = fresh_store(&test_case.test_name);
+        for operation in &test_case.operations {
+            let table = table(&operation.table);
+            match operation.op.as_str() {
+                "put" => store
+                    .put(table, &operation.key, &operation.value)
+                    .expect("record should be put"),
+                "delete" => assert_eq!(
+                    store
+                        .delete(table, &operation.key)
+                        .expect("record should be deleted"),
+                    operation.deleted,
+                    "Test '{}': wrong delete result for {}",
+                    test_case.test_name,
+                    operation.key
+                ),
+                other => panic!("Test '{}': unknown op {}", test_case.test_name, other),
+            }
+        }
+
+        for (name, expected) in &test_case.expected {
+            assert_eq!(
+                &store.list(table(name)).expect("records should list"),
+                expected,
+                "Test '{}': wrong {} records",
+                test_case.test_name,
+                name
+            );
+        }
+        remove_store(&path);
+    }
+}
+
+#[test]
+fn test_app_store_migrations() {
+    let (store, path) = fresh_store("migrations");
+    assert_eq!(store.schema_version().expect("version should read"), 1);
+    assert_eq!(
+        store
+            .get(NOTES, "missing")
+            .expect("missing key should read"),
+        None
+    );
+
+    // reopening runs no migration twice and keeps the records
+    store
+        .put(NOTES, "CMPT:101-001", &Value::from("kept"))
+        .expect("record should be put");
+    let reopened = AppStore::open(&path).expect("app store should reopen");
+    assert_eq!(
+        reopened
+            .get(NOTES, "CMPT:101-001")
+            .expect("record should read"),
+        Some(Value::from("kept"))
+    );
+
+    // a record read as the wrong type is an error, not a default
+    let counts: Table<u32> = Table::new("notes");
+    assert!(reopened.get(counts, "CMPT:101-001").is_err());
+
+    // a database from a newer version is left alone
+    open_connection(&path)
+        .expect("database should open")
+        .execute_batch("PRAGMA user_version = 99")
+        .expect("version should be set");
+    let error = AppStore::open(&path).expect_err("newer database should be refused");
+    assert!(error.contains("newer version"), "{}", error);
+    remove_store(&path);
+}
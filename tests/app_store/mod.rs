@@ -0,0 +1,3 @@
+// Include the app_store_tests module
+#[path = "app_store_tests.rs"]
+mod app_store_tests;
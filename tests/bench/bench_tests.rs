@@ -0,0 +1,280 @@
+use crate::utils;
+/// tests/bench/bench_tests.rs
+///
+/// Benchmark harness tests
+///
+/// Responsible for testing the timing summaries and baseline comparison in data::bench
+/// using JSON-defined test cases, plus a small end-to-end run on a generated database.
+/// Timings themselves vary from machine to machine, so only their bookkeeping is checked
+///
+/// Contains:
+/// --- ---
+/// BenchTestCase -> Benchmark test case struct
+/// BenchTestHelper -> Benchmark test helper struct
+///     Methods:
+///     --- ---
+///     new -> Create a new BenchTestHelper
+///     run_test -> Run a benchmark test case
+///     --- ---
+/// Helper functions:
+///     --- ---
+///     micros -> Read a JSON array of microsecond counts as durations
+///     timing_json -> Convert a StageTiming to JSON with microsecond fields
+///     run_test_file -> Run the test file
+///     --- ---
+/// --- ---
+///
+use classql::data::bench::{
+    generate_dataset, run_benchmarks, BenchConfig, BenchReport, StageTiming, BENCH_QUERIES,
+};
+use classql::data::pool::open_connection;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::time::Duration;
+
+/// Benchmark test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// function -> The data::bench function under test
+/// input -> The input passed to the function
+/// expected -> The expected return value
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for BenchTestCase
+/// Deserialize -> Deserialize trait for BenchTestCase
+/// Serialize -> Serialize trait for BenchTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct BenchTestCase {
+    test_name: String,
+    description: String,
+    function: String,
+    input: Value,
+    expected: Value,
+}
+
+/// Benchmark test helper struct
+///
+/// Fields:
+/// --- ---
+/// None
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Default -> Default trait for BenchTestHelper
+/// --- ---
+///
+#[derive(Default)]
+struct BenchTestHelper {}
+
+/// Benchmark test helper implementation
+///
+/// Methods:
+/// --- ---
+/// new -> Create a new BenchTestHelper
+/// run_test -> Run a benchmark test case
+/// --- ---
+///
+impl BenchTestHelper {
+    /// Create a new BenchTestHelper
+    ///
+    /// Parameters:
+    /// --- ---
+    /// None
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// BenchTestHelper -> The new BenchTestHelper
+    /// --- ---
+    ///
+    fn new() -> Self {
+        Self {}
+    }
+
+    /// Run a benchmark test case
+    ///
+    /// Parameters:
+    /// --- ---
+    /// self -> The BenchTestHelper instance
+    /// test_case -> The benchmark test case to run
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// None
+    /// --- ---
+    ///
+    fn run_test(&mut self, test_case: &BenchTestCase) {
+        println!("Running bench test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+        println!("Function: {}", test_case.function);
+        println!("Input: {}", test_case.input);
+
+        let actual = match test_case.function.as_str() {
+            "from_samples" => timing_json(&StageTiming::from_samples(
+                "stage",
+                &micros(&test_case.input),
+            )),
+            "regressions" => {
+                // build a report whose stage medians are the given microsecond counts
+                let stages = test_case.input["medians"]
+                    .as_object()
+                    .map(|medians| {
+                        medians
+                            .iter()
+                            .map(|(stage, median)| {
+                                StageTiming::from_samples(stage, &micros(&json!([median])))
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                let report = BenchReport {
+                    sections: 0,
+                    queries: 0,
+                    iterations: 1,
+                    matched: 0,
+                    stages,
+                };
+                json!(report.regressions(
+                    &test_case.input["baseline"],
+                    test_case.input["threshold"].as_f64().unwrap_or_default()
+                ))
+            }
+            other => panic!(
+                "Unknown function '{}' in test '{}'",
+                other, test_case.test_name
+            ),
+        };
+
+        assert_eq!(
+            actual, test_case.expected,
+            "Test '{}' returned {} but expected {}",
+            test_case.test_name, actual, test_case.expected
+        );
+        println!("Returned {} as expected\n", actual);
+    }
+}
+
+/// Read a JSON array of microsecond counts as durations
+///
+/// Parameters:
+/// --- ---
+/// values -> The JSON array
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Vec<Duration> -> The durations
+/// --- ---
+///
+fn micros(values: &Value) -> Vec<Duration> {
+    values
+        .as_array()
+        .map(|values| {
+            values
+                .iter()
+                .filter_map(Value::as_u64)
+                .map(Duration::from_micros)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Convert a StageTiming to JSON with microsecond fields
+///
+/// Parameters:
+/// --- ---
+/// timing -> The timing summary
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Value -> The summary's sample count and whole-microsecond timings
+/// --- ---
+///
+fn timing_json(timing: &StageTiming) -> Value {
+    json!({
+        "samples": timing.samples,
+        "min": timing.min.as_micros() as u64,
+        "median": timing.median.as_micros() as u64,
+        "mean": timing.mean.as_micros() as u64,
+        "p95": timing.p95.as_micros() as u64,
+    })
+}
+
+/// Run the bench test file
+///
+/// Parameters:
+/// --- ---
+/// filename -> The filename to run
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// None
+/// --- ---
+///
+fn run_test_file(filename: &str) {
+    let mut helper = BenchTestHelper::new();
+    let content = utils::load_test_file("bench", filename);
+    let test_cases: Vec<BenchTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse bench JSON test file");
+
+    for test_case in test_cases {
+        helper.run_test(&test_case);
+    }
+}
+
+#[test]
+fn test_bench_timing_summaries() {
+    run_test_file("timings.json");
+}
+
+#[test]
+fn test_bench_regressions() {
+    run_test_file("regressions.json");
+}
+
+#[test]
+fn test_bench_synthetic_run() {
+    let db_path =
+        std::env::temp_dir().join(format!("classql-bench-test-{}.db", std::process::id()));
+    let config = BenchConfig {
+        sections: 90,
+        iterations: 2,
+        seed: 7,
+    };
+
+    generate_dataset(&db_path, config.sections, config.seed).expect("dataset should generate");
+    let conn = open_connection(&db_path).expect("dataset should open");
+    let count = |table: &str| -> i64 {
+        conn.query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| {
+            row.get(0)
+        })
+        .expect("table should be readable")
+    };
+    assert_eq!(count("sections"), 90);
+    assert_eq!(count("courses"), 30);
+    assert_eq!(count("professors"), 9);
+    assert!(count("meeting_times") >= 90);
+    drop(conn);
+
+    let report = run_benchmarks(&config, &db_path).expect("every benchmark query should run");
+    let _ = std::fs::remove_file(&db_path);
+
+    assert!(report.matched > 0, "benchmark queries should match classes");
+    let stages: Vec<&str> = report.stages.iter().map(|t| t.stage.as_str()).collect();
+    assert_eq!(stages, ["lex", "parse", "codegen", "query", "end_to_end"]);
+    for timing in &report.stages {
+        assert_eq!(timing.samples, BENCH_QUERIES.len() * config.iterations);
+        assert!(timing.min <= timing.median && timing.median <= timing.p95);
+    }
+}
@@ -0,0 +1,3 @@
+// Include the bench_tests module
+#[path = "bench_tests.rs"]
+mod bench_tests;
@@ -0,0 +1,172 @@
+use crate::utils;
+/// tests/catalog/catalog_tests.rs
+///
+/// Course catalog tests
+///
+/// Responsible for testing the catalog browser's queries in data::catalog using JSON-defined
+/// test cases: each case lists one level of the tree (a term's subjects, a subject's courses,
+/// or a course's sections) from the test database and compares the count and first rows.
+/// Also tests that the catalog index can be created more than once
+///
+/// Contains:
+/// --- ---
+/// CatalogTestCase -> Catalog level test case struct
+/// Helper functions:
+///     --- ---
+///     list_level -> List one level of the catalog as text rows
+///     --- ---
+/// --- ---
+///
+use classql::data::catalog::{
+    create_catalog_index, fetch_courses, fetch_sections, fetch_subjects, CatalogScope,
+};
+use classql::data::pool::open_connection;
+use classql::data::sql::get_test_db_path;
+use serde::{Deserialize, Serialize};
+
+/// Catalog level test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// level -> "subjects", "courses", or "sections"
+/// school_id -> School to list (optional, every school if missing)
+/// term_id -> Term to list (optional, every term if missing)
+/// subject -> Subject whose courses or sections are listed
+/// course_number -> Course whose sections are listed
+/// expected_count -> Expected number of rows
+/// expected_first -> Expected first rows, as text
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for CatalogTestCase
+/// Deserialize -> Deserialize trait for CatalogTestCase
+/// Serialize -> Serialize trait for CatalogTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct CatalogTestCase {
+    test_name: String,
+    description: String,
+    level: String,
+    #[serde(default)]
+    school_id: Option<String>,
+    #[serde(default)]
+    term_id: Option<String>,
+    #[serde(default)]
+    subject: Option<String>,
+    #[serde(default)]
+    course_number: Option<String>,
+    expected_count: usize,
+    expected_first: Vec<String>,
+}
+
+/// List one level of the catalog as text rows
+///
+/// Parameters:
+/// --- ---
+/// test_case -> The test case naming the level and its scope
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Vec<String> -> One row per subject, course, or section
+/// --- ---
+///
+fn list_level(test_case: &CatalogTestCase) -> Vec<String> {
+    let scope = CatalogScope {
+        db_path: get_test_db_path(),
+        school_id: test_case.school_id.clone(),
+        term_id: test_case.term_id.clone(),
+    };
+    let subject = test_case.subject.as_deref().unwrap_or_default();
+    match test_case.level.as_str() {
+        "subjects" => fetch_subjects(&scope)
+            .expect("subjects should load")
+            .into_iter()
+            .map(|s| {
+                format!(
+                    "{} {}: {} courses, {} sections",
+                    s.code,
+                    s.description.unwrap_or_default(),
+                    s.courses,
+                    s.sections
+                )
+            })
+            .collect(),
+        "courses" => fetch_courses(&scope, subject)
+            .expect("courses should load")
+            .into_iter()
+            .map(|c| {
+                format!(
+                    "{} {}: {} cr, {} sections, {} open",
+                    c.number, c.title, c.credit_hours, c.sections, c.open_sections
+                )
+            })
+            .collect(),
+        "sections" => {
+            let number = test_case.course_number.as_deref().unwrap_or_default();
+            fetch_sections(&scope, subject, number)
+                .expect("sections should load")
+                .into_iter()
+                .map(|c| {
+                    format!(
+                        "{} {} {}",
+                        c.subject_code, c.course_number, c.section_sequence
+                    )
+                })
+                .collect()
+        }
+        other => panic!("Test '{}': unknown level {}", test_case.test_name, other),
+    }
+}
+
+#[test]
+fn test_catalog_levels() {
+    let content = utils::load_test_file("catalog", "catalog.json");
+    let test_cases: Vec<CatalogTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse catalog JSON test file");
+
+    for test_case in test_cases {
+        println!("Running catalog test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+
+        let rows = list_level(&test_case);
+        assert_eq!(
+            rows.len(),
+            test_case.expected_count,
+            "Test '{}': wrong number of rows",
+            test_case.test_name
+        );
+        assert_eq!(
+            &rows[..test_case.expected_first.len()],
+            test_case.expected_first.as_slice(),
+            "Test '{}': wrong first rows",
+            test_case.test_name
+        );
+    }
+}
+
+#[test]
+fn test_catalog_index() {
+    // a copy, so the test database itself is never changed
+    let path = std::env::temp_dir().join("classql-catalog-index-test.db");
+    std::fs::copy(get_test_db_path(), &path).expect("test database should copy");
+    let conn = open_connection(&path).expect("copy should open");
+
+    create_catalog_index(&conn).expect("catalog index should be created");
+    create_catalog_index(&conn).expect("creating the catalog index again should do nothing");
+    let indexes: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'index' \
+             AND name = 'idx_sections_catalog'",
+            [],
+            |row| row.get(0),
+        )
+        .expect("index count should load");
+    drop(conn);
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(indexes, 1);
+}
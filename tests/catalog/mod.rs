@@ -0,0 +1,3 @@
+// Include the catalog_tests module
+#[path = "catalog_tests.rs"]
+mod catalog_tests;
@@ -4,7 +4,10 @@ use crate::utils;
 /// Code generator tests
 ///
 /// Responsible for testing the code generator using JSON-defined test cases,
-/// similar to lexer, parser, and semantic tests.
+/// similar to lexer, parser, and semantic tests. The generated WHERE and HAVING clauses of every
+/// successful case are compared against a golden file in tests/codegen/snapshots/, so any
+/// codegen change shows up as a readable diff (rerun with CLASSQL_UPDATE_SNAPSHOTS=1 to
+/// accept it)
 ///
 /// Contains:
 /// --- ---
@@ -17,15 +20,13 @@ use crate::utils;
 ///     --- ---
 /// Helper functions:
 ///     --- ---
-///     load_test_file -> Load the test file
+///     filter_clauses -> Get the top-level WHERE and HAVING clauses of a generated query
+///     find_top_level -> Find a keyword outside of parentheses and quotes
+///     pretty_sql -> Break generated SQL into lines for snapshots
 ///     run_test_file -> Run the test file
 ///     --- ---
 /// --- ---
 ///
-use classql::dsl::codegen::generate_sql;
-use classql::dsl::lexer::Lexer;
-use classql::dsl::parser::Parser;
-use classql::dsl::semantic::semantic_analysis;
 use serde::{Deserialize, Serialize};
 
 /// Codegen test case struct
@@ -36,8 +37,6 @@ use serde::{Deserialize, Serialize};
 /// description -> The description of the test
 /// input -> The input query to generate SQL for
 /// should_succeed -> Whether code generation should succeed
-/// expected_fragments -> SQL fragments that should appear in the output (optional)
-/// forbidden_fragments -> SQL fragments that should NOT appear in the output (optional)
 /// --- ---
 ///
 /// Implemented Traits:
@@ -53,10 +52,6 @@ struct CodegenTestCase {
     description: String,
     input: String,
     should_succeed: bool,
-    #[serde(default)]
-    expected_fragments: Vec<String>,
-    #[serde(default)]
-    forbidden_fragments: Vec<String>,
 }
 
 /// Codegen test helper struct
@@ -109,113 +104,176 @@ impl CodegenTestHelper {
     ///
     /// Returns:
     /// --- ---
-    /// None
+    /// Option<String> -> The snapshot entry (query, WHERE and HAVING clauses) for a successful case
     /// --- ---
     ///
-    fn run_test(&mut self, test_case: &CodegenTestCase) {
+    fn run_test(&mut self, test_case: &CodegenTestCase) -> Option<String> {
         println!("Running codegen test: {}", test_case.test_name);
         println!("Description: {}", test_case.description);
         println!("Input: '{}'", test_case.input);
         println!("Expected to succeed: {}", test_case.should_succeed);
 
-        // lexical analysis
-        let mut lexer = Lexer::new(test_case.input.to_string());
-        let tokens = match lexer.analyze() {
-            Ok(tokens) => tokens,
-            Err(e) => {
-                if test_case.should_succeed {
+        match utils::compile(&test_case.input) {
+            Ok(sql) => {
+                if !test_case.should_succeed {
                     panic!(
-                        "Lexer failed in codegen test '{}' with error: {:?}",
-                        test_case.test_name, e
+                        "Code generation succeeded but was expected to fail in test '{}'\nGenerated SQL: {}",
+                        test_case.test_name, sql
                     );
-                } else {
-                    println!("Lexer failed as expected: {:?}\n", e);
-                    return;
                 }
-            }
-        };
 
-        // parsing
-        let mut parser = Parser::new(test_case.input.to_string());
-        let ast = match parser.parse(&tokens) {
-            Ok(ast) => ast,
-            Err((error, _remaining)) => {
+                println!("Generated SQL:\n{}\n", sql);
+                Some(format!("-- {}\n{}", test_case.input, filter_clauses(&sql)))
+            }
+            Err((stage, error)) => {
+                let error = format!("{} failed: {}", stage, error);
                 if test_case.should_succeed {
                     panic!(
-                        "Parser failed in codegen test '{}' with error: {:?}",
+                        "Code generation failed but was expected to succeed in test '{}': {}",
                         test_case.test_name, error
                     );
-                } else {
-                    println!("Parser failed as expected: {:?}\n", error);
-                    return;
                 }
-            }
-        };
-
-        // semantic analysis
-        if let Err((error, _positions)) = semantic_analysis(&ast) {
-            if test_case.should_succeed {
-                panic!(
-                    "Semantic analysis failed in codegen test '{}' with error: {:?}",
-                    test_case.test_name, error
-                );
-            } else {
-                println!("Semantic analysis failed as expected: {:?}\n", error);
-                return;
+                println!("Failed as expected: {}\n", error);
+                None
             }
         }
+    }
+}
 
-        // code generation
-        match generate_sql(&ast) {
-            Ok(sql) => {
-                if !test_case.should_succeed {
-                    panic!(
-                        "Code generation succeeded but was expected to fail in test '{}'\nGenerated SQL: {}",
-                        test_case.test_name, sql
-                    );
-                }
+/// Get the top-level WHERE and HAVING clauses of a generated query
+///
+/// The SELECT list, joins, and GROUP BY are the same for every query, so snapshots only
+/// record what the query changed (test_codegen_select_skeleton covers the rest)
+///
+/// Parameters:
+/// --- ---
+/// sql -> The generated SQL
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> The WHERE conditions, followed by "HAVING" and its conditions if there are any
+/// --- ---
+///
+fn filter_clauses(sql: &str) -> String {
+    let group_by = find_top_level(sql, " GROUP BY ").unwrap_or(sql.len());
+    let mut clauses = Vec::new();
+    if let Some(start) = find_top_level(sql, " WHERE ") {
+        clauses.push(pretty_sql(&sql[start + " WHERE ".len()..group_by]));
+    }
+    if let Some(start) = find_top_level(sql, " HAVING ") {
+        clauses.push(format!(
+            "HAVING {}",
+            pretty_sql(&sql[start + " HAVING ".len()..])
+        ));
+    }
+    clauses.join("\n")
+}
 
-                println!("Generated SQL:\n{}\n", sql);
+/// Find a keyword outside of parentheses and quotes
+///
+/// Parameters:
+/// --- ---
+/// sql -> The SQL to search
+/// keyword -> The keyword, with its surrounding spaces
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Option<usize> -> Byte offset of the first top-level match, if any
+/// --- ---
+///
+fn find_top_level(sql: &str, keyword: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut in_quotes = false;
+    for (i, c) in sql.char_indices() {
+        match c {
+            '\'' => in_quotes = !in_quotes,
+            '(' if !in_quotes => depth += 1,
+            ')' if !in_quotes => depth = depth.saturating_sub(1),
+            _ if !in_quotes && depth == 0 && sql[i..].starts_with(keyword) => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
 
-                // check expected fragments
-                for fragment in &test_case.expected_fragments {
-                    assert!(
-                        sql.contains(fragment),
-                        "Test '{}': Expected SQL to contain '{}' but it didn't.\nFull SQL: {}",
-                        test_case.test_name,
-                        fragment,
-                        sql
-                    );
-                }
+/// Break generated SQL into lines for snapshots
+///
+/// Starts a new line before every AND/OR (indented by parenthesis depth), and before
+/// top-level clauses and after top-level commas. Text inside quotes and the AND of a
+/// BETWEEN are left alone
+///
+/// Parameters:
+/// --- ---
+/// sql -> The SQL (or SQL fragment) to break up
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> The same SQL spread over several lines
+/// --- ---
+///
+fn pretty_sql(sql: &str) -> String {
+    const CLAUSES: &[&str] = &[
+        " FROM ",
+        " LEFT JOIN ",
+        " JOIN ",
+        " WHERE ",
+        " GROUP BY ",
+        " HAVING ",
+    ];
+    const CONNECTIVES: &[&str] = &[" AND ", " OR "];
 
-                // check forbidden fragments
-                for fragment in &test_case.forbidden_fragments {
-                    assert!(
-                        !sql.contains(fragment),
-                        "Test '{}': SQL should NOT contain '{}' but it did.\nFull SQL: {}",
-                        test_case.test_name,
-                        fragment,
-                        sql
-                    );
-                }
+    let mut pretty = String::new();
+    let mut depth = 0usize;
+    let mut in_quotes = false;
+    let mut in_between = false;
+    let mut i = 0;
+    while i < sql.len() {
+        let rest = &sql[i..];
+        let c = rest.chars().next().unwrap_or_default();
 
-                println!("Code generation succeeded as expected\n");
+        if !in_quotes {
+            if rest.starts_with(" BETWEEN ") {
+                in_between = true;
+            } else if in_between && rest.starts_with(" AND ") {
+                in_between = false;
+                pretty.push_str(" AND ");
+                i += " AND ".len();
+                continue;
             }
-            Err(error) => {
-                if test_case.should_succeed {
-                    panic!(
-                        "Code generation failed but was expected to succeed in test '{}': {:?}",
-                        test_case.test_name, error
-                    );
-                } else {
-                    println!(
-                        "Code generation failed as expected with error: {:?}\n",
-                        error
-                    );
-                }
+            let starts_line = CONNECTIVES
+                .iter()
+                .chain(CLAUSES.iter().filter(|_| depth == 0))
+                .find(|keyword| rest.starts_with(*keyword));
+            if let Some(keyword) = starts_line {
+                pretty.push('\n');
+                pretty.push_str(&"  ".repeat(depth));
+                pretty.push_str(keyword.trim_start());
+                i += keyword.len();
+                continue;
             }
+            if depth == 0 && rest.starts_with(", ") {
+                pretty.push_str(",\n");
+                i += ", ".len();
+                continue;
+            }
+            match c {
+                '(' => depth += 1,
+                ')' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+        }
+        // doubled quotes ('') inside a literal toggle twice, so they stay inside
+        if c == '\'' {
+            in_quotes = !in_quotes;
         }
+
+        pretty.push(c);
+        i += c.len_utf8();
     }
+    pretty
 }
 
 /// Run the codegen test file
@@ -236,9 +294,16 @@ fn run_test_file(filename: &str) {
     let test_cases: Vec<CodegenTestCase> =
         serde_json::from_str(&content).expect("Failed to parse codegen JSON test file");
 
-    for test_case in test_cases {
-        helper.run_test(&test_case);
-    }
+    let snapshots: Vec<(String, String)> = test_cases
+        .iter()
+        .filter_map(|test_case| {
+            helper
+                .run_test(test_case)
+                .map(|snapshot| (test_case.test_name.clone(), snapshot))
+        })
+        .collect();
+
+    utils::check_snapshots("codegen", filename.trim_end_matches(".json"), &snapshots);
 }
 
 #[test]
@@ -261,6 +326,11 @@ fn test_codegen_time_queries() {
     run_test_file("time_queries.json");
 }
 
+#[test]
+fn test_codegen_date_queries() {
+    run_test_file("date_queries.json");
+}
+
 #[test]
 fn test_codegen_day_queries() {
     run_test_file("day_queries.json");
@@ -285,3 +355,18 @@ fn test_codegen_keyword_variations() {
 fn test_codegen_edge_cases() {
     run_test_file("edge_cases.json");
 }
+
+#[test]
+fn test_codegen_field_queries() {
+    run_test_file("field_queries.json");
+}
+
+#[test]
+fn test_codegen_select_skeleton() {
+    let sql = utils::compile("subject is CS").expect("query should compile");
+    utils::check_snapshots(
+        "codegen",
+        "select_skeleton",
+        &[("subject_is_cs".to_string(), pretty_sql(&sql))],
+    );
+}
@@ -0,0 +1,229 @@
+use crate::utils::{self, SectionFixture};
+/// tests/conflicts/conflicts_tests.rs
+///
+/// Cart conflict graph and schedule generation tests
+///
+/// Responsible for testing the cart's background conflict graph in widgets::schedule using
+/// JSON-defined test cases: each graph case adds sections to a ScheduleWidget's cart, waits
+/// for the graph to be built, and compares the conflicting pairs and classes with the expected
+/// ones, with find_conflicting_classes, and the schedules generated from the graph with
+/// generate_schedules; each course case generates the schedules taking one section of each
+/// course and compares them with the expected ones
+///
+/// Contains:
+/// --- ---
+/// GraphTestCase -> Conflict graph test case struct
+/// CourseTestCase -> One section per course test case struct
+/// Helper functions:
+///     --- ---
+///     schedule_ids -> List the unique IDs of each schedule
+///     --- ---
+/// --- ---
+///
+use classql::data::sql::Class;
+use classql::tui::widgets::schedule::{
+    find_conflicting_classes, generate_course_schedules, generate_schedules, ScheduleGenerator,
+    ScheduleWidget,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+/// Conflict graph test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// cart -> Sections added to the cart, in order
+/// removed -> Unique IDs removed from the cart after adding them
+/// expected_pairs -> Expected pairs of display IDs that conflict
+/// expected_conflicted -> Expected unique IDs of the sections conflicting with another
+/// expected_schedules -> Expected number of schedules generated from the graph
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for GraphTestCase
+/// Deserialize -> Deserialize trait for GraphTestCase
+/// Serialize -> Serialize trait for GraphTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct GraphTestCase {
+    test_name: String,
+    description: String,
+    cart: Vec<SectionFixture>,
+    #[serde(default)]
+    removed: Vec<String>,
+    expected_pairs: Vec<(String, String)>,
+    expected_conflicted: Vec<String>,
+    expected_schedules: usize,
+}
+
+/// One section per course test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// cart -> Sections in the cart
+/// unchecked -> Unique IDs left out of generation
+/// expected_schedules -> Expected unique IDs of each schedule's sections, in order
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for CourseTestCase
+/// Deserialize -> Deserialize trait for CourseTestCase
+/// Serialize -> Serialize trait for CourseTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct CourseTestCase {
+    test_name: String,
+    description: String,
+    cart: Vec<SectionFixture>,
+    #[serde(default)]
+    unchecked: Vec<String>,
+    expected_schedules: Vec<Vec<String>>,
+}
+
+/// List the unique IDs of each schedule
+///
+/// Parameters:
+/// --- ---
+/// schedules -> The generated schedules
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Vec<Vec<String>> -> Unique IDs of each schedule's classes, in order
+/// --- ---
+///
+fn schedule_ids(schedules: &[Vec<Arc<Class>>]) -> Vec<Vec<String>> {
+    schedules
+        .iter()
+        .map(|schedule| schedule.iter().map(|class| class.unique_id()).collect())
+        .collect()
+}
+
+#[test]
+fn test_conflict_graphs() {
+    let content = utils::load_test_file("conflicts", "graphs.json");
+    let test_cases: Vec<GraphTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse graphs JSON test file");
+
+    for test_case in test_cases {
+        println!("Running conflict graph test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+
+        let mut widget = ScheduleWidget::new();
+        for section in &test_case.cart {
+            widget.add_to_cart(utils::to_class(section));
+        }
+        for class_id in &test_case.removed {
+            widget.remove_from_cart(class_id);
+        }
+        widget
+            .refresh_conflict_graph()
+            .join()
+            .expect("conflict graph thread should finish");
+        let graph = widget
+            .conflict_graph
+            .get()
+            .expect("conflict graph should be built");
+
+        let mut cart: Vec<Arc<Class>> = widget.cart_classes.values().cloned().collect();
+        cart.sort_by_key(|class| class.unique_id());
+
+        let pairs = graph
+            .conflicting_pairs(&cart)
+            .expect("every cart class should be in the graph");
+        assert_eq!(
+            pairs, test_case.expected_pairs,
+            "Test '{}': wrong conflicting pairs",
+            test_case.test_name
+        );
+        assert_eq!(
+            pairs,
+            find_conflicting_classes(&cart),
+            "Test '{}': graph disagrees with find_conflicting_classes",
+            test_case.test_name
+        );
+
+        let conflicted = graph
+            .conflicted_ids(&cart)
+            .expect("every cart class should be in the graph");
+        let expected_conflicted: HashSet<String> =
+            test_case.expected_conflicted.iter().cloned().collect();
+        assert_eq!(
+            conflicted, expected_conflicted,
+            "Test '{}': wrong conflicting classes",
+            test_case.test_name
+        );
+
+        // schedules built from the graph are the ones checked pair by pair
+        let conflicts = graph
+            .matrix_for(&cart)
+            .expect("every cart class should be in the graph");
+        let from_graph: Vec<Vec<Arc<Class>>> =
+            ScheduleGenerator::with_conflicts(cart.clone(), conflicts).collect();
+        let selected: HashSet<String> = widget.cart_classes.keys().cloned().collect();
+        let checked = generate_schedules(&widget.cart_classes, &selected, false);
+        assert_eq!(
+            schedule_ids(&from_graph),
+            schedule_ids(&checked),
+            "Test '{}': graph schedules differ",
+            test_case.test_name
+        );
+        assert_eq!(
+            from_graph.len(),
+            test_case.expected_schedules,
+            "Test '{}': wrong schedule count",
+            test_case.test_name
+        );
+
+        // a copy of a cart class was never seen by the graph
+        if let Some(class) = cart.first() {
+            let copy = Arc::new(Class::clone(class));
+            assert!(
+                graph.matrix_for(&[copy]).is_none(),
+                "Test '{}': graph matched a class it wasn't built from",
+                test_case.test_name
+            );
+        }
+        println!("Pairs: {:?}\n", pairs);
+    }
+}
+
+#[test]
+fn test_one_section_per_course() {
+    let content = utils::load_test_file("conflicts", "courses.json");
+    let test_cases: Vec<CourseTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse courses JSON test file");
+
+    for test_case in test_cases {
+        println!("Running one per course test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+
+        let cart: HashMap<String, Arc<Class>> = test_case
+            .cart
+            .iter()
+            .map(|section| (section.id.clone(), Arc::new(utils::to_class(section))))
+            .collect();
+        let selected: HashSet<String> = cart
+            .keys()
+            .filter(|class_id| !test_case.unchecked.contains(class_id))
+            .cloned()
+            .collect();
+
+        let schedules = schedule_ids(&generate_course_schedules(&cart, &selected));
+        assert_eq!(
+            schedules, test_case.expected_schedules,
+            "Test '{}': wrong schedules",
+            test_case.test_name
+        );
+        println!("Schedules: {:?}\n", schedules);
+    }
+}
@@ -0,0 +1,3 @@
+// Include the conflicts_tests module
+#[path = "conflicts_tests.rs"]
+mod conflicts_tests;
@@ -0,0 +1,118 @@
+use crate::utils;
+/// tests/cost/cost_tests.rs
+///
+/// Query cost tests
+///
+/// Responsible for testing that dsl::cost warns about searches that scan a huge scope, using
+/// JSON-defined test cases. Each case parses a query and checks the warnings estimated for a
+/// scope of a given size, over one term or every term
+///
+/// Contains:
+/// --- ---
+/// CostTestCase -> Cost test case struct
+/// Helper functions:
+///     --- ---
+///     parse -> Lex and parse a query
+///     run_test_file -> Run the test file
+///     --- ---
+/// --- ---
+///
+use classql::dsl::cost::{estimate_cost, CostScope};
+use classql::dsl::lexer::Lexer;
+use classql::dsl::parser::{Ast, Parser};
+use serde::{Deserialize, Serialize};
+
+/// Cost test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// input -> The query
+/// sections -> How many sections are in scope
+/// all_terms -> Whether the search runs over every term
+/// expected_warnings -> The warnings expected, in order
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for CostTestCase
+/// Deserialize -> Deserialize trait for CostTestCase
+/// Serialize -> Serialize trait for CostTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct CostTestCase {
+    test_name: String,
+    description: String,
+    input: String,
+    sections: usize,
+    all_terms: bool,
+    expected_warnings: Vec<String>,
+}
+
+/// Lex and parse a query
+///
+/// Parameters:
+/// --- ---
+/// input -> The query to parse
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Ast -> The parsed query
+/// --- ---
+///
+fn parse(input: &str) -> Ast {
+    let mut lexer = Lexer::new(input.to_string());
+    let tokens = lexer
+        .analyze()
+        .unwrap_or_else(|_| panic!("'{}' should lex", input));
+    let mut parser = Parser::new(input.to_string());
+    parser
+        .parse(&tokens)
+        .unwrap_or_else(|_| panic!("'{}' should parse", input))
+}
+
+/// Run the test file
+///
+/// Parameters:
+/// --- ---
+/// filename -> The JSON test file in tests/cost/tests
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// None
+/// --- ---
+///
+fn run_test_file(filename: &str) {
+    let content = utils::load_test_file("cost", filename);
+    let test_cases: Vec<CostTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse cost JSON test file");
+
+    for test_case in test_cases {
+        println!("Running cost test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+        println!("Input: {}", test_case.input);
+
+        let ast = parse(&test_case.input);
+        let scope = CostScope {
+            sections: test_case.sections,
+            all_terms: test_case.all_terms,
+        };
+        let warnings = estimate_cost(&ast, &scope);
+
+        assert_eq!(
+            warnings, test_case.expected_warnings,
+            "Test '{}' warned {:?} but expected {:?}",
+            test_case.test_name, warnings, test_case.expected_warnings
+        );
+        println!("Warned {:?} as expected\n", warnings);
+    }
+}
+
+#[test]
+fn test_cost_warnings() {
+    run_test_file("cost_warnings.json");
+}
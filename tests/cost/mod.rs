@@ -0,0 +1,3 @@
+// Include the cost_tests module
+#[path = "cost_tests.rs"]
+mod cost_tests;
@@ -0,0 +1,183 @@
+use crate::utils::{self, SectionFixture};
+/// tests/duplicates/duplicates_tests.rs
+///
+/// Duplicate section tests
+///
+/// Responsible for testing the collapsing of duplicate sections in data::duplicates using
+/// JSON-defined test cases: each case lists the sections of a search in order and the
+/// groups to show in full, and the expected cards, groups, and hidden count
+///
+/// Contains:
+/// --- ---
+/// DuplicatesTestCase -> Duplicate section test case struct
+/// DuplicatesTestHelper -> Duplicate section test helper struct
+///     Methods:
+///     --- ---
+///     new -> Create a new DuplicatesTestHelper
+///     run_test -> Run a duplicate section test case
+///     --- ---
+/// Helper functions:
+///     --- ---
+///     run_test_file -> Run the test file
+///     --- ---
+/// --- ---
+///
+use classql::data::duplicates::collapse_duplicates;
+use classql::data::sql::Class;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Duplicate section test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// sections -> Sections of the search, in order
+/// expanded -> Unique IDs of the first sections of groups shown in full
+/// expected_shown -> Unique IDs expected on cards, in order
+/// expected_groups -> Expected unique IDs of each group, keyed by its first section
+/// expected_hidden -> Expected number of sections folded under another card
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for DuplicatesTestCase
+/// Deserialize -> Deserialize trait for DuplicatesTestCase
+/// Serialize -> Serialize trait for DuplicatesTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct DuplicatesTestCase {
+    test_name: String,
+    description: String,
+    sections: Vec<SectionFixture>,
+    #[serde(default)]
+    expanded: Vec<String>,
+    expected_shown: Vec<String>,
+    expected_groups: HashMap<String, Vec<String>>,
+    expected_hidden: usize,
+}
+
+/// Duplicate section test helper struct
+///
+/// Fields:
+/// --- ---
+/// None
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Default -> Default trait for DuplicatesTestHelper
+/// --- ---
+///
+#[derive(Default)]
+struct DuplicatesTestHelper {}
+
+/// Duplicate section test helper implementation
+///
+/// Methods:
+/// --- ---
+/// new -> Create a new DuplicatesTestHelper
+/// run_test -> Run a duplicate section test case
+/// --- ---
+///
+impl DuplicatesTestHelper {
+    /// Create a new DuplicatesTestHelper
+    ///
+    /// Parameters:
+    /// --- ---
+    /// None
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// DuplicatesTestHelper -> The new DuplicatesTestHelper
+    /// --- ---
+    ///
+    fn new() -> Self {
+        Self {}
+    }
+
+    /// Run a duplicate section test case
+    ///
+    /// Parameters:
+    /// --- ---
+    /// self -> The DuplicatesTestHelper instance
+    /// test_case -> The duplicate section test case to run
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// None
+    /// --- ---
+    ///
+    fn run_test(&mut self, test_case: &DuplicatesTestCase) {
+        println!("Running duplicates test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+
+        let classes: Vec<Class> = test_case.sections.iter().map(utils::to_class).collect();
+        let expanded: HashSet<String> = test_case.expanded.iter().cloned().collect();
+        let collapsed = collapse_duplicates(&classes, &expanded);
+
+        let shown: Vec<String> = collapsed.shown.iter().map(Class::unique_id).collect();
+        assert_eq!(
+            shown, test_case.expected_shown,
+            "Test '{}': wrong cards",
+            test_case.test_name
+        );
+        assert_eq!(
+            collapsed.groups, test_case.expected_groups,
+            "Test '{}': wrong groups",
+            test_case.test_name
+        );
+        assert_eq!(
+            collapsed.hidden(&expanded),
+            test_case.expected_hidden,
+            "Test '{}': wrong hidden count",
+            test_case.test_name
+        );
+
+        // every section of a group leads back to its first section
+        for (first, members) in &collapsed.groups {
+            for member in members {
+                assert_eq!(
+                    collapsed.group_of(member),
+                    Some(first.as_str()),
+                    "Test '{}': {} is in the wrong group",
+                    test_case.test_name,
+                    member
+                );
+            }
+        }
+        println!("Shown: {:?}\n", shown);
+    }
+}
+
+/// Run the duplicate section test file
+///
+/// Parameters:
+/// --- ---
+/// filename -> The filename to run
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// None
+/// --- ---
+///
+fn run_test_file(filename: &str) {
+    let mut helper = DuplicatesTestHelper::new();
+    let content = utils::load_test_file("duplicates", filename);
+    let test_cases: Vec<DuplicatesTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse duplicates JSON test file");
+
+    for test_case in test_cases {
+        helper.run_test(&test_case);
+    }
+}
+
+#[test]
+fn test_duplicate_groups() {
+    run_test_file("groups.json");
+}
@@ -0,0 +1,3 @@
+// Include the duplicates_tests module
+#[path = "duplicates_tests.rs"]
+mod duplicates_tests;
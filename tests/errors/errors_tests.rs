@@ -0,0 +1,235 @@
+use crate::utils;
+/// tests/errors/errors_tests.rs
+///
+/// Error catalog tests
+///
+/// Responsible for testing the error codes and message templates in tui::error_catalog
+/// using JSON-defined test cases, similar to the time and text tests.
+///
+/// Contains:
+/// --- ---
+/// ErrorsTestCase -> Error catalog test case struct
+/// ErrorsTestHelper -> Error catalog test helper struct
+///     Methods:
+///     --- ---
+///     new -> Create a new ErrorsTestHelper
+///     run_test -> Run an error catalog test case
+///     --- ---
+/// Helper functions:
+///     --- ---
+///     find_code -> Look up an ErrorCode by its short code
+///     run_test_file -> Run the test file
+///     --- ---
+/// --- ---
+///
+use classql::tui::error_catalog::{self, diagnostic_header, ErrorCode, CATALOG};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashSet;
+
+/// Error catalog test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// function -> The tui::error_catalog function under test
+/// input -> The input passed to the function
+/// expected -> The expected return value
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for ErrorsTestCase
+/// Deserialize -> Deserialize trait for ErrorsTestCase
+/// Serialize -> Serialize trait for ErrorsTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct ErrorsTestCase {
+    test_name: String,
+    description: String,
+    function: String,
+    input: Value,
+    expected: Value,
+}
+
+/// Error catalog test helper struct
+///
+/// Fields:
+/// --- ---
+/// None
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Default -> Default trait for ErrorsTestHelper
+/// --- ---
+///
+#[derive(Default)]
+struct ErrorsTestHelper {}
+
+/// Error catalog test helper implementation
+///
+/// Methods:
+/// --- ---
+/// new -> Create a new ErrorsTestHelper
+/// run_test -> Run an error catalog test case
+/// --- ---
+///
+impl ErrorsTestHelper {
+    /// Create a new ErrorsTestHelper
+    ///
+    /// Parameters:
+    /// --- ---
+    /// None
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// ErrorsTestHelper -> The new ErrorsTestHelper
+    /// --- ---
+    ///
+    fn new() -> Self {
+        Self {}
+    }
+
+    /// Run an error catalog test case
+    ///
+    /// Parameters:
+    /// --- ---
+    /// self -> The ErrorsTestHelper instance
+    /// test_case -> The error catalog test case to run
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// None
+    /// --- ---
+    ///
+    fn run_test(&mut self, test_case: &ErrorsTestCase) {
+        println!("Running error catalog test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+        println!("Function: {}", test_case.function);
+        println!("Input: {}", test_case.input);
+
+        let actual = match test_case.function.as_str() {
+            "message" => {
+                let key = test_case.input["key"].as_str().unwrap_or_default();
+                let args: Vec<(String, String)> = test_case.input["args"]
+                    .as_object()
+                    .map(|args| {
+                        args.iter()
+                            .map(|(name, value)| {
+                                (name.clone(), value.as_str().unwrap_or_default().to_string())
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                let args: Vec<(&str, &str)> = args
+                    .iter()
+                    .map(|(name, value)| (name.as_str(), value.as_str()))
+                    .collect();
+                json!({
+                    "code": error_catalog::template(key).map(|template| template.code.code()),
+                    "message": error_catalog::message(key, &args),
+                })
+            }
+            "diagnostic_header" => {
+                let code = find_code(test_case.input["code"].as_str().unwrap_or_default());
+                let query = test_case.input["query"].as_str().unwrap_or_default();
+                let positions: Vec<(usize, usize)> =
+                    serde_json::from_value(test_case.input["positions"].clone())
+                        .expect("positions should be a list of [start, end] pairs");
+                json!(diagnostic_header(code, query, &positions))
+            }
+            other => panic!(
+                "Unknown function '{}' in test '{}'",
+                other, test_case.test_name
+            ),
+        };
+
+        assert_eq!(
+            actual, test_case.expected,
+            "Test '{}' returned {} but expected {}",
+            test_case.test_name, actual, test_case.expected
+        );
+        println!("Returned {} as expected\n", actual);
+    }
+}
+
+/// Look up an ErrorCode by its short code
+///
+/// Parameters:
+/// --- ---
+/// code -> The short code (e.g., "E001")
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// ErrorCode -> The matching error code
+/// --- ---
+///
+fn find_code(code: &str) -> ErrorCode {
+    *ErrorCode::ALL
+        .iter()
+        .find(|error_code| error_code.code() == code)
+        .unwrap_or_else(|| panic!("Unknown error code '{}'", code))
+}
+
+/// Run the error catalog test file
+///
+/// Parameters:
+/// --- ---
+/// filename -> The filename to run
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// None
+/// --- ---
+///
+fn run_test_file(filename: &str) {
+    let mut helper = ErrorsTestHelper::new();
+    let content = utils::load_test_file("errors", filename);
+    let test_cases: Vec<ErrorsTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse errors JSON test file");
+
+    for test_case in test_cases {
+        helper.run_test(&test_case);
+    }
+}
+
+#[test]
+fn test_errors_messages() {
+    run_test_file("messages.json");
+}
+
+#[test]
+fn test_errors_diagnostic_headers() {
+    run_test_file("diagnostic_headers.json");
+}
+
+#[test]
+fn test_errors_catalog_is_consistent() {
+    // keys and codes must be unique, and every code needs at least one template
+    let mut keys = HashSet::new();
+    for template in CATALOG {
+        assert!(
+            keys.insert(template.key),
+            "Duplicate catalog key '{}'",
+            template.key
+        );
+    }
+
+    let codes: HashSet<&str> = ErrorCode::ALL.iter().map(|code| code.code()).collect();
+    assert_eq!(codes.len(), ErrorCode::ALL.len(), "Duplicate error codes");
+
+    for code in ErrorCode::ALL {
+        assert!(
+            CATALOG.iter().any(|template| template.code == *code),
+            "Error code {} has no message template",
+            code
+        );
+    }
+}
@@ -0,0 +1,3 @@
+// Include the errors_tests module
+#[path = "errors_tests.rs"]
+mod errors_tests;
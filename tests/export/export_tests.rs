@@ -0,0 +1,135 @@
+use crate::utils::{self, SectionFixture};
+/// tests/export/export_tests.rs
+///
+/// Schedule export tests
+///
+/// Responsible for testing the registration exports in tui::export using JSON-defined test
+/// cases: each CRN case collects a schedule's CRNs and compares the copied text, the
+/// sections without one, and the toast summary; each export case compares the printed
+/// schedule. Also tests the terminal clipboard sequence
+///
+/// Contains:
+/// --- ---
+/// CrnTestCase -> CRN collection test case struct
+/// ExportTestCase -> Printed schedule test case struct
+/// --- ---
+///
+use classql::data::sql::Class;
+use classql::tui::export::{export_lines, osc52_sequence, ScheduleCrns};
+use serde::{Deserialize, Serialize};
+
+/// CRN collection test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// sections -> Sections of the schedule, in order
+/// expected_clipboard -> Expected copied text
+/// expected_missing -> Expected labels of sections without a CRN
+/// expected_summary -> Expected toast summary
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for CrnTestCase
+/// Deserialize -> Deserialize trait for CrnTestCase
+/// Serialize -> Serialize trait for CrnTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct CrnTestCase {
+    test_name: String,
+    description: String,
+    sections: Vec<SectionFixture>,
+    expected_clipboard: String,
+    expected_missing: Vec<String>,
+    expected_summary: String,
+}
+
+/// Printed schedule test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// sections -> Sections of the schedule, in order
+/// expected_lines -> Expected printed lines
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for ExportTestCase
+/// Deserialize -> Deserialize trait for ExportTestCase
+/// Serialize -> Serialize trait for ExportTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct ExportTestCase {
+    test_name: String,
+    description: String,
+    sections: Vec<SectionFixture>,
+    expected_lines: Vec<String>,
+}
+
+#[test]
+fn test_export_crns() {
+    let content = utils::load_test_file("export", "crns.json");
+    let test_cases: Vec<CrnTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse CRNs JSON test file");
+
+    for test_case in test_cases {
+        println!("Running CRN test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+
+        let classes: Vec<Class> = test_case.sections.iter().map(utils::to_class).collect();
+        let crns = ScheduleCrns::from_classes(&classes);
+        assert_eq!(
+            crns.clipboard_text(),
+            test_case.expected_clipboard,
+            "Test '{}': wrong copied text",
+            test_case.test_name
+        );
+        assert_eq!(
+            crns.missing, test_case.expected_missing,
+            "Test '{}': wrong sections without a CRN",
+            test_case.test_name
+        );
+        assert_eq!(
+            crns.summary(),
+            test_case.expected_summary,
+            "Test '{}': wrong summary",
+            test_case.test_name
+        );
+    }
+}
+
+#[test]
+fn test_export_printed_schedules() {
+    let content = utils::load_test_file("export", "exports.json");
+    let test_cases: Vec<ExportTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse exports JSON test file");
+
+    for test_case in test_cases {
+        println!("Running export test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+
+        let classes: Vec<Class> = test_case.sections.iter().map(utils::to_class).collect();
+        assert_eq!(
+            export_lines(&classes),
+            test_case.expected_lines,
+            "Test '{}': wrong printed schedule",
+            test_case.test_name
+        );
+    }
+}
+
+#[test]
+fn test_export_clipboard_sequence() {
+    // OSC 52 with the text base64-encoded, ended by BEL
+    assert_eq!(
+        osc52_sequence("10042 10518"),
+        "\x1b]52;c;MTAwNDIgMTA1MTg=\x07"
+    );
+    assert_eq!(osc52_sequence(""), "\x1b]52;c;\x07");
+}
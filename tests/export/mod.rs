@@ -0,0 +1,3 @@
+// Include the export_tests module
+#[path = "export_tests.rs"]
+mod export_tests;
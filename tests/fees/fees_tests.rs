@@ -0,0 +1,86 @@
+use crate::utils;
+/// tests/fees/fees_tests.rs
+///
+/// Section fee tests
+///
+/// Responsible for testing lab and course fees using JSON-defined test cases. Each total
+/// case lists a schedule's fees and checks each class's label and the schedule's total from
+/// widgets::schedule::total_fees. Query cases run through utils::run_extras_query_file,
+/// which writes fees into a copy of the test database and checks which sections a fee query
+/// returns
+///
+/// Contains:
+/// --- ---
+/// TotalTestCase -> Schedule fee total test case struct
+/// --- ---
+///
+use classql::data::sql::{format_dollars, Class};
+use classql::tui::widgets::schedule::total_fees;
+use serde::{Deserialize, Serialize};
+
+/// Schedule fee total test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// fees -> Fee of each class in the schedule, None if it has none
+/// expected_labels -> Expected fee label of each class, None if it shows no fee
+/// expected_total -> Expected total of the schedule's fees, as formatted
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for TotalTestCase
+/// Deserialize -> Deserialize trait for TotalTestCase
+/// Serialize -> Serialize trait for TotalTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct TotalTestCase {
+    test_name: String,
+    description: String,
+    fees: Vec<Option<f64>>,
+    expected_labels: Vec<Option<String>>,
+    expected_total: String,
+}
+
+#[test]
+fn test_fee_totals() {
+    let content = utils::load_test_file("fees", "totals.json");
+    let test_cases: Vec<TotalTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse totals JSON test file");
+
+    for test_case in test_cases {
+        println!("Running fee total test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+
+        let schedule: Vec<Class> = test_case
+            .fees
+            .iter()
+            .map(|fee| Class {
+                fee: *fee,
+                ..Class::default()
+            })
+            .collect();
+
+        let labels: Vec<Option<String>> = schedule.iter().map(Class::fee_label).collect();
+        assert_eq!(
+            labels, test_case.expected_labels,
+            "Test '{}': wrong labels",
+            test_case.test_name
+        );
+        let total = format_dollars(total_fees(&schedule));
+        assert_eq!(
+            total, test_case.expected_total,
+            "Test '{}': wrong total",
+            test_case.test_name
+        );
+        println!("Total: {}\n", total);
+    }
+}
+
+#[test]
+fn test_fee_queries() {
+    utils::run_extras_query_file("fees", "queries.json", |class| class.fee_label().is_some());
+}
@@ -0,0 +1,3 @@
+// Include the fees_tests module
+#[path = "fees_tests.rs"]
+mod fees_tests;
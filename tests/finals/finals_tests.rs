@@ -0,0 +1,194 @@
+use crate::utils::{self, SectionFixture};
+/// tests/finals/finals_tests.rs
+///
+/// Final exam tests
+///
+/// Responsible for testing final exam blocks using JSON-defined test cases: each case lists
+/// sections with their packed final exam as the query returns it, and the expected labels,
+/// conflicting pairs from widgets::schedule::find_final_conflicts, and exam dates from
+/// widgets::schedule::finals_by_date
+///
+/// Contains:
+/// --- ---
+/// FinalsTestCase -> Final exam test case struct
+/// FinalsTestHelper -> Final exam test helper struct
+///     Methods:
+///     --- ---
+///     new -> Create a new FinalsTestHelper
+///     run_test -> Run a final exam test case
+///     --- ---
+/// Helper functions:
+///     --- ---
+///     run_test_file -> Run the test file
+///     --- ---
+/// --- ---
+///
+use classql::data::sql::{pack_final_exam, Class};
+use classql::tui::widgets::schedule::{finals_by_date, find_final_conflicts};
+use serde::{Deserialize, Serialize};
+
+/// Final exam test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// sections -> Sections of the schedule
+/// expected_labels -> Expected final exam label of each section, None if it has none
+/// expected_conflicts -> Expected pairs of display IDs with conflicting finals
+/// expected_dates -> Expected exam dates in order, each with its sections' unique IDs
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for FinalsTestCase
+/// Deserialize -> Deserialize trait for FinalsTestCase
+/// Serialize -> Serialize trait for FinalsTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct FinalsTestCase {
+    test_name: String,
+    description: String,
+    sections: Vec<SectionFixture>,
+    expected_labels: Vec<Option<String>>,
+    #[serde(default)]
+    expected_conflicts: Vec<(String, String)>,
+    #[serde(default)]
+    expected_dates: Vec<(String, Vec<String>)>,
+}
+
+/// Final exam test helper struct
+///
+/// Fields:
+/// --- ---
+/// None
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Default -> Default trait for FinalsTestHelper
+/// --- ---
+///
+#[derive(Default)]
+struct FinalsTestHelper {}
+
+/// Final exam test helper implementation
+///
+/// Methods:
+/// --- ---
+/// new -> Create a new FinalsTestHelper
+/// run_test -> Run a final exam test case
+/// --- ---
+///
+impl FinalsTestHelper {
+    /// Create a new FinalsTestHelper
+    ///
+    /// Parameters:
+    /// --- ---
+    /// None
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// FinalsTestHelper -> The new FinalsTestHelper
+    /// --- ---
+    ///
+    fn new() -> Self {
+        Self {}
+    }
+
+    /// Run a final exam test case
+    ///
+    /// Parameters:
+    /// --- ---
+    /// self -> The FinalsTestHelper instance
+    /// test_case -> The final exam test case to run
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// None
+    /// --- ---
+    ///
+    fn run_test(&mut self, test_case: &FinalsTestCase) {
+        println!("Running finals test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+
+        let classes: Vec<Class> = test_case.sections.iter().map(utils::to_class).collect();
+
+        let labels: Vec<Option<String>> = classes
+            .iter()
+            .map(|class| class.final_exam.map(|exam| exam.label()))
+            .collect();
+        assert_eq!(
+            labels, test_case.expected_labels,
+            "Test '{}': wrong labels",
+            test_case.test_name
+        );
+
+        // a parsed final packs back to what the query returned
+        for (class, section) in classes.iter().zip(&test_case.sections) {
+            if let Some(exam) = class.final_exam {
+                assert_eq!(
+                    Some(pack_final_exam(&exam)),
+                    section.final_exam,
+                    "Test '{}': {} packed differently",
+                    test_case.test_name,
+                    section.id
+                );
+            }
+        }
+
+        assert_eq!(
+            find_final_conflicts(&classes),
+            test_case.expected_conflicts,
+            "Test '{}': wrong conflicts",
+            test_case.test_name
+        );
+
+        let dates: Vec<(String, Vec<String>)> = finals_by_date(&classes)
+            .into_iter()
+            .map(|(date, exams)| {
+                (
+                    date.to_string(),
+                    exams.iter().map(|(_, class)| class.unique_id()).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            dates, test_case.expected_dates,
+            "Test '{}': wrong exam dates",
+            test_case.test_name
+        );
+        println!("Labels: {:?}\n", labels);
+    }
+}
+
+/// Run the final exam test file
+///
+/// Parameters:
+/// --- ---
+/// filename -> The filename to run
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// None
+/// --- ---
+///
+fn run_test_file(filename: &str) {
+    let mut helper = FinalsTestHelper::new();
+    let content = utils::load_test_file("finals", filename);
+    let test_cases: Vec<FinalsTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse finals JSON test file");
+
+    for test_case in test_cases {
+        helper.run_test(&test_case);
+    }
+}
+
+#[test]
+fn test_final_exams() {
+    run_test_file("exams.json");
+}
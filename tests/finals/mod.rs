@@ -0,0 +1,3 @@
+// Include the finals_tests module
+#[path = "finals_tests.rs"]
+mod finals_tests;
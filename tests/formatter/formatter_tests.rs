@@ -0,0 +1,565 @@
+use crate::utils;
+use classql::dsl::codegen::equality_value;
+/// tests/formatter/formatter_tests.rs
+///
+/// Formatter tests
+///
+/// Responsible for testing that dsl::formatter prints canonical query text, using JSON-defined
+/// test cases, and that formatting round-trips: random queries generated from the grammar
+/// parse, format, and re-parse to an equal AST, and formatting twice changes nothing. Also
+/// tests that quoted values read back as the value
+///
+/// Contains:
+/// --- ---
+/// FormatterTestCase -> Formatter test case struct
+/// FormatterTestHelper -> Formatter test helper struct
+///     Methods:
+///     --- ---
+///     new -> Create a new FormatterTestHelper
+///     parse -> Lex and parse a query
+///     run_test -> Run a formatter test case
+///     --- ---
+/// QueryGenerator -> Seeded generator of random queries that follow the grammar
+///     Methods:
+///     --- ---
+///     new -> Create a generator from a seed
+///     next_u64 -> Next raw random number
+///     pick -> Pick one of several options
+///     chance -> Return true with the given percent chance
+///     query -> Generate a query
+///     term -> Generate an "and"-joined term
+///     factor -> Generate a factor ("not", parentheses, or a comparison)
+///     spelling -> Pick a random spelling of an entity's keyword(s)
+///     comparison -> Generate a single entity comparison
+///     --- ---
+/// Helper functions:
+///     --- ---
+///     run_test_file -> Run the test file
+///     --- ---
+/// --- ---
+///
+use classql::dsl::entities;
+use classql::dsl::formatter::{format_query, quote_string};
+use classql::dsl::lexer::Lexer;
+use classql::dsl::parser::{Ast, Parser};
+use serde::{Deserialize, Serialize};
+
+/// Seed for the round-trip test, printed with any failure so it can be replayed
+const ROUND_TRIP_SEED: u64 = 4440;
+
+/// Number of random queries the round-trip test checks
+const ROUND_TRIP_CASES: usize = 40;
+
+/// Deepest nesting of "not" and parentheses the generator produces
+const MAX_GENERATED_DEPTH: usize = 3;
+
+/// Formatter test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// input -> The query to format
+/// expected -> The expected canonical text
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for FormatterTestCase
+/// Deserialize -> Deserialize trait for FormatterTestCase
+/// Serialize -> Serialize trait for FormatterTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct FormatterTestCase {
+    test_name: String,
+    description: String,
+    input: String,
+    expected: String,
+}
+
+/// Formatter test helper struct
+///
+/// Fields:
+/// --- ---
+/// None
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Default -> Default trait for FormatterTestHelper
+/// --- ---
+///
+#[derive(Default)]
+struct FormatterTestHelper {}
+
+/// Formatter test helper implementation
+///
+/// Methods:
+/// --- ---
+/// new -> Create a new FormatterTestHelper
+/// parse -> Lex and parse a query
+/// run_test -> Run a formatter test case
+/// --- ---
+///
+impl FormatterTestHelper {
+    /// Create a new FormatterTestHelper
+    ///
+    /// Parameters:
+    /// --- ---
+    /// None
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// FormatterTestHelper -> The new FormatterTestHelper
+    /// --- ---
+    ///
+    fn new() -> Self {
+        Self {}
+    }
+
+    /// Lex and parse a query
+    ///
+    /// Parameters:
+    /// --- ---
+    /// self -> The FormatterTestHelper instance
+    /// input -> The query to parse
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// Result<Ast, String> -> The AST, or a description of the lexer or parser error
+    /// --- ---
+    ///
+    fn parse(&self, input: &str) -> Result<Ast, String> {
+        let tokens = Lexer::new(input.to_string())
+            .analyze()
+            .map_err(|error| format!("lexer error: {}", error))?;
+        Parser::new(input.to_string())
+            .parse(&tokens)
+            .map_err(|(error, _)| format!("parser error: {}", error))
+    }
+
+    /// Run a formatter test case
+    ///
+    /// Parameters:
+    /// --- ---
+    /// self -> The FormatterTestHelper instance
+    /// test_case -> The formatter test case to run
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// None
+    /// --- ---
+    ///
+    fn run_test(&mut self, test_case: &FormatterTestCase) {
+        println!("Running formatter test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+        println!("Input: {}", test_case.input);
+
+        let ast = self
+            .parse(&test_case.input)
+            .unwrap_or_else(|error| panic!("Test '{}': {}", test_case.test_name, error));
+        let formatted = format_query(&ast);
+
+        assert_eq!(
+            formatted, test_case.expected,
+            "Test '{}' formatted as '{}' but expected '{}'",
+            test_case.test_name, formatted, test_case.expected
+        );
+        println!("Formatted as '{}' as expected\n", formatted);
+    }
+}
+
+/// Seeded generator of random queries that follow the grammar
+///
+/// Keywords are drawn from every accepted spelling in the entity registry, so the formatter
+/// has to normalize aliases, case, and joined forms to round-trip
+///
+/// Fields:
+/// --- ---
+/// state -> xorshift state, never zero
+/// --- ---
+///
+struct QueryGenerator {
+    state: u64,
+}
+
+/// QueryGenerator Implementation
+///
+/// Methods:
+/// --- ---
+/// new -> Create a generator from a seed
+/// next_u64 -> Next raw random number
+/// pick -> Pick one of several options
+/// chance -> Return true with the given percent chance
+/// query -> Generate a query
+/// term -> Generate an "and"-joined term
+/// factor -> Generate a factor ("not", parentheses, or a comparison)
+/// spelling -> Pick a random spelling of an entity's keyword(s)
+/// comparison -> Generate a single entity comparison
+/// --- ---
+///
+impl QueryGenerator {
+    /// Create a generator from a seed
+    ///
+    /// Parameters:
+    /// --- ---
+    /// seed -> The seed
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// QueryGenerator -> The generator
+    /// --- ---
+    ///
+    fn new(seed: u64) -> Self {
+        Self { state: seed.max(1) }
+    }
+
+    /// Next raw random number
+    ///
+    /// Parameters:
+    /// --- ---
+    /// self -> The generator
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// u64 -> The number
+    /// --- ---
+    ///
+    fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+
+    /// Pick one of several options
+    ///
+    /// Parameters:
+    /// --- ---
+    /// self -> The generator
+    /// options -> The options (must not be empty)
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// T -> The chosen option
+    /// --- ---
+    ///
+    fn pick<T: Copy>(&mut self, options: &[T]) -> T {
+        options[(self.next_u64() % options.len() as u64) as usize]
+    }
+
+    /// Return true with the given percent chance
+    ///
+    /// Parameters:
+    /// --- ---
+    /// self -> The generator
+    /// percent -> Chance out of 100
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// bool -> Whether the chance hit
+    /// --- ---
+    ///
+    fn chance(&mut self, percent: u64) -> bool {
+        self.next_u64() % 100 < percent
+    }
+
+    /// Generate a query
+    ///
+    /// Parameters:
+    /// --- ---
+    /// self -> The generator
+    /// depth -> Remaining "not"/parenthesis nesting
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// String -> One to three terms joined by "or"
+    /// --- ---
+    ///
+    fn query(&mut self, depth: usize) -> String {
+        let count = 1 + self.next_u64() % 3;
+        let terms: Vec<String> = (0..count).map(|_| self.term(depth)).collect();
+        let or = self.pick(&[" or ", " OR ", "  or  "]);
+        terms.join(or)
+    }
+
+    /// Generate an "and"-joined term
+    ///
+    /// Parameters:
+    /// --- ---
+    /// self -> The generator
+    /// depth -> Remaining "not"/parenthesis nesting
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// String -> One to three factors joined by "and"
+    /// --- ---
+    ///
+    fn term(&mut self, depth: usize) -> String {
+        let count = 1 + self.next_u64() % 3;
+        let factors: Vec<String> = (0..count).map(|_| self.factor(depth)).collect();
+        let and = self.pick(&[" and ", " AND ", " and  "]);
+        factors.join(and)
+    }
+
+    /// Generate a factor ("not", parentheses, or a comparison)
+    ///
+    /// Parameters:
+    /// --- ---
+    /// self -> The generator
+    /// depth -> Remaining "not"/parenthesis nesting
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// String -> The factor
+    /// --- ---
+    ///
+    fn factor(&mut self, depth: usize) -> String {
+        if depth > 0 && self.chance(15) {
+            let not = self.pick(&["not ", "NOT "]);
+            format!("{}{}", not, self.factor(depth - 1))
+        } else if depth > 0 && self.chance(20) {
+            format!("({})", self.query(depth - 1))
+        } else {
+            self.comparison()
+        }
+    }
+
+    /// Pick a random spelling of an entity's keyword(s)
+    ///
+    /// Parameters:
+    /// --- ---
+    /// self -> The generator
+    /// name -> The entity's canonical name
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// String -> Any accepted spelling (e.g., "profs" or "credit hour")
+    /// --- ---
+    ///
+    fn spelling(&mut self, name: &str) -> String {
+        let info = entities::entity_named(name).expect("entity should be registered");
+        info.tokens
+            .iter()
+            .map(|keyword| self.pick(&keyword.accepted_spellings()))
+            .collect::<Vec<&str>>()
+            .join(" ")
+    }
+
+    /// Generate a single entity comparison
+    ///
+    /// Parameters:
+    /// --- ---
+    /// self -> The generator
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// String -> The comparison (e.g., "prof contains Smith")
+    /// --- ---
+    ///
+    fn comparison(&mut self) -> String {
+        const CONDITIONS: &[&str] = &[
+            "is",
+            "is not",
+            "equals",
+            "contains",
+            "has",
+            "starts with",
+            "ends with",
+            "=",
+            "!=",
+            "does not equal",
+            "doesn't contain",
+        ];
+        const BINOPS: &[&str] = &["=", "!=", "<", ">", "<=", ">=", "equals"];
+        const VALUES: &[&str] = &[
+            "CS",
+            "math",
+            "O'Brien",
+            "424N",
+            "101",
+            "lecture",
+            "\"Data Structures\"",
+            "\"say \\\"hi\\\"\"",
+        ];
+        const TIMES: &[&str] = &["9:00am", "10:30 pm", "2pm", "12:15pm"];
+
+        let condition = self.pick(CONDITIONS);
+        let value = self.pick(VALUES);
+        let binop = self.pick(BINOPS);
+        let number = self.next_u64() % 200;
+
+        match self.next_u64() % 12 {
+            0 => format!("{} {} {}", self.spelling("professor"), condition, value),
+            1 => {
+                let field = self.pick(&[
+                    "subject",
+                    "number",
+                    "title",
+                    "description",
+                    "prerequisites",
+                    "corequisites",
+                ]);
+                format!("{} {} {}", self.spelling(field), condition, value)
+            }
+            2 => {
+                let field = self.pick(&["subject", "number", "title", "prerequisites"]);
+                if self.chance(30) {
+                    format!("course {} {}", condition, value)
+                } else {
+                    format!("course {} {} {}", self.spelling(field), condition, value)
+                }
+            }
+            3 => {
+                let credit = self.pick(&["credits", "credit hours", "credit-hours", "credit hour"]);
+                format!("{} {} {}", credit, binop, number)
+            }
+            4 => {
+                let field = self.pick(&["enrollment", "size", "cap", "enrollment cap"]);
+                format!("{} {} {}", field, binop, number)
+            }
+            5 => {
+                let field = self.pick(&["method", "campus", "full", "section"]);
+                format!("{} {} {}", self.spelling(field), condition, value)
+            }
+            6 => {
+                let field = self.pick(&["meeting type", "type", "meeting_type"]);
+                format!("{} {} {}", field, condition, value)
+            }
+            7 | 8 => {
+                let field = self.pick(&["start", "end"]);
+                if self.chance(50) {
+                    format!("{} {} to {}", field, self.pick(TIMES), self.pick(TIMES))
+                } else {
+                    format!("{} {} {}", field, binop, self.pick(TIMES))
+                }
+            }
+            _ => {
+                let day = self.pick(&[
+                    "monday",
+                    "tuesday",
+                    "wednesday",
+                    "thursday",
+                    "friday",
+                    "saturday",
+                    "sunday",
+                ]);
+                let day = self.spelling(day);
+                if self.chance(50) {
+                    day
+                } else {
+                    let flag = self.pick(&["true", "false"]);
+                    format!("{} {} {}", day, self.pick(&["is", "=", "is not"]), flag)
+                }
+            }
+        }
+    }
+}
+
+/// Run the formatter test file
+///
+/// Parameters:
+/// --- ---
+/// filename -> The filename to run
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// None
+/// --- ---
+///
+fn run_test_file(filename: &str) {
+    let mut helper = FormatterTestHelper::new();
+    let content = utils::load_test_file("formatter", filename);
+    let test_cases: Vec<FormatterTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse formatter JSON test file");
+
+    for test_case in test_cases {
+        helper.run_test(&test_case);
+    }
+}
+
+#[test]
+fn test_formatter_canonical_text() {
+    run_test_file("canonical.json");
+}
+
+#[test]
+fn test_formatter_round_trip() {
+    let helper = FormatterTestHelper::new();
+    let mut generator = QueryGenerator::new(ROUND_TRIP_SEED);
+
+    for case in 0..ROUND_TRIP_CASES {
+        let query = generator.query(MAX_GENERATED_DEPTH);
+        let context = format!("seed {} case {}: {}", ROUND_TRIP_SEED, case, query);
+
+        let ast = helper.parse(&query).unwrap_or_else(|error| {
+            panic!("generated query should parse ({}): {}", context, error)
+        });
+        let formatted = format_query(&ast);
+        let reparsed = helper.parse(&formatted).unwrap_or_else(|error| {
+            panic!(
+                "formatted '{}' should parse ({}): {}",
+                formatted, context, error
+            )
+        });
+
+        assert!(
+            ast == reparsed,
+            "formatting changed the AST ({})\nformatted: {}",
+            context,
+            formatted
+        );
+        assert_eq!(
+            format_query(&reparsed),
+            formatted,
+            "formatting is not idempotent ({})",
+            context
+        );
+    }
+}
+
+#[test]
+fn test_formatter_quote_string() {
+    let helper = FormatterTestHelper::new();
+    let values = [
+        "Lown, Cheryl",
+        "O'Brien",
+        "Muñoz",
+        "say \"hi\"",
+        "back\\slash",
+    ];
+
+    for value in values {
+        let quoted = quote_string(value);
+        let query = format!("prof is {}", quoted);
+        let ast = helper
+            .parse(&query)
+            .unwrap_or_else(|error| panic!("'{}' should parse: {}", query, error));
+        // the professor comparison sits under the query's wrapping nodes
+        let mut node = ast.head.as_ref().expect("query should have a head");
+        while node.children.len() == 1 {
+            node = &node.children[0];
+        }
+        assert_eq!(
+            equality_value(node).as_deref(),
+            Some(value),
+            "'{}' should read back as the value",
+            query
+        );
+        assert_eq!(format_query(&ast), format!("professor is {}", quoted));
+    }
+}
@@ -0,0 +1,3 @@
+// Include the formatter_tests module
+#[path = "formatter_tests.rs"]
+mod formatter_tests;
@@ -0,0 +1,193 @@
+use crate::utils::{self, MeetingFixture};
+/// tests/free_time/free_time_tests.rs
+///
+/// Free time finder tests
+///
+/// Responsible for testing the free time finder in data::free_time using JSON-defined test
+/// cases: each request case parses a request and compares the days, window, and length it
+/// asks for (or the error); each block case finds the free time of a schedule and compares
+/// the blocks of each day and those free on every day
+///
+/// Contains:
+/// --- ---
+/// ExpectedRequest -> The parsed request in a test case
+/// RequestTestCase -> Request parsing test case struct
+/// BlockTestCase -> Free block test case struct
+/// --- ---
+///
+use classql::data::free_time::{
+    common_free_time, find_free_time, format_range, FreeTimeRequest, DEFAULT_FREE_TIME_REQUEST,
+};
+use classql::utils::time::format_12_hour;
+use serde::{Deserialize, Serialize};
+
+/// The parsed request in a test case
+///
+/// Fields:
+/// --- ---
+/// days -> Expected day codes, in week order
+/// start -> Expected start of the window, e.g., "10:00am"
+/// end -> Expected end of the window, e.g., "3:00pm"
+/// length_minutes -> Expected shortest block
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for ExpectedRequest
+/// Deserialize -> Deserialize trait for ExpectedRequest
+/// Serialize -> Serialize trait for ExpectedRequest
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct ExpectedRequest {
+    days: Vec<String>,
+    start: String,
+    end: String,
+    length_minutes: i32,
+}
+
+/// Request parsing test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// input -> The request to parse
+/// expected -> Expected request, None if parsing should fail
+/// expected_error -> Expected start of the error message, None if parsing should succeed
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for RequestTestCase
+/// Deserialize -> Deserialize trait for RequestTestCase
+/// Serialize -> Serialize trait for RequestTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct RequestTestCase {
+    test_name: String,
+    description: String,
+    input: String,
+    expected: Option<ExpectedRequest>,
+    expected_error: Option<String>,
+}
+
+/// Free block test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// schedule -> Meetings of the schedule
+/// request -> The request to answer
+/// expected_blocks -> Expected free blocks, e.g., "M 11:50am-1:00pm"
+/// expected_common -> Expected blocks free on every requested day
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for BlockTestCase
+/// Deserialize -> Deserialize trait for BlockTestCase
+/// Serialize -> Serialize trait for BlockTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct BlockTestCase {
+    test_name: String,
+    description: String,
+    schedule: Vec<MeetingFixture>,
+    request: String,
+    expected_blocks: Vec<String>,
+    expected_common: Vec<String>,
+}
+
+#[test]
+fn test_free_time_requests() {
+    let content = utils::load_test_file("free_time", "requests.json");
+    let test_cases: Vec<RequestTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse requests JSON test file");
+
+    for test_case in test_cases {
+        println!("Running request test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+
+        let result = FreeTimeRequest::parse(&test_case.input);
+        match (&test_case.expected, &test_case.expected_error, result) {
+            (Some(expected), None, Ok(request)) => {
+                assert_eq!(
+                    request.days, expected.days,
+                    "Test '{}': wrong days",
+                    test_case.test_name
+                );
+                assert_eq!(
+                    format_12_hour(request.start_minutes),
+                    expected.start,
+                    "Test '{}': wrong start",
+                    test_case.test_name
+                );
+                assert_eq!(
+                    format_12_hour(request.end_minutes),
+                    expected.end,
+                    "Test '{}': wrong end",
+                    test_case.test_name
+                );
+                assert_eq!(
+                    request.length_minutes, expected.length_minutes,
+                    "Test '{}': wrong length",
+                    test_case.test_name
+                );
+                println!("Request: {}", request.describe());
+            }
+            (None, Some(expected_error), Err(error)) => {
+                assert!(
+                    error.starts_with(expected_error.as_str()),
+                    "Test '{}': unexpected error '{}'",
+                    test_case.test_name,
+                    error
+                );
+            }
+            (_, _, result) => panic!(
+                "Test '{}': unexpected result {:?}",
+                test_case.test_name, result
+            ),
+        }
+    }
+
+    assert!(FreeTimeRequest::parse(DEFAULT_FREE_TIME_REQUEST).is_ok());
+}
+
+#[test]
+fn test_free_time_blocks() {
+    let content = utils::load_test_file("free_time", "blocks.json");
+    let test_cases: Vec<BlockTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse blocks JSON test file");
+
+    for test_case in test_cases {
+        println!("Running block test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+
+        let schedule = utils::meeting_classes(&test_case.schedule);
+        let request = FreeTimeRequest::parse(&test_case.request).expect("request should parse");
+
+        let blocks: Vec<String> = find_free_time(&schedule, &request)
+            .iter()
+            .map(|block| format!("{} {}", block.day, block.label()))
+            .collect();
+        assert_eq!(
+            blocks, test_case.expected_blocks,
+            "Test '{}': wrong blocks",
+            test_case.test_name
+        );
+
+        let common: Vec<String> = common_free_time(&schedule, &request)
+            .iter()
+            .map(|(start, end)| format_range(*start, *end))
+            .collect();
+        assert_eq!(
+            common, test_case.expected_common,
+            "Test '{}': wrong common blocks",
+            test_case.test_name
+        );
+    }
+}
@@ -0,0 +1,3 @@
+// Include the free_time_tests module
+#[path = "free_time_tests.rs"]
+mod free_time_tests;
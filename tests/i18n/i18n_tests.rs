@@ -0,0 +1,241 @@
+use crate::utils;
+/// tests/i18n/i18n_tests.rs
+///
+/// Localization tests
+///
+/// Responsible for testing locale selection and translation lookups in utils::i18n using
+/// JSON-defined test cases, similar to the time and text tests. Each case builds its own
+/// Localizer so the process-wide locale other tests rely on stays English.
+///
+/// Contains:
+/// --- ---
+/// I18nTestCase -> Localization test case struct
+/// I18nTestHelper -> Localization test helper struct
+///     Methods:
+///     --- ---
+///     new -> Create a new I18nTestHelper
+///     run_test -> Run a localization test case
+///     --- ---
+/// Helper functions:
+///     --- ---
+///     string_args -> Read a JSON object of arguments as (name, value) pairs
+///     run_test_file -> Run the test file
+///     --- ---
+/// --- ---
+///
+use classql::tui::error_catalog::CATALOG;
+use classql::utils::i18n::{normalize_locale, Localizer, AVAILABLE_LOCALES};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// Localization test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// function -> The utils::i18n function under test
+/// input -> The input passed to the function
+/// expected -> The expected return value
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for I18nTestCase
+/// Deserialize -> Deserialize trait for I18nTestCase
+/// Serialize -> Serialize trait for I18nTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct I18nTestCase {
+    test_name: String,
+    description: String,
+    function: String,
+    input: Value,
+    expected: Value,
+}
+
+/// Localization test helper struct
+///
+/// Fields:
+/// --- ---
+/// None
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Default -> Default trait for I18nTestHelper
+/// --- ---
+///
+#[derive(Default)]
+struct I18nTestHelper {}
+
+/// Localization test helper implementation
+///
+/// Methods:
+/// --- ---
+/// new -> Create a new I18nTestHelper
+/// run_test -> Run a localization test case
+/// --- ---
+///
+impl I18nTestHelper {
+    /// Create a new I18nTestHelper
+    ///
+    /// Parameters:
+    /// --- ---
+    /// None
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// I18nTestHelper -> The new I18nTestHelper
+    /// --- ---
+    ///
+    fn new() -> Self {
+        Self {}
+    }
+
+    /// Run a localization test case
+    ///
+    /// Parameters:
+    /// --- ---
+    /// self -> The I18nTestHelper instance
+    /// test_case -> The localization test case to run
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// None
+    /// --- ---
+    ///
+    fn run_test(&mut self, test_case: &I18nTestCase) {
+        println!("Running i18n test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+        println!("Function: {}", test_case.function);
+        println!("Input: {}", test_case.input);
+
+        let actual = match test_case.function.as_str() {
+            "normalize_locale" => {
+                json!(normalize_locale(
+                    test_case.input.as_str().unwrap_or_default()
+                ))
+            }
+            "tr" => {
+                let localizer =
+                    Localizer::new(test_case.input["locale"].as_str().unwrap_or_default());
+                let id = test_case.input["id"].as_str().unwrap_or_default();
+                let english = test_case.input["english"].as_str().unwrap_or_default();
+                let args = string_args(&test_case.input["args"]);
+                let args: Vec<(&str, &str)> = args
+                    .iter()
+                    .map(|(name, value)| (name.as_str(), value.as_str()))
+                    .collect();
+                json!({
+                    "locale": localizer.locale(),
+                    "text": localizer.tr(id, english, &args),
+                })
+            }
+            other => panic!(
+                "Unknown function '{}' in test '{}'",
+                other, test_case.test_name
+            ),
+        };
+
+        assert_eq!(
+            actual, test_case.expected,
+            "Test '{}' returned {} but expected {}",
+            test_case.test_name, actual, test_case.expected
+        );
+        println!("Returned {} as expected\n", actual);
+    }
+}
+
+/// Read a JSON object of arguments as (name, value) pairs
+///
+/// Parameters:
+/// --- ---
+/// args -> The JSON object (missing or non-object means no arguments)
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Vec<(String, String)> -> The arguments
+/// --- ---
+///
+fn string_args(args: &Value) -> Vec<(String, String)> {
+    args.as_object()
+        .map(|args| {
+            args.iter()
+                .map(|(name, value)| (name.clone(), value.as_str().unwrap_or_default().to_string()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Run the i18n test file
+///
+/// Parameters:
+/// --- ---
+/// filename -> The filename to run
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// None
+/// --- ---
+///
+fn run_test_file(filename: &str) {
+    let mut helper = I18nTestHelper::new();
+    let content = utils::load_test_file("i18n", filename);
+    let test_cases: Vec<I18nTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse i18n JSON test file");
+
+    for test_case in test_cases {
+        helper.run_test(&test_case);
+    }
+}
+
+#[test]
+fn test_i18n_locales() {
+    run_test_file("locales.json");
+}
+
+#[test]
+fn test_i18n_translations() {
+    run_test_file("translations.json");
+}
+
+#[test]
+fn test_i18n_locale_files_cover_error_catalog() {
+    // every bundled translation file must parse and translate every compiler error
+    for (locale, _) in AVAILABLE_LOCALES
+        .iter()
+        .filter(|(locale, _)| *locale != "en")
+    {
+        let localizer = Localizer::new(locale);
+        assert_eq!(
+            localizer.locale(),
+            *locale,
+            "locales/{}.ftl failed to load",
+            locale
+        );
+
+        for template in CATALOG {
+            let id = format!("error-{}", template.key.replace('_', "-"));
+            // pass every placeholder the English text uses so Fluent has all its variables
+            let names: Vec<&str> = template
+                .text
+                .split('{')
+                .skip(1)
+                .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+                .collect();
+            let args: Vec<(&str, &str)> = names.iter().map(|name| (*name, "x")).collect();
+            assert!(
+                localizer.translate(&id, &args).is_some(),
+                "locales/{}.ftl is missing '{}'",
+                locale,
+                id
+            );
+        }
+    }
+}
@@ -0,0 +1,3 @@
+// Include the i18n_tests module
+#[path = "i18n_tests.rs"]
+mod i18n_tests;
@@ -0,0 +1,287 @@
+use crate::utils;
+/// tests/injection/injection_tests.rs
+///
+/// SQL injection tests
+///
+/// Responsible for testing that nothing typed into a query can change the database, using
+/// JSON-defined test cases. Each query case runs an adversarial query through the whole
+/// pipeline against a copy of the test database and checks that every table's schema and
+/// rows are unchanged afterwards. Each value case quotes a value with the escaping layer in
+/// dsl::escape and checks SQLite reads the literal back as the same value. Each statement
+/// case runs SQL a codegen bug could produce and checks the read-only search connection
+/// refuses it
+///
+/// Contains:
+/// --- ---
+/// QueryTestCase -> Adversarial query test case struct
+/// ValueTestCase -> Escaped value test case struct
+/// StatementTestCase -> Writing statement test case struct
+/// Helper functions:
+///     --- ---
+///     snapshot -> Every table's schema and row count
+///     --- ---
+/// --- ---
+///
+use classql::data::pool::open_connection;
+use classql::data::sql::{execute_query, get_test_db_path};
+use classql::dsl::escape::{like_literal, string_literal};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Adversarial query test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// input -> The query, trying to break out of a value
+/// expected_stage -> The stage expected to refuse the query ("lexer", "parser",
+///                   "semantic", "codegen"), None if it runs
+/// expected_count -> Expected number of results when it runs
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for QueryTestCase
+/// Deserialize -> Deserialize trait for QueryTestCase
+/// Serialize -> Serialize trait for QueryTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct QueryTestCase {
+    test_name: String,
+    description: String,
+    input: String,
+    #[serde(default)]
+    expected_stage: Option<String>,
+    #[serde(default)]
+    expected_count: usize,
+}
+
+/// Escaped value test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// value -> The raw value
+/// should_quote -> Whether the escaping layer accepts the value
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for ValueTestCase
+/// Deserialize -> Deserialize trait for ValueTestCase
+/// Serialize -> Serialize trait for ValueTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct ValueTestCase {
+    test_name: String,
+    description: String,
+    value: String,
+    should_quote: bool,
+}
+
+/// Writing statement test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// sql -> A statement that would change the database
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for StatementTestCase
+/// Deserialize -> Deserialize trait for StatementTestCase
+/// Serialize -> Serialize trait for StatementTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct StatementTestCase {
+    test_name: String,
+    description: String,
+    sql: String,
+}
+
+/// Every table's schema and row count
+///
+/// Parameters:
+/// --- ---
+/// db_path -> The database to read
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Vec<(String, String, i64)> -> (name, CREATE statement, rows) for each table, by name
+/// --- ---
+///
+fn snapshot(db_path: &Path) -> Vec<(String, String, i64)> {
+    let conn = open_connection(db_path).expect("database should open");
+    let mut stmt = conn
+        .prepare("SELECT name, sql FROM sqlite_master WHERE type = 'table' ORDER BY name")
+        .expect("schema should load");
+    let tables: Vec<(String, String)> = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .expect("schema should load")
+        .collect::<Result<_, _>>()
+        .expect("schema should load");
+    tables
+        .into_iter()
+        .map(|(name, sql)| {
+            let rows = conn
+                .query_row(
+                    &format!("SELECT COUNT(*) FROM \"{}\"", name.replace('"', "\"\"")),
+                    [],
+                    |row| row.get(0),
+                )
+                .expect("rows should count");
+            (name, sql, rows)
+        })
+        .collect()
+}
+
+#[test]
+fn test_injection_queries() {
+    let content = utils::load_test_file("injection", "queries.json");
+    let test_cases: Vec<QueryTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse queries JSON test file");
+
+    // a copy, so a query that got through could only ever change the copy
+    let path =
+        std::env::temp_dir().join(format!("classql-injection-test-{}.db", std::process::id()));
+    std::fs::copy(get_test_db_path(), &path).expect("test database should copy");
+    let before = snapshot(&path);
+
+    for test_case in test_cases {
+        println!("Running injection test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+
+        match (utils::compile(&test_case.input), &test_case.expected_stage) {
+            (Ok(sql), None) => {
+                let results = execute_query(&sql, &path).unwrap_or_else(|e| {
+                    panic!("Test '{}': query failed: {}", test_case.test_name, e)
+                });
+                assert_eq!(
+                    results.len(),
+                    test_case.expected_count,
+                    "Test '{}': wrong number of results",
+                    test_case.test_name
+                );
+            }
+            (Err((stage, _)), Some(expected)) => assert_eq!(
+                stage, expected,
+                "Test '{}': refused at the wrong stage",
+                test_case.test_name
+            ),
+            (Ok(sql), Some(expected)) => panic!(
+                "Test '{}': expected the {} to refuse it, generated {}",
+                test_case.test_name, expected, sql
+            ),
+            (Err((stage, error)), None) => panic!(
+                "Test '{}': {} failed: {}",
+                test_case.test_name, stage, error
+            ),
+        }
+        assert_eq!(
+            snapshot(&path),
+            before,
+            "Test '{}': the database changed",
+            test_case.test_name
+        );
+    }
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_injection_values() {
+    let content = utils::load_test_file("injection", "values.json");
+    let test_cases: Vec<ValueTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse values JSON test file");
+    let conn = open_connection(&get_test_db_path()).expect("test database should open");
+
+    for test_case in test_cases {
+        println!("Running value test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+
+        let literal = string_literal(&test_case.value);
+        assert_eq!(
+            literal.is_ok(),
+            test_case.should_quote,
+            "Test '{}': wrong result {:?}",
+            test_case.test_name,
+            literal
+        );
+        let Ok(literal) = literal else {
+            continue;
+        };
+
+        // the literal reads back as the value, and the LIKE pattern matches only it
+        let read: String = conn
+            .query_row(&format!("SELECT {}", literal), [], |row| row.get(0))
+            .unwrap_or_else(|e| panic!("Test '{}': {}", test_case.test_name, e));
+        assert_eq!(
+            read, test_case.value,
+            "Test '{}': literal {} read back differently",
+            test_case.test_name, literal
+        );
+        let (pattern, escape) =
+            like_literal(&test_case.value, false, false).expect("value should quote");
+        let matches: (bool, bool) = conn
+            .query_row(
+                &format!(
+                    "SELECT ?1 LIKE {}{}, ?2 LIKE {}{}",
+                    pattern, escape, pattern, escape
+                ),
+                [test_case.value.as_str(), "something else entirely"],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap_or_else(|e| panic!("Test '{}': {}", test_case.test_name, e));
+        assert_eq!(
+            matches,
+            (true, false),
+            "Test '{}': pattern {} matched wrongly",
+            test_case.test_name,
+            pattern
+        );
+    }
+}
+
+#[test]
+fn test_injection_read_only() {
+    let content = utils::load_test_file("injection", "statements.json");
+    let test_cases: Vec<StatementTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse statements JSON test file");
+
+    let path =
+        std::env::temp_dir().join(format!("classql-read-only-test-{}.db", std::process::id()));
+    std::fs::copy(get_test_db_path(), &path).expect("test database should copy");
+    let before = snapshot(&path);
+
+    for test_case in test_cases {
+        println!("Running read-only test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+
+        let error = execute_query(&test_case.sql, &path)
+            .expect_err(&format!("Test '{}': the write ran", test_case.test_name));
+        assert!(
+            error.contains("readonly"),
+            "Test '{}': refused for the wrong reason: {}",
+            test_case.test_name,
+            error
+        );
+        assert_eq!(
+            snapshot(&path),
+            before,
+            "Test '{}': the database changed",
+            test_case.test_name
+        );
+    }
+    let _ = std::fs::remove_file(&path);
+
+    // a search never creates a database that isn't there
+    assert!(execute_query("SELECT 1", &path).is_err());
+    assert!(!path.exists());
+}
@@ -0,0 +1,3 @@
+// Include the injection_tests module
+#[path = "injection_tests.rs"]
+mod injection_tests;
@@ -0,0 +1,312 @@
+use crate::utils;
+/// tests/lookups/lookups_tests.rs
+///
+/// Lookup list tests
+///
+/// Responsible for testing the background-loaded lookup lists in data::lookups using
+/// JSON-defined test cases: value completions offered after a field's condition, and exact
+/// values that no class has being reported with the closest known values
+///
+/// Contains:
+/// --- ---
+/// CompletionTestCase -> Value completion test case struct
+/// CompletionDetail -> A completion with the kind and description it should be shown with
+/// ValidationTestCase -> Value validation test case struct
+/// LookupsTestHelper -> Lookup list test helper struct
+///     Methods:
+///     --- ---
+///     new -> Create a new LookupsTestHelper with the lookups loaded
+///     run_completion_test -> Run a value completion test case
+///     run_validation_test -> Run a value validation test case
+///     --- ---
+/// --- ---
+///
+use classql::data::lookups::load_lookups;
+use classql::dsl::compiler::{Compiler, CompilerResult};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Value completion test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// input -> The input to complete
+/// expected_contains -> Completions that should be offered
+/// expected_lacks -> Completions that should NOT be offered
+/// expected_details -> Completions that should be offered with a kind and description
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for CompletionTestCase
+/// Deserialize -> Deserialize trait for CompletionTestCase
+/// Serialize -> Serialize trait for CompletionTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct CompletionTestCase {
+    test_name: String,
+    description: String,
+    input: String,
+    #[serde(default)]
+    expected_contains: Vec<String>,
+    #[serde(default)]
+    expected_lacks: Vec<String>,
+    #[serde(default)]
+    expected_details: Vec<CompletionDetail>,
+}
+
+/// A completion with the kind and description it should be shown with
+///
+/// Fields:
+/// --- ---
+/// text -> The completion
+/// kind -> Label of its kind (e.g., "value", "condition")
+/// description -> Its description
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for CompletionDetail
+/// Deserialize -> Deserialize trait for CompletionDetail
+/// Serialize -> Serialize trait for CompletionDetail
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct CompletionDetail {
+    text: String,
+    kind: String,
+    description: String,
+}
+
+/// Value validation test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// input -> The query to run
+/// expected_code -> Expected error code, None if the query should succeed
+/// expected_message -> Expected error message (optional)
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for ValidationTestCase
+/// Deserialize -> Deserialize trait for ValidationTestCase
+/// Serialize -> Serialize trait for ValidationTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct ValidationTestCase {
+    test_name: String,
+    description: String,
+    input: String,
+    #[serde(default)]
+    expected_code: Option<String>,
+    #[serde(default)]
+    expected_message: Option<String>,
+}
+
+/// Lookup list test helper struct
+///
+/// Fields:
+/// --- ---
+/// compiler -> Compiler for the test school, with its lookups loaded
+/// --- ---
+///
+struct LookupsTestHelper {
+    compiler: Compiler,
+}
+
+/// Lookup list test helper implementation
+///
+/// Methods:
+/// --- ---
+/// new -> Create a new LookupsTestHelper with the lookups loaded
+/// run_completion_test -> Run a value completion test case
+/// run_validation_test -> Run a value validation test case
+/// --- ---
+///
+impl LookupsTestHelper {
+    /// Create a new LookupsTestHelper with the lookups loaded
+    ///
+    /// Parameters:
+    /// --- ---
+    /// None
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// LookupsTestHelper -> The new LookupsTestHelper
+    /// --- ---
+    ///
+    fn new() -> Self {
+        let mut compiler = Compiler::new();
+        compiler.set_school_id(Some("_test".to_string()));
+        for handle in compiler.warm_up_lookups() {
+            handle.join().expect("lookup warm-up should finish");
+        }
+        Self { compiler }
+    }
+
+    /// Run a value completion test case
+    ///
+    /// Parameters:
+    /// --- ---
+    /// self -> The LookupsTestHelper instance
+    /// test_case -> The completion test case to run
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// None
+    /// --- ---
+    ///
+    fn run_completion_test(&mut self, test_case: &CompletionTestCase) {
+        println!("Running completion test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+        println!("Input: {:?}", test_case.input);
+
+        let completions = self.compiler.get_tab_completion(test_case.input.clone());
+        for expected in &test_case.expected_contains {
+            assert!(
+                completions.contains(expected),
+                "Test '{}': expected completion {:?} in {:?}",
+                test_case.test_name,
+                expected,
+                completions
+            );
+        }
+        for unexpected in &test_case.expected_lacks {
+            assert!(
+                !completions.contains(unexpected),
+                "Test '{}': did not expect completion {:?} in {:?}",
+                test_case.test_name,
+                unexpected,
+                completions
+            );
+        }
+        let items = self.compiler.get_completion_items(test_case.input.clone());
+        for expected in &test_case.expected_details {
+            let item = items
+                .iter()
+                .find(|item| item.text == expected.text)
+                .unwrap_or_else(|| {
+                    panic!(
+                        "Test '{}': expected completion {:?} in {:?}",
+                        test_case.test_name, expected.text, completions
+                    )
+                });
+            assert_eq!(
+                (item.kind.label(), item.description.as_str()),
+                (expected.kind.as_str(), expected.description.as_str()),
+                "Test '{}': wrong details for completion {:?}",
+                test_case.test_name,
+                expected.text
+            );
+        }
+        println!("Completions: {:?}\n", completions);
+    }
+
+    /// Run a value validation test case
+    ///
+    /// Parameters:
+    /// --- ---
+    /// self -> The LookupsTestHelper instance
+    /// test_case -> The validation test case to run
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// None
+    /// --- ---
+    ///
+    fn run_validation_test(&mut self, test_case: &ValidationTestCase) {
+        println!("Running validation test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+        println!("Input: {}", test_case.input);
+
+        match (
+            self.compiler.run(&test_case.input),
+            &test_case.expected_code,
+        ) {
+            (CompilerResult::Success { .. }, None) => {}
+            (CompilerResult::SemanticError { code, message, .. }, Some(expected_code)) => {
+                assert_eq!(
+                    code.code(),
+                    expected_code,
+                    "Test '{}' reported the wrong code: {}",
+                    test_case.test_name,
+                    message
+                );
+                if let Some(expected_message) = &test_case.expected_message {
+                    assert_eq!(
+                        &message, expected_message,
+                        "Test '{}' reported the wrong message",
+                        test_case.test_name
+                    );
+                }
+            }
+            (CompilerResult::SemanticError { message, .. }, None) => {
+                panic!(
+                    "Test '{}' should succeed but failed: {}",
+                    test_case.test_name, message
+                );
+            }
+            (CompilerResult::Success { .. }, Some(expected_code)) => {
+                panic!(
+                    "Test '{}' should fail with {} but succeeded",
+                    test_case.test_name, expected_code
+                );
+            }
+            (_, _) => panic!(
+                "Test '{}' failed before semantic analysis",
+                test_case.test_name
+            ),
+        }
+        println!("Result matched\n");
+    }
+}
+
+#[test]
+fn test_lookups_completions() {
+    let mut helper = LookupsTestHelper::new();
+    let content = utils::load_test_file("lookups", "completions.json");
+    let test_cases: Vec<CompletionTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse completions JSON test file");
+
+    for test_case in test_cases {
+        helper.run_completion_test(&test_case);
+    }
+}
+
+#[test]
+fn test_lookups_validation() {
+    let mut helper = LookupsTestHelper::new();
+    let content = utils::load_test_file("lookups", "validation.json");
+    let test_cases: Vec<ValidationTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse validation JSON test file");
+
+    for test_case in test_cases {
+        helper.run_validation_test(&test_case);
+    }
+}
+
+#[test]
+fn test_lookups_load_from_database() {
+    let lookups =
+        load_lookups(Path::new("classy/test.db"), None).expect("test database should load");
+    assert!(lookups.subjects.contains(&"CMPT".to_string()));
+    assert!(lookups.subjects.windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(lookups.campuses.contains(&"On-Line".to_string()));
+    assert_eq!(lookups.meeting_types, ["LAB", "LEC", "WEB"]);
+    assert!(lookups.professor_prefixes.contains(&"Bowen".to_string()));
+    assert!(lookups.professor_prefixes.iter().all(|p| !p.contains(',')));
+
+    let missing = std::env::temp_dir().join("classql-lookups-missing.db");
+    assert_eq!(load_lookups(&missing, None), Ok(Default::default()));
+    assert!(!missing.exists(), "loading should not create the database");
+}
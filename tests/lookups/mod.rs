@@ -0,0 +1,3 @@
+// Include the lookups_tests module
+#[path = "lookups_tests.rs"]
+mod lookups_tests;
@@ -0,0 +1,233 @@
+use crate::utils;
+/// tests/methods/methods_tests.rs
+///
+/// Instruction method tests
+///
+/// Responsible for testing the normalization of instruction methods using JSON-defined test
+/// cases. Each spelling case checks the method data::sql::normalize_instruction_method reads
+/// a spelling as. Each query case writes synced methods into a copy of the test database,
+/// normalizes them the way a sync does, and checks which sections a method query returns
+///
+/// Contains:
+/// --- ---
+/// SpellingTestCase -> Instruction method spelling test case struct
+/// MethodSection -> A section given a synced method for a query case
+/// QueryTestCase -> Method query test case struct
+/// Helper functions:
+///     --- ---
+///     method_copy -> Copy the test database with methods written into it and normalized
+///     --- ---
+/// --- ---
+///
+use classql::data::pool::open_connection;
+use classql::data::sql::{execute_query, get_test_db_path, normalize_instruction_method};
+use classql::data::sync::normalize_instruction_methods;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Instruction method spelling test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// input -> The method as a school spells it
+/// expected -> The method it should be read as
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for SpellingTestCase
+/// Deserialize -> Deserialize trait for SpellingTestCase
+/// Serialize -> Serialize trait for SpellingTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct SpellingTestCase {
+    test_name: String,
+    description: String,
+    input: String,
+    expected: String,
+}
+
+/// A section given a synced method for a query case
+///
+/// Fields:
+/// --- ---
+/// id -> Unique ID, e.g., "CMPT:103L-111"
+/// method -> The method as the school's sync stores it
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for MethodSection
+/// Deserialize -> Deserialize trait for MethodSection
+/// Serialize -> Serialize trait for MethodSection
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct MethodSection {
+    id: String,
+    method: String,
+}
+
+/// Method query test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// sections -> Sections given methods before the copy is normalized
+/// input -> The query
+/// expected_count -> Expected number of results
+/// expected_methods -> Expected methods of the results that have one, sorted
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for QueryTestCase
+/// Deserialize -> Deserialize trait for QueryTestCase
+/// Serialize -> Serialize trait for QueryTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct QueryTestCase {
+    test_name: String,
+    description: String,
+    sections: Vec<MethodSection>,
+    input: String,
+    expected_count: usize,
+    #[serde(default)]
+    expected_methods: Vec<String>,
+}
+
+/// Copy the test database with methods written into it and normalized
+///
+/// Parameters:
+/// --- ---
+/// test_name -> The name of the test, used to name the copy
+/// sections -> The sections to give methods
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// (PathBuf, usize) -> The copy, to be removed by the test, and how many methods changed
+/// --- ---
+///
+fn method_copy(test_name: &str, sections: &[MethodSection]) -> (PathBuf, usize) {
+    let path = std::env::temp_dir().join(format!(
+        "classql-methods-{}-{}.db",
+        test_name,
+        std::process::id()
+    ));
+    std::fs::copy(get_test_db_path(), &path).expect("test database should copy");
+
+    let conn = open_connection(&path).expect("copy should open");
+    for section in sections {
+        let (subject, rest) = section.id.split_once(':').expect("id has a subject");
+        let (course, sequence) = rest.split_once('-').expect("id has a section");
+        let updated = conn
+            .execute(
+                "UPDATE sections SET instruction_method = ?1 \
+                 WHERE subject_code = ?2 AND course_number = ?3 AND sequence = ?4",
+                [section.method.as_str(), subject, course, sequence],
+            )
+            .expect("method should be written");
+        assert!(updated > 0, "{} is not in the test database", section.id);
+    }
+    let changed = normalize_instruction_methods(&conn).expect("methods should normalize");
+    (path, changed)
+}
+
+#[test]
+fn test_method_spellings() {
+    let content = utils::load_test_file("methods", "spellings.json");
+    let test_cases: Vec<SpellingTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse spellings JSON test file");
+
+    for test_case in test_cases {
+        println!("Running method spelling test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+
+        let method = normalize_instruction_method(&test_case.input);
+        assert_eq!(
+            method, test_case.expected,
+            "Test '{}': wrong method",
+            test_case.test_name
+        );
+        // a normalized method reads as itself
+        assert_eq!(
+            normalize_instruction_method(&method),
+            method,
+            "Test '{}': method not stable",
+            test_case.test_name
+        );
+        println!("Method: {}\n", method);
+    }
+}
+
+#[test]
+fn test_normalize_methods_once() {
+    let sections = [
+        MethodSection {
+            id: "CMPT:103L-111".to_string(),
+            method: "WEB".to_string(),
+        },
+        MethodSection {
+            id: "CMPT:120L-111".to_string(),
+            method: "ONLN".to_string(),
+        },
+        MethodSection {
+            id: "CMPT:220L-111".to_string(),
+            method: "Online".to_string(),
+        },
+    ];
+    let (path, changed) = method_copy("once", &sections);
+    // "Online" is already normalized, so only the other two spellings change
+    assert_eq!(changed, 2);
+
+    // normalizing again changes nothing
+    let conn = open_connection(&path).expect("copy should open");
+    assert_eq!(normalize_instruction_methods(&conn), Ok(0));
+    drop(conn);
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_method_queries() {
+    let content = utils::load_test_file("methods", "queries.json");
+    let test_cases: Vec<QueryTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse queries JSON test file");
+
+    for test_case in test_cases {
+        println!("Running method query test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+
+        let (path, _) = method_copy(&test_case.test_name, &test_case.sections);
+        let sql = utils::compile(&test_case.input).unwrap_or_else(|(stage, e)| {
+            panic!("Test '{}': {} failed: {}", test_case.test_name, stage, e)
+        });
+        let results = execute_query(&sql, &path)
+            .unwrap_or_else(|e| panic!("Test '{}': query failed: {}", test_case.test_name, e));
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(
+            results.len(),
+            test_case.expected_count,
+            "Test '{}': wrong number of results",
+            test_case.test_name
+        );
+        let mut methods: Vec<String> = results
+            .iter()
+            .filter_map(|class| class.instruction_method.clone())
+            .filter(|method| !method.is_empty())
+            .collect();
+        methods.sort();
+        assert_eq!(
+            methods, test_case.expected_methods,
+            "Test '{}': wrong methods",
+            test_case.test_name
+        );
+        println!("Results: {}\n", results.len());
+    }
+}
@@ -0,0 +1,3 @@
+// Include the methods_tests module
+#[path = "methods_tests.rs"]
+mod methods_tests;
@@ -1,6 +1,40 @@
+mod advisor;
+mod aliases;
+mod app_store;
+mod bench;
+mod catalog;
 mod codegen;
+mod conflicts;
+mod cost;
+mod duplicates;
+mod errors;
+mod export;
+mod fees;
+mod finals;
+mod formatter;
+mod free_time;
+mod i18n;
+mod injection;
 mod lexer;
+mod lookups;
+mod methods;
 mod parser;
+mod professors;
+mod profiles;
 mod query;
+mod registrar_import;
+mod restrictions;
+mod result_diff;
+mod sample;
+mod save;
 mod semantic;
+mod session;
+mod share;
+mod sync;
+mod term_index;
+mod text;
+mod time;
+mod tui;
 mod utils;
+mod visualize;
+mod watch;
@@ -9,12 +9,14 @@ use crate::utils;
 /// --- ---
 /// ParserTestCase -> Parser test case struct
 /// ExpectedToken -> Expected token struct
+/// CompletionTestCase -> Completion suggestion test case struct
 /// ParserTestHelper -> Parser test helper struct
 ///     Methods:
 ///     --- ---
 ///     new -> Create a new ParserTestHelper
 ///     create_tokens -> Create tokens from input
 ///     test_parse -> Test the parser
+///     test_completion -> Test the parser's completion suggestions
 ///     validate_problematic_tokens -> Validate problematic tokens
 ///     validate_error_type -> Validate error type
 ///     validate_error_message -> Validate error message
@@ -85,6 +87,35 @@ struct ExpectedToken {
     end: usize,
 }
 
+/// Completion suggestion test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// input -> The partial query to complete
+/// expected_contains -> Suggestions that should be offered
+/// expected_lacks -> Suggestions that should NOT be offered
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for CompletionTestCase
+/// Deserialize -> Deserialize trait for CompletionTestCase
+/// Serialize -> Serialize trait for CompletionTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct CompletionTestCase {
+    test_name: String,
+    description: String,
+    input: String,
+    #[serde(default)]
+    expected_contains: Vec<String>,
+    #[serde(default)]
+    expected_lacks: Vec<String>,
+}
+
 /// Parser test helper struct
 ///
 /// Fields:
@@ -107,6 +138,7 @@ struct ParserTestHelper {}
 /// new -> Create a new ParserTestHelper
 /// create_tokens -> Create tokens from input
 /// test_parse -> Test the parser
+/// test_completion -> Test the parser's completion suggestions
 /// validate_problematic_tokens -> Validate problematic tokens
 /// validate_error_type -> Validate error type
 /// validate_error_message -> Validate error message
@@ -154,6 +186,49 @@ impl ParserTestHelper {
         }
     }
 
+    /// Test the parser's completion suggestions
+    ///
+    /// Parameters:
+    /// --- ---
+    /// self -> The ParserTestHelper instance
+    /// test_case -> The completion test case to test
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// None
+    /// --- ---
+    ///
+    fn test_completion(&mut self, test_case: &CompletionTestCase) {
+        println!("Running completion test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+        println!("Input: '{}'", test_case.input);
+
+        let tokens = self.create_tokens(&test_case.input);
+        let mut parser = Parser::new(test_case.input.clone());
+        let suggestions = parser.get_completion_suggestions(&tokens);
+
+        for expected in &test_case.expected_contains {
+            assert!(
+                suggestions.contains(expected),
+                "Test '{}': expected suggestion {:?} in {:?}",
+                test_case.test_name,
+                expected,
+                suggestions
+            );
+        }
+        for unexpected in &test_case.expected_lacks {
+            assert!(
+                !suggestions.contains(unexpected),
+                "Test '{}': did not expect suggestion {:?} in {:?}",
+                test_case.test_name,
+                unexpected,
+                suggestions
+            );
+        }
+        println!("Suggestions: {:?}\n", suggestions);
+    }
+
     /// Test the parser
     ///
     /// Parameters:
@@ -318,6 +393,7 @@ impl ParserTestHelper {
             SyntaxError::EmptyQuery => "EmptyQuery",
             SyntaxError::ExpectedAfter { .. } => "ExpectedAfter",
             SyntaxError::InvalidContext { .. } => "InvalidContext",
+            SyntaxError::TooDeeplyNested { .. } => "TooDeeplyNested",
         };
 
         assert_eq!(
@@ -458,3 +534,25 @@ fn test_ast_structure() {
 fn test_edge_cases() {
     run_test_file("edge_cases.json");
 }
+
+#[test]
+fn test_value_lists() {
+    run_test_file("value_lists.json");
+}
+
+#[test]
+fn test_numeric_ranges() {
+    run_test_file("numeric_ranges.json");
+}
+
+#[test]
+fn test_completions() {
+    let mut helper = ParserTestHelper::new();
+    let content = utils::load_test_file("parser", "completions.json");
+    let test_cases: Vec<CompletionTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse completions JSON test file");
+
+    for test_case in test_cases {
+        helper.test_completion(&test_case);
+    }
+}
@@ -0,0 +1,3 @@
+// Include the professors_tests module
+#[path = "professors_tests.rs"]
+mod professors_tests;
@@ -0,0 +1,295 @@
+use crate::utils;
+/// tests/professors/professors_tests.rs
+///
+/// Professor directory tests
+///
+/// Responsible for testing the professor directory in data::professors against the test
+/// database using JSON-defined test cases: each filter case types a search and compares the
+/// professors matching it, and each name query case searches a copy of the test database
+/// with its names normalized and compares the professors found. Also tests the directory's
+/// departments and section counts, a professor's sections, and name normalization
+///
+/// Contains:
+/// --- ---
+/// FilterTestCase -> Directory search test case struct
+/// NameQueryTestCase -> Professor name search test case struct
+/// Helper functions:
+///     --- ---
+///     test_scope -> The test database, unfiltered like searches on it
+///     normalized_copy -> Copy the test database and normalize its professors' names
+///     --- ---
+/// --- ---
+///
+use classql::data::catalog::CatalogScope;
+use classql::data::pool::open_connection;
+use classql::data::professors::{
+    fetch_professor_sections, fetch_professors, filter_professors, normalize_professor_names,
+};
+use classql::data::sql::{execute_query, get_test_db_path};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+/// Directory search test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// filter -> The text typed in the search box
+/// expected_count -> Expected number of matching professors
+/// expected_first -> Expected names of the first matches
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for FilterTestCase
+/// Deserialize -> Deserialize trait for FilterTestCase
+/// Serialize -> Serialize trait for FilterTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct FilterTestCase {
+    test_name: String,
+    description: String,
+    filter: String,
+    expected_count: usize,
+    expected_first: Vec<String>,
+}
+
+/// Professor name search test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// input -> The query
+/// expected_professors -> Names of the professors teaching the sections found, sorted
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for NameQueryTestCase
+/// Deserialize -> Deserialize trait for NameQueryTestCase
+/// Serialize -> Serialize trait for NameQueryTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct NameQueryTestCase {
+    test_name: String,
+    description: String,
+    input: String,
+    expected_professors: Vec<String>,
+}
+
+/// The test database, unfiltered like searches on it
+///
+/// Parameters:
+/// --- ---
+/// None
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// CatalogScope -> Every school and term in classy/test.db
+/// --- ---
+///
+fn test_scope() -> CatalogScope {
+    CatalogScope {
+        db_path: get_test_db_path(),
+        school_id: None,
+        term_id: None,
+    }
+}
+
+/// Copy the test database and normalize its professors' names
+///
+/// Parameters:
+/// --- ---
+/// name -> Name of the test, so tests running at once use different files
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// (PathBuf, usize) -> The copy, to be removed by the test, and how many names changed
+/// --- ---
+///
+fn normalized_copy(name: &str) -> (PathBuf, usize) {
+    let path = std::env::temp_dir().join(format!(
+        "classql-professors-{}-{}.db",
+        name,
+        std::process::id()
+    ));
+    std::fs::copy(get_test_db_path(), &path).expect("test database should copy");
+    let conn = open_connection(&path).expect("copy should open");
+    let changed = normalize_professor_names(&conn).expect("names should normalize");
+    (path, changed)
+}
+
+#[test]
+fn test_professors_filter() {
+    let content = utils::load_test_file("professors", "filter.json");
+    let test_cases: Vec<FilterTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse filter JSON test file");
+    let professors = fetch_professors(&test_scope()).expect("professors should load");
+
+    for test_case in test_cases {
+        println!("Running filter test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+
+        let names: Vec<String> = filter_professors(&professors, &test_case.filter)
+            .into_iter()
+            .map(|i| professors[i].name.clone())
+            .collect();
+        assert_eq!(
+            names.len(),
+            test_case.expected_count,
+            "Test '{}': wrong number of matches",
+            test_case.test_name
+        );
+        assert_eq!(
+            &names[..test_case.expected_first.len()],
+            test_case.expected_first.as_slice(),
+            "Test '{}': wrong first matches",
+            test_case.test_name
+        );
+    }
+}
+
+#[test]
+fn test_professors_directory() {
+    let professors = fetch_professors(&test_scope()).expect("professors should load");
+    let summary: Vec<(String, Option<String>, Option<String>, usize)> = professors
+        .iter()
+        .take(3)
+        .map(|p| {
+            (
+                p.name.clone(),
+                p.department.clone(),
+                p.email.clone(),
+                p.sections,
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (
+                "Abdelrahman, Allaeddin".to_string(),
+                Some("Arabic".to_string()),
+                Some("Al.Abdelrahman@marist.edu".to_string()),
+                2
+            ),
+            (
+                "Ackers, Robin".to_string(),
+                Some("Psychology".to_string()),
+                Some("Robin.Ackers@marist.edu".to_string()),
+                1
+            ),
+            // two First Year Seminar sections and one Political Science section
+            (
+                "Al-Saeedi, Safa".to_string(),
+                Some("First Year Seminar".to_string()),
+                Some("Safa.Al-Saeedi@marist.edu".to_string()),
+                3
+            ),
+        ]
+    );
+
+    // listing the school's term gives the same directory, another term an empty one
+    let term = CatalogScope {
+        school_id: Some("marist".to_string()),
+        term_id: Some("202440".to_string()),
+        ..test_scope()
+    };
+    assert_eq!(
+        fetch_professors(&term).expect("term should load"),
+        professors
+    );
+    let other_term = CatalogScope {
+        term_id: Some("202540".to_string()),
+        ..term
+    };
+    assert!(fetch_professors(&other_term)
+        .expect("other term should load")
+        .is_empty());
+}
+
+#[test]
+fn test_professors_sections() {
+    let scope = test_scope();
+    let professors = fetch_professors(&scope).expect("professors should load");
+    let aneja = professors
+        .iter()
+        .find(|p| p.name == "Aneja, Sandhya")
+        .expect("Aneja should be listed");
+    assert_eq!(aneja.department.as_deref(), Some("Computing Technology"));
+
+    let sections: Vec<String> = fetch_professor_sections(&scope, aneja)
+        .expect("sections should load")
+        .into_iter()
+        .map(|c| {
+            assert_eq!(c.professor_name.as_deref(), Some("Aneja, Sandhya"));
+            format!(
+                "{} {}-{}",
+                c.subject_code, c.course_number, c.section_sequence
+            )
+        })
+        .collect();
+    assert_eq!(
+        sections,
+        vec!["CMPT 306N-113", "CMPT 306N-114", "MSCS 560L-232"]
+    );
+    assert_eq!(sections.len(), aneja.sections);
+}
+
+#[test]
+fn test_professors_normalize_names() {
+    // the test database was synced with first and last names swapped
+    let (path, changed) = normalized_copy("normalize");
+    assert!(changed > 400, "only {} names changed", changed);
+    let conn = open_connection(&path).expect("copy should open");
+    let (first, last): (String, String) = conn
+        .query_row(
+            "SELECT first_name, last_name FROM professors WHERE name = 'Labouseur, Alan'",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .expect("Labouseur should be listed");
+    assert_eq!((first.as_str(), last.as_str()), ("Alan", "Labouseur"));
+
+    // normalizing again changes nothing
+    assert_eq!(normalize_professor_names(&conn), Ok(0));
+    drop(conn);
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_professors_name_queries() {
+    let content = utils::load_test_file("professors", "name_queries.json");
+    let test_cases: Vec<NameQueryTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse name queries JSON test file");
+    let (path, _) = normalized_copy("name-queries");
+
+    for test_case in test_cases {
+        println!("Running name query test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+
+        let sql = utils::compile(&test_case.input).unwrap_or_else(|(stage, e)| {
+            panic!("Test '{}': {} failed: {}", test_case.test_name, stage, e)
+        });
+        let classes = execute_query(&sql, &path)
+            .unwrap_or_else(|e| panic!("Test '{}': query failed: {}", test_case.test_name, e));
+        let professors: BTreeSet<String> = classes
+            .into_iter()
+            .filter_map(|class| class.professor_name)
+            .collect();
+        assert_eq!(
+            professors.into_iter().collect::<Vec<_>>(),
+            test_case.expected_professors,
+            "Test '{}': wrong professors",
+            test_case.test_name
+        );
+    }
+    let _ = std::fs::remove_file(&path);
+}
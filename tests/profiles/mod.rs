@@ -0,0 +1,3 @@
+// Include the profiles_tests module
+#[path = "profiles_tests.rs"]
+mod profiles_tests;
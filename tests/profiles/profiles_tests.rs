@@ -0,0 +1,229 @@
+use crate::utils;
+/// tests/profiles/profiles_tests.rs
+///
+/// Workspace profile tests
+///
+/// Responsible for testing the workspace profiles in data::profiles using JSON-defined test
+/// cases: each constrain case adds a profile's constraints to a query and compares the query
+/// searched; each positions case maps error ranges in that query back onto the query as
+/// typed. Also tests profile summaries, switching between profiles, and that profiles
+/// round-trip through a file
+///
+/// Contains:
+/// --- ---
+/// ConstrainTestCase -> Constrained query test case struct
+/// PositionsTestCase -> Error range mapping test case struct
+/// Helper functions:
+///     --- ---
+///     named -> Build a profile with just a name
+///     --- ---
+/// --- ---
+///
+use classql::data::profiles::{unconstrain_positions, Profile, Profiles};
+use serde::{Deserialize, Serialize};
+
+/// Constrained query test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// query -> The query as typed
+/// constraints -> The profile's constraints
+/// expected -> Expected query searched
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for ConstrainTestCase
+/// Deserialize -> Deserialize trait for ConstrainTestCase
+/// Serialize -> Serialize trait for ConstrainTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct ConstrainTestCase {
+    test_name: String,
+    description: String,
+    query: String,
+    constraints: Vec<String>,
+    expected: String,
+}
+
+/// Error range mapping test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// query -> The query as typed
+/// positions -> Byte ranges in the constrained query
+/// expected -> Expected byte ranges in the query as typed
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for PositionsTestCase
+/// Deserialize -> Deserialize trait for PositionsTestCase
+/// Serialize -> Serialize trait for PositionsTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct PositionsTestCase {
+    test_name: String,
+    description: String,
+    query: String,
+    positions: Vec<(usize, usize)>,
+    expected: Vec<(usize, usize)>,
+}
+
+/// Build a profile with just a name
+///
+/// Parameters:
+/// --- ---
+/// name -> The profile's name
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Profile -> A profile with no school, term, favorites, constraints, or tags
+/// --- ---
+///
+fn named(name: &str) -> Profile {
+    Profile {
+        name: name.to_string(),
+        ..Profile::default()
+    }
+}
+
+#[test]
+fn test_profiles_constrain() {
+    let content = utils::load_test_file("profiles", "constrain.json");
+    let test_cases: Vec<ConstrainTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse constrain JSON test file");
+
+    for test_case in test_cases {
+        println!("Running constrain test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+
+        let profile = Profile {
+            constraints: test_case.constraints.clone(),
+            ..named("test")
+        };
+        assert_eq!(
+            profile.constrain(&test_case.query),
+            test_case.expected,
+            "Test '{}': wrong query searched",
+            test_case.test_name
+        );
+    }
+}
+
+#[test]
+fn test_profiles_unconstrain_positions() {
+    let content = utils::load_test_file("profiles", "positions.json");
+    let test_cases: Vec<PositionsTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse positions JSON test file");
+
+    for test_case in test_cases {
+        println!("Running positions test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+
+        assert_eq!(
+            unconstrain_positions(&test_case.query, &test_case.positions),
+            test_case.expected,
+            "Test '{}': wrong ranges",
+            test_case.test_name
+        );
+    }
+}
+
+#[test]
+fn test_profiles_summary() {
+    assert_eq!(named("Undeclared").summary(), "Undeclared");
+
+    let profile = Profile {
+        queries: vec!["subject is CMPT".to_string(), "prof is Smith".to_string()],
+        constraints: vec!["credits >= 3".to_string()],
+        tags: vec!["major".to_string(), "cs".to_string()],
+        ..named("CS major")
+    };
+    assert_eq!(
+        profile.summary(),
+        "CS major [major, cs] · 2 favorites · 1 constraint"
+    );
+}
+
+#[test]
+fn test_profiles_switching() {
+    let mut profiles = Profiles::default();
+    assert_eq!(profiles.switch_next(), None);
+
+    assert!(!profiles.upsert(named("CS major")));
+    assert!(!profiles.upsert(named("Minor exploration")));
+    // the same name in another case replaces the profile rather than adding one
+    assert!(profiles.upsert(Profile {
+        tags: vec!["major".to_string()],
+        ..named("cs MAJOR")
+    }));
+    assert_eq!(profiles.profiles.len(), 2);
+
+    // in order, then back to no profile, then around again
+    let switched: Vec<Option<String>> = (0..4)
+        .map(|_| profiles.switch_next().map(|profile| profile.name.clone()))
+        .collect();
+    assert_eq!(
+        switched,
+        vec![
+            Some("cs MAJOR".to_string()),
+            Some("Minor exploration".to_string()),
+            None,
+            Some("cs MAJOR".to_string()),
+        ]
+    );
+    assert_eq!(
+        profiles
+            .find("CS Major")
+            .map(|profile| profile.tags.clone()),
+        Some(vec!["major".to_string()])
+    );
+
+    // removing the active profile leaves none in use
+    assert!(profiles.remove("CS MAJOR"));
+    assert!(!profiles.remove("CS MAJOR"));
+    assert_eq!(profiles.active, None);
+    assert_eq!(profiles.active_profile(), None);
+}
+
+#[test]
+fn test_profiles_round_trip() {
+    let mut profiles = Profiles::default();
+    profiles.upsert(Profile {
+        name: "CS major".to_string(),
+        school_id: Some("marist".to_string()),
+        term_id: Some("202540".to_string()),
+        queries: vec!["subject is CMPT".to_string()],
+        constraints: vec!["credits >= 3".to_string()],
+        tags: vec!["major".to_string()],
+    });
+    profiles.active = Some("CS major".to_string());
+
+    let path = std::env::temp_dir().join("classql-profiles-test.json");
+    profiles.save(&path).expect("profiles should save");
+    let loaded = Profiles::load(&path).expect("profiles should load");
+    let _ = std::fs::remove_file(&path);
+    // the profile in use is kept with the session, not in the file
+    assert_eq!(loaded.profiles, profiles.profiles);
+    assert_eq!(loaded.active, None);
+
+    let missing = std::env::temp_dir().join("classql-profiles-missing.json");
+    let _ = std::fs::remove_file(&missing);
+    assert_eq!(
+        Profiles::load(&missing).expect("missing profiles should load"),
+        Profiles::default()
+    );
+
+    // fields left out of a hand-written file are empty
+    let partial: Profiles = serde_json::from_str(r#"{"profiles": [{"name": "Minor"}]}"#)
+        .expect("a partial profiles file should parse");
+    assert_eq!(partial.profiles, vec![named("Minor")]);
+}
@@ -9,6 +9,7 @@ use crate::utils;
 /// Contains:
 /// --- ---
 /// QueryTestCase -> Query test case struct
+/// TraceTestCase -> Compiler trace test case struct
 /// QueryTestHelper -> Query test helper struct
 ///     Methods:
 ///     --- ---
@@ -19,11 +20,19 @@ use crate::utils;
 ///     --- ---
 ///     load_test_file -> Load the test file
 ///     run_test_file -> Run the test file
+///     test_session_queries_recorded_spans -> Run the session queries with term spans recorded
+///     timing -> Build a QueryTiming from millisecond counts
 ///     --- ---
 /// --- ---
 ///
-use classql::dsl::compiler::Compiler;
+use classql::data::pool::open_connection;
+use classql::data::query_log::{format_entry, record_if_slow};
+use classql::data::sql::{execute_query, Class};
+use classql::data::sync::record_term_spans;
+use classql::dsl::codegen::{limit_clause, ResultOrder, DEFAULT_MAX_ROWS};
+use classql::dsl::compiler::{Compiler, CompilerResult, QueryTiming, TraceEvent};
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 /// Query test case struct
 ///
@@ -37,6 +46,7 @@ use serde::{Deserialize, Serialize};
 /// expected_classes -> Hardcoded expected class results (optional)
 /// min_count -> Minimum number of results (optional)
 /// max_count -> Maximum number of results (optional)
+/// expected_notices -> Notices of what the parser assumed, in order (optional)
 /// --- ---
 ///
 /// Implemented Traits:
@@ -60,6 +70,33 @@ struct QueryTestCase {
     min_count: Option<usize>,
     #[serde(default)]
     max_count: Option<usize>,
+    #[serde(default)]
+    expected_notices: Option<Vec<String>>,
+}
+
+/// Compiler trace test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// input -> The input query to compile
+/// expected -> Expected trace events, as displayed, in order
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for TraceTestCase
+/// Deserialize -> Deserialize trait for TraceTestCase
+/// Serialize -> Serialize trait for TraceTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct TraceTestCase {
+    test_name: String,
+    description: String,
+    input: String,
+    expected: Vec<String>,
 }
 
 /// Expected class result struct
@@ -152,7 +189,9 @@ impl QueryTestHelper {
         let result = compiler.run(&test_case.input);
 
         match result {
-            classql::dsl::compiler::CompilerResult::Success { classes, .. } => {
+            classql::dsl::compiler::CompilerResult::Success {
+                classes, notices, ..
+            } => {
                 if !test_case.should_succeed {
                     panic!(
                         "Query test '{}' succeeded but was expected to fail. Got {} results.",
@@ -162,6 +201,31 @@ impl QueryTestHelper {
                 }
 
                 println!("Query succeeded. Got {} results.", classes.len());
+                assert!(
+                    compiler.last_timing().is_some(),
+                    "Query test '{}': a successful query should record its timing",
+                    test_case.test_name
+                );
+
+                // every section should come back once, however the conditions combine
+                let mut sections: Vec<(&str, &str, &str)> = classes
+                    .iter()
+                    .map(|class| {
+                        (
+                            class.subject_code.as_str(),
+                            class.course_number.as_str(),
+                            class.section_sequence.as_str(),
+                        )
+                    })
+                    .collect();
+                sections.sort_unstable();
+                sections.dedup();
+                assert_eq!(
+                    sections.len(),
+                    classes.len(),
+                    "Query test '{}': a section was returned more than once",
+                    test_case.test_name
+                );
 
                 // Check count constraints
                 if let Some(expected_count) = test_case.expected_count {
@@ -195,6 +259,14 @@ impl QueryTestHelper {
                     );
                 }
 
+                if let Some(expected_notices) = &test_case.expected_notices {
+                    assert_eq!(
+                        &notices, expected_notices,
+                        "Query test '{}': wrong notices",
+                        test_case.test_name
+                    );
+                }
+
                 // Check expected classes if provided
                 if !test_case.expected_classes.is_empty() {
                     for expected in &test_case.expected_classes {
@@ -234,6 +306,11 @@ impl QueryTestHelper {
                         test_case.test_name, result
                     );
                 } else {
+                    assert!(
+                        compiler.last_timing().is_none(),
+                        "Query test '{}': a failed query should not record a timing",
+                        test_case.test_name
+                    );
                     println!("Query failed as expected: {:?}\n", result);
                 }
             }
@@ -333,3 +410,323 @@ fn test_day_conditions() {
 fn test_email_queries() {
     run_test_file("email_queries.json");
 }
+
+#[test]
+fn test_negation_queries() {
+    run_test_file("negation_queries.json");
+}
+
+#[test]
+fn test_mixed_and_or_queries() {
+    run_test_file("mixed_and_or_queries.json");
+}
+
+#[test]
+fn test_date_queries() {
+    run_test_file("date_queries.json");
+}
+
+#[test]
+fn test_session_queries() {
+    run_test_file("session_queries.json");
+}
+
+#[test]
+fn test_session_queries_recorded_spans() {
+    // the same sessions are found once a sync has recorded the term spans
+    let path = utils::copy_test_db_with_extras("recorded-spans", &[]);
+    let conn = open_connection(&path).expect("copy should open");
+    record_term_spans(&conn).expect("term spans should be recorded");
+    drop(conn);
+
+    let content = utils::load_test_file("query", "session_queries.json");
+    let test_cases: Vec<QueryTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse JSON test file");
+    for test_case in test_cases {
+        let (Some(expected_count), true) = (test_case.expected_count, test_case.should_succeed)
+        else {
+            continue;
+        };
+        let sql = utils::compile(&test_case.input)
+            .unwrap_or_else(|(stage, e)| panic!("{} failed: {}", stage, e));
+        let classes = execute_query(&sql, &path).expect("query should run");
+        assert_eq!(
+            classes.len(),
+            expected_count,
+            "Query test '{}': wrong count with recorded term spans",
+            test_case.test_name
+        );
+    }
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_inferred_conditions() {
+    run_test_file("inferred_conditions.json");
+}
+
+/// Build a QueryTiming from millisecond counts
+///
+/// Parameters:
+/// --- ---
+/// compile -> Compile time in milliseconds
+/// execute -> Execution time in milliseconds
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// QueryTiming -> The timing
+/// --- ---
+///
+fn timing(compile: u64, execute: u64) -> QueryTiming {
+    QueryTiming {
+        compile: Duration::from_millis(compile),
+        execute: Duration::from_millis(execute),
+    }
+}
+
+#[test]
+fn test_slow_query_log() {
+    // 2025-01-06T14:03:09Z, with the query's line breaks flattened
+    assert_eq!(
+        format_entry(
+            1_736_172_189,
+            "prof is Smith\n  and subject is CS",
+            &timing(2, 812),
+            37
+        ),
+        "2025-01-06T14:03:09Z compile_ms=2.000 execute_ms=812.000 results=37 \
+         query=prof is Smith and subject is CS"
+    );
+
+    let log_path = std::env::temp_dir()
+        .join(format!("classql-slow-log-{}", std::process::id()))
+        .join("slow_queries.log");
+    let threshold = Duration::from_millis(500);
+    let fast = record_if_slow(&log_path, threshold, "subject is CS", &timing(1, 20), 5);
+    let slow = record_if_slow(
+        &log_path,
+        threshold,
+        "prof contains a",
+        &timing(100, 400),
+        9,
+    );
+    let again = record_if_slow(&log_path, threshold, "credits > 3", &timing(0, 900), 2);
+    let contents = std::fs::read_to_string(&log_path).unwrap_or_default();
+    if let Some(dir) = log_path.parent() {
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    assert_eq!(fast, Ok(false), "fast queries should not be logged");
+    assert_eq!(slow, Ok(true), "queries at the threshold should be logged");
+    assert_eq!(again, Ok(true));
+    let lines: Vec<&str> = contents.lines().collect();
+    assert_eq!(lines.len(), 2, "entries should be appended:\n{}", contents);
+    assert!(
+        lines[0].ends_with("compile_ms=100.000 execute_ms=400.000 results=9 query=prof contains a")
+    );
+    assert!(lines[1].ends_with("results=2 query=credits > 3"));
+}
+
+#[test]
+fn test_meeting_filters_keep_other_meetings() {
+    let mut compiler = Compiler::new();
+    compiler.set_school_id(Some("_test".to_string()));
+
+    // meeting conditions are checked after grouping, so a section with an afternoon
+    // meeting still lists its morning ones
+    let classes = match compiler.run("start >= 12:00pm") {
+        CompilerResult::Success { classes, .. } => classes,
+        other => panic!("query should succeed: {:?}", other),
+    };
+    assert_eq!(classes.len(), 833);
+    assert!(classes
+        .iter()
+        .all(|class| class.meeting_times.iter().any(|m| m.start_minutes >= 720)));
+    assert!(
+        classes
+            .iter()
+            .any(|class| class.meeting_times.iter().any(|m| m.start_minutes < 720)),
+        "sections should keep their morning meetings"
+    );
+
+    // "type is LAB" still shows the lecture of a lecture + lab section
+    let classes = match compiler.run("meeting type is LAB") {
+        CompilerResult::Success { classes, .. } => classes,
+        other => panic!("query should succeed: {:?}", other),
+    };
+    assert!(classes.iter().any(|class| class
+        .meeting_type
+        .as_deref()
+        .is_some_and(|types| types.contains("LEC"))));
+}
+
+#[test]
+fn test_compiler_trace() {
+    let content = utils::load_test_file("query", "trace.json");
+    let test_cases: Vec<TraceTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse trace JSON test file");
+
+    for test_case in test_cases {
+        println!("Running trace test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+
+        let mut compiler = Compiler::new();
+        compiler.set_school_id(Some("_test".to_string()));
+        let mut events: Vec<TraceEvent> = Vec::new();
+        let result = compiler.run_traced(&test_case.input, &mut |event| events.push(event.clone()));
+
+        // the SQL's length changes with codegen, so it is checked against the result instead
+        if let (Some(TraceEvent::Generated { chars }), CompilerResult::Success { sql, .. }) = (
+            events
+                .iter()
+                .find(|event| matches!(event, TraceEvent::Generated { .. })),
+            &result,
+        ) {
+            assert_eq!(*chars, sql.len(), "Test '{}'", test_case.test_name);
+        }
+        let lines: Vec<String> = events
+            .iter()
+            .map(|event| match event {
+                TraceEvent::Generated { .. } => "codegen produced SQL".to_string(),
+                event => event.to_string(),
+            })
+            .collect();
+        assert_eq!(
+            lines, test_case.expected,
+            "Test '{}': wrong trace",
+            test_case.test_name
+        );
+        // a trace ends in a failure exactly when the run does
+        assert_eq!(
+            matches!(events.last(), Some(TraceEvent::Failed { .. })),
+            !matches!(result, CompilerResult::Success { .. }),
+            "Test '{}': trace disagrees with {:?}",
+            test_case.test_name,
+            result
+        );
+    }
+
+    // tracing doesn't change what a run returns
+    let mut compiler = Compiler::new();
+    compiler.set_school_id(Some("_test".to_string()));
+    let traced = compiler.run_traced("sub is CMPT", &mut |_| {});
+    let plain = compiler.run("sub is CMPT");
+    match (traced, plain) {
+        (
+            CompilerResult::Success {
+                sql: traced_sql,
+                classes: traced_classes,
+                ..
+            },
+            CompilerResult::Success { sql, classes, .. },
+        ) => {
+            assert_eq!(traced_sql, sql);
+            assert_eq!(traced_classes.len(), classes.len());
+        }
+        other => panic!("both runs should succeed: {:?}", other),
+    }
+}
+
+#[test]
+fn test_result_order() {
+    let mut compiler = Compiler::new();
+    compiler.set_school_id(Some("_test".to_string()));
+    let mut run = |order: ResultOrder| {
+        compiler.set_result_order(order);
+        match compiler.run("sub is CMPT") {
+            CompilerResult::Success { sql, classes, .. } => (sql, classes),
+            other => panic!("query should succeed: {:?}", other),
+        }
+    };
+
+    let (sql, unordered) = run(ResultOrder::Database);
+    assert!(!sql.contains("ORDER BY"), "database order adds no clause");
+
+    // each ordering's first key never goes backwards, and no section is lost or repeated
+    let checks: [(ResultOrder, fn(&Class, &Class) -> bool); 4] = [
+        (ResultOrder::Course, |a, b| {
+            (&a.subject_code, &a.course_number, &a.section_sequence)
+                <= (&b.subject_code, &b.course_number, &b.section_sequence)
+        }),
+        (ResultOrder::StartTime, |a, b| {
+            let start = |class: &Class| class.meeting_times.iter().map(|m| m.start_minutes).min();
+            (start(a).is_none(), start(a)) <= (start(b).is_none(), start(b))
+        }),
+        (ResultOrder::OpenSeats, |a, b| {
+            let seats = |class: &Class| {
+                class
+                    .max_enrollment
+                    .map(|max| -(max - class.enrollment.unwrap_or(0)) as i64)
+            };
+            (seats(a).is_none(), seats(a)) <= (seats(b).is_none(), seats(b))
+        }),
+        (ResultOrder::Professor, |a, b| {
+            let name = |class: &Class| class.professor_name.as_deref().map(str::to_ascii_lowercase);
+            (name(a).is_none(), name(a)) <= (name(b).is_none(), name(b))
+        }),
+    ];
+    for (order, in_order) in checks {
+        let (sql, classes) = run(order);
+        // the ordering comes before the result cap, so the cap keeps the first results
+        let tail = order.order_by() + &limit_clause(Some(DEFAULT_MAX_ROWS));
+        assert!(sql.ends_with(&tail), "{:?}: {}", order, sql);
+        assert_eq!(classes.len(), unordered.len(), "{:?}", order);
+        if let Some(pair) = classes
+            .windows(2)
+            .find(|pair| !in_order(&pair[0], &pair[1]))
+        {
+            panic!(
+                "{:?}: {}-{}-{} came before {}-{}-{}",
+                order,
+                pair[0].subject_code,
+                pair[0].course_number,
+                pair[0].section_sequence,
+                pair[1].subject_code,
+                pair[1].course_number,
+                pair[1].section_sequence
+            );
+        }
+    }
+}
+
+#[test]
+fn test_result_cap() {
+    let mut compiler = Compiler::new();
+    compiler.set_school_id(Some("_test".to_string()));
+    let mut run = |max_rows: Option<usize>| {
+        compiler.set_max_rows(max_rows);
+        match compiler.run("sub is CMPT") {
+            CompilerResult::Success {
+                sql,
+                classes,
+                truncated,
+                ..
+            } => (sql, classes, truncated),
+            other => panic!("query should succeed: {:?}", other),
+        }
+    };
+
+    let (sql, all, truncated) = run(None);
+    assert!(!sql.contains("LIMIT"), "no cap adds no clause: {}", sql);
+    assert!(!truncated, "no cap never truncates");
+    assert!(all.len() > 10, "the test needs more than 10 results");
+
+    // the cap fetches one extra row only to tell that results were cut off
+    let (sql, capped, truncated) = run(Some(10));
+    assert!(sql.ends_with(" LIMIT 11"), "{}", sql);
+    assert!(truncated, "10 of {} results should be truncated", all.len());
+    assert_eq!(capped.len(), 10);
+    for (capped, first) in capped.iter().zip(&all) {
+        assert_eq!(capped.unique_id(), first.unique_id());
+    }
+
+    // a cap the results just fit under keeps them all
+    let (_, exact, truncated) = run(Some(all.len()));
+    assert!(
+        !truncated,
+        "exactly {} results fit under the cap",
+        all.len()
+    );
+    assert_eq!(exact.len(), all.len());
+}
@@ -0,0 +1,3 @@
+// Include the registrar_import_tests module
+#[path = "registrar_import_tests.rs"]
+mod registrar_import_tests;
@@ -0,0 +1,231 @@
+use crate::utils::{self, SectionFixture};
+/// tests/registrar_import/registrar_import_tests.rs
+///
+/// Registrar import tests
+///
+/// Responsible for testing the pasted schedule import in data::registrar_import using
+/// JSON-defined test cases: each row case reads a paste and compares the CRN, course,
+/// section, days, and times of every row; each match case matches a paste against a small
+/// catalog and compares the section, confidence, and reason picked for every row
+///
+/// Contains:
+/// --- ---
+/// ExpectedRow -> A row read from a paste in a test case
+/// RowTestCase -> Paste reading test case struct
+/// ExpectedMatch -> A match in a match test case
+/// MatchTestCase -> Paste matching test case struct
+/// --- ---
+///
+use classql::data::registrar_import::{match_pasted_rows, parse_pasted_rows};
+use classql::data::sql::Class;
+use classql::data::term_index::day_mask;
+use classql::utils::time::format_12_hour;
+use serde::{Deserialize, Serialize};
+
+/// A row read from a paste in a test case
+///
+/// Fields:
+/// --- ---
+/// crn -> Expected CRN, None if the row has none
+/// course -> Expected course, e.g., "CMPT 101", None if the row has none
+/// section -> Expected section, None if the row has none
+/// days -> Expected days as a day string, e.g., "MWF", None if not given
+/// times -> Expected times, e.g., "8:00am-9:15am", None if not given
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for ExpectedRow
+/// Deserialize -> Deserialize trait for ExpectedRow
+/// Serialize -> Serialize trait for ExpectedRow
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct ExpectedRow {
+    crn: Option<String>,
+    course: Option<String>,
+    section: Option<String>,
+    days: Option<String>,
+    times: Option<String>,
+}
+
+/// Paste reading test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// paste -> The pasted text
+/// expected -> The rows expected, in order
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for RowTestCase
+/// Deserialize -> Deserialize trait for RowTestCase
+/// Serialize -> Serialize trait for RowTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct RowTestCase {
+    test_name: String,
+    description: String,
+    paste: String,
+    expected: Vec<ExpectedRow>,
+}
+
+/// A match in a match test case
+///
+/// Fields:
+/// --- ---
+/// section -> Expected section, e.g., "CMPT:101-001", None if none is picked
+/// confidence -> Expected confidence
+/// reason -> Expected reason
+/// confident -> Whether the match should be added without a second look
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for ExpectedMatch
+/// Deserialize -> Deserialize trait for ExpectedMatch
+/// Serialize -> Serialize trait for ExpectedMatch
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct ExpectedMatch {
+    section: Option<String>,
+    confidence: u8,
+    reason: String,
+    confident: bool,
+}
+
+/// Paste matching test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// catalog -> The sections of the term
+/// paste -> The pasted text
+/// expected -> The matches expected, one per row
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for MatchTestCase
+/// Deserialize -> Deserialize trait for MatchTestCase
+/// Serialize -> Serialize trait for MatchTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct MatchTestCase {
+    test_name: String,
+    description: String,
+    catalog: Vec<SectionFixture>,
+    paste: String,
+    expected: Vec<ExpectedMatch>,
+}
+
+#[test]
+fn test_registrar_import_rows() {
+    let content = utils::load_test_file("registrar_import", "rows.json");
+    let test_cases: Vec<RowTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse rows JSON test file");
+
+    for test_case in test_cases {
+        println!("Running row test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+
+        let rows = parse_pasted_rows(&test_case.paste);
+        assert_eq!(
+            rows.len(),
+            test_case.expected.len(),
+            "Test '{}': wrong number of rows in {:?}",
+            test_case.test_name,
+            rows
+        );
+        for (row, expected) in rows.iter().zip(&test_case.expected) {
+            let course = row
+                .subject
+                .as_ref()
+                .zip(row.number.as_ref())
+                .map(|(subject, number)| format!("{} {}", subject, number));
+            let times = row
+                .times
+                .map(|(start, end)| format!("{}-{}", format_12_hour(start), format_12_hour(end)));
+            let days = expected.days.as_deref().and_then(day_mask);
+            assert_eq!(
+                row.crn, expected.crn,
+                "Test '{}': wrong CRN",
+                test_case.test_name
+            );
+            assert_eq!(
+                course, expected.course,
+                "Test '{}': wrong course",
+                test_case.test_name
+            );
+            assert_eq!(
+                row.section, expected.section,
+                "Test '{}': wrong section",
+                test_case.test_name
+            );
+            assert_eq!(row.days, days, "Test '{}': wrong days", test_case.test_name);
+            assert_eq!(
+                times, expected.times,
+                "Test '{}': wrong times",
+                test_case.test_name
+            );
+        }
+    }
+}
+
+#[test]
+fn test_registrar_import_matches() {
+    let content = utils::load_test_file("registrar_import", "matches.json");
+    let test_cases: Vec<MatchTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse matches JSON test file");
+
+    for test_case in test_cases {
+        println!("Running match test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+
+        let catalog: Vec<Class> = test_case.catalog.iter().map(utils::to_class).collect();
+        let matches = match_pasted_rows(parse_pasted_rows(&test_case.paste), &catalog);
+        assert_eq!(
+            matches.len(),
+            test_case.expected.len(),
+            "Test '{}': wrong number of matches",
+            test_case.test_name
+        );
+        for (found, expected) in matches.iter().zip(&test_case.expected) {
+            println!(
+                "{}: {}% ({})",
+                found.row.label(),
+                found.confidence,
+                found.reason
+            );
+            assert_eq!(
+                found.class.as_ref().map(Class::unique_id),
+                expected.section,
+                "Test '{}': wrong section",
+                test_case.test_name
+            );
+            assert_eq!(
+                found.confidence, expected.confidence,
+                "Test '{}': wrong confidence",
+                test_case.test_name
+            );
+            assert_eq!(
+                found.reason, expected.reason,
+                "Test '{}': wrong reason",
+                test_case.test_name
+            );
+            assert_eq!(
+                found.is_confident(),
+                expected.confident,
+                "Test '{}': wrong confidence cutoff",
+                test_case.test_name
+            );
+        }
+    }
+}
@@ -0,0 +1,3 @@
+// Include the restrictions_tests module
+#[path = "restrictions_tests.rs"]
+mod restrictions_tests;
@@ -0,0 +1,89 @@
+use crate::utils;
+/// tests/restrictions/restrictions_tests.rs
+///
+/// Registration restriction tests
+///
+/// Responsible for testing registration restrictions using JSON-defined test cases. Each
+/// flag case parses a restrictions object as the sync stores it and checks its labels and
+/// packed form. Query cases run through utils::run_extras_query_file, which writes
+/// restrictions into a copy of the test database and checks which sections a restricted
+/// query returns
+///
+/// Contains:
+/// --- ---
+/// FlagTestCase -> Restrictions object test case struct
+/// --- ---
+///
+use classql::data::sql::{pack_restrictions, parse_restrictions};
+use serde::{Deserialize, Serialize};
+
+/// Restrictions object test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// json -> The restrictions object as synced
+/// expected_restricted -> Whether the section is restricted
+/// expected_labels -> Expected line for each restriction
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for FlagTestCase
+/// Deserialize -> Deserialize trait for FlagTestCase
+/// Serialize -> Serialize trait for FlagTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct FlagTestCase {
+    test_name: String,
+    description: String,
+    json: String,
+    expected_restricted: bool,
+    expected_labels: Vec<String>,
+}
+
+#[test]
+fn test_restriction_flags() {
+    let content = utils::load_test_file("restrictions", "flags.json");
+    let test_cases: Vec<FlagTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse flags JSON test file");
+
+    for test_case in test_cases {
+        println!("Running restriction flag test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+
+        let restrictions = parse_restrictions(&test_case.json);
+        assert_eq!(
+            restrictions.is_restricted(),
+            test_case.expected_restricted,
+            "Test '{}': wrong restricted flag",
+            test_case.test_name
+        );
+        assert_eq!(
+            restrictions.labels(),
+            test_case.expected_labels,
+            "Test '{}': wrong labels",
+            test_case.test_name
+        );
+
+        // packed restrictions read back the same
+        let packed = pack_restrictions(&restrictions);
+        assert_eq!(
+            parse_restrictions(&packed),
+            restrictions,
+            "Test '{}': {} read back differently",
+            test_case.test_name,
+            packed
+        );
+        println!("Labels: {:?}\n", restrictions.labels());
+    }
+}
+
+#[test]
+fn test_restricted_queries() {
+    utils::run_extras_query_file("restrictions", "queries.json", |class| {
+        class.restrictions.is_restricted()
+    });
+}
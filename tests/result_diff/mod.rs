@@ -0,0 +1,3 @@
+// Include the result_diff_tests module
+#[path = "result_diff_tests.rs"]
+mod result_diff_tests;
@@ -0,0 +1,203 @@
+use crate::utils;
+/// tests/result_diff/result_diff_tests.rs
+///
+/// Result diff tests
+///
+/// Responsible for testing the comparison of two runs of the same search in
+/// data::result_diff using JSON-defined test cases: each case lists the sections of the
+/// previous and current run, and the expected new, gone, and seat-changed sections
+///
+/// Contains:
+/// --- ---
+/// DiffTestCase -> Result diff test case struct
+/// DiffTestHelper -> Result diff test helper struct
+///     Methods:
+///     --- ---
+///     new -> Create a new DiffTestHelper
+///     run_test -> Run a result diff test case
+///     --- ---
+/// Helper functions:
+///     --- ---
+///     run_test_file -> Run the test file
+///     --- ---
+/// --- ---
+///
+use classql::data::result_diff::{diff_results, RowChange};
+use classql::data::sql::Class;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Result diff test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// previous -> Sections of the previous run
+/// current -> Sections of this run
+/// expected_added -> Unique IDs expected to be new
+/// expected_removed -> Unique IDs expected to be gone, in order
+/// expected_seat_changes -> Expected [before, after] open seats by unique ID
+/// expected_summary -> Expected one-line summary
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for DiffTestCase
+/// Deserialize -> Deserialize trait for DiffTestCase
+/// Serialize -> Serialize trait for DiffTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct DiffTestCase {
+    test_name: String,
+    description: String,
+    previous: Vec<utils::SectionFixture>,
+    current: Vec<utils::SectionFixture>,
+    expected_added: Vec<String>,
+    expected_removed: Vec<String>,
+    expected_seat_changes: HashMap<String, (Option<i32>, Option<i32>)>,
+    expected_summary: String,
+}
+
+/// Result diff test helper struct
+///
+/// Fields:
+/// --- ---
+/// None
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Default -> Default trait for DiffTestHelper
+/// --- ---
+///
+#[derive(Default)]
+struct DiffTestHelper {}
+
+/// Result diff test helper implementation
+///
+/// Methods:
+/// --- ---
+/// new -> Create a new DiffTestHelper
+/// run_test -> Run a result diff test case
+/// --- ---
+///
+impl DiffTestHelper {
+    /// Create a new DiffTestHelper
+    ///
+    /// Parameters:
+    /// --- ---
+    /// None
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// DiffTestHelper -> The new DiffTestHelper
+    /// --- ---
+    ///
+    fn new() -> Self {
+        Self {}
+    }
+
+    /// Run a result diff test case
+    ///
+    /// Parameters:
+    /// --- ---
+    /// self -> The DiffTestHelper instance
+    /// test_case -> The result diff test case to run
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// None
+    /// --- ---
+    ///
+    fn run_test(&mut self, test_case: &DiffTestCase) {
+        println!("Running result diff test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+
+        let previous: Vec<Class> = test_case.previous.iter().map(utils::to_class).collect();
+        let current: Vec<Class> = test_case.current.iter().map(utils::to_class).collect();
+        let diff = diff_results(&previous, &current);
+
+        let mut added: Vec<String> = diff.added.iter().cloned().collect();
+        added.sort();
+        assert_eq!(
+            added, test_case.expected_added,
+            "Test '{}': wrong new sections",
+            test_case.test_name
+        );
+
+        let removed: Vec<String> = diff.removed.iter().map(Class::unique_id).collect();
+        assert_eq!(
+            removed, test_case.expected_removed,
+            "Test '{}': wrong gone sections",
+            test_case.test_name
+        );
+
+        let seat_changes: HashMap<String, (Option<i32>, Option<i32>)> = diff
+            .seat_changes
+            .iter()
+            .map(|(id, change)| (id.clone(), (change.before, change.after)))
+            .collect();
+        assert_eq!(
+            seat_changes, test_case.expected_seat_changes,
+            "Test '{}': wrong seat changes",
+            test_case.test_name
+        );
+
+        // every current section is marked the way the lists say
+        for class in &current {
+            let id = class.unique_id();
+            let expected = if test_case.expected_added.contains(&id) {
+                Some(RowChange::Added)
+            } else {
+                diff.seat_changes.get(&id).copied().map(RowChange::Seats)
+            };
+            assert_eq!(
+                diff.change_for(class),
+                expected,
+                "Test '{}': wrong change for {}",
+                test_case.test_name,
+                id
+            );
+        }
+
+        assert_eq!(
+            diff.summary(),
+            test_case.expected_summary,
+            "Test '{}': wrong summary",
+            test_case.test_name
+        );
+        println!("Summary: {}\n", diff.summary());
+    }
+}
+
+/// Run the result diff test file
+///
+/// Parameters:
+/// --- ---
+/// filename -> The filename to run
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// None
+/// --- ---
+///
+fn run_test_file(filename: &str) {
+    let mut helper = DiffTestHelper::new();
+    let content = utils::load_test_file("result_diff", filename);
+    let test_cases: Vec<DiffTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse result diff JSON test file");
+
+    for test_case in test_cases {
+        helper.run_test(&test_case);
+    }
+}
+
+#[test]
+fn test_result_diffs() {
+    run_test_file("diffs.json");
+}
@@ -0,0 +1,3 @@
+// Include the sample_tests module
+#[path = "sample_tests.rs"]
+mod sample_tests;
@@ -0,0 +1,391 @@
+use crate::utils;
+/// tests/sample/sample_tests.rs
+///
+/// Sample catalog tests
+///
+/// Responsible for testing the sample catalog generator in data::sample using JSON-defined
+/// test cases, plus checks that the generated rows look like synced data and that the same
+/// seed always gives the same catalog
+///
+/// Contains:
+/// --- ---
+/// SampleTestCase -> Sample catalog test case struct
+/// SampleTestHelper -> Sample catalog test helper struct
+///     Methods:
+///     --- ---
+///     new -> Create a new SampleTestHelper
+///     run_test -> Run a sample catalog test case
+///     --- ---
+/// Helper functions:
+///     --- ---
+///     temp_db -> Get a temporary database path unique to a test
+///     generate -> Generate a catalog into a temporary database
+///     dump -> Read every generated row back as text
+///     run_test_file -> Run the test file
+///     --- ---
+/// --- ---
+///
+use classql::data::pool::open_connection;
+use classql::data::sample::{generate_catalog, SampleConfig, SampleSummary, SAMPLE_SCHOOL_ID};
+use classql::data::sql::execute_query;
+use classql::dsl::codegen::{generate_sql_with_filters, ResultOrder};
+use classql::dsl::lexer::Lexer;
+use classql::dsl::parser::Parser;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+
+/// Sample catalog test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// config -> The subjects, sections, and seed to generate with
+/// expected -> The expected row counts, without meetings (optional)
+/// error -> The expected error message (optional)
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for SampleTestCase
+/// Deserialize -> Deserialize trait for SampleTestCase
+/// Serialize -> Serialize trait for SampleTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct SampleTestCase {
+    test_name: String,
+    description: String,
+    config: Value,
+    #[serde(default)]
+    expected: Option<Value>,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+/// Sample catalog test helper struct
+///
+/// Fields:
+/// --- ---
+/// None
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Default -> Default trait for SampleTestHelper
+/// --- ---
+///
+#[derive(Default)]
+struct SampleTestHelper {}
+
+/// Sample catalog test helper implementation
+///
+/// Methods:
+/// --- ---
+/// new -> Create a new SampleTestHelper
+/// run_test -> Run a sample catalog test case
+/// --- ---
+///
+impl SampleTestHelper {
+    /// Create a new SampleTestHelper
+    ///
+    /// Parameters:
+    /// --- ---
+    /// None
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// SampleTestHelper -> The new SampleTestHelper
+    /// --- ---
+    ///
+    fn new() -> Self {
+        Self {}
+    }
+
+    /// Run a sample catalog test case
+    ///
+    /// Parameters:
+    /// --- ---
+    /// self -> The SampleTestHelper instance
+    /// test_case -> The sample catalog test case to run
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// None
+    /// --- ---
+    ///
+    fn run_test(&mut self, test_case: &SampleTestCase) {
+        println!("Running sample test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+        println!("Config: {}", test_case.config);
+
+        let number = |key: &str| test_case.config[key].as_u64().unwrap_or_default();
+        let config = SampleConfig {
+            subjects: number("subjects") as usize,
+            sections: number("sections") as usize,
+            seed: number("seed"),
+            ..SampleConfig::default()
+        };
+        let db_path = temp_db(&test_case.test_name);
+        let result = generate_catalog(&db_path, &config);
+        let _ = std::fs::remove_file(&db_path);
+
+        match (result, &test_case.error) {
+            (Ok(summary), None) => {
+                let actual = json!({
+                    "subjects": summary.subjects,
+                    "courses": summary.courses,
+                    "professors": summary.professors,
+                    "sections": summary.sections,
+                });
+                assert_eq!(
+                    Some(&actual),
+                    test_case.expected.as_ref(),
+                    "Test '{}' generated {}",
+                    test_case.test_name,
+                    actual
+                );
+                // every section has a lecture, and some have a lab too
+                assert!(
+                    summary.meetings >= summary.sections,
+                    "Test '{}': {} meetings for {} sections",
+                    test_case.test_name,
+                    summary.meetings,
+                    summary.sections
+                );
+                println!("Generated {}\n", summary.summary());
+            }
+            (Err(e), Some(expected)) => {
+                assert_eq!(
+                    &e, expected,
+                    "Test '{}' failed differently",
+                    test_case.test_name
+                );
+                println!("Failed as expected: {}\n", e);
+            }
+            (Ok(summary), Some(expected)) => panic!(
+                "Test '{}' generated {} but expected error '{}'",
+                test_case.test_name,
+                summary.summary(),
+                expected
+            ),
+            (Err(e), None) => panic!("Test '{}' failed: {}", test_case.test_name, e),
+        }
+    }
+}
+
+/// Get a temporary database path unique to a test
+///
+/// Parameters:
+/// --- ---
+/// name -> Name of the test using the database
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// PathBuf -> The path, in the system temp directory
+/// --- ---
+///
+fn temp_db(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "classql-sample-test-{}-{}.db",
+        name,
+        std::process::id()
+    ))
+}
+
+/// Generate a catalog into a temporary database
+///
+/// Parameters:
+/// --- ---
+/// name -> Name of the test using the database
+/// config -> Catalog settings
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// (PathBuf, SampleSummary) -> The database path and the catalog's row counts
+/// --- ---
+///
+fn generate(name: &str, config: &SampleConfig) -> (PathBuf, SampleSummary) {
+    let db_path = temp_db(name);
+    let summary = generate_catalog(&db_path, config).expect("catalog should generate");
+    (db_path, summary)
+}
+
+/// Read every generated row back as text
+///
+/// Parameters:
+/// --- ---
+/// db_path -> The generated database
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Vec<String> -> One line per row of courses, professors, sections, and meeting_times
+/// --- ---
+///
+fn dump(db_path: &Path) -> Vec<String> {
+    let conn = open_connection(db_path).expect("catalog should open");
+    let mut rows = Vec::new();
+    for table in ["courses", "professors", "sections", "meeting_times"] {
+        let mut stmt = conn
+            .prepare(&format!("SELECT * FROM {} ORDER BY rowid", table))
+            .expect("table should be readable");
+        let columns = stmt.column_count();
+        let table_rows = stmt
+            .query_map([], |row| {
+                let values = (0..columns)
+                    .map(|i| {
+                        row.get::<_, rusqlite::types::Value>(i)
+                            .map(|v| format!("{:?}", v))
+                    })
+                    .collect::<Result<Vec<String>, _>>()?;
+                Ok(format!("{}: {}", table, values.join(" | ")))
+            })
+            .expect("rows should be readable");
+        for row in table_rows {
+            rows.push(row.expect("row should be readable"));
+        }
+    }
+    rows
+}
+
+/// Run the sample test file
+///
+/// Parameters:
+/// --- ---
+/// filename -> The filename to run
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// None
+/// --- ---
+///
+fn run_test_file(filename: &str) {
+    let mut helper = SampleTestHelper::new();
+    let content = utils::load_test_file("sample", filename);
+    let test_cases: Vec<SampleTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse sample JSON test file");
+
+    for test_case in test_cases {
+        helper.run_test(&test_case);
+    }
+}
+
+#[test]
+fn test_sample_catalogs() {
+    run_test_file("catalogs.json");
+}
+
+#[test]
+fn test_sample_same_seed_same_catalog() {
+    let config = SampleConfig {
+        sections: 150,
+        seed: 11,
+        ..SampleConfig::default()
+    };
+    let (first_path, first) = generate("same-seed-first", &config);
+    let (second_path, second) = generate("same-seed-second", &config);
+    let (other_path, _) = generate(
+        "same-seed-other",
+        &SampleConfig {
+            seed: 12,
+            ..config.clone()
+        },
+    );
+
+    let first_rows = dump(&first_path);
+    let second_rows = dump(&second_path);
+    let other_rows = dump(&other_path);
+    for path in [first_path, second_path, other_path] {
+        let _ = std::fs::remove_file(path);
+    }
+
+    assert_eq!(first, second);
+    assert_eq!(
+        first_rows, second_rows,
+        "same seed should give the same rows"
+    );
+    assert_ne!(
+        first_rows, other_rows,
+        "another seed should give other rows"
+    );
+}
+
+#[test]
+fn test_sample_rows_look_synced() {
+    let (db_path, _) = generate("synced", &SampleConfig::default());
+    let conn = open_connection(&db_path).expect("catalog should open");
+
+    // meeting times use the synced "HH:MM:SS" and dates the synced "YYYY-MM-DDT00:00:00"
+    let bad_times: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM meeting_times WHERE start_minutes NOT GLOB \
+             '[0-2][0-9]:[0-5][0-9]:00' OR end_minutes NOT GLOB '[0-2][0-9]:[0-5][0-9]:00' \
+             OR start_minutes >= end_minutes OR start_date NOT LIKE '____-__-__T00:00:00'",
+            [],
+            |row| row.get(0),
+        )
+        .expect("meeting times should be readable");
+    assert_eq!(bad_times, 0, "every meeting should look like a synced one");
+
+    // every upper-level course requires the course one level below it, which exists
+    let broken_prerequisites: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM courses c WHERE c.number NOT LIKE '1%' AND NOT EXISTS \
+             (SELECT 1 FROM courses p WHERE p.school_id = c.school_id \
+             AND c.prerequisites = p.subject_code || ' ' || p.number \
+             AND CAST(p.number AS INTEGER) = CAST(c.number AS INTEGER) - 100)",
+            [],
+            |row| row.get(0),
+        )
+        .expect("courses should be readable");
+    assert_eq!(broken_prerequisites, 0);
+
+    let sections_without_lecture: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM sections s WHERE NOT EXISTS (SELECT 1 FROM meeting_times m \
+             WHERE m.section_sequence = s.sequence AND m.subject_code = s.subject_code \
+             AND m.course_number = s.course_number AND m.meeting_type = 'LEC')",
+            [],
+            |row| row.get(0),
+        )
+        .expect("sections should be readable");
+    assert_eq!(sections_without_lecture, 0);
+    drop(conn);
+
+    // the compiler's SQL finds classes in it the way it would in synced data
+    let input = "subject is CS and start >= 9:00am and monday";
+    let tokens = Lexer::new(input.to_string())
+        .analyze()
+        .expect("query should lex");
+    let ast = Parser::new(input.to_string())
+        .parse(&tokens)
+        .expect("query should parse");
+    let sql = generate_sql_with_filters(
+        &ast,
+        Some(SAMPLE_SCHOOL_ID),
+        None,
+        &[],
+        ResultOrder::Database,
+        None,
+    )
+    .expect("query should generate SQL");
+    let classes = execute_query(&sql, &db_path);
+    let _ = std::fs::remove_file(&db_path);
+
+    let classes = classes.expect("query should run against the sample catalog");
+    assert!(!classes.is_empty(), "query should match sample classes");
+    assert!(classes.iter().all(|class| class.subject_code == "CS"));
+
+    // CRNs are kept in the section's JSON extras and read back as text
+    assert!(classes.iter().all(|class| class
+        .crn
+        .as_deref()
+        .is_some_and(|crn| crn.len() == 5 && crn.chars().all(|c| c.is_ascii_digit()))));
+}
@@ -0,0 +1,3 @@
+// Include the save_tests module
+#[path = "save_tests.rs"]
+mod save_tests;
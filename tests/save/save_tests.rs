@@ -0,0 +1,504 @@
+use crate::utils::{self, SectionFixture};
+/// tests/save/save_tests.rs
+///
+/// Saved schedule tests
+///
+/// Responsible for testing the save file lines in tui::save using JSON-defined test cases:
+/// each line case reads a class line written by any version of the save format, each
+/// change case resolves a saved schedule against the current database, comparing the
+/// classes it shows and the changes it reports, each name case checks a schedule name
+/// against the saved ones, and each regenerate case finds a schedule's generation settings
+/// in the current database. Also tests that snapshots round-trip
+///
+/// Contains:
+/// --- ---
+/// ExpectedLine -> What a class line reads as
+/// LineTestCase -> Save file line test case struct
+/// ChangeTestCase -> Saved schedule change test case struct
+/// NameTestCase -> Schedule name test case struct
+/// RegenerateTestCase -> Generation settings test case struct
+/// Helper functions:
+///     --- ---
+///     describe_meetings -> Describe a class's meetings, e.g., "MTH 8:00am-9:15am"
+///     describe_snapshot -> Describe every snapshot field of a class
+///     --- ---
+/// --- ---
+///
+use classql::data::sql::{pack_meeting_times, parse_final_exam, Class, Meeting, Restrictions};
+use classql::tui::save::{
+    find_name_conflict, parse_saved_class, resolve_generation_settings, resolve_saved_classes,
+    snapshot_line, unique_schedule_name, validate_schedule_name,
+};
+use classql::tui::widgets::schedule::GenerationSettings;
+use classql::utils::time::format_12_hour;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// What a class line reads as
+///
+/// Fields:
+/// --- ---
+/// class_id -> Expected class unique ID
+/// meetings -> Expected meetings at save time, None if the line has none
+/// snapshot -> Expected snapshot (see describe_snapshot), None if the line has none
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for ExpectedLine
+/// Deserialize -> Deserialize trait for ExpectedLine
+/// Serialize -> Serialize trait for ExpectedLine
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct ExpectedLine {
+    class_id: String,
+    meetings: Option<String>,
+    snapshot: Option<String>,
+}
+
+/// Save file line test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// line -> The class line of a save file
+/// expected -> What the line reads as
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for LineTestCase
+/// Deserialize -> Deserialize trait for LineTestCase
+/// Serialize -> Serialize trait for LineTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct LineTestCase {
+    test_name: String,
+    description: String,
+    line: String,
+    expected: ExpectedLine,
+}
+
+/// Saved schedule change test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// old_format -> Whether the save file predates snapshots (class ID and meeting times only)
+/// saved -> Sections as they were when the schedule was saved
+/// live -> Sections in the current database
+/// expected_classes -> Expected classes shown, e.g., "CMPT 101-001 | title | prof | times | 20 enrolled"
+/// expected_issues -> Expected changes reported
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for ChangeTestCase
+/// Deserialize -> Deserialize trait for ChangeTestCase
+/// Serialize -> Serialize trait for ChangeTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct ChangeTestCase {
+    test_name: String,
+    description: String,
+    old_format: bool,
+    saved: Vec<SectionFixture>,
+    live: Vec<SectionFixture>,
+    expected_classes: Vec<String>,
+    expected_issues: Vec<String>,
+}
+
+/// Schedule name test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// input -> The name as typed
+/// saved -> Names of the saved schedules
+/// expected_name -> Expected saved form of the name, None if it should be rejected
+/// expected_error -> Expected error, None if the name should be accepted
+/// expected_conflict -> Index in saved of the schedule with the same name, if any
+/// expected_unique -> Expected name when made unique, None if it should be rejected
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for NameTestCase
+/// Deserialize -> Deserialize trait for NameTestCase
+/// Serialize -> Serialize trait for NameTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct NameTestCase {
+    test_name: String,
+    description: String,
+    input: String,
+    saved: Vec<String>,
+    expected_name: Option<String>,
+    expected_error: Option<String>,
+    expected_conflict: Option<usize>,
+    expected_unique: Option<String>,
+}
+
+/// Generation settings test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// settings -> Settings a saved schedule was generated with
+/// live -> Sections in the current database
+/// expected_classes -> Expected classes found, e.g., "CMPT 101-001 | MTH 8:00am-9:15am"
+/// expected_issues -> Expected classes reported as no longer offered
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for RegenerateTestCase
+/// Deserialize -> Deserialize trait for RegenerateTestCase
+/// Serialize -> Serialize trait for RegenerateTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct RegenerateTestCase {
+    test_name: String,
+    description: String,
+    settings: GenerationSettings,
+    live: Vec<SectionFixture>,
+    expected_classes: Vec<String>,
+    expected_issues: Vec<String>,
+}
+
+/// Describe a class's meetings
+///
+/// Parameters:
+/// --- ---
+/// meetings -> The meetings
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> e.g., "MTH 8:00am-9:15am", "TBA" if there are none
+/// --- ---
+///
+fn describe_meetings(meetings: &[Meeting]) -> String {
+    if meetings.is_empty() {
+        return "TBA".to_string();
+    }
+    meetings
+        .iter()
+        .map(|meeting| {
+            format!(
+                "{} {}-{}",
+                meeting.days_compact(),
+                format_12_hour(meeting.start_minutes),
+                format_12_hour(meeting.end_minutes)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Describe every snapshot field of a class
+///
+/// Parameters:
+/// --- ---
+/// class -> The class
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> Section, title, professor, meetings, credits, CRN, meeting type, method, and
+///           campus separated by " | ", "-" for missing values
+/// --- ---
+///
+fn describe_snapshot(class: &Class) -> String {
+    format!(
+        "{} {}-{} | {} | {} | {} | {} credits | CRN {} | {} | {} | {}",
+        class.subject_code,
+        class.course_number,
+        class.section_sequence,
+        class.title,
+        class.professor_name.as_deref().unwrap_or("TBA"),
+        describe_meetings(&class.meeting_times),
+        class.credit_hours,
+        class.crn.as_deref().unwrap_or("-"),
+        class.meeting_type.as_deref().unwrap_or("-"),
+        class.instruction_method.as_deref().unwrap_or("-"),
+        class.campus.as_deref().unwrap_or("-")
+    )
+}
+
+#[test]
+fn test_save_lines() {
+    let content = utils::load_test_file("save", "lines.json");
+    let test_cases: Vec<LineTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse lines JSON test file");
+
+    for test_case in test_cases {
+        println!("Running line test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+
+        let saved = parse_saved_class(&test_case.line);
+        assert_eq!(
+            saved.class_id, test_case.expected.class_id,
+            "Test '{}': wrong class ID",
+            test_case.test_name
+        );
+        assert_eq!(
+            saved.meeting_times.as_deref().map(describe_meetings),
+            test_case.expected.meetings,
+            "Test '{}': wrong meetings",
+            test_case.test_name
+        );
+        assert_eq!(
+            saved.snapshot.as_ref().map(describe_snapshot),
+            test_case.expected.snapshot,
+            "Test '{}': wrong snapshot",
+            test_case.test_name
+        );
+    }
+}
+
+#[test]
+fn test_save_snapshot_round_trip() {
+    let class = Class {
+        subject_code: "CMPT".to_string(),
+        course_number: "103L".to_string(),
+        section_sequence: "115".to_string(),
+        crn: Some("10518".to_string()),
+        title: "Lab\twith a tab\nand a line break".to_string(),
+        professor_name: Some("Ada Lovelace".to_string()),
+        credit_hours: 1.5,
+        campus: Some("Main Campus".to_string()),
+        final_exam: parse_final_exam("2024-12-16 08:00:00-10:00:00"),
+        session: Some("first half".to_string()),
+        restrictions: Restrictions {
+            instructor_consent: true,
+            majors: vec!["CMPT".to_string()],
+            ..Restrictions::default()
+        },
+        fee: Some(45.5),
+        days: "MTH".to_string(),
+        meeting_times: vec![Meeting {
+            days: vec!["M".to_string(), "TH".to_string()],
+            start_minutes: 750,
+            end_minutes: 825,
+            ..Meeting::default()
+        }],
+        ..Class::default()
+    };
+
+    let line = snapshot_line(&class);
+    assert!(!line.contains('\n'), "a snapshot must stay on one line");
+
+    let saved = parse_saved_class(&line);
+    let snapshot = saved.snapshot.expect("line should have a snapshot");
+    assert_eq!(saved.class_id, "CMPT:103L-115");
+    assert_eq!(snapshot.unique_id(), class.unique_id());
+    assert_eq!(snapshot.title, "Lab with a tab and a line break");
+    assert_eq!(snapshot.meeting_times, class.meeting_times);
+    assert_eq!(snapshot.days, class.days);
+    assert_eq!(snapshot.professor_name, class.professor_name);
+    assert_eq!(snapshot.credit_hours, class.credit_hours);
+    assert_eq!(snapshot.crn, class.crn);
+    assert_eq!(snapshot.campus, class.campus);
+    assert_eq!(snapshot.meeting_type, None);
+    assert!(snapshot.final_exam.is_some());
+    assert_eq!(snapshot.final_exam, class.final_exam);
+    assert_eq!(snapshot.session, class.session);
+    assert_eq!(snapshot.restrictions, class.restrictions);
+    assert_eq!(snapshot.fee, class.fee);
+
+    // lines saved before finals, sessions, restrictions, and fees were recorded still
+    // read, without them
+    let old_line: Vec<&str> = line.split('\t').take(11).collect();
+    let old = parse_saved_class(&old_line.join("\t"))
+        .snapshot
+        .expect("old line has a snapshot");
+    assert_eq!(old.final_exam, None);
+    assert_eq!(old.session, None);
+    assert!(!old.restrictions.is_restricted());
+    assert_eq!(old.fee, None);
+}
+
+#[test]
+fn test_save_changes() {
+    let content = utils::load_test_file("save", "changes.json");
+    let test_cases: Vec<ChangeTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse changes JSON test file");
+
+    for test_case in test_cases {
+        println!("Running change test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+
+        let saved: Vec<_> = test_case
+            .saved
+            .iter()
+            .map(|section| {
+                let class = utils::to_class(section);
+                let line = if test_case.old_format {
+                    format!(
+                        "{}\t{}",
+                        class.unique_id(),
+                        pack_meeting_times(&class.meeting_times)
+                    )
+                } else {
+                    snapshot_line(&class)
+                };
+                parse_saved_class(&line)
+            })
+            .collect();
+        let live: HashMap<String, Class> = test_case
+            .live
+            .iter()
+            .map(|section| {
+                let class = utils::to_class(section);
+                (class.unique_id(), class)
+            })
+            .collect();
+
+        let (classes, issues) = resolve_saved_classes(&saved, live);
+        let shown: Vec<String> = classes
+            .iter()
+            .map(|class| {
+                format!(
+                    "{} {}-{} | {} | {} | {} | {} enrolled",
+                    class.subject_code,
+                    class.course_number,
+                    class.section_sequence,
+                    class.title,
+                    class.professor_name.as_deref().unwrap_or("TBA"),
+                    describe_meetings(&class.meeting_times),
+                    class
+                        .enrollment
+                        .map_or("-".to_string(), |enrollment| enrollment.to_string())
+                )
+            })
+            .collect();
+        assert_eq!(
+            shown, test_case.expected_classes,
+            "Test '{}': wrong classes",
+            test_case.test_name
+        );
+        assert_eq!(
+            issues, test_case.expected_issues,
+            "Test '{}': wrong issues",
+            test_case.test_name
+        );
+    }
+}
+
+#[test]
+fn test_save_names() {
+    let content = utils::load_test_file("save", "names.json");
+    let test_cases: Vec<NameTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse names JSON test file");
+
+    for test_case in test_cases {
+        println!("Running name test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+
+        // saved schedules get timestamps 1, 2, 3, ... in order
+        let names: Vec<(u64, String)> = test_case
+            .saved
+            .iter()
+            .enumerate()
+            .map(|(idx, name)| (idx as u64 + 1, name.clone()))
+            .collect();
+
+        let name = match validate_schedule_name(&test_case.input) {
+            Ok(name) => name,
+            Err(error) => {
+                assert_eq!(
+                    Some(error),
+                    test_case.expected_error,
+                    "Test '{}': wrong error",
+                    test_case.test_name
+                );
+                assert!(test_case.expected_name.is_none() && test_case.expected_unique.is_none());
+                continue;
+            }
+        };
+        assert_eq!(
+            Some(&name),
+            test_case.expected_name.as_ref(),
+            "Test '{}': wrong name",
+            test_case.test_name
+        );
+
+        let conflict = find_name_conflict(&names, &name, None);
+        assert_eq!(
+            conflict,
+            test_case.expected_conflict.map(|idx| idx as u64 + 1),
+            "Test '{}': wrong conflict",
+            test_case.test_name
+        );
+        // a schedule being renamed never conflicts with itself
+        if let Some(timestamp) = conflict {
+            assert_eq!(
+                find_name_conflict(&names, &name, Some(timestamp)),
+                None,
+                "Test '{}': renaming conflicts with itself",
+                test_case.test_name
+            );
+        }
+        assert_eq!(
+            Some(unique_schedule_name(&names, &name)),
+            test_case.expected_unique,
+            "Test '{}': wrong unique name",
+            test_case.test_name
+        );
+    }
+}
+
+#[test]
+fn test_save_regenerate() {
+    let content = utils::load_test_file("save", "regenerate.json");
+    let test_cases: Vec<RegenerateTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse regenerate JSON test file");
+
+    for test_case in test_cases {
+        println!("Running regenerate test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+
+        let live: HashMap<String, Class> = test_case
+            .live
+            .iter()
+            .map(|section| {
+                let class = utils::to_class(section);
+                (class.unique_id(), class)
+            })
+            .collect();
+
+        let (classes, issues) = resolve_generation_settings(&test_case.settings, live);
+        let found: Vec<String> = classes
+            .iter()
+            .map(|class| {
+                format!(
+                    "{} {}-{} | {}",
+                    class.subject_code,
+                    class.course_number,
+                    class.section_sequence,
+                    describe_meetings(&class.meeting_times)
+                )
+            })
+            .collect();
+        assert_eq!(
+            found, test_case.expected_classes,
+            "Test '{}': wrong classes",
+            test_case.test_name
+        );
+        assert_eq!(
+            issues, test_case.expected_issues,
+            "Test '{}': wrong issues",
+            test_case.test_name
+        );
+    }
+}
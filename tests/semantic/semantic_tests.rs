@@ -157,14 +157,22 @@ impl SemanticTestHelper {
                         test_case.test_name, error
                     );
                 } else {
-                    // we currently expect all semantic failures to be InvalidContext.
+                    // failures are InvalidContext, a number outside its field's range, or a
+                    // pattern that doesn't compile
                     match error {
-                        SemanticError::InvalidContext { .. } => {
+                        SemanticError::InvalidContext { .. }
+                        | SemanticError::OutOfRange { .. }
+                        | SemanticError::NeverMatches { .. }
+                        | SemanticError::InvalidPattern { .. } => {
                             println!(
                                 "Semantic analysis failed as expected with error: {:?}",
                                 error
                             );
                         }
+                        // only the compiler's lookup validation reports unknown values
+                        SemanticError::UnknownValue { .. } => {
+                            panic!("Unexpected unknown value error: {:?}", error)
+                        }
                     }
 
                     // basic sanity checks for reported positions: they must be within input bounds.
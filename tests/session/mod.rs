@@ -0,0 +1,3 @@
+// Include the session_tests module
+#[path = "session_tests.rs"]
+mod session_tests;
@@ -0,0 +1,116 @@
+use crate::utils;
+/// tests/session/session_tests.rs
+///
+/// Session restore tests
+///
+/// Responsible for testing the session kept between launches in tui::session using
+/// JSON-defined test cases: each focus case restores a session left on some screen and
+/// compares the screen it starts on. Also tests that sessions round-trip through a file and
+/// that older or partial session files still load
+///
+/// Contains:
+/// --- ---
+/// FocusTestCase -> Restored screen test case struct
+/// --- ---
+///
+use classql::tui::session::Session;
+use classql::tui::state::FocusMode;
+use serde::{Deserialize, Serialize};
+
+/// Restored screen test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// focus -> The screen the session was left on
+/// query -> The query in the search box
+/// expected_focus -> Expected screen to start on
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for FocusTestCase
+/// Deserialize -> Deserialize trait for FocusTestCase
+/// Serialize -> Serialize trait for FocusTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct FocusTestCase {
+    test_name: String,
+    description: String,
+    focus: FocusMode,
+    query: String,
+    expected_focus: FocusMode,
+}
+
+#[test]
+fn test_session_restored_focus() {
+    let content = utils::load_test_file("session", "focus.json");
+    let test_cases: Vec<FocusTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse focus JSON test file");
+
+    for test_case in test_cases {
+        println!("Running focus test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+
+        let session = Session {
+            focus: test_case.focus.clone(),
+            query: test_case.query.clone(),
+            ..Session::default()
+        };
+        assert_eq!(
+            session.restored_focus(),
+            test_case.expected_focus,
+            "Test '{}': wrong screen",
+            test_case.test_name
+        );
+    }
+}
+
+#[test]
+fn test_session_round_trip() {
+    let session = Session {
+        restore: false,
+        focus: FocusMode::ResultsBrowse,
+        query: "subject is CMPT and credits > 3".to_string(),
+        school_id: Some("marist".to_string()),
+        term_id: Some("202540".to_string()),
+        selected_result: 12,
+        results_scroll: 8,
+        settings_index: 6,
+        saved_schedule_index: 2,
+        profile: Some("CS major".to_string()),
+    };
+
+    let path = std::env::temp_dir().join("classql-session-test.json");
+    session.save(&path).expect("session should save");
+    let loaded = Session::load(&path).expect("session should load");
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(loaded, session);
+
+    // no file yet means a fresh session that restores on the next launch
+    let missing = std::env::temp_dir().join("classql-session-missing.json");
+    let _ = std::fs::remove_file(&missing);
+    let fresh = Session::load(&missing).expect("a missing session should load");
+    assert_eq!(fresh, Session::default());
+    assert!(fresh.restore);
+    assert_eq!(fresh.restored_focus(), FocusMode::MainMenu);
+}
+
+#[test]
+fn test_session_partial_file() {
+    // fields added later take their defaults in an older session file
+    let session: Session =
+        serde_json::from_str(r#"{"focus": "Settings", "query": "subject is MATH"}"#)
+            .expect("a partial session should parse");
+    assert!(session.restore);
+    assert_eq!(session.focus, FocusMode::Settings);
+    assert_eq!(session.query, "subject is MATH");
+    assert_eq!(session.school_id, None);
+    assert_eq!(session.profile, None);
+    assert_eq!(session.selected_result, 0);
+
+    // a screen this version doesn't know is an error, so the caller starts fresh
+    assert!(serde_json::from_str::<Session>(r#"{"focus": "Teleporter"}"#).is_err());
+}
@@ -0,0 +1,3 @@
+// Include the share_tests module
+#[path = "share_tests.rs"]
+mod share_tests;
@@ -0,0 +1,308 @@
+use crate::utils::{self, MeetingFixture};
+/// tests/share/share_tests.rs
+///
+/// Schedule sharing tests
+///
+/// Responsible for testing the shareable schedule tokens in tui::share using JSON-defined
+/// test cases: schedules are encoded and decoded back, damaged tokens and other text are
+/// rejected, and tokens pasted inside links or messages are found. Also tests the shared
+/// free blocks highlighted when a friend's schedule is overlaid on the schedule view
+///
+/// Contains:
+/// --- ---
+/// ShareSchedule -> Schedule contents in a test case
+/// RoundTripTestCase -> Encode and decode test case struct
+/// InvalidTestCase -> Rejected token test case struct
+/// PastedTestCase -> Token inside other text test case struct
+/// FreeBlockTestCase -> Shared free block test case struct
+/// Helper functions:
+///     --- ---
+///     to_shared -> Build a SharedSchedule from test contents
+///     --- ---
+/// --- ---
+///
+use classql::tui::save::load_classes_by_id;
+use classql::tui::share::{SharedSchedule, SHARE_PREFIX};
+use classql::tui::widgets::schedule::{shared_free_blocks, MIN_SHARED_FREE_MINUTES};
+use classql::utils::time::{format_12_hour, DAY_CODES};
+use serde::{Deserialize, Serialize};
+
+/// Schedule contents in a test case
+///
+/// Fields:
+/// --- ---
+/// name -> Name of the schedule
+/// school_id -> School ID (optional)
+/// term_id -> Term ID (optional)
+/// class_ids -> Unique IDs of the sections
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for ShareSchedule
+/// Deserialize -> Deserialize trait for ShareSchedule
+/// Serialize -> Serialize trait for ShareSchedule
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct ShareSchedule {
+    name: String,
+    school_id: Option<String>,
+    term_id: Option<String>,
+    class_ids: Vec<String>,
+}
+
+/// Encode and decode test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// schedule -> The schedule to encode
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for RoundTripTestCase
+/// Deserialize -> Deserialize trait for RoundTripTestCase
+/// Serialize -> Serialize trait for RoundTripTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct RoundTripTestCase {
+    test_name: String,
+    description: String,
+    schedule: ShareSchedule,
+}
+
+/// Rejected token test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// input -> The text to decode
+/// expected_error -> Expected start of the error message
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for InvalidTestCase
+/// Deserialize -> Deserialize trait for InvalidTestCase
+/// Serialize -> Serialize trait for InvalidTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct InvalidTestCase {
+    test_name: String,
+    description: String,
+    input: String,
+    expected_error: String,
+}
+
+/// Token inside other text test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// input -> The text containing a token
+/// expected -> Expected decoded schedule
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for PastedTestCase
+/// Deserialize -> Deserialize trait for PastedTestCase
+/// Serialize -> Serialize trait for PastedTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct PastedTestCase {
+    test_name: String,
+    description: String,
+    input: String,
+    expected: ShareSchedule,
+}
+
+/// Shared free block test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// mine -> Meetings of the schedule being viewed
+/// theirs -> Meetings of the overlaid schedule
+/// day -> Day code to check, e.g., "M"
+/// expected -> Expected free blocks, e.g., "10:15am-12:30pm"
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for FreeBlockTestCase
+/// Deserialize -> Deserialize trait for FreeBlockTestCase
+/// Serialize -> Serialize trait for FreeBlockTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct FreeBlockTestCase {
+    test_name: String,
+    description: String,
+    mine: Vec<MeetingFixture>,
+    theirs: Vec<MeetingFixture>,
+    day: String,
+    expected: Vec<String>,
+}
+
+/// Build a SharedSchedule from test contents
+///
+/// Parameters:
+/// --- ---
+/// schedule -> The test contents
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// SharedSchedule -> The same contents
+/// --- ---
+///
+fn to_shared(schedule: &ShareSchedule) -> SharedSchedule {
+    SharedSchedule {
+        name: schedule.name.clone(),
+        school_id: schedule.school_id.clone(),
+        term_id: schedule.term_id.clone(),
+        class_ids: schedule.class_ids.clone(),
+    }
+}
+
+#[test]
+fn test_share_round_trips() {
+    let content = utils::load_test_file("share", "roundtrip.json");
+    let test_cases: Vec<RoundTripTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse roundtrip JSON test file");
+
+    for test_case in test_cases {
+        println!("Running share test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+
+        let shared = to_shared(&test_case.schedule);
+        let token = shared.encode();
+        println!("Token: {}", token);
+        assert!(
+            token.starts_with(SHARE_PREFIX),
+            "Test '{}': token is missing its prefix",
+            test_case.test_name
+        );
+        assert!(
+            token[SHARE_PREFIX.len()..]
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'),
+            "Test '{}': token isn't URL-safe",
+            test_case.test_name
+        );
+        assert_eq!(
+            SharedSchedule::decode(&token),
+            Ok(shared),
+            "Test '{}': decoding gave a different schedule",
+            test_case.test_name
+        );
+    }
+}
+
+#[test]
+fn test_share_invalid_tokens() {
+    let content = utils::load_test_file("share", "invalid.json");
+    let test_cases: Vec<InvalidTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse invalid JSON test file");
+
+    for test_case in test_cases {
+        println!("Running invalid token test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+
+        let error = SharedSchedule::decode(&test_case.input)
+            .expect_err(&format!("Test '{}' should fail", test_case.test_name));
+        assert!(
+            error.starts_with(&test_case.expected_error),
+            "Test '{}': unexpected error '{}'",
+            test_case.test_name,
+            error
+        );
+    }
+}
+
+#[test]
+fn test_share_pasted_tokens() {
+    let content = utils::load_test_file("share", "pasted.json");
+    let test_cases: Vec<PastedTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse pasted JSON test file");
+
+    for test_case in test_cases {
+        println!("Running pasted token test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+
+        assert_eq!(
+            SharedSchedule::decode(&test_case.input),
+            Ok(to_shared(&test_case.expected)),
+            "Test '{}': wrong schedule",
+            test_case.test_name
+        );
+    }
+}
+
+#[test]
+fn test_share_sections_load_from_database() {
+    let shared = SharedSchedule {
+        name: "Demo".to_string(),
+        school_id: Some("_test".to_string()),
+        term_id: None,
+        class_ids: vec![
+            "CMPT:120L-111".to_string(),
+            "ACCT:204N-111".to_string(),
+            "NONE:000-000".to_string(),
+        ],
+    };
+    let decoded = SharedSchedule::decode(&shared.encode()).expect("token should decode");
+    let class_ids: Vec<&str> = decoded.class_ids.iter().map(String::as_str).collect();
+    let found = load_classes_by_id(
+        decoded.school_id.as_deref(),
+        decoded.term_id.as_deref(),
+        &class_ids,
+    )
+    .expect("test database should load");
+
+    assert!(found.contains_key("CMPT:120L-111"));
+    assert!(found.contains_key("ACCT:204N-111"));
+    assert!(!found.contains_key("NONE:000-000"));
+    assert_eq!(found.len(), 2);
+}
+
+#[test]
+fn test_share_free_blocks() {
+    let content = utils::load_test_file("share", "free_blocks.json");
+    let test_cases: Vec<FreeBlockTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse free blocks JSON test file");
+
+    for test_case in test_cases {
+        println!("Running free block test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+
+        let day = DAY_CODES
+            .iter()
+            .position(|&d| d == test_case.day)
+            .expect("day should be a day code");
+        let blocks: Vec<String> = shared_free_blocks(
+            &utils::meeting_classes(&test_case.mine),
+            &utils::meeting_classes(&test_case.theirs),
+            day,
+            MIN_SHARED_FREE_MINUTES,
+        )
+        .iter()
+        .map(|(start, end)| format!("{}-{}", format_12_hour(*start), format_12_hour(*end)))
+        .collect();
+        assert_eq!(
+            blocks, test_case.expected,
+            "Test '{}': wrong free blocks",
+            test_case.test_name
+        );
+    }
+}
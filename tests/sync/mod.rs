@@ -0,0 +1,3 @@
+// Include the sync_tests module
+#[path = "sync_tests.rs"]
+mod sync_tests;
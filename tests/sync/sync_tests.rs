@@ -0,0 +1,234 @@
+use crate::utils;
+/// tests/sync/sync_tests.rs
+///
+/// Sync apply tests
+///
+/// Responsible for testing how `data::sync` writes the rows a sync sent: multi-row
+/// statements per table, rows skipped by their content hash, and rows removed outside a sync
+///
+/// Contains:
+/// --- ---
+/// ExpectedApplied -> Rows a sync is expected to write, skip, and remove
+/// ApplyTestCase -> Sync apply test case struct
+/// Helper functions:
+///     --- ---
+///     apply -> Apply one sync's changes to a database in a transaction
+///     --- ---
+/// --- ---
+///
+use classql::data::pool::{open_connection, write_transaction};
+use classql::data::sql::execute_query;
+use classql::data::sync::{apply_sync_data, diff_databases, AppliedSync};
+use classy_sync::data_stores::sync_requests::ClassDataSync;
+use serde::Deserialize;
+use std::path::Path;
+
+/// Rows a sync is expected to write, skip, and remove
+///
+/// Fields:
+/// --- ---
+/// written -> Rows inserted or updated
+/// skipped -> Rows left alone
+/// removed -> Rows deleted
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for ExpectedApplied
+/// Deserialize -> Deserialize trait for ExpectedApplied
+/// --- ---
+///
+#[derive(Debug, Deserialize)]
+struct ExpectedApplied {
+    written: usize,
+    skipped: usize,
+    removed: usize,
+}
+
+/// Sync apply test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// syncs -> The changes of each sync, applied in order to a copy of the test database
+/// expected_applied -> What each sync is expected to write, skip, and remove
+/// input -> Query run after the syncs (optional)
+/// expected_count -> Expected number of results of the query
+/// expected_error -> Text the last sync's error should contain, if it should fail (optional)
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for ApplyTestCase
+/// Deserialize -> Deserialize trait for ApplyTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize)]
+struct ApplyTestCase {
+    test_name: String,
+    description: String,
+    syncs: Vec<Vec<ClassDataSync>>,
+    expected_applied: Vec<ExpectedApplied>,
+    #[serde(default)]
+    input: Option<String>,
+    #[serde(default)]
+    expected_count: usize,
+    #[serde(default)]
+    expected_error: Option<String>,
+}
+
+/// Apply one sync's changes to a database in a transaction
+///
+/// Parameters:
+/// --- ---
+/// path -> The database
+/// changes -> The sync's changes
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<AppliedSync, String> -> What the sync wrote, skipped, and removed
+/// --- ---
+///
+fn apply(path: &Path, changes: &[ClassDataSync]) -> Result<AppliedSync, String> {
+    write_transaction(path, &mut |_| {}, |tx| apply_sync_data(tx, changes))
+}
+
+#[test]
+fn test_sync_apply() {
+    let content = utils::load_test_file("sync", "apply.json");
+    let test_cases: Vec<ApplyTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse apply JSON test file");
+
+    for test_case in test_cases {
+        println!("Running sync apply test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+
+        let path = utils::copy_test_db_with_extras(&test_case.test_name, &[]);
+        let mut results: Vec<Result<AppliedSync, String>> = test_case
+            .syncs
+            .iter()
+            .map(|changes| apply(&path, changes))
+            .collect();
+
+        if let Some(expected_error) = &test_case.expected_error {
+            let error = results
+                .pop()
+                .and_then(Result::err)
+                .unwrap_or_else(|| panic!("Test '{}': last sync should fail", test_case.test_name));
+            assert!(
+                error.contains(expected_error),
+                "Test '{}': error '{}' should contain '{}'",
+                test_case.test_name,
+                error,
+                expected_error
+            );
+        }
+        for (index, (result, expected)) in
+            results.iter().zip(&test_case.expected_applied).enumerate()
+        {
+            let applied = result.as_ref().unwrap_or_else(|e| {
+                panic!(
+                    "Test '{}': sync {} failed: {}",
+                    test_case.test_name, index, e
+                )
+            });
+            assert_eq!(
+                (applied.written, applied.skipped, applied.removed),
+                (expected.written, expected.skipped, expected.removed),
+                "Test '{}': sync {} wrote, skipped, and removed the wrong rows",
+                test_case.test_name,
+                index
+            );
+        }
+
+        if let Some(input) = &test_case.input {
+            let sql = utils::compile(input).unwrap_or_else(|(stage, e)| {
+                panic!("Test '{}': {} failed: {}", test_case.test_name, stage, e)
+            });
+            let classes = execute_query(&sql, &path)
+                .unwrap_or_else(|e| panic!("Test '{}': query failed: {}", test_case.test_name, e));
+            assert_eq!(
+                classes.len(),
+                test_case.expected_count,
+                "Test '{}': wrong number of results",
+                test_case.test_name
+            );
+        }
+        let _ = std::fs::remove_file(&path);
+    }
+}
+
+#[test]
+fn test_sync_rewrites_purged_rows() {
+    // a row removed outside a sync is written again even though its hash was recorded
+    let content = utils::load_test_file("sync", "apply.json");
+    let test_cases: Vec<ApplyTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse apply JSON test file");
+    let changes = &test_cases[0].syncs[0];
+
+    let path = utils::copy_test_db_with_extras("sync-purged", &[]);
+    apply(&path, changes).expect("first sync should apply");
+    let conn = open_connection(&path).expect("copy should open");
+    conn.execute("DELETE FROM sections WHERE subject_code = 'SYNC'", [])
+        .expect("sections should be removed");
+    drop(conn);
+
+    let applied = apply(&path, changes).expect("second sync should apply");
+    assert_eq!((applied.written, applied.skipped), (2, 1));
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_sync_diff_same_columns() {
+    // only the section whose row changed counts as updated
+    let current = utils::copy_test_db_with_extras("sync-diff-current", &[]);
+    let staged = utils::copy_test_db_with_extras("sync-diff-staged", &[]);
+    let conn = open_connection(&staged).expect("copy should open");
+    conn.execute(
+        "UPDATE sections SET enrollment = COALESCE(enrollment, 0) + 1 \
+         WHERE rowid = (SELECT MIN(rowid) FROM sections)",
+        [],
+    )
+    .expect("section should be updated");
+    drop(conn);
+
+    let diff = diff_databases(&current, &staged).expect("databases should compare");
+    assert_eq!(
+        (
+            diff.new_sections,
+            diff.updated_sections,
+            diff.removed_sections
+        ),
+        (0, 1, 0)
+    );
+    let _ = std::fs::remove_file(&current);
+    let _ = std::fs::remove_file(&staged);
+}
+
+#[test]
+fn test_sync_diff_changed_columns() {
+    // a column added between versions can't be compared, so every section is replaced
+    let current = utils::copy_test_db_with_extras("sync-diff-old-schema", &[]);
+    let staged = utils::copy_test_db_with_extras("sync-diff-new-schema", &[]);
+    let conn = open_connection(&staged).expect("copy should open");
+    conn.execute("ALTER TABLE sections ADD COLUMN waitlist INTEGER", [])
+        .expect("column should be added");
+    let total: usize = conn
+        .query_row("SELECT COUNT(*) FROM sections", [], |row| row.get(0))
+        .expect("sections should be counted");
+    drop(conn);
+
+    let diff = diff_databases(&current, &staged).expect("databases should compare");
+    assert_eq!(
+        (
+            diff.new_sections,
+            diff.updated_sections,
+            diff.removed_sections
+        ),
+        (0, total, 0)
+    );
+    let _ = std::fs::remove_file(&current);
+    let _ = std::fs::remove_file(&staged);
+}
@@ -0,0 +1,3 @@
+// Include the term_index_tests module
+#[path = "term_index_tests.rs"]
+mod term_index_tests;
@@ -0,0 +1,181 @@
+use crate::utils;
+/// tests/term_index/term_index_tests.rs
+///
+/// Term index tests
+///
+/// Responsible for testing the in-memory term index and result filter in data::term_index
+/// using JSON-defined test cases: each filter is matched through the index built from the
+/// test database, and the result is compared with the expected count and with checking
+/// every section one by one
+///
+/// Contains:
+/// --- ---
+/// FilterTestCase -> Result filter test case struct
+/// DayMaskTestCase -> Day string test case struct
+/// Helper functions:
+///     --- ---
+///     load_classes -> Load every section of the test database
+///     --- ---
+/// --- ---
+///
+use classql::data::sql::{execute_query, Class};
+use classql::data::term_index::{day_mask, load_term_index, ClassFilter, TermIndex};
+use classql::dsl::codegen::generate_scope_sql;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::Path;
+
+/// Database the index is built from
+const TEST_DB: &str = "classy/test.db";
+
+/// Result filter test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// filter -> The filter text
+/// expected_count -> Number of sections the filter should match
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for FilterTestCase
+/// Deserialize -> Deserialize trait for FilterTestCase
+/// Serialize -> Serialize trait for FilterTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct FilterTestCase {
+    test_name: String,
+    description: String,
+    filter: String,
+    expected_count: usize,
+}
+
+/// Day string test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// days -> The day string
+/// expected -> Expected bitmask, None if the text isn't a day string
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for DayMaskTestCase
+/// Deserialize -> Deserialize trait for DayMaskTestCase
+/// Serialize -> Serialize trait for DayMaskTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct DayMaskTestCase {
+    test_name: String,
+    description: String,
+    days: String,
+    expected: Option<u8>,
+}
+
+/// Load every section of the test database
+///
+/// Parameters:
+/// --- ---
+/// None
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Vec<Class> -> The sections, as the term index loads them
+/// --- ---
+///
+fn load_classes() -> Vec<Class> {
+    execute_query(
+        &generate_scope_sql(None, None, &[]).expect("scope should generate SQL"),
+        Path::new(TEST_DB),
+    )
+    .expect("test database should load")
+}
+
+#[test]
+fn test_term_index_filters() {
+    let classes = load_classes();
+    let index = load_term_index(
+        Path::new(TEST_DB),
+        &generate_scope_sql(None, None, &[]).expect("scope should generate SQL"),
+    )
+    .expect("test database should index");
+    assert_eq!(index.len(), classes.len());
+
+    let content = utils::load_test_file("term_index", "filters.json");
+    let test_cases: Vec<FilterTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse filters JSON test file");
+
+    for test_case in test_cases {
+        println!("Running filter test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+
+        let filter = ClassFilter::parse(&test_case.filter, &index);
+        let matched = index.matching_ids(&filter);
+        let checked: BTreeSet<String> = classes
+            .iter()
+            .filter(|class| filter.matches(class))
+            .map(Class::unique_id)
+            .collect();
+
+        assert_eq!(
+            matched.len(),
+            test_case.expected_count,
+            "Test '{}' matched the wrong sections with {:?}",
+            test_case.test_name,
+            filter
+        );
+        assert_eq!(
+            matched, checked,
+            "Test '{}': the index and the one-by-one check disagree",
+            test_case.test_name
+        );
+        println!("Matched {} sections\n", matched.len());
+    }
+}
+
+#[test]
+fn test_term_index_day_masks() {
+    let content = utils::load_test_file("term_index", "day_masks.json");
+    let test_cases: Vec<DayMaskTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse day masks JSON test file");
+
+    for test_case in test_cases {
+        println!("Running day mask test: {}", test_case.test_name);
+        assert_eq!(
+            day_mask(&test_case.days),
+            test_case.expected,
+            "Test '{}': {}",
+            test_case.test_name,
+            test_case.description
+        );
+    }
+}
+
+#[test]
+fn test_term_index_lookup_by_id() {
+    let classes = load_classes();
+    let first = classes.first().expect("test database has sections").clone();
+    let index = TermIndex::build(classes);
+
+    let found = index
+        .class(&first.unique_id())
+        .expect("indexed section should be found");
+    assert_eq!(found.unique_id(), first.unique_id());
+    assert!(index.class("NONE:000-00").is_none());
+
+    let missing = std::env::temp_dir().join("classql-term-index-missing.db");
+    let empty = load_term_index(
+        &missing,
+        &generate_scope_sql(None, None, &[]).expect("scope should generate SQL"),
+    )
+    .expect("a missing database gives an empty index");
+    assert!(empty.is_empty());
+    assert!(!missing.exists(), "loading should not create the database");
+}
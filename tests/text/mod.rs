@@ -0,0 +1,3 @@
+// Include the text_tests module
+#[path = "text_tests.rs"]
+mod text_tests;
@@ -0,0 +1,218 @@
+use crate::utils;
+/// tests/text/text_tests.rs
+///
+/// Text utility tests
+///
+/// Responsible for testing the accent- and case-insensitive matching helpers, "matches"
+/// patterns, edit distance, name splitting, and the error caret renderer in utils::text using
+/// JSON-defined test cases, similar to the time tests.
+///
+/// Contains:
+/// --- ---
+/// TextTestCase -> Text test case struct
+/// TextTestHelper -> Text test helper struct
+///     Methods:
+///     --- ---
+///     new -> Create a new TextTestHelper
+///     run_test -> Run a text test case
+///     --- ---
+/// Helper functions:
+///     --- ---
+///     run_test_file -> Run the test file
+///     --- ---
+/// --- ---
+///
+use classql::utils::text::{
+    compile_pattern, edit_distance, fold_text, like_match, render_caret, split_name,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// Text test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// function -> The utils::text function under test
+/// input -> The input passed to the function
+/// expected -> The expected return value
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for TextTestCase
+/// Deserialize -> Deserialize trait for TextTestCase
+/// Serialize -> Serialize trait for TextTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct TextTestCase {
+    test_name: String,
+    description: String,
+    function: String,
+    input: Value,
+    expected: Value,
+}
+
+/// Text test helper struct
+///
+/// Fields:
+/// --- ---
+/// None
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Default -> Default trait for TextTestHelper
+/// --- ---
+///
+#[derive(Default)]
+struct TextTestHelper {}
+
+/// Text test helper implementation
+///
+/// Methods:
+/// --- ---
+/// new -> Create a new TextTestHelper
+/// run_test -> Run a text test case
+/// --- ---
+///
+impl TextTestHelper {
+    /// Create a new TextTestHelper
+    ///
+    /// Parameters:
+    /// --- ---
+    /// None
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// TextTestHelper -> The new TextTestHelper
+    /// --- ---
+    ///
+    fn new() -> Self {
+        Self {}
+    }
+
+    /// Run a text test case
+    ///
+    /// Parameters:
+    /// --- ---
+    /// self -> The TextTestHelper instance
+    /// test_case -> The text test case to run
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// None
+    /// --- ---
+    ///
+    fn run_test(&mut self, test_case: &TextTestCase) {
+        println!("Running text test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+        println!("Function: {}", test_case.function);
+        println!("Input: {}", test_case.input);
+
+        let actual = match test_case.function.as_str() {
+            "fold_text" => json!(fold_text(test_case.input.as_str().unwrap_or_default())),
+            "like_match" => {
+                let pattern = test_case.input["pattern"].as_str().unwrap_or_default();
+                let text = test_case.input["text"].as_str().unwrap_or_default();
+                let escape = test_case.input["escape"]
+                    .as_str()
+                    .and_then(|escape| escape.chars().next());
+                json!(like_match(pattern, text, escape))
+            }
+            // a compiled pattern gives whether the text matches, a refused one its reason
+            "compile_pattern" => {
+                let pattern = test_case.input["pattern"].as_str().unwrap_or_default();
+                let text = test_case.input["text"].as_str().unwrap_or_default();
+                match compile_pattern(pattern) {
+                    Ok(regex) => json!(regex.is_match(text)),
+                    Err(reason) => json!(reason),
+                }
+            }
+            "edit_distance" => {
+                let a = test_case.input["a"].as_str().unwrap_or_default();
+                let b = test_case.input["b"].as_str().unwrap_or_default();
+                json!(edit_distance(a, b))
+            }
+            "split_name" => json!(split_name(test_case.input.as_str().unwrap_or_default())),
+            "render_caret" => {
+                let input = test_case.input["input"].as_str().unwrap_or_default();
+                let positions: Vec<(usize, usize)> =
+                    serde_json::from_value(test_case.input["positions"].clone())
+                        .expect("positions should be a list of [start, end] pairs");
+                let max_width = test_case.input["max_width"]
+                    .as_u64()
+                    .map(|width| width as usize);
+                json!(render_caret(input, &positions, max_width))
+            }
+            other => panic!(
+                "Unknown function '{}' in test '{}'",
+                other, test_case.test_name
+            ),
+        };
+
+        assert_eq!(
+            actual, test_case.expected,
+            "Test '{}' returned {} but expected {}",
+            test_case.test_name, actual, test_case.expected
+        );
+        println!("Returned {} as expected\n", actual);
+    }
+}
+
+/// Run the text test file
+///
+/// Parameters:
+/// --- ---
+/// filename -> The filename to run
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// None
+/// --- ---
+///
+fn run_test_file(filename: &str) {
+    let mut helper = TextTestHelper::new();
+    let content = utils::load_test_file("text", filename);
+    let test_cases: Vec<TextTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse text JSON test file");
+
+    for test_case in test_cases {
+        helper.run_test(&test_case);
+    }
+}
+
+#[test]
+fn test_text_folding() {
+    run_test_file("folding.json");
+}
+
+#[test]
+fn test_text_like_patterns() {
+    run_test_file("like_patterns.json");
+}
+
+#[test]
+fn test_text_patterns() {
+    run_test_file("patterns.json");
+}
+
+#[test]
+fn test_text_edit_distance() {
+    run_test_file("edit_distance.json");
+}
+
+#[test]
+fn test_text_name_splitting() {
+    run_test_file("name_splitting.json");
+}
+
+#[test]
+fn test_text_caret_rendering() {
+    run_test_file("caret_rendering.json");
+}
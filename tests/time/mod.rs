@@ -0,0 +1,3 @@
+// Include the time_tests module
+#[path = "time_tests.rs"]
+mod time_tests;
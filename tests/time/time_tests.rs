@@ -0,0 +1,245 @@
+use crate::utils;
+/// tests/time/time_tests.rs
+///
+/// Time utility tests
+///
+/// Responsible for testing the shared time helpers in utils::time, the date and week
+/// checks meetings build on them, and the part-of-term sessions sections run in, using
+/// JSON-defined test cases, similar to lexer, parser, and codegen tests.
+///
+/// Contains:
+/// --- ---
+/// TimeTestCase -> Time test case struct
+/// TimeTestHelper -> Time test helper struct
+///     Methods:
+///     --- ---
+///     new -> Create a new TimeTestHelper
+///     run_test -> Run a time test case
+///     --- ---
+/// Helper functions:
+///     --- ---
+///     run_test_file -> Run the test file
+///     --- ---
+/// --- ---
+///
+use classql::data::sql::{normalize_session, parse_meeting_times, sessions_overlap};
+use classql::utils::time::{
+    day_order, format_12_hour, format_12_hour_padded, normalize_time, parse_date_phrase,
+    parse_days, parse_time, ranges_overlap, Date, SchoolTimezone,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// Time test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// function -> The utils::time function under test
+/// input -> The input passed to the function
+/// expected -> The expected return value (null for None)
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for TimeTestCase
+/// Deserialize -> Deserialize trait for TimeTestCase
+/// Serialize -> Serialize trait for TimeTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct TimeTestCase {
+    test_name: String,
+    description: String,
+    function: String,
+    input: Value,
+    expected: Value,
+}
+
+/// Time test helper struct
+///
+/// Fields:
+/// --- ---
+/// None
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Default -> Default trait for TimeTestHelper
+/// --- ---
+///
+#[derive(Default)]
+struct TimeTestHelper {}
+
+/// Time test helper implementation
+///
+/// Methods:
+/// --- ---
+/// new -> Create a new TimeTestHelper
+/// run_test -> Run a time test case
+/// --- ---
+///
+impl TimeTestHelper {
+    /// Create a new TimeTestHelper
+    ///
+    /// Parameters:
+    /// --- ---
+    /// None
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// TimeTestHelper -> The new TimeTestHelper
+    /// --- ---
+    ///
+    fn new() -> Self {
+        Self {}
+    }
+
+    /// Run a time test case
+    ///
+    /// Parameters:
+    /// --- ---
+    /// self -> The TimeTestHelper instance
+    /// test_case -> The time test case to run
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// None
+    /// --- ---
+    ///
+    fn run_test(&mut self, test_case: &TimeTestCase) {
+        println!("Running time test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+        println!("Function: {}", test_case.function);
+        println!("Input: {}", test_case.input);
+
+        let text = test_case.input.as_str().unwrap_or_default();
+        let number = test_case.input.as_i64().unwrap_or_default() as i32;
+
+        let actual = match test_case.function.as_str() {
+            "parse_time" => json!(parse_time(text)),
+            "normalize_time" => json!(normalize_time(text)),
+            "format_12_hour" => json!(format_12_hour(number)),
+            "format_12_hour_padded" => json!(format_12_hour_padded(number)),
+            "parse_days" => json!(parse_days(text)),
+            "day_order" => json!(day_order(text)),
+            "ranges_overlap" => {
+                let bounds: Vec<i32> = serde_json::from_value(test_case.input.clone())
+                    .expect("ranges_overlap input must be [start1, end1, start2, end2]");
+                json!(ranges_overlap(bounds[0], bounds[1], bounds[2], bounds[3]))
+            }
+            "parse_date" => json!(Date::parse(text).map(|date| date.to_string())),
+            "day_code" => json!(Date::parse(text).map(|date| date.day_code())),
+            "week_index" => json!(Date::parse(text).map(|date| date.week_index())),
+            "short_label" => json!(Date::parse(text).map(|date| date.short_label())),
+            "parse_date_phrase" => json!(parse_date_phrase(text)),
+            // meetings are given packed, as in the meeting_times column of search results
+            "meetings_overlap" => {
+                let packed: Vec<String> = serde_json::from_value(test_case.input.clone())
+                    .expect("meetings_overlap input must be two packed meetings");
+                let first = &parse_meeting_times(&packed[0])[0];
+                let second = &parse_meeting_times(&packed[1])[0];
+                json!(first.overlaps(second))
+            }
+            "meets_on_date" => {
+                let meeting =
+                    &parse_meeting_times(test_case.input["meeting"].as_str().unwrap_or_default())
+                        [0];
+                let date = Date::parse(test_case.input["date"].as_str().unwrap_or_default())
+                    .expect("meets_on_date input must have a YYYY-MM-DD date");
+                json!(meeting.meets_on_date(date))
+            }
+            "schedule_note" => json!(parse_meeting_times(text)[0].schedule_note()),
+            "normalize_session" => json!(normalize_session(text)),
+            // null stands for a section whose session is unknown
+            "sessions_overlap" => {
+                let sessions: Vec<Option<String>> = serde_json::from_value(test_case.input.clone())
+                    .expect("sessions_overlap input must be two sessions");
+                json!(sessions_overlap(
+                    sessions[0].as_deref(),
+                    sessions[1].as_deref()
+                ))
+            }
+            "utc_offset" | "local_time" => {
+                let zone = test_case.input["zone"].as_str().unwrap_or_default();
+                let utc_seconds = test_case.input["utc_seconds"].as_i64().unwrap_or_default();
+                let timezone = SchoolTimezone::from_name(zone).unwrap_or_else(|| {
+                    panic!("Unsupported zone in test '{}'", test_case.test_name)
+                });
+                if test_case.function == "utc_offset" {
+                    json!(timezone.offset_minutes_at(utc_seconds))
+                } else {
+                    let (date, minutes) = timezone.local_datetime(utc_seconds);
+                    json!(format!("{} {}", date, format_12_hour(minutes)))
+                }
+            }
+            other => panic!(
+                "Unknown function '{}' in test '{}'",
+                other, test_case.test_name
+            ),
+        };
+
+        assert_eq!(
+            actual, test_case.expected,
+            "Test '{}' returned {} but expected {}",
+            test_case.test_name, actual, test_case.expected
+        );
+        println!("Returned {} as expected\n", actual);
+    }
+}
+
+/// Run the time test file
+///
+/// Parameters:
+/// --- ---
+/// filename -> The filename to run
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// None
+/// --- ---
+///
+fn run_test_file(filename: &str) {
+    let mut helper = TimeTestHelper::new();
+    let content = utils::load_test_file("time", filename);
+    let test_cases: Vec<TimeTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse time JSON test file");
+
+    for test_case in test_cases {
+        helper.run_test(&test_case);
+    }
+}
+
+#[test]
+fn test_time_clock_times() {
+    run_test_file("clock_times.json");
+}
+
+#[test]
+fn test_time_days() {
+    run_test_file("days.json");
+}
+
+#[test]
+fn test_time_dates() {
+    run_test_file("dates.json");
+}
+
+#[test]
+fn test_time_overlaps() {
+    run_test_file("overlaps.json");
+}
+
+#[test]
+fn test_time_meetings() {
+    run_test_file("meetings.json");
+}
+
+#[test]
+fn test_time_sessions() {
+    run_test_file("sessions.json");
+}
@@ -0,0 +1,3 @@
+// Include the tui_tests module
+#[path = "tui_tests.rs"]
+mod tui_tests;
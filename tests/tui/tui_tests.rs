@@ -0,0 +1,532 @@
+use crate::utils;
+/// tests/tui_tests.rs
+///
+/// TUI integration tests
+///
+/// Responsible for driving the whole TUI application with synthetic key events and checking
+/// what it draws. The app renders to ratatui's TestBackend instead of a real terminal, so
+/// search, cart, and schedule navigation can be exercised without a person at the keyboard.
+/// Each JSON test case is a list of steps: keys to press and/or text to type or paste, followed by
+/// what the screen, focus, and toast should look like afterwards. A case can start from a
+/// restored session instead of a fresh launch, and with workspace profiles to switch between
+///
+/// Contains:
+/// --- ---
+/// TuiTestCase -> TUI test case struct
+/// TuiTestStep -> A single step of a TUI test case
+/// TuiHarness -> Fake terminal harness around TuiApp
+///     Methods:
+///     --- ---
+///     new -> Create a harness at the main menu with the test database selected
+///     restore -> Start where a session left off and redraw
+///     press -> Press a key and redraw
+///     type_text -> Type text one character at a time
+///     paste -> Paste text in one piece
+///     screen -> Get the drawn frame as text
+///     run_test -> Run a TUI test case
+///     --- ---
+/// Helper functions:
+///     --- ---
+///     parse_key -> Turn a key name (e.g., "Down", "Alt+c") into a KeyEvent
+///     run_test_file -> Run the test file
+///     --- ---
+/// --- ---
+///
+use classql::data::profiles::Profiles;
+use classql::dsl::compiler::Compiler;
+use classql::tui::events::AppEvent;
+use classql::tui::session::Session;
+use classql::tui::state::FocusMode;
+use classql::tui::widgets::{KeyAction, Widget};
+use classql::tui::TuiApp;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::backend::TestBackend;
+use ratatui::layout::{Position, Rect};
+use ratatui::Terminal;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Width of the fake terminal
+const SCREEN_WIDTH: u16 = 120;
+
+/// Height of the fake terminal
+const SCREEN_HEIGHT: u16 = 45;
+
+/// TUI test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// profiles -> Workspace profiles to switch between (optional)
+/// session -> Session to restore before the first step (optional)
+/// steps -> The steps to perform, in order
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for TuiTestCase
+/// Deserialize -> Deserialize trait for TuiTestCase
+/// Serialize -> Serialize trait for TuiTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct TuiTestCase {
+    test_name: String,
+    description: String,
+    #[serde(default)]
+    profiles: Option<Profiles>,
+    #[serde(default)]
+    session: Option<Session>,
+    steps: Vec<TuiTestStep>,
+}
+
+/// A single step of a TUI test case
+///
+/// Text is typed first, then pasted, then the keys are pressed, then the expectations are
+/// checked
+///
+/// Fields:
+/// --- ---
+/// text -> Text to type (optional)
+/// paste -> Text to paste, the way a terminal with bracketed paste sends it (optional)
+/// keys -> Key names to press (see parse_key)
+/// focus -> Expected focus mode after the step, as its Debug name (optional)
+/// screen_contains -> Text that should appear somewhere on screen
+/// screen_lacks -> Text that should NOT appear anywhere on screen
+/// toast_contains -> Text the toast message should contain (optional)
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for TuiTestStep
+/// Deserialize -> Deserialize trait for TuiTestStep
+/// Serialize -> Serialize trait for TuiTestStep
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct TuiTestStep {
+    #[serde(default)]
+    text: Option<String>,
+    #[serde(default)]
+    paste: Option<String>,
+    #[serde(default)]
+    keys: Vec<String>,
+    #[serde(default)]
+    focus: Option<String>,
+    #[serde(default)]
+    screen_contains: Vec<String>,
+    #[serde(default)]
+    screen_lacks: Vec<String>,
+    #[serde(default)]
+    toast_contains: Option<String>,
+}
+
+/// Fake terminal harness around TuiApp
+///
+/// Fields:
+/// --- ---
+/// app -> The application, drawing to a TestBackend
+/// --- ---
+///
+struct TuiHarness {
+    app: TuiApp<TestBackend>,
+}
+
+impl TuiHarness {
+    /// Create a harness at the main menu with the test database selected
+    ///
+    /// Returns:
+    /// --- ---
+    /// TuiHarness -> The new harness, with its first frame drawn
+    /// --- ---
+    ///
+    fn new() -> Self {
+        let mut compiler = Compiler::new();
+        compiler.set_school_id(Some("_test".to_string()));
+        let terminal = Terminal::new(TestBackend::new(SCREEN_WIDTH, SCREEN_HEIGHT))
+            .expect("TestBackend terminal should be created");
+
+        let mut app = TuiApp::with_terminal(compiler, terminal);
+        app.settings.selected_school_id = Some("_test".to_string());
+        // timings vary, so keep slow test machines from writing to the slow query log
+        app.search.slow_query_threshold = Duration::MAX;
+        app.update(AppEvent::Tick);
+        app.draw().expect("first frame should draw");
+        TuiHarness { app }
+    }
+
+    /// Start where a session left off and redraw, the way a launch would
+    ///
+    /// Parameters:
+    /// --- ---
+    /// self -> The TuiHarness instance
+    /// session -> The session to restore
+    /// --- ---
+    ///
+    fn restore(&mut self, session: &Session) {
+        self.app.restore_session(session);
+        self.app.update(AppEvent::Tick);
+        self.app.draw().expect("frame should draw");
+    }
+
+    /// Press a key and redraw, the way one pass of the event loop would
+    ///
+    /// Parameters:
+    /// --- ---
+    /// self -> The TuiHarness instance
+    /// key -> The key event to send
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// bool -> false if the key asked the application to exit
+    /// --- ---
+    ///
+    fn press(&mut self, key: KeyEvent) -> bool {
+        let keep_running = self.app.update(AppEvent::Key(key));
+        self.app.update(AppEvent::Tick);
+        self.app.draw().expect("frame should draw");
+        keep_running
+    }
+
+    /// Type text one character at a time
+    ///
+    /// Parameters:
+    /// --- ---
+    /// self -> The TuiHarness instance
+    /// text -> The text to type
+    /// --- ---
+    ///
+    fn type_text(&mut self, text: &str) {
+        for c in text.chars() {
+            self.press(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+    }
+
+    /// Paste text in one piece and redraw
+    ///
+    /// Parameters:
+    /// --- ---
+    /// self -> The TuiHarness instance
+    /// text -> The text to paste
+    /// --- ---
+    ///
+    fn paste(&mut self, text: &str) {
+        self.app.update(AppEvent::Paste(text.to_string()));
+        self.app.update(AppEvent::Tick);
+        self.app.draw().expect("frame should draw");
+    }
+
+    /// Get the drawn frame as text
+    ///
+    /// Parameters:
+    /// --- ---
+    /// self -> The TuiHarness instance
+    /// --- ---
+    ///
+    /// Returns:
+    /// --- ---
+    /// String -> One line per terminal row, trailing spaces removed
+    /// --- ---
+    ///
+    fn screen(&self) -> String {
+        let buffer = self.app.terminal().backend().buffer();
+        (0..buffer.area.height)
+            .map(|y| {
+                let row: String = (0..buffer.area.width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect();
+                row.trim_end().to_string()
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Run a TUI test case
+    ///
+    /// Parameters:
+    /// --- ---
+    /// self -> The TuiHarness instance
+    /// test_case -> The TUI test case to run
+    /// --- ---
+    ///
+    fn run_test(&mut self, test_case: &TuiTestCase) {
+        println!("Running TUI test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+
+        for (i, step) in test_case.steps.iter().enumerate() {
+            if let Some(text) = &step.text {
+                self.type_text(text);
+            }
+            if let Some(text) = &step.paste {
+                self.paste(text);
+            }
+            for name in &step.keys {
+                let key = parse_key(name).unwrap_or_else(|| {
+                    panic!(
+                        "Test '{}': unknown key name '{}'",
+                        test_case.test_name, name
+                    )
+                });
+                if !self.press(key) {
+                    panic!(
+                        "Test '{}' step {}: '{}' exited the application",
+                        test_case.test_name, i, name
+                    );
+                }
+            }
+
+            let screen = self.screen();
+            let context = format!(
+                "Test '{}' step {} (text {:?}, paste {:?}, keys {:?})",
+                test_case.test_name, i, step.text, step.paste, step.keys
+            );
+
+            if let Some(focus) = &step.focus {
+                let actual = format!("{:?}", self.app.focus_mode());
+                assert_eq!(
+                    &actual, focus,
+                    "{}: wrong focus mode\nScreen:\n{}",
+                    context, screen
+                );
+            }
+            for expected in &step.screen_contains {
+                assert!(
+                    screen.contains(expected.as_str()),
+                    "{}: expected '{}' on screen\nScreen:\n{}",
+                    context,
+                    expected,
+                    screen
+                );
+            }
+            for unexpected in &step.screen_lacks {
+                assert!(
+                    !screen.contains(unexpected.as_str()),
+                    "{}: did not expect '{}' on screen\nScreen:\n{}",
+                    context,
+                    unexpected,
+                    screen
+                );
+            }
+            if let Some(expected) = &step.toast_contains {
+                let toast = self.app.toast_message().unwrap_or_default();
+                assert!(
+                    toast.contains(expected.as_str()),
+                    "{}: expected toast containing '{}', got '{}'",
+                    context,
+                    expected,
+                    toast
+                );
+            }
+        }
+    }
+}
+
+/// Turn a key name into a KeyEvent
+///
+/// Names are a single character ("c"), "Space", or a KeyCode name ("Enter", "Esc", "Tab",
+/// "BackTab", "Backspace", "Up", "Down", "Left", "Right", "PageUp", "PageDown"), optionally
+/// prefixed with "Alt+" or "Ctrl+"
+///
+/// Parameters:
+/// --- ---
+/// name -> The key name
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Option<KeyEvent> -> The key event, or None if the name is not recognized
+/// --- ---
+///
+fn parse_key(name: &str) -> Option<KeyEvent> {
+    let (modifiers, key) = if let Some(key) = name.strip_prefix("Alt+") {
+        (KeyModifiers::ALT, key)
+    } else if let Some(key) = name.strip_prefix("Ctrl+") {
+        (KeyModifiers::CONTROL, key)
+    } else {
+        (KeyModifiers::NONE, name)
+    };
+
+    let mut chars = key.chars();
+    let code = match (chars.next(), chars.next()) {
+        (Some(c), None) => KeyCode::Char(c),
+        _ => match key {
+            "Space" => KeyCode::Char(' '),
+            "Enter" => KeyCode::Enter,
+            "Esc" => KeyCode::Esc,
+            "Tab" => KeyCode::Tab,
+            "BackTab" => KeyCode::BackTab,
+            "Backspace" => KeyCode::Backspace,
+            "Up" => KeyCode::Up,
+            "Down" => KeyCode::Down,
+            "Left" => KeyCode::Left,
+            "Right" => KeyCode::Right,
+            "PageUp" => KeyCode::PageUp,
+            "PageDown" => KeyCode::PageDown,
+            _ => return None,
+        },
+    };
+    Some(KeyEvent::new(code, modifiers))
+}
+
+/// Run the TUI test file
+///
+/// Every test case gets a fresh application
+///
+/// Parameters:
+/// --- ---
+/// filename -> The name of the test file
+/// --- ---
+///
+fn run_test_file(filename: &str) {
+    let content = utils::load_test_file("tui", filename);
+    let test_cases: Vec<TuiTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse TUI JSON test file");
+
+    for test_case in test_cases {
+        let mut harness = TuiHarness::new();
+        if let Some(profiles) = &test_case.profiles {
+            harness.app.set_profiles(profiles.clone());
+        }
+        if let Some(session) = &test_case.session {
+            harness.restore(session);
+        }
+        harness.run_test(&test_case);
+    }
+}
+
+#[test]
+fn test_tui_search_navigation() {
+    run_test_file("search_navigation.json");
+}
+
+#[test]
+fn test_tui_cart_navigation() {
+    run_test_file("cart_navigation.json");
+}
+
+#[test]
+fn test_tui_schedule_navigation() {
+    run_test_file("schedule_navigation.json");
+}
+
+#[test]
+fn test_tui_session_restore() {
+    run_test_file("session_restore.json");
+}
+
+#[test]
+fn test_tui_profiles() {
+    run_test_file("profiles.json");
+}
+
+#[test]
+fn test_tui_catalog() {
+    run_test_file("catalog.json");
+}
+
+#[test]
+fn test_tui_professors() {
+    run_test_file("professors.json");
+}
+
+#[test]
+fn test_tui_completions() {
+    run_test_file("completions.json");
+}
+
+#[test]
+fn test_tui_background_events() {
+    let mut harness = TuiHarness::new();
+
+    // a task's events wait in the queue until the next tick applies them, in order
+    let sender = harness.app.event_sender();
+    std::thread::spawn(move || {
+        sender
+            .send(AppEvent::SyncRetried(
+                "Database is busy, retrying".to_string(),
+            ))
+            .expect("app should be listening");
+        sender
+            .send(AppEvent::Action(KeyAction::Navigate(FocusMode::Settings)))
+            .expect("app should be listening");
+    })
+    .join()
+    .expect("task should finish");
+    assert_eq!(harness.app.focus_mode(), &FocusMode::MainMenu);
+
+    assert!(harness.app.update(AppEvent::Tick));
+    assert_eq!(harness.app.focus_mode(), &FocusMode::Settings);
+    assert_eq!(
+        harness.app.toast_message(),
+        Some("Database is busy, retrying")
+    );
+
+    // a failed sync is reported like one run in the foreground
+    assert!(harness.app.update(AppEvent::SyncFinished(
+        Err("server unreachable".to_string())
+    )));
+    assert_eq!(
+        harness.app.toast_message(),
+        Some("Sync failed (database was busy, retried 1 time(s)): server unreachable")
+    );
+    assert!(!harness.app.settings.is_syncing);
+
+    // an exit sent by a task stops the loop
+    harness
+        .app
+        .event_sender()
+        .send(AppEvent::Action(KeyAction::Exit))
+        .expect("app should be listening");
+    assert!(!harness.app.update(AppEvent::Tick));
+}
+
+#[test]
+fn test_tui_widget_hooks() {
+    let mut harness = TuiHarness::new();
+    let screen = Rect::new(0, 0, SCREEN_WIDTH, SCREEN_HEIGHT);
+
+    // the cursor follows the query typed, after the "> " prefix in the centered search bar
+    harness.press(parse_key("Enter").expect("key should parse"));
+    harness.type_text("subject is CMPT");
+    assert_eq!(
+        harness.app.search.cursor_position(screen),
+        Some(Position { x: 53, y: 14 })
+    );
+
+    // accepting a completion leaves it past the completion and the space after it
+    harness.type_text(" a");
+    harness.press(parse_key("Tab").expect("key should parse"));
+    harness.press(parse_key("Tab").expect("key should parse"));
+    assert_eq!(harness.app.search.input, "subject is CMPT and ");
+    assert_eq!(
+        harness.app.search.cursor_position(screen),
+        Some(Position { x: 58, y: 14 })
+    );
+    for _ in 0.." and ".len() {
+        harness.press(parse_key("Backspace").expect("key should parse"));
+    }
+
+    // browsing the results hides it
+    harness.press(parse_key("Enter").expect("key should parse"));
+    harness.press(parse_key("Down").expect("key should parse"));
+    assert_eq!(harness.app.focus_mode(), &FocusMode::ResultsBrowse);
+    assert_eq!(harness.app.search.cursor_position(screen), None);
+
+    // the second row's middle result stays in view when the grid shrinks to one row
+    harness.press(parse_key("Down").expect("key should parse"));
+    harness.press(parse_key("Right").expect("key should parse"));
+    assert_eq!(harness.app.search.selected_result, 4);
+    assert_eq!(harness.app.search.results_scroll, 0);
+    assert!(harness.app.update(AppEvent::Resize(SCREEN_WIDTH, 30)));
+    assert_eq!(harness.app.search.results_scroll, 3);
+
+    // leaving the search stops typing into the result filter
+    harness.press(parse_key("/").expect("key should parse"));
+    assert!(harness.app.search.editing_filter);
+    assert!(harness
+        .app
+        .update(AppEvent::Action(KeyAction::Navigate(FocusMode::QueryGuide))));
+    assert!(!harness.app.search.editing_filter);
+}
@@ -6,9 +6,40 @@
 /// --- ---
 /// load_test_file -> Generic function to load test JSON files
 /// run_test_file -> Generic function to run test files with a custom processor
+/// check_snapshots -> Compare output against a module's golden snapshot file
+/// parse_snapshots -> Split a snapshot file into named entries
+/// render_snapshots -> Build a snapshot file from named entries
+/// diff_lines -> Line-by-line diff of two texts
+/// copy_test_db_with_extras -> Copy the test database with JSON extras written into sections
+/// ExtrasSection -> A section given JSON extras for a query case
+/// ExtrasQueryTestCase -> Test case for a query over sections given JSON extras
+/// run_extras_query_file -> Run a file of queries over sections given JSON extras
+/// analyze -> Lex, parse, and analyze a query
+/// compile -> Lex, parse, analyze, and generate SQL for a query
+/// MeetingFixture -> A weekly meeting of a test section
+/// SectionFixture -> A section in a test case
+/// to_meeting -> Build a Meeting from a test meeting
+/// to_class -> Build a Class from a test section
+/// meeting_classes -> Build one class per test meeting
 /// --- ---
 ///
+use classql::data::pool::open_connection;
+use classql::data::sql::{execute_query, get_test_db_path, parse_final_exam, Class, Meeting};
+use classql::dsl::codegen::generate_sql;
+use classql::dsl::lexer::Lexer;
+use classql::dsl::parser::{Ast, Parser};
+use classql::dsl::semantic::semantic_analysis;
+use classql::utils::time::{parse_days, parse_time};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
 use std::fs;
+use std::path::PathBuf;
+
+/// Environment variable that rewrites snapshot files from the current output
+pub const UPDATE_SNAPSHOTS_VAR: &str = "CLASSQL_UPDATE_SNAPSHOTS";
+
+/// Prefix of the line that starts each entry in a snapshot file
+const SNAPSHOT_HEADER: &str = "=== ";
 
 /// Load a test file from a module's tests directory
 ///
@@ -27,3 +58,525 @@ pub fn load_test_file(module_name: &str, filename: &str) -> String {
     let path = format!("tests/{}/tests/{}", module_name, filename);
     fs::read_to_string(&path).unwrap_or_else(|_| panic!("Failed to read test file: {}", path))
 }
+
+/// Compare output against a module's golden snapshot file
+///
+/// Snapshots live in tests/<module>/snapshots/<name>.snap, one entry per test case. Run the
+/// tests with CLASSQL_UPDATE_SNAPSHOTS=1 to rewrite the file from the current output, then
+/// review the change in git like any other diff
+///
+/// Parameters:
+/// --- ---
+/// module_name -> The name of the module (e.g., "codegen")
+/// snapshot_name -> The snapshot file's name without extension
+/// actual -> (entry name, output) pairs, in the order they should be written
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// None, panics with a diff of every entry that changed, is missing, or is stale
+/// --- ---
+///
+pub fn check_snapshots(module_name: &str, snapshot_name: &str, actual: &[(String, String)]) {
+    let path = format!("tests/{}/snapshots/{}.snap", module_name, snapshot_name);
+
+    if std::env::var(UPDATE_SNAPSHOTS_VAR).is_ok_and(|value| value == "1") {
+        fs::create_dir_all(format!("tests/{}/snapshots", module_name))
+            .unwrap_or_else(|e| panic!("Failed to create snapshot directory: {}", e));
+        fs::write(&path, render_snapshots(actual))
+            .unwrap_or_else(|e| panic!("Failed to write snapshot file {}: {}", path, e));
+        println!("Updated snapshot file: {}", path);
+        return;
+    }
+
+    let expected = parse_snapshots(&fs::read_to_string(&path).unwrap_or_default());
+    let mut failures = Vec::new();
+
+    for (name, output) in actual {
+        match expected
+            .iter()
+            .find(|(expected_name, _)| expected_name == name)
+        {
+            Some((_, snapshot)) if snapshot == output => {}
+            Some((_, snapshot)) => failures.push(format!(
+                "'{}' changed (- snapshot, + actual):\n{}",
+                name,
+                diff_lines(snapshot, output)
+            )),
+            None => failures.push(format!("'{}' has no snapshot:\n{}", name, output)),
+        }
+    }
+
+    let actual_names: BTreeSet<&str> = actual.iter().map(|(name, _)| name.as_str()).collect();
+    for (name, _) in &expected {
+        if !actual_names.contains(name.as_str()) {
+            failures.push(format!("'{}' has a snapshot but no test case", name));
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "{} snapshot(s) in {} don't match.\nIf the change is intended, rerun with {}=1 and review the diff.\n\n{}",
+        failures.len(),
+        path,
+        UPDATE_SNAPSHOTS_VAR,
+        failures.join("\n\n")
+    );
+}
+
+/// Split a snapshot file into named entries
+///
+/// Parameters:
+/// --- ---
+/// content -> The snapshot file's contents
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Vec<(String, String)> -> (entry name, output) pairs in file order
+/// --- ---
+///
+pub fn parse_snapshots(content: &str) -> Vec<(String, String)> {
+    let mut entries: Vec<(String, Vec<&str>)> = Vec::new();
+    for line in content.lines() {
+        if let Some(name) = line.strip_prefix(SNAPSHOT_HEADER) {
+            entries.push((name.to_string(), Vec::new()));
+        } else if let Some((_, lines)) = entries.last_mut() {
+            lines.push(line);
+        }
+    }
+
+    // entries are separated by a blank line, which isn't part of the output
+    entries
+        .into_iter()
+        .map(|(name, lines)| (name, lines.join("\n").trim_end().to_string()))
+        .collect()
+}
+
+/// Build a snapshot file from named entries
+///
+/// Parameters:
+/// --- ---
+/// entries -> (entry name, output) pairs
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> The snapshot file's contents
+/// --- ---
+///
+pub fn render_snapshots(entries: &[(String, String)]) -> String {
+    entries
+        .iter()
+        .map(|(name, output)| format!("{}{}\n{}\n", SNAPSHOT_HEADER, name, output))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Line-by-line diff of two texts
+///
+/// Parameters:
+/// --- ---
+/// expected -> The old text
+/// actual -> The new text
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// String -> Every line prefixed with "  " (unchanged), "- " (only in expected), or "+ " (only in actual)
+/// --- ---
+///
+pub fn diff_lines(expected: &str, actual: &str) -> String {
+    let old: Vec<&str> = expected.lines().collect();
+    let new: Vec<&str> = actual.lines().collect();
+
+    // longest common subsequence table, filled from the end
+    let mut common = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut lines = Vec::new();
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            lines.push(format!("  {}", old[i]));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || common[i + 1][j] >= common[i][j + 1]) {
+            lines.push(format!("- {}", old[i]));
+            i += 1;
+        } else {
+            lines.push(format!("+ {}", new[j]));
+            j += 1;
+        }
+    }
+    lines.join("\n")
+}
+
+/// Copy the test database with JSON extras written into some of its sections
+///
+/// The test database has no synced extras, so fields the sync keeps there (restrictions,
+/// fees, ...) are tested on a copy. The copy is named after the test so tests running at
+/// the same time never share one
+///
+/// Parameters:
+/// --- ---
+/// copy_name -> Name for the copy, e.g., the test case's name
+/// sections -> (unique ID, e.g., "CMPT:103L-111", object merged into the section's extras)
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// PathBuf -> Path to the copy, for the caller to remove
+/// --- ---
+///
+pub fn copy_test_db_with_extras(
+    copy_name: &str,
+    sections: &[(String, serde_json::Value)],
+) -> PathBuf {
+    let path =
+        std::env::temp_dir().join(format!("classql-{}-{}.db", copy_name, std::process::id()));
+    fs::copy(get_test_db_path(), &path).expect("test database should copy");
+
+    let conn = open_connection(&path).expect("copy should open");
+    for (id, extras) in sections {
+        let (subject, rest) = id.split_once(':').expect("id has a subject");
+        let (course, sequence) = rest.split_once('-').expect("id has a section");
+        let updated = conn
+            .execute(
+                "UPDATE sections SET other = json_patch(COALESCE(other, '{}'), json(?1)) \
+                 WHERE subject_code = ?2 AND course_number = ?3 AND sequence = ?4",
+                [extras.to_string().as_str(), subject, course, sequence],
+            )
+            .expect("extras should be written");
+        assert!(updated > 0, "{} is not in the test database", id);
+    }
+    path
+}
+
+/// A section given JSON extras for a query case
+///
+/// Fields:
+/// --- ---
+/// id -> Unique ID, e.g., "CMPT:103L-111"
+/// extras -> Object merged into the section's extras, e.g., {"fee": 45}
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for ExtrasSection
+/// Deserialize -> Deserialize trait for ExtrasSection
+/// Serialize -> Serialize trait for ExtrasSection
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ExtrasSection {
+    pub id: String,
+    pub extras: serde_json::Value,
+}
+
+/// Test case for a query over sections given JSON extras
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// sections -> Sections given extras before the query runs
+/// input -> The query
+/// expected_count -> Expected number of results
+/// expected_flagged -> Expected unique IDs of the results the module's check flags (e.g.,
+///                     the ones charging a fee)
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for ExtrasQueryTestCase
+/// Deserialize -> Deserialize trait for ExtrasQueryTestCase
+/// Serialize -> Serialize trait for ExtrasQueryTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ExtrasQueryTestCase {
+    pub test_name: String,
+    pub description: String,
+    pub sections: Vec<ExtrasSection>,
+    pub input: String,
+    pub expected_count: usize,
+    #[serde(default)]
+    pub expected_flagged: Vec<String>,
+}
+
+/// Run a file of queries over sections given JSON extras
+///
+/// Each case writes its sections' extras into a copy of the test database, runs the query
+/// on it, and checks how many sections it returns and which of them are flagged
+///
+/// Parameters:
+/// --- ---
+/// module_name -> The name of the module (e.g., "fees")
+/// filename -> The name of the test file to run
+/// flagged -> Whether a result is flagged (e.g., charges a fee)
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// None, panics on the first case that returns the wrong sections
+/// --- ---
+///
+pub fn run_extras_query_file(module_name: &str, filename: &str, flagged: fn(&Class) -> bool) {
+    let content = load_test_file(module_name, filename);
+    let test_cases: Vec<ExtrasQueryTestCase> = serde_json::from_str(&content)
+        .unwrap_or_else(|e| panic!("Failed to parse {} JSON test file: {}", filename, e));
+
+    for test_case in test_cases {
+        println!(
+            "Running {} query test: {}",
+            module_name, test_case.test_name
+        );
+        println!("Description: {}", test_case.description);
+
+        let extras: Vec<(String, serde_json::Value)> = test_case
+            .sections
+            .iter()
+            .map(|section| (section.id.clone(), section.extras.clone()))
+            .collect();
+        let path =
+            copy_test_db_with_extras(&format!("{}-{}", module_name, test_case.test_name), &extras);
+        let sql = compile(&test_case.input).unwrap_or_else(|(stage, e)| {
+            panic!("Test '{}': {} failed: {}", test_case.test_name, stage, e)
+        });
+        let results = execute_query(&sql, &path)
+            .unwrap_or_else(|e| panic!("Test '{}': query failed: {}", test_case.test_name, e));
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(
+            results.len(),
+            test_case.expected_count,
+            "Test '{}': wrong number of results",
+            test_case.test_name
+        );
+        let mut flagged_ids: Vec<String> = results
+            .iter()
+            .filter(|class| flagged(class))
+            .map(|class| class.unique_id())
+            .collect();
+        flagged_ids.sort();
+        assert_eq!(
+            flagged_ids, test_case.expected_flagged,
+            "Test '{}': wrong flagged results",
+            test_case.test_name
+        );
+        println!("Results: {}\n", results.len());
+    }
+}
+
+/// Lex, parse, and analyze a query
+///
+/// Parameters:
+/// --- ---
+/// input -> The query
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<Ast, (&'static str, String)> -> The analyzed AST, or the stage that failed
+///                                        ("lexer", "parser", "semantic") and its error
+/// --- ---
+///
+pub fn analyze(input: &str) -> Result<Ast, (&'static str, String)> {
+    let mut lexer = Lexer::new(input.to_string());
+    let tokens = lexer.analyze().map_err(|e| ("lexer", format!("{:?}", e)))?;
+
+    let mut parser = Parser::new(input.to_string());
+    let ast = parser
+        .parse(&tokens)
+        .map_err(|(error, _remaining)| ("parser", format!("{:?}", error)))?;
+
+    semantic_analysis(&ast).map_err(|(error, _positions)| ("semantic", format!("{:?}", error)))?;
+    Ok(ast)
+}
+
+/// Lex, parse, analyze, and generate SQL for a query
+///
+/// Parameters:
+/// --- ---
+/// input -> The query
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Result<String, (&'static str, String)> -> The generated SQL, or the stage that failed
+///                                           (analyze's stages or "codegen") and its error
+/// --- ---
+///
+pub fn compile(input: &str) -> Result<String, (&'static str, String)> {
+    let ast = analyze(input)?;
+    generate_sql(&ast).map_err(|error| ("codegen", error.to_string()))
+}
+
+/// A weekly meeting of a test section
+///
+/// Fields:
+/// --- ---
+/// days -> Day codes, e.g., "MWF", "TTH"
+/// start -> Start time, e.g., "9:00am", "14:30"
+/// end -> End time, in the same forms as start
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for MeetingFixture
+/// Clone -> Clone trait for MeetingFixture
+/// Default -> Default trait for MeetingFixture
+/// Deserialize -> Deserialize trait for MeetingFixture
+/// Serialize -> Serialize trait for MeetingFixture
+/// --- ---
+///
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct MeetingFixture {
+    pub days: String,
+    pub start: String,
+    pub end: String,
+}
+
+/// A section in a test case
+///
+/// Every field but the ID is optional, so each module's JSON only lists what its tests read
+///
+/// Fields:
+/// --- ---
+/// id -> Unique ID, e.g., "CMPT:101-001"
+/// title -> Course title
+/// professor -> Professor's name
+/// crn -> Registration number
+/// campus -> Campus name
+/// method -> Instruction method
+/// enrollment -> Seats taken
+/// max_enrollment -> Seats offered
+/// final_exam -> Packed final exam, as the query returns it
+/// meetings -> Weekly meetings
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for SectionFixture
+/// Clone -> Clone trait for SectionFixture
+/// Default -> Default trait for SectionFixture
+/// Deserialize -> Deserialize trait for SectionFixture
+/// Serialize -> Serialize trait for SectionFixture
+/// --- ---
+///
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct SectionFixture {
+    pub id: String,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub professor: Option<String>,
+    #[serde(default)]
+    pub crn: Option<String>,
+    #[serde(default)]
+    pub campus: Option<String>,
+    #[serde(default)]
+    pub method: Option<String>,
+    #[serde(default)]
+    pub enrollment: Option<i32>,
+    #[serde(default)]
+    pub max_enrollment: Option<i32>,
+    #[serde(default)]
+    pub final_exam: Option<String>,
+    #[serde(default)]
+    pub meetings: Vec<MeetingFixture>,
+}
+
+/// Build a Meeting from a test meeting
+///
+/// Parameters:
+/// --- ---
+/// meeting -> The test meeting
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Meeting -> A meeting with the parsed days and times
+/// --- ---
+///
+pub fn to_meeting(meeting: &MeetingFixture) -> Meeting {
+    Meeting {
+        days: parse_days(&meeting.days),
+        start_minutes: parse_time(&meeting.start).expect("start time should parse"),
+        end_minutes: parse_time(&meeting.end).expect("end time should parse"),
+        ..Meeting::default()
+    }
+}
+
+/// Build a Class from a test section
+///
+/// Parameters:
+/// --- ---
+/// section -> The test section
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Class -> A class with the section's ID parts, fields, parsed final exam, and meetings
+/// --- ---
+///
+pub fn to_class(section: &SectionFixture) -> Class {
+    let (subject, rest) = section.id.split_once(':').expect("id has a subject");
+    let (course, sequence) = rest.split_once('-').expect("id has a section");
+    let meeting_times: Vec<Meeting> = section.meetings.iter().map(to_meeting).collect();
+    Class {
+        subject_code: subject.to_string(),
+        course_number: course.to_string(),
+        section_sequence: sequence.to_string(),
+        title: section.title.clone(),
+        professor_name: section.professor.clone(),
+        crn: section.crn.clone(),
+        campus: section.campus.clone(),
+        instruction_method: section.method.clone(),
+        enrollment: section.enrollment,
+        max_enrollment: section.max_enrollment,
+        final_exam: section.final_exam.as_deref().and_then(parse_final_exam),
+        days: meeting_times
+            .iter()
+            .map(Meeting::days_compact)
+            .collect::<Vec<_>>()
+            .concat(),
+        meeting_times,
+        ..Class::default()
+    }
+}
+
+/// Build one class per test meeting
+///
+/// Parameters:
+/// --- ---
+/// meetings -> The test meetings
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Vec<Class> -> Classes with distinct sections, each with one meeting
+/// --- ---
+///
+pub fn meeting_classes(meetings: &[MeetingFixture]) -> Vec<Class> {
+    meetings
+        .iter()
+        .enumerate()
+        .map(|(idx, meeting)| Class {
+            subject_code: "TEST".to_string(),
+            course_number: format!("{}", 100 + idx),
+            section_sequence: "001".to_string(),
+            meeting_times: vec![to_meeting(meeting)],
+            ..Class::default()
+        })
+        .collect()
+}
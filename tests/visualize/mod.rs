@@ -0,0 +1,3 @@
+// Include the visualize_tests module
+#[path = "visualize_tests.rs"]
+mod visualize_tests;
@@ -0,0 +1,174 @@
+use crate::utils;
+/// tests/visualize/visualize_tests.rs
+///
+/// AST visualization tests
+///
+/// Responsible for testing debug_utils::visualizetree and debug_utils::tokenstream using
+/// JSON-defined test cases: each output case parses a query and compares the AST printed in
+/// a format line by line, and each token case compares a query's printed token stream,
+/// including tokens the lexer couldn't recognize. Also tests that format names from the
+/// command line parse
+///
+/// Contains:
+/// --- ---
+/// VisualizeTestCase -> Visualization test case struct
+/// TokensTestCase -> Token stream test case struct
+/// Helper functions:
+///     --- ---
+///     parse -> Lex and parse a query
+///     --- ---
+/// --- ---
+///
+use classql::debug_utils::tokenstream::token_lines;
+use classql::debug_utils::visualizetree::{render_ast, TreeFormat};
+use classql::dsl::lexer::Lexer;
+use classql::dsl::parser::{Ast, Parser};
+use serde::{Deserialize, Serialize};
+
+/// Visualization test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// input -> The query to parse
+/// format -> The format to print the AST in ("dot", "mermaid", "tree")
+/// expected -> The expected output, one entry per line
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for VisualizeTestCase
+/// Deserialize -> Deserialize trait for VisualizeTestCase
+/// Serialize -> Serialize trait for VisualizeTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct VisualizeTestCase {
+    test_name: String,
+    description: String,
+    input: String,
+    format: String,
+    expected: Vec<String>,
+}
+
+/// Token stream test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// input -> The query to lex
+/// expected -> The expected token lines
+/// should_lex -> Whether Lexer::analyze accepts the query
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for TokensTestCase
+/// Deserialize -> Deserialize trait for TokensTestCase
+/// Serialize -> Serialize trait for TokensTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct TokensTestCase {
+    test_name: String,
+    description: String,
+    input: String,
+    expected: Vec<String>,
+    should_lex: bool,
+}
+
+/// Lex and parse a query
+///
+/// Parameters:
+/// --- ---
+/// input -> The query
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// Ast -> The query's AST
+/// --- ---
+///
+fn parse(input: &str) -> Ast {
+    let mut lexer = Lexer::new(input.to_string());
+    let tokens = lexer.analyze().expect("query should lex");
+    let mut parser = Parser::new(input.to_string());
+    parser
+        .parse(&tokens)
+        .unwrap_or_else(|(error, _)| panic!("query should parse: {:?}", error))
+}
+
+#[test]
+fn test_visualize_outputs() {
+    let content = utils::load_test_file("visualize", "outputs.json");
+    let test_cases: Vec<VisualizeTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse outputs JSON test file");
+
+    for test_case in test_cases {
+        println!("Running visualize test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+
+        let format: TreeFormat = test_case.format.parse().expect("format should parse");
+        let output = render_ast(&test_case.input, &parse(&test_case.input), format);
+        assert_eq!(
+            output.lines().collect::<Vec<_>>(),
+            test_case.expected,
+            "Test '{}': wrong output",
+            test_case.test_name
+        );
+    }
+}
+
+#[test]
+fn test_visualize_tokens() {
+    let content = utils::load_test_file("visualize", "tokens.json");
+    let test_cases: Vec<TokensTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse tokens JSON test file");
+
+    for test_case in test_cases {
+        println!("Running tokens test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+
+        let mut lexer = Lexer::new(test_case.input.clone());
+        let tokens = lexer.tokenize();
+        assert_eq!(
+            token_lines(&test_case.input, &tokens),
+            test_case.expected,
+            "Test '{}': wrong token lines",
+            test_case.test_name
+        );
+
+        // analyze gives the same tokens when it accepts the query
+        match lexer.analyze() {
+            Ok(analyzed) => {
+                assert!(
+                    test_case.should_lex,
+                    "Test '{}': expected a lexer error",
+                    test_case.test_name
+                );
+                assert_eq!(
+                    token_lines(&test_case.input, &analyzed),
+                    test_case.expected,
+                    "Test '{}': analyze and tokenize differ",
+                    test_case.test_name
+                );
+            }
+            Err(error) => assert!(
+                !test_case.should_lex,
+                "Test '{}': lexer error {:?}",
+                test_case.test_name, error
+            ),
+        }
+    }
+}
+
+#[test]
+fn test_visualize_format_names() {
+    assert_eq!("dot".parse(), Ok(TreeFormat::Dot));
+    assert_eq!("Mermaid".parse(), Ok(TreeFormat::Mermaid));
+    assert_eq!("tree".parse(), Ok(TreeFormat::Tree));
+    let error = "png".parse::<TreeFormat>().expect_err("png isn't a format");
+    assert!(error.contains("png"), "{}", error);
+}
@@ -0,0 +1,3 @@
+// Include the watch_tests module
+#[path = "watch_tests.rs"]
+mod watch_tests;
@@ -0,0 +1,310 @@
+use crate::utils::{self, SectionFixture};
+/// tests/watch/watch_tests.rs
+///
+/// Watch list tests
+///
+/// Responsible for testing the watch-list checks in data::watch, the rate-limited
+/// desktop notifier in tui::notifier, and the hook payloads in data::watch_hook using
+/// JSON-defined test cases: each watch case watches some sections, runs a series of
+/// checks against new data, and compares the event messages of every check; each
+/// notifier case queues messages at set times and compares what would be sent; each
+/// payload case compares the JSON sent to hooks for one event
+///
+/// Contains:
+/// --- ---
+/// WatchTestCase -> Watch list test case struct
+/// NotifyStep -> A step of a notifier test case
+/// NotifyTestCase -> Notifier test case struct
+/// PayloadTestCase -> Hook payload test case struct
+/// Helper functions:
+///     --- ---
+///     single_event -> Watch a section and get the one event of a check
+///     --- ---
+/// --- ---
+///
+use classql::data::sql::Class;
+use classql::data::watch::{WatchEvent, WatchList};
+use classql::data::watch_hook::{event_payload, WatchHook};
+use classql::tui::notifier::{Notifier, NOTIFY_INTERVAL};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::time::{Duration, Instant};
+
+/// Watch list test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// watched -> Sections as they were when they were watched
+/// checks -> The term's sections at each check, in order
+/// expected -> Expected event messages of each check
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for WatchTestCase
+/// Deserialize -> Deserialize trait for WatchTestCase
+/// Serialize -> Serialize trait for WatchTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct WatchTestCase {
+    test_name: String,
+    description: String,
+    watched: Vec<SectionFixture>,
+    checks: Vec<Vec<SectionFixture>>,
+    expected: Vec<Vec<String>>,
+}
+
+/// A step of a notifier test case
+///
+/// Fields:
+/// --- ---
+/// at -> Seconds since the start of the test
+/// push -> Messages queued at that time
+/// expected -> Expected notification body sent at that time, None if nothing is sent
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for NotifyStep
+/// Deserialize -> Deserialize trait for NotifyStep
+/// Serialize -> Serialize trait for NotifyStep
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct NotifyStep {
+    at: u64,
+    push: Vec<String>,
+    expected: Option<String>,
+}
+
+/// Notifier test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// enabled -> Whether notifications are turned on
+/// steps -> The steps, in time order
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for NotifyTestCase
+/// Deserialize -> Deserialize trait for NotifyTestCase
+/// Serialize -> Serialize trait for NotifyTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct NotifyTestCase {
+    test_name: String,
+    description: String,
+    enabled: bool,
+    steps: Vec<NotifyStep>,
+}
+
+/// Hook payload test case struct
+///
+/// Fields:
+/// --- ---
+/// test_name -> The name of the test
+/// description -> The description of the test
+/// watched -> The section as it was when it was watched
+/// current -> The term's sections at the check
+/// expected -> Expected payload of the check's one event
+/// --- ---
+///
+/// Implemented Traits:
+/// --- ---
+/// Debug -> Debug trait for PayloadTestCase
+/// Deserialize -> Deserialize trait for PayloadTestCase
+/// Serialize -> Serialize trait for PayloadTestCase
+/// --- ---
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct PayloadTestCase {
+    test_name: String,
+    description: String,
+    watched: SectionFixture,
+    current: Vec<SectionFixture>,
+    expected: Value,
+}
+
+/// Watch a section and get the one event of a check
+///
+/// Parameters:
+/// --- ---
+/// watched -> The section as it was when it was watched
+/// current -> The term's sections at the check
+/// --- ---
+///
+/// Returns:
+/// --- ---
+/// WatchEvent -> The event the check reported
+/// --- ---
+///
+fn single_event(watched: &SectionFixture, current: &[SectionFixture]) -> WatchEvent {
+    let mut watch_list = WatchList::default();
+    watch_list.toggle(&utils::to_class(watched), Some("_test"), None);
+    let current: Vec<Class> = current.iter().map(utils::to_class).collect();
+    let mut events = watch_list.check(Some("_test"), None, &current);
+    assert_eq!(events.len(), 1, "expected exactly one event");
+    events.remove(0)
+}
+
+#[test]
+fn test_watch_events() {
+    let content = utils::load_test_file("watch", "events.json");
+    let test_cases: Vec<WatchTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse events JSON test file");
+
+    for test_case in test_cases {
+        println!("Running watch test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+
+        let mut watch_list = WatchList::default();
+        for section in &test_case.watched {
+            assert!(watch_list.toggle(&utils::to_class(section), Some("_test"), None));
+        }
+
+        for (check, expected) in test_case.checks.iter().zip(&test_case.expected) {
+            let current: Vec<Class> = check.iter().map(utils::to_class).collect();
+            let messages: Vec<String> = watch_list
+                .check(Some("_test"), None, &current)
+                .iter()
+                .map(WatchEvent::message)
+                .collect();
+            assert_eq!(
+                &messages, expected,
+                "Test '{}': wrong events",
+                test_case.test_name
+            );
+            println!("Events: {:?}", messages);
+        }
+        println!();
+    }
+}
+
+#[test]
+fn test_watch_list_toggle_and_scopes() {
+    let class = utils::to_class(&SectionFixture {
+        id: "CMPT:101-001".to_string(),
+        enrollment: Some(25),
+        max_enrollment: Some(25),
+        ..SectionFixture::default()
+    });
+    let mut watch_list = WatchList::default();
+    assert!(watch_list.toggle(&class, Some("marist"), Some("202540")));
+    assert!(watch_list.contains(&class.unique_id()));
+    assert_eq!(
+        watch_list.scopes(),
+        vec![(Some("marist".to_string()), Some("202540".to_string()))]
+    );
+
+    // a check of another term leaves the section alone
+    assert!(watch_list
+        .check(Some("marist"), Some("202610"), &[])
+        .is_empty());
+    assert!(watch_list.sections[0].offered);
+
+    let path = std::env::temp_dir().join("classql-watch-list-test.json");
+    watch_list.save(&path).expect("watch list should save");
+    let loaded = WatchList::load(&path).expect("watch list should load");
+    assert_eq!(loaded.sections, watch_list.sections);
+    let _ = std::fs::remove_file(&path);
+
+    assert!(!watch_list.toggle(&class, Some("marist"), Some("202540")));
+    assert!(watch_list.sections.is_empty());
+}
+
+#[test]
+fn test_watch_notifications() {
+    let content = utils::load_test_file("watch", "notifications.json");
+    let test_cases: Vec<NotifyTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse notifications JSON test file");
+
+    for test_case in test_cases {
+        println!("Running notifier test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+
+        let mut notifier = Notifier::new(NOTIFY_INTERVAL);
+        notifier.set_enabled(test_case.enabled);
+        let start = Instant::now();
+        for step in &test_case.steps {
+            notifier.push(step.push.iter().cloned());
+            assert_eq!(
+                notifier.take_due(start + Duration::from_secs(step.at)),
+                step.expected,
+                "Test '{}': wrong notification at {}s",
+                test_case.test_name,
+                step.at
+            );
+        }
+    }
+}
+
+#[test]
+fn test_watch_hook_payloads() {
+    let content = utils::load_test_file("watch", "payloads.json");
+    let test_cases: Vec<PayloadTestCase> =
+        serde_json::from_str(&content).expect("Failed to parse payloads JSON test file");
+
+    for test_case in test_cases {
+        println!("Running payload test: {}", test_case.test_name);
+        println!("Description: {}", test_case.description);
+
+        let event = single_event(&test_case.watched, &test_case.current);
+        assert_eq!(
+            event_payload(&event),
+            test_case.expected,
+            "Test '{}': wrong payload",
+            test_case.test_name
+        );
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn test_watch_hook_command() {
+    let opened = SectionFixture {
+        id: "CMPT:101-001".to_string(),
+        enrollment: Some(24),
+        max_enrollment: Some(25),
+        ..SectionFixture::default()
+    };
+    let event = single_event(
+        &SectionFixture {
+            enrollment: Some(25),
+            ..opened.clone()
+        },
+        &[opened],
+    );
+
+    // the command gets the payload on standard input
+    let output = std::env::temp_dir().join("classql-watch-hook-test.json");
+    let hook = WatchHook {
+        command: Some(format!("cat > '{}'", output.display())),
+        webhook_url: None,
+    };
+    assert!(hook.run(std::slice::from_ref(&event)).is_empty());
+    let written = std::fs::read_to_string(&output).expect("command should write the payload");
+    let _ = std::fs::remove_file(&output);
+    let payload: Value = serde_json::from_str(&written).expect("payload should be JSON");
+    assert_eq!(payload, event_payload(&event));
+
+    // a failing command is reported, not panicked on
+    let failing = WatchHook {
+        command: Some("exit 3".to_string()),
+        webhook_url: None,
+    };
+    let failures = failing.run(&[event]);
+    assert_eq!(failures.len(), 1);
+    assert!(
+        failures[0].starts_with("Watch command failed"),
+        "{:?}",
+        failures
+    );
+    assert!(WatchHook::default().is_empty());
+}